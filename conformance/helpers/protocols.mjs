@@ -25,8 +25,8 @@ export const parameters = {
 export const cggmp = {
   // CGGMP requires a prehashed message
   message: "a3e6e406aeb475f43aa762bb752a8f9d57b7fa327a2a53c7ae00b13f8d116b38",
-  dkgSessionIdSeed: Array.from(fromHexString("ee507039fb7b14bf8190f300c66732110b401a68ba8e0d3fa464809972d33489")),
-  signSessionIdSeed: Array.from(fromHexString("289e497ac7c2640adda5bf9bf0e9a05833f1807d1c4dce3f73e3483513bfa25e")),
+  dkgSessionId: { id: "ee507039fb7b14bf8190f300c66732110b401a68ba8e0d3fa464809972d33489" },
+  signSessionId: { id: "289e497ac7c2640adda5bf9bf0e9a05833f1807d1c4dce3f73e3483513bfa25e" },
 };
 
 export const frost = {
@@ -17,7 +17,7 @@ try {
   const meetingId = await room.create(userIds, userIds[0]);
   const el = document.getElementById("meeting-id");
   el.innerHTML = `
-    <p class="meeting-id">${meetingId}</p>`;
+    <p class="meeting-id">${meetingId.id}</p>`;
 
 } catch (e) {
   console.error(e);
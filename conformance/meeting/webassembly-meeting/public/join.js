@@ -26,7 +26,7 @@ const userData = {
 
 const room = new MeetingRoom(serverUrl);
 const participants = await room.join(
-  meetingId, userId, userData);
+  { id: meetingId }, userId, userData);
 
 const el = document.getElementById("participants");
 el.innerHTML = `
@@ -7,7 +7,7 @@ import {
   parameters,
   cggmp,
 } from '../../../helpers/protocols.mjs';
-const { message, dkgSessionIdSeed, signSessionIdSeed } = cggmp;
+const { message, dkgSessionId, signSessionId } = cggmp;
 
 const dkgScript = './tests/dkg.js';
 const signScript = './tests/sign.js';
@@ -23,7 +23,7 @@ for (let i = 0;i < parameters.parties;i++) {
         partyIndex: i,
         server,
         parameters,
-        sessionIdSeed: dkgSessionIdSeed,
+        sessionId: dkgSessionId,
       }
     });
 
@@ -58,7 +58,7 @@ signers.forEach((keyShare, index) => {
         indices,
         server,
         parameters,
-        sessionIdSeed: signSessionIdSeed,
+        sessionId: signSessionId,
         keyShare,
         message,
       }
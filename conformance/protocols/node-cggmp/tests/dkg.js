@@ -5,7 +5,7 @@ import {
 import { createRequire } from 'node:module';
 const require = createRequire(import.meta.url);
 const polysig = require('../build/polysig.node');
-const { partyIndex, server, parameters, sessionIdSeed } = workerData;
+const { partyIndex, server, parameters, sessionId } = workerData;
 const partyKeys = require("./ecdsa.json").slice(0, parameters.parties);
 
 const { CggmpProtocol } = polysig;
@@ -38,7 +38,7 @@ const party = {
 const keyShare = await CggmpProtocol.dkg(
   options,
   party,
-  sessionIdSeed,
+  sessionId,
   signer,
 );
 
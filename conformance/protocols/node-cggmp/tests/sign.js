@@ -5,7 +5,7 @@ import {
 import { createRequire } from 'node:module';
 const require = createRequire(import.meta.url);
 const polysig = require('../build/polysig.node');
-const { partyIndex, server, parameters, sessionIdSeed, keyShare, indices, message } = workerData;
+const { partyIndex, server, parameters, sessionId, keyShare, indices, message } = workerData;
 const partyKeys = require("./ecdsa.json").slice(0, parameters.parties);
 
 const { CggmpProtocol } = polysig;
@@ -38,7 +38,7 @@ const party = {
 const protocol = new CggmpProtocol(options, keyShare);
 const signature = await protocol.sign(
   party,
-  sessionIdSeed,
+  sessionId,
   signer,
   message,
 );
@@ -7,7 +7,7 @@ const { CggmpProtocol } = module;
 
 const params = new URLSearchParams(document.location.search);
 const pageData = JSON.parse(params.get('data'));
-const { partyIndex, server, parameters, sessionIdSeed } = pageData;
+const { partyIndex, server, parameters, sessionId } = pageData;
 const partyKeys = JSON.parse(params.get('keys'));
 
 const publicKey = partyKeys[partyIndex].encrypt.public;
@@ -38,7 +38,7 @@ const party = {
 const keyShare = await CggmpProtocol.dkg(
   options,
   party,
-  sessionIdSeed,
+  sessionId,
   signer,
 );
 
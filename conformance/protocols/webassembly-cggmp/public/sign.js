@@ -7,7 +7,7 @@ const { CggmpProtocol } = module;
 
 const params = new URLSearchParams(document.location.search);
 const pageData = JSON.parse(params.get('data'));
-const { partyIndex, server, parameters, sessionIdSeed, keyShare, indices, message } = pageData;
+const { partyIndex, server, parameters, sessionId, keyShare, indices, message } = pageData;
 const partyKeys = JSON.parse(params.get('keys'));
 
 const publicKey = partyKeys[partyIndex].encrypt.public;
@@ -38,7 +38,7 @@ const party = {
 const protocol = new CggmpProtocol(options, keyShare);
 const signature = await protocol.sign(
   party,
-  sessionIdSeed,
+  sessionId,
   signer,
   message,
 );
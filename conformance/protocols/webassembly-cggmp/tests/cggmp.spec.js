@@ -12,7 +12,7 @@ import {
 import { createRequire } from 'node:module';
 const require = createRequire(import.meta.url);
 const partyKeys = require('./ecdsa.json');
-const { message, dkgSessionIdSeed, signSessionIdSeed } = cggmp;
+const { message, dkgSessionId, signSessionId } = cggmp;
 
 test("CGGMP: dkg and sign message", async ({ context, page }) => {
   // The default timeout is 90 seconds which fails on Firefox
@@ -39,7 +39,7 @@ test("CGGMP: dkg and sign message", async ({ context, page }) => {
       partyIndex: i,
       server,
       parameters,
-      sessionIdSeed: dkgSessionIdSeed,
+      sessionId: dkgSessionId,
     };
     const url = `${URL}/dkg.html?data=${encodeURIComponent(JSON.stringify(pageData))}&keys=${encodeURIComponent(JSON.stringify(partyKeys.slice(0, parameters.parties)))}`;
 
@@ -82,7 +82,7 @@ test("CGGMP: dkg and sign message", async ({ context, page }) => {
       indices,
       server,
       parameters,
-      sessionIdSeed: signSessionIdSeed,
+      sessionId: signSessionId,
       keyShare,
       message,
     };
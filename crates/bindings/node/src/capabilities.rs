@@ -0,0 +1,77 @@
+//! Capabilities compiled into the build.
+use napi_derive::napi;
+
+/// Capabilities of a build of the library.
+#[napi(object)]
+#[derive(Debug)]
+pub struct Capabilities {
+    /// Version of the library.
+    pub version: String,
+    /// Wire protocol encoding versions.
+    pub encoding_versions: Vec<u32>,
+    /// Encodings for peer message payloads.
+    pub payload_encodings: Vec<String>,
+    /// Threshold protocols.
+    pub protocols: Vec<String>,
+    /// Single-party signers.
+    pub signers: Vec<String>,
+    /// Curves and the backends that implement them.
+    pub curves: Vec<CurveBackend>,
+    /// Other optional features.
+    pub features: Vec<String>,
+}
+
+impl From<polysig_driver::Capabilities> for Capabilities {
+    fn from(value: polysig_driver::Capabilities) -> Self {
+        Self {
+            version: value.version,
+            encoding_versions: value
+                .encoding_versions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            payload_encodings: value.payload_encodings,
+            protocols: value.protocols,
+            signers: value.signers,
+            curves: value
+                .curves
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            features: value.features,
+        }
+    }
+}
+
+/// Curve and the library that implements it.
+#[napi(object)]
+#[derive(Debug)]
+pub struct CurveBackend {
+    /// Name of the curve.
+    pub curve: String,
+    /// Name of the crate implementing the curve.
+    pub backend: String,
+}
+
+impl From<polysig_driver::CurveBackend> for CurveBackend {
+    fn from(value: polysig_driver::CurveBackend) -> Self {
+        Self {
+            curve: value.curve,
+            backend: value.backend,
+        }
+    }
+}
+
+/// Capabilities compiled into this build.
+///
+/// Exchange the capabilities with the other participants
+/// to check they run compatible builds before starting
+/// a ceremony.
+#[napi]
+pub fn capabilities() -> Capabilities {
+    #[cfg(feature = "protocol")]
+    let capabilities = polysig_client::capabilities();
+    #[cfg(not(feature = "protocol"))]
+    let capabilities = polysig_driver::capabilities();
+    capabilities.into()
+}
@@ -2,6 +2,9 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+/// Capabilities compiled into the build.
+pub mod capabilities;
+
 /// Threshold signature protocols.
 #[cfg(any(feature = "cggmp", feature = "frost"))]
 pub mod protocols;
@@ -0,0 +1,84 @@
+//! Cancel ceremonies using an abort handle.
+use napi_derive::napi;
+use polysig_client::{CancellationToken, SessionOptions};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+/// Tokens for abort handles keyed by handle identifier.
+///
+/// Class instances cannot be passed to async functions so
+/// ceremonies receive the identifier of the handle instead.
+fn handles() -> &'static Mutex<HashMap<u32, CancellationToken>> {
+    static HANDLES: OnceLock<Mutex<HashMap<u32, CancellationToken>>> =
+        OnceLock::new();
+    HANDLES.get_or_init(Default::default)
+}
+
+/// Signal for an abort handle passed to ceremony functions.
+#[napi(object)]
+pub struct AbortSignal {
+    /// Identifier of the abort handle.
+    pub id: u32,
+}
+
+/// Handle used to cancel ceremonies, similar to
+/// an `AbortController`.
+#[napi]
+pub struct AbortHandle {
+    id: u32,
+    token: CancellationToken,
+}
+
+#[napi]
+impl AbortHandle {
+    /// Create an abort handle.
+    #[napi(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> AbortHandle {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let token = CancellationToken::new();
+        handles().lock().unwrap().insert(id, token.clone());
+        Self { id, token }
+    }
+
+    /// Signal to pass to ceremony functions.
+    #[napi(getter)]
+    pub fn signal(&self) -> AbortSignal {
+        AbortSignal { id: self.id }
+    }
+
+    /// Whether the handle has been aborted.
+    #[napi(getter)]
+    pub fn aborted(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Cancel ceremonies using the signal for this handle.
+    #[napi]
+    pub fn abort(&self) {
+        self.token.cancel();
+    }
+}
+
+impl Drop for AbortHandle {
+    fn drop(&mut self) {
+        handles().lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Cancel the ceremony for a session when the handle aborts.
+pub(crate) fn cancel_on_abort(
+    options: &mut SessionOptions,
+    signal: Option<AbortSignal>,
+) {
+    if let Some(signal) = signal {
+        options.cancel =
+            handles().lock().unwrap().get(&signal.id).cloned();
+    }
+}
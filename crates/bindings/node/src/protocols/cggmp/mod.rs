@@ -1,16 +1,22 @@
 //! Bindings for the CGGMP protocol.
 use anyhow::Error;
-use napi::bindgen_prelude::{Env, JsError, Result};
+use napi::bindgen_prelude::{
+    AsyncTask, Buffer, Env, JsError, Result,
+};
+use napi::Task;
+use napi::threadsafe_function::ThreadsafeFunction;
 use napi_derive::napi;
+use polysig_driver::bip32::DerivationPath;
 use polysig_driver::cggmp::Participant;
-use polysig_driver::synedrion::{
-    ecdsa::{self, SigningKey},
-    SessionId,
-};
+use polysig_driver::digest::DigestAlgorithm;
+use polysig_driver::synedrion::ecdsa::{self, SigningKey};
 use polysig_protocol::{hex, PATTERN};
 use std::collections::BTreeSet;
 
-use super::types::{KeyShare, SessionOptions};
+use super::cancel::{cancel_on_abort, AbortSignal};
+use super::error::into_js_error;
+use super::progress::observe;
+use super::types::{KeyShare, SessionId, SessionOptions};
 
 mod types;
 
@@ -20,6 +26,10 @@ use types::{
 };
 
 /// CGGMP protocol.
+///
+/// Ceremony functions accept an optional callback which
+/// receives progress events as the ceremony advances and an
+/// optional abort signal used to cancel the ceremony.
 #[napi]
 pub struct CggmpProtocol {
     options: polysig_client::SessionOptions,
@@ -34,8 +44,11 @@ impl CggmpProtocol {
         options: SessionOptions,
         key_share: KeyShare,
     ) -> Result<CggmpProtocol> {
-        let options: polysig_client::SessionOptions =
+        let mut options: polysig_client::SessionOptions =
             options.try_into().map_err(Error::new)?;
+        if let Some(policy) = key_share.policy.clone() {
+            options.key_share_policy = Some(policy.into());
+        }
         let key_share: ThresholdKeyShare =
             key_share.try_into().map_err(Error::new)?;
         Ok(Self { options, key_share })
@@ -64,11 +77,16 @@ impl CggmpProtocol {
     pub async fn dkg(
         options: SessionOptions,
         party: PartyOptions,
-        session_id_seed: Vec<u8>,
+        session_id: SessionId,
         signer: Vec<u8>,
+        progress: Option<ThreadsafeFunction<serde_json::Value>>,
+        signal: Option<AbortSignal>,
     ) -> Result<KeyShare> {
-        let options: polysig_client::SessionOptions =
+        let mut options: polysig_client::SessionOptions =
             options.try_into().map_err(Error::new)?;
+        observe(&mut options, progress);
+        cancel_on_abort(&mut options, signal);
+        let session_id = into_session_id(session_id)?;
 
         let party: polysig_driver::cggmp::PartyOptions =
             party.try_into().map_err(Error::new)?;
@@ -82,34 +100,76 @@ impl CggmpProtocol {
         let key_share = polysig_client::cggmp::dkg::<Params>(
             options,
             participant,
-            SessionId::from_seed(&session_id_seed),
+            session_id,
         )
         .await
-        .map_err(Error::new)?;
+        .map_err(into_js_error)?;
 
         let key_share: KeyShare =
             key_share.try_into().map_err(Error::new)?;
         Ok(key_share)
     }
 
-    /// Sign a message.
+    /// Sign a hex-encoded message.
+    ///
+    /// Without a digest algorithm the message must be a
+    /// 32 byte prehash otherwise the prehash is computed
+    /// using the digest algorithm.
     #[napi]
     pub async fn sign(
         &self,
         party: PartyOptions,
-        session_id_seed: Vec<u8>,
+        session_id: SessionId,
         signer: Vec<u8>,
         message: String,
+        progress: Option<ThreadsafeFunction<serde_json::Value>>,
+        signal: Option<AbortSignal>,
+        digest: Option<String>,
+    ) -> Result<RecoverableSignature> {
+        let message = hex::decode(&message).map_err(Error::new)?;
+        self.sign_bytes(
+            party,
+            session_id,
+            signer,
+            message.into(),
+            progress,
+            signal,
+            digest,
+        )
+        .await
+    }
+
+    /// Sign a message supplied as bytes.
+    ///
+    /// Accepts a `Buffer` so binary digests from hashing
+    /// libraries can be signed without a hex round trip.
+    #[napi]
+    pub async fn sign_bytes(
+        &self,
+        party: PartyOptions,
+        session_id: SessionId,
+        signer: Vec<u8>,
+        message: Buffer,
+        progress: Option<ThreadsafeFunction<serde_json::Value>>,
+        signal: Option<AbortSignal>,
+        digest: Option<String>,
     ) -> Result<RecoverableSignature> {
-        let options = self.options.clone();
+        let mut options = self.options.clone();
+        observe(&mut options, progress);
+        cancel_on_abort(&mut options, signal);
+        let session_id = into_session_id(session_id)?;
         let party: polysig_driver::cggmp::PartyOptions =
             party.try_into().map_err(Error::new)?;
         let signer: SigningKey =
             signer.as_slice().try_into().map_err(Error::new)?;
         let verifier = signer.verifying_key().clone();
-        let message = hex::decode(&message).map_err(Error::new)?;
-        let message: [u8; 32] =
-            message.as_slice().try_into().map_err(Error::new)?;
+        let digest: DigestAlgorithm = digest
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(Error::new)?
+            .unwrap_or_default();
+        let message = digest.prehash(&message).map_err(Error::new)?;
         let participant = Participant::new(signer, verifier, party)
             .map_err(Error::new)?;
 
@@ -122,12 +182,12 @@ impl CggmpProtocol {
         let signature = polysig_client::cggmp::sign(
             options,
             participant,
-            SessionId::from_seed(&session_id_seed),
+            session_id,
             &key_share,
             &message,
         )
         .await
-        .map_err(Error::new)?;
+        .map_err(into_js_error)?;
 
         let signature: RecoverableSignature =
             signature.try_into().map_err(Error::new)?;
@@ -140,14 +200,19 @@ impl CggmpProtocol {
         &self,
         // options: SessionOptions,
         party: PartyOptions,
-        session_id_seed: Vec<u8>,
+        session_id: SessionId,
         signer: Vec<u8>,
         account_verifying_key: VerifyingKey,
         key_share: Option<KeyShare>,
         old_threshold: i64,
         new_threshold: i64,
+        progress: Option<ThreadsafeFunction<serde_json::Value>>,
+        signal: Option<AbortSignal>,
     ) -> Result<KeyShare> {
-        let options = self.options.clone();
+        let mut options = self.options.clone();
+        observe(&mut options, progress);
+        cancel_on_abort(&mut options, signal);
+        let session_id = into_session_id(session_id)?;
         let party: polysig_driver::cggmp::PartyOptions =
             party.try_into().map_err(Error::new)?;
         let signer: SigningKey =
@@ -169,35 +234,62 @@ impl CggmpProtocol {
         let key_share = polysig_client::cggmp::reshare(
             options,
             participant,
-            SessionId::from_seed(&session_id_seed),
+            session_id,
             account_verifying_key,
             key_share,
             old_threshold as usize,
             new_threshold as usize,
         )
         .await
-        .map_err(Error::new)?;
+        .map_err(into_js_error)?;
 
         let key_share: KeyShare =
             key_share.try_into().map_err(Error::new)?;
         Ok(key_share)
     }
 
+    /// Derive a session identifier bound to the parties,
+    /// threshold and purpose of a ceremony.
+    ///
+    /// The purpose is one of `key-gen`, `aux-gen`,
+    /// `key-refresh`, `key-resharing` or `sign` and the
+    /// nonce must be agreed by all the parties, for example
+    /// the meeting identifier.
+    #[napi(js_name = "deriveSessionId")]
+    pub fn derive_session_id(
+        verifiers: Vec<VerifyingKey>,
+        threshold: u16,
+        purpose: String,
+        nonce: Vec<u8>,
+    ) -> Result<SessionId> {
+        let mut keys: Vec<ecdsa::VerifyingKey> =
+            Vec::with_capacity(verifiers.len());
+        for verifier in verifiers {
+            keys.push(verifier.try_into().map_err(Error::new)?);
+        }
+        let purpose: polysig_driver::cggmp::SessionPurpose =
+            purpose.parse().map_err(Error::new)?;
+        Ok(polysig_driver::cggmp::session_id_seed(
+            &keys, threshold, purpose, &nonce,
+        )
+        .into())
+    }
+
     /// Generate a BIP32 derived child key.
+    ///
+    /// Derivation runs on the libuv thread pool so the
+    /// event loop is not blocked.
     #[napi(js_name = "deriveBip32")]
     pub fn derive_bip32(
         &self,
         derivation_path: String,
-    ) -> std::result::Result<KeyShare, napi::JsError> {
-        use polysig_driver::bip32::DerivationPath;
+    ) -> Result<AsyncTask<DeriveBip32>> {
         let derivation_path: DerivationPath =
             derivation_path.parse().map_err(Error::new)?;
-        let child_key = polysig_driver::cggmp::derive_bip32(
-            &self.key_share,
-            &derivation_path,
-        )
-        .map_err(Error::new)?;
-        Ok(child_key.try_into().map_err(Error::new)?)
+        Ok(AsyncTask::new(DeriveBip32 {
+            key_share: self.key_share.clone(),
+            derivation_path,
+        }))
     }
 
     /// Generate a PEM-encoded keypair for the noise protocol.
@@ -219,3 +311,38 @@ impl CggmpProtocol {
         Ok(env.to_js_value(&(pem, public_key)).map_err(Error::new)?)
     }
 }
+
+/// Convert a session identifier for the protocol library.
+fn into_session_id(
+    session_id: SessionId,
+) -> Result<polysig_driver::synedrion::SessionId> {
+    let seed: [u8; 32] = session_id.try_into().map_err(Error::new)?;
+    Ok(polysig_driver::synedrion::SessionId::from_seed(&seed))
+}
+
+/// Task to derive a BIP32 child key share.
+pub struct DeriveBip32 {
+    key_share: ThresholdKeyShare,
+    derivation_path: DerivationPath,
+}
+
+impl Task for DeriveBip32 {
+    type Output = ThresholdKeyShare;
+    type JsValue = KeyShare;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(polysig_driver::cggmp::derive_bip32(
+            &self.key_share,
+            &self.derivation_path,
+        )
+        .map_err(Error::new)?)
+    }
+
+    fn resolve(
+        &mut self,
+        _env: Env,
+        output: Self::Output,
+    ) -> Result<Self::JsValue> {
+        Ok(output.try_into().map_err(Error::new)?)
+    }
+}
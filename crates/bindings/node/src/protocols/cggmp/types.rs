@@ -79,12 +79,10 @@ impl TryFrom<PartyOptions> for polysig_driver::cggmp::PartyOptions {
         for verifier in value.verifiers {
             verifiers.push(verifier.try_into()?);
         }
-        Ok(polysig_driver::PartyOptions::new(
-            value.public_key,
-            value.participants,
-            value.is_initiator,
-            verifiers,
-        )?)
+        polysig_driver::PartyOptions::builder(value.public_key)
+            .verifiers(value.participants, verifiers)
+            .initiator(value.is_initiator)
+            .build()
     }
 }
 
@@ -0,0 +1,18 @@
+//! Structured errors for failed ceremonies.
+use napi::{Error, Status};
+
+/// Convert a client error into a javascript error.
+///
+/// The message is the JSON-encoded error info with the
+/// `code`, `kind`, `message`, `party` and `retryable` fields
+/// which are also set as properties on errors thrown by the
+/// webassembly bindings, for example:
+///
+/// ```javascript
+/// const { kind, retryable } = JSON.parse(error.message);
+/// ```
+pub(crate) fn into_js_error(error: polysig_client::Error) -> Error {
+    let info = error.info();
+    let reason = serde_json::to_string(&info).unwrap_or(info.message);
+    Error::new(Status::GenericFailure, reason)
+}
@@ -1,6 +1,10 @@
 macro_rules! frost_impl {
     ($name:ident) => {
         /// FROST protocol.
+        ///
+        /// Ceremony functions accept an optional callback which
+        /// receives progress events as the ceremony advances and
+        /// an optional abort signal used to cancel the ceremony.
         #[napi]
         pub struct $name {
             options: polysig_client::SessionOptions,
@@ -15,8 +19,11 @@ macro_rules! frost_impl {
                 options: SessionOptions,
                 key_share: KeyShare,
             ) -> Result<$name> {
-                let options: polysig_client::SessionOptions =
+                let mut options: polysig_client::SessionOptions =
                     options.try_into().map_err(Error::new)?;
+                if let Some(policy) = key_share.policy.clone() {
+                    options.key_share_policy = Some(policy.into());
+                }
                 let key_share: ThresholdKeyShare =
                     key_share.try_into().map_err(Error::new)?;
                 Ok(Self { options, key_share })
@@ -29,9 +36,23 @@ macro_rules! frost_impl {
                 party: PartyOptions,
                 signer: SigningKey,
                 identifiers: Vec<Identifier>,
+                progress: Option<
+                    napi::threadsafe_function::ThreadsafeFunction<
+                        serde_json::Value,
+                    >,
+                >,
+                signal: Option<crate::protocols::cancel::AbortSignal>,
             ) -> Result<KeyShare> {
-                let options: polysig_client::SessionOptions =
+                let mut options: polysig_client::SessionOptions =
                     options.try_into().map_err(Error::new)?;
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
 
                 let party: ProtocolPartyOptions =
                     party.try_into().map_err(Error::new)?;
@@ -48,9 +69,10 @@ macro_rules! frost_impl {
                     ids.push(id.try_into()?);
                 }
 
-                let key_share = dkg(options, participant, ids)
-                    .await
-                    .map_err(Error::new)?;
+                let key_share =
+                    dkg(options, participant, ids).await.map_err(
+                        crate::protocols::error::into_js_error,
+                    )?;
 
                 let key_share: KeyShare =
                     key_share.try_into().map_err(Error::new)?;
@@ -65,8 +87,55 @@ macro_rules! frost_impl {
                 signer: SigningKey,
                 identifiers: Vec<Identifier>,
                 message: String,
+                progress: Option<
+                    napi::threadsafe_function::ThreadsafeFunction<
+                        serde_json::Value,
+                    >,
+                >,
+                signal: Option<crate::protocols::cancel::AbortSignal>,
+                digest: Option<String>,
+            ) -> Result<Signature> {
+                self.sign_bytes(
+                    party,
+                    signer,
+                    identifiers,
+                    message.into_bytes().into(),
+                    progress,
+                    signal,
+                    digest,
+                )
+                .await
+            }
+
+            /// Sign a message supplied as bytes.
+            ///
+            /// Accepts a `Buffer` so binary digests from hashing
+            /// libraries can be signed without an encoding round
+            /// trip.
+            #[napi]
+            pub async fn sign_bytes(
+                &self,
+                party: PartyOptions,
+                signer: SigningKey,
+                identifiers: Vec<Identifier>,
+                message: napi::bindgen_prelude::Buffer,
+                progress: Option<
+                    napi::threadsafe_function::ThreadsafeFunction<
+                        serde_json::Value,
+                    >,
+                >,
+                signal: Option<crate::protocols::cancel::AbortSignal>,
+                digest: Option<String>,
             ) -> Result<Signature> {
-                let options = self.options.clone();
+                let mut options = self.options.clone();
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
                 let party: ProtocolPartyOptions =
                     party.try_into().map_err(Error::new)?;
                 let signer: ProtocolSigningKey = signer.try_into()?;
@@ -80,18 +149,76 @@ macro_rules! frost_impl {
                     ids.push(id.try_into()?);
                 }
 
+                let digest: polysig_driver::digest::DigestAlgorithm =
+                    digest
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .map_err(Error::new)?
+                        .unwrap_or_default();
+
                 let signature = sign(
                     options,
                     participant,
                     ids,
                     self.key_share.clone(),
-                    message.as_bytes().to_vec(),
+                    digest.digest(&message),
                 )
                 .await
-                .map_err(Error::new)?;
+                .map_err(crate::protocols::error::into_js_error)?;
 
                 Ok(signature.try_into()?)
             }
+
+            /// Refresh the key share.
+            #[napi]
+            pub async fn refresh(
+                &self,
+                party: PartyOptions,
+                signer: SigningKey,
+                identifiers: Vec<Identifier>,
+                progress: Option<
+                    napi::threadsafe_function::ThreadsafeFunction<
+                        serde_json::Value,
+                    >,
+                >,
+                signal: Option<crate::protocols::cancel::AbortSignal>,
+            ) -> Result<KeyShare> {
+                let mut options = self.options.clone();
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
+                let party: ProtocolPartyOptions =
+                    party.try_into().map_err(Error::new)?;
+                let signer: ProtocolSigningKey = signer.try_into()?;
+                let verifier = signer.verifying_key().clone();
+                let participant =
+                    Participant::new(signer, verifier, party)
+                        .map_err(Error::new)?;
+
+                let mut ids = Vec::with_capacity(identifiers.len());
+                for id in identifiers {
+                    ids.push(id.try_into()?);
+                }
+
+                let key_share = refresh(
+                    options,
+                    participant,
+                    ids,
+                    self.key_share.clone(),
+                )
+                .await
+                .map_err(crate::protocols::error::into_js_error)?;
+
+                let key_share: KeyShare =
+                    key_share.try_into().map_err(Error::new)?;
+                Ok(key_share)
+            }
         }
     };
 }
@@ -218,12 +345,12 @@ macro_rules! frost_types {
                 for verifier in value.verifiers {
                     verifiers.push(verifier.try_into()?);
                 }
-                Ok(polysig_driver::PartyOptions::new(
+                Ok(polysig_driver::PartyOptions::builder(
                     value.public_key,
-                    value.participants,
-                    value.is_initiator,
-                    verifiers,
                 )
+                .verifiers(value.participants, verifiers)
+                .initiator(value.is_initiator)
+                .build()
                 .map_err(Error::new)?)
             }
         }
@@ -3,7 +3,7 @@ use crate::protocols::types::{KeyShare, SessionOptions};
 use anyhow::Error;
 use napi::bindgen_prelude::Result;
 use napi_derive::napi;
-use polysig_client::frost::ed25519::{dkg, sign};
+use polysig_client::frost::ed25519::{dkg, refresh, sign};
 use polysig_driver::{
     self as driver,
     frost::ed25519::{
@@ -5,5 +5,11 @@ pub(crate) mod core;
 #[cfg(feature = "frost-ed25519")]
 pub mod ed25519;
 
+#[cfg(feature = "frost-ristretto255")]
+pub mod ristretto255;
+
+#[cfg(feature = "frost-secp256k1")]
+pub mod secp256k1;
+
 #[cfg(feature = "frost-secp256k1-tr")]
 pub mod secp256k1_tr;
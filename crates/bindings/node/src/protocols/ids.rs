@@ -0,0 +1,43 @@
+//! Identifiers for meeting rooms and protocol ceremonies.
+use super::types::{MeetingId, SessionId};
+use anyhow::Error;
+use napi::bindgen_prelude::Result;
+use napi_derive::napi;
+use polysig_protocol as protocol;
+use rand::{rngs::OsRng, RngCore};
+
+/// Generate a random meeting identifier.
+#[napi(js_name = "generateMeetingId")]
+pub fn generate_meeting_id() -> MeetingId {
+    protocol::MeetingId::new_v4().into()
+}
+
+/// Parse a meeting identifier.
+///
+/// Any UUID encoding is accepted and the identifier
+/// is returned in the canonical encoding.
+#[napi(js_name = "parseMeetingId")]
+pub fn parse_meeting_id(value: String) -> Result<MeetingId> {
+    let id: protocol::MeetingId =
+        MeetingId { id: value }.try_into().map_err(Error::new)?;
+    Ok(id.into())
+}
+
+/// Generate a random session identifier.
+#[napi(js_name = "generateSessionId")]
+pub fn generate_session_id() -> SessionId {
+    let mut id = [0u8; 32];
+    OsRng.fill_bytes(&mut id);
+    id.into()
+}
+
+/// Parse a session identifier.
+///
+/// Accepts 32 bytes of hex with an optional `0x` prefix
+/// and returns the identifier in the canonical encoding.
+#[napi(js_name = "parseSessionId")]
+pub fn parse_session_id(value: String) -> Result<SessionId> {
+    let id: [u8; 32] =
+        SessionId { id: value }.try_into().map_err(Error::new)?;
+    Ok(id.into())
+}
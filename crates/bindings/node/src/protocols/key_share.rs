@@ -0,0 +1,81 @@
+//! Encode, decode and encrypt key shares.
+//!
+//! Encoded key shares use the same JSON format as the
+//! keystore for the Rust libraries.
+use anyhow::Error;
+use napi::bindgen_prelude::Result;
+use napi_derive::napi;
+use polysig_driver::encrypt;
+
+use super::types::KeyShare;
+
+/// Key share encrypted with a symmetric key.
+#[napi(object)]
+#[derive(Debug)]
+pub struct EncryptedKeyShare {
+    /// Container version.
+    pub version: u16,
+    /// Nonce for the cipher.
+    pub nonce: Vec<u8>,
+    /// Encrypted key share and authentication tag.
+    pub ciphertext: Vec<u8>,
+}
+
+impl From<encrypt::EncryptedKeyShare> for EncryptedKeyShare {
+    fn from(value: encrypt::EncryptedKeyShare) -> Self {
+        Self {
+            version: value.version,
+            nonce: value.nonce,
+            ciphertext: value.ciphertext,
+        }
+    }
+}
+
+impl From<EncryptedKeyShare> for encrypt::EncryptedKeyShare {
+    fn from(value: EncryptedKeyShare) -> Self {
+        Self {
+            version: value.version,
+            nonce: value.nonce,
+            ciphertext: value.ciphertext,
+        }
+    }
+}
+
+/// Encode a key share as JSON.
+#[napi]
+pub fn encode_key_share(key_share: KeyShare) -> Result<String> {
+    let key_share: polysig_driver::KeyShare = key_share.into();
+    Ok(key_share.encode().map_err(Error::new)?)
+}
+
+/// Decode a key share from JSON.
+#[napi]
+pub fn decode_key_share(value: String) -> Result<KeyShare> {
+    let key_share = polysig_driver::KeyShare::decode(&value)
+        .map_err(Error::new)?;
+    Ok(key_share.into())
+}
+
+/// Encrypt a key share using a 32 byte key.
+#[napi]
+pub fn encrypt_key_share(
+    key_share: KeyShare,
+    key: Vec<u8>,
+) -> Result<EncryptedKeyShare> {
+    let key_share: polysig_driver::KeyShare = key_share.into();
+    let encrypted =
+        encrypt::EncryptedKeyShare::encrypt(&key_share, &key)
+            .map_err(Error::new)?;
+    Ok(encrypted.into())
+}
+
+/// Decrypt a key share using a 32 byte key.
+#[napi]
+pub fn decrypt_key_share(
+    encrypted: EncryptedKeyShare,
+    key: Vec<u8>,
+) -> Result<KeyShare> {
+    let encrypted: encrypt::EncryptedKeyShare = encrypted.into();
+    let key_share = encrypted.decrypt(&key).map_err(Error::new)?;
+    Ok(key_share.into())
+}
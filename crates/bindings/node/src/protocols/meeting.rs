@@ -1,10 +1,67 @@
 //! Bindings for meeting points.
-use super::types::{MeetingItem, PublicKeys, UserId};
-use anyhow::Result;
+use super::error::into_js_error;
+use super::types::{MeetingId, MeetingItem, PublicKeys, UserId};
+use anyhow::Error;
+use napi::bindgen_prelude::Result;
 use napi_derive::napi;
 use polysig_client::meeting;
 use polysig_protocol as protocol;
 
+/// Create a meeting room used to exchange public keys.
+///
+/// Resolves to the meeting identifier which the initiator
+/// shares with the other participants.
+#[napi(js_name = "createMeeting")]
+pub async fn create_meeting(
+    url: String,
+    identifiers: Vec<UserId>,
+    initiator: UserId,
+) -> Result<MeetingId> {
+    let mut ids = Vec::with_capacity(identifiers.len());
+    for id in identifiers {
+        ids.push(id.try_into()?);
+    }
+    let initiator: protocol::UserId = initiator.try_into()?;
+
+    Ok(meeting::create(&url, ids, initiator)
+        .await
+        .map_err(into_js_error)?
+        .into())
+}
+
+/// Join a meeting room used to exchange public keys.
+///
+/// Resolves once every participant has joined to the
+/// public keys of all the participants.
+#[napi(js_name = "joinMeeting")]
+pub async fn join_meeting(
+    url: String,
+    meeting_id: MeetingId,
+    user_id: UserId,
+    data: PublicKeys,
+) -> Result<Vec<MeetingItem>> {
+    let meeting_id: protocol::MeetingId =
+        meeting_id.try_into().map_err(Error::new)?;
+    let results = meeting::join(
+        &url,
+        meeting_id,
+        user_id.try_into()?,
+        data.into(),
+    )
+    .await
+    .map_err(into_js_error)?;
+
+    let mut output = Vec::with_capacity(results.len());
+    for result in results {
+        output.push(MeetingItem {
+            user_id: result.0.into(),
+            data: result.1.into(),
+        })
+    }
+
+    Ok(output)
+}
+
 /// Create and join meeting rooms.
 #[napi]
 pub struct MeetingRoom {
@@ -25,42 +82,19 @@ impl MeetingRoom {
         &self,
         identifiers: Vec<UserId>,
         initiator: UserId,
-    ) -> Result<String> {
-        let mut ids = Vec::with_capacity(identifiers.len());
-        for id in identifiers {
-            ids.push(id.try_into()?);
-        }
-
-        Ok(meeting::create(&self.url, ids, initiator.try_into()?)
-            .await?
-            .to_string())
+    ) -> Result<MeetingId> {
+        create_meeting(self.url.clone(), identifiers, initiator).await
     }
 
     /// Join a meeting room used to exchange public keys.
     #[napi]
     pub async fn join(
         &self,
-        meeting_id: String,
+        meeting_id: MeetingId,
         user_id: UserId,
         data: PublicKeys,
     ) -> Result<Vec<MeetingItem>> {
-        let meeting_id: protocol::MeetingId = meeting_id.parse()?;
-        let results = meeting::join(
-            &self.url,
-            meeting_id,
-            user_id.try_into()?,
-            data.into(),
-        )
-        .await?;
-
-        let mut output = Vec::with_capacity(results.len());
-        for result in results {
-            output.push(MeetingItem {
-                user_id: result.0.into(),
-                data: result.1.into(),
-            })
-        }
-
-        Ok(output)
+        join_meeting(self.url.clone(), meeting_id, user_id, data)
+            .await
     }
 }
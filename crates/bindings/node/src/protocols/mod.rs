@@ -4,8 +4,13 @@ pub mod cggmp;
 #[cfg(feature = "frost")]
 pub mod frost;
 
+pub mod cancel;
+pub mod ids;
+pub mod key_share;
 pub mod meeting;
 
+mod error;
+mod progress;
 mod types;
 
 #[napi::module_init]
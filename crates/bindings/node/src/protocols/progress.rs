@@ -0,0 +1,32 @@
+//! Forward ceremony progress events to a callback.
+use futures::StreamExt;
+use napi::threadsafe_function::{
+    ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use polysig_client::{progress_channel, SessionOptions};
+
+/// Call the callback with the progress events for a session.
+///
+/// The callback is invoked as `callback(null, event)` where
+/// events are plain objects with a `type` field, for
+/// example `{ type: "round", round: 2 }`.
+pub(crate) fn observe(
+    options: &mut SessionOptions,
+    callback: Option<ThreadsafeFunction<serde_json::Value>>,
+) {
+    let Some(callback) = callback else {
+        return;
+    };
+    let (progress, mut events) = progress_channel();
+    options.progress = Some(progress);
+    tokio::spawn(async move {
+        while let Some(event) = events.next().await {
+            if let Ok(event) = serde_json::to_value(&event) {
+                callback.call(
+                    Ok(event),
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+        }
+    });
+}
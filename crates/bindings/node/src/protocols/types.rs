@@ -30,6 +30,7 @@ impl TryFrom<Keypair> for protocol::Keypair {
 pub struct KeyShare {
     pub version: u16,
     pub contents: String,
+    pub policy: Option<KeySharePolicy>,
 }
 
 impl From<polysig_driver::KeyShare> for KeyShare {
@@ -37,6 +38,7 @@ impl From<polysig_driver::KeyShare> for KeyShare {
         Self {
             version: value.version,
             contents: value.contents,
+            policy: value.policy.map(Into::into),
         }
     }
 }
@@ -46,6 +48,79 @@ impl From<KeyShare> for polysig_driver::KeyShare {
         Self {
             version: value.version,
             contents: value.contents,
+            policy: value.policy.map(Into::into),
+        }
+    }
+}
+
+/// Rules enforced before signing with a key share.
+///
+/// The expiry is in seconds since the UNIX epoch.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct KeySharePolicy {
+    pub expires_at: Option<i64>,
+    pub derivation_paths: Option<Vec<String>>,
+    pub chains: Option<Vec<String>>,
+}
+
+impl From<polysig_driver::KeySharePolicy> for KeySharePolicy {
+    fn from(value: polysig_driver::KeySharePolicy) -> Self {
+        Self {
+            expires_at: value
+                .expires_at
+                .map(|t| i64::try_from(t).unwrap_or(i64::MAX)),
+            derivation_paths: Some(value.derivation_paths),
+            chains: Some(value.chains),
+        }
+    }
+}
+
+impl From<KeySharePolicy> for polysig_driver::KeySharePolicy {
+    fn from(value: KeySharePolicy) -> Self {
+        Self {
+            // Negative timestamps are already expired
+            expires_at: value
+                .expires_at
+                .map(|t| u64::try_from(t).unwrap_or_default()),
+            derivation_paths: value
+                .derivation_paths
+                .unwrap_or_default(),
+            chains: value.chains.unwrap_or_default(),
+        }
+    }
+}
+
+/// Context for a signature checked against the
+/// key share policy.
+#[napi(object)]
+#[derive(Debug)]
+pub struct SigningContext {
+    pub derivation_path: Option<String>,
+    pub chain: Option<String>,
+}
+
+impl From<SigningContext> for polysig_client::SigningContext {
+    fn from(value: SigningContext) -> Self {
+        Self {
+            derivation_path: value.derivation_path,
+            chain: value.chain,
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Debug)]
+pub struct KeepaliveOptions {
+    pub ping_interval: u32,
+    pub idle_timeout: u32,
+}
+
+impl From<KeepaliveOptions> for polysig_client::KeepaliveOptions {
+    fn from(value: KeepaliveOptions) -> Self {
+        Self {
+            ping_interval: value.ping_interval,
+            idle_timeout: value.idle_timeout,
         }
     }
 }
@@ -91,20 +166,34 @@ pub struct SessionOptions {
     pub keypair: Keypair,
     pub server: ServerOptions,
     pub parameters: Parameters,
+    pub signing_context: Option<SigningContext>,
+    pub round_timeout: Option<u32>,
+    pub keepalive: Option<KeepaliveOptions>,
 }
 
 impl TryFrom<SessionOptions> for polysig_client::SessionOptions {
-    type Error = polysig_driver::Error;
+    type Error = polysig_client::Error;
 
     fn try_from(value: SessionOptions) -> Result<Self, Self::Error> {
-        Ok(polysig_client::SessionOptions {
-            keypair: value.keypair.try_into()?,
-            server: value.server.into(),
-            parameters: value.parameters.into(),
-        })
+        let mut builder = polysig_client::SessionOptions::builder(
+            value.keypair.try_into()?,
+            value.server.into(),
+            value.parameters.into(),
+        );
+        if let Some(context) = value.signing_context {
+            builder = builder.signing_context(context.into());
+        }
+        if let Some(seconds) = value.round_timeout {
+            builder = builder.round_timeout(seconds);
+        }
+        if let Some(keepalive) = value.keepalive {
+            builder = builder.keepalive(keepalive.into());
+        }
+        builder.build()
     }
 }
 
+/// User identifier for a meeting room (SHA-256 checksum).
 #[napi(object)]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserId {
@@ -129,6 +218,72 @@ impl From<protocol::UserId> for UserId {
     }
 }
 
+/// Meeting room identifier.
+///
+/// Encoded as a lowercase hyphenated UUID.
+#[napi(object)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MeetingId {
+    /// Meeting identifier.
+    pub id: String,
+}
+
+impl TryFrom<MeetingId> for protocol::MeetingId {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MeetingId) -> Result<Self, Self::Error> {
+        value.id.parse().map_err(|_| {
+            anyhow::anyhow!(
+                "invalid meeting id '{}', expected a UUID",
+                value.id
+            )
+        })
+    }
+}
+
+impl From<protocol::MeetingId> for MeetingId {
+    fn from(value: protocol::MeetingId) -> Self {
+        Self {
+            id: value.to_string(),
+        }
+    }
+}
+
+/// Session identifier for a protocol ceremony.
+///
+/// Encoded as 32 bytes of lowercase hex; every party in
+/// a ceremony must use the same session identifier.
+#[napi(object)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionId {
+    /// Session identifier.
+    pub id: String,
+}
+
+impl TryFrom<SessionId> for [u8; 32] {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SessionId) -> Result<Self, Self::Error> {
+        let id = value.id.strip_prefix("0x").unwrap_or(&value.id);
+        match protocol::hex::decode(id).map(<[u8; 32]>::try_from) {
+            Ok(Ok(id)) => Ok(id),
+            _ => Err(anyhow::anyhow!(
+                "invalid session id '{}', expected 32 bytes of hex",
+                value.id
+            )),
+        }
+    }
+}
+
+impl From<[u8; 32]> for SessionId {
+    fn from(value: [u8; 32]) -> Self {
+        Self {
+            id: protocol::hex::encode(value),
+        }
+    }
+}
+
+/// Public keys exchanged in a meeting room.
 #[napi(object)]
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -161,6 +316,7 @@ impl From<protocol::PublicKeys> for PublicKeys {
     }
 }
 
+/// Public keys of a meeting room participant.
 #[napi(object)]
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
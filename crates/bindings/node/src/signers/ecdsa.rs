@@ -23,7 +23,9 @@ impl EcdsaSigner {
             ecdsa::EcdsaSigner::from_slice(&signing_key)
                 .map_err(Error::new)?;
         Ok(Self {
-            inner: ecdsa::EcdsaSigner::new(Cow::Owned(signing_key)),
+            inner: ecdsa::EcdsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
         })
     }
 
@@ -33,6 +35,39 @@ impl EcdsaSigner {
         ecdsa::EcdsaSigner::random().to_bytes().as_slice().to_vec()
     }
 
+    /// Derive a signing key from a seed and BIP-32 derivation
+    /// path, for example `m/44'/60'/0'/0/0`.
+    #[napi(js_name = "deriveFromSeed")]
+    pub fn derive_from_seed(
+        seed: Vec<u8>,
+        path: String,
+    ) -> Result<Vec<u8>, JsError> {
+        let path: ecdsa::DerivationPath =
+            path.parse().map_err(Error::new)?;
+        let signing_key =
+            ecdsa::EcdsaSigner::derive_from_seed(&seed, &path)
+                .map_err(Error::new)?;
+        Ok(signing_key.to_bytes().as_slice().to_vec())
+    }
+
+    /// Create a BIP-44 derivation path.
+    #[napi(js_name = "bip44Path")]
+    pub fn bip44_path(
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        address_index: u32,
+    ) -> Result<String, JsError> {
+        let path = ecdsa::EcdsaSigner::bip44_path(
+            coin_type,
+            account,
+            change,
+            address_index,
+        )
+        .map_err(Error::new)?;
+        Ok(path.to_string())
+    }
+
     /// Sign the given message, hashing it with the curve’s
     /// default digest function, and returning a signature
     /// and recovery ID.
@@ -148,4 +183,17 @@ impl EcdsaSigner {
         let hash = digest.finalize();
         hash.to_vec()
     }
+
+    /// Compute the Keccak256 digest of a structured message.
+    ///
+    /// The value is encoded as canonical JSON (RFC 8785) so
+    /// the digest is the same regardless of key order.
+    #[napi(js_name = "keccak256Json")]
+    pub fn keccak256_json(
+        value: serde_json::Value,
+    ) -> Result<Vec<u8>, JsError> {
+        let message = polysig_protocol::canonical::to_vec(&value)
+            .map_err(Error::new)?;
+        Ok(Self::keccak256(message))
+    }
 }
@@ -21,7 +21,9 @@ impl EddsaSigner {
         let signing_key =
             eddsa::EddsaSigner::from_bytes(&signing_key);
         Ok(Self {
-            inner: eddsa::EddsaSigner::new(Cow::Owned(signing_key)),
+            inner: eddsa::EddsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
         })
     }
 
@@ -31,6 +33,21 @@ impl EddsaSigner {
         eddsa::EddsaSigner::random().to_bytes().as_slice().to_vec()
     }
 
+    /// Derive a signing key from a seed using SLIP-0010.
+    ///
+    /// Every component of the path must be hardened,
+    /// for example `m/44'/501'/0'/0'`.
+    #[napi(js_name = "deriveFromSeed")]
+    pub fn derive_from_seed(
+        seed: Vec<u8>,
+        path: String,
+    ) -> Result<Vec<u8>, JsError> {
+        let signing_key =
+            eddsa::EddsaSigner::derive_from_seed(&seed, &path)
+                .map_err(Error::new)?;
+        Ok(signing_key.to_bytes().as_slice().to_vec())
+    }
+
     /// Sign a message.
     #[napi]
     pub fn sign(&self, message: Vec<u8>) -> Vec<u8> {
@@ -0,0 +1,35 @@
+//! BIP-39 mnemonics used to derive signing keys.
+use anyhow::Error;
+use napi::JsError;
+use napi_derive::napi;
+use polysig_driver::signers::mnemonic;
+
+/// Generate a random mnemonic phrase.
+///
+/// The word count must be 12, 15, 18, 21 or 24.
+#[napi]
+pub fn generate_mnemonic(word_count: u32) -> Result<String, JsError> {
+    let mnemonic = mnemonic::generate(word_count as usize)
+        .map_err(Error::new)?;
+    Ok(mnemonic.to_string())
+}
+
+/// Normalize a mnemonic phrase and verify the checksum.
+#[napi]
+pub fn parse_mnemonic(phrase: String) -> Result<String, JsError> {
+    let mnemonic = mnemonic::parse(&phrase).map_err(Error::new)?;
+    Ok(mnemonic.to_string())
+}
+
+/// Compute the seed for a mnemonic phrase.
+///
+/// Use an empty string when there is no passphrase.
+#[napi]
+pub fn mnemonic_to_seed(
+    phrase: String,
+    passphrase: String,
+) -> Result<Vec<u8>, JsError> {
+    let seed = mnemonic::to_seed(&phrase, &passphrase)
+        .map_err(Error::new)?;
+    Ok(seed.to_vec())
+}
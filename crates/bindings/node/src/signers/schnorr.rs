@@ -23,7 +23,7 @@ impl SchnorrSigner {
                 .map_err(Error::new)?;
         Ok(Self {
             inner: schnorr::SchnorrSigner::new(Cow::Owned(
-                signing_key,
+                signing_key.into(),
             )),
         })
     }
@@ -37,6 +37,39 @@ impl SchnorrSigner {
             .to_vec()
     }
 
+    /// Derive a signing key from a seed and BIP-32 derivation
+    /// path, for example `m/86'/0'/0'/0/0`.
+    #[napi(js_name = "deriveFromSeed")]
+    pub fn derive_from_seed(
+        seed: Vec<u8>,
+        path: String,
+    ) -> Result<Vec<u8>, JsError> {
+        let path: schnorr::DerivationPath =
+            path.parse().map_err(Error::new)?;
+        let signing_key =
+            schnorr::SchnorrSigner::derive_from_seed(&seed, &path)
+                .map_err(Error::new)?;
+        Ok(signing_key.to_bytes().as_slice().to_vec())
+    }
+
+    /// Create a BIP-86 derivation path.
+    #[napi(js_name = "bip86Path")]
+    pub fn bip86_path(
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        address_index: u32,
+    ) -> Result<String, JsError> {
+        let path = schnorr::SchnorrSigner::bip86_path(
+            coin_type,
+            account,
+            change,
+            address_index,
+        )
+        .map_err(Error::new)?;
+        Ok(path.to_string())
+    }
+
     /// Sign a message.
     #[napi]
     pub fn sign(&self, message: Vec<u8>) -> Vec<u8> {
@@ -0,0 +1,17 @@
+//! Capabilities compiled into the build.
+use crate::convert::to_py;
+use pyo3::prelude::*;
+
+/// Capabilities compiled into this build.
+///
+/// Exchange the capabilities with the other participants
+/// to check they run compatible builds before starting
+/// a ceremony.
+#[pyfunction]
+pub fn capabilities(py: Python<'_>) -> PyResult<PyObject> {
+    #[cfg(feature = "protocol")]
+    let capabilities = polysig_client::capabilities();
+    #[cfg(not(feature = "protocol"))]
+    let capabilities = polysig_driver::capabilities();
+    to_py(py, &capabilities)
+}
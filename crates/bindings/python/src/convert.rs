@@ -0,0 +1,32 @@
+//! Conversion between Python objects and serde types.
+//!
+//! Byte arrays may be given as `bytes` or lists of integers
+//! and are returned as lists of integers.
+use crate::error::py_err;
+use pyo3::prelude::*;
+use pythonize::{depythonize, pythonize};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Convert a serializable value into a Python object.
+pub(crate) fn to_py<T: Serialize>(
+    py: Python<'_>,
+    value: &T,
+) -> PyResult<PyObject> {
+    Ok(pythonize(py, value).map_err(py_err)?.unbind())
+}
+
+/// Convert a Python object into a deserializable value.
+pub(crate) fn from_py<T: DeserializeOwned>(
+    value: &Bound<'_, PyAny>,
+) -> PyResult<T> {
+    depythonize(value).map_err(py_err)
+}
+
+/// Convert a serializable value into a Python object
+/// from a future running without the GIL.
+#[cfg(feature = "protocol")]
+pub(crate) fn to_py_with_gil<T: Serialize>(
+    value: &T,
+) -> PyResult<PyObject> {
+    Python::with_gil(|py| to_py(py, value))
+}
@@ -0,0 +1,34 @@
+//! Errors raised in Python.
+use pyo3::{create_exception, exceptions::PyException, PyErr};
+
+create_exception!(
+    polysig,
+    PolysigError,
+    PyException,
+    "Error raised by the polysig library."
+);
+
+/// Convert an error into a Python exception.
+pub(crate) fn py_err(e: impl std::fmt::Display) -> PyErr {
+    PolysigError::new_err(e.to_string())
+}
+
+/// Convert a client error into a Python exception.
+///
+/// The `code`, `kind`, `party` and `retryable` attributes
+/// of the exception are set from the error info so callers
+/// can decide whether to retry a ceremony.
+#[cfg(feature = "protocol")]
+pub(crate) fn py_client_err(e: polysig_client::Error) -> PyErr {
+    use pyo3::Python;
+    let info = e.info();
+    let err = PolysigError::new_err(info.message);
+    Python::with_gil(|py| {
+        let value = err.value_bound(py);
+        let _ = value.setattr("code", info.code);
+        let _ = value.setattr("kind", info.kind.to_string());
+        let _ = value.setattr("party", info.party);
+        let _ = value.setattr("retryable", info.retryable);
+    });
+    err
+}
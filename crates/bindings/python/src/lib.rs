@@ -0,0 +1,40 @@
+//! Python bindings for the polysig library.
+//!
+//! Ceremony functions return awaitables driven by a tokio
+//! runtime so they can be used directly from `asyncio`.
+#![deny(missing_docs)]
+use pyo3::prelude::*;
+
+mod capabilities;
+mod convert;
+mod error;
+
+pub use error::PolysigError;
+
+/// Threshold signature protocols.
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub mod protocols;
+
+/// Single party signers.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+pub mod signers;
+
+#[pymodule]
+#[pyo3(name = "polysig")]
+fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("PolysigError", m.py().get_type_bound::<PolysigError>())?;
+    m.add_function(wrap_pyfunction!(capabilities::capabilities, m)?)?;
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "eddsa",
+        feature = "schnorr"
+    ))]
+    signers::register(m)?;
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    protocols::register(m)?;
+    Ok(())
+}
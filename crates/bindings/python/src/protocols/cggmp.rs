@@ -0,0 +1,295 @@
+//! Bindings for the CGGMP protocol.
+use crate::{
+    convert::{from_py, to_py, to_py_with_gil},
+    error::{py_client_err, py_err},
+};
+use polysig_client::SessionOptions;
+use polysig_driver::synedrion::{
+    self,
+    ecdsa::{SigningKey, VerifyingKey},
+    SessionId,
+};
+use polysig_driver::{
+    cggmp::{self, Participant},
+    digest::DigestAlgorithm,
+    KeyShare,
+};
+use polysig_protocol::hex;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use serde::Deserialize;
+use std::{borrow::Cow, collections::BTreeSet};
+
+#[cfg(not(debug_assertions))]
+type Params = synedrion::ProductionParams;
+#[cfg(debug_assertions)]
+type Params = synedrion::TestParams;
+
+type ThresholdKeyShare =
+    synedrion::ThresholdKeyShare<Params, VerifyingKey>;
+
+/// Options for a party participating in a protocol.
+///
+/// Required in the bindings to convert the `verifiers`
+/// from bytes to verifying keys.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartyOptions {
+    public_key: Vec<u8>,
+    participants: Vec<Vec<u8>>,
+    is_initiator: bool,
+    verifiers: Vec<Vec<u8>>,
+}
+
+impl TryFrom<PartyOptions> for cggmp::PartyOptions {
+    type Error = PyErr;
+
+    fn try_from(value: PartyOptions) -> Result<Self, Self::Error> {
+        let mut verifiers = Vec::with_capacity(value.verifiers.len());
+        for key in &value.verifiers {
+            verifiers.push(
+                VerifyingKey::from_sec1_bytes(key).map_err(py_err)?,
+            );
+        }
+
+        cggmp::PartyOptions::builder(value.public_key)
+            .verifiers(value.participants, verifiers)
+            .initiator(value.is_initiator)
+            .build()
+            .map_err(py_err)
+    }
+}
+
+/// Create a participant from the party options and signing key.
+fn participant(
+    party: &Bound<'_, PyAny>,
+    signer: &[u8],
+) -> PyResult<Participant> {
+    let party: PartyOptions = from_py(party)?;
+    let signer: SigningKey = signer.try_into().map_err(py_err)?;
+    let verifier = signer.verifying_key().clone();
+    Participant::new(signer, verifier, party.try_into()?)
+        .map_err(py_err)
+}
+
+/// CGGMP protocol.
+///
+/// Ceremony methods return awaitables for use with `asyncio`.
+#[pyclass(module = "polysig")]
+pub struct CggmpProtocol {
+    options: SessionOptions,
+    key_share: ThresholdKeyShare,
+}
+
+#[pymethods]
+impl CggmpProtocol {
+    /// Create a CGGMP protocol.
+    #[new]
+    pub fn new(
+        options: &Bound<'_, PyAny>,
+        key_share: &Bound<'_, PyAny>,
+    ) -> PyResult<CggmpProtocol> {
+        let mut options: SessionOptions = from_py(options)?;
+        let key_share: KeyShare = from_py(key_share)?;
+        if key_share.policy.is_some() {
+            options.key_share_policy = key_share.policy.clone();
+        }
+        let key_share: ThresholdKeyShare =
+            (&key_share).try_into().map_err(py_err)?;
+        Ok(Self { options, key_share })
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(
+            self.key_share.verifying_key().to_sec1_bytes().to_vec(),
+        )
+    }
+
+    /// Compute the Ethereum address for the verifying key.
+    pub fn address(&self) -> String {
+        let public_key = self
+            .key_share
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        polysig_driver::address(&public_key)
+    }
+
+    /// Distributed key generation.
+    #[staticmethod]
+    pub fn dkg<'py>(
+        py: Python<'py>,
+        options: &Bound<'py, PyAny>,
+        party: &Bound<'py, PyAny>,
+        session_id_seed: Vec<u8>,
+        signer: &[u8],
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let options: SessionOptions = from_py(options)?;
+        let participant = participant(party, signer)?;
+        future_into_py(py, async move {
+            let key_share = polysig_client::cggmp::dkg::<Params>(
+                options,
+                participant,
+                SessionId::from_seed(&session_id_seed),
+            )
+            .await
+            .map_err(py_client_err)?;
+
+            let key_share: KeyShare =
+                (&key_share).try_into().map_err(py_err)?;
+            to_py_with_gil(&key_share)
+        })
+    }
+
+    /// Sign a hex-encoded message.
+    ///
+    /// Without a digest algorithm the message must be a
+    /// 32 byte prehash otherwise the prehash is computed
+    /// using the digest algorithm.
+    #[pyo3(signature = (
+        party, session_id_seed, signer, message, digest=None
+    ))]
+    pub fn sign<'py>(
+        &self,
+        py: Python<'py>,
+        party: &Bound<'py, PyAny>,
+        session_id_seed: Vec<u8>,
+        signer: &[u8],
+        message: String,
+        digest: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let message = hex::decode(&message).map_err(py_err)?;
+        self.sign_bytes(
+            py,
+            party,
+            session_id_seed,
+            signer,
+            message,
+            digest,
+        )
+    }
+
+    /// Sign a message supplied as bytes.
+    ///
+    /// Binary digests from hashing libraries can be signed
+    /// without a hex round trip.
+    #[pyo3(signature = (
+        party, session_id_seed, signer, message, digest=None
+    ))]
+    pub fn sign_bytes<'py>(
+        &self,
+        py: Python<'py>,
+        party: &Bound<'py, PyAny>,
+        session_id_seed: Vec<u8>,
+        signer: &[u8],
+        message: Vec<u8>,
+        digest: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let options = self.options.clone();
+        let participant = participant(party, signer)?;
+
+        let mut selected_parties = BTreeSet::new();
+        selected_parties
+            .extend(participant.party().verifiers().iter());
+        let key_share =
+            self.key_share.to_key_share(&selected_parties);
+
+        let digest: DigestAlgorithm = digest
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(py_err)?
+            .unwrap_or_default();
+        let message = digest.prehash(&message).map_err(py_err)?;
+
+        future_into_py(py, async move {
+            let signature = polysig_client::cggmp::sign(
+                options,
+                participant,
+                SessionId::from_seed(&session_id_seed),
+                &key_share,
+                &message,
+            )
+            .await
+            .map_err(py_client_err)?;
+            to_py_with_gil(&signature)
+        })
+    }
+
+    /// Reshare key shares.
+    ///
+    /// New participants pass `None` for the key share.
+    pub fn reshare<'py>(
+        &self,
+        py: Python<'py>,
+        party: &Bound<'py, PyAny>,
+        session_id_seed: Vec<u8>,
+        signer: &[u8],
+        account_verifying_key: &[u8],
+        key_share: Option<&Bound<'py, PyAny>>,
+        old_threshold: usize,
+        new_threshold: usize,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let options = self.options.clone();
+        let participant = participant(party, signer)?;
+        let account_verifying_key =
+            VerifyingKey::from_sec1_bytes(account_verifying_key)
+                .map_err(py_err)?;
+
+        let key_share: Option<ThresholdKeyShare> =
+            if let Some(key_share) = key_share {
+                let key_share: KeyShare = from_py(key_share)?;
+                Some((&key_share).try_into().map_err(py_err)?)
+            } else {
+                None
+            };
+
+        future_into_py(py, async move {
+            let key_share = polysig_client::cggmp::reshare(
+                options,
+                participant,
+                SessionId::from_seed(&session_id_seed),
+                account_verifying_key,
+                key_share,
+                old_threshold,
+                new_threshold,
+            )
+            .await
+            .map_err(py_client_err)?;
+
+            let key_share: KeyShare =
+                (&key_share).try_into().map_err(py_err)?;
+            to_py_with_gil(&key_share)
+        })
+    }
+
+    /// Generate a BIP32 derived child key.
+    pub fn derive_bip32(
+        &self,
+        py: Python<'_>,
+        derivation_path: String,
+    ) -> PyResult<PyObject> {
+        use polysig_driver::bip32::DerivationPath;
+
+        let derivation_path: DerivationPath =
+            derivation_path.parse().map_err(py_err)?;
+        let child_key = polysig_driver::cggmp::derive_bip32(
+            &self.key_share,
+            &derivation_path,
+        )
+        .map_err(py_err)?;
+        let child_key: KeyShare =
+            (&child_key).try_into().map_err(py_err)?;
+        to_py(py, &child_key)
+    }
+
+    /// Generate an encyption keypair for the noise protocol.
+    #[staticmethod]
+    pub fn generate_keypair(py: Python<'_>) -> PyResult<PyObject> {
+        let keypair =
+            polysig_protocol::Keypair::generate().map_err(py_err)?;
+        to_py(py, &keypair)
+    }
+}
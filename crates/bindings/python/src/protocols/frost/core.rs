@@ -0,0 +1,136 @@
+macro_rules! frost_impl {
+    ($name:ident) => {
+        /// FROST protocol.
+        ///
+        /// Ceremony methods return awaitables for use
+        /// with `asyncio`.
+        #[pyclass(module = "polysig")]
+        pub struct $name {
+            options: SessionOptions,
+            key_share: ThresholdKeyShare,
+        }
+
+        #[pymethods]
+        impl $name {
+            /// Create a FROST protocol.
+            #[new]
+            pub fn new(
+                options: &Bound<'_, PyAny>,
+                key_share: &Bound<'_, PyAny>,
+            ) -> PyResult<$name> {
+                let mut options: SessionOptions = from_py(options)?;
+                let key_share: KeyShare = from_py(key_share)?;
+                if key_share.policy.is_some() {
+                    options.key_share_policy =
+                        key_share.policy.clone();
+                }
+                let key_share: ThresholdKeyShare =
+                    (&key_share).try_into().map_err(py_err)?;
+                Ok(Self { options, key_share })
+            }
+
+            /// Verifying key for the group.
+            pub fn verifying_key(
+                &self,
+            ) -> PyResult<std::borrow::Cow<'static, [u8]>> {
+                Ok(std::borrow::Cow::Owned(
+                    self.key_share
+                        .1
+                        .verifying_key()
+                        .serialize()
+                        .map_err(py_err)?,
+                ))
+            }
+
+            /// Distributed key generation.
+            #[staticmethod]
+            pub fn dkg<'py>(
+                py: Python<'py>,
+                options: &Bound<'py, PyAny>,
+                party: &Bound<'py, PyAny>,
+                signer: Vec<u8>,
+                identifiers: Vec<u16>,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                let options: SessionOptions = from_py(options)?;
+                let party: PartyOptions = from_py(party)?;
+                let signer: SigningKey = into_signing_key(signer)?;
+                let verifier = signer.verifying_key().clone();
+                let participant =
+                    Participant::new(signer, verifier, party)
+                        .map_err(py_err)?;
+                let ids = into_identifiers(identifiers)?;
+
+                future_into_py(py, async move {
+                    let key_share = dkg(options, participant, ids)
+                        .await
+                        .map_err(py_client_err)?;
+
+                    let key_share: KeyShare =
+                        (&key_share).try_into().map_err(py_err)?;
+                    to_py_with_gil(&key_share)
+                })
+            }
+
+            /// Sign a message.
+            ///
+            /// The digest algorithm is applied to the message
+            /// before signing.
+            #[pyo3(signature = (
+                party, signer, identifiers, message, digest=None
+            ))]
+            pub fn sign<'py>(
+                &self,
+                py: Python<'py>,
+                party: &Bound<'py, PyAny>,
+                signer: Vec<u8>,
+                identifiers: Vec<u16>,
+                message: Vec<u8>,
+                digest: Option<String>,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                let options = self.options.clone();
+                let party: PartyOptions = from_py(party)?;
+                let signer: SigningKey = into_signing_key(signer)?;
+                let verifier = signer.verifying_key().clone();
+                let participant =
+                    Participant::new(signer, verifier, party)
+                        .map_err(py_err)?;
+                let ids = into_identifiers(identifiers)?;
+                let digest: polysig_driver::digest::DigestAlgorithm =
+                    digest
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .map_err(py_err)?
+                        .unwrap_or_default();
+                let message = digest.digest(&message);
+
+                let key_share = self.key_share.clone();
+                future_into_py(py, async move {
+                    let signature = sign(
+                        options,
+                        participant,
+                        ids,
+                        key_share,
+                        message,
+                    )
+                    .await
+                    .map_err(py_client_err)?;
+                    to_py_with_gil(&signature)
+                })
+            }
+        }
+
+        /// Convert party numbers into identifiers.
+        fn into_identifiers(
+            identifiers: Vec<u16>,
+        ) -> PyResult<Vec<Identifier>> {
+            let mut ids = Vec::with_capacity(identifiers.len());
+            for id in identifiers {
+                ids.push(id.try_into().map_err(py_err)?);
+            }
+            Ok(ids)
+        }
+    };
+}
+
+pub(crate) use frost_impl;
@@ -0,0 +1,29 @@
+//! FROST protocol implementations.
+use pyo3::prelude::*;
+
+pub(crate) mod core;
+
+#[cfg(feature = "frost-ed25519")]
+pub mod ed25519;
+
+#[cfg(feature = "frost-ristretto255")]
+pub mod ristretto255;
+
+#[cfg(feature = "frost-secp256k1")]
+pub mod secp256k1;
+
+#[cfg(feature = "frost-secp256k1-tr")]
+pub mod secp256k1_tr;
+
+/// Register the FROST protocol classes.
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    #[cfg(feature = "frost-ed25519")]
+    m.add_class::<ed25519::FrostEd25519Protocol>()?;
+    #[cfg(feature = "frost-ristretto255")]
+    m.add_class::<ristretto255::FrostRistretto255Protocol>()?;
+    #[cfg(feature = "frost-secp256k1")]
+    m.add_class::<secp256k1::FrostSecp256K1Protocol>()?;
+    #[cfg(feature = "frost-secp256k1-tr")]
+    m.add_class::<secp256k1_tr::FrostSecp256K1TrProtocol>()?;
+    Ok(())
+}
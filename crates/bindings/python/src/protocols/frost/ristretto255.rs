@@ -0,0 +1,29 @@
+//! FROST Ristretto255 protocol.
+use crate::{
+    convert::{from_py, to_py_with_gil},
+    error::{py_client_err, py_err},
+};
+use polysig_client::{
+    frost::ristretto255::{dkg, sign},
+    SessionOptions,
+};
+use polysig_driver::{
+    frost::ristretto255::{
+        self as frost, Identifier, Participant, PartyOptions,
+        SigningKey,
+    },
+    KeyShare,
+};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+
+/// Threshold key share for FROST Ristretto255.
+pub type ThresholdKeyShare = frost::KeyShare;
+
+fn into_signing_key(value: Vec<u8>) -> PyResult<SigningKey> {
+    let bytes: [u8; 32] =
+        value.as_slice().try_into().map_err(py_err)?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+super::core::frost_impl!(FrostRistretto255Protocol);
@@ -0,0 +1,27 @@
+//! FROST Secp256k1 Taproot protocol.
+use crate::{
+    convert::{from_py, to_py_with_gil},
+    error::{py_client_err, py_err},
+};
+use polysig_client::{
+    frost::secp256k1_tr::{dkg, sign},
+    SessionOptions,
+};
+use polysig_driver::{
+    frost::secp256k1_tr::{
+        self as frost, Identifier, Participant, PartyOptions,
+        SigningKey,
+    },
+    KeyShare,
+};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+
+/// Threshold key share for FROST Secp256k1 Taproot.
+pub type ThresholdKeyShare = frost::KeyShare;
+
+fn into_signing_key(value: Vec<u8>) -> PyResult<SigningKey> {
+    SigningKey::from_bytes(&value).map_err(py_err)
+}
+
+super::core::frost_impl!(FrostSecp256K1TrProtocol);
@@ -0,0 +1,107 @@
+//! Bindings for meeting points.
+use crate::{
+    convert::{from_py, to_py_with_gil},
+    error::{py_client_err, py_err},
+};
+use polysig_client::meeting;
+use polysig_protocol::{MeetingId, PublicKeys, UserId};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+
+/// Create a meeting room used to exchange public keys.
+///
+/// Resolves to the meeting identifier which the initiator
+/// shares with the other participants.
+#[pyfunction]
+pub fn create_meeting(
+    py: Python<'_>,
+    url: String,
+    identifiers: Vec<Vec<u8>>,
+    initiator: Vec<u8>,
+) -> PyResult<Bound<'_, PyAny>> {
+    let identifiers = parse_user_identifiers(identifiers)?;
+    let initiator = parse_user_id(initiator)?;
+    future_into_py(py, async move {
+        let meeting_id =
+            meeting::create(&url, identifiers, initiator)
+                .await
+                .map_err(py_client_err)?;
+        Ok(meeting_id.to_string())
+    })
+}
+
+/// Join a meeting room used to exchange public keys.
+///
+/// Resolves once every participant has joined to the
+/// public keys of all the participants.
+#[pyfunction]
+pub fn join_meeting<'py>(
+    py: Python<'py>,
+    url: String,
+    meeting_id: String,
+    user_id: Vec<u8>,
+    data: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let meeting_id: MeetingId = meeting_id.parse().map_err(py_err)?;
+    let user_id = parse_user_id(user_id)?;
+    let data: PublicKeys = from_py(data)?;
+    future_into_py(py, async move {
+        let results = meeting::join(&url, meeting_id, user_id, data)
+            .await
+            .map_err(py_client_err)?;
+        to_py_with_gil(&results)
+    })
+}
+
+/// Create and join meeting rooms.
+#[pyclass(module = "polysig")]
+pub struct MeetingRoom {
+    url: String,
+}
+
+#[pymethods]
+impl MeetingRoom {
+    /// Create a meeting room.
+    #[new]
+    pub fn new(url: String) -> MeetingRoom {
+        Self { url }
+    }
+
+    /// Create a meeting room used to exchange public keys.
+    pub fn create<'py>(
+        &self,
+        py: Python<'py>,
+        identifiers: Vec<Vec<u8>>,
+        initiator: Vec<u8>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        create_meeting(py, self.url.clone(), identifiers, initiator)
+    }
+
+    /// Join a meeting room used to exchange public keys.
+    pub fn join<'py>(
+        &self,
+        py: Python<'py>,
+        meeting_id: String,
+        user_id: Vec<u8>,
+        data: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        join_meeting(py, self.url.clone(), meeting_id, user_id, data)
+    }
+}
+
+/// Parse a collection of user identifiers.
+fn parse_user_identifiers(
+    identifiers: Vec<Vec<u8>>,
+) -> PyResult<Vec<UserId>> {
+    let mut ids = Vec::with_capacity(identifiers.len());
+    for id in identifiers {
+        ids.push(parse_user_id(id)?);
+    }
+    Ok(ids)
+}
+
+/// Parse a single user identifier (SHA256 checksum).
+fn parse_user_id(id: Vec<u8>) -> PyResult<UserId> {
+    let id: [u8; 32] = id.as_slice().try_into().map_err(py_err)?;
+    Ok(id.into())
+}
@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+
+#[cfg(feature = "cggmp")]
+pub mod cggmp;
+
+#[cfg(feature = "frost")]
+pub mod frost;
+
+pub mod meeting;
+
+/// Register the protocol classes and functions.
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    #[cfg(feature = "cggmp")]
+    m.add_class::<cggmp::CggmpProtocol>()?;
+    #[cfg(feature = "frost")]
+    frost::register(m)?;
+    m.add_class::<meeting::MeetingRoom>()?;
+    m.add_function(wrap_pyfunction!(meeting::create_meeting, m)?)?;
+    m.add_function(wrap_pyfunction!(meeting::join_meeting, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! ECDSA signatures compatible with Ethereum.
+use crate::{
+    convert::{from_py, to_py},
+    error::py_err,
+};
+use polysig_driver::{
+    recoverable_signature::RecoverableSignature,
+    signers::ecdsa::{self, Signature},
+};
+use pyo3::prelude::*;
+use std::borrow::Cow;
+
+/// Signer for ECDSA.
+#[pyclass(module = "polysig")]
+pub struct EcdsaSigner {
+    inner: ecdsa::EcdsaSigner<'static>,
+}
+
+#[pymethods]
+impl EcdsaSigner {
+    /// Create a new signer.
+    #[new]
+    pub fn new(signing_key: &[u8]) -> PyResult<EcdsaSigner> {
+        let signing_key = ecdsa::EcdsaSigner::from_slice(signing_key)
+            .map_err(py_err)?;
+        Ok(Self {
+            inner: ecdsa::EcdsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
+        })
+    }
+
+    /// Generate a random signing key.
+    #[staticmethod]
+    pub fn random() -> Cow<'static, [u8]> {
+        Cow::Owned(
+            ecdsa::EcdsaSigner::random()
+                .to_bytes()
+                .as_slice()
+                .to_vec(),
+        )
+    }
+
+    /// Sign the given message, hashing it with the curve’s
+    /// default digest function, and returning a signature
+    /// and recovery ID.
+    pub fn sign_recoverable(
+        &self,
+        py: Python<'_>,
+        message: &[u8],
+    ) -> PyResult<PyObject> {
+        let result =
+            self.inner.sign_recoverable(message).map_err(py_err)?;
+        let signature: RecoverableSignature = result.into();
+        to_py(py, &signature)
+    }
+
+    /// Sign the given message prehash, returning a signature
+    /// and recovery ID.
+    pub fn sign_prehash_recoverable(
+        &self,
+        py: Python<'_>,
+        message: &[u8],
+    ) -> PyResult<PyObject> {
+        let result = self
+            .inner
+            .sign_prehash_recoverable(message)
+            .map_err(py_err)?;
+        let signature: RecoverableSignature = result.into();
+        to_py(py, &signature)
+    }
+
+    /// Sign a message.
+    pub fn sign(&self, message: &[u8]) -> Cow<'static, [u8]> {
+        let result = self.inner.sign(message);
+        Cow::Owned(result.to_bytes().as_slice().to_vec())
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(
+            self.inner.verifying_key().to_sec1_bytes().to_vec(),
+        )
+    }
+
+    /// Verify a message.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> PyResult<()> {
+        let signature =
+            Signature::from_slice(signature).map_err(py_err)?;
+        self.inner.verify(message, &signature).map_err(py_err)
+    }
+
+    /// Verify a prehash.
+    pub fn verify_prehash(
+        &self,
+        prehash: &[u8],
+        signature: &[u8],
+    ) -> PyResult<()> {
+        let signature =
+            Signature::from_slice(signature).map_err(py_err)?;
+        self.inner
+            .verify_prehash(prehash, &signature)
+            .map_err(py_err)
+    }
+
+    /// Sign a message for Ethereum first hashing the message
+    /// with the Keccak256 digest.
+    pub fn sign_eth(
+        &self,
+        py: Python<'_>,
+        message: &[u8],
+    ) -> PyResult<PyObject> {
+        let result = self.inner.sign_eth(message).map_err(py_err)?;
+        let signature: RecoverableSignature = result.into();
+        to_py(py, &signature)
+    }
+
+    /// Recover the public key from a signature and recovery identifier.
+    #[staticmethod]
+    pub fn recover(
+        message: &[u8],
+        signature: &Bound<'_, PyAny>,
+    ) -> PyResult<Cow<'static, [u8]>> {
+        let signature: RecoverableSignature = from_py(signature)?;
+        let verifying_key =
+            ecdsa::EcdsaSigner::recover(message, signature)
+                .map_err(py_err)?;
+        Ok(Cow::Owned(verifying_key.to_sec1_bytes().to_vec()))
+    }
+
+    /// Compute the Keccak256 digest of a message.
+    #[staticmethod]
+    pub fn keccak256(message: &[u8]) -> Cow<'static, [u8]> {
+        use polysig_driver::sha3::{Digest, Keccak256};
+        let digest = Keccak256::new_with_prefix(message);
+        let hash = digest.finalize();
+        Cow::Owned(hash.to_vec())
+    }
+
+    /// Compute the Keccak256 digest of a structured message.
+    ///
+    /// The value is encoded as canonical JSON (RFC 8785) so
+    /// the digest is the same regardless of key order.
+    #[staticmethod]
+    pub fn keccak256_json(
+        value: &Bound<'_, PyAny>,
+    ) -> PyResult<Cow<'static, [u8]>> {
+        let value: polysig_protocol::serde_json::Value =
+            from_py(value)?;
+        let message = polysig_protocol::canonical::to_vec(&value)
+            .map_err(py_err)?;
+        Ok(Self::keccak256(&message))
+    }
+}
@@ -0,0 +1,61 @@
+//! EdDSA signatures compatible with Solana.
+use crate::error::py_err;
+use polysig_driver::signers::eddsa::{self, Signature};
+use pyo3::prelude::*;
+use std::borrow::Cow;
+
+/// Signer for EdDSA.
+#[pyclass(module = "polysig")]
+pub struct EddsaSigner {
+    inner: eddsa::EddsaSigner<'static>,
+}
+
+#[pymethods]
+impl EddsaSigner {
+    /// Create a new signer.
+    #[new]
+    pub fn new(signing_key: &[u8]) -> PyResult<EddsaSigner> {
+        let signing_key: [u8; 32] =
+            signing_key.try_into().map_err(py_err)?;
+        let signing_key =
+            eddsa::EddsaSigner::from_bytes(&signing_key);
+        Ok(Self {
+            inner: eddsa::EddsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
+        })
+    }
+
+    /// Generate a random signing key.
+    #[staticmethod]
+    pub fn random() -> Cow<'static, [u8]> {
+        Cow::Owned(
+            eddsa::EddsaSigner::random()
+                .to_bytes()
+                .as_slice()
+                .to_vec(),
+        )
+    }
+
+    /// Sign a message.
+    pub fn sign(&self, message: &[u8]) -> Cow<'static, [u8]> {
+        let result = self.inner.sign(message);
+        Cow::Owned(result.to_bytes().as_slice().to_vec())
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.inner.verifying_key().to_bytes().to_vec())
+    }
+
+    /// Verify a message.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> PyResult<()> {
+        let signature: Signature =
+            signature.try_into().map_err(py_err)?;
+        self.inner.verify(message, &signature).map_err(py_err)
+    }
+}
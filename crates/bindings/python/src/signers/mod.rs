@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+
+#[cfg(feature = "ecdsa")]
+pub mod ecdsa;
+
+#[cfg(feature = "eddsa")]
+pub mod eddsa;
+
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
+
+/// Register the signer classes.
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    #[cfg(feature = "ecdsa")]
+    m.add_class::<ecdsa::EcdsaSigner>()?;
+    #[cfg(feature = "eddsa")]
+    m.add_class::<eddsa::EddsaSigner>()?;
+    #[cfg(feature = "schnorr")]
+    m.add_class::<schnorr::SchnorrSigner>()?;
+    Ok(())
+}
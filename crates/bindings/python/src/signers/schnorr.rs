@@ -0,0 +1,71 @@
+//! Taproot Schnorr signatures compatible with Bitcoin (BIP-340).
+use crate::error::py_err;
+use polysig_driver::signers::schnorr::{self, Signature};
+use pyo3::prelude::*;
+use std::borrow::Cow;
+
+/// Signer for Schnorr.
+#[pyclass(module = "polysig")]
+pub struct SchnorrSigner {
+    inner: schnorr::SchnorrSigner<'static>,
+}
+
+#[pymethods]
+impl SchnorrSigner {
+    /// Create a new signer.
+    #[new]
+    pub fn new(signing_key: &[u8]) -> PyResult<SchnorrSigner> {
+        let signing_key =
+            schnorr::SchnorrSigner::from_slice(signing_key)
+                .map_err(py_err)?;
+        Ok(Self {
+            inner: schnorr::SchnorrSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
+        })
+    }
+
+    /// Generate a random signing key.
+    #[staticmethod]
+    pub fn random() -> Cow<'static, [u8]> {
+        Cow::Owned(
+            schnorr::SchnorrSigner::random()
+                .to_bytes()
+                .as_slice()
+                .to_vec(),
+        )
+    }
+
+    /// Sign a message.
+    pub fn sign(&self, message: &[u8]) -> Cow<'static, [u8]> {
+        let result = self.inner.sign(message);
+        Cow::Owned(result.to_bytes().as_slice().to_vec())
+    }
+
+    /// Attempt to sign the given message digest, returning a
+    /// digital signature on success, or an error if something went wrong.
+    pub fn sign_prehash(
+        &self,
+        prehash: &[u8],
+    ) -> PyResult<Cow<'static, [u8]>> {
+        let result =
+            self.inner.sign_prehash(prehash).map_err(py_err)?;
+        Ok(Cow::Owned(result.to_bytes().as_slice().to_vec()))
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.inner.verifying_key().to_bytes().to_vec())
+    }
+
+    /// Verify a message.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> PyResult<()> {
+        let signature: Signature =
+            signature.try_into().map_err(py_err)?;
+        self.inner.verify(message, &signature).map_err(py_err)
+    }
+}
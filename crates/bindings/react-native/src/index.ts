@@ -0,0 +1,663 @@
+// React Native bindings for the polysig library.
+//
+// Wraps the UniFFI bindings (generated into `./generated` by
+// uniffi-bindgen-react-native) with an API matching the
+// webassembly bindings so wallets can share code between
+// the browser and React Native.
+//
+// Ceremony functions accept the same trailing `progress` and
+// `signal` arguments as the webassembly bindings; an
+// `AbortSignal` cancels the ceremony but progress events are
+// not emitted by the native client so the callback is
+// currently never invoked.
+import * as ffi from "./generated/polysig_uniffi";
+import { normalizeServerUrl } from "./transport";
+
+export { normalizeServerUrl } from "./transport";
+
+/** Bytes as an array of numbers, typed array or buffer. */
+export type Bytes = ArrayLike<number> | ArrayBuffer;
+
+/** Keypair for the noise transport. */
+export type Keypair = {
+  private: Bytes;
+  public: Bytes;
+  type: string;
+};
+
+/** Options for the relay server connection. */
+export type ServerOptions = {
+  serverUrl: string;
+  serverPublicKey: Bytes;
+  pattern?: string;
+};
+
+/** Parameters for key generation. */
+export type Parameters = {
+  parties: number;
+  threshold: number;
+};
+
+/** Options for creating or joining a session. */
+export type SessionOptions = {
+  keypair: Keypair;
+  server: ServerOptions;
+  parameters: Parameters;
+  signingContext?: SigningContext;
+  /** Timeout in seconds for each round of the ceremony. */
+  roundTimeout?: number;
+  /** Keepalive for the connection to the server. */
+  keepalive?: KeepaliveOptions;
+};
+
+/** Keepalive settings in seconds. */
+export type KeepaliveOptions = {
+  pingInterval: number;
+  idleTimeout: number;
+};
+
+/** Context for a signature checked against the key share policy. */
+export type SigningContext = {
+  derivationPath?: string;
+  chain?: string;
+};
+
+/** Options for a party participating in a protocol. */
+export type PartyOptions = {
+  publicKey: Bytes;
+  participants: Bytes[];
+  isInitiator: boolean;
+  verifiers: Bytes[];
+  partyIndex?: number;
+};
+
+/** Key share encoded for storage. */
+export type KeyShare = {
+  version: number;
+  contents: string;
+  policy?: KeySharePolicy;
+};
+
+/** Rules enforced before signing with a key share. */
+export type KeySharePolicy = {
+  /** Seconds since the UNIX epoch. */
+  expiresAt?: bigint;
+  derivationPaths: string[];
+  chains: string[];
+};
+
+/** Signature with a recovery identifier. */
+export type RecoverableSignature = {
+  bytes: number[];
+  recoveryId: number;
+};
+
+/** Public keys exchanged in a meeting room. */
+export type PublicKeys = {
+  publicKey: Bytes;
+  verifyingKey: Bytes;
+  associatedData?: unknown;
+};
+
+/** Capabilities compiled into a build of the library. */
+export type Capabilities = {
+  version: string;
+  encodingVersions: number[];
+  payloadEncodings: string[];
+  protocols: string[];
+  signers: string[];
+  curves: CurveBackend[];
+  features: string[];
+};
+
+/** Curve and the library that implements it. */
+export type CurveBackend = {
+  curve: string;
+  backend: string;
+};
+
+/** Callback for progress events (not yet emitted). */
+export type ProgressCallback = (event: unknown) => void;
+
+function toBuffer(value: Bytes): ArrayBuffer {
+  if (value instanceof ArrayBuffer) {
+    return value;
+  }
+  return Uint8Array.from(value).buffer;
+}
+
+function toArray(value: ArrayBuffer): number[] {
+  return Array.from(new Uint8Array(value));
+}
+
+function toHex(value: ArrayBuffer): string {
+  return toArray(value)
+    .map((byte) => byte.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+function fromHex(hex: string): ArrayBuffer {
+  const bytes = hex.match(/.{1,2}/g) ?? [];
+  return toBuffer(bytes.map((byte) => parseInt(byte, 16)));
+}
+
+// Cancel the ceremony when the signal is aborted.
+function cancellation(
+  signal?: AbortSignal,
+): ffi.CancellationToken | undefined {
+  if (!signal) {
+    return undefined;
+  }
+  const token = new ffi.CancellationToken();
+  if (signal.aborted) {
+    token.cancel();
+  } else {
+    signal.addEventListener("abort", () => token.cancel(), {
+      once: true,
+    });
+  }
+  return token;
+}
+
+function sessionOptions(
+  options: SessionOptions,
+  signal?: AbortSignal,
+): ffi.SessionOptions {
+  return {
+    keypair: {
+      private: toBuffer(options.keypair.private),
+      public: toBuffer(options.keypair.public),
+      keyType: options.keypair.type,
+    },
+    server: {
+      serverUrl: normalizeServerUrl(options.server.serverUrl),
+      serverPublicKey: toBuffer(options.server.serverPublicKey),
+      pattern: options.server.pattern,
+    },
+    parameters: options.parameters,
+    cancel: cancellation(signal),
+    signingContext: options.signingContext,
+    roundTimeout: options.roundTimeout,
+    keepalive: options.keepalive,
+  };
+}
+
+function partyOptions(party: PartyOptions): ffi.PartyOptions {
+  return {
+    publicKey: toBuffer(party.publicKey),
+    participants: party.participants.map(toBuffer),
+    isInitiator: party.isInitiator,
+    verifiers: party.verifiers.map(toBuffer),
+  };
+}
+
+function recoverableSignature(
+  signature: ffi.RecoverableSignature,
+): RecoverableSignature {
+  return {
+    bytes: toArray(signature.bytes),
+    recoveryId: signature.recoveryId,
+  };
+}
+
+/**
+ * CGGMP protocol.
+ *
+ * Ceremony functions accept an optional callback for progress
+ * events and an optional `AbortSignal` used to cancel the
+ * ceremony.
+ */
+export class CggmpProtocol {
+  #options: SessionOptions;
+  #keyShare: KeyShare;
+
+  /** Create a CGGMP protocol. */
+  constructor(options: SessionOptions, keyShare: KeyShare) {
+    this.#options = options;
+    this.#keyShare = keyShare;
+  }
+
+  /** Verifying key for this signer. */
+  verifyingKey(): Uint8Array {
+    return new Uint8Array(ffi.cggmpVerifyingKey(this.#keyShare));
+  }
+
+  /** Compute the Ethereum address for the verifying key. */
+  address(): string {
+    return ffi.cggmpAddress(this.#keyShare);
+  }
+
+  /** Distributed key generation. */
+  static async dkg(
+    options: SessionOptions,
+    party: PartyOptions,
+    sessionIdSeed: Bytes,
+    signer: Bytes,
+    _progress?: ProgressCallback,
+    signal?: AbortSignal,
+  ): Promise<KeyShare> {
+    return ffi.cggmpDkg(
+      sessionOptions(options, signal),
+      partyOptions(party),
+      toBuffer(sessionIdSeed),
+      toBuffer(signer),
+    );
+  }
+
+  /**
+   * Sign a hex-encoded message.
+   *
+   * Without a digest algorithm the message must be a 32 byte
+   * prehash otherwise the prehash is computed using the digest
+   * algorithm (sha256, sha256d, keccak256 or blake2b256).
+   */
+  async sign(
+    party: PartyOptions,
+    sessionIdSeed: Bytes,
+    signer: Bytes,
+    message: string,
+    _progress?: ProgressCallback,
+    signal?: AbortSignal,
+    digest?: string,
+  ): Promise<RecoverableSignature> {
+    return this.signBytes(
+      party,
+      sessionIdSeed,
+      signer,
+      fromHex(message),
+      _progress,
+      signal,
+      digest,
+    );
+  }
+
+  /**
+   * Sign a message supplied as bytes.
+   *
+   * Binary digests from hashing libraries can be signed
+   * without a hex round trip.
+   */
+  async signBytes(
+    party: PartyOptions,
+    sessionIdSeed: Bytes,
+    signer: Bytes,
+    message: Bytes,
+    _progress?: ProgressCallback,
+    signal?: AbortSignal,
+    digest?: string,
+  ): Promise<RecoverableSignature> {
+    const signature = await ffi.cggmpSign(
+      sessionOptions(this.#options, signal),
+      partyOptions(party),
+      toBuffer(sessionIdSeed),
+      toBuffer(signer),
+      this.#keyShare,
+      toBuffer(message),
+      digest,
+    );
+    return recoverableSignature(signature);
+  }
+
+  /** Reshare key shares. */
+  async reshare(
+    party: PartyOptions,
+    sessionIdSeed: Bytes,
+    signer: Bytes,
+    accountVerifyingKey: Bytes,
+    keyShare: KeyShare | null,
+    oldThreshold: number,
+    newThreshold: number,
+    _progress?: ProgressCallback,
+    signal?: AbortSignal,
+  ): Promise<KeyShare> {
+    return ffi.cggmpReshare(
+      sessionOptions(this.#options, signal),
+      partyOptions(party),
+      toBuffer(sessionIdSeed),
+      toBuffer(signer),
+      toBuffer(accountVerifyingKey),
+      keyShare ?? undefined,
+      oldThreshold,
+      newThreshold,
+    );
+  }
+
+  /** Generate a BIP32 derived child key. */
+  deriveBip32(derivationPath: string): KeyShare {
+    return ffi.cggmpDeriveBip32(this.#keyShare, derivationPath);
+  }
+
+  /** Generate an encryption keypair for the noise protocol. */
+  static generateKeypair(): Keypair {
+    const keypair = ffi.generateKeypair();
+    return {
+      private: toArray(keypair.private),
+      public: toArray(keypair.public),
+      type: keypair.keyType,
+    };
+  }
+}
+
+type FrostFunctions = {
+  dkg: (
+    options: ffi.SessionOptions,
+    party: ffi.PartyOptions,
+    signer: ArrayBuffer,
+    identifiers: number[],
+  ) => Promise<KeyShare>;
+  sign: (
+    options: ffi.SessionOptions,
+    party: ffi.PartyOptions,
+    signer: ArrayBuffer,
+    identifiers: number[],
+    keyShare: KeyShare,
+    message: ArrayBuffer,
+    digest: string | undefined,
+  ) => Promise<ArrayBuffer>;
+  verifyingKey: (keyShare: KeyShare) => ArrayBuffer;
+};
+
+// Create a FROST protocol class for a curve; refreshing and
+// repairing key shares are not available on React Native.
+function frostProtocol(functions: FrostFunctions) {
+  return class FrostProtocol {
+    #options: SessionOptions;
+    #keyShare: KeyShare;
+
+    /** Create a FROST protocol. */
+    constructor(options: SessionOptions, keyShare: KeyShare) {
+      this.#options = options;
+      this.#keyShare = keyShare;
+    }
+
+    /** Verifying key for the group. */
+    verifyingKey(): Uint8Array {
+      return new Uint8Array(functions.verifyingKey(this.#keyShare));
+    }
+
+    /** Distributed key generation. */
+    static async dkg(
+      options: SessionOptions,
+      party: PartyOptions,
+      signer: Bytes,
+      identifiers: number[],
+      _progress?: ProgressCallback,
+      signal?: AbortSignal,
+    ): Promise<KeyShare> {
+      return functions.dkg(
+        sessionOptions(options, signal),
+        partyOptions(party),
+        toBuffer(signer),
+        identifiers,
+      );
+    }
+
+    /**
+     * Sign a message; resolves to the hex-encoded signature.
+     *
+     * The optional digest algorithm is applied to the message
+     * before signing.
+     */
+    async sign(
+      party: PartyOptions,
+      signer: Bytes,
+      identifiers: number[],
+      message: Bytes,
+      _progress?: ProgressCallback,
+      signal?: AbortSignal,
+      digest?: string,
+    ): Promise<string> {
+      const signature = await functions.sign(
+        sessionOptions(this.#options, signal),
+        partyOptions(party),
+        toBuffer(signer),
+        identifiers,
+        this.#keyShare,
+        toBuffer(message),
+        digest,
+      );
+      return toHex(signature);
+    }
+  };
+}
+
+/** FROST Ed25519 protocol. */
+export const FrostEd25519Protocol = frostProtocol({
+  dkg: ffi.frostEd25519Dkg,
+  sign: ffi.frostEd25519Sign,
+  verifyingKey: ffi.frostEd25519VerifyingKey,
+});
+
+/** FROST Ristretto255 protocol. */
+export const FrostRistretto255Protocol = frostProtocol({
+  dkg: ffi.frostRistretto255Dkg,
+  sign: ffi.frostRistretto255Sign,
+  verifyingKey: ffi.frostRistretto255VerifyingKey,
+});
+
+/** FROST Secp256k1 protocol. */
+export const FrostSecp256K1Protocol = frostProtocol({
+  dkg: ffi.frostSecp256k1Dkg,
+  sign: ffi.frostSecp256k1Sign,
+  verifyingKey: ffi.frostSecp256k1VerifyingKey,
+});
+
+/** FROST Secp256k1 Taproot protocol. */
+export const FrostSecp256K1TrProtocol = frostProtocol({
+  dkg: ffi.frostSecp256k1TrDkg,
+  sign: ffi.frostSecp256k1TrSign,
+  verifyingKey: ffi.frostSecp256k1TrVerifyingKey,
+});
+
+/** Create and join meeting rooms. */
+export class MeetingRoom {
+  #url: string;
+
+  /** Create a meeting room. */
+  constructor(url: string) {
+    this.#url = normalizeServerUrl(url);
+  }
+
+  /** Create a meeting room used to exchange public keys. */
+  async create(identifiers: Bytes[], initiator: Bytes): Promise<string> {
+    return ffi.createMeeting(
+      this.#url,
+      identifiers.map(toBuffer),
+      toBuffer(initiator),
+    );
+  }
+
+  /** Join a meeting room used to exchange public keys. */
+  async join(
+    meetingId: string,
+    userId: Bytes,
+    data: PublicKeys,
+  ): Promise<[number[], PublicKeys][]> {
+    const results = await ffi.joinMeeting(
+      this.#url,
+      meetingId,
+      toBuffer(userId),
+      {
+        publicKey: toBuffer(data.publicKey),
+        verifyingKey: toBuffer(data.verifyingKey),
+        associatedData:
+          data.associatedData === undefined
+            ? undefined
+            : JSON.stringify(data.associatedData),
+      },
+    );
+    return results.map((item) => [
+      toArray(item.userId),
+      {
+        publicKey: toArray(item.data.publicKey),
+        verifyingKey: toArray(item.data.verifyingKey),
+        associatedData:
+          item.data.associatedData === undefined
+            ? undefined
+            : JSON.parse(item.data.associatedData),
+      },
+    ]);
+  }
+}
+
+/** Signer for ECDSA. */
+export class EcdsaSigner {
+  #inner: ffi.EcdsaSigner;
+
+  /** Create a new signer. */
+  constructor(signingKey: Bytes) {
+    this.#inner = new ffi.EcdsaSigner(toBuffer(signingKey));
+  }
+
+  /** Generate a random signing key. */
+  static random(): Uint8Array {
+    return new Uint8Array(ffi.ecdsaRandomSigningKey());
+  }
+
+  /** Sign a message returning a signature and recovery ID. */
+  signRecoverable(message: Bytes): RecoverableSignature {
+    return recoverableSignature(
+      this.#inner.signRecoverable(toBuffer(message)),
+    );
+  }
+
+  /** Sign a message prehash returning a signature and recovery ID. */
+  signPrehashRecoverable(message: Bytes): RecoverableSignature {
+    return recoverableSignature(
+      this.#inner.signPrehashRecoverable(toBuffer(message)),
+    );
+  }
+
+  /** Sign a message. */
+  sign(message: Bytes): Uint8Array {
+    return new Uint8Array(this.#inner.sign(toBuffer(message)));
+  }
+
+  /** Verifying key for this signer. */
+  verifyingKey(): Uint8Array {
+    return new Uint8Array(this.#inner.verifyingKey());
+  }
+
+  /** Compute the Ethereum address for the verifying key. */
+  address(): string {
+    return this.#inner.address();
+  }
+
+  /** Verify a message. */
+  verify(message: Bytes, signature: Bytes): void {
+    this.#inner.verify(toBuffer(message), toBuffer(signature));
+  }
+
+  /** Verify a prehash. */
+  verifyPrehash(prehash: Bytes, signature: Bytes): void {
+    this.#inner.verifyPrehash(toBuffer(prehash), toBuffer(signature));
+  }
+
+  /** Sign a message for Ethereum hashing with Keccak256. */
+  signEth(message: Bytes): RecoverableSignature {
+    return recoverableSignature(this.#inner.signEth(toBuffer(message)));
+  }
+
+  /** Recover the public key from a signature. */
+  static recover(
+    message: Bytes,
+    signature: RecoverableSignature,
+  ): Uint8Array {
+    return new Uint8Array(
+      ffi.ecdsaRecover(toBuffer(message), {
+        bytes: toBuffer(signature.bytes),
+        recoveryId: signature.recoveryId,
+      }),
+    );
+  }
+
+  /** Compute the Keccak256 digest of a message. */
+  static keccak256(message: Bytes): Uint8Array {
+    return new Uint8Array(ffi.keccak256(toBuffer(message)));
+  }
+
+  /**
+   * Compute the Keccak256 digest of a structured message.
+   *
+   * The value is encoded as canonical JSON (RFC 8785) so the
+   * digest is the same regardless of key order.
+   */
+  static keccak256Json(value: unknown): Uint8Array {
+    return new Uint8Array(ffi.keccak256Json(JSON.stringify(value)));
+  }
+}
+
+/** Signer for EdDSA. */
+export class EddsaSigner {
+  #inner: ffi.EddsaSigner;
+
+  /** Create a new signer. */
+  constructor(signingKey: Bytes) {
+    this.#inner = new ffi.EddsaSigner(toBuffer(signingKey));
+  }
+
+  /** Generate a random signing key. */
+  static random(): Uint8Array {
+    return new Uint8Array(ffi.eddsaRandomSigningKey());
+  }
+
+  /** Sign a message. */
+  sign(message: Bytes): Uint8Array {
+    return new Uint8Array(this.#inner.sign(toBuffer(message)));
+  }
+
+  /** Verifying key for this signer. */
+  verifyingKey(): Uint8Array {
+    return new Uint8Array(this.#inner.verifyingKey());
+  }
+
+  /** Verify a message. */
+  verify(message: Bytes, signature: Bytes): void {
+    this.#inner.verify(toBuffer(message), toBuffer(signature));
+  }
+}
+
+/** Signer for Schnorr. */
+export class SchnorrSigner {
+  #inner: ffi.SchnorrSigner;
+
+  /** Create a new signer. */
+  constructor(signingKey: Bytes) {
+    this.#inner = new ffi.SchnorrSigner(toBuffer(signingKey));
+  }
+
+  /** Generate a random signing key. */
+  static random(): Uint8Array {
+    return new Uint8Array(ffi.schnorrRandomSigningKey());
+  }
+
+  /** Sign a message. */
+  sign(message: Bytes): Uint8Array {
+    return new Uint8Array(this.#inner.sign(toBuffer(message)));
+  }
+
+  /** Sign a message digest. */
+  sign_prehash(prehash: Bytes): number[] {
+    return toArray(this.#inner.signPrehash(toBuffer(prehash)));
+  }
+
+  /** Verifying key for this signer. */
+  verifyingKey(): Uint8Array {
+    return new Uint8Array(this.#inner.verifyingKey());
+  }
+
+  /** Verify a message. */
+  verify(message: Bytes, signature: Bytes): void {
+    this.#inner.verify(toBuffer(message), toBuffer(signature));
+  }
+}
+
+/**
+ * Capabilities compiled into this build.
+ *
+ * Exchange the capabilities with the other participants to
+ * check they run compatible builds before starting a ceremony.
+ */
+export function capabilities(): Capabilities {
+  return ffi.capabilities();
+}
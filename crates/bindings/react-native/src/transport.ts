@@ -0,0 +1,37 @@
+// Websocket transport differences between the browser and
+// the native client used on iOS and Android.
+import { Platform } from "react-native";
+
+// Loopback hosts which the Android emulator exposes on a
+// separate address.
+const LOOPBACK = /^(wss?:\/\/)(localhost|127\.0\.0\.1)(?=[:/]|$)/i;
+
+// Address of the host machine from the Android emulator.
+const ANDROID_EMULATOR_HOST = "10.0.2.2";
+
+/**
+ * Normalize a relay or meeting server URL for the native client.
+ *
+ * Browsers accept `http:` and `https:` URLs for websockets but
+ * the native client only accepts `ws:` and `wss:` so the scheme
+ * is converted. On Android loopback hosts are rewritten to the
+ * address of the host machine as seen from the emulator.
+ *
+ * Parsing is done with regular expressions as the `URL`
+ * implementation in React Native does not support `hostname`.
+ */
+export function normalizeServerUrl(url: string): string {
+  let result = url.replace(/^http(s?):\/\//i, "ws$1://");
+  if (Platform.OS === "android" && isEmulatorHost()) {
+    result = result.replace(LOOPBACK, `$1${ANDROID_EMULATOR_HOST}`);
+  }
+  return result;
+}
+
+// Loopback hosts are only rewritten on the emulator, on a
+// device they refer to the device itself.
+function isEmulatorHost(): boolean {
+  const constants = Platform.constants as { Fingerprint?: string };
+  const fingerprint = constants.Fingerprint ?? "";
+  return /generic|emulator|sdk_gphone/i.test(fingerprint);
+}
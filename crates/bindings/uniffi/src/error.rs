@@ -0,0 +1,70 @@
+use polysig_protocol::ErrorKind;
+use std::fmt;
+
+/// Error raised in Swift and Kotlin.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum PolysigError {
+    /// Ceremony was cancelled.
+    #[error("ceremony was cancelled")]
+    Cancelled,
+
+    /// Error generated by the library.
+    #[error("{message}")]
+    Generic {
+        /// Error message.
+        message: String,
+        /// Category of the error, eg: `transport`.
+        kind: String,
+        /// Whether the operation may succeed if retried.
+        retryable: bool,
+    },
+}
+
+impl PolysigError {
+    /// Create a generic error from an error message.
+    pub(crate) fn other(e: impl fmt::Display) -> Self {
+        Self::classified(e, ErrorKind::Protocol, false)
+    }
+
+    /// Create a generic error with the classification
+    /// of the underlying error.
+    fn classified(
+        e: impl fmt::Display,
+        kind: ErrorKind,
+        retryable: bool,
+    ) -> Self {
+        Self::Generic {
+            message: e.to_string(),
+            kind: kind.to_string(),
+            retryable,
+        }
+    }
+}
+
+impl From<polysig_driver::Error> for PolysigError {
+    fn from(value: polysig_driver::Error) -> Self {
+        let (kind, retryable) = (value.kind(), value.is_retryable());
+        Self::classified(value, kind, retryable)
+    }
+}
+
+impl From<polysig_protocol::Error> for PolysigError {
+    fn from(value: polysig_protocol::Error) -> Self {
+        let (kind, retryable) = (value.kind(), value.is_retryable());
+        Self::classified(value, kind, retryable)
+    }
+}
+
+#[cfg(feature = "protocol")]
+impl From<polysig_client::Error> for PolysigError {
+    fn from(value: polysig_client::Error) -> Self {
+        match value {
+            polysig_client::Error::Cancelled => Self::Cancelled,
+            _ => {
+                let (kind, retryable) =
+                    (value.kind(), value.is_retryable());
+                Self::classified(value, kind, retryable)
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Swift and Kotlin bindings for the polysig library.
+//!
+//! Generate the foreign language sources with the
+//! `uniffi-bindgen` binary (requires the `cli` feature):
+//!
+//! ```text
+//! cargo run --features cli --bin uniffi-bindgen generate \
+//!   --library target/release/libpolysig_uniffi.so \
+//!   --language swift --out-dir out
+//! ```
+//!
+//! Ceremony functions are exported as async functions
+//! driven by a tokio runtime.
+#![deny(missing_docs)]
+
+uniffi::setup_scaffolding!();
+
+mod capabilities;
+mod error;
+
+pub use capabilities::{capabilities, Capabilities, CurveBackend};
+pub use error::PolysigError;
+
+#[cfg(any(feature = "ecdsa", feature = "cggmp"))]
+use polysig_driver::recoverable_signature;
+
+/// Threshold signature protocols.
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub mod protocols;
+
+/// Single party signers.
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+pub mod signers;
+
+/// Signature with a recovery identifier.
+#[cfg(any(feature = "ecdsa", feature = "cggmp"))]
+#[derive(Debug, uniffi::Record)]
+pub struct RecoverableSignature {
+    /// Signature bytes.
+    pub bytes: Vec<u8>,
+    /// Recovery identifier.
+    pub recovery_id: u8,
+}
+
+#[cfg(any(feature = "ecdsa", feature = "cggmp"))]
+impl From<recoverable_signature::RecoverableSignature>
+    for RecoverableSignature
+{
+    fn from(
+        value: recoverable_signature::RecoverableSignature,
+    ) -> Self {
+        Self {
+            bytes: value.bytes,
+            recovery_id: value.recovery_id,
+        }
+    }
+}
+
+#[cfg(any(feature = "ecdsa", feature = "cggmp"))]
+impl From<RecoverableSignature>
+    for recoverable_signature::RecoverableSignature
+{
+    fn from(value: RecoverableSignature) -> Self {
+        Self {
+            bytes: value.bytes,
+            recovery_id: value.recovery_id,
+        }
+    }
+}
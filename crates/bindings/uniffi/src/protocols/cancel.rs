@@ -0,0 +1,44 @@
+//! Cancel ceremonies.
+/// Token used to cancel a ceremony.
+///
+/// Assign the token to the session options and call
+/// `cancel()` to abort the ceremony; the ceremony function
+/// then fails with a cancelled error.
+#[derive(uniffi::Object)]
+pub struct CancellationToken {
+    inner: polysig_client::CancellationToken,
+}
+
+#[uniffi::export]
+impl CancellationToken {
+    /// Create a new cancellation token.
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self {
+            inner: polysig_client::CancellationToken::new(),
+        }
+    }
+
+    /// Cancel the ceremony.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    /// Client cancellation token.
+    pub(crate) fn token(&self) -> polysig_client::CancellationToken {
+        self.inner.clone()
+    }
+}
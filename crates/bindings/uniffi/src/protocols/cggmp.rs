@@ -0,0 +1,189 @@
+//! Bindings for the CGGMP protocol.
+use super::types::{KeyShare, PartyOptions, SessionOptions};
+use crate::{PolysigError, RecoverableSignature};
+use polysig_driver::{
+    cggmp::Participant,
+    digest::DigestAlgorithm,
+    synedrion::{
+        self,
+        ecdsa::{SigningKey, VerifyingKey},
+        SessionId,
+    },
+};
+use std::collections::BTreeSet;
+
+#[cfg(not(debug_assertions))]
+type Params = synedrion::ProductionParams;
+#[cfg(debug_assertions)]
+type Params = synedrion::TestParams;
+
+type ThresholdKeyShare =
+    synedrion::ThresholdKeyShare<Params, VerifyingKey>;
+
+/// Distributed key generation.
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn cggmp_dkg(
+    options: SessionOptions,
+    party: PartyOptions,
+    session_id_seed: Vec<u8>,
+    signer: Vec<u8>,
+) -> Result<KeyShare, PolysigError> {
+    let key_share = polysig_client::cggmp::dkg::<Params>(
+        options.try_into()?,
+        participant(party, signer)?,
+        SessionId::from_seed(&session_id_seed),
+    )
+    .await?;
+    into_key_share(&key_share)
+}
+
+/// Sign a message.
+///
+/// Without a digest algorithm the message must be a 32 byte
+/// prehash otherwise the prehash is computed using the
+/// digest algorithm.
+#[uniffi::export(async_runtime = "tokio")]
+#[allow(clippy::too_many_arguments)]
+pub async fn cggmp_sign(
+    options: SessionOptions,
+    party: PartyOptions,
+    session_id_seed: Vec<u8>,
+    signer: Vec<u8>,
+    key_share: KeyShare,
+    message: Vec<u8>,
+    digest: Option<String>,
+) -> Result<RecoverableSignature, PolysigError> {
+    let mut options: polysig_client::SessionOptions =
+        options.try_into()?;
+    options.key_share_policy =
+        key_share.policy.clone().map(Into::into);
+    let participant = participant(party, signer)?;
+    let key_share = from_key_share(key_share)?;
+
+    let mut selected_parties = BTreeSet::new();
+    selected_parties.extend(participant.party().verifiers().iter());
+    let key_share = key_share.to_key_share(&selected_parties);
+
+    let digest: DigestAlgorithm = digest
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(PolysigError::other)?
+        .unwrap_or_default();
+    let message =
+        digest.prehash(&message).map_err(PolysigError::other)?;
+
+    let signature = polysig_client::cggmp::sign(
+        options,
+        participant,
+        SessionId::from_seed(&session_id_seed),
+        &key_share,
+        &message,
+    )
+    .await?;
+    Ok(signature.into())
+}
+
+/// Reshare key shares.
+///
+/// New participants pass `None` for the key share.
+#[uniffi::export(async_runtime = "tokio")]
+#[allow(clippy::too_many_arguments)]
+pub async fn cggmp_reshare(
+    options: SessionOptions,
+    party: PartyOptions,
+    session_id_seed: Vec<u8>,
+    signer: Vec<u8>,
+    account_verifying_key: Vec<u8>,
+    key_share: Option<KeyShare>,
+    old_threshold: u16,
+    new_threshold: u16,
+) -> Result<KeyShare, PolysigError> {
+    let account_verifying_key =
+        VerifyingKey::from_sec1_bytes(&account_verifying_key)
+            .map_err(PolysigError::other)?;
+    let key_share = key_share.map(from_key_share).transpose()?;
+
+    let key_share = polysig_client::cggmp::reshare(
+        options.try_into()?,
+        participant(party, signer)?,
+        SessionId::from_seed(&session_id_seed),
+        account_verifying_key,
+        key_share,
+        old_threshold as usize,
+        new_threshold as usize,
+    )
+    .await?;
+    into_key_share(&key_share)
+}
+
+/// Verifying key for a CGGMP key share.
+#[uniffi::export]
+pub fn cggmp_verifying_key(
+    key_share: KeyShare,
+) -> Result<Vec<u8>, PolysigError> {
+    let key_share = from_key_share(key_share)?;
+    Ok(key_share.verifying_key().to_sec1_bytes().to_vec())
+}
+
+/// Compute the Ethereum address for a CGGMP key share.
+#[uniffi::export]
+pub fn cggmp_address(
+    key_share: KeyShare,
+) -> Result<String, PolysigError> {
+    let key_share = from_key_share(key_share)?;
+    let public_key = key_share
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    Ok(polysig_driver::address(&public_key))
+}
+
+/// Generate a BIP32 derived child key.
+#[uniffi::export]
+pub fn cggmp_derive_bip32(
+    key_share: KeyShare,
+    derivation_path: String,
+) -> Result<KeyShare, PolysigError> {
+    use polysig_driver::bip32::DerivationPath;
+
+    let key_share = from_key_share(key_share)?;
+    let derivation_path: DerivationPath =
+        derivation_path.parse().map_err(PolysigError::other)?;
+    let child_key = polysig_driver::cggmp::derive_bip32(
+        &key_share,
+        &derivation_path,
+    )
+    .map_err(PolysigError::other)?;
+    into_key_share(&child_key)
+}
+
+/// Create a participant from the party options and signing key.
+fn participant(
+    party: PartyOptions,
+    signer: Vec<u8>,
+) -> Result<Participant, PolysigError> {
+    let party = party.into_party_options(|key| {
+        VerifyingKey::from_sec1_bytes(key)
+            .map_err(PolysigError::other)
+    })?;
+    let signer: SigningKey =
+        signer.as_slice().try_into().map_err(PolysigError::other)?;
+    let verifier = signer.verifying_key().clone();
+    Ok(Participant::new(signer, verifier, party)?)
+}
+
+fn from_key_share(
+    key_share: KeyShare,
+) -> Result<ThresholdKeyShare, PolysigError> {
+    let key_share: polysig_driver::KeyShare = key_share.into();
+    Ok((&key_share).try_into()?)
+}
+
+fn into_key_share(
+    key_share: &ThresholdKeyShare,
+) -> Result<KeyShare, PolysigError> {
+    let key_share: polysig_driver::KeyShare = key_share.try_into()?;
+    Ok(key_share.into())
+}
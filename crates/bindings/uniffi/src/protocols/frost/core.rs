@@ -0,0 +1,112 @@
+macro_rules! frost_impl {
+    ($dkg:ident, $sign:ident, $verifying_key:ident) => {
+        /// Distributed key generation.
+        #[uniffi::export(async_runtime = "tokio")]
+        pub async fn $dkg(
+            options: SessionOptions,
+            party: PartyOptions,
+            signer: Vec<u8>,
+            identifiers: Vec<u16>,
+        ) -> Result<KeyShare, PolysigError> {
+            let key_share = dkg(
+                options.try_into()?,
+                participant(party, signer)?,
+                into_identifiers(identifiers)?,
+            )
+            .await?;
+            into_key_share(&key_share)
+        }
+
+        /// Sign a message.
+        ///
+        /// The digest algorithm is applied to the message before
+        /// signing; returns the serialized signature.
+        #[uniffi::export(async_runtime = "tokio")]
+        #[allow(clippy::too_many_arguments)]
+        pub async fn $sign(
+            options: SessionOptions,
+            party: PartyOptions,
+            signer: Vec<u8>,
+            identifiers: Vec<u16>,
+            key_share: KeyShare,
+            message: Vec<u8>,
+            digest: Option<String>,
+        ) -> Result<Vec<u8>, PolysigError> {
+            let digest: polysig_driver::digest::DigestAlgorithm =
+                digest
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()
+                    .map_err(PolysigError::other)?
+                    .unwrap_or_default();
+            let mut options: polysig_client::SessionOptions =
+                options.try_into()?;
+            options.key_share_policy =
+                key_share.policy.clone().map(Into::into);
+            let signature = sign(
+                options,
+                participant(party, signer)?,
+                into_identifiers(identifiers)?,
+                from_key_share(key_share)?,
+                digest.digest(&message),
+            )
+            .await?;
+            signature.serialize().map_err(PolysigError::other)
+        }
+
+        /// Verifying key for the group.
+        #[uniffi::export]
+        pub fn $verifying_key(
+            key_share: KeyShare,
+        ) -> Result<Vec<u8>, PolysigError> {
+            let key_share = from_key_share(key_share)?;
+            key_share
+                .1
+                .verifying_key()
+                .serialize()
+                .map_err(PolysigError::other)
+        }
+
+        /// Create a participant from the party options
+        /// and signing key.
+        fn participant(
+            party: PartyOptions,
+            signer: Vec<u8>,
+        ) -> Result<Participant, PolysigError> {
+            let party =
+                party.into_party_options(into_verifying_key)?;
+            let signer = into_signing_key(signer)?;
+            let verifier = signer.verifying_key().clone();
+            Ok(Participant::new(signer, verifier, party)?)
+        }
+
+        /// Convert party numbers into identifiers.
+        fn into_identifiers(
+            identifiers: Vec<u16>,
+        ) -> Result<Vec<Identifier>, PolysigError> {
+            let mut ids = Vec::with_capacity(identifiers.len());
+            for id in identifiers {
+                ids.push(id.try_into().map_err(PolysigError::other)?);
+            }
+            Ok(ids)
+        }
+
+        fn from_key_share(
+            key_share: KeyShare,
+        ) -> Result<ThresholdKeyShare, PolysigError> {
+            let key_share: polysig_driver::KeyShare =
+                key_share.into();
+            Ok((&key_share).try_into()?)
+        }
+
+        fn into_key_share(
+            key_share: &ThresholdKeyShare,
+        ) -> Result<KeyShare, PolysigError> {
+            let key_share: polysig_driver::KeyShare =
+                key_share.try_into()?;
+            Ok(key_share.into())
+        }
+    };
+}
+
+pub(crate) use frost_impl;
@@ -0,0 +1,34 @@
+//! FROST Ed25519 protocol.
+use crate::{
+    protocols::types::{KeyShare, PartyOptions, SessionOptions},
+    PolysigError,
+};
+use polysig_client::frost::ed25519::{dkg, sign};
+use polysig_driver::frost::ed25519::{
+    self as frost, Identifier, Participant, SigningKey, VerifyingKey,
+};
+
+/// Threshold key share for FROST Ed25519.
+type ThresholdKeyShare = frost::KeyShare;
+
+fn into_signing_key(
+    value: Vec<u8>,
+) -> Result<SigningKey, PolysigError> {
+    let bytes: [u8; 32] =
+        value.as_slice().try_into().map_err(PolysigError::other)?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+fn into_verifying_key(
+    value: &[u8],
+) -> Result<VerifyingKey, PolysigError> {
+    let bytes: [u8; 32] =
+        value.try_into().map_err(PolysigError::other)?;
+    VerifyingKey::from_bytes(&bytes).map_err(PolysigError::other)
+}
+
+super::core::frost_impl!(
+    frost_ed25519_dkg,
+    frost_ed25519_sign,
+    frost_ed25519_verifying_key
+);
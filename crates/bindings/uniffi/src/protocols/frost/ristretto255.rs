@@ -0,0 +1,34 @@
+//! FROST Ristretto255 protocol.
+use crate::{
+    protocols::types::{KeyShare, PartyOptions, SessionOptions},
+    PolysigError,
+};
+use polysig_client::frost::ristretto255::{dkg, sign};
+use polysig_driver::frost::ristretto255::{
+    self as frost, Identifier, Participant, SigningKey, VerifyingKey,
+};
+
+/// Threshold key share for FROST Ristretto255.
+type ThresholdKeyShare = frost::KeyShare;
+
+fn into_signing_key(
+    value: Vec<u8>,
+) -> Result<SigningKey, PolysigError> {
+    let bytes: [u8; 32] =
+        value.as_slice().try_into().map_err(PolysigError::other)?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+fn into_verifying_key(
+    value: &[u8],
+) -> Result<VerifyingKey, PolysigError> {
+    let bytes: [u8; 32] =
+        value.try_into().map_err(PolysigError::other)?;
+    VerifyingKey::from_bytes(&bytes).map_err(PolysigError::other)
+}
+
+super::core::frost_impl!(
+    frost_ristretto255_dkg,
+    frost_ristretto255_sign,
+    frost_ristretto255_verifying_key
+);
@@ -0,0 +1,30 @@
+//! FROST Secp256k1 Taproot protocol.
+use crate::{
+    protocols::types::{KeyShare, PartyOptions, SessionOptions},
+    PolysigError,
+};
+use polysig_client::frost::secp256k1_tr::{dkg, sign};
+use polysig_driver::frost::secp256k1_tr::{
+    self as frost, Identifier, Participant, SigningKey, VerifyingKey,
+};
+
+/// Threshold key share for FROST Secp256k1 Taproot.
+type ThresholdKeyShare = frost::KeyShare;
+
+fn into_signing_key(
+    value: Vec<u8>,
+) -> Result<SigningKey, PolysigError> {
+    SigningKey::from_bytes(&value).map_err(PolysigError::other)
+}
+
+fn into_verifying_key(
+    value: &[u8],
+) -> Result<VerifyingKey, PolysigError> {
+    VerifyingKey::from_bytes(value).map_err(PolysigError::other)
+}
+
+super::core::frost_impl!(
+    frost_secp256k1_tr_dkg,
+    frost_secp256k1_tr_sign,
+    frost_secp256k1_tr_verifying_key
+);
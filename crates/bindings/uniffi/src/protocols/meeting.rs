@@ -0,0 +1,61 @@
+//! Bindings for meeting points.
+use super::types::{MeetingItem, PublicKeys};
+use crate::PolysigError;
+use polysig_client::meeting;
+use polysig_protocol::{MeetingId, UserId};
+
+/// Create a meeting room used to exchange public keys.
+///
+/// Returns the meeting identifier which the initiator
+/// shares with the other participants.
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn create_meeting(
+    url: String,
+    identifiers: Vec<Vec<u8>>,
+    initiator: Vec<u8>,
+) -> Result<String, PolysigError> {
+    let mut ids = Vec::with_capacity(identifiers.len());
+    for id in identifiers {
+        ids.push(parse_user_id(id)?);
+    }
+    let meeting_id =
+        meeting::create(&url, ids, parse_user_id(initiator)?).await?;
+    Ok(meeting_id.to_string())
+}
+
+/// Join a meeting room used to exchange public keys.
+///
+/// Returns once every participant has joined with the
+/// public keys of all the participants.
+#[uniffi::export(async_runtime = "tokio")]
+pub async fn join_meeting(
+    url: String,
+    meeting_id: String,
+    user_id: Vec<u8>,
+    data: PublicKeys,
+) -> Result<Vec<MeetingItem>, PolysigError> {
+    let meeting_id: MeetingId =
+        meeting_id.parse().map_err(PolysigError::other)?;
+    let results = meeting::join(
+        &url,
+        meeting_id,
+        parse_user_id(user_id)?,
+        data.try_into()?,
+    )
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .map(|(user_id, data)| MeetingItem {
+            user_id: user_id.as_ref().to_vec(),
+            data: data.into(),
+        })
+        .collect())
+}
+
+/// Parse a single user identifier (SHA256 checksum).
+fn parse_user_id(id: Vec<u8>) -> Result<UserId, PolysigError> {
+    let id: [u8; 32] =
+        id.as_slice().try_into().map_err(PolysigError::other)?;
+    Ok(id.into())
+}
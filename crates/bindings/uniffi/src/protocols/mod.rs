@@ -0,0 +1,9 @@
+#[cfg(feature = "cggmp")]
+pub mod cggmp;
+
+#[cfg(feature = "frost")]
+pub mod frost;
+
+pub mod cancel;
+pub mod meeting;
+pub mod types;
@@ -0,0 +1,316 @@
+//! Records exchanged with the protocol functions.
+use super::cancel::CancellationToken;
+use crate::PolysigError;
+use polysig_protocol::{self as protocol, KeyType};
+use std::sync::Arc;
+
+/// Keypair for the noise transport.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Keypair {
+    /// Private key.
+    pub private: Vec<u8>,
+    /// Public key.
+    pub public: Vec<u8>,
+    /// Type of the key, eg: `noise`.
+    pub key_type: String,
+}
+
+impl TryFrom<Keypair> for protocol::Keypair {
+    type Error = PolysigError;
+
+    fn try_from(value: Keypair) -> Result<Self, Self::Error> {
+        Ok(protocol::Keypair::new(
+            value.private,
+            value.public,
+            value.key_type.parse()?,
+        ))
+    }
+}
+
+/// Generate a keypair for the noise transport.
+#[uniffi::export]
+pub fn generate_keypair() -> Result<Keypair, PolysigError> {
+    let keypair = protocol::Keypair::generate()?;
+    Ok(Keypair {
+        private: keypair.private_key().to_vec(),
+        public: keypair.public_key().to_vec(),
+        key_type: KeyType::Noise.to_string(),
+    })
+}
+
+/// Options for the relay server connection.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ServerOptions {
+    /// URL of the relay server.
+    pub server_url: String,
+    /// Noise public key of the relay server.
+    pub server_public_key: Vec<u8>,
+    /// Noise protocol pattern.
+    #[uniffi(default = None)]
+    pub pattern: Option<String>,
+}
+
+impl From<ServerOptions> for polysig_client::ServerOptions {
+    fn from(value: ServerOptions) -> Self {
+        polysig_client::ServerOptions {
+            server_url: value.server_url,
+            server_public_key: value.server_public_key,
+            pattern: value.pattern,
+        }
+    }
+}
+
+/// Parameters for key generation.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Parameters {
+    /// Number of parties.
+    pub parties: u16,
+    /// Threshold for signing.
+    pub threshold: u16,
+}
+
+impl From<Parameters> for protocol::Parameters {
+    fn from(value: Parameters) -> Self {
+        protocol::Parameters {
+            parties: value.parties,
+            threshold: value.threshold,
+        }
+    }
+}
+
+/// Options for creating or joining a session.
+#[derive(uniffi::Record)]
+pub struct SessionOptions {
+    /// Keypair for the participant.
+    pub keypair: Keypair,
+    /// Server options.
+    pub server: ServerOptions,
+    /// Parameters for key generation.
+    pub parameters: Parameters,
+    /// Token used to cancel the ceremony.
+    #[uniffi(default = None)]
+    pub cancel: Option<Arc<CancellationToken>>,
+    /// Context checked against the key share policy
+    /// when signing.
+    #[uniffi(default = None)]
+    pub signing_context: Option<SigningContext>,
+    /// Timeout in seconds for each round of the ceremony.
+    #[uniffi(default = None)]
+    pub round_timeout: Option<u32>,
+    /// Keepalive for the connection to the server.
+    #[uniffi(default = None)]
+    pub keepalive: Option<KeepaliveOptions>,
+}
+
+impl TryFrom<SessionOptions> for polysig_client::SessionOptions {
+    type Error = PolysigError;
+
+    fn try_from(value: SessionOptions) -> Result<Self, Self::Error> {
+        let mut builder = polysig_client::SessionOptions::builder(
+            value.keypair.try_into()?,
+            value.server.into(),
+            value.parameters.into(),
+        );
+        if let Some(token) = value.cancel {
+            builder = builder.cancel(token.token());
+        }
+        if let Some(context) = value.signing_context {
+            builder = builder.signing_context(context.into());
+        }
+        if let Some(seconds) = value.round_timeout {
+            builder = builder.round_timeout(seconds);
+        }
+        if let Some(keepalive) = value.keepalive {
+            builder = builder.keepalive(keepalive.into());
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Options for a party participating in a protocol.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PartyOptions {
+    /// Noise public key of this participant.
+    pub public_key: Vec<u8>,
+    /// Noise public keys of all participants.
+    pub participants: Vec<Vec<u8>>,
+    /// Whether this participant is the session initiator.
+    pub is_initiator: bool,
+    /// Verifying keys of all participants.
+    pub verifiers: Vec<Vec<u8>>,
+}
+
+impl PartyOptions {
+    /// Convert to the driver party options parsing
+    /// the verifying keys.
+    pub(crate) fn into_party_options<V>(
+        self,
+        parse: impl Fn(&[u8]) -> Result<V, PolysigError>,
+    ) -> Result<polysig_driver::PartyOptions<V>, PolysigError> {
+        let mut verifiers = Vec::with_capacity(self.verifiers.len());
+        for verifier in &self.verifiers {
+            verifiers.push(parse(verifier)?);
+        }
+        Ok(polysig_driver::PartyOptions::builder(self.public_key)
+            .verifiers(self.participants, verifiers)
+            .initiator(self.is_initiator)
+            .build()?)
+    }
+}
+
+/// Key share encoded for storage.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct KeyShare {
+    /// Version of the encoding.
+    pub version: u16,
+    /// Encoded key share contents.
+    pub contents: String,
+    /// Rules enforced before signing with the key share.
+    #[uniffi(default = None)]
+    pub policy: Option<KeySharePolicy>,
+}
+
+impl From<polysig_driver::KeyShare> for KeyShare {
+    fn from(value: polysig_driver::KeyShare) -> Self {
+        Self {
+            version: value.version,
+            contents: value.contents,
+            policy: value.policy.map(Into::into),
+        }
+    }
+}
+
+impl From<KeyShare> for polysig_driver::KeyShare {
+    fn from(value: KeyShare) -> Self {
+        Self {
+            version: value.version,
+            contents: value.contents,
+            policy: value.policy.map(Into::into),
+        }
+    }
+}
+
+/// Rules enforced before signing with a key share.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct KeySharePolicy {
+    /// Seconds since the UNIX epoch after which the key
+    /// share can no longer be used to sign.
+    #[uniffi(default = None)]
+    pub expires_at: Option<u64>,
+    /// Allowed derivation path prefixes.
+    #[uniffi(default = [])]
+    pub derivation_paths: Vec<String>,
+    /// Allowed chain identifiers.
+    #[uniffi(default = [])]
+    pub chains: Vec<String>,
+}
+
+impl From<polysig_driver::KeySharePolicy> for KeySharePolicy {
+    fn from(value: polysig_driver::KeySharePolicy) -> Self {
+        Self {
+            expires_at: value.expires_at,
+            derivation_paths: value.derivation_paths,
+            chains: value.chains,
+        }
+    }
+}
+
+impl From<KeySharePolicy> for polysig_driver::KeySharePolicy {
+    fn from(value: KeySharePolicy) -> Self {
+        Self {
+            expires_at: value.expires_at,
+            derivation_paths: value.derivation_paths,
+            chains: value.chains,
+        }
+    }
+}
+
+/// Context for a signature checked against the
+/// key share policy.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct SigningContext {
+    /// Derivation path for the signature.
+    #[uniffi(default = None)]
+    pub derivation_path: Option<String>,
+    /// Chain the signature is for.
+    #[uniffi(default = None)]
+    pub chain: Option<String>,
+}
+
+impl From<SigningContext> for polysig_client::SigningContext {
+    fn from(value: SigningContext) -> Self {
+        Self {
+            derivation_path: value.derivation_path,
+            chain: value.chain,
+        }
+    }
+}
+
+/// Keepalive for the connection to the server.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct KeepaliveOptions {
+    /// Seconds between pings sent to the server.
+    pub ping_interval: u32,
+    /// Seconds without a message from the server before
+    /// the connection is considered lost.
+    pub idle_timeout: u32,
+}
+
+impl From<KeepaliveOptions> for polysig_client::KeepaliveOptions {
+    fn from(value: KeepaliveOptions) -> Self {
+        Self {
+            ping_interval: value.ping_interval,
+            idle_timeout: value.idle_timeout,
+        }
+    }
+}
+
+/// Public keys exchanged in a meeting room.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PublicKeys {
+    /// Transport public key.
+    pub public_key: Vec<u8>,
+    /// Verifiying key.
+    pub verifying_key: Vec<u8>,
+    /// Optional associated data encoded as JSON.
+    #[uniffi(default = None)]
+    pub associated_data: Option<String>,
+}
+
+impl TryFrom<PublicKeys> for protocol::PublicKeys {
+    type Error = PolysigError;
+
+    fn try_from(value: PublicKeys) -> Result<Self, Self::Error> {
+        let associated_data = value
+            .associated_data
+            .map(|data| serde_json::from_str(&data))
+            .transpose()
+            .map_err(PolysigError::other)?;
+        Ok(protocol::PublicKeys {
+            public_key: value.public_key,
+            verifying_key: value.verifying_key,
+            associated_data,
+        })
+    }
+}
+
+impl From<protocol::PublicKeys> for PublicKeys {
+    fn from(value: protocol::PublicKeys) -> Self {
+        PublicKeys {
+            public_key: value.public_key,
+            verifying_key: value.verifying_key,
+            associated_data: value
+                .associated_data
+                .map(|data| data.to_string()),
+        }
+    }
+}
+
+/// Public keys of a meeting room participant.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct MeetingItem {
+    /// User identifier (SHA-256 checksum).
+    pub user_id: Vec<u8>,
+    /// Data for the user.
+    pub data: PublicKeys,
+}
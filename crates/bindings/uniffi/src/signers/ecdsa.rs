@@ -0,0 +1,146 @@
+//! ECDSA signatures compatible with Ethereum.
+use crate::{PolysigError, RecoverableSignature};
+use polysig_driver::{
+    recoverable_signature,
+    signers::ecdsa::{self, Signature},
+};
+use std::borrow::Cow;
+
+/// Signer for ECDSA.
+#[derive(uniffi::Object)]
+pub struct EcdsaSigner {
+    inner: ecdsa::EcdsaSigner<'static>,
+}
+
+#[uniffi::export]
+impl EcdsaSigner {
+    /// Create a new signer.
+    #[uniffi::constructor]
+    pub fn new(signing_key: Vec<u8>) -> Result<Self, PolysigError> {
+        let signing_key =
+            ecdsa::EcdsaSigner::from_slice(&signing_key)?;
+        Ok(Self {
+            inner: ecdsa::EcdsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
+        })
+    }
+
+    /// Sign the given message, hashing it with the curve’s
+    /// default digest function, and returning a signature
+    /// and recovery ID.
+    pub fn sign_recoverable(
+        &self,
+        message: Vec<u8>,
+    ) -> Result<RecoverableSignature, PolysigError> {
+        let result = self.inner.sign_recoverable(&message)?;
+        let signature: recoverable_signature::RecoverableSignature =
+            result.into();
+        Ok(signature.into())
+    }
+
+    /// Sign the given message prehash, returning a signature
+    /// and recovery ID.
+    pub fn sign_prehash_recoverable(
+        &self,
+        message: Vec<u8>,
+    ) -> Result<RecoverableSignature, PolysigError> {
+        let result = self.inner.sign_prehash_recoverable(&message)?;
+        let signature: recoverable_signature::RecoverableSignature =
+            result.into();
+        Ok(signature.into())
+    }
+
+    /// Sign a message.
+    pub fn sign(&self, message: Vec<u8>) -> Vec<u8> {
+        let result = self.inner.sign(&message);
+        result.to_bytes().as_slice().to_vec()
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Vec<u8> {
+        self.inner.verifying_key().to_sec1_bytes().to_vec()
+    }
+
+    /// Compute the Ethereum address for the verifying key.
+    pub fn address(&self) -> String {
+        let public_key = self
+            .inner
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        polysig_driver::address(&public_key)
+    }
+
+    /// Verify a message.
+    pub fn verify(
+        &self,
+        message: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<(), PolysigError> {
+        let signature = Signature::from_slice(&signature)
+            .map_err(PolysigError::other)?;
+        Ok(self.inner.verify(&message, &signature)?)
+    }
+
+    /// Verify a prehash.
+    pub fn verify_prehash(
+        &self,
+        prehash: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<(), PolysigError> {
+        let signature = Signature::from_slice(&signature)
+            .map_err(PolysigError::other)?;
+        Ok(self.inner.verify_prehash(&prehash, &signature)?)
+    }
+
+    /// Sign a message for Ethereum first hashing the message
+    /// with the Keccak256 digest.
+    pub fn sign_eth(
+        &self,
+        message: Vec<u8>,
+    ) -> Result<RecoverableSignature, PolysigError> {
+        let result = self.inner.sign_eth(&message)?;
+        let signature: recoverable_signature::RecoverableSignature =
+            result.into();
+        Ok(signature.into())
+    }
+}
+
+/// Generate a random ECDSA signing key.
+#[uniffi::export]
+pub fn ecdsa_random_signing_key() -> Vec<u8> {
+    ecdsa::EcdsaSigner::random().to_bytes().as_slice().to_vec()
+}
+
+/// Recover the ECDSA public key from a signature and
+/// recovery identifier.
+#[uniffi::export]
+pub fn ecdsa_recover(
+    message: Vec<u8>,
+    signature: RecoverableSignature,
+) -> Result<Vec<u8>, PolysigError> {
+    let verifying_key =
+        ecdsa::EcdsaSigner::recover(&message, signature.into())?;
+    Ok(verifying_key.to_sec1_bytes().to_vec())
+}
+
+/// Compute the Keccak256 digest of a message.
+#[uniffi::export]
+pub fn keccak256(message: Vec<u8>) -> Vec<u8> {
+    use polysig_driver::sha3::{Digest, Keccak256};
+    let digest = Keccak256::new_with_prefix(&message);
+    let hash = digest.finalize();
+    hash.to_vec()
+}
+
+/// Compute the Keccak256 digest of a JSON document.
+///
+/// The document is encoded as canonical JSON (RFC 8785) so
+/// the digest is the same regardless of key order.
+#[uniffi::export]
+pub fn keccak256_json(json: String) -> Result<Vec<u8>, PolysigError> {
+    let message = polysig_protocol::canonical::canonicalize(&json)?;
+    Ok(keccak256(message.into_bytes()))
+}
@@ -0,0 +1,59 @@
+//! EdDSA signatures compatible with Solana.
+use crate::PolysigError;
+use polysig_driver::signers::eddsa::{self, Signature};
+use std::borrow::Cow;
+
+/// Signer for EdDSA.
+#[derive(uniffi::Object)]
+pub struct EddsaSigner {
+    inner: eddsa::EddsaSigner<'static>,
+}
+
+#[uniffi::export]
+impl EddsaSigner {
+    /// Create a new signer.
+    #[uniffi::constructor]
+    pub fn new(signing_key: Vec<u8>) -> Result<Self, PolysigError> {
+        let signing_key: [u8; 32] = signing_key
+            .as_slice()
+            .try_into()
+            .map_err(PolysigError::other)?;
+        let signing_key =
+            eddsa::EddsaSigner::from_bytes(&signing_key);
+        Ok(Self {
+            inner: eddsa::EddsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
+        })
+    }
+
+    /// Sign a message.
+    pub fn sign(&self, message: Vec<u8>) -> Vec<u8> {
+        let result = self.inner.sign(&message);
+        result.to_bytes().as_slice().to_vec()
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Vec<u8> {
+        self.inner.verifying_key().to_bytes().to_vec()
+    }
+
+    /// Verify a message.
+    pub fn verify(
+        &self,
+        message: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<(), PolysigError> {
+        let signature: Signature = signature
+            .as_slice()
+            .try_into()
+            .map_err(PolysigError::other)?;
+        Ok(self.inner.verify(&message, &signature)?)
+    }
+}
+
+/// Generate a random EdDSA signing key.
+#[uniffi::export]
+pub fn eddsa_random_signing_key() -> Vec<u8> {
+    eddsa::EddsaSigner::random().to_bytes().as_slice().to_vec()
+}
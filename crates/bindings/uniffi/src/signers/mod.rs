@@ -0,0 +1,8 @@
+#[cfg(feature = "ecdsa")]
+pub mod ecdsa;
+
+#[cfg(feature = "eddsa")]
+pub mod eddsa;
+
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
@@ -0,0 +1,68 @@
+//! Taproot Schnorr signatures compatible with Bitcoin (BIP-340).
+use crate::PolysigError;
+use polysig_driver::signers::schnorr::{self, Signature};
+use std::borrow::Cow;
+
+/// Signer for Schnorr.
+#[derive(uniffi::Object)]
+pub struct SchnorrSigner {
+    inner: schnorr::SchnorrSigner<'static>,
+}
+
+#[uniffi::export]
+impl SchnorrSigner {
+    /// Create a new signer.
+    #[uniffi::constructor]
+    pub fn new(signing_key: Vec<u8>) -> Result<Self, PolysigError> {
+        let signing_key =
+            schnorr::SchnorrSigner::from_slice(&signing_key)?;
+        Ok(Self {
+            inner: schnorr::SchnorrSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
+        })
+    }
+
+    /// Sign a message.
+    pub fn sign(&self, message: Vec<u8>) -> Vec<u8> {
+        let result = self.inner.sign(&message);
+        result.to_bytes().as_slice().to_vec()
+    }
+
+    /// Attempt to sign the given message digest, returning a
+    /// digital signature on success, or an error if something went wrong.
+    pub fn sign_prehash(
+        &self,
+        prehash: Vec<u8>,
+    ) -> Result<Vec<u8>, PolysigError> {
+        let result = self.inner.sign_prehash(&prehash)?;
+        Ok(result.to_bytes().as_slice().to_vec())
+    }
+
+    /// Verifying key for this signer.
+    pub fn verifying_key(&self) -> Vec<u8> {
+        self.inner.verifying_key().to_bytes().to_vec()
+    }
+
+    /// Verify a message.
+    pub fn verify(
+        &self,
+        message: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<(), PolysigError> {
+        let signature: Signature = signature
+            .as_slice()
+            .try_into()
+            .map_err(PolysigError::other)?;
+        Ok(self.inner.verify(&message, &signature)?)
+    }
+}
+
+/// Generate a random Schnorr signing key.
+#[uniffi::export]
+pub fn schnorr_random_signing_key() -> Vec<u8> {
+    schnorr::SchnorrSigner::random()
+        .to_bytes()
+        .as_slice()
+        .to_vec()
+}
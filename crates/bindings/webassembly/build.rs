@@ -1,4 +1,5 @@
 use rustc_version::{version_meta, Channel};
+use std::{env, fs, path::PathBuf};
 
 fn main() {
     println!("cargo::rustc-check-cfg=cfg(CHANNEL_NIGHTLY)");
@@ -10,5 +11,14 @@ fn main() {
         Channel::Nightly => "CHANNEL_NIGHTLY",
         Channel::Dev => "CHANNEL_DEV",
     };
-    println!("cargo:rustc-cfg={}", channel)
+    println!("cargo:rustc-cfg={}", channel);
+
+    // TypeScript definitions for the serialized types
+    // included in the generated `.d.ts` file
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(
+        out_dir.join("polysig.d.ts"),
+        polysig_client::typescript::declarations(),
+    )
+    .unwrap();
 }
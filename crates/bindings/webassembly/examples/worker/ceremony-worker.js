@@ -0,0 +1,68 @@
+// Main thread side of the worker harness.
+//
+// Call `init()` for the bindings on the main thread before
+// running ceremonies so results can be decoded.
+import {
+  createSessionHandle,
+  decodeSessionResult,
+} from "../../pkg/polysig_webassembly_bindings.js";
+
+export class CeremonyWorker {
+  #worker;
+  #pending = new Map();
+  #nextId = 0;
+
+  constructor(url = new URL("./worker.js", import.meta.url)) {
+    this.#worker = new Worker(url, { type: "module" });
+    this.#worker.onmessage = (event) => this.#onMessage(event.data);
+  }
+
+  // Run a ceremony in the worker.
+  //
+  // The `request` is passed to `createSessionHandle()`, the
+  // optional `progress` callback receives progress events and
+  // the optional `signal` cancels the ceremony.
+  run(request, { progress, signal } = {}) {
+    const handle = createSessionHandle(request);
+    const id = this.#nextId++;
+    return new Promise((resolve, reject) => {
+      if (signal?.aborted) {
+        reject(signal.reason);
+        return;
+      }
+      signal?.addEventListener("abort", () => {
+        this.#worker.postMessage({ id, abort: true });
+      });
+      this.#pending.set(id, { resolve, reject, progress });
+      // Transfer the handle to avoid copying key material
+      this.#worker.postMessage({ id, handle }, [handle]);
+    });
+  }
+
+  terminate() {
+    this.#worker.terminate();
+    for (const { reject } of this.#pending.values()) {
+      reject(new Error("worker terminated"));
+    }
+    this.#pending.clear();
+  }
+
+  #onMessage({ id, progress, result, error }) {
+    const pending = this.#pending.get(id);
+    if (!pending) {
+      return;
+    }
+    if (progress) {
+      pending.progress?.(progress);
+      return;
+    }
+    this.#pending.delete(id);
+    if (error) {
+      const err = new Error(error.message);
+      err.name = error.name;
+      pending.reject(err);
+    } else {
+      pending.resolve(decodeSessionResult(result));
+    }
+  }
+}
@@ -0,0 +1,43 @@
+// Web Worker that runs polysig ceremonies off the main thread.
+//
+// Receives `{ id, handle }` messages where `handle` is the
+// `ArrayBuffer` returned by `createSessionHandle()` and replies
+// with `{ id, progress }` events followed by `{ id, result }`
+// or `{ id, error }`. Post `{ id, abort: true }` to cancel a
+// running ceremony.
+import init, {
+  runSession,
+} from "../../pkg/polysig_webassembly_bindings.js";
+
+const ready = init();
+const controllers = new Map();
+
+self.onmessage = async (event) => {
+  const { id, handle, abort } = event.data;
+  if (abort) {
+    controllers.get(id)?.abort();
+    return;
+  }
+
+  await ready;
+  const controller = new AbortController();
+  controllers.set(id, controller);
+  try {
+    const result = await runSession(
+      handle,
+      (progress) => self.postMessage({ id, progress }),
+      controller.signal,
+    );
+    // Transfer the result rather than copying it
+    self.postMessage({ id, result }, [result]);
+  } catch (e) {
+    // Custom error properties do not survive the structured
+    // clone; the message carries the JSON error information
+    self.postMessage({
+      id,
+      error: { name: e.name, message: e.message },
+    });
+  } finally {
+    controllers.delete(id);
+  }
+};
@@ -0,0 +1,16 @@
+//! Capabilities compiled into the build.
+use wasm_bindgen::prelude::*;
+
+/// Capabilities compiled into this build.
+///
+/// Resolves to a `Capabilities` object; exchange the
+/// capabilities with the other participants to check they
+/// run compatible builds before starting a ceremony.
+#[wasm_bindgen]
+pub fn capabilities() -> Result<JsValue, JsError> {
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    let capabilities = polysig_client::capabilities();
+    #[cfg(not(any(feature = "cggmp", feature = "frost")))]
+    let capabilities = polysig_driver::capabilities();
+    Ok(serde_wasm_bindgen::to_value(&capabilities)?)
+}
@@ -0,0 +1,432 @@
+//! Keystore for browsers backed by IndexedDB.
+//!
+//! Key shares and noise keypairs are encrypted with AES-GCM
+//! before they are written to the database. The encryption
+//! key is a non-extractable WebCrypto key stored alongside
+//! the entries so it can be used by the keystore but never
+//! read by scripts or exported from the browser.
+//!
+//! Entry identifiers and kinds are stored in plain text so
+//! entries can be listed without decrypting them.
+use js_sys::{
+    Array, ArrayBuffer, Object, Promise, Reflect, Uint8Array,
+};
+use polysig_driver::KeyShare;
+use polysig_protocol::Keypair;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::{
+    Crypto, CryptoKey, IdbDatabase, IdbFactory, IdbObjectStore,
+    IdbRequest, IdbTransactionMode,
+};
+
+/// Default database name.
+const DATABASE: &str = "polysig";
+
+/// Database schema version.
+const VERSION: u32 = 1;
+
+/// Object store for the encrypted entries.
+const ENTRIES: &str = "entries";
+
+/// Object store for the encryption key.
+const KEYS: &str = "keys";
+
+/// Identifier for the encryption key.
+const ENCRYPTION_KEY: &str = "encryption-key";
+
+/// Encryption algorithm.
+const ALGORITHM: &str = "AES-GCM";
+
+/// Kind of keystore entry.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum EntryKind {
+    KeyShare,
+    Keypair,
+}
+
+impl EntryKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::KeyShare => "keyShare",
+            Self::Keypair => "keypair",
+        }
+    }
+}
+
+/// Entry stored in the database.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    id: String,
+    kind: EntryKind,
+    /// Milliseconds since the UNIX epoch.
+    created_at: f64,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+/// Metadata returned when listing entries.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryInfo {
+    id: String,
+    kind: EntryKind,
+    created_at: f64,
+}
+
+/// Encrypted storage for key shares and noise keypairs.
+///
+/// Open a keystore with `Keystore.open()`; all other
+/// methods return promises.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Keystore {
+    db: IdbDatabase,
+    key: CryptoKey,
+}
+
+#[wasm_bindgen]
+impl Keystore {
+    /// Open a keystore.
+    ///
+    /// The database is created the first time it is opened;
+    /// the promise resolves to a `Keystore`.
+    pub fn open(name: Option<String>) -> Promise {
+        future_to_promise(async move {
+            let db =
+                open_database(name.as_deref().unwrap_or(DATABASE))
+                    .await?;
+            let key = encryption_key(&db).await?;
+            Ok(Keystore { db, key }.into())
+        })
+    }
+
+    /// List the entries in the keystore.
+    ///
+    /// Resolves to an array of objects with the `id`, `kind`
+    /// (`"keyShare"` or `"keypair"`) and `createdAt` for
+    /// each entry.
+    pub fn list(&self) -> Promise {
+        let keystore = self.clone();
+        future_to_promise(async move {
+            let store =
+                keystore.store(IdbTransactionMode::Readonly)?;
+            let values: Array =
+                request(&store.get_all()?).await?.unchecked_into();
+            let mut entries = Vec::new();
+            for value in values.iter() {
+                let entry: Entry =
+                    serde_wasm_bindgen::from_value(value)?;
+                entries.push(EntryInfo {
+                    id: entry.id,
+                    kind: entry.kind,
+                    created_at: entry.created_at,
+                });
+            }
+            Ok(serde_wasm_bindgen::to_value(&entries)?)
+        })
+    }
+
+    /// Save a key share.
+    ///
+    /// An existing entry with the same identifier is replaced.
+    #[wasm_bindgen(js_name = "saveKeyShare")]
+    pub fn save_key_share(
+        &self,
+        id: String,
+        key_share: JsValue,
+    ) -> Result<Promise, JsError> {
+        let key_share: KeyShare =
+            serde_wasm_bindgen::from_value(key_share)?;
+        let plaintext = key_share.encode()?;
+        Ok(self.save(id, EntryKind::KeyShare, plaintext.into_bytes()))
+    }
+
+    /// Load a key share.
+    ///
+    /// Resolves to `undefined` when the entry does not exist.
+    #[wasm_bindgen(js_name = "loadKeyShare")]
+    pub fn load_key_share(&self, id: String) -> Promise {
+        let keystore = self.clone();
+        future_to_promise(async move {
+            let Some(plaintext) =
+                keystore.load(&id, EntryKind::KeyShare).await?
+            else {
+                return Ok(JsValue::UNDEFINED);
+            };
+            let plaintext = String::from_utf8(plaintext)
+                .map_err(JsError::from)?;
+            let key_share = KeyShare::decode(&plaintext)
+                .map_err(JsError::from)?;
+            Ok(serde_wasm_bindgen::to_value(&key_share)?)
+        })
+    }
+
+    /// Save a PEM-encoded noise keypair.
+    ///
+    /// An existing entry with the same identifier is replaced.
+    #[wasm_bindgen(js_name = "saveKeypair")]
+    pub fn save_keypair(
+        &self,
+        id: String,
+        keypair: String,
+    ) -> Result<Promise, JsError> {
+        Keypair::decode_pem(&keypair)?;
+        Ok(self.save(id, EntryKind::Keypair, keypair.into_bytes()))
+    }
+
+    /// Load a PEM-encoded noise keypair.
+    ///
+    /// Resolves to `undefined` when the entry does not exist.
+    #[wasm_bindgen(js_name = "loadKeypair")]
+    pub fn load_keypair(&self, id: String) -> Promise {
+        let keystore = self.clone();
+        future_to_promise(async move {
+            let Some(plaintext) =
+                keystore.load(&id, EntryKind::Keypair).await?
+            else {
+                return Ok(JsValue::UNDEFINED);
+            };
+            let pem = String::from_utf8(plaintext)
+                .map_err(JsError::from)?;
+            Ok(pem.into())
+        })
+    }
+
+    /// Delete an entry.
+    ///
+    /// Deleting an entry that does not exist is not an error.
+    pub fn delete(&self, id: String) -> Promise {
+        let keystore = self.clone();
+        future_to_promise(async move {
+            let store =
+                keystore.store(IdbTransactionMode::Readwrite)?;
+            request(&store.delete(&id.into())?).await?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    /// Close the database connection.
+    pub fn close(&self) {
+        self.db.close();
+    }
+}
+
+impl Keystore {
+    /// Object store for the entries.
+    fn store(
+        &self,
+        mode: IdbTransactionMode,
+    ) -> Result<IdbObjectStore, JsValue> {
+        self.db
+            .transaction_with_str_and_mode(ENTRIES, mode)?
+            .object_store(ENTRIES)
+    }
+
+    /// Encrypt and save an entry.
+    fn save(
+        &self,
+        id: String,
+        kind: EntryKind,
+        plaintext: Vec<u8>,
+    ) -> Promise {
+        let keystore = self.clone();
+        future_to_promise(async move {
+            let mut nonce = [0u8; 12];
+            getrandom::getrandom(&mut nonce)
+                .map_err(|e| JsError::new(&e.to_string()))?;
+            let params =
+                algorithm(&nonce, &additional_data(&id, kind))?;
+            let ciphertext = JsFuture::from(
+                subtle()?.encrypt_with_object_and_buffer_source(
+                    &params,
+                    &keystore.key,
+                    &Uint8Array::from(plaintext.as_slice()),
+                )?,
+            )
+            .await?;
+            let entry = Entry {
+                id: id.clone(),
+                kind,
+                created_at: js_sys::Date::now(),
+                nonce: nonce.to_vec(),
+                ciphertext: into_bytes(ciphertext),
+            };
+            let value = serde_wasm_bindgen::to_value(&entry)?;
+            let store =
+                keystore.store(IdbTransactionMode::Readwrite)?;
+            request(&store.put_with_key(&value, &id.into())?).await?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    /// Load and decrypt an entry.
+    async fn load(
+        &self,
+        id: &str,
+        kind: EntryKind,
+    ) -> Result<Option<Vec<u8>>, JsValue> {
+        let store = self.store(IdbTransactionMode::Readonly)?;
+        let value = request(&store.get(&id.into())?).await?;
+        if value.is_undefined() {
+            return Ok(None);
+        }
+        let entry: Entry = serde_wasm_bindgen::from_value(value)?;
+        if entry.kind != kind {
+            return Err(JsError::new(&format!(
+                "keystore entry '{}' is not a {}",
+                id,
+                kind.as_str(),
+            ))
+            .into());
+        }
+        let params =
+            algorithm(&entry.nonce, &additional_data(id, kind))?;
+        let plaintext = JsFuture::from(
+            subtle()?.decrypt_with_object_and_buffer_source(
+                &params,
+                &self.key,
+                &Uint8Array::from(entry.ciphertext.as_slice()),
+            )?,
+        )
+        .await?;
+        Ok(Some(into_bytes(plaintext)))
+    }
+}
+
+/// Open the database creating the object stores if necessary.
+async fn open_database(name: &str) -> Result<IdbDatabase, JsValue> {
+    let factory: IdbFactory =
+        Reflect::get(&js_sys::global(), &"indexedDB".into())?
+            .dyn_into()
+            .map_err(|_| {
+                JsError::new("IndexedDB is not available")
+            })?;
+    let open = factory.open_with_u32(name, VERSION)?;
+    let target = open.clone();
+    let on_upgrade = Closure::once_into_js(move || {
+        if let Ok(db) = target.result() {
+            let db: IdbDatabase = db.unchecked_into();
+            let _ = db.create_object_store(ENTRIES);
+            let _ = db.create_object_store(KEYS);
+        }
+    });
+    open.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+    Ok(request(&open).await?.unchecked_into())
+}
+
+/// Load the encryption key creating it if necessary.
+async fn encryption_key(
+    db: &IdbDatabase,
+) -> Result<CryptoKey, JsValue> {
+    let store = db
+        .transaction_with_str_and_mode(
+            KEYS,
+            IdbTransactionMode::Readonly,
+        )?
+        .object_store(KEYS)?;
+    let key = request(&store.get(&ENCRYPTION_KEY.into())?).await?;
+    if !key.is_undefined() {
+        return Ok(key.unchecked_into());
+    }
+
+    let params = Object::new();
+    Reflect::set(&params, &"name".into(), &ALGORITHM.into())?;
+    Reflect::set(&params, &"length".into(), &256.into())?;
+    let usages = Array::of2(&"encrypt".into(), &"decrypt".into());
+    let key: CryptoKey = JsFuture::from(
+        subtle()?
+            .generate_key_with_object(&params, false, &usages)?,
+    )
+    .await?
+    .unchecked_into();
+
+    // Use add() so concurrent callers do not replace a key
+    // that may already have been used to encrypt entries
+    let store = db
+        .transaction_with_str_and_mode(
+            KEYS,
+            IdbTransactionMode::Readwrite,
+        )?
+        .object_store(KEYS)?;
+    if request(&store.add_with_key(&key, &ENCRYPTION_KEY.into())?)
+        .await
+        .is_ok()
+    {
+        return Ok(key);
+    }
+
+    let store = db
+        .transaction_with_str_and_mode(
+            KEYS,
+            IdbTransactionMode::Readonly,
+        )?
+        .object_store(KEYS)?;
+    Ok(request(&store.get(&ENCRYPTION_KEY.into())?)
+        .await?
+        .unchecked_into())
+}
+
+/// Wait for a database request to complete.
+async fn request(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let target = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            let result =
+                target.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+        });
+        let target = request.clone();
+        let on_error = Closure::once_into_js(move || {
+            let error = target
+                .error()
+                .ok()
+                .flatten()
+                .map(JsValue::from)
+                .unwrap_or(JsValue::UNDEFINED);
+            let _ = reject.call1(&JsValue::UNDEFINED, &error);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await
+}
+
+/// WebCrypto API for the current global scope.
+fn subtle() -> Result<web_sys::SubtleCrypto, JsValue> {
+    let crypto: Crypto =
+        Reflect::get(&js_sys::global(), &"crypto".into())?
+            .dyn_into()
+            .map_err(|_| {
+                JsError::new("WebCrypto is not available")
+            })?;
+    Ok(crypto.subtle())
+}
+
+/// Encryption parameters for an entry.
+fn algorithm(nonce: &[u8], aad: &[u8]) -> Result<Object, JsValue> {
+    let params = Object::new();
+    Reflect::set(&params, &"name".into(), &ALGORITHM.into())?;
+    Reflect::set(&params, &"iv".into(), &Uint8Array::from(nonce))?;
+    Reflect::set(
+        &params,
+        &"additionalData".into(),
+        &Uint8Array::from(aad),
+    )?;
+    Ok(params)
+}
+
+/// Bind the ciphertext to the entry so encrypted entries
+/// cannot be swapped.
+fn additional_data(id: &str, kind: EntryKind) -> Vec<u8> {
+    format!("{}:{}", kind.as_str(), id).into_bytes()
+}
+
+/// Copy the bytes from an `ArrayBuffer`.
+fn into_bytes(value: JsValue) -> Vec<u8> {
+    let buffer: ArrayBuffer = value.unchecked_into();
+    Uint8Array::new(&buffer).to_vec()
+}
@@ -2,6 +2,10 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+/// Capabilities compiled into the build.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub mod capabilities;
+
 /// Threshold signature protocols.
 #[cfg(all(
     target_arch = "wasm32",
@@ -18,6 +22,21 @@ pub mod protocols;
 ))]
 pub mod signers;
 
+/// Encrypted storage for key shares and noise keypairs.
+#[cfg(all(
+    target_arch = "wasm32",
+    target_os = "unknown",
+    feature = "keystore",
+    any(feature = "cggmp", feature = "frost")
+))]
+pub mod keystore;
+
+/// TypeScript definitions for the serialized types.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT: &str =
+    include_str!(concat!(env!("OUT_DIR"), "/polysig.d.ts"));
+
 /// Initialize the panic hook and logging.
 #[doc(hidden)]
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
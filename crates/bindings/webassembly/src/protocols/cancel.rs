@@ -0,0 +1,26 @@
+//! Cancel ceremonies using an `AbortSignal`.
+use polysig_client::{CancellationToken, SessionOptions};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::AbortSignal;
+
+/// Cancel the ceremony for a session when the signal aborts.
+pub(crate) fn cancel_on_abort(
+    options: &mut SessionOptions,
+    signal: Option<AbortSignal>,
+) {
+    let Some(signal) = signal else {
+        return;
+    };
+    let token = CancellationToken::new();
+    if signal.aborted() {
+        token.cancel();
+    } else {
+        let cancel = token.clone();
+        let listener = Closure::once_into_js(move || cancel.cancel());
+        let _ = signal.add_event_listener_with_callback(
+            "abort",
+            listener.unchecked_ref(),
+        );
+    }
+    options.cancel = Some(token);
+}
@@ -1,5 +1,6 @@
 //! Bindings for the CGGMP protocol.
 use polysig_client::SessionOptions;
+use polysig_driver::digest::DigestAlgorithm;
 use polysig_driver::synedrion::{
     self,
     ecdsa::{SigningKey, VerifyingKey},
@@ -15,6 +16,8 @@ use std::collections::BTreeSet;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 
+use super::{cancel::cancel_on_abort, ids, progress::observe};
+
 #[cfg(not(debug_assertions))]
 type Params = synedrion::ProductionParams;
 #[cfg(debug_assertions)]
@@ -49,16 +52,18 @@ impl TryFrom<PartyOptions> for cggmp::PartyOptions {
             );
         }
 
-        Ok(cggmp::PartyOptions::new(
-            value.public_key,
-            value.participants,
-            value.is_initiator,
-            verifiers,
-        )?)
+        Ok(cggmp::PartyOptions::builder(value.public_key)
+            .verifiers(value.participants, verifiers)
+            .initiator(value.is_initiator)
+            .build()?)
     }
 }
 
 /// CGGMP protocol.
+///
+/// Ceremony functions accept an optional callback which
+/// receives progress events as the ceremony advances and an
+/// optional `AbortSignal` used to cancel the ceremony.
 #[wasm_bindgen]
 pub struct CggmpProtocol {
     options: SessionOptions,
@@ -73,10 +78,13 @@ impl CggmpProtocol {
         options: JsValue,
         key_share: JsValue,
     ) -> Result<CggmpProtocol, JsError> {
-        let options: SessionOptions =
+        let mut options: SessionOptions =
             serde_wasm_bindgen::from_value(options)?;
         let key_share: KeyShare =
             serde_wasm_bindgen::from_value(key_share)?;
+        if key_share.policy.is_some() {
+            options.key_share_policy = key_share.policy.clone();
+        }
         let key_share: ThresholdKeyShare =
             (&key_share).try_into().map_err(JsError::from)?;
         Ok(Self { options, key_share })
@@ -103,11 +111,17 @@ impl CggmpProtocol {
     pub fn dkg(
         options: JsValue,
         party: JsValue,
-        session_id_seed: Vec<u8>,
+        session_id: JsValue,
         signer: Vec<u8>,
+        progress: Option<js_sys::Function>,
+        signal: Option<web_sys::AbortSignal>,
     ) -> Result<JsValue, JsError> {
-        let options: SessionOptions =
+        let mut options: SessionOptions =
             serde_wasm_bindgen::from_value(options)?;
+        observe(&mut options, progress);
+        cancel_on_abort(&mut options, signal);
+        let session_id =
+            SessionId::from_seed(&ids::session_id(session_id)?);
         let party: PartyOptions =
             serde_wasm_bindgen::from_value(party)?;
         let signer: SigningKey =
@@ -120,7 +134,7 @@ impl CggmpProtocol {
             let key_share = polysig_client::cggmp::dkg::<Params>(
                 options,
                 participant,
-                SessionId::from_seed(&session_id_seed),
+                session_id,
             )
             .await?;
 
@@ -132,15 +146,49 @@ impl CggmpProtocol {
         Ok(future_to_promise(fut).into())
     }
 
-    /// Sign a message.
+    /// Sign a hex-encoded message.
+    ///
+    /// Without a digest algorithm the message must be a
+    /// 32 byte prehash otherwise the prehash is computed
+    /// using the digest algorithm.
     pub fn sign(
         &self,
         party: JsValue,
-        session_id_seed: Vec<u8>,
+        session_id: JsValue,
         signer: Vec<u8>,
         message: String,
+        progress: Option<js_sys::Function>,
+        signal: Option<web_sys::AbortSignal>,
+        digest: Option<String>,
+    ) -> Result<JsValue, JsError> {
+        let message: Vec<u8> =
+            hex::decode(&message).map_err(JsError::from)?;
+        self.sign_bytes(
+            party, session_id, signer, message, progress, signal,
+            digest,
+        )
+    }
+
+    /// Sign a message supplied as bytes.
+    ///
+    /// Accepts a `Uint8Array` so binary digests from hashing
+    /// libraries can be signed without a hex round trip.
+    #[wasm_bindgen(js_name = "signBytes")]
+    pub fn sign_bytes(
+        &self,
+        party: JsValue,
+        session_id: JsValue,
+        signer: Vec<u8>,
+        message: Vec<u8>,
+        progress: Option<js_sys::Function>,
+        signal: Option<web_sys::AbortSignal>,
+        digest: Option<String>,
     ) -> Result<JsValue, JsError> {
-        let options = self.options.clone();
+        let mut options = self.options.clone();
+        observe(&mut options, progress);
+        cancel_on_abort(&mut options, signal);
+        let session_id =
+            SessionId::from_seed(&ids::session_id(session_id)?);
         let party: PartyOptions =
             serde_wasm_bindgen::from_value(party)?;
         let signer: SigningKey =
@@ -157,16 +205,20 @@ impl CggmpProtocol {
         let key_share =
             self.key_share.to_key_share(&selected_parties);
 
-        let message: Vec<u8> =
-            hex::decode(&message).map_err(JsError::from)?;
-        let message: [u8; 32] =
-            message.as_slice().try_into().map_err(JsError::from)?;
+        let digest: DigestAlgorithm = digest
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(JsError::from)?
+            .unwrap_or_default();
+        let message =
+            digest.prehash(&message).map_err(JsError::from)?;
 
         let fut = async move {
             let signature = polysig_client::cggmp::sign(
                 options,
                 participant,
-                SessionId::from_seed(&session_id_seed),
+                session_id,
                 &key_share,
                 &message,
             )
@@ -180,14 +232,20 @@ impl CggmpProtocol {
     pub fn reshare(
         &self,
         party: JsValue,
-        session_id_seed: Vec<u8>,
+        session_id: JsValue,
         signer: Vec<u8>,
         account_verifying_key: JsValue,
         key_share: JsValue,
         old_threshold: usize,
         new_threshold: usize,
+        progress: Option<js_sys::Function>,
+        signal: Option<web_sys::AbortSignal>,
     ) -> Result<JsValue, JsError> {
-        let options = self.options.clone();
+        let mut options = self.options.clone();
+        observe(&mut options, progress);
+        cancel_on_abort(&mut options, signal);
+        let session_id =
+            SessionId::from_seed(&ids::session_id(session_id)?);
         let party: PartyOptions =
             serde_wasm_bindgen::from_value(party)?;
         let signer: SigningKey =
@@ -213,7 +271,7 @@ impl CggmpProtocol {
             let key_share = polysig_client::cggmp::reshare(
                 options,
                 participant,
-                SessionId::from_seed(&session_id_seed),
+                session_id,
                 account_verifying_key,
                 key_share,
                 old_threshold,
@@ -225,6 +283,35 @@ impl CggmpProtocol {
         Ok(future_to_promise(fut).into())
     }
 
+    /// Derive a session identifier bound to the parties,
+    /// threshold and purpose of a ceremony.
+    ///
+    /// The verifiers are SEC1 encoded verifying keys, the
+    /// purpose is one of `key-gen`, `aux-gen`, `key-refresh`,
+    /// `key-resharing` or `sign` and the nonce must be agreed
+    /// by all the parties, for example the meeting identifier.
+    #[wasm_bindgen(js_name = "deriveSessionId")]
+    pub fn derive_session_id(
+        verifiers: JsValue,
+        threshold: u16,
+        purpose: String,
+        nonce: Vec<u8>,
+    ) -> Result<JsValue, JsError> {
+        let verifiers: Vec<Vec<u8>> =
+            serde_wasm_bindgen::from_value(verifiers)?;
+        let mut keys = Vec::with_capacity(verifiers.len());
+        for key in &verifiers {
+            keys.push(
+                VerifyingKey::from_sec1_bytes(key)
+                    .map_err(JsError::from)?,
+            );
+        }
+        let purpose: cggmp::SessionPurpose = purpose.parse()?;
+        let seed =
+            cggmp::session_id_seed(&keys, threshold, purpose, &nonce);
+        Ok(serde_wasm_bindgen::to_value(&ids::SessionId::from(seed))?)
+    }
+
     /// Generate a BIP32 derived child key.
     #[wasm_bindgen(js_name = "deriveBip32")]
     pub fn derive_bip32(
@@ -1,6 +1,10 @@
 macro_rules! frost_impl {
     ($name:ident) => {
         /// FROST protocol.
+        ///
+        /// Ceremony functions accept an optional callback which
+        /// receives progress events as the ceremony advances and
+        /// an optional `AbortSignal` used to cancel the ceremony.
         #[wasm_bindgen]
         pub struct $name {
             options: polysig_client::SessionOptions,
@@ -15,31 +19,46 @@ macro_rules! frost_impl {
                 options: JsValue,
                 key_share: JsValue,
             ) -> Result<$name, JsError> {
-                let options: SessionOptions =
+                let mut options: SessionOptions =
                     serde_wasm_bindgen::from_value(options)?;
                 let key_share: KeyShare =
                     serde_wasm_bindgen::from_value(key_share)?;
+                if key_share.policy.is_some() {
+                    options.key_share_policy =
+                        key_share.policy.clone();
+                }
                 let key_share: ThresholdKeyShare =
                     (&key_share).try_into().map_err(JsError::from)?;
                 Ok(Self { options, key_share })
             }
 
+            /// Verifying key for the group.
+            #[wasm_bindgen(js_name = "verifyingKey")]
+            pub fn verifying_key(&self) -> Result<Vec<u8>, JsError> {
+                Ok(self.key_share.1.verifying_key().serialize()?)
+            }
+
             /// Distributed key generation.
-            pub async fn dkg(
+            pub fn dkg(
                 options: JsValue,
                 party: JsValue,
                 signer: Vec<u8>,
                 identifiers: Vec<u16>,
+                progress: Option<js_sys::Function>,
+                signal: Option<web_sys::AbortSignal>,
             ) -> Result<JsValue, JsError> {
-                let options: SessionOptions =
+                let mut options: SessionOptions =
                     serde_wasm_bindgen::from_value(options)?;
-
-                tracing::info!("decoding the party: {:#?}", party);
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
                 let party: PartyOptions =
                     serde_wasm_bindgen::from_value(party)?;
-
-                tracing::info!("party decoding completed!!!!");
-
                 let signer: SigningKey = into_signing_key(signer)?;
                 let verifier = signer.verifying_key().clone();
 
@@ -67,14 +86,25 @@ macro_rules! frost_impl {
             }
 
             /// Sign a message.
-            pub async fn sign(
+            pub fn sign(
                 &self,
                 party: JsValue,
                 signer: Vec<u8>,
                 identifiers: Vec<u16>,
                 message: Vec<u8>,
+                progress: Option<js_sys::Function>,
+                signal: Option<web_sys::AbortSignal>,
+                digest: Option<String>,
             ) -> Result<JsValue, JsError> {
-                let options = self.options.clone();
+                let mut options = self.options.clone();
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
                 let party: PartyOptions =
                     serde_wasm_bindgen::from_value(party)?;
                 let signer: SigningKey = into_signing_key(signer)?;
@@ -88,6 +118,15 @@ macro_rules! frost_impl {
                     ids.push(id.try_into()?);
                 }
 
+                let digest: polysig_driver::digest::DigestAlgorithm =
+                    digest
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .map_err(JsError::from)?
+                        .unwrap_or_default();
+                let message = digest.digest(&message);
+
                 let key_share = self.key_share.clone();
                 let fut = async move {
                     let signature = sign(
@@ -102,6 +141,122 @@ macro_rules! frost_impl {
                 };
                 Ok(future_to_promise(fut).into())
             }
+
+            /// Refresh the key share.
+            pub fn refresh(
+                &self,
+                party: JsValue,
+                signer: Vec<u8>,
+                identifiers: Vec<u16>,
+                progress: Option<js_sys::Function>,
+                signal: Option<web_sys::AbortSignal>,
+            ) -> Result<JsValue, JsError> {
+                let mut options = self.options.clone();
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
+                let party: PartyOptions =
+                    serde_wasm_bindgen::from_value(party)?;
+                let signer: SigningKey = into_signing_key(signer)?;
+                let verifier = signer.verifying_key().clone();
+                let participant =
+                    Participant::new(signer, verifier, party)
+                        .map_err(JsError::from)?;
+
+                let mut ids = Vec::with_capacity(identifiers.len());
+                for id in identifiers {
+                    ids.push(id.try_into()?);
+                }
+
+                let key_share = self.key_share.clone();
+                let fut = async move {
+                    let key_share =
+                        refresh(options, participant, ids, key_share)
+                            .await?;
+
+                    let key_share: KeyShare = (&key_share)
+                        .try_into()
+                        .map_err(JsError::from)?;
+
+                    Ok(serde_wasm_bindgen::to_value(&key_share)?)
+                };
+                Ok(future_to_promise(fut).into())
+            }
+
+            /// Repair the key share of a participant.
+            ///
+            /// Helpers pass their key share and the participant
+            /// being repaired passes `null`; the promise resolves
+            /// to the repaired key share for that participant.
+            #[wasm_bindgen(js_name = "repairShare")]
+            pub fn repair_share(
+                options: JsValue,
+                party: JsValue,
+                signer: Vec<u8>,
+                identifiers: Vec<u16>,
+                repair_identifier: u16,
+                key_share: JsValue,
+                progress: Option<js_sys::Function>,
+                signal: Option<web_sys::AbortSignal>,
+            ) -> Result<JsValue, JsError> {
+                let mut options: SessionOptions =
+                    serde_wasm_bindgen::from_value(options)?;
+                crate::protocols::progress::observe(
+                    &mut options,
+                    progress,
+                );
+                crate::protocols::cancel::cancel_on_abort(
+                    &mut options,
+                    signal,
+                );
+                let party: PartyOptions =
+                    serde_wasm_bindgen::from_value(party)?;
+                let signer: SigningKey = into_signing_key(signer)?;
+                let verifier = signer.verifying_key().clone();
+                let participant =
+                    Participant::new(signer, verifier, party)
+                        .map_err(JsError::from)?;
+
+                let mut ids = Vec::with_capacity(identifiers.len());
+                for id in identifiers {
+                    ids.push(id.try_into()?);
+                }
+                let repair_identifier: Identifier =
+                    repair_identifier.try_into()?;
+
+                let key_share: Option<KeyShare> =
+                    serde_wasm_bindgen::from_value(key_share)?;
+                let key_share: Option<ThresholdKeyShare> = key_share
+                    .as_ref()
+                    .map(|key_share| key_share.try_into())
+                    .transpose()
+                    .map_err(JsError::from)?;
+
+                let fut = async move {
+                    let key_share = repair_share(
+                        options,
+                        participant,
+                        ids,
+                        repair_identifier,
+                        key_share,
+                    )
+                    .await?;
+
+                    let key_share: Option<KeyShare> = key_share
+                        .as_ref()
+                        .map(|key_share| key_share.try_into())
+                        .transpose()
+                        .map_err(JsError::from)?;
+
+                    Ok(serde_wasm_bindgen::to_value(&key_share)?)
+                };
+                Ok(future_to_promise(fut).into())
+            }
         }
     };
 }
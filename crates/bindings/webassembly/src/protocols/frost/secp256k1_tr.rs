@@ -1,6 +1,6 @@
 //! FROST Secp256k1 Taproot protocol.
 use polysig_client::{
-    frost::secp256k1_tr::{dkg, sign},
+    frost::secp256k1_tr::{dkg, refresh, repair_share, sign},
     SessionOptions,
 };
 use polysig_driver::{
@@ -0,0 +1,119 @@
+//! Identifiers for meeting rooms and protocol ceremonies.
+//!
+//! Identifiers are objects with an `id` string in the
+//! canonical encoding so they can be passed to workers
+//! and encoded as JSON; meeting identifiers are lowercase
+//! hyphenated UUIDs and session identifiers are 32 bytes
+//! of lowercase hex.
+use polysig_protocol::{hex, MeetingId as ProtocolMeetingId};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Meeting room identifier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MeetingId {
+    id: String,
+}
+
+impl TryFrom<MeetingId> for ProtocolMeetingId {
+    type Error = JsError;
+
+    fn try_from(value: MeetingId) -> Result<Self, Self::Error> {
+        value.id.parse().map_err(|_| {
+            JsError::new(&format!(
+                "invalid meeting id '{}', expected a UUID",
+                value.id
+            ))
+        })
+    }
+}
+
+impl From<ProtocolMeetingId> for MeetingId {
+    fn from(value: ProtocolMeetingId) -> Self {
+        Self {
+            id: value.to_string(),
+        }
+    }
+}
+
+/// Session identifier for a protocol ceremony.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SessionId {
+    id: String,
+}
+
+impl TryFrom<SessionId> for [u8; 32] {
+    type Error = JsError;
+
+    fn try_from(value: SessionId) -> Result<Self, Self::Error> {
+        let id = value.id.strip_prefix("0x").unwrap_or(&value.id);
+        match hex::decode(id).map(<[u8; 32]>::try_from) {
+            Ok(Ok(id)) => Ok(id),
+            _ => Err(JsError::new(&format!(
+                "invalid session id '{}', expected 32 bytes of hex",
+                value.id
+            ))),
+        }
+    }
+}
+
+impl From<[u8; 32]> for SessionId {
+    fn from(value: [u8; 32]) -> Self {
+        Self {
+            id: hex::encode(value),
+        }
+    }
+}
+
+/// Parse a meeting identifier object.
+pub(crate) fn meeting_id(
+    value: JsValue,
+) -> Result<ProtocolMeetingId, JsError> {
+    let id: MeetingId = serde_wasm_bindgen::from_value(value)?;
+    id.try_into()
+}
+
+/// Parse a session identifier object.
+#[cfg(feature = "cggmp")]
+pub(crate) fn session_id(
+    value: JsValue,
+) -> Result<[u8; 32], JsError> {
+    let id: SessionId = serde_wasm_bindgen::from_value(value)?;
+    id.try_into()
+}
+
+/// Generate a random meeting identifier.
+#[wasm_bindgen(js_name = "generateMeetingId")]
+pub fn generate_meeting_id() -> Result<JsValue, JsError> {
+    let id: MeetingId = ProtocolMeetingId::new_v4().into();
+    Ok(serde_wasm_bindgen::to_value(&id)?)
+}
+
+/// Parse a meeting identifier.
+///
+/// Any UUID encoding is accepted and the identifier
+/// is returned in the canonical encoding.
+#[wasm_bindgen(js_name = "parseMeetingId")]
+pub fn parse_meeting_id(value: String) -> Result<JsValue, JsError> {
+    let id: ProtocolMeetingId = MeetingId { id: value }.try_into()?;
+    Ok(serde_wasm_bindgen::to_value(&MeetingId::from(id))?)
+}
+
+/// Generate a random session identifier.
+#[wasm_bindgen(js_name = "generateSessionId")]
+pub fn generate_session_id() -> Result<JsValue, JsError> {
+    let mut id = [0u8; 32];
+    OsRng.fill_bytes(&mut id);
+    Ok(serde_wasm_bindgen::to_value(&SessionId::from(id))?)
+}
+
+/// Parse a session identifier.
+///
+/// Accepts 32 bytes of hex with an optional `0x` prefix
+/// and returns the identifier in the canonical encoding.
+#[wasm_bindgen(js_name = "parseSessionId")]
+pub fn parse_session_id(value: String) -> Result<JsValue, JsError> {
+    let id: [u8; 32] = SessionId { id: value }.try_into()?;
+    Ok(serde_wasm_bindgen::to_value(&SessionId::from(id))?)
+}
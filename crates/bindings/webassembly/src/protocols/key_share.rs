@@ -0,0 +1,47 @@
+//! Encode, decode and encrypt key shares.
+//!
+//! Encoded key shares use the same JSON format as the
+//! keystore for the Rust libraries.
+use polysig_driver::{encrypt::EncryptedKeyShare, KeyShare};
+use wasm_bindgen::prelude::*;
+
+/// Encode a key share as JSON.
+#[wasm_bindgen(js_name = "encodeKeyShare")]
+pub fn encode_key_share(
+    key_share: JsValue,
+) -> Result<String, JsError> {
+    let key_share: KeyShare =
+        serde_wasm_bindgen::from_value(key_share)?;
+    Ok(key_share.encode()?)
+}
+
+/// Decode a key share from JSON.
+#[wasm_bindgen(js_name = "decodeKeyShare")]
+pub fn decode_key_share(value: String) -> Result<JsValue, JsError> {
+    let key_share = KeyShare::decode(&value)?;
+    Ok(serde_wasm_bindgen::to_value(&key_share)?)
+}
+
+/// Encrypt a key share using a 32 byte key.
+#[wasm_bindgen(js_name = "encryptKeyShare")]
+pub fn encrypt_key_share(
+    key_share: JsValue,
+    key: Vec<u8>,
+) -> Result<JsValue, JsError> {
+    let key_share: KeyShare =
+        serde_wasm_bindgen::from_value(key_share)?;
+    let encrypted = EncryptedKeyShare::encrypt(&key_share, &key)?;
+    Ok(serde_wasm_bindgen::to_value(&encrypted)?)
+}
+
+/// Decrypt a key share using a 32 byte key.
+#[wasm_bindgen(js_name = "decryptKeyShare")]
+pub fn decrypt_key_share(
+    encrypted: JsValue,
+    key: Vec<u8>,
+) -> Result<JsValue, JsError> {
+    let encrypted: EncryptedKeyShare =
+        serde_wasm_bindgen::from_value(encrypted)?;
+    let key_share = encrypted.decrypt(&key)?;
+    Ok(serde_wasm_bindgen::to_value(&key_share)?)
+}
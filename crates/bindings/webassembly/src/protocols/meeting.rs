@@ -1,9 +1,11 @@
 //! Bindings for meeting points.
 use polysig_client::meeting;
-use polysig_protocol::{MeetingId, PublicKeys, UserId};
+use polysig_protocol::{PublicKeys, UserId};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 
+use super::ids::{self, MeetingId};
+
 /// Create and join meeting rooms.
 #[wasm_bindgen]
 pub struct MeetingRoom {
@@ -19,6 +21,9 @@ impl MeetingRoom {
     }
 
     /// Create a meeting room used to exchange public keys.
+    ///
+    /// Resolves to the meeting identifier which the initiator
+    /// shares with the other participants.
     pub fn create(
         &self,
         identifiers: JsValue,
@@ -32,6 +37,7 @@ impl MeetingRoom {
         let fut = async move {
             let meeting_id =
                 meeting::create(&url, identifiers, initiator).await?;
+            let meeting_id: MeetingId = meeting_id.into();
             Ok(serde_wasm_bindgen::to_value(&meeting_id)?)
         };
         Ok(future_to_promise(fut).into())
@@ -40,13 +46,12 @@ impl MeetingRoom {
     /// Join a meeting room used to exchange public keys.
     pub fn join(
         &self,
-        meeting_id: String,
+        meeting_id: JsValue,
         user_id: Vec<u8>,
         data: JsValue,
     ) -> Result<JsValue, JsError> {
         let url = self.url.clone();
-        let meeting_id: MeetingId =
-            meeting_id.parse().map_err(JsError::from)?;
+        let meeting_id = ids::meeting_id(meeting_id)?;
         let user_id = parse_user_id(user_id)?;
         let data: PublicKeys = serde_wasm_bindgen::from_value(data)?;
 
@@ -4,5 +4,20 @@ pub mod cggmp;
 #[cfg(feature = "frost")]
 pub mod frost;
 
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub mod ids;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub mod key_share;
+
 #[cfg(any(feature = "cggmp", feature = "frost"))]
 pub mod meeting;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+mod cancel;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+mod progress;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub mod worker;
@@ -0,0 +1,29 @@
+//! Forward ceremony progress events to a callback.
+use futures::StreamExt;
+use polysig_client::{progress_channel, SessionOptions};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
+
+/// Call the callback with the progress events for a session.
+///
+/// Events are plain objects with a `type` field, for
+/// example `{ type: "round", round: 2 }`.
+pub(crate) fn observe(
+    options: &mut SessionOptions,
+    callback: Option<js_sys::Function>,
+) {
+    let Some(callback) = callback else {
+        return;
+    };
+    let (progress, mut events) = progress_channel();
+    options.progress = Some(progress);
+    spawn_local(async move {
+        while let Some(event) = events.next().await {
+            if let Ok(event) = serde_wasm_bindgen::to_value(&event) {
+                // Exceptions thrown by the callback
+                // must not abort the ceremony
+                let _ = callback.call1(&JsValue::NULL, &event);
+            }
+        }
+    });
+}
@@ -0,0 +1,258 @@
+//! Run ceremonies in a Web Worker.
+//!
+//! Creating a session handle is separate from running the
+//! ceremony so the protocol computation does not block the
+//! main thread. Call `createSessionHandle()` on the main thread
+//! and transfer the returned `ArrayBuffer` to a worker which
+//! calls `runSession()`; the promise resolves to an
+//! `ArrayBuffer` that is transferred back and decoded with
+//! `decodeSessionResult()`.
+use js_sys::{Array, ArrayBuffer, Object, Uint8Array};
+use polysig_client::SessionOptions;
+use polysig_driver::KeyShare;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+/// Protocol for a session handle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Protocol {
+    #[cfg(feature = "cggmp")]
+    Cggmp,
+    #[cfg(feature = "frost-ed25519")]
+    FrostEd25519,
+    #[cfg(feature = "frost-ristretto255")]
+    FrostRistretto255,
+    #[cfg(feature = "frost-secp256k1")]
+    FrostSecp256k1,
+    #[cfg(feature = "frost-secp256k1-tr")]
+    FrostSecp256k1Tr,
+}
+
+/// Ceremony for a session handle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Ceremony {
+    Dkg,
+    Sign,
+}
+
+/// Everything required to run a ceremony.
+///
+/// Party options depend upon the protocol so they are
+/// validated when the ceremony runs.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionHandle {
+    protocol: Protocol,
+    ceremony: Ceremony,
+    options: SessionOptions,
+    party: Value,
+    signer: Vec<u8>,
+    /// Key share for signing.
+    #[serde(default)]
+    key_share: Option<KeyShare>,
+    /// Message to sign.
+    #[serde(default)]
+    message: Vec<u8>,
+    /// Identifiers for the FROST protocols.
+    #[serde(default)]
+    identifiers: Vec<u16>,
+    /// Session identifier for the CGGMP protocol.
+    #[serde(default)]
+    session_id: Option<super::ids::SessionId>,
+}
+
+/// Create a session handle that can be transferred to a worker.
+///
+/// The handle is an object with the `protocol` (for example
+/// `"cggmp"` or `"frost-ed25519"`), the `ceremony` (`"dkg"`
+/// or `"sign"`), the session `options`, `party` options and
+/// `signer` bytes. Signing requires the `keyShare` and the
+/// `message` bytes; for CGGMP the message is the 32 byte
+/// prehash. FROST ceremonies require the `identifiers` and
+/// CGGMP ceremonies the `sessionId`.
+#[wasm_bindgen(js_name = "createSessionHandle")]
+pub fn create_session_handle(
+    handle: JsValue,
+) -> Result<ArrayBuffer, JsError> {
+    let handle: SessionHandle =
+        serde_json::from_value(into_json(&handle)?)?;
+    if let (Ceremony::Sign, None) =
+        (handle.ceremony, &handle.key_share)
+    {
+        return Err(JsError::new("key share is required to sign"));
+    }
+    #[cfg(feature = "cggmp")]
+    if let Protocol::Cggmp = handle.protocol {
+        let session_id =
+            handle.session_id.clone().ok_or_else(|| {
+                JsError::new("session id is required for CGGMP")
+            })?;
+        let _: [u8; 32] = session_id.try_into()?;
+    }
+    Ok(into_buffer(&serde_json::to_vec(&handle)?))
+}
+
+/// Run the ceremony for a session handle.
+///
+/// Intended to be called from a Web Worker; the promise
+/// resolves to an `ArrayBuffer` containing the encoded result.
+#[wasm_bindgen(js_name = "runSession")]
+pub fn run_session(
+    handle: ArrayBuffer,
+    progress: Option<js_sys::Function>,
+    signal: Option<web_sys::AbortSignal>,
+) -> Result<JsValue, JsError> {
+    let handle: SessionHandle =
+        serde_json::from_slice(&Uint8Array::new(&handle).to_vec())?;
+    let promise: js_sys::Promise =
+        start(handle, progress, signal)?.unchecked_into();
+    let fut = async move {
+        let result = JsFuture::from(promise).await?;
+        let result = serde_json::to_vec(&into_json(&result)?)
+            .map_err(JsError::from)?;
+        Ok(into_buffer(&result).into())
+    };
+    Ok(future_to_promise(fut).into())
+}
+
+/// Decode the result of a ceremony run in a worker.
+///
+/// Resolves to the same value as calling the protocol
+/// function directly; a key share for key generation and
+/// the signature for signing.
+#[wasm_bindgen(js_name = "decodeSessionResult")]
+pub fn decode_session_result(
+    result: ArrayBuffer,
+) -> Result<JsValue, JsError> {
+    let value: Value =
+        serde_json::from_slice(&Uint8Array::new(&result).to_vec())?;
+    Ok(value.serialize(&Serializer::json_compatible())?)
+}
+
+/// Start the ceremony using the protocol bindings.
+fn start(
+    handle: SessionHandle,
+    progress: Option<js_sys::Function>,
+    signal: Option<web_sys::AbortSignal>,
+) -> Result<JsValue, JsError> {
+    let serializer = Serializer::json_compatible();
+    let options = handle.options.serialize(&serializer)?;
+    let party = handle.party.serialize(&serializer)?;
+    let key_share = handle.key_share.serialize(&serializer)?;
+    #[cfg(feature = "cggmp")]
+    let session_id = handle.session_id.serialize(&serializer)?;
+
+    #[allow(unused_macros)]
+    macro_rules! frost {
+        ($protocol:ty) => {
+            match handle.ceremony {
+                Ceremony::Dkg => <$protocol>::dkg(
+                    options,
+                    party,
+                    handle.signer,
+                    handle.identifiers,
+                    progress,
+                    signal,
+                ),
+                Ceremony::Sign => {
+                    <$protocol>::new(options, key_share)?.sign(
+                        party,
+                        handle.signer,
+                        handle.identifiers,
+                        handle.message,
+                        progress,
+                        signal,
+                    )
+                }
+            }
+        };
+    }
+
+    match handle.protocol {
+        #[cfg(feature = "cggmp")]
+        Protocol::Cggmp => {
+            use super::cggmp::CggmpProtocol;
+            match handle.ceremony {
+                Ceremony::Dkg => CggmpProtocol::dkg(
+                    options,
+                    party,
+                    session_id,
+                    handle.signer,
+                    progress,
+                    signal,
+                ),
+                Ceremony::Sign => CggmpProtocol::new(
+                    options, key_share,
+                )?
+                .sign(
+                    party,
+                    session_id,
+                    handle.signer,
+                    polysig_protocol::hex::encode(&handle.message),
+                    progress,
+                    signal,
+                ),
+            }
+        }
+        #[cfg(feature = "frost-ed25519")]
+        Protocol::FrostEd25519 => {
+            frost!(super::frost::ed25519::FrostEd25519Protocol)
+        }
+        #[cfg(feature = "frost-ristretto255")]
+        Protocol::FrostRistretto255 => {
+            frost!(
+                super::frost::ristretto255::FrostRistretto255Protocol
+            )
+        }
+        #[cfg(feature = "frost-secp256k1")]
+        Protocol::FrostSecp256k1 => {
+            frost!(super::frost::secp256k1::FrostSecp256K1Protocol)
+        }
+        #[cfg(feature = "frost-secp256k1-tr")]
+        Protocol::FrostSecp256k1Tr => {
+            frost!(
+                super::frost::secp256k1_tr::FrostSecp256K1TrProtocol
+            )
+        }
+    }
+}
+
+/// Copy bytes into a new `ArrayBuffer` owned by Javascript
+/// so it can be transferred.
+fn into_buffer(bytes: &[u8]) -> ArrayBuffer {
+    Uint8Array::from(bytes).buffer()
+}
+
+/// Convert a Javascript value to JSON.
+///
+/// Typed arrays are converted to arrays of numbers so
+/// values may be encoded without the Javascript
+/// `JSON.stringify()` which does not support them.
+fn into_json(value: &JsValue) -> Result<Value, JsError> {
+    if let Some(bytes) = value.dyn_ref::<Uint8Array>() {
+        return Ok(bytes.to_vec().into());
+    }
+    if let Some(array) = value.dyn_ref::<Array>() {
+        let mut values = Vec::with_capacity(array.length() as usize);
+        for value in array.iter() {
+            values.push(into_json(&value)?);
+        }
+        return Ok(Value::Array(values));
+    }
+    if value.is_object() && !value.is_function() {
+        let mut map = Map::new();
+        for entry in Object::entries(value.unchecked_ref()).iter() {
+            let entry: Array = entry.unchecked_into();
+            if let Some(key) = entry.get(0).as_string() {
+                map.insert(key, into_json(&entry.get(1))?);
+            }
+        }
+        return Ok(Value::Object(map));
+    }
+    Ok(serde_wasm_bindgen::from_value(value.clone())?)
+}
@@ -18,7 +18,9 @@ impl EddsaSigner {
         let signing_key =
             eddsa::EddsaSigner::from_bytes(&signing_key);
         Ok(Self {
-            inner: eddsa::EddsaSigner::new(Cow::Owned(signing_key)),
+            inner: eddsa::EddsaSigner::new(Cow::Owned(
+                signing_key.into(),
+            )),
         })
     }
 
@@ -27,6 +29,20 @@ impl EddsaSigner {
         eddsa::EddsaSigner::random().to_bytes().as_slice().to_vec()
     }
 
+    /// Derive a signing key from a seed using SLIP-0010.
+    ///
+    /// Every component of the path must be hardened,
+    /// for example `m/44'/501'/0'/0'`.
+    #[wasm_bindgen(js_name = "deriveFromSeed")]
+    pub fn derive_from_seed(
+        seed: &[u8],
+        path: &str,
+    ) -> Result<Vec<u8>, JsError> {
+        let signing_key =
+            eddsa::EddsaSigner::derive_from_seed(seed, path)?;
+        Ok(signing_key.to_bytes().as_slice().to_vec())
+    }
+
     /// Sign a message.
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
         let result = self.inner.sign(message);
@@ -0,0 +1,30 @@
+//! BIP-39 mnemonics used to derive signing keys.
+use polysig_driver::signers::mnemonic;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+/// Generate a random mnemonic phrase.
+///
+/// The word count must be 12, 15, 18, 21 or 24.
+#[wasm_bindgen(js_name = "generateMnemonic")]
+pub fn generate_mnemonic(
+    word_count: usize,
+) -> Result<String, JsError> {
+    Ok(mnemonic::generate(word_count)?.to_string())
+}
+
+/// Normalize a mnemonic phrase and verify the checksum.
+#[wasm_bindgen(js_name = "parseMnemonic")]
+pub fn parse_mnemonic(phrase: &str) -> Result<String, JsError> {
+    Ok(mnemonic::parse(phrase)?.to_string())
+}
+
+/// Compute the seed for a mnemonic phrase.
+///
+/// Use an empty string when there is no passphrase.
+#[wasm_bindgen(js_name = "mnemonicToSeed")]
+pub fn mnemonic_to_seed(
+    phrase: &str,
+    passphrase: &str,
+) -> Result<Vec<u8>, JsError> {
+    Ok(mnemonic::to_seed(phrase, passphrase)?.to_vec())
+}
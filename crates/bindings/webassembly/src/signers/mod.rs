@@ -6,3 +6,10 @@ pub mod eddsa;
 
 #[cfg(feature = "schnorr")]
 pub mod schnorr;
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+pub mod mnemonic;
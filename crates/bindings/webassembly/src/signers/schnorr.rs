@@ -18,7 +18,7 @@ impl SchnorrSigner {
             schnorr::SchnorrSigner::from_slice(signing_key)?;
         Ok(Self {
             inner: schnorr::SchnorrSigner::new(Cow::Owned(
-                signing_key,
+                signing_key.into(),
             )),
         })
     }
@@ -31,6 +31,36 @@ impl SchnorrSigner {
             .to_vec()
     }
 
+    /// Derive a signing key from a seed and BIP-32 derivation
+    /// path, for example `m/86'/0'/0'/0/0`.
+    #[wasm_bindgen(js_name = "deriveFromSeed")]
+    pub fn derive_from_seed(
+        seed: &[u8],
+        path: &str,
+    ) -> Result<Vec<u8>, JsError> {
+        let path: schnorr::DerivationPath = path.parse()?;
+        let signing_key =
+            schnorr::SchnorrSigner::derive_from_seed(seed, &path)?;
+        Ok(signing_key.to_bytes().as_slice().to_vec())
+    }
+
+    /// Create a BIP-86 derivation path.
+    #[wasm_bindgen(js_name = "bip86Path")]
+    pub fn bip86_path(
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        address_index: u32,
+    ) -> Result<String, JsError> {
+        let path = schnorr::SchnorrSigner::bip86_path(
+            coin_type,
+            account,
+            change,
+            address_index,
+        )?;
+        Ok(path.to_string())
+    }
+
     /// Sign a message.
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
         let result = self.inner.sign(message);
@@ -0,0 +1,323 @@
+//! Browser integration tests for the web client.
+//!
+//! Requires a relay server listening on `ws://127.0.0.1:8008`
+//! using the test server key and a meeting server listening
+//! on `ws://127.0.0.1:8009`; the `wasm-test` task starts both
+//! servers and runs the tests in headless Chrome:
+//!
+//! ```text
+//! cargo make wasm-test
+//! ```
+//!
+//! Set `POLYSIG_RELAY_SERVER` or `POLYSIG_MEETING_SERVER` when
+//! compiling the tests to use other servers.
+#![cfg(all(
+    target_arch = "wasm32",
+    target_os = "unknown",
+    feature = "cggmp"
+))]
+
+use futures::{
+    future::{select, try_join_all, Either},
+    StreamExt,
+};
+use polysig_client::{
+    cggmp::{dkg, sign},
+    meeting, Client, ClientOptions, EventStream, NetworkTransport,
+    ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    cggmp::{Participant, PartyOptions},
+    k256::ecdsa::{
+        signature::hazmat::PrehashVerifier, RecoveryId, Signature,
+        SigningKey, VerifyingKey,
+    },
+    synedrion::{SessionId, TestParams, ThresholdKeyShare},
+};
+use polysig_protocol::{
+    hex, Event, Keypair, Parameters, PublicKeys, UserId,
+};
+use rand::{rngs::OsRng, Rng};
+use std::collections::BTreeSet;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type KeyShare = ThresholdKeyShare<TestParams, VerifyingKey>;
+
+const RELAY_SERVER: &str = match option_env!("POLYSIG_RELAY_SERVER") {
+    Some(server) => server,
+    None => "ws://127.0.0.1:8008",
+};
+
+const MEETING_SERVER: &str =
+    match option_env!("POLYSIG_MEETING_SERVER") {
+        Some(server) => server,
+        None => "ws://127.0.0.1:8009",
+    };
+
+const SERVER_PUBLIC_KEY: &str = include_str!(
+    "../../../integration_tests/tests/server_public_key.txt"
+);
+
+fn server() -> ServerOptions {
+    ServerOptions {
+        server_url: RELAY_SERVER.to_owned(),
+        server_public_key: hex::decode(SERVER_PUBLIC_KEY.trim())
+            .unwrap(),
+        pattern: None,
+    }
+}
+
+fn session_options(
+    keypair: Keypair,
+    parameters: Parameters,
+) -> SessionOptions {
+    SessionOptions {
+        keypair,
+        server: server(),
+        parameters,
+        policy: None,
+        progress: None,
+        cancel: None,
+        key_share_policy: None,
+        signing_context: None,
+        attestation: None,
+        round_timeout: None,
+        keepalive: None,
+    }
+}
+
+async fn new_client(keypair: Keypair) -> (Client, EventStream) {
+    let server = server();
+    let options = ClientOptions {
+        keypair: Some(keypair),
+        server_public_key: Some(server.server_public_key),
+        ..Default::default()
+    };
+    let url = options.url(&server.server_url);
+    let (mut client, event_loop) =
+        Client::new(&url, options).await.unwrap();
+    client.connect().await.unwrap();
+    (client, event_loop.run())
+}
+
+async fn wait_for(
+    stream: &mut EventStream,
+    predicate: impl Fn(&Event) -> bool,
+) -> Event {
+    while let Some(event) = stream.next().await {
+        let event = event.unwrap();
+        if predicate(&event) {
+            return event;
+        }
+    }
+    panic!("event stream closed");
+}
+
+/// Wait for an event while driving the event loop of a peer.
+async fn wait_with_peer(
+    stream: &mut EventStream,
+    peer: &mut EventStream,
+    predicate: impl Fn(&Event) -> bool,
+) -> Event {
+    let wait = Box::pin(wait_for(stream, predicate));
+    let drive = Box::pin(wait_for(peer, |_| false));
+    match select(wait, drive).await {
+        Either::Left((event, _)) => event,
+        Either::Right(_) => unreachable!(),
+    }
+}
+
+/// Sends a payload larger than a noise protocol message so
+/// it is split into chunks and joined by the peer.
+#[wasm_bindgen_test]
+async fn web_peer_channel_chunks() {
+    let sender_key = Keypair::generate().unwrap();
+    let receiver_key = Keypair::generate().unwrap();
+
+    let (mut sender, mut sender_stream) =
+        new_client(sender_key.clone()).await;
+    let (mut receiver, mut receiver_stream) =
+        new_client(receiver_key.clone()).await;
+    wait_for(&mut sender_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await;
+    wait_for(&mut receiver_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await;
+
+    receiver
+        .connect_peer(sender_key.public_key())
+        .await
+        .unwrap();
+    wait_with_peer(
+        &mut receiver_stream,
+        &mut sender_stream,
+        |event| matches!(event, Event::PeerConnected { .. }),
+    )
+    .await;
+
+    let payload: Vec<u8> =
+        (0..131_072).map(|i| (i % 251) as u8).collect();
+    sender
+        .send_blob(receiver_key.public_key(), payload.clone(), None)
+        .await
+        .unwrap();
+
+    let received = wait_with_peer(
+        &mut receiver_stream,
+        &mut sender_stream,
+        |event| matches!(event, Event::BinaryMessage { .. }),
+    )
+    .await;
+    let Event::BinaryMessage { message, .. } = received else {
+        unreachable!();
+    };
+    assert_eq!(payload, message);
+
+    sender.close().await.unwrap();
+    receiver.close().await.unwrap();
+}
+
+/// Exchanges public keys using a meeting point.
+#[wasm_bindgen_test]
+async fn web_meeting_point() {
+    let user_ids: Vec<UserId> =
+        (1..=3u8).map(|i| UserId::from([i; 32])).collect();
+    let meeting_id = meeting::create(
+        MEETING_SERVER,
+        user_ids.clone(),
+        user_ids[0],
+    )
+    .await
+    .unwrap();
+
+    let results = try_join_all(user_ids.iter().enumerate().map(
+        |(index, user_id)| {
+            meeting::join(
+                MEETING_SERVER,
+                meeting_id,
+                *user_id,
+                PublicKeys {
+                    public_key: vec![index as u8 + 1],
+                    verifying_key: vec![index as u8 + 1],
+                    associated_data: None,
+                },
+            )
+        },
+    ))
+    .await
+    .unwrap();
+
+    assert_eq!(3, results[0].len());
+    assert!(results.windows(2).all(|w| w[0] == w[1]));
+}
+
+/// CGGMP distributed key generation followed by signing (2-of-3).
+#[wasm_bindgen_test]
+async fn web_cggmp_dkg_sign_2_3() {
+    let parameters = Parameters {
+        parties: 3,
+        threshold: 2,
+    };
+    let signers: Vec<SigningKey> =
+        (0..3).map(|_| SigningKey::random(&mut OsRng)).collect();
+    let verifiers: Vec<VerifyingKey> =
+        signers.iter().map(|s| *s.verifying_key()).collect();
+
+    // Key generation
+    let keypairs: Vec<Keypair> =
+        (0..3).map(|_| Keypair::generate().unwrap()).collect();
+    let public_keys: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+    let seed: [u8; 32] = OsRng.gen();
+    let session_id = SessionId::from_seed(&seed);
+    let key_shares: Vec<KeyShare> = try_join_all(
+        keypairs.into_iter().zip(signers.iter()).enumerate().map(
+            |(index, (keypair, signer))| {
+                let party = PartyOptions::new(
+                    public_keys[index].clone(),
+                    public_keys.clone(),
+                    index == 0,
+                    verifiers.clone(),
+                )
+                .unwrap();
+                let participant = Participant::new(
+                    signer.clone(),
+                    *signer.verifying_key(),
+                    party,
+                )
+                .unwrap();
+                dkg::<TestParams>(
+                    session_options(keypair, parameters),
+                    participant,
+                    session_id.clone(),
+                )
+            },
+        ),
+    )
+    .await
+    .unwrap();
+    let verifying_key = key_shares[0].verifying_key().clone();
+
+    // Sign with the first and last parties
+    let selected = [0, 2];
+    let keypairs: Vec<Keypair> = selected
+        .iter()
+        .map(|_| Keypair::generate().unwrap())
+        .collect();
+    let public_keys: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+    let selected_verifiers: Vec<VerifyingKey> =
+        selected.iter().map(|i| verifiers[*i]).collect();
+    let message: [u8; 32] = OsRng.gen();
+    let seed: [u8; 32] = OsRng.gen();
+    let session_id = SessionId::from_seed(&seed);
+
+    let mut ceremonies = Vec::new();
+    for (index, (keypair, party_index)) in
+        keypairs.into_iter().zip(selected).enumerate()
+    {
+        let signer = signers[party_index].clone();
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            selected_verifiers.clone(),
+        )
+        .unwrap();
+        let participant = Participant::new(
+            signer.clone(),
+            *signer.verifying_key(),
+            party,
+        )
+        .unwrap();
+        let parties: BTreeSet<VerifyingKey> =
+            participant.party().verifiers().iter().cloned().collect();
+        let key_share =
+            key_shares[party_index].to_key_share(&parties);
+        let options = session_options(keypair, parameters);
+        let session_id = session_id.clone();
+        ceremonies.push(async move {
+            sign(
+                options,
+                participant,
+                session_id,
+                &key_share,
+                &message,
+            )
+            .await
+        });
+    }
+    let signatures = try_join_all(ceremonies).await.unwrap();
+
+    assert_eq!(2, signatures.len());
+    for signature in signatures {
+        let (signature, _): (Signature, RecoveryId) =
+            signature.try_into().unwrap();
+        verifying_key.verify_prehash(&message, &signature).unwrap();
+    }
+}
@@ -0,0 +1,243 @@
+//! Remote attestation of co-signer environments.
+//!
+//! Assign an [Attestation] to the client options (or the session
+//! options for the high-level protocol functions) to exchange
+//! attestation evidence with every peer when a session starts.
+//!
+//! Each participant creates evidence (for example an SGX, SEV or
+//! TPM quote) bound to the session and their transport public
+//! key and sends it to every peer once the peer is connected. A
+//! peer connection is only registered with the server after the
+//! evidence from the peer has been verified so the session never
+//! becomes active and no signature shares are contributed unless
+//! every participant accepted the evidence of every other
+//! participant.
+//!
+//! Every participant in a session must assign an attestation;
+//! peers that do not send evidence are never registered and the
+//! session will not become active.
+use crate::{Error, NetworkTransport, Result, Transport};
+use async_trait::async_trait;
+use polysig_protocol::{hex, Event, SessionId, SessionState};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+const DOMAIN: &[u8] = b"polysig-attestation";
+
+/// Evidence that a participant runs in an approved environment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Evidence {
+    /// Format of the quote, eg: `sgx-dcap`, `sev-snp` or `tpm`.
+    pub format: String,
+    /// Encoded quote.
+    #[serde(with = "hex::serde")]
+    pub quote: Vec<u8>,
+}
+
+/// Create and verify attestation evidence.
+#[async_trait]
+pub trait Attestation: Send + Sync {
+    /// Create evidence for this participant.
+    ///
+    /// The report data must be embedded in the quote so the
+    /// evidence cannot be replayed in another session or
+    /// by another participant.
+    async fn attest(
+        &self,
+        report_data: &[u8; 32],
+    ) -> Result<Evidence>;
+
+    /// Verify the evidence sent by a peer.
+    ///
+    /// Implementations must check the report data embedded in
+    /// the quote matches the expected report data and that the
+    /// measurements belong to an approved environment.
+    async fn verify(
+        &self,
+        peer_key: &[u8],
+        evidence: &Evidence,
+        report_data: &[u8; 32],
+    ) -> Result<()>;
+}
+
+/// Report data that binds evidence to a session and the
+/// transport public key of a participant.
+pub fn report_data(
+    session_id: &SessionId,
+    public_key: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hasher.update(session_id.as_bytes());
+    hasher.update(public_key);
+    hasher.finalize().into()
+}
+
+/// Message sent to peers during the attestation exchange.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum AttestationMessage {
+    /// Evidence for the sender.
+    Attestation(Evidence),
+    /// Sender verified the evidence of the receiver.
+    AttestationAccepted,
+}
+
+/// Exchange attestation evidence with session peers.
+///
+/// Only one side of each peer connection registers the
+/// connection with the server so a peer is attested once we
+/// verified the evidence of the peer and the peer accepted
+/// our evidence.
+pub(crate) struct AttestationExchange {
+    attestation: Arc<dyn Attestation>,
+    evidence: Option<Evidence>,
+    connected: HashSet<Vec<u8>>,
+    received: HashMap<Vec<u8>, (SessionId, Evidence)>,
+    verified: HashSet<Vec<u8>>,
+    accepted: HashSet<Vec<u8>>,
+}
+
+impl AttestationExchange {
+    /// Create an attestation exchange.
+    pub fn new(attestation: Arc<dyn Attestation>) -> Self {
+        Self {
+            attestation,
+            evidence: None,
+            connected: HashSet::new(),
+            received: HashMap::new(),
+            verified: HashSet::new(),
+            accepted: HashSet::new(),
+        }
+    }
+
+    /// Send our evidence to a connected peer.
+    ///
+    /// Returns whether the peer is attested.
+    pub async fn peer_connected(
+        &mut self,
+        transport: &mut Transport,
+        session: &SessionState,
+        peer_key: &[u8],
+    ) -> Result<bool> {
+        let evidence = match &self.evidence {
+            Some(evidence) => evidence.clone(),
+            None => {
+                let evidence = self
+                    .attestation
+                    .attest(&report_data(
+                        &session.session_id,
+                        transport.public_key(),
+                    ))
+                    .await?;
+                self.evidence = Some(evidence.clone());
+                evidence
+            }
+        };
+        transport
+            .send_json(
+                peer_key,
+                &AttestationMessage::Attestation(evidence),
+                Some(session.session_id),
+            )
+            .await?;
+        self.connected.insert(peer_key.to_vec());
+        self.verify(transport, session, peer_key).await
+    }
+
+    /// Handle an attestation message sent by a peer.
+    ///
+    /// Evidence may arrive before the session is known or
+    /// before the peer is connected so it is kept until
+    /// both are available.
+    ///
+    /// Returns the public key of the peer when the peer
+    /// is attested.
+    pub async fn message_received(
+        &mut self,
+        transport: &mut Transport,
+        session: Option<&SessionState>,
+        event: Event,
+    ) -> Result<Option<Vec<u8>>> {
+        let Event::JsonMessage {
+            peer_key,
+            message,
+            session_id: Some(session_id),
+        } = event
+        else {
+            return Ok(None);
+        };
+        let Ok(message) = message.deserialize::<AttestationMessage>()
+        else {
+            return Ok(None);
+        };
+        match message {
+            AttestationMessage::Attestation(evidence) => {
+                self.received
+                    .insert(peer_key.clone(), (session_id, evidence));
+            }
+            AttestationMessage::AttestationAccepted => {
+                self.accepted.insert(peer_key.clone());
+            }
+        }
+        let attested = match session {
+            Some(session) if self.connected.contains(&peer_key) => {
+                self.verify(transport, session, &peer_key).await?
+            }
+            _ => false,
+        };
+        Ok(attested.then_some(peer_key))
+    }
+
+    /// Verify evidence received from a peer and notify the
+    /// peer that the evidence was accepted.
+    ///
+    /// Returns whether the peer is attested.
+    async fn verify(
+        &mut self,
+        transport: &mut Transport,
+        session: &SessionState,
+        peer_key: &[u8],
+    ) -> Result<bool> {
+        if let Some((session_id, evidence)) =
+            self.received.remove(peer_key)
+        {
+            if session_id != session.session_id {
+                return Err(Error::AttestationRejected(
+                    hex::encode(peer_key),
+                    "session identifier mismatch".to_owned(),
+                ));
+            }
+            self.attestation
+                .verify(
+                    peer_key,
+                    &evidence,
+                    &report_data(&session.session_id, peer_key),
+                )
+                .await
+                .map_err(|e| {
+                    Error::AttestationRejected(
+                        hex::encode(peer_key),
+                        e.to_string(),
+                    )
+                })?;
+            transport
+                .send_json(
+                    peer_key,
+                    &AttestationMessage::AttestationAccepted,
+                    Some(session.session_id),
+                )
+                .await?;
+            self.verified.insert(peer_key.to_vec());
+            return Ok(self.accepted.remove(peer_key));
+        }
+        // Acceptance received after the evidence was verified
+        Ok(self.verified.contains(peer_key)
+            && self.accepted.remove(peer_key))
+    }
+}
@@ -0,0 +1,61 @@
+//! Cancellation for ceremonies.
+//!
+//! Assign a [CancellationToken] to the client options (or the
+//! session options for the high-level protocol functions) and
+//! call [CancellationToken::cancel] to abort a ceremony. The
+//! event loop closes any sessions created by the client and
+//! the socket before yielding [Error::Cancelled](crate::Error).
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Token used to cancel a ceremony.
+///
+/// Clones share the same cancellation state.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    state: Arc<watch::Sender<bool>>,
+}
+
+impl CancellationToken {
+    /// Create a new cancellation token.
+    pub fn new() -> Self {
+        let (state, _) = watch::channel(false);
+        Self {
+            state: Arc::new(state),
+        }
+    }
+
+    /// Cancel the ceremony.
+    pub fn cancel(&self) {
+        self.state.send_replace(true);
+    }
+
+    /// Whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.state.borrow()
+    }
+
+    /// Wait until the token is cancelled.
+    pub async fn cancelled(&self) {
+        let mut receiver = self.state.subscribe();
+        // Sender is owned by the token so the channel
+        // cannot be closed while waiting
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wait until an optional token is cancelled.
+///
+/// Never completes when no token is assigned.
+pub(crate) async fn cancelled(token: Option<CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
@@ -0,0 +1,27 @@
+//! Capabilities of the client build.
+pub use polysig_driver::{Capabilities, CurveBackend};
+
+/// Capabilities compiled into this build.
+///
+/// Extends the capabilities of the driver library with the
+/// optional client features available on this platform.
+pub fn capabilities() -> Capabilities {
+    let native =
+        cfg!(not(all(target_arch = "wasm32", target_os = "unknown")));
+    let features = [
+        ("faults", native && cfg!(feature = "faults")),
+        ("http2", native && cfg!(feature = "http2")),
+        ("journal", native && cfg!(feature = "journal")),
+        ("log-filter", native && cfg!(feature = "log-filter")),
+        ("record", native && cfg!(feature = "record")),
+        ("rustls-tls", native && cfg!(feature = "rustls-tls")),
+    ];
+    let mut capabilities = polysig_driver::capabilities();
+    capabilities.features.extend(
+        features
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_owned()),
+    );
+    capabilities
+}
@@ -13,14 +13,84 @@ macro_rules! client_impl {
             broadcast: bool,
             session_id: Option<SessionId>,
         ) -> Result<()> {
-            let mut peers = self.peers.write().await;
-            if let Some(peer) = peers.get_mut(public_key.as_ref()) {
-                let request = encrypt_peer_channel(
-                    public_key, peer, payload, encoding, broadcast,
-                    session_id,
-                )
-                .await?;
+            let peer = {
+                let peers = self.peers.read().await;
+                peers.get(public_key.as_ref()).cloned()
+            };
+            if let Some(peer) = peer {
+                #[cfg(all(
+                    feature = "record",
+                    not(all(
+                        target_arch = "wasm32",
+                        target_os = "unknown"
+                    ))
+                ))]
+                if let Some(recorder) = &self.options.recorder {
+                    recorder.outgoing(
+                        public_key.as_ref(),
+                        payload,
+                        encoding,
+                        session_id,
+                    );
+                }
 
+                #[cfg(all(
+                    feature = "faults",
+                    not(all(
+                        target_arch = "wasm32",
+                        target_os = "unknown"
+                    ))
+                ))]
+                if let Some(faults) = &self.options.faults {
+                    let public_key = public_key.as_ref();
+                    let mut state = peer.lock().await;
+                    for _ in 0..faults.copies(public_key) {
+                        let request = encrypt_peer_channel(
+                            public_key, &mut state, payload,
+                            encoding, broadcast, session_id,
+                        )?;
+                        faults.send(
+                            public_key,
+                            &self.outbound_tx,
+                            InternalMessage::Request(request),
+                        )?;
+                    }
+                    return Ok(());
+                }
+
+                // Prefer a direct connection to the peer
+                #[cfg(not(all(
+                    target_arch = "wasm32",
+                    target_os = "unknown"
+                )))]
+                if let Some(direct) = &self.direct {
+                    if direct
+                        .send(
+                            public_key.as_ref(),
+                            payload,
+                            encoding,
+                            broadcast,
+                            session_id,
+                        )
+                        .await?
+                    {
+                        return Ok(());
+                    }
+                }
+
+                let recipient = public_key.as_ref().to_vec();
+                let payload = payload.to_vec();
+                let (_state, request) =
+                    with_peer(&peer, move |state| {
+                        encrypt_peer_channel(
+                            recipient, state, &payload, encoding,
+                            broadcast, session_id,
+                        )
+                    })
+                    .await?;
+
+                // Queue the request before the lock for the
+                // peer is released to preserve message order
                 self.outbound_tx
                     .send(InternalMessage::Request(request))?;
                 Ok(())
@@ -31,6 +101,63 @@ macro_rules! client_impl {
             }
         }
 
+        /// Broadcast a buffer to the other participants in a
+        /// session.
+        ///
+        /// When this client has a group key for the session the
+        /// payload is encrypted once and the server relays it to
+        /// the participants otherwise it is sent to each peer.
+        async fn broadcast(
+            &mut self,
+            session: &SessionState,
+            payload: &[u8],
+            encoding: Encoding,
+        ) -> Result<()> {
+            let public_key = self
+                .options
+                .keypair
+                .as_ref()
+                .unwrap()
+                .public_key()
+                .to_vec();
+            let key = {
+                let groups = self.groups.read().await;
+                groups
+                    .get(&(session.session_id, public_key.clone()))
+                    .cloned()
+            };
+            if let Some(key) = key {
+                let envelope = key.encrypt(
+                    &public_key,
+                    &session.session_id,
+                    payload,
+                    encoding,
+                )?;
+                let request = RequestMessage::Opaque(
+                    OpaqueMessage::GroupMessage {
+                        public_key,
+                        session_id: session.session_id,
+                        envelope,
+                    },
+                );
+                self.outbound_tx
+                    .send(InternalMessage::Request(request))?;
+                Ok(())
+            } else {
+                for peer_key in session.recipients(&public_key) {
+                    self.relay(
+                        &peer_key,
+                        payload,
+                        encoding,
+                        true,
+                        Some(session.session_id),
+                    )
+                    .await?;
+                }
+                Ok(())
+            }
+        }
+
         /// Encrypt a request message and send over the encrypted
         /// server channel.
         async fn request(
@@ -69,6 +196,48 @@ macro_rules! client_impl {
                 .outbound_tx
                 .send(InternalMessage::Buffer(buffer))?)
         }
+
+        /// Notify the progress sender for this client.
+        pub(crate) fn notify(&self, event: crate::ProgressEvent) {
+            #[cfg(all(
+                feature = "faults",
+                not(all(
+                    target_arch = "wasm32",
+                    target_os = "unknown"
+                ))
+            ))]
+            if let crate::ProgressEvent::Round { round } = &event {
+                if self
+                    .options
+                    .faults
+                    .as_ref()
+                    .is_some_and(|faults| faults.sever(*round))
+                {
+                    tracing::debug!(round, "fault sever connection");
+                    let _ =
+                        self.outbound_tx.send(InternalMessage::Close);
+                }
+            }
+
+            if let Some(progress) = &self.options.progress {
+                // Receivers may stop listening at any time
+                let _ = progress.unbounded_send(event);
+            }
+        }
+
+        /// Attestation exchanged with peers for this client.
+        pub(crate) fn attestation(
+            &self,
+        ) -> Option<std::sync::Arc<dyn crate::Attestation>> {
+            self.options.attestation.clone()
+        }
+
+        /// Timeout for each round of a ceremony.
+        pub(crate) fn round_timeout(
+            &self,
+        ) -> Option<std::time::Duration> {
+            self.options.round_timeout
+        }
     };
 }
 
@@ -87,14 +256,26 @@ macro_rules! client_transport_impl {
             /// Perform initial handshake with the server.
             async fn connect(&mut self) -> Result<()> {
                 if self.options.is_encrypted() {
+                    // Session token is encrypted to the server
+                    // static key in the first handshake message
+                    let early = match &self.options.session_token {
+                        Some(token) => {
+                            if !is_zero_rtt(self.options.pattern()) {
+                                return Err(Error::ZeroRttPattern);
+                            }
+                            encode(token).await?
+                        }
+                        None => Vec::new(),
+                    };
+
                     let request = {
                         let mut state = self.server.write().await;
 
                         let (len, payload) = match &mut *state {
                             Some(ProtocolState::Handshake(initiator)) => {
                                 let mut request = vec![0u8; 1024];
-                                let len =
-                                    initiator.write_message(&[], &mut request)?;
+                                let len = initiator
+                                    .write_message(&early, &mut request)?;
                                 (len, request)
                             }
                             _ => return Err(Error::NotHandshakeState),
@@ -133,7 +314,7 @@ macro_rules! client_transport_impl {
                 }
 
                 tracing::debug!(
-                    to = ?hex::encode(public_key.as_ref()),
+                    to = %fingerprint(public_key.as_ref()),
                     "peer handshake initiator"
                 );
 
@@ -142,14 +323,10 @@ macro_rules! client_transport_impl {
                     .local_private_key(self.options.keypair.as_ref().unwrap().private_key())
                     .remote_public_key(public_key.as_ref())
                     .build_initiator()?;
-                let peer_state =
+                let mut peer_state =
                     ProtocolState::Handshake(Box::new(handshake));
 
-                let state = peers
-                    .entry(public_key.as_ref().to_vec())
-                    .or_insert(peer_state);
-
-                let (len, payload) = match state {
+                let (len, payload) = match &mut peer_state {
                     ProtocolState::Handshake(initiator) => {
                         let mut request = vec![0u8; 1024];
                         let len =
@@ -158,6 +335,10 @@ macro_rules! client_transport_impl {
                     }
                     _ => return Err(Error::NotHandshakeState),
                 };
+                peers.insert(
+                    public_key.as_ref().to_vec(),
+                    Arc::new(Mutex::new(peer_state)),
+                );
                 drop(peers);
 
                 let request = RequestMessage::Transparent(
@@ -192,6 +373,28 @@ macro_rules! client_transport_impl {
                 .await
             }
 
+            /// Send a serialized message to a peer via the
+            /// relay service.
+            async fn send_serialized<S>(
+                &mut self,
+                public_key: &[u8],
+                payload: &S,
+                encoding: Encoding,
+                session_id: Option<SessionId>,
+            ) -> Result<()>
+            where
+                S: Serialize + Send + Sync,
+            {
+                self.relay(
+                    public_key,
+                    &JsonMessage::serialize_with(payload, encoding)?,
+                    encoding,
+                    false,
+                    session_id,
+                )
+                .await
+            }
+
             /// Send a binary message to a peer via the relay service.
             async fn send_blob(
                 &mut self,
@@ -209,6 +412,79 @@ macro_rules! client_transport_impl {
                 .await
             }
 
+            /// Create a group key for the broadcasts of this
+            /// client in a session and send it to the other
+            /// participants over the peer channels.
+            async fn create_group_key(
+                &mut self,
+                session: &SessionState,
+            ) -> Result<()> {
+                let key = crate::group::GroupKey::generate()?;
+                let message =
+                    crate::group::GroupKeyMessage::from(&key);
+                let recipients = session.recipients(self.public_key());
+                for peer_key in recipients {
+                    self.send_json(
+                        &peer_key,
+                        &message,
+                        Some(session.session_id),
+                    )
+                    .await?;
+                }
+                let public_key = self.public_key().to_vec();
+                let mut groups = self.groups.write().await;
+                groups.insert((session.session_id, public_key), key);
+                Ok(())
+            }
+
+            /// Broadcast a JSON message to the other participants
+            /// in a session.
+            async fn broadcast_json<S>(
+                &mut self,
+                session: &SessionState,
+                payload: &S,
+            ) -> Result<()>
+            where
+                S: Serialize + Send + Sync,
+            {
+                self.broadcast(
+                    session,
+                    &JsonMessage::serialize(payload)?,
+                    Encoding::Json,
+                )
+                .await
+            }
+
+            /// Broadcast a serialized message to the other
+            /// participants in a session.
+            async fn broadcast_serialized<S>(
+                &mut self,
+                session: &SessionState,
+                payload: &S,
+                encoding: Encoding,
+            ) -> Result<()>
+            where
+                S: Serialize + Send + Sync,
+            {
+                self.broadcast(
+                    session,
+                    &JsonMessage::serialize_with(payload, encoding)?,
+                    encoding,
+                )
+                .await
+            }
+
+            /// Broadcast a binary message to the other participants
+            /// in a session.
+            async fn broadcast_blob(
+                &mut self,
+                session: &SessionState,
+                payload: Vec<u8>,
+            ) -> Result<()> {
+                self.broadcast(session, &payload, Encoding::Blob)
+                    .await
+            }
+
             /// Create a new meeting point.
             async fn new_meeting(
                 &mut self,
@@ -244,11 +520,57 @@ macro_rules! client_transport_impl {
                 &mut self,
                 participant_keys: Vec<Vec<u8>>,
             ) -> Result<()> {
-                let session = SessionRequest { participant_keys };
+                let session = SessionRequest {
+                    participant_keys,
+                    observer_keys: Vec::new(),
+                    parameters: None,
+                    deadline: None,
+                };
+                let message = ServerMessage::NewSession(session);
+                self.request(message).await
+            }
+
+            /// Create a new session with protocol parameters.
+            async fn new_session_with_parameters(
+                &mut self,
+                participant_keys: Vec<Vec<u8>>,
+                parameters: Parameters,
+            ) -> Result<()> {
+                let session = SessionRequest {
+                    participant_keys,
+                    observer_keys: Vec::new(),
+                    parameters: Some(parameters),
+                    deadline: None,
+                };
+                let message = ServerMessage::NewSession(session);
+                self.request(message).await
+            }
+
+            /// Create a new session with observers.
+            async fn new_observed_session(
+                &mut self,
+                participant_keys: Vec<Vec<u8>>,
+                observer_keys: Vec<Vec<u8>>,
+            ) -> Result<()> {
+                let session = SessionRequest {
+                    participant_keys,
+                    observer_keys,
+                    parameters: None,
+                    deadline: None,
+                };
                 let message = ServerMessage::NewSession(session);
                 self.request(message).await
             }
 
+            /// Create a new session from a request.
+            async fn request_session(
+                &mut self,
+                request: SessionRequest,
+            ) -> Result<()> {
+                let message = ServerMessage::NewSession(request);
+                self.request(message).await
+            }
+
             /// Register a peer connection in a session.
             async fn register_connection(
                 &mut self,
@@ -271,6 +593,26 @@ macro_rules! client_transport_impl {
                 self.request(message).await
             }
 
+            /// Resume a session after reconnecting.
+            async fn resume_session(
+                &mut self,
+                token: SessionToken,
+            ) -> Result<()> {
+                let message = ServerMessage::ResumeSession(token);
+                self.request(message).await
+            }
+
+            /// Request the digest of the messages relayed
+            /// in a session.
+            async fn session_digest(
+                &mut self,
+                session_id: SessionId,
+            ) -> Result<()> {
+                let message =
+                    ServerMessage::GetSessionDigest(session_id);
+                self.request(message).await
+            }
+
             #[cfg(not(target_arch="wasm32"))]
             async fn close(&self) -> Result<()> {
                 self.outbound_tx.send(InternalMessage::Close)?;
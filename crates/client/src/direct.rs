@@ -0,0 +1,556 @@
+//! Direct connections between peers.
+//!
+//! Assign [DirectOptions] to the client options to listen for
+//! direct connections and call
+//! [upgrade_peer](crate::Client::upgrade_peer) after the peer
+//! handshake to offer a direct connection to a peer; messages
+//! to the peer bypass the relay server once the connection
+//! is established which reduces relay bandwidth for heavy
+//! rounds between well-connected peers.
+//!
+//! Connection candidates are exchanged as control messages
+//! over the relayed peer channel. The peer receiving an offer
+//! dials the candidates and when none are reachable answers
+//! with its own candidates so the offering peer can dial
+//! instead; when neither peer can reach the other messages
+//! continue to be relayed.
+//!
+//! Direct connections perform a new noise handshake using the
+//! `KK` pattern as both peers already know the static key of
+//! the other peer. When writing to a direct connection fails
+//! the connection is dropped and messages are relayed again.
+//!
+//! Messages in flight on the relay may be delivered after
+//! messages sent on a direct connection so peers should be
+//! upgraded before starting a ceremony.
+use crate::{
+    decrypt_peer_channel, encrypt_peer_channel,
+    event_loop::{IncomingMessage, InternalMessage},
+    Error, Peers, Result,
+};
+use polysig_protocol::{
+    decode, encode, fingerprint, hex,
+    snow::{Builder, TransportState},
+    Encoding, JsonMessage, Keypair, OpaqueMessage, ProtocolState,
+    RequestMessage, SessionId,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    sync::{mpsc, Mutex, RwLock},
+};
+
+/// Noise parameters pattern for direct connections.
+const PATTERN_KK: &str = "Noise_KK_25519_ChaChaPoly_BLAKE2s";
+
+/// Maximum size of a handshake frame.
+const HANDSHAKE_LEN: usize = 1024;
+
+/// Maximum size of a message frame.
+const FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Maximum number of candidates dialed for a peer.
+const MAX_CANDIDATES: usize = 8;
+
+/// Options for direct peer connections.
+#[derive(Debug, Clone)]
+pub struct DirectOptions {
+    /// Address to listen on for direct connections.
+    pub listen: SocketAddr,
+    /// Addresses advertised to peers.
+    ///
+    /// When empty the local address of the listener is
+    /// advertised; set the candidates when listening on an
+    /// unspecified address or behind a port mapping.
+    pub candidates: Vec<SocketAddr>,
+    /// Timeout for connecting and completing the handshake
+    /// with a candidate.
+    pub connect_timeout: Duration,
+}
+
+impl Default for DirectOptions {
+    fn default() -> Self {
+        Self {
+            listen: ([0, 0, 0, 0], 0).into(),
+            candidates: Vec::new(),
+            connect_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Control message sent over the relayed peer channel.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "direct", rename_all = "camelCase")]
+pub(crate) enum DirectMessage {
+    /// Offer a direct connection to the candidates.
+    Offer {
+        /// Addresses of the offering peer.
+        candidates: Vec<SocketAddr>,
+    },
+    /// Answer an offer that could not be dialed.
+    Answer {
+        /// Addresses of the answering peer.
+        candidates: Vec<SocketAddr>,
+    },
+}
+
+/// Message from a direct connection for the event loop.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum DirectIncoming {
+    /// Direct connection was established.
+    Connected(Vec<u8>),
+    /// Message received from a peer.
+    Message {
+        /// Public key of the peer.
+        public_key: Vec<u8>,
+        /// Encoding of the message.
+        encoding: Encoding,
+        /// Decrypted message contents.
+        contents: Vec<u8>,
+        /// Session identifier.
+        session_id: Option<SessionId>,
+    },
+}
+
+/// Direct connection to a peer.
+struct Link {
+    id: u64,
+    state: Arc<Mutex<ProtocolState>>,
+    writer: OwnedWriteHalf,
+}
+
+/// Direct connections for a client.
+#[derive(Clone)]
+pub(crate) struct Direct {
+    keypair: Keypair,
+    candidates: Vec<SocketAddr>,
+    connect_timeout: Duration,
+    links: Arc<RwLock<HashMap<Vec<u8>, Link>>>,
+    expected: Arc<RwLock<HashSet<Vec<u8>>>>,
+    next_id: Arc<AtomicU64>,
+    peers: Peers,
+    inbound_tx: mpsc::UnboundedSender<IncomingMessage>,
+    outbound_tx: mpsc::UnboundedSender<InternalMessage>,
+}
+
+impl Direct {
+    /// Listen for direct connections.
+    ///
+    /// The listener is closed when the event loop is dropped.
+    pub async fn listen(
+        options: &DirectOptions,
+        keypair: Keypair,
+        peers: Peers,
+        inbound_tx: mpsc::UnboundedSender<IncomingMessage>,
+        outbound_tx: mpsc::UnboundedSender<InternalMessage>,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind(options.listen).await?;
+        let candidates = if options.candidates.is_empty() {
+            vec![listener.local_addr()?]
+        } else {
+            options.candidates.clone()
+        };
+        let direct = Self {
+            keypair,
+            candidates,
+            connect_timeout: options.connect_timeout,
+            links: Default::default(),
+            expected: Default::default(),
+            next_id: Default::default(),
+            peers,
+            inbound_tx,
+            outbound_tx,
+        };
+        tokio::task::spawn(direct.clone().accept(listener));
+        Ok(direct)
+    }
+
+    /// Determine if there is a direct connection to a peer.
+    pub async fn is_connected(&self, public_key: &[u8]) -> bool {
+        let links = self.links.read().await;
+        links.contains_key(public_key)
+    }
+
+    /// Create an offer for a peer.
+    pub async fn offer(&self, public_key: &[u8]) -> DirectMessage {
+        let mut expected = self.expected.write().await;
+        expected.insert(public_key.to_vec());
+        DirectMessage::Offer {
+            candidates: self.candidates.clone(),
+        }
+    }
+
+    /// Handle a control message from a peer.
+    pub fn handle(
+        &self,
+        public_key: Vec<u8>,
+        message: DirectMessage,
+    ) {
+        let direct = self.clone();
+        tokio::task::spawn(async move {
+            match message {
+                DirectMessage::Offer { candidates } => {
+                    if !direct.dial(&public_key, &candidates).await {
+                        // Ask the peer to dial this client instead
+                        if let Err(e) =
+                            direct.answer(&public_key).await
+                        {
+                            tracing::warn!(
+                                error = %e,
+                                "direct answer"
+                            );
+                        }
+                    }
+                }
+                DirectMessage::Answer { candidates } => {
+                    if !direct.dial(&public_key, &candidates).await {
+                        tracing::debug!(
+                            peer = %fingerprint(&public_key),
+                            "direct connection unavailable"
+                        );
+                    }
+                }
+            }
+        });
+    }
+
+    /// Send a message to a peer over a direct connection.
+    ///
+    /// Returns `false` when there is no direct connection to
+    /// the peer or the connection failed so the message must
+    /// be relayed.
+    pub async fn send(
+        &self,
+        public_key: &[u8],
+        payload: &[u8],
+        encoding: Encoding,
+        broadcast: bool,
+        session_id: Option<SessionId>,
+    ) -> Result<bool> {
+        let mut links = self.links.write().await;
+        let Some(link) = links.get_mut(public_key) else {
+            return Ok(false);
+        };
+        let request = {
+            let mut state = link.state.lock().await;
+            encrypt_peer_channel(
+                public_key, &mut state, payload, encoding, broadcast,
+                session_id,
+            )?
+        };
+        let frame = encode(&request).await?;
+        if let Err(e) = write_frame(&mut link.writer, &frame).await {
+            tracing::debug!(
+                peer = %fingerprint(public_key),
+                error = %e,
+                "direct connection lost, using relay"
+            );
+            links.remove(public_key);
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Answer an offer with the candidates for this client.
+    async fn answer(&self, public_key: &[u8]) -> Result<()> {
+        {
+            let mut expected = self.expected.write().await;
+            expected.insert(public_key.to_vec());
+        }
+        let payload =
+            JsonMessage::serialize(&DirectMessage::Answer {
+                candidates: self.candidates.clone(),
+            })?;
+        let peer = {
+            let peers = self.peers.read().await;
+            peers.get(public_key).cloned()
+        }
+        .ok_or_else(|| {
+            Error::PeerNotFound(hex::encode(public_key))
+        })?;
+        let mut state = peer.lock().await;
+        let request = encrypt_peer_channel(
+            public_key,
+            &mut state,
+            &payload,
+            Encoding::Json,
+            false,
+            None,
+        )?;
+        self.outbound_tx.send(InternalMessage::Request(request))?;
+        Ok(())
+    }
+
+    /// Accept direct connections until the event loop
+    /// is dropped.
+    async fn accept(self, listener: TcpListener) {
+        loop {
+            tokio::select! {
+                _ = self.inbound_tx.closed() => break,
+                result = listener.accept() => match result {
+                    Ok((stream, addr)) => {
+                        self.spawn_respond(stream, addr)
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "direct accept");
+                    }
+                },
+            }
+        }
+    }
+
+    /// Handshake with a peer that dialed this client in
+    /// a separate task.
+    fn spawn_respond(&self, stream: TcpStream, addr: SocketAddr) {
+        let direct = self.clone();
+        tokio::task::spawn(async move {
+            if let Err(e) = direct.respond(stream).await {
+                tracing::debug!(
+                    addr = %addr,
+                    error = %e,
+                    "direct connection rejected"
+                );
+            }
+        });
+    }
+
+    /// Dial the candidates for a peer until a direct
+    /// connection is established.
+    async fn dial(
+        &self,
+        public_key: &[u8],
+        candidates: &[SocketAddr],
+    ) -> bool {
+        for addr in candidates.iter().take(MAX_CANDIDATES) {
+            match self.connect(public_key, *addr).await {
+                Ok(()) => return true,
+                Err(e) => {
+                    tracing::debug!(
+                        addr = %addr,
+                        error = %e,
+                        "direct dial"
+                    );
+                }
+            }
+        }
+        false
+    }
+
+    /// Connect to a candidate and handshake as the initiator.
+    async fn connect(
+        &self,
+        public_key: &[u8],
+        addr: SocketAddr,
+    ) -> Result<()> {
+        let handshake = async {
+            let mut stream = TcpStream::connect(addr).await?;
+            stream.set_nodelay(true)?;
+            write_frame(&mut stream, self.keypair.public_key())
+                .await?;
+
+            let mut initiator = Builder::new(PATTERN_KK.parse()?)
+                .local_private_key(self.keypair.private_key())
+                .remote_public_key(public_key)
+                .build_initiator()?;
+            let mut buf = vec![0u8; HANDSHAKE_LEN];
+            let len = initiator.write_message(&[], &mut buf)?;
+            write_frame(&mut stream, &buf[..len]).await?;
+            let message =
+                read_frame(&mut stream, HANDSHAKE_LEN).await?;
+            initiator.read_message(&message, &mut buf)?;
+            Ok::<_, Error>((stream, initiator.into_transport_mode()?))
+        };
+        let (stream, transport) = self.timeout(handshake).await?;
+        self.register(public_key.to_vec(), stream, transport).await
+    }
+
+    /// Handshake as the responder with a peer that dialed
+    /// this client.
+    async fn respond(&self, stream: TcpStream) -> Result<()> {
+        let handshake = async {
+            let mut stream = stream;
+            stream.set_nodelay(true)?;
+            let public_key =
+                read_frame(&mut stream, HANDSHAKE_LEN).await?;
+            {
+                let expected = self.expected.read().await;
+                if !expected.contains(&public_key) {
+                    return Err(Error::DirectPeer(hex::encode(
+                        &public_key,
+                    )));
+                }
+            }
+
+            let mut responder = Builder::new(PATTERN_KK.parse()?)
+                .local_private_key(self.keypair.private_key())
+                .remote_public_key(&public_key)
+                .build_responder()?;
+            let message =
+                read_frame(&mut stream, HANDSHAKE_LEN).await?;
+            let mut buf = vec![0u8; HANDSHAKE_LEN];
+            responder.read_message(&message, &mut buf)?;
+            let len = responder.write_message(&[], &mut buf)?;
+            write_frame(&mut stream, &buf[..len]).await?;
+            Ok::<_, Error>((
+                public_key,
+                stream,
+                responder.into_transport_mode()?,
+            ))
+        };
+        let (public_key, stream, transport) =
+            self.timeout(handshake).await?;
+        self.register(public_key, stream, transport).await
+    }
+
+    /// Register a direct connection and read messages from
+    /// the peer until the connection is closed.
+    async fn register(
+        &self,
+        public_key: Vec<u8>,
+        stream: TcpStream,
+        transport: TransportState,
+    ) -> Result<()> {
+        let (reader, writer) = stream.into_split();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let state =
+            Arc::new(Mutex::new(ProtocolState::Transport(transport)));
+        {
+            let mut expected = self.expected.write().await;
+            expected.remove(&public_key);
+            let mut links = self.links.write().await;
+            links.insert(
+                public_key.clone(),
+                Link {
+                    id,
+                    state: state.clone(),
+                    writer,
+                },
+            );
+        }
+
+        tracing::debug!(
+            peer = %fingerprint(&public_key),
+            "direct connection established"
+        );
+
+        self.inbound_tx.send(IncomingMessage::Direct(
+            DirectIncoming::Connected(public_key.clone()),
+        ))?;
+
+        let direct = self.clone();
+        tokio::task::spawn(async move {
+            if let Err(e) =
+                direct.read(&public_key, reader, state).await
+            {
+                tracing::debug!(
+                    peer = %fingerprint(&public_key),
+                    error = %e,
+                    "direct connection closed"
+                );
+            }
+            let mut links = direct.links.write().await;
+            if links
+                .get(&public_key)
+                .is_some_and(|link| link.id == id)
+            {
+                links.remove(&public_key);
+            }
+        });
+        Ok(())
+    }
+
+    /// Read messages from a direct connection.
+    async fn read(
+        &self,
+        public_key: &[u8],
+        mut reader: OwnedReadHalf,
+        state: Arc<Mutex<ProtocolState>>,
+    ) -> Result<()> {
+        loop {
+            let frame = tokio::select! {
+                _ = self.inbound_tx.closed() => return Ok(()),
+                frame = read_frame(&mut reader, FRAME_LEN) => frame?,
+            };
+            let (session_id, envelope) = match decode(&frame).await? {
+                RequestMessage::Opaque(
+                    OpaqueMessage::PeerMessage {
+                        session_id,
+                        envelope,
+                        ..
+                    },
+                ) => (session_id, envelope),
+                _ => return Err(Error::DirectMessage),
+            };
+            let (encoding, contents) = {
+                let mut state = state.lock().await;
+                decrypt_peer_channel(&mut state, envelope)?
+            };
+            self.inbound_tx.send(IncomingMessage::Direct(
+                DirectIncoming::Message {
+                    public_key: public_key.to_vec(),
+                    encoding,
+                    contents,
+                    session_id,
+                },
+            ))?;
+        }
+    }
+
+    /// Apply the connect timeout to a handshake.
+    async fn timeout<T>(
+        &self,
+        handshake: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        tokio::time::timeout(self.connect_timeout, handshake)
+            .await
+            .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))?
+    }
+}
+
+/// Write a length-prefixed frame.
+async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    buffer: &[u8],
+) -> Result<()> {
+    let mut frame = Vec::with_capacity(buffer.len() + 4);
+    frame.extend_from_slice(&(buffer.len() as u32).to_be_bytes());
+    frame.extend_from_slice(buffer);
+    writer.write_all(&frame).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read a length-prefixed frame.
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    limit: usize,
+) -> Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len).await?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "frame too large",
+        )
+        .into());
+    }
+    let mut buffer = vec![0u8; len];
+    reader.read_exact(&mut buffer).await?;
+    Ok(buffer)
+}
@@ -1,7 +1,31 @@
 use crate::event_loop::{IncomingMessage, InternalMessage};
+use polysig_driver::classify;
 use polysig_protocol::http::StatusCode;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use polysig_protocol::ErrorKind;
+
+/// Structured description of an error.
+///
+/// Used by the bindings so callers can decide whether
+/// to retry a ceremony without parsing error messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorInfo {
+    /// Stable error code, eg: `NO_REPLY`.
+    pub code: String,
+    /// Category of the error.
+    pub kind: ErrorKind,
+    /// Human-readable error message.
+    pub message: String,
+    /// Party number of the offending party when known.
+    pub party: Option<u16>,
+    /// Whether the operation may succeed if retried.
+    pub retryable: bool,
+}
+
 /// Errors generated by the relay client.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -11,10 +35,32 @@ pub enum Error {
     #[error("{0} {1}")]
     ConnectError(StatusCode, String),
 
+    /// Error generated when the server does not support the
+    /// websocket subprotocol for the wire encoding version
+    /// of the client.
+    #[error("server does not support websocket subprotocol {0}")]
+    UnsupportedSubprotocol(String),
+
     /// Error generated by the server.
     #[error("{0} {1}")]
     ServerError(StatusCode, String),
 
+    /// Error generated when the server presents a static key
+    /// that is not pinned.
+    #[error("server key {0} is not pinned")]
+    ServerKeyNotPinned(String),
+
+    /// Error generated when the server public key is not known
+    /// and the noise pattern does not transmit the server
+    /// static key.
+    #[error("noise pattern {0} does not transmit the server key")]
+    ServerKeyPattern(String),
+
+    /// Error generated when the session parameters are not
+    /// valid for the participants or the protocol.
+    #[error("invalid session parameters: {0}")]
+    InvalidParameters(#[source] polysig_protocol::Error),
+
     /// Error generated if the client expects a reply but none was received.
     #[error("server did not reply")]
     NoReply,
@@ -50,6 +96,20 @@ pub enum Error {
     #[error("invalid peer handshake message")]
     InvalidPeerHandshakeMessage,
 
+    /// Error generated when the static key sent by a peer
+    /// during the handshake does not match the public key
+    /// of the peer.
+    #[error(r#"handshake static key for peer "{0}" does not match"#)]
+    PeerKeyMismatch(String),
+
+    /// Error generated when a session token is sent with the
+    /// server handshake but the noise pattern does not encrypt
+    /// the first handshake message.
+    #[error(
+        "session token requires a zero round trip noise pattern"
+    )]
+    ZeroRttPattern,
+
     /// Error generated when the client fails to write to the websocket.
     #[error("web socket failed to send")]
     WebSocketSend,
@@ -68,11 +128,146 @@ pub enum Error {
     #[error("public key {0} is not a session participant")]
     NotSessionParticipant(String),
 
+    /// Error generated when the server times out a session
+    /// because the deadline for the session was reached.
+    #[error("session {0} deadline was reached")]
+    SessionDeadline(polysig_protocol::SessionId),
+
+    /// Error generated when a round of a ceremony does not
+    /// complete before the round timeout.
+    #[error(
+        "round {round} timed out waiting for parties {missing:?}"
+    )]
+    RoundTimeout {
+        /// Round number.
+        round: u16,
+        /// Parties that did not send a message for the round.
+        missing: Vec<u16>,
+    },
+
+    /// Error generated when a protocol ceremony fails.
+    ///
+    /// Includes a report describing the parties that
+    /// participated and any blame evidence.
+    #[cfg(any(
+        feature = "bls",
+        feature = "cggmp",
+        feature = "frost",
+        feature = "musig2"
+    ))]
+    #[error("ceremony failed: {source}")]
+    Ceremony {
+        /// Report for the failed ceremony.
+        report: Box<crate::CeremonyReport>,
+        /// Underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// Error generated when a signing roster has fewer signers
+    /// than the signing threshold.
+    #[cfg(feature = "frost")]
+    #[error("signing roster has {0} signers but threshold is {1}")]
+    RosterThreshold(usize, u16),
+
+    /// Error generated when the public keys or identifiers in a
+    /// signing roster are not unique.
+    #[cfg(feature = "frost")]
+    #[error("signing roster entries must be unique")]
+    RosterNotUnique,
+
+    /// Error generated when a session participant is not in the
+    /// signing roster or a signer is not a session participant.
+    #[cfg(feature = "frost")]
+    #[error("public key {0} does not match the signing roster")]
+    RosterParticipant(String),
+
+    /// Error generated when the identifier for our key share does
+    /// not match our entry in the signing roster.
+    #[cfg(feature = "frost")]
+    #[error("key share identifier does not match signing roster")]
+    RosterIdentifier,
+
+    /// Error generated when a signing policy rejects a signature.
+    #[error("signing policy rejected signature: {0}")]
+    PolicyRejected(String),
+
+    /// Error generated when a signing policy defers a signature.
+    #[error("signing policy deferred signature: {0}")]
+    PolicyDeferred(String),
+
+    /// Error generated when the attestation evidence sent
+    /// by a peer is rejected.
+    #[error("attestation from peer {0} rejected: {1}")]
+    AttestationRejected(String, String),
+
+    /// Error generated when a group message is received for a
+    /// session without a group key.
+    #[error("no group key for session {0}")]
+    GroupKeyNotFound(polysig_protocol::SessionId),
+
+    /// Error generated when a group message could not be
+    /// encrypted or decrypted.
+    #[error("group message could not be encrypted or decrypted")]
+    GroupMessage,
+
+    /// Error generated when upgrading to a direct connection
+    /// without direct connection options.
+    #[error("direct connections are not enabled")]
+    DirectDisabled,
+
+    /// Error generated when a direct connection is dialed by
+    /// a peer that was not offered a direct connection.
+    #[error("unexpected direct connection from peer {0}")]
+    DirectPeer(String),
+
+    /// Error generated when a direct connection receives a
+    /// message that is not a peer message.
+    #[error("invalid message on direct connection")]
+    DirectMessage,
+
+    /// Error generated when the connection closes before
+    /// a ceremony completes.
+    #[error("connection closed before the ceremony completed")]
+    ConnectionClosed,
+
+    /// Error generated when no message is received from the
+    /// server within the keepalive idle timeout.
+    #[error("connection to the server is idle")]
+    IdleTimeout,
+
+    /// Error generated when the server closes the connection
+    /// because a new connection for the same public key
+    /// replaced it.
+    #[error("connection was superseded by another connection")]
+    Superseded,
+
+    /// Error generated when a ceremony is cancelled.
+    #[error("ceremony was cancelled")]
+    Cancelled,
+
+    /// Error generated when a key share does not have
+    /// a verifying key.
+    #[cfg(feature = "cggmp")]
+    #[error("key share does not have a verifying key")]
+    KeyShareVerifyingKey,
+
     #[cfg(feature = "cggmp")]
     /// Could not locate ack for key init phase.
     #[error("could not find an ACK for key init phase")]
     NoKeyInitAck,
 
+    /// Error generated when an account is not known to the
+    /// orchestrator or the account registry.
+    #[error("unknown account {0}")]
+    UnknownAccount(String),
+
+    /// Error generated when the identifiers of signing
+    /// requests are not unique.
+    #[cfg(feature = "cggmp")]
+    #[error("duplicate signing request {0}")]
+    DuplicateSignRequest(String),
+
     /// Javascript string error message.
     #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
     #[error("{0}")]
@@ -88,6 +283,52 @@ pub enum Error {
     #[error("unknown javascript error (type conversion failed)")]
     JsError,
 
+    /// Error generated when a transcript is not available
+    /// for a party in a replay.
+    #[cfg(all(
+        feature = "record",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[error("no transcript for party {0}")]
+    TranscriptNotFound(String),
+
+    /// Error generated when a driver fails replaying
+    /// a transcript.
+    ///
+    /// The sequence number is zero when the driver fails
+    /// before any recorded message is delivered.
+    #[cfg(all(
+        feature = "record",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[error("replay failed for party {party} at message {sequence}: {source}")]
+    Replay {
+        /// Party number.
+        party: u16,
+        /// Sequence number of the recorded message.
+        sequence: u64,
+        /// Driver error.
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+
+    #[cfg(all(
+        feature = "http2",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    /// Error generated when a server URL is not valid for
+    /// an HTTP/2 connection.
+    #[error("invalid relay url {0}")]
+    RelayUrl(String),
+
+    #[cfg(all(
+        feature = "log-filter",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    /// Error generated when log filter directives are invalid
+    /// or the filter could not be changed.
+    #[error("log filter: {0}")]
+    LogFilter(String),
+
     /// Error generated when the native client fails to reunite
     /// the stream and sink.
     #[error("stream and sink reunite failed")]
@@ -124,6 +365,19 @@ pub enum Error {
     #[error(transparent)]
     Websocket(#[from] tokio_tungstenite::tungstenite::Error),
 
+    #[cfg(all(
+        feature = "http2",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    /// Error generated by the HTTP/2 library.
+    #[error(transparent)]
+    Http2(#[from] h2::Error),
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    /// Error generated when a worker task fails.
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+
     /// Error generated sending a request over a channel.
     #[error(transparent)]
     RequestMpscSend(
@@ -141,6 +395,30 @@ pub enum Error {
     #[error(transparent)]
     FrostEd25519Core(#[from] polysig_driver::frost_ed25519::Error),
 
+    #[cfg(feature = "frost-ed448")]
+    /// FROST library error.
+    #[error(transparent)]
+    FrostEd448Core(#[from] polysig_driver::frost_ed448::Error),
+
+    #[cfg(feature = "frost-p256")]
+    /// FROST library error.
+    #[error(transparent)]
+    FrostP256Core(#[from] polysig_driver::frost_p256::Error),
+
+    #[cfg(feature = "frost-ristretto255")]
+    /// FROST library error.
+    #[error(transparent)]
+    FrostRistretto255Core(
+        #[from] polysig_driver::frost_ristretto255::Error,
+    ),
+
+    #[cfg(feature = "frost-secp256k1")]
+    /// FROST library error.
+    #[error(transparent)]
+    FrostSecp256k1Core(
+        #[from] polysig_driver::frost_secp256k1::Error,
+    ),
+
     #[cfg(feature = "frost-secp256k1-tr")]
     /// FROST library error.
     #[error(transparent)]
@@ -148,15 +426,293 @@ pub enum Error {
         #[from] polysig_driver::frost_secp256k1_tr::Error,
     ),
 
+    #[cfg(feature = "bls")]
+    /// BLS library error.
+    #[error(transparent)]
+    Bls(#[from] polysig_driver::bls::Error),
+
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    /// Ceremony certificate error.
+    #[error(transparent)]
+    Certificate(#[from] polysig_driver::certificate::Error),
+
     #[cfg(feature = "cggmp")]
     /// CGGMP library error.
     #[error(transparent)]
     Cggmp(#[from] polysig_driver::cggmp::Error),
 
+    #[cfg(feature = "elgamal")]
+    /// ElGamal library error.
+    #[error(transparent)]
+    Elgamal(#[from] polysig_driver::elgamal::Error),
+
     #[cfg(feature = "frost")]
     /// FROST library error.
     #[error(transparent)]
     Frost(#[from] polysig_driver::frost::Error),
+
+    #[cfg(feature = "musig2")]
+    /// MuSig2 library error.
+    #[error(transparent)]
+    Musig2(#[from] polysig_driver::musig2::Error),
+}
+
+impl Error {
+    /// Ceremony report when this error was generated
+    /// by a failed protocol ceremony.
+    #[cfg(any(
+        feature = "bls",
+        feature = "cggmp",
+        feature = "frost",
+        feature = "musig2"
+    ))]
+    pub fn report(&self) -> Option<&crate::CeremonyReport> {
+        match self {
+            Error::Ceremony { report, .. } => Some(report),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Stable code for this error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::ConnectError(_, _) => "CONNECT_ERROR",
+            Error::UnsupportedSubprotocol(_) => "SUBPROTOCOL",
+            Error::ServerError(_, _) => "SERVER_ERROR",
+            Error::ServerKeyNotPinned(_)
+            | Error::ServerKeyPattern(_) => "SERVER_KEY",
+            Error::InvalidParameters(_) => "PARAMETERS",
+            Error::NoReply => "NO_REPLY",
+            Error::ConnectionClosed => "CONNECTION_CLOSED",
+            Error::Superseded => "SUPERSEDED",
+            Error::IdleTimeout => "IDLE_TIMEOUT",
+            Error::PeerAlreadyExists
+            | Error::PeerAlreadyExistsMaybeRace => {
+                "PEER_ALREADY_EXISTS"
+            }
+            Error::PeerNotFound(_) => "PEER_NOT_FOUND",
+            Error::NotHandshakeState
+            | Error::NotTransportState
+            | Error::InvalidPeerHandshakeMessage
+            | Error::PeerKeyMismatch(_) => "PEER_HANDSHAKE",
+            Error::ZeroRttPattern => "NOISE",
+            Error::WebSocketSend => "WEBSOCKET_SEND",
+            Error::MeetingIdentifiersNotUnique
+            | Error::MeetingInitiatorNotExist => {
+                "MEETING_IDENTIFIERS"
+            }
+            Error::NotSessionParticipant(_) => {
+                "NOT_SESSION_PARTICIPANT"
+            }
+            Error::SessionDeadline(_) => "SESSION_DEADLINE",
+            Error::RoundTimeout { .. } => "ROUND_TIMEOUT",
+            #[cfg(any(
+                feature = "bls",
+                feature = "cggmp",
+                feature = "frost",
+                feature = "musig2"
+            ))]
+            Error::Ceremony { .. } => "CEREMONY_FAILED",
+            #[cfg(feature = "frost")]
+            Error::RosterThreshold(_, _)
+            | Error::RosterNotUnique
+            | Error::RosterParticipant(_)
+            | Error::RosterIdentifier => "SIGNING_ROSTER",
+            Error::PolicyRejected(_) => "POLICY_REJECTED",
+            Error::PolicyDeferred(_) => "POLICY_DEFERRED",
+            Error::AttestationRejected(_, _) => {
+                "ATTESTATION_REJECTED"
+            }
+            Error::GroupKeyNotFound(_) | Error::GroupMessage => {
+                "GROUP_KEY"
+            }
+            Error::DirectDisabled
+            | Error::DirectPeer(_)
+            | Error::DirectMessage => "DIRECT",
+            Error::Cancelled => "CANCELLED",
+            #[cfg(all(
+                target_arch = "wasm32",
+                target_os = "unknown"
+            ))]
+            Error::JsString(_)
+            | Error::JsValue(_)
+            | Error::JsError => "JAVASCRIPT",
+            Error::Io(_) => "IO",
+            #[cfg(not(all(
+                target_arch = "wasm32",
+                target_os = "unknown"
+            )))]
+            Error::Websocket(_) => "WEBSOCKET",
+            #[cfg(all(
+                feature = "http2",
+                not(all(
+                    target_arch = "wasm32",
+                    target_os = "unknown"
+                ))
+            ))]
+            Error::Http2(_) | Error::RelayUrl(_) => "HTTP2",
+            #[cfg(not(all(
+                target_arch = "wasm32",
+                target_os = "unknown"
+            )))]
+            Error::Join(_) => "INTERNAL",
+            Error::Protocol(_) | Error::Json(_) => "ENCODING",
+            Error::Snow(_) => "NOISE",
+            Error::StreamReunite
+            | Error::Generic(_)
+            | Error::RequestMpscSend(_)
+            | Error::ResponseMpscSend(_) => "INTERNAL",
+            _ => "PROTOCOL",
+        }
+    }
+
+    /// Category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::ConnectError(_, _)
+            | Error::NoReply
+            | Error::ConnectionClosed
+            | Error::Superseded
+            | Error::IdleTimeout
+            | Error::WebSocketSend
+            | Error::StreamReunite
+            | Error::Io(_) => ErrorKind::Transport,
+            #[cfg(all(
+                target_arch = "wasm32",
+                target_os = "unknown"
+            ))]
+            Error::JsString(_)
+            | Error::JsValue(_)
+            | Error::JsError => ErrorKind::Transport,
+            #[cfg(not(all(
+                target_arch = "wasm32",
+                target_os = "unknown"
+            )))]
+            Error::Websocket(_) => ErrorKind::Transport,
+            #[cfg(all(
+                feature = "http2",
+                not(all(
+                    target_arch = "wasm32",
+                    target_os = "unknown"
+                ))
+            ))]
+            Error::Http2(_) => ErrorKind::Transport,
+            Error::ServerError(_, _)
+            | Error::ServerKeyNotPinned(_)
+            | Error::ServerKeyPattern(_)
+            | Error::UnsupportedSubprotocol(_)
+            | Error::PeerAlreadyExists
+            | Error::PeerAlreadyExistsMaybeRace
+            | Error::PeerNotFound(_)
+            | Error::NotHandshakeState
+            | Error::NotTransportState
+            | Error::InvalidPeerHandshakeMessage
+            | Error::PeerKeyMismatch(_)
+            | Error::ZeroRttPattern
+            | Error::MeetingIdentifiersNotUnique
+            | Error::MeetingInitiatorNotExist
+            | Error::NotSessionParticipant(_)
+            | Error::SessionDeadline(_)
+            | Error::RoundTimeout { .. }
+            | Error::AttestationRejected(_, _)
+            | Error::GroupKeyNotFound(_)
+            | Error::GroupMessage
+            | Error::DirectDisabled
+            | Error::DirectPeer(_)
+            | Error::DirectMessage
+            | Error::Snow(_) => ErrorKind::Session,
+            Error::Cancelled => ErrorKind::Abort,
+            #[cfg(any(
+                feature = "bls",
+                feature = "cggmp",
+                feature = "frost",
+                feature = "musig2"
+            ))]
+            Error::Ceremony { source, .. } => source.kind(),
+            Error::Protocol(e) => e.kind(),
+            Error::Driver(e) => e.kind(),
+            Error::Generic(e) => classify(&**e)
+                .map(|(kind, _)| kind)
+                .unwrap_or(ErrorKind::Protocol),
+            _ => ErrorKind::Protocol,
+        }
+    }
+
+    /// Party number of the offending party when known.
+    ///
+    /// For failed ceremonies this is the first party
+    /// that was blamed or did not respond.
+    pub fn party(&self) -> Option<u16> {
+        match self {
+            #[cfg(any(
+                feature = "bls",
+                feature = "cggmp",
+                feature = "frost",
+                feature = "musig2"
+            ))]
+            Error::Ceremony { report, .. } => {
+                report.offenders().first().copied()
+            }
+            Error::RoundTimeout { missing, .. } => {
+                missing.first().copied()
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the operation may succeed if retried.
+    ///
+    /// Transport failures other than a superseded connection,
+    /// server errors, peer races, session deadlines and round
+    /// timeouts are transient; a failed ceremony is retryable
+    /// when the underlying error is or when offending parties
+    /// are known so they can be excluded from the next attempt.
+    /// Driver and protocol errors keep the classification of
+    /// the underlying error.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::ConnectError(status, _)
+            | Error::ServerError(status, _) => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            Error::PeerAlreadyExistsMaybeRace
+            | Error::SessionDeadline(_)
+            | Error::RoundTimeout { .. }
+            | Error::PolicyDeferred(_) => true,
+            // Another client is connected with the same key
+            Error::Superseded => false,
+            #[cfg(any(
+                feature = "bls",
+                feature = "cggmp",
+                feature = "frost",
+                feature = "musig2"
+            ))]
+            Error::Ceremony { report, source } => {
+                source.is_retryable()
+                    || !report.offenders().is_empty()
+            }
+            Error::Protocol(e) => e.is_retryable(),
+            Error::Driver(e) => e.is_retryable(),
+            Error::Generic(e) => classify(&**e)
+                .map(|(_, retryable)| retryable)
+                .unwrap_or(false),
+            _ => self.kind() == ErrorKind::Transport,
+        }
+    }
+
+    /// Structured description of this error.
+    pub fn info(&self) -> ErrorInfo {
+        ErrorInfo {
+            code: self.code().to_owned(),
+            kind: self.kind(),
+            message: self.to_string(),
+            party: self.party(),
+            retryable: self.is_retryable(),
+        }
+    }
 }
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
@@ -179,7 +735,36 @@ impl From<wasm_bindgen::JsValue> for Error {
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 impl From<Error> for wasm_bindgen::JsValue {
     fn from(value: Error) -> Self {
-        let s = value.to_string();
-        wasm_bindgen::JsValue::from_str(&s)
+        use wasm_bindgen::JsValue;
+        let info = value.info();
+        // Message is the serialized info so errors are
+        // handled the same way as with the node bindings
+        let message = serde_json::to_string(&info)
+            .unwrap_or_else(|_| info.message.clone());
+        let error = js_sys::Error::new(&message);
+        error.set_name("PolysigError");
+        let fields = [
+            ("code", JsValue::from_str(&info.code)),
+            (
+                "kind",
+                serde_wasm_bindgen::to_value(&info.kind)
+                    .unwrap_or(JsValue::UNDEFINED),
+            ),
+            (
+                "party",
+                info.party
+                    .map(JsValue::from)
+                    .unwrap_or(JsValue::NULL),
+            ),
+            ("retryable", JsValue::from_bool(info.retryable)),
+        ];
+        for (key, field) in fields {
+            let _ = js_sys::Reflect::set(
+                &error,
+                &JsValue::from_str(key),
+                &field,
+            );
+        }
+        error.into()
     }
 }
@@ -2,18 +2,25 @@ use futures::{
     sink::SinkExt,
     stream::{BoxStream, Stream},
 };
-use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::{collections::VecDeque, sync::Arc};
+use tokio::sync::{mpsc, Mutex};
 
 use polysig_protocol::{
-    channel::decrypt_server_channel, decode, hex, snow::Builder,
-    Encoding, Event, HandshakeMessage, MeetingResponse,
-    OpaqueMessage, ProtocolState, RequestMessage, ResponseMessage,
-    SealedEnvelope, ServerMessage, SessionId, TransparentMessage,
+    channel::{decrypt_server_channel, encrypt_server_channel},
+    decode, encode, fingerprint, hex, is_initiator_key_known,
+    snow::Builder,
+    Encoding, Event, HandshakeMessage, JsonMessage, MeetingResponse,
+    OpaqueMessage, Priority, ProtocolState, RequestMessage,
+    ResponseMessage, SealedEnvelope, ServerMessage, SessionId,
+    TransparentMessage,
 };
 
-use super::{decrypt_peer_channel, Peers, Server};
-use crate::{ClientOptions, Error, Result};
+use super::{decrypt_peer_channel, with_peer, Peers, Server};
+use crate::{
+    group::{GroupKeyMessage, Groups},
+    keepalive::Keepalive,
+    ClientOptions, Error, Result,
+};
 
 /// Stream of events emitted by an event loop.
 pub type EventStream = BoxStream<'static, Result<Event>>;
@@ -34,6 +41,75 @@ pub enum InternalMessage {
     Close,
 }
 
+impl InternalMessage {
+    /// Priority for sending the message.
+    ///
+    /// Closing the connection has the lowest priority so
+    /// the messages already queued are sent first.
+    fn priority(&self) -> Priority {
+        match self {
+            InternalMessage::Request(request) => request.priority(),
+            InternalMessage::Buffer(_) => Priority::Control,
+            InternalMessage::Close => Priority::Bulk,
+        }
+    }
+}
+
+/// Queue of outbound messages ordered by priority.
+///
+/// Messages waiting in the outbound channel are moved to a
+/// queue for each priority so that handshakes and server
+/// messages are sent before round messages and round messages
+/// before large payloads; messages with the same priority
+/// are sent in order.
+pub(crate) struct OutboundQueue {
+    receiver: mpsc::UnboundedReceiver<InternalMessage>,
+    queues: [VecDeque<InternalMessage>; 3],
+}
+
+impl OutboundQueue {
+    /// Receive the next message with the highest priority.
+    ///
+    /// Cancel safe as messages are moved to the queues
+    /// without waiting once a message has been received.
+    pub(crate) async fn recv(&mut self) -> Option<InternalMessage> {
+        if self.queues.iter().all(|q| q.is_empty()) {
+            let message = self.receiver.recv().await?;
+            self.push(message);
+        }
+        while let Ok(message) = self.receiver.try_recv() {
+            self.push(message);
+        }
+        Priority::ALL
+            .iter()
+            .find_map(|priority| self.queue(*priority).pop_front())
+    }
+
+    fn push(&mut self, message: InternalMessage) {
+        self.queue(message.priority()).push_back(message);
+    }
+
+    fn queue(
+        &mut self,
+        priority: Priority,
+    ) -> &mut VecDeque<InternalMessage> {
+        &mut self.queues[priority as usize]
+    }
+}
+
+impl From<mpsc::UnboundedReceiver<InternalMessage>>
+    for OutboundQueue
+{
+    fn from(
+        receiver: mpsc::UnboundedReceiver<InternalMessage>,
+    ) -> Self {
+        Self {
+            receiver,
+            queues: Default::default(),
+        }
+    }
+}
+
 #[doc(hidden)]
 #[derive(Debug)]
 pub enum IncomingMessage {
@@ -41,6 +117,21 @@ pub enum IncomingMessage {
     Response(ResponseMessage),
     /// Meeting client message.
     Meeting(MeetingResponse),
+    /// Message from a direct peer connection.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    Direct(crate::direct::DirectIncoming),
+    /// Message relayed from a peer decrypted by a worker.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    Decrypted {
+        /// Public key of the peer.
+        public_key: Vec<u8>,
+        /// Encoding and contents of the message.
+        ///
+        /// Boxed as errors may contain incoming messages.
+        result: Box<Result<(Encoding, Vec<u8>)>>,
+        /// Session identifier.
+        session_id: Option<SessionId>,
+    },
 }
 
 /// Event loop for a client.
@@ -57,9 +148,15 @@ where
     pub(crate) inbound_tx: mpsc::UnboundedSender<IncomingMessage>,
     pub(crate) inbound_rx: mpsc::UnboundedReceiver<IncomingMessage>,
     pub(crate) outbound_tx: mpsc::UnboundedSender<InternalMessage>,
-    pub(crate) outbound_rx: mpsc::UnboundedReceiver<InternalMessage>,
+    pub(crate) outbound_rx: OutboundQueue,
     pub(crate) server: Server,
     pub(crate) peers: Peers,
+    pub(crate) groups: Groups,
+    pub(crate) keepalive: Keepalive,
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub(crate) direct: Option<crate::direct::Direct>,
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub(crate) workers: crate::worker::Workers,
 }
 
 impl<M, E, R, W> EventLoop<M, E, R, W>
@@ -73,6 +170,7 @@ where
         options: Arc<ClientOptions>,
         server: Server,
         peers: Peers,
+        groups: Groups,
         incoming: ResponseMessage,
         outbound_tx: mpsc::UnboundedSender<InternalMessage>,
     ) -> Result<Option<Event>> {
@@ -115,9 +213,19 @@ where
                 public_key,
                 envelope,
                 session_id,
-            }) => Ok(Some(
-                Self::handle_relayed_message(
-                    peers, public_key, envelope, session_id,
+            }) => Ok(Self::handle_relayed_message(
+                peers, groups, public_key, envelope, session_id,
+            )
+            .await?),
+            ResponseMessage::Opaque(
+                OpaqueMessage::GroupMessage {
+                    public_key,
+                    envelope,
+                    session_id,
+                },
+            ) => Ok(Some(
+                Self::handle_group_message(
+                    groups, public_key, envelope, session_id,
                 )
                 .await?,
             )),
@@ -146,12 +254,36 @@ where
                     unreachable!()
                 }
             }
+            // Reply to a keepalive ping
+            ResponseMessage::Noop => Ok(None),
             _ => {
                 panic!("unhandled message");
             }
         }
     }
 
+    /// Request asking the server to close a session.
+    pub(crate) async fn close_session_request(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Option<RequestMessage>> {
+        let payload =
+            encode(&ServerMessage::CloseSession(session_id)).await?;
+        let envelope = {
+            let mut server = self.server.write().await;
+            match server.as_mut() {
+                Some(server) => {
+                    encrypt_server_channel(server, &payload, false)
+                        .await?
+                }
+                None => return Ok(None),
+            }
+        };
+        Ok(Some(RequestMessage::Opaque(
+            OpaqueMessage::ServerMessage(envelope),
+        )))
+    }
+
     /// Process an inner message from the server after
     /// decrypting the envelope.
     pub(crate) async fn handle_server_channel_message(
@@ -173,8 +305,20 @@ where
             ServerMessage::SessionTimeout(session_id) => {
                 Ok(Some(Event::SessionTimeout(session_id)))
             }
-            ServerMessage::SessionFinished(session_id) => {
-                Ok(Some(Event::SessionFinished(session_id)))
+            ServerMessage::SessionFinished(session_id, stats) => {
+                Ok(Some(Event::SessionFinished(session_id, stats)))
+            }
+            ServerMessage::SessionResumed(response) => {
+                Ok(Some(Event::SessionResumed(response)))
+            }
+            ServerMessage::SessionTranscript(transcript) => {
+                Ok(Some(Event::SessionTranscript(transcript)))
+            }
+            ServerMessage::SessionDigest(transcript) => {
+                Ok(Some(Event::SessionDigest(transcript)))
+            }
+            ServerMessage::ServerKey(server_key) => {
+                Ok(Some(Event::ServerKeyRotated { server_key }))
             }
             _ => Ok(None),
         }
@@ -187,21 +331,29 @@ where
         buf: Vec<u8>,
     ) -> Result<Event> {
         let mut state = server.write().await;
-        let transport = match state.take() {
+        let (transport, server_key) = match state.take() {
             Some(ProtocolState::Handshake(mut initiator)) => {
                 let mut read_buf = vec![0u8; 1024];
                 initiator.read_message(&buf[..len], &mut read_buf)?;
 
-                initiator.into_transport_mode()?
+                let server_key = initiator
+                    .get_remote_static()
+                    .map(|k| k.to_vec())
+                    .or_else(|| options.server_public_key.clone());
+                (initiator.into_transport_mode()?, server_key)
             }
             _ => return Err(Error::NotHandshakeState),
         };
 
+        if let (Some(trust), Some(server_key)) =
+            (&options.server_trust, &server_key)
+        {
+            trust.verify(server_key)?;
+        }
+
         *state = Some(ProtocolState::Transport(transport));
 
-        Ok(Event::ServerConnected {
-            server_key: options.server_public_key.clone(),
-        })
+        Ok(Event::ServerConnected { server_key })
     }
 
     async fn peer_handshake_responder(
@@ -219,28 +371,44 @@ where
             //Ok(None)
         } else {
             tracing::debug!(
-                from = ?hex::encode(public_key.as_ref()),
+                from = %fingerprint(public_key.as_ref()),
                 "peer handshake responder"
             );
 
-            let builder = Builder::new(options.params()?);
-            let mut responder = builder
+            let builder = Builder::new(options.params()?)
                 .local_private_key(
                     options.keypair.as_ref().unwrap().private_key(),
-                )
-                .remote_public_key(public_key.as_ref())
-                .build_responder()?;
+                );
+            let builder = if is_initiator_key_known(options.pattern())
+            {
+                builder.remote_public_key(public_key.as_ref())
+            } else {
+                builder
+            };
+            let mut responder = builder.build_responder()?;
 
             let mut read_buf = vec![0u8; 1024];
             responder.read_message(&buf[..len], &mut read_buf)?;
 
+            // Static key sent by the peer must belong to the
+            // connection assigned by the server
+            if let Some(remote) = responder.get_remote_static() {
+                if remote != public_key.as_ref() {
+                    return Err(Error::PeerKeyMismatch(hex::encode(
+                        public_key.as_ref(),
+                    )));
+                }
+            }
+
             let mut payload = vec![0u8; 1024];
             let len = responder.write_message(&[], &mut payload)?;
 
             let transport = responder.into_transport_mode()?;
             peers.insert(
                 public_key.as_ref().to_vec(),
-                ProtocolState::Transport(transport),
+                Arc::new(Mutex::new(ProtocolState::Transport(
+                    transport,
+                ))),
             );
 
             let request = RequestMessage::Transparent(
@@ -278,10 +446,14 @@ where
             };
 
         tracing::debug!(
-            from = ?hex::encode(public_key.as_ref()),
+            from = %fingerprint(public_key.as_ref()),
             "peer handshake done"
         );
 
+        // Handshake state is not shared with workers
+        let peer = Arc::try_unwrap(peer)
+            .map_err(|_| Error::NotHandshakeState)?
+            .into_inner();
         let transport = match peer {
             ProtocolState::Handshake(mut initiator) => {
                 let mut read_buf = vec![0u8; 1024];
@@ -293,7 +465,7 @@ where
 
         peers.insert(
             public_key.as_ref().to_vec(),
-            ProtocolState::Transport(transport),
+            Arc::new(Mutex::new(ProtocolState::Transport(transport))),
         );
 
         Ok(Event::PeerConnected {
@@ -303,29 +475,109 @@ where
 
     async fn handle_relayed_message(
         peers: Peers,
+        groups: Groups,
         public_key: impl AsRef<[u8]>,
         envelope: SealedEnvelope,
         session_id: Option<SessionId>,
+    ) -> Result<Option<Event>> {
+        let peer = {
+            let peers = peers.read().await;
+            peers.get(public_key.as_ref()).cloned()
+        };
+        if let Some(peer) = peer {
+            let (_, (encoding, contents)) =
+                with_peer(&peer, move |state| {
+                    decrypt_peer_channel(state, envelope)
+                })
+                .await?;
+
+            Self::handle_peer_payload(
+                groups,
+                public_key.as_ref().to_vec(),
+                encoding,
+                contents,
+                session_id,
+            )
+            .await
+        } else {
+            Err(Error::PeerNotFound(hex::encode(public_key.as_ref())))
+        }
+    }
+
+    /// Process a decrypted message from a peer.
+    pub(crate) async fn handle_peer_payload(
+        groups: Groups,
+        public_key: Vec<u8>,
+        encoding: Encoding,
+        contents: Vec<u8>,
+        session_id: Option<SessionId>,
+    ) -> Result<Option<Event>> {
+        // Group keys are consumed by the event loop; a key is
+        // assigned to the peer that sent it over the peer channel
+        // and only decrypts group messages from that peer
+        if let (Encoding::Json, Some(session_id)) =
+            (encoding, session_id)
+        {
+            if let Ok(message) =
+                serde_json::from_slice::<GroupKeyMessage>(&contents)
+            {
+                let mut groups = groups.write().await;
+                groups.insert(
+                    (session_id, public_key),
+                    message.try_into()?,
+                );
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(Self::message_event(
+            public_key, encoding, contents, session_id,
+        )))
+    }
+
+    async fn handle_group_message(
+        groups: Groups,
+        public_key: Vec<u8>,
+        envelope: SealedEnvelope,
+        session_id: SessionId,
     ) -> Result<Event> {
-        let mut peers = peers.write().await;
-        if let Some(peer) = peers.get_mut(public_key.as_ref()) {
+        let groups = groups.read().await;
+        if let Some(key) =
+            groups.get(&(session_id, public_key.clone()))
+        {
             let (encoding, contents) =
-                decrypt_peer_channel(peer, envelope).await?;
-            match encoding {
-                Encoding::Noop => unreachable!(),
-                Encoding::Blob => Ok(Event::BinaryMessage {
-                    peer_key: public_key.as_ref().to_vec(),
-                    message: contents,
-                    session_id,
-                }),
-                Encoding::Json => Ok(Event::JsonMessage {
-                    peer_key: public_key.as_ref().to_vec(),
-                    message: contents.into(),
-                    session_id,
-                }),
-            }
+                key.decrypt(&public_key, &session_id, envelope)?;
+            Ok(Self::message_event(
+                public_key,
+                encoding,
+                contents,
+                Some(session_id),
+            ))
         } else {
-            Err(Error::PeerNotFound(hex::encode(public_key.as_ref())))
+            Err(Error::GroupKeyNotFound(session_id))
+        }
+    }
+
+    fn message_event(
+        peer_key: Vec<u8>,
+        encoding: Encoding,
+        contents: Vec<u8>,
+        session_id: Option<SessionId>,
+    ) -> Event {
+        match encoding {
+            Encoding::Noop => unreachable!(),
+            Encoding::Blob => Event::BinaryMessage {
+                peer_key,
+                message: contents,
+                session_id,
+            },
+            Encoding::Json
+            | Encoding::Cbor
+            | Encoding::MessagePack => Event::JsonMessage {
+                peer_key,
+                message: JsonMessage::new(contents, encoding),
+                session_id,
+            },
         }
     }
 }
@@ -338,11 +590,37 @@ macro_rules! event_loop_run_impl {
             let options = self.options.clone();
             let server = self.server.clone();
             let peers = self.peers.clone();
+            let groups = self.groups.clone();
 
             let s = stream! {
+                // Sessions created by this client which are
+                // closed when the ceremony is cancelled
+                let mut owned_sessions: Vec<SessionId> = Vec::new();
                 loop {
                     tokio::select!(
                         biased;
+                        _ = crate::cancel::cancelled(options.cancel.clone()) => {
+                            for session_id in owned_sessions.drain(..) {
+                                let result = match self
+                                    .close_session_request(session_id)
+                                    .await
+                                {
+                                    Ok(Some(request)) => {
+                                        self.send_message(request).await
+                                    }
+                                    Ok(None) => Ok(()),
+                                    Err(e) => Err(e),
+                                };
+                                if let Err(e) = result {
+                                    tracing::warn!(error = %e);
+                                }
+                            }
+                            if let Err(e) = self.handle_cancel().await {
+                                tracing::warn!(error = %e);
+                            }
+                            yield Err(crate::Error::Cancelled);
+                            break;
+                        },
                         Some(message_out) = self.outbound_rx.recv() => {
                             match message_out {
                                 InternalMessage::Request(request) => {
@@ -369,6 +647,7 @@ macro_rules! event_loop_run_impl {
                         Some(message_in) = self.ws_reader.next() => {
                             match message_in {
                                 Ok(message) => {
+                                    self.keepalive.received();
                                     if let Err(e) = Self::read_message(
                                         options.clone(),
                                         message,
@@ -383,26 +662,139 @@ macro_rules! event_loop_run_impl {
                             }
                         },
                         Some(event_message) = self.inbound_rx.recv() => {
-                            match event_message {
+                            let result = match event_message {
+                                // Relayed messages are decrypted by
+                                // the worker for the peer
+                                #[cfg(not(all(
+                                    target_arch = "wasm32",
+                                    target_os = "unknown"
+                                )))]
+                                IncomingMessage::Response(
+                                    ResponseMessage::Opaque(
+                                        OpaqueMessage::PeerMessage {
+                                            public_key,
+                                            envelope,
+                                            session_id,
+                                        },
+                                    ),
+                                ) => {
+                                    self.workers.decrypt(
+                                        &peers,
+                                        &self.inbound_tx,
+                                        public_key,
+                                        envelope,
+                                        session_id,
+                                    );
+                                    continue;
+                                }
+                                #[cfg(not(all(
+                                    target_arch = "wasm32",
+                                    target_os = "unknown"
+                                )))]
+                                IncomingMessage::Decrypted {
+                                    public_key,
+                                    result,
+                                    session_id,
+                                } => match *result {
+                                    Ok((encoding, contents)) => {
+                                        Self::handle_peer_payload(
+                                            groups.clone(),
+                                            public_key,
+                                            encoding,
+                                            contents,
+                                            session_id,
+                                        ).await
+                                    }
+                                    Err(e) => Err(e),
+                                },
                                 IncomingMessage::Response(message) => {
-                                    match Self::handle_incoming_message(
+                                    Self::handle_incoming_message(
                                         options.clone(),
                                         server.clone(),
                                         peers.clone(),
+                                        groups.clone(),
                                         message,
                                         self.outbound_tx.clone(),
-                                    ).await {
-                                        Ok(Some(event)) => {
-                                            yield Ok(event);
+                                    ).await
+                                }
+                                IncomingMessage::Meeting(message) => {
+                                    yield Ok(Event::Meeting(message));
+                                    continue;
+                                }
+                                #[cfg(not(all(
+                                    target_arch = "wasm32",
+                                    target_os = "unknown"
+                                )))]
+                                IncomingMessage::Direct(message) => {
+                                    self.handle_direct_message(message).await
+                                }
+                            };
+
+                            // Direct connection control messages
+                            // are consumed by the event loop
+                            #[cfg(not(all(
+                                target_arch = "wasm32",
+                                target_os = "unknown"
+                            )))]
+                            let result = match result {
+                                Ok(Some(event)) => Ok(self.intercept_direct(event)),
+                                result => result,
+                            };
+
+                            match result {
+                                Ok(Some(event)) => {
+                                    #[cfg(all(
+                                        feature = "record",
+                                        not(all(
+                                            target_arch = "wasm32",
+                                            target_os = "unknown"
+                                        ))
+                                    ))]
+                                    if let Some(recorder) = &options.recorder {
+                                        recorder.incoming(&event);
+                                    }
+                                    #[cfg(all(
+                                        feature = "journal",
+                                        not(all(
+                                            target_arch = "wasm32",
+                                            target_os = "unknown"
+                                        ))
+                                    ))]
+                                    if let Some(journal) = &options.journal {
+                                        journal.append(&event);
+                                    }
+                                    match &event {
+                                        Event::SessionCreated(session) => {
+                                            owned_sessions.push(session.session_id);
                                         }
-                                        Err(e) => {
-                                            yield Err(e)
+                                        Event::SessionFinished(id, _) => {
+                                            owned_sessions.retain(|s| s != id);
                                         }
                                         _ => {}
                                     }
+                                    yield Ok(event);
                                 }
-                                IncomingMessage::Meeting(message) => {
-                                    yield Ok(Event::Meeting(message))
+                                Err(e) => {
+                                    yield Err(e)
+                                }
+                                _ => {}
+                            }
+                        },
+                        action = self.keepalive.next() => {
+                            match action {
+                                crate::keepalive::KeepaliveAction::Ping => {
+                                    if let Err(e) = self.send_message(RequestMessage::Noop).await {
+                                        tracing::warn!(error = %e);
+                                        yield Err(e)
+                                    }
+                                }
+                                crate::keepalive::KeepaliveAction::Timeout => {
+                                    tracing::warn!("keepalive::idle_timeout");
+                                    if let Err(e) = self.handle_cancel().await {
+                                        tracing::warn!(error = %e);
+                                    }
+                                    yield Err(crate::Error::IdleTimeout);
+                                    break;
                                 }
                             }
                         },
@@ -0,0 +1,138 @@
+//! Fault injection for testing error handling.
+//!
+//! Enable the `faults` feature and assign [Faults] to the
+//! client options to drop, duplicate or delay messages sent
+//! to peers and to sever the connection when a ceremony
+//! reaches a round.
+//!
+//! Faults are applied to the plaintext before it is encrypted
+//! so the noise transport channel with each peer stays in
+//! sync; a dropped message is never encrypted and a duplicated
+//! message is encrypted and sent twice so the peer receives
+//! the same message two times.
+//!
+//! Decisions are made by a random number generator seeded
+//! from the [FaultConfig] so a run can be repeated with the
+//! same faults as long as messages are sent in the same order.
+use crate::{event_loop::InternalMessage, Result};
+use polysig_protocol::fingerprint;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use tokio::{sync::mpsc, time::Instant};
+
+/// Faults to inject into a client.
+#[derive(Debug, Clone, Default)]
+pub struct FaultConfig {
+    /// Seed for the random number generator.
+    pub seed: u64,
+    /// Probability that a message to a peer is dropped
+    /// between `0.0` and `1.0`.
+    pub drop_rate: f64,
+    /// Probability that a message to a peer is sent twice
+    /// between `0.0` and `1.0`.
+    pub duplicate_rate: f64,
+    /// Delay messages sent to peers keyed by public key.
+    ///
+    /// Messages to a delayed peer are sent in order.
+    pub delay: HashMap<Vec<u8>, Duration>,
+    /// Close the connection when a ceremony reaches
+    /// this round number.
+    pub sever_at_round: Option<u16>,
+}
+
+/// Injects faults into the messages sent by a client.
+///
+/// Each client must be assigned its own instance.
+pub struct Faults {
+    config: FaultConfig,
+    rng: Mutex<StdRng>,
+    delayed: Mutex<HashMap<Vec<u8>, DelayQueue>>,
+}
+
+type DelayQueue = mpsc::UnboundedSender<(Instant, InternalMessage)>;
+
+impl Faults {
+    /// Create fault injection for a client.
+    pub fn new(config: FaultConfig) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(config.seed)),
+            config,
+            delayed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fault configuration.
+    pub fn config(&self) -> &FaultConfig {
+        &self.config
+    }
+
+    /// Number of times to send a message to a peer.
+    ///
+    /// Zero when the message is dropped.
+    pub(crate) fn copies(&self, peer_key: &[u8]) -> usize {
+        let mut rng = self.rng.lock().unwrap();
+        if rng.gen::<f64>() < self.config.drop_rate {
+            tracing::debug!(
+                peer_key = %fingerprint(peer_key),
+                "fault drop message"
+            );
+            0
+        } else if rng.gen::<f64>() < self.config.duplicate_rate {
+            tracing::debug!(
+                peer_key = %fingerprint(peer_key),
+                "fault duplicate message"
+            );
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Whether to close the connection at a round.
+    pub(crate) fn sever(&self, round: u16) -> bool {
+        self.config.sever_at_round == Some(round)
+    }
+
+    /// Send a message to a peer applying any delay.
+    pub(crate) fn send(
+        &self,
+        peer_key: &[u8],
+        outbound_tx: &mpsc::UnboundedSender<InternalMessage>,
+        message: InternalMessage,
+    ) -> Result<()> {
+        let Some(delay) = self.config.delay.get(peer_key) else {
+            outbound_tx.send(message)?;
+            return Ok(());
+        };
+
+        let mut delayed = self.delayed.lock().unwrap();
+        let queue = delayed
+            .entry(peer_key.to_vec())
+            .or_insert_with(|| delay_queue(outbound_tx.clone()));
+        if queue.send((Instant::now() + *delay, message)).is_err() {
+            tracing::debug!(
+                peer_key = %fingerprint(peer_key),
+                "fault delay queue closed"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Spawn a task that forwards messages to the event loop
+/// once the delay for each message has elapsed.
+fn delay_queue(
+    outbound_tx: mpsc::UnboundedSender<InternalMessage>,
+) -> DelayQueue {
+    let (tx, mut rx) =
+        mpsc::unbounded_channel::<(Instant, InternalMessage)>();
+    tokio::task::spawn(async move {
+        while let Some((deadline, message)) = rx.recv().await {
+            tokio::time::sleep_until(deadline).await;
+            if outbound_tx.send(message).is_err() {
+                break;
+            }
+        }
+    });
+    tx
+}
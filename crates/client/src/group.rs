@@ -0,0 +1,187 @@
+//! Session group keys for broadcast messages.
+//!
+//! Broadcasting over the peer channels encrypts the same
+//! payload once for every peer. A participant may create a
+//! symmetric group key for the messages it broadcasts in a
+//! session which is sent to the other participants over the
+//! peer channels; broadcasts are then encrypted once with the
+//! group key and the relay server fans out the envelope to the
+//! other session participants.
+//!
+//! Every participant has its own group key and a key is only
+//! accepted from the participant that owns it over the
+//! authenticated peer channel. Group messages are decrypted
+//! with the key of the sender so a participant cannot send
+//! messages as another member of the group.
+use crate::{Error, Result};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use polysig_protocol::{
+    hex, Chunk, Encoding, SealedEnvelope, SessionId,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+
+/// Group keys by session and the public key of the
+/// participant that owns the key.
+pub(crate) type Groups =
+    Arc<RwLock<HashMap<(SessionId, Vec<u8>), GroupKey>>>;
+
+const DOMAIN: &[u8] = b"polysig-group";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+const CHUNK_SIZE: usize = 65535 - NONCE_LEN - TAG_LEN;
+
+/// Symmetric key for the broadcasts of a participant in
+/// a session.
+#[derive(Clone)]
+pub(crate) struct GroupKey([u8; KEY_LEN]);
+
+impl GroupKey {
+    /// Generate a random group key.
+    pub fn generate() -> Result<Self> {
+        let mut key = [0u8; KEY_LEN];
+        getrandom::getrandom(&mut key)
+            .map_err(|e| Error::Generic(e.to_string().into()))?;
+        Ok(Self(key))
+    }
+
+    /// Encrypt a payload into chunks of an envelope.
+    pub fn encrypt(
+        &self,
+        sender: &[u8],
+        session_id: &SessionId,
+        payload: &[u8],
+        encoding: Encoding,
+    ) -> Result<SealedEnvelope> {
+        let cipher = self.cipher();
+        let parts: Vec<&[u8]> = if payload.is_empty() {
+            vec![&[]]
+        } else {
+            payload.chunks(CHUNK_SIZE).collect()
+        };
+        let total = parts.len();
+        let mut chunks = Vec::with_capacity(total);
+        for (index, part) in parts.into_iter().enumerate() {
+            let mut nonce = [0u8; NONCE_LEN];
+            getrandom::getrandom(&mut nonce)
+                .map_err(|e| Error::Generic(e.to_string().into()))?;
+            let aad = aad(sender, session_id, encoding, index, total);
+            let ciphertext = cipher
+                .encrypt(
+                    XNonce::from_slice(&nonce),
+                    Payload {
+                        msg: part,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| Error::GroupMessage)?;
+            let mut contents = nonce.to_vec();
+            contents.extend_from_slice(&ciphertext);
+            chunks.push(Chunk {
+                length: contents.len(),
+                contents,
+            });
+        }
+        Ok(SealedEnvelope {
+            encoding,
+            chunks,
+            broadcast: true,
+        })
+    }
+
+    /// Decrypt the chunks of an envelope.
+    pub fn decrypt(
+        &self,
+        sender: &[u8],
+        session_id: &SessionId,
+        envelope: SealedEnvelope,
+    ) -> Result<(Encoding, Vec<u8>)> {
+        let cipher = self.cipher();
+        let total = envelope.chunks.len();
+        let mut payload = Vec::new();
+        for (index, chunk) in envelope.chunks.into_iter().enumerate()
+        {
+            if chunk.contents.len() < NONCE_LEN + TAG_LEN {
+                return Err(Error::GroupMessage);
+            }
+            let (nonce, ciphertext) =
+                chunk.contents.split_at(NONCE_LEN);
+            let aad = aad(
+                sender,
+                session_id,
+                envelope.encoding,
+                index,
+                total,
+            );
+            let mut plaintext = cipher
+                .decrypt(
+                    XNonce::from_slice(nonce),
+                    Payload {
+                        msg: ciphertext,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| Error::GroupMessage)?;
+            payload.append(&mut plaintext);
+        }
+        Ok((envelope.encoding, payload))
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(&self.0))
+    }
+}
+
+/// Associated data binds each chunk to the session, the
+/// sender and the position in the message.
+fn aad(
+    sender: &[u8],
+    session_id: &SessionId,
+    encoding: Encoding,
+    index: usize,
+    total: usize,
+) -> Vec<u8> {
+    let mut aad = DOMAIN.to_vec();
+    aad.extend_from_slice(session_id.as_bytes());
+    aad.extend_from_slice(sender);
+    aad.push(encoding.into());
+    aad.extend_from_slice(&(index as u32).to_be_bytes());
+    aad.extend_from_slice(&(total as u32).to_be_bytes());
+    aad
+}
+
+/// Message that sends a group key to a peer.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) struct GroupKeyMessage {
+    #[serde(with = "hex::serde")]
+    group_key: Vec<u8>,
+}
+
+impl From<&GroupKey> for GroupKeyMessage {
+    fn from(value: &GroupKey) -> Self {
+        Self {
+            group_key: value.0.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<GroupKeyMessage> for GroupKey {
+    type Error = Error;
+
+    fn try_from(
+        value: GroupKeyMessage,
+    ) -> std::result::Result<Self, Self::Error> {
+        let key: [u8; KEY_LEN] = value
+            .group_key
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::GroupMessage)?;
+        Ok(Self(key))
+    }
+}
@@ -0,0 +1,278 @@
+//! Relay connections multiplexed over HTTP/2.
+//!
+//! An [Http2Connection] is a single HTTP/2 connection to a
+//! relay server; clients created with the connection open a
+//! stream on the connection instead of a websocket so the
+//! clients for several sessions share one connection and each
+//! session has its own flow control window.
+//!
+//! Messages are written to the request and response bodies
+//! as length-prefixed frames, see [polysig_protocol::stream];
+//! the server must enable HTTP/2 streams.
+//!
+//! Servers with `ws:` or `http:` URLs are connected using
+//! HTTP/2 with prior knowledge and servers with `wss:` or
+//! `https:` URLs negotiate HTTP/2 using ALPN and the webpki
+//! root certificates.
+use bytes::Bytes;
+use futures::{future::poll_fn, sink, stream, Sink, Stream};
+use h2::{client::SendRequest, Reason, RecvStream, SendStream};
+use polysig_protocol::{
+    http::{
+        uri::{Authority, Scheme},
+        Method, Request, StatusCode, Uri,
+    },
+    stream::{encode_frame, FrameDecoder},
+    STREAM_PATH, SUBPROTOCOL, SUBPROTOCOL_HEADER,
+};
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio_rustls::{
+    rustls::{pki_types::ServerName, ClientConfig, RootCertStore},
+    TlsConnector,
+};
+use tokio_tungstenite::tungstenite::{
+    protocol::Message, Error as WsError,
+};
+
+use crate::{Error, Result};
+
+/// Connection to a relay server shared by clients.
+///
+/// Cloning the connection is cheap and each clone opens
+/// streams on the same underlying connection.
+#[derive(Clone)]
+pub struct Http2Connection {
+    send_request: SendRequest<Bytes>,
+    scheme: Scheme,
+    authority: Authority,
+}
+
+impl Http2Connection {
+    /// Connect to a relay server.
+    ///
+    /// The server URL may use the websocket or HTTP schemes,
+    /// for example `ws://localhost:8008`.
+    pub async fn connect(server: &str) -> Result<Self> {
+        let uri: Uri = server
+            .parse()
+            .map_err(|_| Error::RelayUrl(server.to_owned()))?;
+        let scheme = match uri.scheme_str() {
+            Some("ws") | Some("http") => Scheme::HTTP,
+            Some("wss") | Some("https") => Scheme::HTTPS,
+            _ => return Err(Error::RelayUrl(server.to_owned())),
+        };
+        let authority = uri
+            .authority()
+            .cloned()
+            .ok_or_else(|| Error::RelayUrl(server.to_owned()))?;
+        let port = authority.port_u16().unwrap_or(
+            if scheme == Scheme::HTTPS { 443 } else { 80 },
+        );
+
+        let tcp =
+            TcpStream::connect((authority.host(), port)).await?;
+        tcp.set_nodelay(true)?;
+
+        let send_request = if scheme == Scheme::HTTPS {
+            let mut roots = RootCertStore::empty();
+            roots.extend(
+                webpki_roots::TLS_SERVER_ROOTS.iter().cloned(),
+            );
+            let mut config = ClientConfig::builder()
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            config.alpn_protocols = vec![b"h2".to_vec()];
+            let name =
+                ServerName::try_from(authority.host().to_owned())
+                    .map_err(|_| {
+                        Error::RelayUrl(server.to_owned())
+                    })?;
+            let tls = TlsConnector::from(Arc::new(config))
+                .connect(name, tcp)
+                .await?;
+            let (send_request, connection) =
+                h2::client::handshake(tls).await?;
+            tokio::spawn(drive(connection));
+            send_request
+        } else {
+            let (send_request, connection) =
+                h2::client::handshake(tcp).await?;
+            tokio::spawn(drive(connection));
+            send_request
+        };
+
+        Ok(Self {
+            send_request,
+            scheme,
+            authority,
+        })
+    }
+
+    /// Open a stream for a client.
+    ///
+    /// The query string is the query for the connection URL
+    /// of the client, see
+    /// [ClientOptions::url](crate::ClientOptions::url).
+    pub(crate) async fn open(
+        &self,
+        query: Option<&str>,
+    ) -> Result<(StreamReader, StreamWriter)> {
+        let path_and_query = match query {
+            Some(query) => format!("{}?{}", STREAM_PATH, query),
+            None => STREAM_PATH.to_owned(),
+        };
+        let uri = Uri::builder()
+            .scheme(self.scheme.clone())
+            .authority(self.authority.clone())
+            .path_and_query(path_and_query)
+            .build()
+            .map_err(|e| Error::RelayUrl(e.to_string()))?;
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header(SUBPROTOCOL_HEADER, SUBPROTOCOL)
+            .body(())
+            .map_err(|e| Error::RelayUrl(e.to_string()))?;
+
+        let mut send_request =
+            self.send_request.clone().ready().await?;
+        let (response, send) =
+            send_request.send_request(request, false)?;
+        let response = response.await?;
+
+        let status = response.status();
+        if status == StatusCode::UPGRADE_REQUIRED
+            || (status == StatusCode::OK
+                && response
+                    .headers()
+                    .get(SUBPROTOCOL_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    != Some(SUBPROTOCOL))
+        {
+            return Err(Error::UnsupportedSubprotocol(
+                SUBPROTOCOL.to_owned(),
+            ));
+        }
+        if status != StatusCode::OK {
+            return Err(Error::ConnectError(
+                status,
+                status.to_string(),
+            ));
+        }
+
+        Ok((reader(response.into_body()), writer(send)))
+    }
+}
+
+/// Messages read from a stream.
+pub(crate) type StreamReader = std::pin::Pin<
+    Box<
+        dyn Stream<Item = std::result::Result<Message, WsError>>
+            + Send
+            + Sync,
+    >,
+>;
+
+/// Messages written to a stream.
+pub(crate) type StreamWriter = std::pin::Pin<
+    Box<dyn Sink<Message, Error = WsError> + Send + Sync>,
+>;
+
+/// Drive the connection until it is closed.
+async fn drive<T>(connection: h2::client::Connection<T, Bytes>)
+where
+    T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    if let Err(e) = connection.await {
+        tracing::warn!(error = %e, "http2::connection");
+    }
+}
+
+fn into_ws_error(error: h2::Error) -> WsError {
+    WsError::Io(std::io::Error::other(error))
+}
+
+/// Decode frames from the response body as binary messages.
+///
+/// The stream ends after the first error.
+fn reader(body: RecvStream) -> StreamReader {
+    Box::pin(stream::unfold(
+        Some((body, FrameDecoder::default())),
+        |state| async move {
+            let (mut body, mut decoder) = state?;
+            loop {
+                match decoder.next_frame() {
+                    Ok(Some(frame)) => {
+                        return Some((
+                            Ok(Message::Binary(frame)),
+                            Some((body, decoder)),
+                        ))
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        let e = std::io::Error::other(e);
+                        return Some((Err(WsError::Io(e)), None));
+                    }
+                }
+                match body.data().await? {
+                    Ok(chunk) => {
+                        // Open the flow control window for the
+                        // data that has been received
+                        let _ = body
+                            .flow_control()
+                            .release_capacity(chunk.len());
+                        decoder.push(&chunk);
+                    }
+                    Err(e) => {
+                        return Some((Err(into_ws_error(e)), None))
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Encode binary messages as frames in the request body.
+///
+/// A close message ends the request body.
+fn writer(send: SendStream<Bytes>) -> StreamWriter {
+    Box::pin(sink::unfold(send, |mut send, message| async move {
+        match message {
+            Message::Binary(buffer) => {
+                let frame = encode_frame(&buffer).map_err(|e| {
+                    WsError::Io(std::io::Error::other(e))
+                })?;
+                send_data(&mut send, frame.into())
+                    .await
+                    .map_err(into_ws_error)?;
+            }
+            Message::Close(_) => {
+                send.send_data(Bytes::new(), true)
+                    .map_err(into_ws_error)?;
+            }
+            _ => {}
+        }
+        Ok::<_, WsError>(send)
+    }))
+}
+
+/// Send data once the peer has granted capacity
+/// for the stream.
+async fn send_data(
+    send: &mut SendStream<Bytes>,
+    mut data: Bytes,
+) -> std::result::Result<(), h2::Error> {
+    while !data.is_empty() {
+        send.reserve_capacity(data.len());
+        let capacity =
+            poll_fn(|cx| send.poll_capacity(cx))
+                .await
+                .ok_or_else(|| h2::Error::from(Reason::CANCEL))??;
+        if capacity > 0 {
+            let chunk = data.split_to(capacity.min(data.len()));
+            send.send_data(chunk, false)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,533 @@
+//! Persistent journal of session events.
+//!
+//! Enable the `journal` feature and assign a [Journal] to the
+//! client options to append every event for a session to a
+//! file in the journal directory after it has been decrypted.
+//! Events are written in the order they are emitted by the
+//! event loop and each event is assigned an offset starting
+//! at one for the first event of a session.
+//!
+//! When an application exits before a ceremony completes it
+//! can create a new client with the same journal and call
+//! [Journal::resume] with the event stream; the resumed stream
+//! yields the events that were not processed before the exit
+//! followed by the events from the new connection. The offset
+//! of the last processed event is committed to disk each time
+//! the next event is requested from a resumed stream.
+//!
+//! Each session is written to a JSON lines file named after
+//! the session identifier; every line is a [JournalEntry]. The
+//! committed offset is stored in a separate file with the
+//! `offset` extension.
+//!
+//! Journals contain the plaintext of every message received
+//! from peers, including secret shares sent in peer to peer
+//! rounds, so the journal directory must be protected in the
+//! same way as key shares.
+use crate::{timestamp_millis, Error, EventStream, Result};
+use futures::StreamExt;
+use polysig_protocol::{hex, Event, SessionId, SessionState};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Extension for journal files.
+const JOURNAL: &str = "jsonl";
+
+/// Extension for committed offset files.
+const OFFSET: &str = "offset";
+
+/// Session event stored in a journal.
+///
+/// The session identifier is not stored as every entry
+/// in a journal file belongs to the same session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum JournalEvent {
+    /// Session was created.
+    #[serde(rename_all = "camelCase")]
+    SessionCreated {
+        /// Public keys of all participants.
+        #[serde(with = "keys")]
+        all_participants: Vec<Vec<u8>>,
+    },
+    /// Session is ready.
+    #[serde(rename_all = "camelCase")]
+    SessionReady {
+        /// Public keys of all participants.
+        #[serde(with = "keys")]
+        all_participants: Vec<Vec<u8>>,
+    },
+    /// Session is active.
+    #[serde(rename_all = "camelCase")]
+    SessionActive {
+        /// Public keys of all participants.
+        #[serde(with = "keys")]
+        all_participants: Vec<Vec<u8>>,
+    },
+    /// Session timed out.
+    SessionTimeout,
+    /// Session was finished.
+    SessionFinished,
+    /// JSON message received from a peer.
+    #[serde(rename_all = "camelCase")]
+    JsonMessage {
+        /// Public key of the peer.
+        #[serde(with = "hex::serde")]
+        peer_key: Vec<u8>,
+        /// JSON message.
+        message: Value,
+    },
+    /// Binary message received from a peer.
+    #[serde(rename_all = "camelCase")]
+    BinaryMessage {
+        /// Public key of the peer.
+        #[serde(with = "hex::serde")]
+        peer_key: Vec<u8>,
+        /// Message buffer.
+        #[serde(with = "hex::serde")]
+        message: Vec<u8>,
+    },
+}
+
+impl JournalEvent {
+    /// Convert an event into a journal event.
+    ///
+    /// Returns `None` for events that do not belong to
+    /// a session.
+    fn from_event(
+        event: &Event,
+    ) -> Result<Option<(SessionId, JournalEvent)>> {
+        Ok(Some(match event {
+            Event::SessionCreated(state) => (
+                state.session_id,
+                JournalEvent::SessionCreated {
+                    all_participants: state.all_participants.clone(),
+                },
+            ),
+            Event::SessionReady(state) => (
+                state.session_id,
+                JournalEvent::SessionReady {
+                    all_participants: state.all_participants.clone(),
+                },
+            ),
+            Event::SessionActive(state) => (
+                state.session_id,
+                JournalEvent::SessionActive {
+                    all_participants: state.all_participants.clone(),
+                },
+            ),
+            Event::SessionTimeout(session_id) => {
+                (*session_id, JournalEvent::SessionTimeout)
+            }
+            Event::SessionFinished(session_id, _) => {
+                (*session_id, JournalEvent::SessionFinished)
+            }
+            Event::JsonMessage {
+                peer_key,
+                message,
+                session_id: Some(session_id),
+            } => (
+                *session_id,
+                JournalEvent::JsonMessage {
+                    peer_key: peer_key.clone(),
+                    message: message.deserialize()?,
+                },
+            ),
+            Event::BinaryMessage {
+                peer_key,
+                message,
+                session_id: Some(session_id),
+            } => (
+                *session_id,
+                JournalEvent::BinaryMessage {
+                    peer_key: peer_key.clone(),
+                    message: message.clone(),
+                },
+            ),
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Convert this journal event into an event for a session.
+    pub fn into_event(self, session_id: SessionId) -> Result<Event> {
+        let state = |all_participants| SessionState {
+            session_id,
+            all_participants,
+            token: None,
+        };
+        Ok(match self {
+            JournalEvent::SessionCreated { all_participants } => {
+                Event::SessionCreated(state(all_participants))
+            }
+            JournalEvent::SessionReady { all_participants } => {
+                Event::SessionReady(state(all_participants))
+            }
+            JournalEvent::SessionActive { all_participants } => {
+                Event::SessionActive(state(all_participants))
+            }
+            JournalEvent::SessionTimeout => {
+                Event::SessionTimeout(session_id)
+            }
+            JournalEvent::SessionFinished => {
+                Event::SessionFinished(session_id, None)
+            }
+            JournalEvent::JsonMessage { peer_key, message } => {
+                Event::JsonMessage {
+                    peer_key,
+                    message: serde_json::to_vec(&message)?.into(),
+                    session_id: Some(session_id),
+                }
+            }
+            JournalEvent::BinaryMessage { peer_key, message } => {
+                Event::BinaryMessage {
+                    peer_key,
+                    message,
+                    session_id: Some(session_id),
+                }
+            }
+        })
+    }
+}
+
+/// Event stored in a journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    /// Position in the session journal starting at one.
+    pub offset: u64,
+    /// Milliseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// Session event.
+    #[serde(flatten)]
+    pub event: JournalEvent,
+}
+
+/// Appends session events to files in a directory.
+#[derive(Clone)]
+pub struct Journal {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    dir: PathBuf,
+    writers: Mutex<HashMap<SessionId, Writer>>,
+}
+
+struct Writer {
+    file: File,
+    offset: u64,
+}
+
+impl Journal {
+    /// Open a journal in a directory.
+    ///
+    /// The directory is created if it does not exist.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                dir,
+                writers: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+
+    /// Journal directory.
+    pub fn dir(&self) -> &Path {
+        &self.inner.dir
+    }
+
+    /// Sessions that have a journal file.
+    pub fn sessions(&self) -> Result<Vec<SessionId>> {
+        let mut sessions = Vec::new();
+        for entry in std::fs::read_dir(self.dir())? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str())
+                != Some(JOURNAL)
+            {
+                continue;
+            }
+            if let Some(session_id) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse().ok())
+            {
+                sessions.push(session_id);
+            }
+        }
+        sessions.sort();
+        Ok(sessions)
+    }
+
+    /// Offset of the last event appended for a session.
+    ///
+    /// Zero when no events have been appended.
+    pub fn offset(&self, session_id: SessionId) -> Result<u64> {
+        let writers = self.inner.writers.lock().unwrap();
+        if let Some(writer) = writers.get(&session_id) {
+            return Ok(writer.offset);
+        }
+        self.last_offset(session_id)
+    }
+
+    /// Events for a session with an offset after `offset`.
+    ///
+    /// A trailing line that was only partially written when
+    /// the application exited is ignored.
+    pub fn entries(
+        &self,
+        session_id: SessionId,
+        offset: u64,
+    ) -> Result<Vec<JournalEntry>> {
+        let path = self.path(session_id, JOURNAL);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let lines = BufReader::new(File::open(path)?)
+            .lines()
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let last = lines.len().saturating_sub(1);
+        let mut entries = Vec::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: JournalEntry =
+                match serde_json::from_str(&line) {
+                    Ok(entry) => entry,
+                    Err(e) if index == last => {
+                        tracing::warn!(
+                            session_id = %session_id,
+                            error = %e,
+                            "journal ignore partial entry"
+                        );
+                        break;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+            if entry.offset > offset {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Offset of the last processed event for a session.
+    ///
+    /// Zero when no events have been processed.
+    pub fn committed(&self, session_id: SessionId) -> Result<u64> {
+        let path = self.path(session_id, OFFSET);
+        if !path.exists() {
+            return Ok(0);
+        }
+        let contents = std::fs::read_to_string(path)?;
+        contents.trim().parse().map_err(|_| {
+            Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "journal offset is not a number",
+            ))
+        })
+    }
+
+    /// Commit the offset of the last processed event
+    /// for a session.
+    pub fn commit(
+        &self,
+        session_id: SessionId,
+        offset: u64,
+    ) -> Result<()> {
+        // Write to a temporary file and rename so the offset
+        // is never partially written
+        let path = self.path(session_id, OFFSET);
+        let temp = path.with_extension("offset.tmp");
+        std::fs::write(&temp, offset.to_string())?;
+        std::fs::rename(temp, path)?;
+        Ok(())
+    }
+
+    /// Delete the journal for a session.
+    ///
+    /// Call this once a ceremony has completed and the
+    /// output has been stored.
+    pub fn remove(&self, session_id: SessionId) -> Result<()> {
+        self.inner.writers.lock().unwrap().remove(&session_id);
+        for extension in [JOURNAL, OFFSET] {
+            let path = self.path(session_id, extension);
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Resume the events for a session.
+    ///
+    /// The returned stream yields the events after the committed
+    /// offset followed by the events from `stream` which should
+    /// be the event stream for a client using this journal.
+    ///
+    /// When the next event is requested the offset of the
+    /// previous event for the session is committed; events
+    /// that do not belong to the session are passed through.
+    pub fn resume(
+        &self,
+        session_id: SessionId,
+        mut stream: EventStream,
+    ) -> Result<EventStream> {
+        let committed = self.committed(session_id)?;
+        let entries = self.entries(session_id, committed)?;
+        let journal = self.clone();
+        let s = async_stream::stream! {
+            for entry in entries {
+                let offset = entry.offset;
+                yield entry.event.into_event(session_id);
+                journal.commit_processed(session_id, offset);
+            }
+            while let Some(event) = stream.next().await {
+                let offset = match &event {
+                    Ok(event)
+                        if session_of(event) == Some(session_id) =>
+                    {
+                        journal.offset(session_id).ok()
+                    }
+                    _ => None,
+                };
+                yield event;
+                if let Some(offset) = offset {
+                    journal.commit_processed(session_id, offset);
+                }
+            }
+        };
+        Ok(Box::pin(s))
+    }
+
+    /// Append an event to the journal.
+    ///
+    /// Failures are logged so journaling never interrupts
+    /// a ceremony.
+    pub(crate) fn append(&self, event: &Event) {
+        if let Err(e) = self.try_append(event) {
+            tracing::warn!(error = %e, "journal append event");
+        }
+    }
+
+    fn try_append(&self, event: &Event) -> Result<()> {
+        let Some((session_id, event)) =
+            JournalEvent::from_event(event)?
+        else {
+            return Ok(());
+        };
+
+        let mut writers = self.inner.writers.lock().unwrap();
+        if !writers.contains_key(&session_id) {
+            let writer = self.open_writer(session_id)?;
+            writers.insert(session_id, writer);
+        }
+        let writer = writers.get_mut(&session_id).unwrap();
+        let entry = JournalEntry {
+            offset: writer.offset + 1,
+            timestamp: timestamp_millis(),
+            event,
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        writer.file.write_all(&line)?;
+        writer.file.flush()?;
+        writer.offset = entry.offset;
+        Ok(())
+    }
+
+    /// Open the journal file for a session to append events
+    /// after the last complete entry.
+    fn open_writer(&self, session_id: SessionId) -> Result<Writer> {
+        let offset = self.last_offset(session_id)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(self.path(session_id, JOURNAL))?;
+
+        // Terminate a partially written entry so the next
+        // entry starts on a new line
+        if file.metadata()?.len() > 0 {
+            let mut last = [0u8; 1];
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                file.write_all(b"\n")?;
+            }
+        }
+        Ok(Writer { file, offset })
+    }
+
+    fn last_offset(&self, session_id: SessionId) -> Result<u64> {
+        Ok(self
+            .entries(session_id, 0)?
+            .last()
+            .map(|e| e.offset)
+            .unwrap_or_default())
+    }
+
+    fn commit_processed(&self, session_id: SessionId, offset: u64) {
+        if let Err(e) = self.commit(session_id, offset) {
+            tracing::warn!(error = %e, "journal commit offset");
+        }
+    }
+
+    fn path(
+        &self,
+        session_id: SessionId,
+        extension: &str,
+    ) -> PathBuf {
+        self.dir().join(format!("{}.{}", session_id, extension))
+    }
+}
+
+/// Session identifier for an event.
+fn session_of(event: &Event) -> Option<SessionId> {
+    match event {
+        Event::SessionCreated(state)
+        | Event::SessionReady(state)
+        | Event::SessionActive(state) => Some(state.session_id),
+        Event::SessionTimeout(session_id)
+        | Event::SessionFinished(session_id, _) => Some(*session_id),
+        Event::JsonMessage { session_id, .. }
+        | Event::BinaryMessage { session_id, .. } => *session_id,
+        _ => None,
+    }
+}
+
+/// Serialize a list of public keys as hex strings.
+mod keys {
+    use polysig_protocol::hex;
+    use serde::{
+        de::Error, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    pub fn serialize<S: Serializer>(
+        keys: &[Vec<u8>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        keys.iter()
+            .map(hex::encode)
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|key| hex::decode(key).map_err(D::Error::custom))
+            .collect()
+    }
+}
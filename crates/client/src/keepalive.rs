@@ -0,0 +1,371 @@
+//! Keepalive for relay connections.
+//!
+//! When keepalive is enabled the event loop sends a ping to
+//! the server at an interval and the server replies to each
+//! ping; a connection that does not receive any message from
+//! the server within the idle timeout is considered lost and
+//! the event stream yields [crate::Error::IdleTimeout] so the
+//! caller can reconnect instead of waiting forever.
+//!
+//! Browsers throttle timers for hidden pages and freeze pages
+//! in the back/forward cache so the web client pauses the
+//! idle timeout whilst the page is hidden and pings the server
+//! as soon as the page is shown again; a socket that was closed
+//! whilst the page was hidden fails the ping.
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::ClientOptions;
+
+/// Keepalive settings for a relay connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct KeepaliveOptions {
+    /// Seconds between pings sent to the server.
+    pub ping_interval: u32,
+    /// Seconds without a message from the server before
+    /// the connection is considered lost.
+    pub idle_timeout: u32,
+}
+
+impl Default for KeepaliveOptions {
+    fn default() -> Self {
+        Self {
+            ping_interval: 15,
+            idle_timeout: 45,
+        }
+    }
+}
+
+/// Action for the event loop.
+pub(crate) enum KeepaliveAction {
+    /// Send a ping to the server.
+    Ping,
+    /// Connection is idle.
+    Timeout,
+}
+
+/// Keepalive state for an event loop.
+pub(crate) struct Keepalive(Option<State>);
+
+impl Keepalive {
+    /// Create keepalive state for a client.
+    ///
+    /// Keepalive is disabled when no options are given and
+    /// for meeting point connections which are not encrypted.
+    pub fn new(options: &ClientOptions) -> Self {
+        let options = options
+            .keepalive
+            .as_ref()
+            .filter(|_| options.is_encrypted());
+        Self(options.map(|options| State {
+            idle: Idle {
+                interval: Duration::from_secs(
+                    options.ping_interval.max(1).into(),
+                ),
+                idle_timeout: Duration::from_secs(
+                    options.idle_timeout.into(),
+                ),
+                idle: Duration::ZERO,
+                paused: false,
+            },
+            timer: None,
+        }))
+    }
+
+    /// Record that a message was received from the server.
+    pub fn received(&mut self) {
+        if let Some(state) = &mut self.0 {
+            state.idle.idle = Duration::ZERO;
+        }
+    }
+
+    /// Wait for the next keepalive action.
+    ///
+    /// Never resolves when keepalive is disabled.
+    pub async fn next(&mut self) -> KeepaliveAction {
+        match &mut self.0 {
+            Some(state) => state.next().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+struct State {
+    idle: Idle,
+    /// Timer created when first polled.
+    timer: Option<Timer>,
+}
+
+/// Tracks the time since a message was received.
+struct Idle {
+    interval: Duration,
+    idle_timeout: Duration,
+    /// Time since a message was received counted
+    /// in ping intervals.
+    idle: Duration,
+    /// Whether the idle timeout is paused.
+    paused: bool,
+}
+
+impl Idle {
+    /// Handle a tick of the timer.
+    fn tick(&mut self) -> Option<KeepaliveAction> {
+        if self.paused {
+            return None;
+        }
+        self.idle += self.interval;
+        if self.idle >= self.idle_timeout {
+            Some(KeepaliveAction::Timeout)
+        } else {
+            Some(KeepaliveAction::Ping)
+        }
+    }
+
+    /// Pause the idle timeout.
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    fn pause(&mut self) {
+        tracing::debug!("keepalive::pause");
+        self.paused = true;
+    }
+
+    /// Resume the idle timeout.
+    ///
+    /// Returns a ping to verify the connection when the
+    /// idle timeout was paused.
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    fn resume(&mut self) -> Option<KeepaliveAction> {
+        if !self.paused {
+            return None;
+        }
+        tracing::debug!("keepalive::resume");
+        self.paused = false;
+        self.idle = Duration::ZERO;
+        Some(KeepaliveAction::Ping)
+    }
+}
+
+impl State {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    async fn next(&mut self) -> KeepaliveAction {
+        let interval = self.idle.interval;
+        loop {
+            let timer = self.timer.get_or_insert_with(|| {
+                let mut timer = tokio::time::interval_at(
+                    tokio::time::Instant::now() + interval,
+                    interval,
+                );
+                timer.set_missed_tick_behavior(
+                    tokio::time::MissedTickBehavior::Delay,
+                );
+                timer
+            });
+            timer.tick().await;
+            if let Some(action) = self.idle.tick() {
+                return action;
+            }
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    async fn next(&mut self) -> KeepaliveAction {
+        let interval = self.idle.interval;
+        let timer = self.timer.get_or_insert_with(|| Timer {
+            ticks: web::Interval::new(interval),
+            lifecycle: web::Lifecycle::new(),
+        });
+        loop {
+            let action = tokio::select! {
+                Some(_) = timer.ticks.recv() => self.idle.tick(),
+                Some(visible) = timer.lifecycle.recv() => {
+                    if visible {
+                        self.idle.resume()
+                    } else {
+                        self.idle.pause();
+                        None
+                    }
+                }
+                else => std::future::pending().await,
+            };
+            if let Some(action) = action {
+                return action;
+            }
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+type Timer = tokio::time::Interval;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+struct Timer {
+    ticks: web::Interval,
+    lifecycle: web::Lifecycle,
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) use web::Interval;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod web {
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use wasm_bindgen::{prelude::*, JsCast};
+    use web_sys::{
+        Event, EventTarget, PageTransitionEvent, VisibilityState,
+    };
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = setInterval)]
+        fn set_interval(
+            handler: &js_sys::Function,
+            timeout: i32,
+        ) -> i32;
+
+        #[wasm_bindgen(js_name = clearInterval)]
+        fn clear_interval(handle: i32);
+    }
+
+    /// Timer using the global `setInterval` function so it
+    /// works in windows and workers.
+    pub(crate) struct Interval {
+        handle: i32,
+        ticks: mpsc::UnboundedReceiver<()>,
+        _callback: Closure<dyn FnMut()>,
+    }
+
+    impl Interval {
+        pub fn new(interval: Duration) -> Self {
+            let (tx, ticks) = mpsc::unbounded_channel();
+            let callback = Closure::<dyn FnMut()>::new(move || {
+                let _ = tx.send(());
+            });
+            let millis =
+                interval.as_millis().try_into().unwrap_or(i32::MAX);
+            let handle = set_interval(
+                callback.as_ref().unchecked_ref(),
+                millis,
+            );
+            Self {
+                handle,
+                ticks,
+                _callback: callback,
+            }
+        }
+
+        pub async fn recv(&mut self) -> Option<()> {
+            self.ticks.recv().await
+        }
+    }
+
+    impl Drop for Interval {
+        fn drop(&mut self) {
+            clear_interval(self.handle);
+        }
+    }
+
+    type Listener =
+        (EventTarget, &'static str, Closure<dyn FnMut(Event)>);
+
+    /// Page lifecycle events for the page running the client.
+    ///
+    /// Receives `false` when the page is hidden or stored in
+    /// the back/forward cache and `true` when it is shown again.
+    pub(super) struct Lifecycle {
+        changes: mpsc::UnboundedReceiver<bool>,
+        listeners: Vec<Listener>,
+    }
+
+    impl Lifecycle {
+        pub fn new() -> Self {
+            let (tx, changes) = mpsc::unbounded_channel();
+            let mut listeners = Vec::new();
+
+            // Workers do not have a window or document
+            if let Some(window) = web_sys::window() {
+                if let Some(document) = window.document() {
+                    let target = document.clone();
+                    let changed = tx.clone();
+                    listeners.push(listen(
+                        document.into(),
+                        "visibilitychange",
+                        move |_| {
+                            let visible = target.visibility_state()
+                                == VisibilityState::Visible;
+                            let _ = changed.send(visible);
+                        },
+                    ));
+                }
+
+                let changed = tx.clone();
+                listeners.push(listen(
+                    window.clone().into(),
+                    "pagehide",
+                    move |event| {
+                        if is_persisted(&event) {
+                            let _ = changed.send(false);
+                        }
+                    },
+                ));
+                listeners.push(listen(
+                    window.into(),
+                    "pageshow",
+                    move |event| {
+                        if is_persisted(&event) {
+                            let _ = tx.send(true);
+                        }
+                    },
+                ));
+            }
+
+            Self { changes, listeners }
+        }
+
+        pub async fn recv(&mut self) -> Option<bool> {
+            self.changes.recv().await
+        }
+    }
+
+    impl Drop for Lifecycle {
+        fn drop(&mut self) {
+            for (target, name, callback) in self.listeners.drain(..) {
+                let _ = target.remove_event_listener_with_callback(
+                    name,
+                    callback.as_ref().unchecked_ref(),
+                );
+            }
+        }
+    }
+
+    fn listen(
+        target: EventTarget,
+        name: &'static str,
+        handler: impl FnMut(Event) + 'static,
+    ) -> Listener {
+        let callback = Closure::<dyn FnMut(Event)>::new(handler);
+        let _ = target.add_event_listener_with_callback(
+            name,
+            callback.as_ref().unchecked_ref(),
+        );
+        (target, name, callback)
+    }
+
+    /// Whether a page transition event is for a page stored
+    /// in or restored from the back/forward cache.
+    fn is_persisted(event: &Event) -> bool {
+        event
+            .dyn_ref::<PageTransitionEvent>()
+            .is_some_and(|event| event.persisted())
+    }
+}
+
+// The web timer and event listeners store JavaScript values
+// which are not `Send` but the event loop must be `Send`
+// when running in native code.
+//
+// The webassembly client only runs in a single threaded
+// context so we can implement `Send` to appease the compiler.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+unsafe impl Send for Keepalive {}
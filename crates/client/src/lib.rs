@@ -7,22 +7,138 @@
 //! [web-sys](https://docs.rs/web-sys/latest/web_sys/) when
 //! compiling for webassembly otherwise
 //! [tokio-tunsgtenite](https://docs.rs/tokio-tungstenite/latest/tokio_tungstenite/).
+//!
+//! Enable the `rustls-tls` feature to connect to `wss:` URLs
+//! from native platforms without a system TLS library such as
+//! iOS and Android.
+//!
+//! Enable the `typescript` feature to generate TypeScript
+//! definitions for the serialized types.
+//!
+//! Enable the `record` feature to record decrypted peer
+//! messages for debugging, see the [record] module.
+//!
+//! Enable the `journal` feature to persist session events
+//! so an event stream can be resumed, see the [journal]
+//! module.
+//!
+//! Enable the `faults` feature to inject faults into the
+//! messages sent by a client for testing, see the [faults]
+//! module.
+//!
+//! Enable the `http2` feature to multiplex the relay
+//! connections for several clients over a single HTTP/2
+//! connection on native platforms, see the [http2] module.
+//!
+//! Enable the `log-filter` feature to change the log filter
+//! at runtime, see the [log_filter] module.
+//!
+//! Assign [DirectOptions] to upgrade peers to direct
+//! connections on native platforms, see the [direct] module.
+//!
+//! Assign an [Attestation] to require co-signers to prove
+//! they run in an approved environment, see the [attestation]
+//! module.
+//!
+//! Assign a [ServerTrust] to connect without knowing the
+//! server public key in advance, see the [trust] module.
+//!
+//! Use an [AccountRegistry] to track the parties that hold
+//! shares for each account, see the [registry] module.
 
 #![deny(missing_docs)]
 #![cfg_attr(all(doc, CHANNEL_NIGHTLY), feature(doc_auto_cfg))]
 
+pub mod attestation;
+mod cancel;
+mod capabilities;
 mod client;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub mod direct;
 mod error;
 mod event_loop;
-#[cfg(any(feature = "cggmp", feature = "frost"))]
+#[cfg(all(
+    feature = "faults",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub mod faults;
+mod group;
+#[cfg(all(
+    feature = "http2",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub mod http2;
+#[cfg(all(
+    feature = "journal",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub mod journal;
+mod keepalive;
+#[cfg(all(
+    feature = "log-filter",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub mod log_filter;
+mod progress;
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
 mod protocols;
+#[cfg(all(
+    feature = "record",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub mod record;
+pub mod registry;
 mod transport;
+pub mod trust;
+#[cfg(all(
+    feature = "typescript",
+    any(
+        feature = "bls",
+        feature = "cggmp",
+        feature = "frost",
+        feature = "musig2"
+    )
+))]
+pub mod typescript;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod worker;
 
+pub use attestation::Attestation;
+pub use cancel::CancellationToken;
+pub use capabilities::{capabilities, Capabilities, CurveBackend};
 pub(crate) use client::{client_impl, client_transport_impl};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use direct::DirectOptions;
 pub use event_loop::EventStream;
-#[cfg(any(feature = "cggmp", feature = "frost"))]
+#[cfg(all(
+    feature = "http2",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub use http2::Http2Connection;
+pub use keepalive::KeepaliveOptions;
+#[cfg(all(
+    feature = "log-filter",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub use log_filter::LogFilter;
+pub use progress::{
+    progress_channel, ProgressEvent, ProgressReceiver, ProgressSender,
+};
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
 pub use protocols::*;
+pub use registry::{Account, AccountParty, AccountRegistry};
 pub use transport::{NetworkTransport, Transport};
+pub use trust::ServerTrust;
 
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 mod native;
@@ -39,14 +155,16 @@ mod web;
 pub use web::{WebClient as Client, WebEventLoop as EventLoop};
 
 use polysig_protocol::{
-    hex, snow::params::NoiseParams, Chunk, Encoding, Keypair,
-    OpaqueMessage, ProtocolState, RequestMessage, SealedEnvelope,
-    SessionId, PATTERN,
+    hex, is_responder_key_transmitted,
+    snow::{params::NoiseParams, Builder},
+    Chunk, Encoding, Keypair, OpaqueMessage, ProtocolState,
+    RequestMessage, SealedEnvelope, SessionId, SessionToken, PATTERN,
 };
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 
-pub(crate) type Peers = Arc<RwLock<HashMap<Vec<u8>, ProtocolState>>>;
+pub(crate) type Peer = Arc<Mutex<ProtocolState>>;
+pub(crate) type Peers = Arc<RwLock<HashMap<Vec<u8>, Peer>>>;
 pub(crate) type Server = Arc<RwLock<Option<ProtocolState>>>;
 
 /// Options used to create a new websocket client.
@@ -56,17 +174,80 @@ pub struct ClientOptions {
     pub keypair: Option<Keypair>,
     /// Public key for the server to connect to.
     pub server_public_key: Option<Vec<u8>>,
+    /// Trusted server keys verified during the server handshake.
+    ///
+    /// Required when the server public key is not known; the
+    /// pattern must transmit the server static key such as
+    /// [PATTERN_IX](polysig_protocol::PATTERN_IX).
+    pub server_trust: Option<ServerTrust>,
     /// Noise parameters pattern.
     ///
     /// If no pattern is specified the default noise parameters
-    /// pattern is used.
+    /// pattern is used; the server must allow the pattern.
+    ///
+    /// Use [PATTERN_IK](polysig_protocol::PATTERN_IK) to
+    /// authenticate with the server public key in the first
+    /// handshake message.
     pub pattern: Option<String>,
+    /// Session token sent with the server handshake.
+    ///
+    /// The server resumes the session as soon as the handshake
+    /// completes saving a round trip when reconnecting; requires
+    /// a zero round trip pattern such as `IK`.
+    pub session_token: Option<SessionToken>,
+    /// Sender notified as ceremonies progress.
+    pub progress: Option<ProgressSender>,
+    /// Token used to cancel ceremonies.
+    pub cancel: Option<CancellationToken>,
+    /// Attestation exchanged with peers when a session starts.
+    pub attestation: Option<Arc<dyn Attestation>>,
+    /// Timeout for each round of a ceremony.
+    ///
+    /// Ceremonies fail with the parties that have not sent
+    /// a message when a round does not complete in time;
+    /// only supported on native platforms.
+    pub round_timeout: Option<std::time::Duration>,
+    /// Keepalive for the connection to the server.
+    ///
+    /// Requires a server that replies to pings; when no
+    /// options are given keepalive is disabled.
+    pub keepalive: Option<KeepaliveOptions>,
+    /// Options for direct peer connections.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub direct: Option<DirectOptions>,
+    /// Recorder for decrypted peer messages.
+    #[cfg(all(
+        feature = "record",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub recorder: Option<record::Recorder>,
+    /// Journal for session events.
+    #[cfg(all(
+        feature = "journal",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub journal: Option<journal::Journal>,
+    /// Fault injection for testing.
+    #[cfg(all(
+        feature = "faults",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub faults: Option<faults::Faults>,
+    /// Connection used to open an HTTP/2 stream to the
+    /// server instead of a websocket.
+    #[cfg(all(
+        feature = "http2",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub http2: Option<http2::Http2Connection>,
 }
 
 impl ClientOptions {
     /// Determine if this client expects to use an encrypted channel.
     pub fn is_encrypted(&self) -> bool {
-        self.keypair.is_some() && self.server_public_key.is_some()
+        self.keypair.is_some()
+            && (self.server_public_key.is_some()
+                || self.server_trust.is_some())
     }
 
     /// Build a connection URL for the given server.
@@ -76,36 +257,120 @@ impl ClientOptions {
     pub fn url(&self, server: &str) -> String {
         let server = server.trim_end_matches('/');
         if let Some(keypair) = &self.keypair {
-            format!(
+            let mut url = format!(
                 "{}/?public_key={}",
                 server,
                 hex::encode(keypair.public_key())
-            )
+            );
+            if let Some(pattern) = &self.pattern {
+                url.push_str("&pattern=");
+                url.push_str(pattern);
+            }
+            url
         } else {
             server.to_string()
         }
     }
 
+    /// Noise parameters pattern.
+    pub fn pattern(&self) -> &str {
+        self.pattern.as_deref().unwrap_or(PATTERN)
+    }
+
     /// Parse noise parameters from the pattern.
     pub fn params(&self) -> Result<NoiseParams> {
-        let pattern = self
-            .pattern
-            .as_ref()
-            .map(|s| &s[..])
-            .unwrap_or_else(|| PATTERN);
-        Ok(pattern.parse()?)
+        Ok(self.pattern().parse()?)
+    }
+
+    /// Handshake state for the server transport.
+    ///
+    /// When the server public key is not known the server
+    /// static key is learned from the handshake reply.
+    pub(crate) fn server_handshake(
+        &self,
+    ) -> Result<Option<ProtocolState>> {
+        let Some(keypair) =
+            self.keypair.as_ref().filter(|_| self.is_encrypted())
+        else {
+            return Ok(None);
+        };
+        let builder = Builder::new(self.params()?)
+            .local_private_key(keypair.private_key());
+        let builder = match &self.server_public_key {
+            Some(server_public_key) => {
+                builder.remote_public_key(server_public_key)
+            }
+            None if is_responder_key_transmitted(self.pattern()) => {
+                builder
+            }
+            None => {
+                return Err(Error::ServerKeyPattern(
+                    self.pattern().to_owned(),
+                ))
+            }
+        };
+        let handshake = builder.build_initiator()?;
+        Ok(Some(ProtocolState::Handshake(Box::new(handshake))))
     }
 }
 
-pub use error::Error;
+pub use error::{Error, ErrorInfo, ErrorKind};
 
 /// Result type for the client library.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Milliseconds since the UNIX epoch.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub(crate) fn timestamp_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Milliseconds since the UNIX epoch.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) fn timestamp_millis() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Run peer channel cryptography for a peer.
+///
+/// On native platforms the work runs on the blocking thread
+/// pool so the cryptography for distinct peers runs in parallel
+/// and does not stall the event loop. The lock for the peer is
+/// returned so that callers can hold it until the message has
+/// been queued which preserves the order of messages for a peer.
+async fn with_peer<T, F>(
+    peer: &Peer,
+    f: F,
+) -> Result<(OwnedMutexGuard<ProtocolState>, T)>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ProtocolState) -> Result<T> + Send + 'static,
+{
+    let mut state = Arc::clone(peer).lock_owned().await;
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    {
+        tokio::task::spawn_blocking(move || {
+            let value = f(&mut state)?;
+            Ok((state, value))
+        })
+        .await?
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        let value = f(&mut state)?;
+        Ok((state, value))
+    }
+}
+
 /// Encrypt a message to send to a peer.
 ///
 /// The protocol must be in transport mode.
-async fn encrypt_peer_channel(
+fn encrypt_peer_channel(
     public_key: impl AsRef<[u8]>,
     peer: &mut ProtocolState,
     payload: &[u8],
@@ -138,7 +403,7 @@ async fn encrypt_peer_channel(
 /// Decrypt a message received from a peer.
 ///
 /// The protocol must be in transport mode.
-async fn decrypt_peer_channel(
+fn decrypt_peer_channel(
     peer: &mut ProtocolState,
     envelope: SealedEnvelope,
 ) -> Result<(Encoding, Vec<u8>)> {
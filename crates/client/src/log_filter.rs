@@ -0,0 +1,85 @@
+//! Change the log filter at runtime.
+//!
+//! Long-lived processes can capture diagnostics for a failing
+//! ceremony without restarting by installing the layer for a
+//! [LogFilter] and changing the filter directives when needed.
+//!
+//! Ceremonies run by the protocol helpers are wrapped in a
+//! `session` span with the session identifier so the filter
+//! may enable verbose logs for a single session, see
+//! [LogFilter::enable_session].
+use polysig_protocol::SessionId;
+use tracing::Level;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::{Error, Result};
+
+/// Handle used to change the log filter at runtime.
+///
+/// Install the layer as the first layer of the
+/// tracing subscriber.
+#[derive(Clone)]
+pub struct LogFilter(reload::Handle<EnvFilter, Registry>);
+
+impl LogFilter {
+    /// Create a log filter layer for the filter directives
+    /// and a handle to change the filter.
+    pub fn new(
+        directives: &str,
+    ) -> Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        let filter = parse(directives)?;
+        let (layer, handle) = reload::Layer::new(filter);
+        Ok((layer, Self(handle)))
+    }
+
+    /// Directives for the current filter.
+    pub fn directives(&self) -> Result<String> {
+        self.0
+            .with_current(|filter| filter.to_string())
+            .map_err(|e| Error::LogFilter(e.to_string()))
+    }
+
+    /// Replace the filter.
+    pub fn set(&self, directives: &str) -> Result<()> {
+        let filter = parse(directives)?;
+        self.0
+            .reload(filter)
+            .map_err(|e| Error::LogFilter(e.to_string()))
+    }
+
+    /// Enable logs for the client library up to a level
+    /// for a single session.
+    ///
+    /// The directive is added to the current filter, for
+    /// example to trace the protocol messages for a session
+    /// use [Level::TRACE].
+    pub fn enable_session(
+        &self,
+        session_id: &SessionId,
+        level: Level,
+    ) -> Result<()> {
+        let mut directives = self.directives()?;
+        if !directives.is_empty() {
+            directives.push(',');
+        }
+        directives.push_str(&session_directive(session_id, level));
+        self.set(&directives)
+    }
+}
+
+/// Filter directive that enables logs for the client
+/// library up to a level for a session.
+pub fn session_directive(
+    session_id: &SessionId,
+    level: Level,
+) -> String {
+    format!(
+        "polysig_client[session{{session_id={}}}]={}",
+        session_id, level
+    )
+}
+
+fn parse(directives: &str) -> Result<EnvFilter> {
+    EnvFilter::try_new(directives)
+        .map_err(|e| Error::LogFilter(e.to_string()))
+}
@@ -1,27 +1,29 @@
 use async_stream::stream;
-use futures::{
-    sink::SinkExt,
-    stream::{SplitSink, SplitStream},
-    StreamExt,
-};
+use futures::{sink::SinkExt, Sink, Stream, StreamExt};
 use serde::Serialize;
-use std::{collections::HashSet, sync::Arc};
-use tokio::{
-    net::TcpStream,
-    sync::{mpsc, RwLock},
-};
+use std::{collections::HashSet, pin::Pin, sync::Arc};
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio_tungstenite::{
-    connect_async, tungstenite::protocol::Message, MaybeTlsStream,
-    WebSocketStream,
+    connect_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        error::ProtocolError,
+        http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderValue},
+        protocol::Message,
+    },
 };
 
 use polysig_protocol::{
-    channel::encrypt_server_channel, decode, encode, hex,
-    http::StatusCode, snow::Builder, zlib, Encoding, Event,
+    channel::encrypt_server_channel, decode, encode, fingerprint,
+    hex,
+    http::StatusCode, is_zero_rtt, snow::Builder, zlib, Encoding,
+    Event,
     HandshakeMessage, JsonMessage, MeetingResponse, PublicKeys,
-    MeetingId, MeetingRequest, OpaqueMessage, ProtocolState,
-    RequestMessage, ResponseMessage, ServerMessage, SessionId,
-    SessionRequest, TransparentMessage, UserId,
+    MeetingId, MeetingRequest, OpaqueMessage, Parameters,
+    ProtocolState, RequestMessage, ResponseMessage, ServerMessage,
+    SessionId,
+    SessionRequest, SessionState, SessionToken, TransparentMessage,
+    UserId, CLOSE_SUPERSEDED, SUBPROTOCOL,
 };
 
 use super::{
@@ -30,18 +32,27 @@ use super::{
         event_loop_run_impl, EventLoop, EventStream, IncomingMessage,
         InternalMessage,
     },
-    Peers, Server,
+    with_peer, Peers, Server,
 };
 use crate::{
-    client_impl, client_transport_impl, ClientOptions, Error, Result,
+    client_impl, client_transport_impl,
+    direct::{Direct, DirectIncoming, DirectMessage},
+    group::Groups,
+    keepalive::Keepalive,
+    ClientOptions, Error, Result,
 };
 
 type WsMessage = Message;
 type WsError = tokio_tungstenite::tungstenite::Error;
-type WsReadStream =
-    SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+type WsReadStream = Pin<
+    Box<
+        dyn Stream<Item = std::result::Result<WsMessage, WsError>>
+            + Send
+            + Sync,
+    >,
+>;
 type WsWriteStream =
-    SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, WsMessage>;
+    Pin<Box<dyn Sink<WsMessage, Error = WsError> + Send + Sync>>;
 
 /// Event loop for the websocket client.
 pub type NativeEventLoop =
@@ -54,6 +65,8 @@ pub struct NativeClient {
     outbound_tx: mpsc::UnboundedSender<InternalMessage>,
     server: Server,
     peers: Peers,
+    groups: Groups,
+    direct: Option<Direct>,
 }
 
 impl NativeClient {
@@ -62,68 +75,156 @@ impl NativeClient {
         server: &str,
         options: ClientOptions,
     ) -> Result<(Self, NativeEventLoop)> {
-        let (stream, response) = connect_async(server).await?;
+        let handshake = options.server_handshake()?;
 
-        let status: u16 = response.status().into();
-        if status != StatusCode::SWITCHING_PROTOCOLS.as_u16() {
-            return Err(Error::ConnectError(
-                StatusCode::from_u16(status).unwrap(),
-                response.status().to_string(),
-            ));
-        }
-
-        let (ws_writer, ws_reader) = stream.split();
-
-        let server = if let (Some(keypair), Some(server_public_key)) =
-            (&options.keypair, &options.server_public_key)
-        {
-            let builder = Builder::new(options.params()?);
-            let handshake = builder
-                .local_private_key(keypair.private_key())
-                .remote_public_key(server_public_key)
-                .build_initiator()?;
-
-            // State for the server transport
-            Arc::new(RwLock::new(Some(ProtocolState::Handshake(
-                Box::new(handshake),
-            ))))
-        } else {
-            Arc::new(RwLock::new(None))
+        #[cfg(feature = "http2")]
+        let (ws_reader, ws_writer) = match &options.http2 {
+            Some(connection) => {
+                let url: polysig_protocol::http::Uri =
+                    server.parse().map_err(|_| {
+                        Error::RelayUrl(server.to_owned())
+                    })?;
+                connection.open(url.query()).await?
+            }
+            None => Self::connect(server).await?,
         };
+        #[cfg(not(feature = "http2"))]
+        let (ws_reader, ws_writer) = Self::connect(server).await?;
+
+        // State for the server transport
+        let server = Arc::new(RwLock::new(handshake));
 
         // Channel for writing outbound messages to send
         // to the server
         let (outbound_tx, outbound_rx) =
             mpsc::unbounded_channel::<InternalMessage>();
 
+        // Decoded socket messages are sent over this channel
+        let (inbound_tx, inbound_rx) =
+            mpsc::unbounded_channel::<IncomingMessage>();
+
         let peers = Arc::new(RwLock::new(Default::default()));
+        let groups = Arc::new(RwLock::new(Default::default()));
+
+        let direct = match (&options.direct, &options.keypair) {
+            (Some(direct), Some(keypair)) => Some(
+                Direct::listen(
+                    direct,
+                    keypair.clone(),
+                    peers.clone(),
+                    inbound_tx.clone(),
+                    outbound_tx.clone(),
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
         let options = Arc::new(options);
         let client = Self {
             options: options.clone(),
             outbound_tx: outbound_tx.clone(),
             server: server.clone(),
             peers: peers.clone(),
+            groups: groups.clone(),
+            direct: direct.clone(),
         };
 
-        // Decoded socket messages are sent over this channel
-        let (inbound_tx, inbound_rx) =
-            mpsc::unbounded_channel::<IncomingMessage>();
-
         let event_loop = EventLoop {
+            keepalive: Keepalive::new(&options),
             options,
             ws_reader,
             ws_writer,
             inbound_tx,
             inbound_rx,
             outbound_tx,
-            outbound_rx,
+            outbound_rx: outbound_rx.into(),
             server,
             peers,
+            groups,
+            direct,
+            workers: Default::default(),
         };
 
         Ok((client, event_loop))
     }
 
+    /// Connect a websocket to the server.
+    async fn connect(
+        server: &str,
+    ) -> Result<(WsReadStream, WsWriteStream)> {
+        let mut request = server.into_client_request()?;
+        request.headers_mut().insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(SUBPROTOCOL),
+        );
+
+        // Servers reject the subprotocol when the wire encoding
+        // is not supported and servers that predate negotiation
+        // do not select a subprotocol
+        let (stream, response) = match connect_async(request).await {
+            Ok(result) => result,
+            Err(WsError::Http(response))
+                if response.status().as_u16()
+                    == StatusCode::UPGRADE_REQUIRED.as_u16() =>
+            {
+                return Err(Error::UnsupportedSubprotocol(
+                    SUBPROTOCOL.to_owned(),
+                ));
+            }
+            Err(WsError::Protocol(
+                ProtocolError::SecWebSocketSubProtocolError(_),
+            )) => {
+                return Err(Error::UnsupportedSubprotocol(
+                    SUBPROTOCOL.to_owned(),
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let status: u16 = response.status().into();
+        if status != StatusCode::SWITCHING_PROTOCOLS.as_u16() {
+            return Err(Error::ConnectError(
+                StatusCode::from_u16(status).unwrap(),
+                response.status().to_string(),
+            ));
+        }
+
+        let (ws_writer, ws_reader) = stream.split();
+        Ok((Box::pin(ws_reader), Box::pin(ws_writer)))
+    }
+
+    /// Offer a direct connection to a peer.
+    ///
+    /// The peer handshake must have been completed; the
+    /// [Event::PeerUpgraded] event is dispatched when the
+    /// direct connection is established otherwise messages
+    /// continue to be relayed.
+    pub async fn upgrade_peer(
+        &mut self,
+        public_key: &[u8],
+    ) -> Result<()> {
+        let direct =
+            self.direct.as_ref().ok_or(Error::DirectDisabled)?;
+        let offer = direct.offer(public_key).await;
+        self.relay(
+            public_key,
+            &JsonMessage::serialize(&offer)?,
+            Encoding::Json,
+            false,
+            None,
+        )
+        .await
+    }
+
+    /// Determine if there is a direct connection to a peer.
+    pub async fn is_direct(&self, public_key: &[u8]) -> bool {
+        match &self.direct {
+            Some(direct) => direct.is_connected(public_key).await,
+            None => false,
+        }
+    }
+
     client_impl!();
 }
 
@@ -137,6 +238,12 @@ impl EventLoop<WsMessage, WsError, WsReadStream, WsWriteStream> {
         incoming: Message,
         event_proxy: &mut mpsc::UnboundedSender<IncomingMessage>,
     ) -> Result<()> {
+        if let Message::Close(Some(frame)) = &incoming {
+            if u16::from(frame.code) == CLOSE_SUPERSEDED {
+                return Err(Error::Superseded);
+            }
+        }
+
         if let Message::Binary(buffer) = incoming {
             let inflated = zlib::inflate(&buffer)?;
 
@@ -189,17 +296,64 @@ impl EventLoop<WsMessage, WsError, WsReadStream, WsWriteStream> {
             .map_err(|_| Error::WebSocketSend)
     }
 
-    async fn handle_close_message(self) -> Result<()> {
-        let mut websocket: WebSocketStream<
-            MaybeTlsStream<TcpStream>,
-        > = self
-            .ws_reader
-            .reunite(self.ws_writer)
-            .map_err(|_| Error::StreamReunite)?;
-
-        websocket.close(None).await?;
+    async fn handle_close_message(mut self) -> Result<()> {
+        self.ws_writer.send(Message::Close(None)).await?;
         Ok(())
     }
 
+    /// Process a message from a direct peer connection.
+    async fn handle_direct_message(
+        &self,
+        message: DirectIncoming,
+    ) -> Result<Option<Event>> {
+        match message {
+            DirectIncoming::Connected(peer_key) => {
+                Ok(Some(Event::PeerUpgraded { peer_key }))
+            }
+            DirectIncoming::Message {
+                public_key,
+                encoding,
+                contents,
+                session_id,
+            } => {
+                Self::handle_peer_payload(
+                    self.groups.clone(),
+                    public_key,
+                    encoding,
+                    contents,
+                    session_id,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Consume direct connection control messages.
+    ///
+    /// Offers are ignored when direct connections are
+    /// not enabled.
+    fn intercept_direct(&self, event: Event) -> Option<Event> {
+        if let Event::JsonMessage {
+            peer_key, message, ..
+        } = &event
+        {
+            if let Ok(control) =
+                message.deserialize::<DirectMessage>()
+            {
+                if let Some(direct) = &self.direct {
+                    direct.handle(peer_key.clone(), control);
+                }
+                return None;
+            }
+        }
+        Some(event)
+    }
+
+    /// Release the socket when a ceremony is cancelled or
+    /// the connection is idle.
+    async fn handle_cancel(self) -> Result<()> {
+        self.handle_close_message().await
+    }
+
     event_loop_run_impl!();
 }
@@ -0,0 +1,70 @@
+//! Events describing the progress of a ceremony.
+//!
+//! Assign a [ProgressSender] to the client options (or the
+//! session options for the high-level protocol functions) to
+//! receive events as the session is created, participants
+//! connect and the protocol advances through each round.
+use futures::channel::mpsc;
+use polysig_protocol::SessionId;
+use serde::{Deserialize, Serialize};
+
+/// Sender for progress events.
+pub type ProgressSender = mpsc::UnboundedSender<ProgressEvent>;
+
+/// Receiver for progress events.
+pub type ProgressReceiver = mpsc::UnboundedReceiver<ProgressEvent>;
+
+/// Create a channel for progress events.
+///
+/// The receiver stream ends once every client holding
+/// the sender has been dropped.
+pub fn progress_channel() -> (ProgressSender, ProgressReceiver) {
+    mpsc::unbounded()
+}
+
+/// Event emitted as a ceremony progresses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ProgressEvent {
+    /// Handshake with the relay server completed.
+    ServerConnected,
+    /// Session was created by the initiator.
+    SessionCreated {
+        /// Session identifier.
+        session_id: SessionId,
+    },
+    /// Every participant has joined the session.
+    SessionReady {
+        /// Session identifier.
+        session_id: SessionId,
+    },
+    /// Handshake with a participant completed.
+    PeerConnected {
+        /// Noise transport public key of the participant.
+        public_key: Vec<u8>,
+    },
+    /// Connections between all participants are established.
+    SessionActive {
+        /// Session identifier.
+        session_id: SessionId,
+    },
+    /// Protocol entered a new round.
+    Round {
+        /// Round number.
+        round: u16,
+    },
+    /// Protocol message received from a participant.
+    MessageReceived {
+        /// Round number of the message.
+        round: u16,
+        /// Party number of the sender, when known.
+        party: Option<u16>,
+    },
+    /// Protocol completed.
+    Completed,
+}
@@ -0,0 +1,81 @@
+//! Distributed key generation for BLS.
+use crate::{
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
+    Error, NetworkTransport, Result, Transport,
+};
+use async_trait::async_trait;
+use polysig_protocol::{hex, Event, Parameters, SessionState};
+
+use polysig_driver::bls::{DkgDriver as BlsDriver, KeyShare};
+
+/// BLS key generation driver.
+pub struct DkgDriver {
+    bridge: Bridge<BlsDriver>,
+}
+
+impl DkgDriver {
+    /// Create a new BLS key generator.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        params: Parameters,
+    ) -> Result<Self> {
+        let party_number = session
+            .party_number(transport.public_key())
+            .ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(
+                    transport.public_key(),
+                ))
+            })?;
+
+        let driver = BlsDriver::new(party_number, params)?;
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl Driver for DkgDriver {
+    type Output = KeyShare;
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl From<DkgDriver> for Transport {
+    fn from(value: DkgDriver) -> Self {
+        value.bridge.transport
+    }
+}
@@ -0,0 +1,141 @@
+//! Driver for the threshold BLS protocol.
+use polysig_driver::{
+    bls::{Identifier, KeyShare, PartyOptions, Signature},
+    digest::DigestAlgorithm,
+};
+use polysig_protocol::{Parameters, SessionState};
+
+use crate::{
+    new_client, protocols::policy::approve,
+    protocols::validate_parameters, wait_for_close, wait_for_driver,
+    wait_for_session, wait_for_session_finish, Driver,
+    SessionHandler, SessionInitiator, SessionOptions,
+    SessionParticipant, Transport,
+};
+
+mod dkg;
+mod sign;
+
+pub use dkg::DkgDriver;
+pub use sign::SignatureDriver;
+
+/// Run distributed key generation for the BLS protocol.
+pub async fn dkg(
+    options: SessionOptions,
+    party: PartyOptions,
+) -> crate::Result<KeyShare> {
+    let params = options.parameters.clone();
+    validate_parameters(&params, party.participants().len(), 1)?;
+    run_session(
+        options,
+        &party,
+        Some(params),
+        move |transport, session| {
+            DkgDriver::new(transport, session, params)
+        },
+    )
+    .await
+}
+
+/// Sign a message using the BLS protocol.
+///
+/// The identifiers are the key share identifiers of each
+/// signer ordered by session party number.
+pub async fn sign(
+    options: SessionOptions,
+    party: PartyOptions,
+    identifiers: Vec<Identifier>,
+    key_share: KeyShare,
+    message: Vec<u8>,
+) -> crate::Result<Signature> {
+    approve(&options, "bls", &party, &[message.as_slice()], false)
+        .await?;
+    run_session(options, &party, None, move |transport, session| {
+        SignatureDriver::new(
+            transport,
+            session,
+            identifiers,
+            key_share,
+            message,
+        )
+    })
+    .await
+}
+
+/// Sign the digest of a message using the BLS protocol.
+pub async fn sign_message(
+    options: SessionOptions,
+    party: PartyOptions,
+    identifiers: Vec<Identifier>,
+    key_share: KeyShare,
+    message: &[u8],
+    digest: DigestAlgorithm,
+) -> crate::Result<Signature> {
+    sign(
+        options,
+        party,
+        identifiers,
+        key_share,
+        digest.digest(message),
+    )
+    .await
+}
+
+async fn run_session<D, F>(
+    options: SessionOptions,
+    party: &PartyOptions,
+    parameters: Option<Parameters>,
+    new_driver: F,
+) -> crate::Result<D::Output>
+where
+    D: Driver + Into<Transport>,
+    F: FnOnce(Transport, SessionState) -> crate::Result<D>,
+{
+    // Create the client
+    let (client, event_loop) = new_client(options).await?;
+
+    let mut transport: Transport = client.into();
+
+    // Handshake with the server
+    transport.connect().await?;
+
+    // Start the event stream
+    let mut stream = event_loop.run();
+
+    // Wait for the session to become active
+    let client_session = if party.is_initiator() {
+        let initiator = SessionInitiator::new(
+            transport,
+            party.participants().to_vec(),
+        );
+        SessionHandler::Initiator(match parameters {
+            Some(parameters) => initiator.with_parameters(parameters),
+            None => initiator,
+        })
+    } else {
+        SessionHandler::Participant(SessionParticipant::new(
+            transport,
+        ))
+    };
+
+    let (transport, session) =
+        wait_for_session(&mut stream, client_session).await?;
+
+    let protocol_session_id = session.session_id;
+
+    let driver = new_driver(transport, session)?;
+
+    let (mut transport, output) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    // Close the session and socket
+    if party.is_initiator() {
+        transport.close_session(protocol_session_id).await?;
+        wait_for_session_finish(&mut stream, protocol_session_id)
+            .await?;
+    }
+    transport.close().await?;
+    wait_for_close(&mut stream).await?;
+
+    Ok(output)
+}
@@ -1,15 +1,18 @@
-use std::num::NonZeroU16;
+use std::{collections::HashSet, num::NonZeroU16, time::Duration};
 
 use crate::{
-    protocols::Driver, EventStream, NetworkTransport, Result,
-    Transport,
+    protocols::Driver, EventStream, NetworkTransport, ProgressEvent,
+    Result, Transport,
 };
 use futures::StreamExt;
-use polysig_protocol::{Event, SessionId, SessionState};
+use polysig_protocol::{fingerprint, Event, SessionId, SessionState};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::Instrument;
 
 use polysig_driver::{Error, ProtocolDriver, Round};
 
-use super::public_key_to_str;
+use super::report::{CeremonyReport, CeremonyTracker, RoundStatus};
 
 /// Connects a network transport with a protocol driver.
 pub(crate) struct Bridge<D: ProtocolDriver> {
@@ -17,18 +20,83 @@ pub(crate) struct Bridge<D: ProtocolDriver> {
     pub(crate) driver: Option<D>,
     pub(crate) session: SessionState,
     pub(crate) party_number: NonZeroU16,
+    tracker: CeremonyTracker,
+    round_number: u16,
+    /// Messages for the current round, sent again when
+    /// the transport is replaced.
+    outbox: Vec<(Vec<u8>, Value)>,
+    /// Digests of the messages received from peers so
+    /// messages that are sent again are discarded.
+    received: HashSet<[u8; 32]>,
 }
 
 impl<D: ProtocolDriver> Bridge<D> {
+    /// Create a new bridge.
+    pub fn new(
+        transport: Transport,
+        driver: D,
+        session: SessionState,
+        party_number: NonZeroU16,
+    ) -> Self {
+        Self {
+            transport,
+            driver: Some(driver),
+            session,
+            party_number,
+            tracker: Default::default(),
+            round_number: 0,
+            outbox: Vec::new(),
+            received: HashSet::new(),
+        }
+    }
+
+    /// Replace the transport and send the messages for
+    /// the current round again.
+    ///
+    /// Returns the previous transport.
+    pub async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        let previous =
+            std::mem::replace(&mut self.transport, transport);
+        self.send_outbox().await?;
+        Ok(previous)
+    }
+
+    /// Report on the progress of the ceremony.
+    pub fn report(&self) -> CeremonyReport {
+        self.tracker
+            .report(self.session.session_id, self.party_number)
+    }
+
+    /// Status of the current round.
+    pub fn round_status(&self) -> RoundStatus {
+        self.tracker.round_status()
+    }
+
+    /// Timeout for each round.
+    pub fn round_timeout(&self) -> Option<Duration> {
+        self.transport.round_timeout()
+    }
+
     /// Handle event from the client event loop stream.
     pub async fn handle_event(
         &mut self,
         event: Event,
     ) -> Result<Option<D::Output>> {
+        if let Event::SessionTimeout(session_id) = &event {
+            if session_id == &self.session.session_id {
+                return Err(crate::Error::SessionDeadline(
+                    *session_id,
+                ));
+            }
+        }
+
         if let Event::JsonMessage {
+            peer_key,
             message,
             session_id,
-            ..
         } = event
         {
             if let Some(session_id) = &session_id {
@@ -39,7 +107,31 @@ impl<D: ProtocolDriver> Bridge<D> {
                 return Err(Error::SessionIdRequired.into());
             }
 
-            let message: D::Message = message.deserialize()?;
+            let mut hasher = Sha256::new();
+            hasher.update(&peer_key);
+            hasher.update(message.as_bytes());
+            if !self.received.insert(hasher.finalize().into()) {
+                tracing::debug!(
+                    from = %fingerprint(&peer_key),
+                    "discard repeated message"
+                );
+                return Ok(None);
+            }
+
+            let sender = self.session.party_number(&peer_key);
+            let message: D::Message = match message.deserialize() {
+                Ok(message) => message,
+                Err(e) => {
+                    self.tracker.blame(sender, e.to_string());
+                    return Err(e.into());
+                }
+            };
+            let round = message.round_number().get();
+            self.tracker.received(round, sender);
+            self.transport.notify(ProgressEvent::MessageReceived {
+                round,
+                party: sender.map(|p| p.get()),
+            });
 
             let driver = self.driver.as_mut().unwrap();
             let round_info =
@@ -48,66 +140,127 @@ impl<D: ProtocolDriver> Bridge<D> {
             // println!("{:#?}", round_info);
 
             if !round_info.can_finalize {
-                driver.handle_incoming(message).map_err(Box::from)?;
-                let round_info =
-                    driver.round_info().map_err(Box::from)?;
-                if round_info.can_finalize {
-                    if let Some(result) = driver
-                        .try_finalize_round()
-                        .map_err(Box::from)?
-                    {
-                        return Ok(Some(result));
-                    }
-
-                    let messages =
-                        driver.proceed().map_err(Box::from)?;
-
-                    /*
-                    println!(
-                        "*** DISPATCH MESSAGES ({}) ***",
-                        messages.len()
-                    );
-                    */
-
-                    self.dispatch_round_messages(messages).await?;
+                if let Err(e) =
+                    compute(|| driver.handle_incoming(message))
+                {
+                    let culprit = driver.culprit(&e).or(sender);
+                    self.tracker.blame(culprit, e.to_string());
+                    return Err(crate::Error::Generic(Box::new(e)));
                 }
+
+                self.finalize_rounds().await
+            } else {
+                Ok(None)
             }
+        } else {
+            Ok(None)
         }
-
-        Ok(None)
     }
 
     /// Start running the protocol.
-    pub async fn execute(&mut self) -> Result<()> {
+    ///
+    /// The protocol may complete without receiving any messages,
+    /// for example when a party only distributes key shares.
+    pub async fn execute(&mut self) -> Result<Option<D::Output>> {
         let driver = self.driver.as_mut().unwrap();
-        let messages = driver.proceed().map_err(Box::from)?;
+        let messages =
+            compute(|| driver.proceed()).map_err(Box::from)?;
         self.dispatch_round_messages(messages).await?;
-        Ok(())
+        self.finalize_rounds().await
+    }
+
+    /// Keep finalizing rounds as a round may already be complete
+    /// after proceeding, for example when the final round does
+    /// not wait for any messages.
+    async fn finalize_rounds(&mut self) -> Result<Option<D::Output>> {
+        loop {
+            let driver = self.driver.as_mut().unwrap();
+            let round_info =
+                driver.round_info().map_err(Box::from)?;
+            let round = round_info.round_number as u16;
+            self.tracker.set_round(round);
+            if round > self.round_number {
+                self.round_number = round;
+                self.transport.notify(ProgressEvent::Round { round });
+            }
+            if !round_info.can_finalize {
+                break;
+            }
+
+            let result = match compute(|| driver.try_finalize_round())
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    let culprit = driver.culprit(&e);
+                    self.tracker.blame(culprit, e.to_string());
+                    return Err(crate::Error::Generic(Box::new(e)));
+                }
+            };
+
+            if let Some(result) = result {
+                self.transport.notify(ProgressEvent::Completed);
+                return Ok(Some(result));
+            }
+
+            let messages = match compute(|| driver.proceed()) {
+                Ok(messages) => messages,
+                Err(e) => {
+                    let culprit = driver.culprit(&e);
+                    self.tracker.blame(culprit, e.to_string());
+                    return Err(crate::Error::Generic(Box::new(e)));
+                }
+            };
+
+            /*
+            println!(
+                "*** DISPATCH MESSAGES ({}) ***",
+                messages.len()
+            );
+            */
+
+            self.dispatch_round_messages(messages).await?;
+        }
+        Ok(None)
     }
 
     /// Send messages to peers.
+    ///
+    /// Messages are buffered before they are sent so they
+    /// can be sent again if the connection is lost.
     async fn dispatch_round_messages(
         &mut self,
         messages: Vec<D::Message>,
     ) -> Result<()> {
+        let mut outbox = Vec::with_capacity(messages.len());
         for message in messages {
             let party_number = message.receiver();
-
-            let owner_key =
-                self.session.peer_key(self.party_number).unwrap();
+            self.tracker.sent(*party_number);
             let peer_key =
                 self.session.peer_key(*party_number).unwrap();
+            outbox.push((
+                peer_key.to_vec(),
+                serde_json::to_value(&message)?,
+            ));
+        }
+        self.outbox = outbox;
+        self.send_outbox().await
+    }
 
+    /// Send the messages for the current round.
+    async fn send_outbox(&mut self) -> Result<()> {
+        let owner_key =
+            self.session.peer_key(self.party_number).unwrap();
+        for (peer_key, message) in &self.outbox {
             tracing::info!(
-                to = public_key_to_str(peer_key),
-                from = public_key_to_str(owner_key),
+                to = %fingerprint(peer_key),
+                from = %fingerprint(owner_key),
                 "dispatch_message"
             );
 
             self.transport
                 .send_json(
                     peer_key,
-                    &message,
+                    message,
                     Some(self.session.session_id),
                 )
                 .await?;
@@ -116,26 +269,218 @@ impl<D: ProtocolDriver> Bridge<D> {
     }
 }
 
+/// Run a CPU-heavy driver step.
+///
+/// Rounds with expensive proofs (for example CGGMP auxiliary
+/// info generation) can take seconds; on a multi-threaded
+/// runtime the worker hands its other tasks to the rest of
+/// the runtime first so network I/O and progress events keep
+/// flowing while the round is computed.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn compute<T>(f: impl FnOnce() -> T) -> T {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+    match Handle::try_current() {
+        Ok(handle)
+            if handle.runtime_flavor()
+                == RuntimeFlavor::MultiThread =>
+        {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Run a driver step.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn compute<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
 /// Wait for a driver to complete.
+///
+/// The driver runs in a `session` span with the session
+/// identifier so logs can be filtered for a session.
 pub async fn wait_for_driver<D>(
+    stream: &mut EventStream,
+    driver: D,
+) -> Result<(Transport, D::Output)>
+where
+    D: Driver + Into<Transport>,
+{
+    let span = match driver.report().session_id {
+        Some(session_id) => {
+            tracing::info_span!("session", session_id = %session_id)
+        }
+        None => tracing::info_span!("session"),
+    };
+    drive(stream, driver).instrument(span).await
+}
+
+async fn drive<D>(
     stream: &mut EventStream,
     mut driver: D,
 ) -> Result<(Transport, D::Output)>
 where
     D: Driver + Into<Transport>,
 {
-    driver.execute().await?;
+    match driver.execute().await {
+        Ok(Some(output)) => return Ok((driver.into(), output)),
+        Ok(None) => {}
+        Err(e) => return Err(ceremony_error(&driver, e)),
+    }
 
     #[allow(unused_assignments)]
     let mut output: Option<D::Output> = None;
-    while let Some(event) = stream.next().await {
-        let event = event?;
-        if let Some(result) = driver.handle_event(event).await? {
-            output = Some(result);
-            break;
+    let mut timer = RoundTimer::new(&driver);
+    while let Some(event) = timer.next(stream, &driver).await {
+        let result = match event {
+            Ok(event) => driver.handle_event(event).await,
+            Err(e) => Err(e),
+        };
+        timer.update(&driver);
+        match result {
+            Ok(Some(result)) => {
+                output = Some(result);
+                break;
+            }
+            Ok(None) => {}
+            Err(e) => return Err(ceremony_error(&driver, e)),
         }
     }
-    Ok((driver.into(), output.take().unwrap()))
+    match output.take() {
+        Some(output) => Ok((driver.into(), output)),
+        None => Err(ceremony_error(
+            &driver,
+            crate::Error::ConnectionClosed,
+        )),
+    }
+}
+
+/// Deadline for the current round of a driver.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+struct RoundTimer {
+    timeout: Option<Duration>,
+    round: Option<u16>,
+    deadline: Option<tokio::time::Instant>,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl RoundTimer {
+    fn new<D: Driver>(driver: &D) -> Self {
+        let timeout = driver.round_timeout();
+        Self {
+            timeout,
+            round: driver.round_status().map(|s| s.round),
+            deadline: timeout
+                .map(|t| tokio::time::Instant::now() + t),
+        }
+    }
+
+    /// Restart the timer when the driver moves to a new round.
+    fn update<D: Driver>(&mut self, driver: &D) {
+        let round = driver.round_status().map(|s| s.round);
+        if round != self.round {
+            self.round = round;
+            self.deadline =
+                self.timeout.map(|t| tokio::time::Instant::now() + t);
+        }
+    }
+
+    /// Next event from the stream.
+    ///
+    /// Yields an error with the parties missing from the
+    /// current round when the deadline is reached.
+    async fn next<D: Driver>(
+        &mut self,
+        stream: &mut EventStream,
+        driver: &D,
+    ) -> Option<Result<Event>> {
+        let Some(deadline) = self.deadline else {
+            return stream.next().await;
+        };
+        match tokio::time::timeout_at(deadline, stream.next()).await {
+            Ok(event) => event,
+            Err(_) => Some(Err(round_timeout(driver))),
+        }
+    }
+}
+
+/// Deadline for the current round of a driver.
+///
+/// Uses the `setInterval` timer of the host so round
+/// timeouts work in windows and workers; the timer is
+/// replaced when the driver moves to a new round.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+struct RoundTimer {
+    timeout: Option<Duration>,
+    round: Option<u16>,
+    deadline: Option<crate::keepalive::Interval>,
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl RoundTimer {
+    fn new<D: Driver>(driver: &D) -> Self {
+        let timeout = driver.round_timeout();
+        Self {
+            timeout,
+            round: driver.round_status().map(|s| s.round),
+            deadline: timeout.map(crate::keepalive::Interval::new),
+        }
+    }
+
+    /// Restart the timer when the driver moves to a new round.
+    fn update<D: Driver>(&mut self, driver: &D) {
+        let round = driver.round_status().map(|s| s.round);
+        if round != self.round {
+            self.round = round;
+            self.deadline =
+                self.timeout.map(crate::keepalive::Interval::new);
+        }
+    }
+
+    /// Next event from the stream.
+    ///
+    /// Yields an error with the parties missing from the
+    /// current round when the deadline is reached.
+    async fn next<D: Driver>(
+        &mut self,
+        stream: &mut EventStream,
+        driver: &D,
+    ) -> Option<Result<Event>> {
+        let Some(deadline) = self.deadline.as_mut() else {
+            return stream.next().await;
+        };
+        tokio::select! {
+            event = stream.next() => event,
+            _ = deadline.recv() => Some(Err(round_timeout(driver))),
+        }
+    }
+}
+
+/// Error for a round that did not complete before the
+/// deadline with the parties missing from the round.
+fn round_timeout<D: Driver>(driver: &D) -> crate::Error {
+    let (round, missing) = driver
+        .round_status()
+        .map(|s| (s.round, s.missing()))
+        .unwrap_or_default();
+    tracing::warn!(
+        round = round,
+        missing = ?missing,
+        "round timeout"
+    );
+    crate::Error::RoundTimeout { round, missing }
+}
+
+/// Attach the ceremony report for a driver to an error.
+fn ceremony_error<D: Driver>(
+    driver: &D,
+    error: crate::Error,
+) -> crate::Error {
+    crate::Error::Ceremony {
+        report: Box::new(driver.report()),
+        source: Box::new(error),
+    }
 }
 
 /// Wait for a close event.
@@ -162,7 +507,7 @@ pub async fn wait_for_session_finish(
 ) -> crate::Result<()> {
     while let Some(event) = stream.next().await {
         let event = event?;
-        if let Event::SessionFinished(id) = event {
+        if let Event::SessionFinished(id, _) = event {
             if session_id == id {
                 break;
             }
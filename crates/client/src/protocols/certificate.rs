@@ -0,0 +1,134 @@
+//! Ceremony certificates for key generation.
+use crate::{
+    protocols::{
+        timestamp, Bridge, CeremonyReport, Driver, RoundStatus,
+    },
+    Error, NetworkTransport, Result, Transport,
+};
+use async_trait::async_trait;
+use polysig_protocol::{hex, Event, PartyNumber, SessionState};
+
+use polysig_driver::{
+    certificate::{
+        Ceremony, CeremonyCertificate, CertificateDriver,
+        CertificateKey, CertifiedDriver as Certified, Endorse,
+    },
+    ProtocolDriver,
+};
+
+/// Driver that runs a protocol and then endorses a
+/// ceremony certificate.
+pub struct CertifiedDriver<D, V>
+where
+    D: ProtocolDriver,
+    V: CertificateKey,
+{
+    bridge: Bridge<Certified<D, V>>,
+}
+
+impl<D, V> CertifiedDriver<D, V>
+where
+    D: ProtocolDriver,
+    V: CertificateKey,
+{
+    /// Create a new certified driver.
+    ///
+    /// The ceremony function is called with the output of the
+    /// protocol to create the ceremony statement that every
+    /// participant endorses using the signing key.
+    pub fn new<F>(
+        transport: Transport,
+        session: SessionState,
+        new_driver: impl FnOnce(PartyNumber) -> Result<D>,
+        signing_key: V::SigningKey,
+        started_at: u64,
+        ceremony: F,
+    ) -> Result<Self>
+    where
+        F: FnOnce(&D::Output) -> polysig_driver::Result<Ceremony>
+            + Send
+            + Sync
+            + 'static,
+        V::SigningKey: Send + Sync + 'static,
+    {
+        let party_number = session
+            .party_number(transport.public_key())
+            .ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(
+                    transport.public_key(),
+                ))
+            })?;
+
+        let endorse: Endorse<D::Output, V> =
+            Box::new(move |output| {
+                let ceremony = ceremony(output)?;
+                Ok(CertificateDriver::new(
+                    party_number,
+                    ceremony,
+                    &signing_key,
+                    started_at,
+                    timestamp(),
+                )?)
+            });
+
+        let driver =
+            Certified::new(new_driver(party_number)?, endorse);
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl<D, V> Driver for CertifiedDriver<D, V>
+where
+    D: ProtocolDriver + Send + Sync,
+    D::Output: Send + Sync,
+    V: CertificateKey + Send + Sync,
+{
+    type Output = (D::Output, CeremonyCertificate);
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl<D, V> From<CertifiedDriver<D, V>> for Transport
+where
+    D: ProtocolDriver,
+    V: CertificateKey,
+{
+    fn from(value: CertifiedDriver<D, V>) -> Self {
+        value.bridge.transport
+    }
+}
@@ -1,6 +1,6 @@
 //! Aux info generation for CGGMP.
 use crate::{
-    protocols::{Bridge, Driver},
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
     NetworkTransport, Transport,
 };
 use async_trait::async_trait;
@@ -46,12 +46,8 @@ where
 
         let driver = CggmpDriver::new(session_id, signer, verifiers)?;
 
-        let bridge = Bridge {
-            transport,
-            driver: Some(driver),
-            session,
-            party_number,
-        };
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
         Ok(Self { bridge })
     }
 }
@@ -70,13 +66,32 @@ where
         Ok(self.bridge.handle_event(event).await?)
     }
 
-    async fn execute(&mut self) -> Result<()> {
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
         Ok(self.bridge.execute().await?)
     }
 
     fn into_transport(self) -> Transport {
         self.bridge.transport
     }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
 }
 
 impl<P> From<AuxGenDriver<P>> for Transport
@@ -1,6 +1,6 @@
 //! Key resharing for CGGMP.
 use crate::{
-    protocols::{Bridge, Driver},
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
     NetworkTransport, Transport,
 };
 use async_trait::async_trait;
@@ -14,6 +14,7 @@ use polysig_driver::{
         KeyResharingInputs, SchemeParams, SessionId,
         ThresholdKeyShare,
     },
+    BatchDriver,
 };
 
 /// CGGMP key resharing driver.
@@ -48,12 +49,8 @@ where
         let driver =
             CggmpDriver::new(session_id, signer, verifiers, inputs)?;
 
-        let bridge = Bridge {
-            transport,
-            driver: Some(driver),
-            session,
-            party_number,
-        };
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
         Ok(Self { bridge })
     }
 }
@@ -72,13 +69,32 @@ where
         Ok(self.bridge.handle_event(event).await?)
     }
 
-    async fn execute(&mut self) -> Result<()> {
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
         Ok(self.bridge.execute().await?)
     }
 
     fn into_transport(self) -> Transport {
         self.bridge.transport
     }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
 }
 
 impl<P> From<KeyResharingDriver<P>> for Transport
@@ -89,3 +105,106 @@ where
         value.bridge.transport
     }
 }
+
+/// CGGMP key resharing driver for multiple key shares.
+///
+/// Each key share is reshared by a separate driver and
+/// the messages for all drivers are multiplexed over the
+/// same session.
+pub struct KeyResharingBatchDriver<P>
+where
+    P: SchemeParams + 'static,
+{
+    bridge: Bridge<BatchDriver<CggmpDriver<P>>>,
+}
+
+impl<P> KeyResharingBatchDriver<P>
+where
+    P: SchemeParams + 'static,
+{
+    /// Create a new CGGMP batch resharing driver.
+    ///
+    /// Each entry in the batch is a session identifier and the
+    /// inputs for the key share to be reshared.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        signer: SigningKey,
+        verifiers: Vec<VerifyingKey>,
+        batch: Vec<(SessionId, KeyResharingInputs<P, VerifyingKey>)>,
+    ) -> Result<Self> {
+        let party_number = session
+            .party_number(transport.public_key())
+            .ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(
+                    transport.public_key(),
+                ))
+            })?;
+
+        let mut drivers = Vec::with_capacity(batch.len());
+        for (session_id, inputs) in batch {
+            drivers.push(CggmpDriver::new(
+                session_id,
+                signer.clone(),
+                verifiers.clone(),
+                inputs,
+            )?);
+        }
+
+        let driver = BatchDriver::new(drivers);
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl<P> Driver for KeyResharingBatchDriver<P>
+where
+    P: SchemeParams + 'static,
+{
+    type Output = Vec<ThresholdKeyShare<P, VerifyingKey>>;
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl<P> From<KeyResharingBatchDriver<P>> for Transport
+where
+    P: SchemeParams + 'static,
+{
+    fn from(value: KeyResharingBatchDriver<P>) -> Self {
+        value.bridge.transport
+    }
+}
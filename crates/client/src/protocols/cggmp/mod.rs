@@ -1,23 +1,36 @@
 //! Driver for the CGGMP protocol.
 use crate::{
-    new_client, wait_for_close, wait_for_driver, wait_for_session,
+    new_client,
+    protocols::certificate::CertifiedDriver,
+    protocols::policy::{approve, key_share_policy},
+    protocols::timestamp,
+    protocols::validate_parameters,
+    wait_for_close, wait_for_driver, wait_for_session,
     wait_for_session_finish, Error, EventStream, NetworkTransport,
     SessionHandler, SessionInitiator, SessionOptions,
     SessionParticipant, Transport,
 };
 use futures::StreamExt;
 use polysig_driver::{
-    cggmp::Participant,
+    certificate::{
+        Ceremony, CeremonyCertificate, Error as CertificateError,
+    },
+    cggmp::{
+        validate_party, KeyInitDriver as KeyInitCggmpDriver,
+        KeyResharingDriver as KeyResharingCggmpDriver, Participant,
+        ThresholdShare,
+    },
+    digest::DigestAlgorithm,
     recoverable_signature::RecoverableSignature,
     synedrion::{
         self,
         ecdsa::{SigningKey, VerifyingKey},
-        KeyResharingInputs, NewHolder, OldHolder, PrehashedMessage,
-        SchemeParams, SessionId, ThresholdKeyShare,
+        KeyResharingInputs, KeyShare, NewHolder, OldHolder,
+        PrehashedMessage, SchemeParams, SessionId, ThresholdKeyShare,
     },
 };
 use polysig_protocol::{
-    Event, SessionId as ProtocolSessionId, SessionState,
+    Event, Parameters, SessionId as ProtocolSessionId, SessionState,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
@@ -27,6 +40,7 @@ mod key_gen;
 mod key_init;
 mod key_refresh;
 mod key_resharing;
+mod orchestrator;
 mod sign;
 
 #[doc(hidden)]
@@ -38,9 +52,14 @@ pub use key_init::KeyInitDriver;
 #[doc(hidden)]
 pub use key_refresh::KeyRefreshDriver;
 #[doc(hidden)]
-pub use key_resharing::KeyResharingDriver;
+pub use key_resharing::{
+    KeyResharingBatchDriver, KeyResharingDriver,
+};
+pub use orchestrator::{
+    Orchestrator, OrchestratorOptions, SignOutcome, SignRequest,
+};
 #[doc(hidden)]
-pub use sign::SignatureDriver;
+pub use sign::{SignatureBatchDriver, SignatureDriver};
 
 /// Message sent by key init participants to
 /// notify clients that are not participating
@@ -62,9 +81,53 @@ pub async fn dkg<P: SchemeParams + 'static>(
     participant: Participant,
     session_id: SessionId,
 ) -> crate::Result<ThresholdKeyShare<P, VerifyingKey>> {
+    let (key_share, _) =
+        run_dkg::<P>(options, participant, session_id, false).await?;
+    Ok(key_share)
+}
+
+/// Run threshold DKG for the CGGMP protocol and create a
+/// ceremony certificate.
+///
+/// Once key generation completes every participant endorses a
+/// statement describing the ceremony so that auditors can later
+/// verify which parties created the key.
+pub async fn dkg_certificate<P: SchemeParams + 'static>(
+    options: SessionOptions,
+    participant: Participant,
+    session_id: SessionId,
+) -> crate::Result<(
+    ThresholdKeyShare<P, VerifyingKey>,
+    CeremonyCertificate,
+)> {
+    let (key_share, certificate) =
+        run_dkg::<P>(options, participant, session_id, true).await?;
+    Ok((key_share, certificate.unwrap()))
+}
+
+async fn run_dkg<P: SchemeParams + 'static>(
+    options: SessionOptions,
+    participant: Participant,
+    session_id: SessionId,
+    with_certificate: bool,
+) -> crate::Result<(
+    ThresholdKeyShare<P, VerifyingKey>,
+    Option<CeremonyCertificate>,
+)> {
+    validate_party(participant.party())?;
+
+    let params = options.parameters;
+    let started_at = timestamp();
     let n = options.parameters.parties as usize;
     let t = options.parameters.threshold as usize;
 
+    // CGGMP requires a threshold of at least two
+    validate_parameters(
+        &params,
+        participant.party().participants().len(),
+        2,
+    )?;
+
     // Create the client
     let (client, event_loop) = new_client(options).await?;
 
@@ -78,10 +141,13 @@ pub async fn dkg<P: SchemeParams + 'static>(
 
     // Wait for the session to become active
     let client_session = if participant.party().is_initiator() {
-        SessionHandler::Initiator(SessionInitiator::new(
-            transport,
-            participant.party().participants().to_vec(),
-        ))
+        SessionHandler::Initiator(
+            SessionInitiator::new(
+                transport,
+                participant.party().participants().to_vec(),
+            )
+            .with_parameters(params),
+        )
     } else {
         SessionHandler::Participant(SessionParticipant::new(
             transport,
@@ -92,47 +158,136 @@ pub async fn dkg<P: SchemeParams + 'static>(
         wait_for_session(&mut stream, client_session).await?;
 
     let protocol_session_id = session.session_id;
+    let verifiers = participant.party().verifiers().to_vec();
+
+    let (mut transport, mut stream, t_key_share, certificate) =
+        if with_certificate && t == n {
+            // Every party holds a key share after key init so
+            // the endorsements replace the key init acknowledgements
+            let signer = participant.signing_key().to_owned();
+            let init_verifiers = verifiers.clone();
+            let driver = CertifiedDriver::<_, VerifyingKey>::new(
+                transport,
+                session,
+                move |_| {
+                    Ok(KeyInitCggmpDriver::<P>::new(
+                        session_id,
+                        signer,
+                        init_verifiers,
+                    )?)
+                },
+                participant.signing_key().to_owned(),
+                started_at,
+                move |key_share: &KeyShare<P, VerifyingKey>| {
+                    if key_share.verifying_key().is_none() {
+                        return Err(
+                            CertificateError::VerifyingKey.into()
+                        );
+                    }
+                    ceremony(
+                        protocol_session_id,
+                        params,
+                        &verifiers,
+                        &ThresholdKeyShare::from_key_share(key_share),
+                    )
+                },
+            )?;
+            let (transport, (key_share, certificate)) =
+                wait_for_driver(&mut stream, driver).await?;
+            let t_key_share =
+                ThresholdKeyShare::from_key_share(&key_share);
+            (transport, stream, t_key_share, Some(certificate))
+        } else {
+            let (transport, mut stream, t_key_share, acks) =
+                make_dkg_init::<P>(
+                    t,
+                    &participant,
+                    transport,
+                    stream,
+                    protocol_session_id,
+                    session.clone(),
+                    session_id,
+                )
+                .await?;
 
-    let (transport, stream, t_key_share, acks) = make_dkg_init::<P>(
-        t,
-        &participant,
-        transport,
-        stream,
-        protocol_session_id,
-        session.clone(),
-        session_id,
-    )
-    .await?;
-
-    // Do key resharing phase
-    let (mut transport, mut stream, t_key_share) = if t < n {
-        let account_verifying_key =
-            if let Some(t_key_share) = &t_key_share {
-                t_key_share.verifying_key().clone()
+            if t == n {
+                (transport, stream, t_key_share.unwrap(), None)
             } else {
-                let ack = acks
-                    .iter()
-                    .find(|a| a.party_index == 0)
-                    .ok_or(Error::NoKeyInitAck)?;
-                ack.key_share_verifying_key.clone()
-            };
-
-        make_dkg_reshare::<P>(
-            t,
-            t,
-            account_verifying_key,
-            t_key_share,
-            transport,
-            stream,
-            session,
-            session_id,
-            participant.signing_key().to_owned(),
-            participant.party().verifiers(),
-        )
-        .await?
-    } else {
-        (transport, stream, t_key_share.unwrap())
-    };
+                // Do key resharing phase
+                let account_verifying_key =
+                    if let Some(t_key_share) = &t_key_share {
+                        t_key_share.verifying_key().clone()
+                    } else {
+                        let ack = acks
+                            .iter()
+                            .find(|a| a.party_index == 0)
+                            .ok_or(Error::NoKeyInitAck)?;
+                        ack.key_share_verifying_key.clone()
+                    };
+
+                if with_certificate {
+                    let inputs = reshare_inputs(
+                        t,
+                        t,
+                        account_verifying_key,
+                        t_key_share,
+                        &verifiers,
+                    );
+                    let signer = participant.signing_key().to_owned();
+                    let reshare_verifiers = verifiers.clone();
+                    let driver =
+                        CertifiedDriver::<_, VerifyingKey>::new(
+                            transport,
+                            session,
+                            move |_| {
+                                Ok(KeyResharingCggmpDriver::<P>::new(
+                                    session_id,
+                                    signer,
+                                    reshare_verifiers,
+                                    inputs,
+                                )?)
+                            },
+                            participant.signing_key().to_owned(),
+                            started_at,
+                            move |key_share: &ThresholdKeyShare<
+                                P,
+                                VerifyingKey,
+                            >| {
+                                ceremony(
+                                    protocol_session_id,
+                                    params,
+                                    &verifiers,
+                                    key_share,
+                                )
+                            },
+                        )?;
+                    let (transport, (t_key_share, certificate)) =
+                        wait_for_driver(&mut stream, driver).await?;
+                    (
+                        transport,
+                        stream,
+                        t_key_share,
+                        Some(certificate),
+                    )
+                } else {
+                    let (transport, stream, t_key_share) =
+                        make_dkg_reshare::<P>(
+                            t,
+                            t,
+                            account_verifying_key,
+                            t_key_share,
+                            transport,
+                            stream,
+                            session,
+                            session_id,
+                            participant.signing_key().to_owned(),
+                            &verifiers,
+                        )
+                        .await?;
+                    (transport, stream, t_key_share, None)
+                }
+            }
+        };
 
     // Close the session and socket
     if participant.party().is_initiator() {
@@ -144,7 +299,35 @@ pub async fn dkg<P: SchemeParams + 'static>(
     transport.close().await?;
     wait_for_close(&mut stream).await?;
 
-    Ok(t_key_share)
+    Ok((t_key_share, certificate))
+}
+
+/// Create the ceremony statement for a CGGMP key.
+///
+/// The transcript is the public output of key generation; the
+/// threshold, the verifying key and the public share of every
+/// party ordered by party number.
+fn ceremony<P: SchemeParams>(
+    session_id: ProtocolSessionId,
+    params: Parameters,
+    verifiers: &[VerifyingKey],
+    key_share: &ThresholdKeyShare<P, VerifyingKey>,
+) -> polysig_driver::Result<Ceremony> {
+    let share = ThresholdShare::from_key_share(key_share, verifiers)?;
+    let verifying_key = key_share.verifying_key().to_sec1_bytes();
+    let transcript = serde_json::to_vec(&(
+        share.threshold,
+        &verifying_key,
+        &share.parties,
+    ))?;
+    Ok(Ceremony::new(
+        "cggmp",
+        session_id,
+        params,
+        verifiers,
+        verifying_key.to_vec(),
+        &transcript,
+    )?)
 }
 
 /// Make initialize key share for threshold DKG.
@@ -276,6 +459,8 @@ pub async fn reshare<P: SchemeParams>(
     old_threshold: usize,
     new_threshold: usize,
 ) -> crate::Result<ThresholdKeyShare<P, VerifyingKey>> {
+    validate_party(participant.party())?;
+
     // Create the client
     let (client, event_loop) = new_client(options).await?;
 
@@ -349,11 +534,137 @@ async fn make_dkg_reshare<P: SchemeParams + 'static>(
     EventStream,
     ThresholdKeyShare<P, VerifyingKey>,
 )> {
+    let inputs = reshare_inputs(
+        old_threshold,
+        new_threshold,
+        account_verifying_key,
+        t_key_share,
+        verifiers,
+    );
+
+    let driver = KeyResharingDriver::<P>::new(
+        transport,
+        session,
+        session_id,
+        signer,
+        verifiers.to_vec(),
+        inputs,
+    )?;
+
+    let (transport, key_share) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    Ok((transport, stream, key_share))
+}
+
+/// Account key share to be reshared in a batch.
+pub struct ReshareAccount<P: SchemeParams> {
+    /// Session identifier for the account resharing.
+    ///
+    /// Must be unique for each account in a batch.
+    pub session_id: SessionId,
+    /// Verifying key for the account.
+    pub account_verifying_key: VerifyingKey,
+    /// Existing key share when the participant is an old holder.
+    pub key_share: Option<ThresholdKeyShare<P, VerifyingKey>>,
+}
+
+/// Reshare the key shares for multiple accounts
+/// in a single session.
+///
+/// Key shares are returned in the same order as
+/// the accounts.
+pub async fn reshare_batch<P: SchemeParams + 'static>(
+    options: SessionOptions,
+    participant: Participant,
+    accounts: Vec<ReshareAccount<P>>,
+    old_threshold: usize,
+    new_threshold: usize,
+) -> crate::Result<Vec<ThresholdKeyShare<P, VerifyingKey>>> {
+    validate_party(participant.party())?;
+
+    // Create the client
+    let (client, event_loop) = new_client(options).await?;
+
+    let mut transport: Transport = client.into();
+
+    // Handshake with the server
+    transport.connect().await?;
+
+    // Start the event stream
+    let mut stream = event_loop.run();
+
+    // Wait for the session to become active
+    let client_session = if participant.party().is_initiator() {
+        SessionHandler::Initiator(SessionInitiator::new(
+            transport,
+            participant.party().participants().to_vec(),
+        ))
+    } else {
+        SessionHandler::Participant(SessionParticipant::new(
+            transport,
+        ))
+    };
+
+    let (transport, session) =
+        wait_for_session(&mut stream, client_session).await?;
+
+    let protocol_session_id = session.session_id;
+
+    let verifiers = participant.party().verifiers();
+    let batch = accounts
+        .into_iter()
+        .map(|account| {
+            (
+                account.session_id,
+                reshare_inputs(
+                    old_threshold,
+                    new_threshold,
+                    account.account_verifying_key,
+                    account.key_share,
+                    verifiers,
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let driver = KeyResharingBatchDriver::<P>::new(
+        transport,
+        session,
+        participant.signing_key().to_owned(),
+        verifiers.to_vec(),
+        batch,
+    )?;
+
+    let (mut transport, key_shares) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    // Close the session and socket
+    if participant.party().is_initiator() {
+        transport.close_session(protocol_session_id).await?;
+        wait_for_session_finish(&mut stream, protocol_session_id)
+            .await?;
+    }
+
+    transport.close().await?;
+    wait_for_close(&mut stream).await?;
+
+    Ok(key_shares)
+}
+
+/// Prepare the inputs for key resharing.
+fn reshare_inputs<P: SchemeParams + 'static>(
+    old_threshold: usize,
+    new_threshold: usize,
+    account_verifying_key: VerifyingKey,
+    t_key_share: Option<ThresholdKeyShare<P, VerifyingKey>>,
+    verifiers: &[VerifyingKey],
+) -> KeyResharingInputs<P, VerifyingKey> {
     let old_holders = BTreeSet::from_iter(
         verifiers.iter().cloned().take(old_threshold),
     );
 
-    let inputs = if let Some(t_key_share) = t_key_share {
+    if let Some(t_key_share) = t_key_share {
         let new_holder = NewHolder {
             verifying_key: account_verifying_key,
             old_threshold,
@@ -387,21 +698,58 @@ async fn make_dkg_reshare<P: SchemeParams + 'static>(
                 .collect::<BTreeSet<_>>(),
             new_threshold,
         }
-    };
+    }
+}
 
-    let driver = KeyResharingDriver::<P>::new(
-        transport,
-        session,
+/// Sign a message using the CGGMP protocol after computing
+/// the prehash of the message with a digest algorithm.
+///
+/// The digest algorithm must produce 32 bytes.
+pub async fn sign_message<P: SchemeParams + 'static>(
+    options: SessionOptions,
+    participant: Participant,
+    session_id: SessionId,
+    key_share: &synedrion::KeyShare<P, VerifyingKey>,
+    message: &[u8],
+    digest: DigestAlgorithm,
+) -> crate::Result<RecoverableSignature> {
+    let prehashed_message = digest.prehash(message)?;
+    sign(
+        options,
+        participant,
         session_id,
-        signer,
-        verifiers.to_vec(),
-        inputs,
-    )?;
-
-    let (transport, key_share) =
-        wait_for_driver(&mut stream, driver).await?;
+        key_share,
+        &prehashed_message,
+    )
+    .await
+}
 
-    Ok((transport, stream, key_share))
+/// Sign a message with a key share container using the
+/// CGGMP protocol.
+///
+/// The policy stored in the container is enforced before
+/// joining the signing session.
+pub async fn sign_key_share<P: SchemeParams + 'static>(
+    options: SessionOptions,
+    participant: Participant,
+    session_id: SessionId,
+    key_share: &polysig_driver::KeyShare,
+    prehashed_message: &PrehashedMessage,
+) -> crate::Result<RecoverableSignature> {
+    let options = key_share_policy(options, key_share);
+    let key_share: ThresholdKeyShare<P, VerifyingKey> =
+        key_share.try_into()?;
+    let mut selected_parties = BTreeSet::new();
+    selected_parties.extend(participant.party().verifiers().iter());
+    let key_share = key_share.to_key_share(&selected_parties);
+    sign(
+        options,
+        participant,
+        session_id,
+        &key_share,
+        prehashed_message,
+    )
+    .await
 }
 
 /// Sign a message using the CGGMP protocol.
@@ -412,6 +760,17 @@ pub async fn sign<P: SchemeParams + 'static>(
     key_share: &synedrion::KeyShare<P, VerifyingKey>,
     prehashed_message: &PrehashedMessage,
 ) -> crate::Result<RecoverableSignature> {
+    validate_party(participant.party())?;
+
+    approve(
+        &options,
+        "cggmp",
+        participant.party(),
+        &[prehashed_message.as_slice()],
+        true,
+    )
+    .await?;
+
     // Create the client
     let (client, event_loop) = new_client(options).await?;
 
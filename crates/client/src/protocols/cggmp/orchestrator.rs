@@ -0,0 +1,287 @@
+//! Orchestrate signing ceremonies for batches of messages
+//! across multiple accounts.
+use super::{AuxGenDriver, SignatureBatchDriver};
+use crate::{
+    new_client, protocols::policy::approve, wait_for_close,
+    wait_for_driver, wait_for_session, wait_for_session_finish,
+    Error, ErrorInfo, ErrorKind, NetworkTransport, SessionHandler,
+    SessionInitiator, SessionOptions, SessionParticipant, Transport,
+};
+use polysig_driver::{
+    cggmp::{validate_party, Participant},
+    recoverable_signature::RecoverableSignature,
+    synedrion::{
+        ecdsa::VerifyingKey, KeyShare, PrehashedMessage,
+        SchemeParams, SessionId,
+    },
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Options for an orchestrator.
+#[derive(Debug, Clone, Copy)]
+pub struct OrchestratorOptions {
+    /// Maximum number of messages signed concurrently
+    /// in a session.
+    pub concurrency: usize,
+    /// Number of times a batch is retried when the
+    /// ceremony fails with a retryable error.
+    pub retries: usize,
+}
+
+impl Default for OrchestratorOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            retries: 2,
+        }
+    }
+}
+
+/// Request to sign a message using an account.
+#[derive(Debug, Clone)]
+pub struct SignRequest {
+    /// Identifier for the request in the results.
+    pub id: String,
+    /// Account that signs the message.
+    pub account: String,
+    /// Session identifier for the signature.
+    pub session_id: SessionId,
+    /// Prehashed message to sign.
+    pub message: PrehashedMessage,
+}
+
+/// Outcome of a signing request.
+#[derive(Debug)]
+pub enum SignOutcome {
+    /// Message was signed.
+    Signed(RecoverableSignature),
+    /// Ceremony for the message failed.
+    Failed {
+        /// Number of attempts to sign the message.
+        attempts: usize,
+        /// Error for the last attempt.
+        error: ErrorInfo,
+    },
+}
+
+/// Schedules the signing ceremonies for a collection
+/// of accounts and messages.
+///
+/// Requests are split into batches of at most `concurrency`
+/// messages that are signed concurrently in a single session;
+/// the connection and aux info are reused for every batch
+/// until a ceremony fails. Failed batches are retried using
+/// a new connection while the error is retryable.
+///
+/// Every party must run the orchestrator with the same
+/// requests in the same order and the same options so
+/// that the batches match.
+pub struct Orchestrator<P: SchemeParams + 'static> {
+    session_options: SessionOptions,
+    participant: Participant,
+    accounts: HashMap<String, KeyShare<P, VerifyingKey>>,
+    options: OrchestratorOptions,
+}
+
+impl<P: SchemeParams + 'static> Orchestrator<P> {
+    /// Create an orchestrator for the key shares of
+    /// a collection of accounts.
+    pub fn new(
+        session_options: SessionOptions,
+        participant: Participant,
+        accounts: HashMap<String, KeyShare<P, VerifyingKey>>,
+        options: OrchestratorOptions,
+    ) -> Self {
+        Self {
+            session_options,
+            participant,
+            accounts,
+            options,
+        }
+    }
+
+    /// Sign the messages for a collection of requests.
+    ///
+    /// The outcome for each request is keyed by the
+    /// request identifier.
+    pub async fn sign(
+        &self,
+        requests: Vec<SignRequest>,
+    ) -> crate::Result<HashMap<String, SignOutcome>> {
+        let mut ids = HashSet::new();
+        for request in &requests {
+            if !self.accounts.contains_key(&request.account) {
+                return Err(Error::UnknownAccount(
+                    request.account.clone(),
+                ));
+            }
+            if !ids.insert(request.id.as_str()) {
+                return Err(Error::DuplicateSignRequest(
+                    request.id.clone(),
+                ));
+            }
+        }
+
+        validate_party(self.participant.party())?;
+
+        let messages: Vec<&[u8]> =
+            requests.iter().map(|r| r.message.as_slice()).collect();
+        approve(
+            &self.session_options,
+            "cggmp",
+            self.participant.party(),
+            &messages,
+            true,
+        )
+        .await?;
+
+        let concurrency = self.options.concurrency.max(1);
+        let mut pending: VecDeque<&[SignRequest]> =
+            requests.chunks(concurrency).collect();
+        let mut results = HashMap::new();
+        let mut attempts = 0;
+
+        while !pending.is_empty() {
+            if let Err(e) = self
+                .run(&mut pending, &mut results, &mut attempts)
+                .await
+            {
+                if e.kind() == ErrorKind::Abort {
+                    return Err(e);
+                }
+
+                attempts += 1;
+                if e.is_retryable()
+                    && attempts <= self.options.retries
+                {
+                    tracing::warn!(
+                        attempts = %attempts,
+                        error = %e,
+                        "orchestrator::retry",
+                    );
+                    continue;
+                }
+
+                // Give up on the batch that failed
+                if let Some(batch) = pending.pop_front() {
+                    let error = e.info();
+                    for request in batch {
+                        results.insert(
+                            request.id.clone(),
+                            SignOutcome::Failed {
+                                attempts,
+                                error: error.clone(),
+                            },
+                        );
+                    }
+                }
+                attempts = 0;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Connect and sign the pending batches in a session.
+    ///
+    /// Batches are removed once they have been signed so
+    /// the batch at the front is the batch that failed
+    /// when an error is returned.
+    async fn run(
+        &self,
+        pending: &mut VecDeque<&[SignRequest]>,
+        results: &mut HashMap<String, SignOutcome>,
+        attempts: &mut usize,
+    ) -> crate::Result<()> {
+        let Some(first) = pending.front().and_then(|b| b.first())
+        else {
+            return Ok(());
+        };
+
+        // Create the client
+        let (client, event_loop) =
+            new_client(self.session_options.clone()).await?;
+
+        let mut transport: Transport = client.into();
+
+        // Handshake with the server
+        transport.connect().await?;
+
+        // Start the event stream
+        let mut stream = event_loop.run();
+
+        // Wait for the session to become active
+        let party = self.participant.party();
+        let client_session = if party.is_initiator() {
+            SessionHandler::Initiator(SessionInitiator::new(
+                transport,
+                party.participants().to_vec(),
+            ))
+        } else {
+            SessionHandler::Participant(SessionParticipant::new(
+                transport,
+            ))
+        };
+
+        let (transport, session) =
+            wait_for_session(&mut stream, client_session).await?;
+
+        let protocol_session_id = session.session_id;
+        let signer = self.participant.signing_key();
+        let verifiers = party.verifiers();
+
+        // Aux info is shared by every batch in the session
+        let driver = AuxGenDriver::<P>::new(
+            transport,
+            session.clone(),
+            first.session_id.clone(),
+            signer.clone(),
+            verifiers.to_vec(),
+        )?;
+        let (mut transport, aux_info) =
+            wait_for_driver(&mut stream, driver).await?;
+
+        while let Some(batch) = pending.front() {
+            let driver = SignatureBatchDriver::<P>::new(
+                transport,
+                session.clone(),
+                signer.clone(),
+                verifiers.to_vec(),
+                &aux_info,
+                batch
+                    .iter()
+                    .map(|request| {
+                        (
+                            request.session_id.clone(),
+                            &self.accounts[&request.account],
+                            &request.message,
+                        )
+                    })
+                    .collect(),
+            )?;
+            let (next_transport, signatures) =
+                wait_for_driver(&mut stream, driver).await?;
+            transport = next_transport;
+
+            for (request, signature) in batch.iter().zip(signatures) {
+                results.insert(
+                    request.id.clone(),
+                    SignOutcome::Signed(signature),
+                );
+            }
+            pending.pop_front();
+            *attempts = 0;
+        }
+
+        // Close the session and socket
+        if party.is_initiator() {
+            transport.close_session(protocol_session_id).await?;
+            wait_for_session_finish(&mut stream, protocol_session_id)
+                .await?;
+        }
+        transport.close().await?;
+        wait_for_close(&mut stream).await?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! Signature generation for CGGMP.
 use crate::{
-    protocols::{Bridge, Driver},
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
     Error, NetworkTransport, Result, Transport,
 };
 use async_trait::async_trait;
@@ -13,6 +13,7 @@ use polysig_driver::{
         ecdsa::{SigningKey, VerifyingKey},
         AuxInfo, KeyShare, PrehashedMessage, SchemeParams, SessionId,
     },
+    BatchDriver,
 };
 
 /// CGGMP signing driver.
@@ -55,12 +56,8 @@ where
             prehashed_message,
         )?;
 
-        let bridge = Bridge {
-            transport,
-            driver: Some(driver),
-            session,
-            party_number,
-        };
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
         Ok(Self { bridge })
     }
 }
@@ -79,13 +76,32 @@ where
         Ok(self.bridge.handle_event(event).await?)
     }
 
-    async fn execute(&mut self) -> Result<()> {
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
         Ok(self.bridge.execute().await?)
     }
 
     fn into_transport(self) -> Transport {
         self.bridge.transport
     }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
 }
 
 impl<P> From<SignatureDriver<P>> for Transport
@@ -96,3 +112,113 @@ where
         value.bridge.transport
     }
 }
+
+/// CGGMP signing driver for multiple messages.
+///
+/// Each message is signed by a separate driver using the
+/// same aux info and the messages for all drivers are
+/// multiplexed over the same session.
+pub struct SignatureBatchDriver<P>
+where
+    P: SchemeParams + 'static,
+{
+    bridge: Bridge<BatchDriver<CggmpDriver<P>>>,
+}
+
+impl<P> SignatureBatchDriver<P>
+where
+    P: SchemeParams + 'static,
+{
+    /// Create a new CGGMP batch signature driver.
+    ///
+    /// Each entry in the batch is a session identifier, the
+    /// key share and the prehashed message to sign.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        signer: SigningKey,
+        verifiers: Vec<VerifyingKey>,
+        aux_info: &AuxInfo<P, VerifyingKey>,
+        batch: Vec<(
+            SessionId,
+            &KeyShare<P, VerifyingKey>,
+            &PrehashedMessage,
+        )>,
+    ) -> Result<Self> {
+        let party_number = session
+            .party_number(transport.public_key())
+            .ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(
+                    transport.public_key(),
+                ))
+            })?;
+
+        let mut drivers = Vec::with_capacity(batch.len());
+        for (session_id, key_share, prehashed_message) in batch {
+            drivers.push(CggmpDriver::new(
+                session_id,
+                signer.clone(),
+                verifiers.clone(),
+                key_share,
+                aux_info,
+                prehashed_message,
+            )?);
+        }
+
+        let driver = BatchDriver::new(drivers);
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl<P> Driver for SignatureBatchDriver<P>
+where
+    P: SchemeParams + 'static,
+{
+    type Output = Vec<RecoverableSignature>;
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl<P> From<SignatureBatchDriver<P>> for Transport
+where
+    P: SchemeParams + 'static,
+{
+    fn from(value: SignatureBatchDriver<P>) -> Self {
+        value.bridge.transport
+    }
+}
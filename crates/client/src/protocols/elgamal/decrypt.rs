@@ -0,0 +1,93 @@
+//! Threshold decryption for ElGamal.
+use crate::{
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
+    Error, NetworkTransport, Result, Transport,
+};
+use async_trait::async_trait;
+use polysig_protocol::{hex, Event, SessionState};
+
+use polysig_driver::elgamal::{
+    Ciphertext, DecryptionDriver as ElgamalDriver, Identifier,
+    KeyShare,
+};
+
+/// ElGamal decryption driver.
+pub struct DecryptionDriver {
+    bridge: Bridge<ElgamalDriver>,
+}
+
+impl DecryptionDriver {
+    /// Create a new ElGamal decryption driver.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        identifiers: Vec<Identifier>,
+        key_share: &KeyShare,
+        ciphertext: Ciphertext,
+        aad: Vec<u8>,
+    ) -> Result<Self> {
+        let party_number = session
+            .party_number(transport.public_key())
+            .ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(
+                    transport.public_key(),
+                ))
+            })?;
+
+        let driver = ElgamalDriver::new(
+            party_number,
+            identifiers,
+            key_share,
+            ciphertext,
+            aad,
+        )?;
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl Driver for DecryptionDriver {
+    type Output = Vec<u8>;
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl From<DecryptionDriver> for Transport {
+    fn from(value: DecryptionDriver) -> Self {
+        value.bridge.transport
+    }
+}
@@ -0,0 +1,84 @@
+//! Driver for threshold ElGamal decryption.
+use polysig_driver::elgamal::{
+    Ciphertext, Identifier, KeyShare, Participant,
+};
+
+use crate::{
+    new_client, wait_for_close, wait_for_driver, wait_for_session,
+    wait_for_session_finish, SessionHandler, SessionInitiator,
+    SessionOptions, SessionParticipant, Transport,
+};
+
+mod decrypt;
+
+pub use decrypt::DecryptionDriver;
+
+/// Run distributed key generation for an encryption key.
+///
+/// Keys are generated using FROST Secp256k1 key generation.
+pub use crate::protocols::frost::secp256k1::dkg;
+
+/// Decrypt a ciphertext with a threshold of the key holders.
+///
+/// The identifiers are the key share identifiers of each
+/// decryptor ordered by session party number.
+pub async fn decrypt(
+    options: SessionOptions,
+    participant: Participant,
+    identifiers: Vec<Identifier>,
+    key_share: &KeyShare,
+    ciphertext: Ciphertext,
+    aad: Vec<u8>,
+) -> crate::Result<Vec<u8>> {
+    // Create the client
+    let (client, event_loop) = new_client(options).await?;
+
+    let mut transport: Transport = client.into();
+
+    // Handshake with the server
+    transport.connect().await?;
+
+    // Start the event stream
+    let mut stream = event_loop.run();
+
+    // Wait for the session to become active
+    let client_session = if participant.party().is_initiator() {
+        SessionHandler::Initiator(SessionInitiator::new(
+            transport,
+            participant.party().participants().to_vec(),
+        ))
+    } else {
+        SessionHandler::Participant(SessionParticipant::new(
+            transport,
+        ))
+    };
+
+    let (transport, session) =
+        wait_for_session(&mut stream, client_session).await?;
+
+    let protocol_session_id = session.session_id;
+
+    // Wait for the ciphertext to be decrypted
+    let driver = DecryptionDriver::new(
+        transport,
+        session,
+        identifiers,
+        key_share,
+        ciphertext,
+        aad,
+    )?;
+
+    let (mut transport, message) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    // Close the session and socket
+    if participant.party().is_initiator() {
+        transport.close_session(protocol_session_id).await?;
+        wait_for_session_finish(&mut stream, protocol_session_id)
+            .await?;
+    }
+    transport.close().await?;
+    wait_for_close(&mut stream).await?;
+
+    Ok(message)
+}
@@ -0,0 +1,81 @@
+//! Trusted dealer key generation for FROST.
+macro_rules! frost_dealer_impl {
+    () => {
+        /// Generate key shares using a trusted dealer.
+        ///
+        /// The dealer splits the signing key into shares and
+        /// distributes a share to every other participant; when
+        /// no signing key is given the dealer generates a new
+        /// signing key.
+        ///
+        /// Only the dealer may provide a signing key; a trusted
+        /// dealer knows the entire signing key so distributed key
+        /// generation should be preferred when possible.
+        pub async fn deal(
+            options: SessionOptions,
+            participant: Participant,
+            identifiers: Vec<Identifier>,
+            dealer: Identifier,
+            signing_key: Option<SigningKey>,
+        ) -> crate::Result<KeyShare> {
+            let params = options.parameters;
+
+            // Create the client
+            let (client, event_loop) = new_client(options).await?;
+
+            let mut transport: Transport = client.into();
+
+            // Handshake with the server
+            transport.connect().await?;
+
+            // Start the event stream
+            let mut stream = event_loop.run();
+
+            // Wait for the session to become active
+            let client_session = if participant.party().is_initiator()
+            {
+                SessionHandler::Initiator(SessionInitiator::new(
+                    transport,
+                    participant.party().participants().to_vec(),
+                ))
+            } else {
+                SessionHandler::Participant(SessionParticipant::new(
+                    transport,
+                ))
+            };
+
+            let (transport, session) =
+                wait_for_session(&mut stream, client_session).await?;
+
+            let protocol_session_id = session.session_id;
+
+            let driver = dealer::new_driver(
+                transport,
+                session,
+                params,
+                identifiers,
+                dealer,
+                signing_key,
+            )?;
+
+            let (mut transport, key_share) =
+                wait_for_driver(&mut stream, driver).await?;
+
+            // Close the session and socket
+            if participant.party().is_initiator() {
+                transport.close_session(protocol_session_id).await?;
+                wait_for_session_finish(
+                    &mut stream,
+                    protocol_session_id,
+                )
+                .await?;
+            }
+            transport.close().await?;
+            wait_for_close(&mut stream).await?;
+
+            Ok(key_share)
+        }
+    };
+}
+
+pub(crate) use frost_dealer_impl;
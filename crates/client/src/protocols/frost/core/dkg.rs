@@ -1,6 +1,6 @@
 //! Generic distributed key generation for FROST.
 use crate::{
-    protocols::{Bridge, Driver},
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
     Result, Transport,
 };
 use async_trait::async_trait;
@@ -28,12 +28,8 @@ where
         party_number: PartyNumber,
         driver: D,
     ) -> Self {
-        let bridge = Bridge {
-            transport,
-            driver: Some(driver),
-            session,
-            party_number,
-        };
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
         Self {
             bridge,
             marker: std::marker::PhantomData,
@@ -56,13 +52,32 @@ where
         Ok(self.bridge.handle_event(event).await?)
     }
 
-    async fn execute(&mut self) -> Result<()> {
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
         Ok(self.bridge.execute().await?)
     }
 
     fn into_transport(self) -> Transport {
         self.bridge.transport
     }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
 }
 
 impl<D, O> From<DkgDriver<D, O>> for Transport
@@ -75,14 +90,60 @@ where
 }
 
 macro_rules! frost_dkg_impl {
-    () => {
+    ($protocol:literal) => {
         /// Run distributed key generation for the FROST protocol.
         pub async fn dkg(
             options: SessionOptions,
             participant: Participant,
             identifiers: Vec<Identifier>,
         ) -> crate::Result<KeyShare> {
-            let params = options.parameters;
+            let (key_share, _) =
+                run_dkg(options, participant, identifiers, false)
+                    .await?;
+            Ok(key_share)
+        }
+
+        /// Run distributed key generation for the FROST protocol
+        /// and create a ceremony certificate.
+        ///
+        /// Once key generation completes every participant
+        /// endorses a statement describing the ceremony so that
+        /// auditors can later verify which parties created the key.
+        pub async fn dkg_certificate(
+            options: SessionOptions,
+            participant: Participant,
+            identifiers: Vec<Identifier>,
+        ) -> crate::Result<(KeyShare, CeremonyCertificate)> {
+            let (key_share, certificate) =
+                run_dkg(options, participant, identifiers, true)
+                    .await?;
+            Ok((key_share, certificate.unwrap()))
+        }
+
+        /// Run distributed key generation for the FROST protocol
+        /// with weighted participants.
+        ///
+        /// The weight of each participant is taken from the party
+        /// options and the threshold in the session options is the
+        /// total weight required to sign; a key share is returned
+        /// for every share held by this participant in the order
+        /// of the share identifiers.
+        pub async fn dkg_weighted(
+            options: SessionOptions,
+            participant: Participant,
+        ) -> crate::Result<Vec<KeyShare>> {
+            let threshold = options.parameters.threshold;
+            let weights = participant.party().weights();
+
+            // FROST requires at least two signers
+            validate_parameters(
+                &Parameters {
+                    parties: weights.total(),
+                    threshold,
+                },
+                weights.total() as usize,
+                2,
+            )?;
 
             // Create the client
             let (client, event_loop) = new_client(options).await?;
@@ -95,7 +156,8 @@ macro_rules! frost_dkg_impl {
             // Start the event stream
             let mut stream = event_loop.run();
 
-            // Wait for the session to become active
+            // The number of parties is the total weight so the
+            // server does not validate the parameters
             let client_session = if participant.party().is_initiator()
             {
                 SessionHandler::Initiator(SessionInitiator::new(
@@ -111,20 +173,137 @@ macro_rules! frost_dkg_impl {
             let (transport, session) =
                 wait_for_session(&mut stream, client_session).await?;
 
-            let key_gen = dkg::new_driver(
-                transport,
-                session,
-                params,
-                identifiers,
+            let key_gen = dkg::new_weighted_driver(
+                transport, session, threshold, weights,
             )?;
-
-            let (transport, key_share) =
+            let (transport, key_shares) =
                 wait_for_driver(&mut stream, key_gen).await?;
 
             transport.close().await?;
             wait_for_close(&mut stream).await?;
 
-            Ok(key_share)
+            Ok(key_shares)
+        }
+
+        async fn run_dkg(
+            options: SessionOptions,
+            participant: Participant,
+            identifiers: Vec<Identifier>,
+            with_certificate: bool,
+        ) -> crate::Result<(KeyShare, Option<CeremonyCertificate>)> {
+            let params = options.parameters;
+            let started_at = timestamp();
+
+            // FROST requires at least two signers
+            validate_parameters(
+                &params,
+                participant.party().participants().len(),
+                2,
+            )?;
+
+            // Create the client
+            let (client, event_loop) = new_client(options).await?;
+
+            let mut transport: Transport = client.into();
+
+            // Handshake with the server
+            transport.connect().await?;
+
+            // Start the event stream
+            let mut stream = event_loop.run();
+
+            // Wait for the session to become active
+            let client_session = if participant.party().is_initiator()
+            {
+                SessionHandler::Initiator(
+                    SessionInitiator::new(
+                        transport,
+                        participant.party().participants().to_vec(),
+                    )
+                    .with_parameters(params),
+                )
+            } else {
+                SessionHandler::Participant(SessionParticipant::new(
+                    transport,
+                ))
+            };
+
+            let (transport, session) =
+                wait_for_session(&mut stream, client_session).await?;
+
+            let (transport, key_share, certificate) =
+                if with_certificate {
+                    let driver = new_certified_driver(
+                        transport,
+                        session,
+                        params,
+                        identifiers,
+                        &participant,
+                        started_at,
+                    )?;
+                    let (transport, (key_share, certificate)) =
+                        wait_for_driver(&mut stream, driver).await?;
+                    (transport, key_share, Some(certificate))
+                } else {
+                    let key_gen = dkg::new_driver(
+                        transport,
+                        session,
+                        params,
+                        identifiers,
+                    )?;
+                    let (transport, key_share) =
+                        wait_for_driver(&mut stream, key_gen).await?;
+                    (transport, key_share, None)
+                };
+
+            transport.close().await?;
+            wait_for_close(&mut stream).await?;
+
+            Ok((key_share, certificate))
+        }
+
+        /// Create a key generation driver that endorses a
+        /// ceremony certificate once key generation completes.
+        fn new_certified_driver(
+            transport: Transport,
+            session: SessionState,
+            params: Parameters,
+            identifiers: Vec<Identifier>,
+            participant: &Participant,
+            started_at: u64,
+        ) -> crate::Result<CertifiedDriver<DkgDriver, VerifyingKey>> {
+            let session_id = session.session_id;
+            let verifiers = participant.party().verifiers().to_vec();
+            CertifiedDriver::new(
+                transport,
+                session,
+                |party_number| {
+                    Ok(DkgDriver::new(
+                        party_number,
+                        params,
+                        identifiers,
+                    )?)
+                },
+                participant.signing_key().clone(),
+                started_at,
+                move |key_share: &KeyShare| {
+                    let public_key_package = &key_share.1;
+                    let verifying_key = public_key_package
+                        .verifying_key()
+                        .serialize()
+                        .map_err(
+                            polysig_driver::frost::Error::from,
+                        )?;
+                    Ok(Ceremony::new(
+                        $protocol,
+                        session_id,
+                        params,
+                        &verifiers,
+                        verifying_key,
+                        &serde_json::to_vec(public_key_package)?,
+                    )?)
+                },
+            )
         }
     };
 }
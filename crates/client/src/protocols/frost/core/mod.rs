@@ -1,2 +1,6 @@
+pub(crate) mod dealer;
 pub(crate) mod dkg;
+pub(crate) mod preprocess;
+pub(crate) mod refresh;
+pub(crate) mod repair;
 pub(crate) mod sign;
@@ -0,0 +1,75 @@
+//! Nonce pre-processing for FROST.
+macro_rules! frost_preprocess_impl {
+    () => {
+        /// Generate a batch of signing nonces ahead of time.
+        ///
+        /// Commitments for the nonces are exchanged with every
+        /// other participant so that a later signing session
+        /// only needs a single round trip; the returned store
+        /// should be encrypted for storage and each nonce taken
+        /// from the encrypted container so it is used only once.
+        pub async fn preprocess(
+            options: SessionOptions,
+            participant: Participant,
+            identifiers: Vec<Identifier>,
+            key_share: KeyShare,
+            count: u32,
+        ) -> crate::Result<NonceStore> {
+            // Create the client
+            let (client, event_loop) = new_client(options).await?;
+
+            let mut transport: Transport = client.into();
+
+            // Handshake with the server
+            transport.connect().await?;
+
+            // Start the event stream
+            let mut stream = event_loop.run();
+
+            // Wait for the session to become active
+            let client_session = if participant.party().is_initiator()
+            {
+                SessionHandler::Initiator(SessionInitiator::new(
+                    transport,
+                    participant.party().participants().to_vec(),
+                ))
+            } else {
+                SessionHandler::Participant(SessionParticipant::new(
+                    transport,
+                ))
+            };
+
+            let (transport, session) =
+                wait_for_session(&mut stream, client_session).await?;
+
+            let protocol_session_id = session.session_id;
+
+            let driver = preprocess::new_driver(
+                transport,
+                session,
+                identifiers,
+                key_share,
+                count,
+            )?;
+
+            let (mut transport, nonce_store) =
+                wait_for_driver(&mut stream, driver).await?;
+
+            // Close the session and socket
+            if participant.party().is_initiator() {
+                transport.close_session(protocol_session_id).await?;
+                wait_for_session_finish(
+                    &mut stream,
+                    protocol_session_id,
+                )
+                .await?;
+            }
+            transport.close().await?;
+            wait_for_close(&mut stream).await?;
+
+            Ok(nonce_store)
+        }
+    };
+}
+
+pub(crate) use frost_preprocess_impl;
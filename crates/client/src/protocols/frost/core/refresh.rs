@@ -0,0 +1,74 @@
+//! Share refresh for FROST.
+macro_rules! frost_refresh_impl {
+    () => {
+        /// Refresh the key shares for the FROST protocol.
+        ///
+        /// All participants holding a key share must take
+        /// part and the identifiers must match the identifiers
+        /// of the existing key shares.
+        pub async fn refresh(
+            options: SessionOptions,
+            participant: Participant,
+            identifiers: Vec<Identifier>,
+            key_share: KeyShare,
+        ) -> crate::Result<KeyShare> {
+            let params = options.parameters;
+
+            // Create the client
+            let (client, event_loop) = new_client(options).await?;
+
+            let mut transport: Transport = client.into();
+
+            // Handshake with the server
+            transport.connect().await?;
+
+            // Start the event stream
+            let mut stream = event_loop.run();
+
+            // Wait for the session to become active
+            let client_session = if participant.party().is_initiator()
+            {
+                SessionHandler::Initiator(SessionInitiator::new(
+                    transport,
+                    participant.party().participants().to_vec(),
+                ))
+            } else {
+                SessionHandler::Participant(SessionParticipant::new(
+                    transport,
+                ))
+            };
+
+            let (transport, session) =
+                wait_for_session(&mut stream, client_session).await?;
+
+            let protocol_session_id = session.session_id;
+
+            let driver = refresh::new_driver(
+                transport,
+                session,
+                params,
+                identifiers,
+                key_share,
+            )?;
+
+            let (mut transport, key_share) =
+                wait_for_driver(&mut stream, driver).await?;
+
+            // Close the session and socket
+            if participant.party().is_initiator() {
+                transport.close_session(protocol_session_id).await?;
+                wait_for_session_finish(
+                    &mut stream,
+                    protocol_session_id,
+                )
+                .await?;
+            }
+            transport.close().await?;
+            wait_for_close(&mut stream).await?;
+
+            Ok(key_share)
+        }
+    };
+}
+
+pub(crate) use frost_refresh_impl;
@@ -0,0 +1,78 @@
+//! Share repair for FROST.
+macro_rules! frost_repair_impl {
+    () => {
+        /// Repair the key share for a participant.
+        ///
+        /// The session participants are the helpers holding
+        /// key shares (at least the threshold) and the participant
+        /// whose key share is being repaired.
+        ///
+        /// Helpers must provide their key share and receive `None`
+        /// when the repair is complete; the participant being
+        /// repaired must not provide a key share and receives the
+        /// repaired key share.
+        pub async fn repair_share(
+            options: SessionOptions,
+            participant: Participant,
+            identifiers: Vec<Identifier>,
+            repair_identifier: Identifier,
+            key_share: Option<KeyShare>,
+        ) -> crate::Result<Option<KeyShare>> {
+            // Create the client
+            let (client, event_loop) = new_client(options).await?;
+
+            let mut transport: Transport = client.into();
+
+            // Handshake with the server
+            transport.connect().await?;
+
+            // Start the event stream
+            let mut stream = event_loop.run();
+
+            // Wait for the session to become active
+            let client_session = if participant.party().is_initiator()
+            {
+                SessionHandler::Initiator(SessionInitiator::new(
+                    transport,
+                    participant.party().participants().to_vec(),
+                ))
+            } else {
+                SessionHandler::Participant(SessionParticipant::new(
+                    transport,
+                ))
+            };
+
+            let (transport, session) =
+                wait_for_session(&mut stream, client_session).await?;
+
+            let protocol_session_id = session.session_id;
+
+            let driver = repair::new_driver(
+                transport,
+                session,
+                identifiers,
+                repair_identifier,
+                key_share,
+            )?;
+
+            let (mut transport, key_share) =
+                wait_for_driver(&mut stream, driver).await?;
+
+            // Close the session and socket
+            if participant.party().is_initiator() {
+                transport.close_session(protocol_session_id).await?;
+                wait_for_session_finish(
+                    &mut stream,
+                    protocol_session_id,
+                )
+                .await?;
+            }
+            transport.close().await?;
+            wait_for_close(&mut stream).await?;
+
+            Ok(key_share)
+        }
+    };
+}
+
+pub(crate) use frost_repair_impl;
@@ -3,10 +3,231 @@ use async_trait::async_trait;
 use polysig_driver::ProtocolDriver;
 
 use crate::{
-    protocols::{Bridge, Driver},
-    Result, Transport,
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
+    Error, Result, Transport,
 };
-use polysig_protocol::{Event, PartyNumber, SessionState};
+use polysig_protocol::{hex, Event, PartyNumber, SessionState};
+use std::collections::BTreeSet;
+
+/// Signers participating in a FROST signing session.
+///
+/// Each signer is the transport public key of a session
+/// participant and the identifier of the signer's key share
+/// so that any subset of the key holders may sign.
+#[derive(Debug, Clone)]
+pub struct Roster<I> {
+    signers: Vec<(Vec<u8>, I)>,
+}
+
+impl<I> Roster<I>
+where
+    I: Ord + Copy,
+{
+    /// Create a roster from transport public keys
+    /// and key share identifiers.
+    pub fn new(signers: Vec<(Vec<u8>, I)>) -> Self {
+        Self { signers }
+    }
+
+    /// Number of signers in the roster.
+    pub fn len(&self) -> usize {
+        self.signers.len()
+    }
+
+    /// Determine if the roster is empty.
+    pub fn is_empty(&self) -> bool {
+        self.signers.is_empty()
+    }
+
+    /// Identifier for a transport public key.
+    pub fn identifier(&self, public_key: &[u8]) -> Option<I> {
+        self.signers
+            .iter()
+            .find(|(key, _)| key.as_slice() == public_key)
+            .map(|(_, id)| *id)
+    }
+
+    /// Ensure the roster meets the threshold, the public
+    /// keys and identifiers are unique and that the roster
+    /// contains our public key and key share identifier.
+    pub fn validate(
+        &self,
+        threshold: u16,
+        public_key: &[u8],
+        identifier: &I,
+    ) -> Result<()> {
+        if self.signers.len() < threshold as usize {
+            return Err(Error::RosterThreshold(
+                self.signers.len(),
+                threshold,
+            ));
+        }
+
+        let public_keys = self
+            .signers
+            .iter()
+            .map(|(key, _)| key)
+            .collect::<BTreeSet<_>>();
+        let identifiers = self
+            .signers
+            .iter()
+            .map(|(_, id)| id)
+            .collect::<BTreeSet<_>>();
+        if public_keys.len() != self.signers.len()
+            || identifiers.len() != self.signers.len()
+        {
+            return Err(Error::RosterNotUnique);
+        }
+
+        match self.identifier(public_key) {
+            Some(id) if &id == identifier => Ok(()),
+            Some(_) => Err(Error::RosterIdentifier),
+            None => {
+                Err(Error::RosterParticipant(hex::encode(public_key)))
+            }
+        }
+    }
+
+    /// Identifiers ordered by party number in a session.
+    ///
+    /// Every session participant must be in the roster and
+    /// every signer in the roster must be a session participant.
+    pub fn identifiers(
+        &self,
+        session: &SessionState,
+    ) -> Result<Vec<I>> {
+        if let Some((public_key, _)) = self
+            .signers
+            .iter()
+            .find(|(key, _)| session.party_number(key).is_none())
+        {
+            return Err(Error::RosterParticipant(hex::encode(
+                public_key,
+            )));
+        }
+
+        session
+            .all_participants
+            .iter()
+            .map(|public_key| {
+                self.identifier(public_key).ok_or_else(|| {
+                    Error::RosterParticipant(hex::encode(public_key))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Signers holding weighted shares in a FROST signing session.
+///
+/// Each signer is the transport public key of a session
+/// participant and the identifiers of every key share held by
+/// the signer; all signers must use the same roster.
+#[derive(Debug, Clone)]
+pub struct WeightedRoster<I> {
+    signers: Vec<(Vec<u8>, Vec<I>)>,
+}
+
+impl<I> WeightedRoster<I>
+where
+    I: Ord + Copy,
+{
+    /// Create a roster from transport public keys and the
+    /// key share identifiers held by each signer.
+    pub fn new(signers: Vec<(Vec<u8>, Vec<I>)>) -> Self {
+        Self { signers }
+    }
+
+    /// Total weight of the signers in the roster.
+    pub fn weight(&self) -> usize {
+        self.signers.iter().map(|(_, ids)| ids.len()).sum()
+    }
+
+    /// Select the identifiers used to sign so the total weight
+    /// of the selected shares meets the threshold.
+    ///
+    /// Every session participant signs with at least one share
+    /// and further shares are selected in the order of the
+    /// session participants until the threshold is met.
+    ///
+    /// Returns the selected identifiers and the party number
+    /// of the participant that holds each share.
+    pub fn select(
+        &self,
+        session: &SessionState,
+        threshold: u16,
+    ) -> Result<(Vec<I>, Vec<PartyNumber>)> {
+        if let Some((public_key, _)) = self
+            .signers
+            .iter()
+            .find(|(key, _)| session.party_number(key).is_none())
+        {
+            return Err(Error::RosterParticipant(hex::encode(
+                public_key,
+            )));
+        }
+
+        let public_keys = self
+            .signers
+            .iter()
+            .map(|(key, _)| key)
+            .collect::<BTreeSet<_>>();
+        let identifiers = self
+            .signers
+            .iter()
+            .flat_map(|(_, ids)| ids)
+            .collect::<BTreeSet<_>>();
+        if public_keys.len() != self.signers.len()
+            || identifiers.len() != self.weight()
+        {
+            return Err(Error::RosterNotUnique);
+        }
+
+        if self.weight() < threshold as usize {
+            return Err(Error::RosterThreshold(
+                self.weight(),
+                threshold,
+            ));
+        }
+
+        let mut holdings = Vec::new();
+        for public_key in &session.all_participants {
+            let mut ids = self
+                .signers
+                .iter()
+                .find(|(key, _)| key == public_key)
+                .map(|(_, ids)| ids.clone())
+                .filter(|ids| !ids.is_empty())
+                .ok_or_else(|| {
+                    Error::RosterParticipant(hex::encode(public_key))
+                })?;
+            ids.sort();
+            holdings.push(ids);
+        }
+
+        let mut counts = vec![1; holdings.len()];
+        let mut selected = holdings.len();
+        for (index, ids) in holdings.iter().enumerate() {
+            while selected < threshold as usize
+                && counts[index] < ids.len()
+            {
+                counts[index] += 1;
+                selected += 1;
+            }
+        }
+
+        let mut identifiers = Vec::with_capacity(selected);
+        let mut owners = Vec::with_capacity(selected);
+        for (index, ids) in holdings.iter().enumerate() {
+            let party = PartyNumber::new(index as u16 + 1).unwrap();
+            for id in &ids[..counts[index]] {
+                identifiers.push(*id);
+                owners.push(party);
+            }
+        }
+        Ok((identifiers, owners))
+    }
+}
 
 /// FROST signing driver.
 pub struct SignatureDriver<D, O>
@@ -28,12 +249,8 @@ where
         party_number: PartyNumber,
         driver: D,
     ) -> Self {
-        let bridge = Bridge {
-            transport,
-            driver: Some(driver),
-            session,
-            party_number,
-        };
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
         Self {
             bridge,
             marker: std::marker::PhantomData,
@@ -56,13 +273,32 @@ where
         Ok(self.bridge.handle_event(event).await?)
     }
 
-    async fn execute(&mut self) -> Result<()> {
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
         Ok(self.bridge.execute().await?)
     }
 
     fn into_transport(self) -> Transport {
         self.bridge.transport
     }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
 }
 
 impl<D, O> From<SignatureDriver<D, O>> for Transport
@@ -75,8 +311,20 @@ where
 }
 
 macro_rules! frost_sign_impl {
-    () => {
+    ($protocol:literal) => {
+        /// Signers participating in a signing session.
+        pub type Roster =
+            crate::protocols::frost::core::sign::Roster<Identifier>;
+
+        /// Signers holding weighted shares in a signing session.
+        pub type WeightedRoster =
+            crate::protocols::frost::core::sign::WeightedRoster<
+                Identifier,
+            >;
+
         /// Sign a message using the FROST protocol.
+        ///
+        /// Every signer exchanges messages with every other signer.
         pub async fn sign(
             options: SessionOptions,
             participant: Participant,
@@ -85,8 +333,290 @@ macro_rules! frost_sign_impl {
             key_share: KeyShare,
             message: Vec<u8>,
         ) -> crate::Result<Signature> {
+            sign_with_mode(
+                options,
+                participant,
+                identifiers,
+                key_share,
+                message,
+                SignatureMode::Broadcast,
+            )
+            .await
+        }
+
+        /// Sign the digest of a message using the FROST protocol.
+        pub async fn sign_message(
+            options: SessionOptions,
+            participant: Participant,
+            // Identifiers must match the KeyPackage identifiers!
+            identifiers: Vec<Identifier>,
+            key_share: KeyShare,
+            message: &[u8],
+            digest: polysig_driver::digest::DigestAlgorithm,
+        ) -> crate::Result<Signature> {
+            sign(
+                options,
+                participant,
+                identifiers,
+                key_share,
+                digest.digest(message),
+            )
+            .await
+        }
+
+        /// Sign a message with a key share container using
+        /// the FROST protocol.
+        ///
+        /// The policy stored in the container is enforced
+        /// before joining the signing session.
+        pub async fn sign_key_share(
+            options: SessionOptions,
+            participant: Participant,
+            // Identifiers must match the KeyPackage identifiers!
+            identifiers: Vec<Identifier>,
+            key_share: &polysig_driver::KeyShare,
+            message: Vec<u8>,
+        ) -> crate::Result<Signature> {
+            let options = crate::protocols::policy::key_share_policy(
+                options, key_share,
+            );
+            sign(
+                options,
+                participant,
+                identifiers,
+                key_share.try_into()?,
+                message,
+            )
+            .await
+        }
+
+        /// Sign a message using the FROST protocol with
+        /// the given message topology.
+        ///
+        /// Use [SignatureMode::Coordinator] so signers only
+        /// exchange messages with the coordinator which reduces
+        /// the number of messages for large signing groups.
+        pub async fn sign_with_mode(
+            options: SessionOptions,
+            participant: Participant,
+            // Identifiers must match the KeyPackage identifiers!
+            identifiers: Vec<Identifier>,
+            key_share: KeyShare,
+            message: Vec<u8>,
+            mode: SignatureMode,
+        ) -> crate::Result<Signature> {
+            approve(
+                &options,
+                $protocol,
+                participant.party(),
+                &[message.as_slice()],
+                false,
+            )
+            .await?;
+            let min_signers = options.parameters.threshold as u16;
+            sign_session(
+                options,
+                participant,
+                move |transport, session| {
+                    sign::new_driver(
+                        transport,
+                        session,
+                        identifiers,
+                        min_signers,
+                        key_share,
+                        message,
+                        mode,
+                    )
+                },
+            )
+            .await
+        }
+
+        /// Sign a message using the FROST protocol with an
+        /// explicit roster of signers.
+        ///
+        /// Any subset of the key holders that meets the threshold
+        /// may sign; session participants are mapped to the
+        /// identifiers in the roster by party number.
+        pub async fn sign_with_roster(
+            options: SessionOptions,
+            participant: Participant,
+            roster: Roster,
+            key_share: KeyShare,
+            message: Vec<u8>,
+            mode: SignatureMode,
+        ) -> crate::Result<Signature> {
+            roster.validate(
+                *key_share.0.min_signers(),
+                participant.party().public_key(),
+                key_share.0.identifier(),
+            )?;
+            approve(
+                &options,
+                $protocol,
+                participant.party(),
+                &[message.as_slice()],
+                false,
+            )
+            .await?;
+            sign_session(
+                options,
+                participant,
+                move |transport, session| {
+                    let identifiers = roster.identifiers(&session)?;
+                    let signers = identifiers.len() as u16;
+                    sign::new_driver(
+                        transport,
+                        session,
+                        identifiers,
+                        signers,
+                        key_share,
+                        message,
+                        mode,
+                    )
+                },
+            )
+            .await
+        }
+
+        /// Sign a message using the FROST protocol with
+        /// weighted shares.
+        ///
+        /// Identifiers are selected from the roster so the total
+        /// weight of the signing shares meets the threshold of
+        /// the key shares; this participant signs with each
+        /// selected share it holds.
+        pub async fn sign_weighted(
+            options: SessionOptions,
+            participant: Participant,
+            roster: WeightedRoster,
+            key_shares: Vec<KeyShare>,
+            message: Vec<u8>,
+        ) -> crate::Result<Signature> {
+            let threshold = key_shares
+                .first()
+                .map(|key_share| *key_share.0.min_signers())
+                .ok_or(crate::Error::RosterIdentifier)?;
+            approve(
+                &options,
+                $protocol,
+                participant.party(),
+                &[message.as_slice()],
+                false,
+            )
+            .await?;
+            let signatures = sign_session(
+                options,
+                participant,
+                move |transport, session| {
+                    sign::new_weighted_driver(
+                        transport, session, &roster, threshold,
+                        key_shares, message,
+                    )
+                },
+            )
+            .await?;
+            signatures
+                .into_iter()
+                .next()
+                .ok_or(crate::Error::RosterIdentifier)
+        }
+
+        /// Sign multiple messages using the FROST protocol
+        /// in a single session.
+        ///
+        /// The signing rounds for every message are run together
+        /// so commitments for all the messages are exchanged in
+        /// one round; signatures are returned in the same order
+        /// as the messages.
+        pub async fn sign_batch(
+            options: SessionOptions,
+            participant: Participant,
+            // Identifiers must match the KeyPackage identifiers!
+            identifiers: Vec<Identifier>,
+            key_share: KeyShare,
+            messages: Vec<Vec<u8>>,
+            mode: SignatureMode,
+        ) -> crate::Result<Vec<Signature>> {
+            let batch = messages
+                .iter()
+                .map(|m| m.as_slice())
+                .collect::<Vec<_>>();
+            approve(
+                &options,
+                $protocol,
+                participant.party(),
+                &batch,
+                false,
+            )
+            .await?;
             let min_signers = options.parameters.threshold as u16;
+            sign_session(
+                options,
+                participant,
+                move |transport, session| {
+                    sign::new_batch_driver(
+                        transport,
+                        session,
+                        identifiers,
+                        min_signers,
+                        key_share,
+                        messages,
+                        mode,
+                    )
+                },
+            )
+            .await
+        }
+
+        /// Sign a message using a nonce from pre-processing.
+        ///
+        /// Every signer must take the nonce at the same index from
+        /// their nonce store; only signature shares are exchanged
+        /// so signing needs a single round trip.
+        pub async fn sign_preprocessed(
+            options: SessionOptions,
+            participant: Participant,
+            // Identifiers must match the KeyPackage identifiers!
+            identifiers: Vec<Identifier>,
+            key_share: KeyShare,
+            message: Vec<u8>,
+            nonce: PreprocessedNonce,
+        ) -> crate::Result<Signature> {
+            approve(
+                &options,
+                $protocol,
+                participant.party(),
+                &[message.as_slice()],
+                false,
+            )
+            .await?;
+            sign_session(
+                options,
+                participant,
+                move |transport, session| {
+                    sign::new_preprocessed_driver(
+                        transport,
+                        session,
+                        identifiers,
+                        key_share,
+                        message,
+                        nonce,
+                    )
+                },
+            )
+            .await
+        }
 
+        async fn sign_session<D, F>(
+            options: SessionOptions,
+            participant: Participant,
+            new_driver: F,
+        ) -> crate::Result<D::Output>
+        where
+            D: crate::protocols::Driver + Into<Transport>,
+            F: FnOnce(Transport, SessionState) -> crate::Result<D>,
+        {
             // Create the client
             let (client, event_loop) = new_client(options).await?;
 
@@ -116,17 +646,10 @@ macro_rules! frost_sign_impl {
 
             let protocol_session_id = session.session_id;
 
-            // Wait for message to be signed
-            let driver = sign::new_driver(
-                transport,
-                session,
-                identifiers,
-                min_signers,
-                key_share,
-                message,
-            )?;
+            // Wait for the messages to be signed
+            let driver = new_driver(transport, session)?;
 
-            let (mut transport, signature) =
+            let (mut transport, output) =
                 wait_for_driver(&mut stream, driver).await?;
 
             // Close the session and socket
@@ -141,7 +664,7 @@ macro_rules! frost_sign_impl {
             transport.close().await?;
             wait_for_close(&mut stream).await?;
 
-            Ok(signature)
+            Ok(output)
         }
     };
 }
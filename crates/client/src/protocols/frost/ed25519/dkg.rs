@@ -5,6 +5,7 @@ use polysig_protocol::{hex, Parameters, SessionState};
 use polysig_driver::{
     frost::ed25519::{DkgDriver as FrostDriver, KeyShare},
     frost_ed25519::Identifier,
+    WeightedDriver, Weights,
 };
 
 /// Distributed key generation driver for FROST Ed25519
@@ -13,6 +14,14 @@ pub type DkgDriver = crate::protocols::frost::core::dkg::DkgDriver<
     KeyShare,
 >;
 
+/// Distributed key generation driver for a weighted party
+/// for FROST Ed25519.
+pub type WeightedDkgDriver =
+    crate::protocols::frost::core::dkg::DkgDriver<
+        WeightedDriver<FrostDriver>,
+        Vec<KeyShare>,
+    >;
+
 /// Create a new FROST Ed25519 DKG driver.
 pub fn new_driver(
     transport: Transport,
@@ -32,3 +41,55 @@ pub fn new_driver(
 
     Ok(DkgDriver::new(transport, session, party_number, driver))
 }
+
+/// Create a new FROST Ed25519 DKG driver for a weighted party.
+///
+/// A key share is generated for every share held by the party;
+/// the threshold is the total weight required to sign.
+pub fn new_weighted_driver(
+    transport: Transport,
+    session: SessionState,
+    threshold: u16,
+    weights: Weights,
+) -> Result<WeightedDkgDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    if weights.len() != session.all_participants.len() {
+        return Err(polysig_driver::Error::ParticipantWeightLength(
+            session.all_participants.len(),
+            weights.len(),
+        )
+        .into());
+    }
+
+    let params = Parameters {
+        parties: weights.total(),
+        threshold,
+    };
+    let identifiers = (1..=params.parties)
+        .map(Identifier::try_from)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut drivers = Vec::new();
+    for share in weights.shares(party_number) {
+        drivers.push((
+            share,
+            FrostDriver::new(share, params, identifiers.clone())?,
+        ));
+    }
+    let driver =
+        WeightedDriver::new(party_number, weights.owners(), drivers)?;
+
+    Ok(WeightedDkgDriver::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    ))
+}
@@ -0,0 +1,41 @@
+//! Share refresh for FROST Ed25519.
+use crate::{Error, NetworkTransport, Result, Transport};
+use polysig_protocol::{hex, Parameters, SessionState};
+
+use polysig_driver::{
+    frost::ed25519::{KeyShare, RefreshDriver as FrostDriver},
+    frost_ed25519::Identifier,
+};
+
+/// Share refresh driver for FROST Ed25519.
+pub type RefreshDriver =
+    crate::protocols::frost::core::dkg::DkgDriver<
+        FrostDriver,
+        KeyShare,
+    >;
+
+/// Create a new FROST Ed25519 share refresh driver.
+pub fn new_driver(
+    transport: Transport,
+    session: SessionState,
+    params: Parameters,
+    identifiers: Vec<Identifier>,
+    key_share: KeyShare,
+) -> Result<RefreshDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new(
+        party_number,
+        params,
+        identifiers,
+        key_share,
+    )?;
+
+    Ok(RefreshDriver::new(transport, session, party_number, driver))
+}
@@ -0,0 +1,95 @@
+//! Distributed key generation for FROST Ed448.
+use crate::{Error, NetworkTransport, Result, Transport};
+use polysig_protocol::{hex, Parameters, SessionState};
+
+use polysig_driver::{
+    frost::ed448::{DkgDriver as FrostDriver, KeyShare},
+    frost_ed448::Identifier,
+    WeightedDriver, Weights,
+};
+
+/// Distributed key generation driver for FROST Ed448
+pub type DkgDriver = crate::protocols::frost::core::dkg::DkgDriver<
+    FrostDriver,
+    KeyShare,
+>;
+
+/// Distributed key generation driver for a weighted party
+/// for FROST Ed448.
+pub type WeightedDkgDriver =
+    crate::protocols::frost::core::dkg::DkgDriver<
+        WeightedDriver<FrostDriver>,
+        Vec<KeyShare>,
+    >;
+
+/// Create a new FROST Ed448 DKG driver.
+pub fn new_driver(
+    transport: Transport,
+    session: SessionState,
+    params: Parameters,
+    identifiers: Vec<Identifier>,
+) -> Result<DkgDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new(party_number, params, identifiers)?;
+
+    Ok(DkgDriver::new(transport, session, party_number, driver))
+}
+
+/// Create a new FROST Ed448 DKG driver for a weighted party.
+///
+/// A key share is generated for every share held by the party;
+/// the threshold is the total weight required to sign.
+pub fn new_weighted_driver(
+    transport: Transport,
+    session: SessionState,
+    threshold: u16,
+    weights: Weights,
+) -> Result<WeightedDkgDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    if weights.len() != session.all_participants.len() {
+        return Err(polysig_driver::Error::ParticipantWeightLength(
+            session.all_participants.len(),
+            weights.len(),
+        )
+        .into());
+    }
+
+    let params = Parameters {
+        parties: weights.total(),
+        threshold,
+    };
+    let identifiers = (1..=params.parties)
+        .map(Identifier::try_from)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut drivers = Vec::new();
+    for share in weights.shares(party_number) {
+        drivers.push((
+            share,
+            FrostDriver::new(share, params, identifiers.clone())?,
+        ));
+    }
+    let driver =
+        WeightedDriver::new(party_number, weights.owners(), drivers)?;
+
+    Ok(WeightedDkgDriver::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    ))
+}
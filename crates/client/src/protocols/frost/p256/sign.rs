@@ -0,0 +1,204 @@
+//! Signature generation for FROST P-256.
+use polysig_driver::{
+    frost::p256::{
+        KeyShare, PreprocessedNonce, SignatureDriver as FrostDriver,
+        SignatureMode,
+    },
+    frost_p256::{Identifier, Signature},
+    BatchDriver, WeightedDriver,
+};
+
+use crate::{
+    protocols::frost::core::sign::WeightedRoster, Error,
+    NetworkTransport, Result, Transport,
+};
+use polysig_protocol::{hex, PartyNumber, SessionState};
+
+/// Signature generation driver for FROST P-256.
+pub type SignatureDriver =
+    crate::protocols::frost::core::sign::SignatureDriver<
+        FrostDriver,
+        Signature,
+    >;
+
+/// Batch signature generation driver for FROST P-256.
+pub type SignatureBatchDriver =
+    crate::protocols::frost::core::sign::SignatureDriver<
+        BatchDriver<FrostDriver>,
+        Vec<Signature>,
+    >;
+
+/// Signature generation driver for a weighted party
+/// for FROST P-256.
+pub type WeightedSignatureDriver =
+    crate::protocols::frost::core::sign::SignatureDriver<
+        WeightedDriver<FrostDriver>,
+        Vec<Signature>,
+    >;
+
+/// Create a new FROST P-256 signature driver.
+pub fn new_driver(
+    transport: Transport,
+    session: SessionState,
+    identifiers: Vec<Identifier>,
+    min_signers: u16,
+    key_share: KeyShare,
+    message: Vec<u8>,
+    mode: SignatureMode,
+) -> Result<SignatureDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new_with_mode(
+        party_number,
+        identifiers,
+        min_signers,
+        key_share,
+        message,
+        mode,
+    )?;
+
+    Ok(SignatureDriver::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    ))
+}
+
+/// Create a new FROST P-256 batch signature driver.
+///
+/// A signature driver is created for each message and the
+/// messages for all drivers are multiplexed over the session.
+pub fn new_batch_driver(
+    transport: Transport,
+    session: SessionState,
+    identifiers: Vec<Identifier>,
+    min_signers: u16,
+    key_share: KeyShare,
+    messages: Vec<Vec<u8>>,
+    mode: SignatureMode,
+) -> Result<SignatureBatchDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let mut drivers = Vec::with_capacity(messages.len());
+    for message in messages {
+        drivers.push(FrostDriver::new_with_mode(
+            party_number,
+            identifiers.clone(),
+            min_signers,
+            key_share.clone(),
+            message,
+            mode,
+        )?);
+    }
+
+    Ok(SignatureBatchDriver::new(
+        transport,
+        session,
+        party_number,
+        BatchDriver::new(drivers),
+    ))
+}
+
+/// Create a new FROST P-256 signature driver using a nonce
+/// from pre-processing.
+pub fn new_preprocessed_driver(
+    transport: Transport,
+    session: SessionState,
+    identifiers: Vec<Identifier>,
+    key_share: KeyShare,
+    message: Vec<u8>,
+    nonce: PreprocessedNonce,
+) -> Result<SignatureDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new_preprocessed(
+        party_number,
+        identifiers,
+        key_share,
+        message,
+        nonce,
+    )?;
+
+    Ok(SignatureDriver::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    ))
+}
+
+/// Create a new FROST P-256 signature driver for a
+/// weighted party.
+///
+/// Identifiers are selected from the roster to meet the
+/// threshold and a signature driver is created for each
+/// selected share held by this party.
+pub fn new_weighted_driver(
+    transport: Transport,
+    session: SessionState,
+    roster: &WeightedRoster<Identifier>,
+    threshold: u16,
+    key_shares: Vec<KeyShare>,
+    message: Vec<u8>,
+) -> Result<WeightedSignatureDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let (identifiers, owners) = roster.select(&session, threshold)?;
+    let min_signers = identifiers.len() as u16;
+
+    let mut drivers = Vec::new();
+    for (index, id) in identifiers.iter().enumerate() {
+        if owners[index] != party_number {
+            continue;
+        }
+        let key_share = key_shares
+            .iter()
+            .find(|k| k.0.identifier() == id)
+            .cloned()
+            .ok_or(Error::RosterIdentifier)?;
+        let share = PartyNumber::new(index as u16 + 1).unwrap();
+        drivers.push((
+            share,
+            FrostDriver::new(
+                share,
+                identifiers.clone(),
+                min_signers,
+                key_share,
+                message.clone(),
+            )?,
+        ));
+    }
+    let driver = WeightedDriver::new(party_number, owners, drivers)?;
+
+    Ok(WeightedSignatureDriver::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    ))
+}
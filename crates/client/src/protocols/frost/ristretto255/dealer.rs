@@ -0,0 +1,42 @@
+//! Trusted dealer key generation for FROST Ristretto255.
+use crate::{Error, NetworkTransport, Result, Transport};
+use polysig_protocol::{hex, Parameters, SessionState};
+
+use polysig_driver::{
+    frost::ristretto255::{DealerDriver as FrostDriver, KeyShare},
+    frost_ristretto255::{Identifier, SigningKey},
+};
+
+/// Trusted dealer key generation driver for FROST Ristretto255.
+pub type DealerDriver = crate::protocols::frost::core::dkg::DkgDriver<
+    FrostDriver,
+    KeyShare,
+>;
+
+/// Create a new FROST Ristretto255 trusted dealer driver.
+pub fn new_driver(
+    transport: Transport,
+    session: SessionState,
+    params: Parameters,
+    identifiers: Vec<Identifier>,
+    dealer: Identifier,
+    signing_key: Option<SigningKey>,
+) -> Result<DealerDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new(
+        party_number,
+        params,
+        identifiers,
+        dealer,
+        signing_key,
+    )?;
+
+    Ok(DealerDriver::new(transport, session, party_number, driver))
+}
@@ -0,0 +1,43 @@
+//! Driver for the FROST Ristretto255 protocol.
+use polysig_driver::{
+    certificate::{Ceremony, CeremonyCertificate},
+    frost::ristretto255::{
+        DkgDriver, KeyShare, Participant, Signature, VerifyingKey,
+    },
+    frost_ristretto255::{Identifier, SigningKey},
+};
+
+use crate::{
+    new_client,
+    protocols::certificate::CertifiedDriver,
+    protocols::frost::core::{
+        dealer::frost_dealer_impl, dkg::frost_dkg_impl,
+        preprocess::frost_preprocess_impl,
+        refresh::frost_refresh_impl, repair::frost_repair_impl,
+        sign::frost_sign_impl,
+    },
+    protocols::policy::approve,
+    protocols::{timestamp, validate_parameters},
+    wait_for_close, wait_for_driver, wait_for_session,
+    wait_for_session_finish, NetworkTransport, SessionHandler,
+    SessionInitiator, SessionOptions, SessionParticipant, Transport,
+};
+use polysig_protocol::{Parameters, SessionState};
+
+pub use polysig_driver::frost::ristretto255::{
+    NonceStore, PreprocessedNonce, SignatureMode,
+};
+
+mod dealer;
+mod dkg;
+mod preprocess;
+mod refresh;
+mod repair;
+mod sign;
+
+frost_dkg_impl!("frost-ristretto255");
+frost_dealer_impl!();
+frost_preprocess_impl!();
+frost_sign_impl!("frost-ristretto255");
+frost_refresh_impl!();
+frost_repair_impl!();
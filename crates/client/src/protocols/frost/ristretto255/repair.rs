@@ -0,0 +1,40 @@
+//! Share repair for FROST Ristretto255.
+use crate::{Error, NetworkTransport, Result, Transport};
+use polysig_protocol::{hex, SessionState};
+
+use polysig_driver::{
+    frost::ristretto255::{KeyShare, RepairDriver as FrostDriver},
+    frost_ristretto255::Identifier,
+};
+
+/// Share repair driver for FROST Ristretto255.
+pub type RepairDriver = crate::protocols::frost::core::dkg::DkgDriver<
+    FrostDriver,
+    Option<KeyShare>,
+>;
+
+/// Create a new FROST Ristretto255 share repair driver.
+pub fn new_driver(
+    transport: Transport,
+    session: SessionState,
+    identifiers: Vec<Identifier>,
+    repair_identifier: Identifier,
+    key_share: Option<KeyShare>,
+) -> Result<RepairDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new(
+        party_number,
+        identifiers,
+        repair_identifier,
+        key_share,
+    )?;
+
+    Ok(RepairDriver::new(transport, session, party_number, driver))
+}
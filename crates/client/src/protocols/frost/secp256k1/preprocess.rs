@@ -0,0 +1,48 @@
+//! Nonce pre-processing for FROST Secp256k1.
+use crate::{Error, NetworkTransport, Result, Transport};
+use polysig_protocol::{hex, SessionState};
+
+use polysig_driver::{
+    frost::secp256k1::{
+        KeyShare, NonceStore, PreprocessDriver as FrostDriver,
+    },
+    frost_secp256k1::Identifier,
+};
+
+/// Nonce pre-processing driver for FROST Secp256k1.
+pub type PreprocessDriver =
+    crate::protocols::frost::core::dkg::DkgDriver<
+        FrostDriver,
+        NonceStore,
+    >;
+
+/// Create a new FROST Secp256k1 nonce pre-processing driver.
+pub fn new_driver(
+    transport: Transport,
+    session: SessionState,
+    identifiers: Vec<Identifier>,
+    key_share: KeyShare,
+    count: u32,
+) -> Result<PreprocessDriver> {
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = FrostDriver::new(
+        party_number,
+        identifiers,
+        key_share,
+        count,
+    )?;
+
+    Ok(PreprocessDriver::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    ))
+}
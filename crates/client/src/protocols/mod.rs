@@ -1,22 +1,72 @@
-use crate::{Client, ClientOptions, EventLoop, Result, Transport};
+use crate::{
+    Attestation, CancellationToken, Client, ClientOptions, EventLoop,
+    KeepaliveOptions, ProgressSender, Result, Transport,
+};
 use async_trait::async_trait;
-use polysig_protocol::{hex, Event, Keypair, Parameters};
+use polysig_driver::KeySharePolicy;
+use polysig_protocol::{
+    snow::params::NoiseParams, Event, Keypair, Parameters,
+};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 mod bridge;
 pub mod meeting;
+mod plugin;
+mod policy;
+mod report;
 mod session;
+#[cfg(any(
+    all(feature = "cggmp", feature = "ecdsa"),
+    feature = "frost-ed25519",
+    feature = "frost-secp256k1-tr"
+))]
+mod signer;
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+mod transfer;
+
+#[cfg(feature = "bls")]
+pub mod bls;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+mod certificate;
 
 #[cfg(feature = "cggmp")]
 pub mod cggmp;
 
+#[cfg(feature = "elgamal")]
+pub mod elgamal;
+
 #[cfg(feature = "frost")]
 pub mod frost;
 
+#[cfg(feature = "musig2")]
+pub mod musig2;
+
 pub(crate) use bridge::Bridge;
 pub use bridge::{
     wait_for_close, wait_for_driver, wait_for_session_finish,
 };
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub use certificate::CertifiedDriver;
+pub use plugin::{run_protocol, PluginDriver};
+pub use policy::{
+    AllowAll, PolicyDecision, SessionMetadata, SigningContext,
+    SigningPolicy, SigningRequest,
+};
+pub use polysig_driver::digest::DigestAlgorithm;
+pub use report::{
+    Blame, CeremonyReport, PartyStatus, RoundReport, RoundStatus,
+};
+#[cfg(all(feature = "cggmp", feature = "ecdsa"))]
+pub use signer::CggmpSigner;
+#[cfg(feature = "frost-ed25519")]
+pub use signer::FrostEd25519Signer;
+#[cfg(feature = "frost-secp256k1-tr")]
+pub use signer::FrostTaprootSigner;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub use transfer::{receive_share, transfer_share, TransferDriver};
 
 pub use session::{
     wait_for_session, SessionEventHandler, SessionHandler,
@@ -25,6 +75,7 @@ pub use session::{
 
 /// Server options.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct ServerOptions {
     /// URL for the server.
@@ -37,6 +88,7 @@ pub struct ServerOptions {
 
 /// Options used to drive a session to completion.
 #[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct SessionOptions {
     /// Keypair for the participant.
@@ -45,6 +97,138 @@ pub struct SessionOptions {
     pub server: ServerOptions,
     /// Parameters for key generation.
     pub parameters: Parameters,
+    /// Policy consulted before signing.
+    ///
+    /// When no policy is assigned every signature is approved.
+    #[serde(skip)]
+    pub policy: Option<Arc<dyn SigningPolicy>>,
+    /// Policy stored with the key share.
+    ///
+    /// Checked before the signing policy is consulted.
+    #[serde(default)]
+    pub key_share_policy: Option<KeySharePolicy>,
+    /// Derivation path and chain for a signature.
+    #[serde(default)]
+    pub signing_context: Option<SigningContext>,
+    /// Sender notified as the ceremony progresses.
+    #[serde(skip)]
+    pub progress: Option<ProgressSender>,
+    /// Token used to cancel the ceremony.
+    #[serde(skip)]
+    pub cancel: Option<CancellationToken>,
+    /// Attestation exchanged with peers when the session starts.
+    #[serde(skip)]
+    pub attestation: Option<Arc<dyn Attestation>>,
+    /// Timeout in seconds for each round of the ceremony.
+    #[serde(default)]
+    pub round_timeout: Option<u32>,
+    /// Keepalive for the connection to the server.
+    #[serde(default)]
+    pub keepalive: Option<KeepaliveOptions>,
+}
+
+impl SessionOptions {
+    /// Create a builder for session options.
+    pub fn builder(
+        keypair: Keypair,
+        server: ServerOptions,
+        parameters: Parameters,
+    ) -> SessionOptionsBuilder {
+        SessionOptionsBuilder {
+            options: SessionOptions {
+                keypair,
+                server,
+                parameters,
+                policy: None,
+                key_share_policy: None,
+                signing_context: None,
+                progress: None,
+                cancel: None,
+                attestation: None,
+                round_timeout: None,
+                keepalive: None,
+            },
+        }
+    }
+}
+
+/// Builder for session options.
+pub struct SessionOptionsBuilder {
+    options: SessionOptions,
+}
+
+impl SessionOptionsBuilder {
+    /// Set the policy consulted before signing.
+    pub fn policy(mut self, policy: Arc<dyn SigningPolicy>) -> Self {
+        self.options.policy = Some(policy);
+        self
+    }
+
+    /// Set the policy stored with the key share.
+    pub fn key_share_policy(
+        mut self,
+        policy: KeySharePolicy,
+    ) -> Self {
+        self.options.key_share_policy = Some(policy);
+        self
+    }
+
+    /// Set the derivation path and chain for a signature.
+    pub fn signing_context(
+        mut self,
+        context: SigningContext,
+    ) -> Self {
+        self.options.signing_context = Some(context);
+        self
+    }
+
+    /// Set the sender notified as the ceremony progresses.
+    pub fn progress(mut self, progress: ProgressSender) -> Self {
+        self.options.progress = Some(progress);
+        self
+    }
+
+    /// Set the token used to cancel the ceremony.
+    pub fn cancel(mut self, cancel: CancellationToken) -> Self {
+        self.options.cancel = Some(cancel);
+        self
+    }
+
+    /// Set the attestation exchanged with peers.
+    pub fn attestation(
+        mut self,
+        attestation: Arc<dyn Attestation>,
+    ) -> Self {
+        self.options.attestation = Some(attestation);
+        self
+    }
+
+    /// Set the timeout in seconds for each round.
+    ///
+    /// The ceremony fails with the parties that have not
+    /// sent a message when a round does not complete in time.
+    pub fn round_timeout(mut self, seconds: u32) -> Self {
+        self.options.round_timeout = Some(seconds);
+        self
+    }
+
+    /// Set the keepalive for the connection to the server.
+    ///
+    /// The ceremony fails when no message is received from
+    /// the server within the idle timeout.
+    pub fn keepalive(mut self, keepalive: KeepaliveOptions) -> Self {
+        self.options.keepalive = Some(keepalive);
+        self
+    }
+
+    /// Validate the settings and build the session options.
+    pub fn build(self) -> Result<SessionOptions> {
+        self.options.parameters.validate()?;
+        if let Some(pattern) = &self.options.server.pattern {
+            pattern.parse::<NoiseParams>()?;
+        }
+        Ok(self.options)
+    }
 }
 
 /// Drives a protocol to completion bridging between
@@ -61,26 +245,128 @@ pub trait Driver {
     ) -> Result<Option<Self::Output>>;
 
     /// Start running the protocol.
-    async fn execute(&mut self) -> Result<()>;
+    ///
+    /// Yields the output when the protocol completes
+    /// without receiving any messages.
+    async fn execute(&mut self) -> Result<Option<Self::Output>>;
 
     /// Consume this driver into the underlying transport.
     fn into_transport(self) -> Transport;
+
+    /// Continue with a reconnected transport after the
+    /// connection for the current transport was lost.
+    ///
+    /// Messages for the current round are sent again using
+    /// the new transport and peers discard any messages they
+    /// have already received; the new transport must have
+    /// channels with the peers in the session.
+    ///
+    /// Returns the previous transport.
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport>;
+
+    /// Report on the progress of the ceremony.
+    ///
+    /// Used to describe the state of the ceremony when
+    /// the driver fails.
+    fn report(&self) -> CeremonyReport {
+        Default::default()
+    }
+
+    /// Status of the current round.
+    ///
+    /// Used to enforce the round timeout and report the
+    /// parties that have not sent a message for the round.
+    fn round_status(&self) -> Option<RoundStatus> {
+        None
+    }
+
+    /// Timeout for each round of the ceremony.
+    ///
+    /// When the timeout elapses before the driver moves to
+    /// the next round the ceremony fails with
+    /// [Error::RoundTimeout](crate::Error::RoundTimeout).
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
 }
 
 /// Create a new client using the provided session options.
 pub(crate) async fn new_client(
     options: SessionOptions,
 ) -> Result<(Client, EventLoop)> {
+    if options.cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+        return Err(crate::Error::Cancelled);
+    }
     let server_url = options.server.server_url;
+    #[cfg(all(
+        feature = "record",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    let recorder = crate::record::Recorder::from_env(
+        options.keypair.public_key(),
+    )?;
     let options = ClientOptions {
         keypair: Some(options.keypair),
         server_public_key: Some(options.server.server_public_key),
+        server_trust: None,
         pattern: options.server.pattern,
+        session_token: None,
+        progress: options.progress,
+        cancel: options.cancel,
+        attestation: options.attestation,
+        round_timeout: options
+            .round_timeout
+            .map(|secs| std::time::Duration::from_secs(secs.into())),
+        keepalive: options.keepalive,
+        #[cfg(not(all(
+            target_arch = "wasm32",
+            target_os = "unknown"
+        )))]
+        direct: None,
+        #[cfg(all(
+            feature = "record",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        recorder,
+        #[cfg(all(
+            feature = "journal",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        journal: None,
+        #[cfg(all(
+            feature = "faults",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        faults: None,
+        #[cfg(all(
+            feature = "http2",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        http2: None,
     };
     let url = options.url(&server_url);
     Ok(Client::new(&url, options).await?)
 }
 
-pub(crate) fn public_key_to_str(public_key: &[u8]) -> String {
-    hex::encode(&public_key[0..6])
+/// Validate the session parameters for the participants
+/// and the minimum threshold supported by a protocol.
+///
+/// Called before connecting so that invalid parameters are
+/// rejected before any protocol rounds start.
+pub(crate) fn validate_parameters(
+    parameters: &Parameters,
+    participants: usize,
+    min_threshold: u16,
+) -> Result<()> {
+    parameters
+        .validate_session(participants, min_threshold)
+        .map_err(crate::Error::InvalidParameters)
+}
+
+/// Seconds since the UNIX epoch.
+pub(crate) fn timestamp() -> u64 {
+    crate::timestamp_millis() / 1000
 }
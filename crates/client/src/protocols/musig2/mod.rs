@@ -0,0 +1,100 @@
+//! Driver for the MuSig2 protocol.
+use polysig_driver::{
+    digest::DigestAlgorithm,
+    musig2::{KeyMode, Participant, Signature},
+};
+
+use crate::{
+    new_client, protocols::policy::approve, wait_for_close,
+    wait_for_driver, wait_for_session, wait_for_session_finish,
+    SessionHandler, SessionInitiator, SessionOptions,
+    SessionParticipant, Transport,
+};
+
+mod sign;
+
+pub use sign::SignatureDriver;
+
+/// Sign the digest of a message using the MuSig2 protocol.
+pub async fn sign_message(
+    options: SessionOptions,
+    participant: Participant,
+    message: &[u8],
+    digest: DigestAlgorithm,
+    mode: KeyMode,
+) -> crate::Result<Signature> {
+    sign(options, participant, digest.digest(message), mode).await
+}
+
+/// Sign a message using the MuSig2 protocol.
+///
+/// Every participant in the session must sign; the
+/// signature verifies against the aggregated public key
+/// of the participant verifying keys.
+pub async fn sign(
+    options: SessionOptions,
+    participant: Participant,
+    message: Vec<u8>,
+    mode: KeyMode,
+) -> crate::Result<Signature> {
+    approve(
+        &options,
+        "musig2",
+        participant.party(),
+        &[message.as_slice()],
+        false,
+    )
+    .await?;
+
+    // Create the client
+    let (client, event_loop) = new_client(options).await?;
+
+    let mut transport: Transport = client.into();
+
+    // Handshake with the server
+    transport.connect().await?;
+
+    // Start the event stream
+    let mut stream = event_loop.run();
+
+    // Wait for the session to become active
+    let client_session = if participant.party().is_initiator() {
+        SessionHandler::Initiator(SessionInitiator::new(
+            transport,
+            participant.party().participants().to_vec(),
+        ))
+    } else {
+        SessionHandler::Participant(SessionParticipant::new(
+            transport,
+        ))
+    };
+
+    let (transport, session) =
+        wait_for_session(&mut stream, client_session).await?;
+
+    let protocol_session_id = session.session_id;
+
+    // Wait for the message to be signed
+    let driver = SignatureDriver::new(
+        transport,
+        session,
+        participant.signing_key().clone(),
+        participant.party().verifiers().to_vec(),
+        message,
+        mode,
+    )?;
+
+    let (mut transport, signature) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    // Close the session and socket
+    if participant.party().is_initiator() {
+        transport.close_session(protocol_session_id).await?;
+        wait_for_session_finish(&mut stream, protocol_session_id)
+            .await?;
+    }
+    transport.close().await?;
+    wait_for_close(&mut stream).await?;
+
+    Ok(signature)
+}
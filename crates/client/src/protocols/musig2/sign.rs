@@ -0,0 +1,93 @@
+//! Signature generation for MuSig2.
+use crate::{
+    protocols::{Bridge, CeremonyReport, Driver, RoundStatus},
+    Error, NetworkTransport, Result, Transport,
+};
+use async_trait::async_trait;
+use polysig_protocol::{hex, Event, SessionState};
+
+use polysig_driver::musig2::{
+    KeyMode, Signature, SignatureDriver as Musig2Driver, SigningKey,
+    VerifyingKey,
+};
+
+/// MuSig2 signing driver.
+pub struct SignatureDriver {
+    bridge: Bridge<Musig2Driver>,
+}
+
+impl SignatureDriver {
+    /// Create a new MuSig2 signature driver.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        signing_key: SigningKey,
+        verifiers: Vec<VerifyingKey>,
+        message: Vec<u8>,
+        mode: KeyMode,
+    ) -> Result<Self> {
+        let party_number = session
+            .party_number(transport.public_key())
+            .ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(
+                    transport.public_key(),
+                ))
+            })?;
+
+        let driver = Musig2Driver::new(
+            party_number,
+            signing_key,
+            verifiers,
+            message,
+            mode,
+        )?;
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl Driver for SignatureDriver {
+    type Output = Signature;
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl From<SignatureDriver> for Transport {
+    fn from(value: SignatureDriver) -> Self {
+        value.bridge.transport
+    }
+}
@@ -0,0 +1,168 @@
+//! Run threshold protocols registered as plugins.
+//!
+//! Third party protocols implement [ProtocolDriver] and register
+//! the driver with a [Protocol] so they can be run to completion
+//! without changes to this crate.
+use async_trait::async_trait;
+use polysig_driver::{
+    PartyOptions, PluginDriver as ProtocolPlugin, Protocol,
+    ProtocolDriver,
+};
+use polysig_protocol::{hex, Event, PartyNumber, SessionState};
+
+use crate::{
+    protocols::{
+        new_client, wait_for_close, wait_for_driver,
+        wait_for_session, wait_for_session_finish, Bridge,
+        CeremonyReport, Driver, RoundStatus, SessionHandler,
+        SessionInitiator, SessionOptions, SessionParticipant,
+    },
+    Error, NetworkTransport, Result, Transport,
+};
+
+/// Drives a protocol registered as a plugin.
+pub struct PluginDriver<P: Protocol> {
+    bridge: Bridge<ProtocolPlugin<P>>,
+}
+
+impl<P: Protocol> PluginDriver<P> {
+    /// Create a new plugin driver.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        party_number: PartyNumber,
+        driver: P::Driver,
+    ) -> Self {
+        let bridge = Bridge::new(
+            transport,
+            ProtocolPlugin::new(driver),
+            session,
+            party_number,
+        );
+        Self { bridge }
+    }
+}
+
+#[async_trait]
+impl<P> Driver for PluginDriver<P>
+where
+    P: Protocol + Send + Sync,
+    P::Driver: Send + Sync,
+    <P::Driver as ProtocolDriver>::Output: Send + Sync,
+{
+    type Output = <P::Driver as ProtocolDriver>::Output;
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl<P: Protocol> From<PluginDriver<P>> for Transport {
+    fn from(value: PluginDriver<P>) -> Self {
+        value.bridge.transport
+    }
+}
+
+/// Run a protocol registered as a plugin to completion.
+///
+/// Once the session is active the driver for the protocol
+/// is created from our party number and the session state.
+pub async fn run_protocol<P, V, F>(
+    options: SessionOptions,
+    party: &PartyOptions<V>,
+    new_driver: F,
+) -> Result<<P::Driver as ProtocolDriver>::Output>
+where
+    P: Protocol + Send + Sync,
+    P::Driver: Send + Sync,
+    <P::Driver as ProtocolDriver>::Output: Send + Sync,
+    F: FnOnce(PartyNumber, &SessionState) -> Result<P::Driver>,
+{
+    // Create the client
+    let (client, event_loop) = new_client(options).await?;
+
+    let mut transport: Transport = client.into();
+
+    // Handshake with the server
+    transport.connect().await?;
+
+    // Start the event stream
+    let mut stream = event_loop.run();
+
+    // Wait for the session to become active
+    let client_session = if party.is_initiator() {
+        SessionHandler::Initiator(SessionInitiator::new(
+            transport,
+            party.participants().to_vec(),
+        ))
+    } else {
+        SessionHandler::Participant(SessionParticipant::new(
+            transport,
+        ))
+    };
+
+    let (transport, session) =
+        wait_for_session(&mut stream, client_session).await?;
+
+    let protocol_session_id = session.session_id;
+
+    let party_number = session
+        .party_number(transport.public_key())
+        .ok_or_else(|| {
+        Error::NotSessionParticipant(hex::encode(
+            transport.public_key(),
+        ))
+    })?;
+
+    let driver = new_driver(party_number, &session)?;
+    let driver = PluginDriver::<P>::new(
+        transport,
+        session,
+        party_number,
+        driver,
+    );
+
+    let (mut transport, output) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    // Close the session and socket
+    if party.is_initiator() {
+        transport.close_session(protocol_session_id).await?;
+        wait_for_session_finish(&mut stream, protocol_session_id)
+            .await?;
+    }
+    transport.close().await?;
+    wait_for_close(&mut stream).await?;
+
+    Ok(output)
+}
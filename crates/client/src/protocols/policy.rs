@@ -0,0 +1,167 @@
+//! Policy hooks evaluated before participating in a signature.
+//!
+//! Assign a [SigningPolicy] to [SessionOptions::policy] and the
+//! high-level `sign` functions will ask the policy for a decision
+//! before joining a signing session; when no policy is assigned
+//! every request is approved.
+//!
+//! When [SessionOptions::key_share_policy] is assigned the rules
+//! stored with the key share are checked against the
+//! [SigningContext] first and a signature that breaks a rule
+//! is rejected without consulting the signing policy. The
+//! `sign_key_share` functions read the rules from the key
+//! share container so callers do not need to assign them.
+use async_trait::async_trait;
+use polysig_driver::{KeyShare, PartyOptions};
+use polysig_protocol::Parameters;
+use serde::{Deserialize, Serialize};
+
+use crate::{protocols::timestamp, Error, Result, SessionOptions};
+
+/// Describes what a signature is for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct SigningContext {
+    /// Derivation path when signing with a derived key.
+    pub derivation_path: Option<String>,
+    /// Chain identifier such as `eip155:1`.
+    pub chain: Option<String>,
+}
+
+/// Details of a signature presented to a signing policy.
+#[derive(Debug)]
+pub struct SigningRequest<'a> {
+    /// Name of the signing protocol.
+    pub protocol: &'a str,
+    /// Message to be signed.
+    pub message: &'a [u8],
+    /// Whether the message is a prehashed digest.
+    pub prehashed: bool,
+    /// Derivation path when signing with a derived key.
+    pub derivation_path: Option<&'a str>,
+    /// Chain identifier.
+    pub chain: Option<&'a str>,
+    /// Metadata for the signing session.
+    pub session: SessionMetadata<'a>,
+}
+
+/// Session metadata for a signing request.
+#[derive(Debug)]
+pub struct SessionMetadata<'a> {
+    /// Parameters for the session.
+    pub parameters: Parameters,
+    /// Noise transport public keys of the signers.
+    pub participants: &'a [Vec<u8>],
+    /// Index of this signer in the participants.
+    pub party_index: usize,
+    /// Whether this signer initiates the session.
+    pub is_initiator: bool,
+}
+
+/// Decision made by a signing policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// Participate in the signature.
+    Approve,
+    /// Refuse to participate in the signature.
+    Reject(String),
+    /// Do not participate yet.
+    ///
+    /// Use this when approval happens out of band (for example,
+    /// waiting for a second factor) and the caller should retry
+    /// once approval has been granted; policies may instead
+    /// wait for approval before returning a decision.
+    Defer(String),
+}
+
+/// Policy consulted before participating in a signature.
+#[async_trait]
+pub trait SigningPolicy: Send + Sync {
+    /// Decide whether to participate in a signature.
+    async fn evaluate(
+        &self,
+        request: &SigningRequest<'_>,
+    ) -> PolicyDecision;
+}
+
+/// Policy that approves every signature.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AllowAll;
+
+#[async_trait]
+impl SigningPolicy for AllowAll {
+    async fn evaluate(
+        &self,
+        _request: &SigningRequest<'_>,
+    ) -> PolicyDecision {
+        PolicyDecision::Approve
+    }
+}
+
+/// Assign the policy stored in a key share container to the
+/// session options.
+///
+/// The policy in the container replaces any key share policy
+/// assigned to the options.
+pub(crate) fn key_share_policy(
+    mut options: SessionOptions,
+    key_share: &KeyShare,
+) -> SessionOptions {
+    if let Some(policy) = &key_share.policy {
+        options.key_share_policy = Some(policy.clone());
+    }
+    options
+}
+
+/// Check the key share policy and ask the session policy to
+/// approve signing the messages.
+pub(crate) async fn approve<V>(
+    options: &SessionOptions,
+    protocol: &str,
+    party: &PartyOptions<V>,
+    messages: &[&[u8]],
+    prehashed: bool,
+) -> Result<()> {
+    let context = options.signing_context.as_ref();
+    let derivation_path =
+        context.and_then(|c| c.derivation_path.as_deref());
+    let chain = context.and_then(|c| c.chain.as_deref());
+
+    if let Some(policy) = &options.key_share_policy {
+        policy
+            .check(timestamp(), derivation_path, chain)
+            .map_err(|e| Error::PolicyRejected(e.to_string()))?;
+    }
+
+    let policy = match &options.policy {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+
+    for message in messages {
+        let request = SigningRequest {
+            protocol,
+            message,
+            prehashed,
+            derivation_path,
+            chain,
+            session: SessionMetadata {
+                parameters: options.parameters,
+                participants: party.participants(),
+                party_index: party.party_index(),
+                is_initiator: party.is_initiator(),
+            },
+        };
+        match policy.evaluate(&request).await {
+            PolicyDecision::Approve => {}
+            PolicyDecision::Reject(reason) => {
+                return Err(Error::PolicyRejected(reason));
+            }
+            PolicyDecision::Defer(reason) => {
+                return Err(Error::PolicyDeferred(reason));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,297 @@
+//! Reports describing the progress of a ceremony.
+use polysig_protocol::{PartyNumber, SessionId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Structured report for a ceremony that failed to complete.
+///
+/// Callers can inspect the report to determine which parties
+/// did not participate or misbehaved so that a ceremony can be
+/// retried excluding the offending parties.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CeremonyReport {
+    /// Session identifier.
+    pub session_id: Option<SessionId>,
+    /// Party number of the party that generated this report.
+    pub party_number: Option<u16>,
+    /// Round number when the ceremony failed.
+    pub round_number: u16,
+    /// Parties that sent at least one message.
+    pub responded: Vec<u16>,
+    /// Message delivery information for each round.
+    pub rounds: Vec<RoundReport>,
+    /// Evidence assigning blame to parties.
+    pub blame: Vec<Blame>,
+}
+
+impl CeremonyReport {
+    /// Parties that should be excluded when retrying the ceremony.
+    ///
+    /// Includes parties missing from the failed round and
+    /// any parties that have been blamed.
+    pub fn offenders(&self) -> Vec<u16> {
+        let mut offenders = BTreeSet::new();
+        if let Some(round) =
+            self.rounds.iter().find(|r| r.round == self.round_number)
+        {
+            offenders.extend(round.missing.iter().copied());
+        }
+        offenders.extend(self.blame.iter().filter_map(|b| b.party));
+        offenders.into_iter().collect()
+    }
+}
+
+/// Messages received for a single round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundReport {
+    /// Round number.
+    pub round: u16,
+    /// Parties that sent a message for this round.
+    pub received: Vec<u16>,
+    /// Parties that did not send a message for this round.
+    pub missing: Vec<u16>,
+}
+
+/// Status of the current round of a ceremony.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundStatus {
+    /// Round number.
+    pub round: u16,
+    /// Parties expected to send a message for the round
+    /// ordered by party number.
+    pub parties: Vec<PartyStatus>,
+}
+
+impl RoundStatus {
+    /// Parties that have sent a message for the round.
+    pub fn received(&self) -> Vec<u16> {
+        self.parties
+            .iter()
+            .filter(|p| p.received)
+            .map(|p| p.party)
+            .collect()
+    }
+
+    /// Parties that have not sent a message for the round.
+    pub fn missing(&self) -> Vec<u16> {
+        self.parties
+            .iter()
+            .filter(|p| !p.received)
+            .map(|p| p.party)
+            .collect()
+    }
+}
+
+/// Whether a party has sent a message for a round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartyStatus {
+    /// Party number.
+    pub party: u16,
+    /// Whether a message was received from the party.
+    pub received: bool,
+}
+
+/// Evidence assigning blame for a failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Blame {
+    /// Party being blamed, when the party is known.
+    pub party: Option<u16>,
+    /// Round number when the failure occurred.
+    pub round: u16,
+    /// Reason for the blame.
+    pub reason: String,
+}
+
+/// Tracks messages received by a bridge.
+#[derive(Debug, Default)]
+pub(crate) struct CeremonyTracker {
+    /// Parties we dispatched messages to and expect replies from.
+    peers: BTreeSet<u16>,
+    /// Parties that sent messages keyed by round number.
+    received: BTreeMap<u16, BTreeSet<u16>>,
+    /// Current round number.
+    round_number: u16,
+    /// Collected blame evidence.
+    blame: Vec<Blame>,
+}
+
+impl CeremonyTracker {
+    /// Record a party we sent a message to.
+    pub fn sent(&mut self, receiver: PartyNumber) {
+        self.peers.insert(receiver.get());
+    }
+
+    /// Record a message received from a party.
+    pub fn received(
+        &mut self,
+        round: u16,
+        sender: Option<PartyNumber>,
+    ) {
+        self.round_number = self.round_number.max(round);
+        if let Some(sender) = sender {
+            self.received
+                .entry(round)
+                .or_default()
+                .insert(sender.get());
+        }
+    }
+
+    /// Update the current round number.
+    pub fn set_round(&mut self, round: u16) {
+        self.round_number = self.round_number.max(round);
+    }
+
+    /// Assign blame to a party.
+    pub fn blame(
+        &mut self,
+        party: Option<PartyNumber>,
+        reason: impl Into<String>,
+    ) {
+        self.blame.push(Blame {
+            party: party.map(|p| p.get()),
+            round: self.round_number,
+            reason: reason.into(),
+        });
+    }
+
+    /// Status of the current round.
+    pub fn round_status(&self) -> RoundStatus {
+        let received = self.received.get(&self.round_number);
+        RoundStatus {
+            round: self.round_number,
+            parties: self
+                .peers
+                .iter()
+                .map(|party| PartyStatus {
+                    party: *party,
+                    received: received
+                        .is_some_and(|r| r.contains(party)),
+                })
+                .collect(),
+        }
+    }
+
+    /// Create a report from the tracked state.
+    pub fn report(
+        &self,
+        session_id: SessionId,
+        party_number: PartyNumber,
+    ) -> CeremonyReport {
+        let responded = self
+            .received
+            .values()
+            .flatten()
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let rounds = (1..=self.round_number)
+            .map(|round| {
+                let received = self
+                    .received
+                    .get(&round)
+                    .cloned()
+                    .unwrap_or_default();
+                let missing = self
+                    .peers
+                    .difference(&received)
+                    .copied()
+                    .collect();
+                RoundReport {
+                    round,
+                    received: received.into_iter().collect(),
+                    missing,
+                }
+            })
+            .collect();
+
+        CeremonyReport {
+            session_id: Some(session_id),
+            party_number: Some(party_number.get()),
+            round_number: self.round_number,
+            responded: responded.into_iter().collect(),
+            rounds,
+            blame: self.blame.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn party(value: u16) -> PartyNumber {
+        PartyNumber::new(value).unwrap()
+    }
+
+    fn tracker() -> CeremonyTracker {
+        let mut tracker = CeremonyTracker::default();
+        for receiver in 2..=4 {
+            tracker.sent(party(receiver));
+        }
+        tracker
+    }
+
+    #[test]
+    fn round_status_missing() {
+        let mut tracker = tracker();
+        tracker.received(1, Some(party(2)));
+        tracker.received(1, Some(party(3)));
+        tracker.received(1, Some(party(4)));
+        tracker.received(2, Some(party(3)));
+
+        let status = tracker.round_status();
+        assert_eq!(2, status.round);
+        assert_eq!(vec![3], status.received());
+        assert_eq!(vec![2, 4], status.missing());
+    }
+
+    #[test]
+    fn report_rounds() {
+        let mut tracker = tracker();
+        tracker.received(1, Some(party(2)));
+        tracker.received(1, Some(party(4)));
+        // Unknown senders are not recorded
+        tracker.received(1, None);
+
+        let report = tracker.report(SessionId::nil(), party(1));
+        assert_eq!(Some(1), report.party_number);
+        assert_eq!(1, report.round_number);
+        assert_eq!(vec![2, 4], report.responded);
+        assert_eq!(1, report.rounds.len());
+        assert_eq!(vec![2, 4], report.rounds[0].received);
+        assert_eq!(vec![3], report.rounds[0].missing);
+        assert_eq!(vec![3], report.offenders());
+    }
+
+    #[test]
+    fn offenders_include_blame() {
+        let mut tracker = tracker();
+        tracker.received(1, Some(party(2)));
+        tracker.received(1, Some(party(4)));
+        tracker.set_round(2);
+        tracker.received(2, Some(party(2)));
+        tracker.received(2, Some(party(3)));
+        tracker.blame(Some(party(2)), "invalid signature share");
+        tracker.blame(None, "local failure");
+
+        let report = tracker.report(SessionId::nil(), party(1));
+        assert_eq!(2, report.blame.len());
+        assert_eq!(Some(2), report.blame[0].party);
+        assert_eq!(2, report.blame[0].round);
+        assert_eq!(None, report.blame[1].party);
+        // Missing from the failed round and blamed, without
+        // parties that only missed an earlier round
+        assert_eq!(vec![2, 4], report.offenders());
+    }
+
+    #[test]
+    fn offenders_empty() {
+        let report = CeremonyReport::default();
+        assert!(report.offenders().is_empty());
+    }
+}
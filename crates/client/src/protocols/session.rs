@@ -1,7 +1,12 @@
-use crate::{EventStream, NetworkTransport, Result, Transport};
+use crate::{
+    attestation::AttestationExchange, EventStream, NetworkTransport,
+    ProgressEvent, Result, Transport,
+};
 use async_trait::async_trait;
-use futures::StreamExt;
-use polysig_protocol::{Event, SessionState};
+use futures::{future::try_join_all, StreamExt};
+use polysig_protocol::{
+    Event, Parameters, SessionRequest, SessionState,
+};
 use tokio::sync::Mutex;
 
 /// Trait for types that handle session related events.
@@ -58,8 +63,12 @@ impl From<SessionHandler> for Transport {
 pub struct SessionInitiator {
     transport: Transport,
     all_participants: Vec<Vec<u8>>,
+    parameters: Option<Parameters>,
+    deadline: Option<u64>,
     session_state: Mutex<Option<SessionState>>,
     requested_session: bool,
+    attestation: Option<AttestationExchange>,
+    early_peers: Vec<Vec<u8>>,
 }
 
 impl SessionInitiator {
@@ -68,22 +77,62 @@ impl SessionInitiator {
         transport: Transport,
         all_participants: Vec<Vec<u8>>,
     ) -> Self {
+        let attestation =
+            transport.attestation().map(AttestationExchange::new);
         Self {
             transport,
             all_participants,
+            parameters: None,
+            deadline: None,
             session_state: Mutex::new(None),
             requested_session: false,
+            attestation,
+            early_peers: Vec::new(),
         }
     }
 
+    /// Set the protocol parameters sent with the request
+    /// to create the session.
+    pub fn with_parameters(mut self, parameters: Parameters) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// Set a deadline for the session in seconds since
+    /// the UNIX epoch.
+    ///
+    /// The server notifies the participants that the session
+    /// timed out when the deadline is reached and drivers
+    /// return [SessionDeadline](crate::Error::SessionDeadline).
+    pub fn with_deadline(mut self, deadline: u64) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
     /// Lazily request to create new session only once.
     async fn new_session(&mut self) -> Result<()> {
         if !self.requested_session
             && self.transport.is_connected().await
         {
-            self.transport
-                .new_session(self.all_participants.clone())
-                .await?;
+            let participants = self.all_participants.clone();
+            if self.deadline.is_some() {
+                let request = SessionRequest {
+                    participant_keys: participants,
+                    observer_keys: Vec::new(),
+                    parameters: self.parameters.clone(),
+                    deadline: self.deadline,
+                };
+                self.transport.request_session(request).await?;
+            } else if let Some(parameters) = self.parameters.clone() {
+                self.transport
+                    .new_session_with_parameters(
+                        participants,
+                        parameters,
+                    )
+                    .await?;
+            } else {
+                self.transport.new_session(participants).await?;
+            }
 
             self.requested_session = true;
         }
@@ -104,42 +153,98 @@ impl SessionEventHandler for SessionInitiator {
         self.new_session().await?;
 
         match event {
+            Event::ServerConnected { .. } => {
+                self.transport.notify(ProgressEvent::ServerConnected);
+            }
             Event::SessionCreated(session) => {
                 tracing::info!(
                     id = ?session.session_id.to_string(),
                     "session created");
+                self.transport.notify(
+                    ProgressEvent::SessionCreated {
+                        session_id: session.session_id,
+                    },
+                );
 
                 let mut state = self.session_state.lock().await;
+                if state.is_none() {
+                    early_peers_connected(
+                        &mut self.transport,
+                        self.attestation.as_mut(),
+                        &session,
+                        &mut self.early_peers,
+                    )
+                    .await?;
+                }
                 *state = Some(session);
             }
             Event::SessionReady(session) => {
                 tracing::info!(
                     id = ?session.session_id.to_string(),
                     "session ready");
+                self.transport.notify(ProgressEvent::SessionReady {
+                    session_id: session.session_id,
+                });
 
-                let connections =
-                    session.connections(self.transport.public_key());
-
-                for key in connections {
-                    self.transport.connect_peer(key).await?;
+                // Notifications may arrive before the session
+                // is created
+                let mut state = self.session_state.lock().await;
+                if state.is_none() {
+                    early_peers_connected(
+                        &mut self.transport,
+                        self.attestation.as_mut(),
+                        &session,
+                        &mut self.early_peers,
+                    )
+                    .await?;
+                    *state = Some(session.clone());
                 }
+                drop(state);
+
+                connect_peers(&self.transport, &session).await?;
             }
             Event::PeerConnected { peer_key } => {
                 let state = self.session_state.lock().await;
-                let session = state.as_ref().unwrap();
-                let connections =
-                    session.connections(self.transport.public_key());
-                if connections.contains(&peer_key) {
-                    self.transport
-                        .register_connection(
-                            &session.session_id,
-                            peer_key.as_slice(),
-                        )
-                        .await?;
+                if let Some(session) = state.as_ref() {
+                    peer_connected(
+                        &mut self.transport,
+                        self.attestation.as_mut(),
+                        session,
+                        peer_key,
+                    )
+                    .await?;
+                } else {
+                    self.early_peers.push(peer_key);
                 }
             }
+            event @ Event::JsonMessage { .. } => {
+                let state = self.session_state.lock().await;
+                message_received(
+                    &mut self.transport,
+                    self.attestation.as_mut(),
+                    state.as_ref(),
+                    event,
+                )
+                .await?;
+            }
             Event::SessionActive(session) => {
-                return Ok(Some(session))
+                self.transport.notify(ProgressEvent::SessionActive {
+                    session_id: session.session_id,
+                });
+                return Ok(Some(session));
+            }
+            // Session timed out before it was active
+            Event::SessionTimeout(session_id)
+                if self.deadline.is_some() =>
+            {
+                let state = self.session_state.lock().await;
+                if state.as_ref().map(|s| s.session_id)
+                    == Some(session_id)
+                {
+                    return Err(crate::Error::SessionDeadline(
+                        session_id,
+                    ));
+                }
             }
             _ => {}
         }
@@ -157,14 +262,20 @@ impl From<SessionInitiator> for Transport {
 pub struct SessionParticipant {
     transport: Transport,
     session_state: Mutex<Option<SessionState>>,
+    attestation: Option<AttestationExchange>,
+    early_peers: Vec<Vec<u8>>,
 }
 
 impl SessionParticipant {
     /// Create a new session participant.
     pub fn new(transport: Transport) -> Self {
+        let attestation =
+            transport.attestation().map(AttestationExchange::new);
         Self {
             transport,
             session_state: Mutex::new(None),
+            attestation,
+            early_peers: Vec::new(),
         }
     }
 }
@@ -181,40 +292,60 @@ impl SessionEventHandler for SessionParticipant {
         event: Event,
     ) -> Result<Option<SessionState>> {
         match event {
+            Event::ServerConnected { .. } => {
+                self.transport.notify(ProgressEvent::ServerConnected);
+            }
             Event::SessionReady(session) => {
                 let mut state = self.session_state.lock().await;
                 *state = Some(session.clone());
+                drop(state);
 
                 tracing::info!(
                     id = ?session.session_id.to_string(),
                     "session ready");
+                self.transport.notify(ProgressEvent::SessionReady {
+                    session_id: session.session_id,
+                });
 
-                for key in
-                    session.connections(self.transport.public_key())
-                {
-                    self.transport.connect_peer(key).await?;
-                }
+                early_peers_connected(
+                    &mut self.transport,
+                    self.attestation.as_mut(),
+                    &session,
+                    &mut self.early_peers,
+                )
+                .await?;
+                connect_peers(&self.transport, &session).await?;
             }
             Event::PeerConnected { peer_key } => {
                 let state = self.session_state.lock().await;
                 if let Some(session) = state.as_ref() {
-                    let connections = session
-                        .connections(self.transport.public_key());
-                    if connections.contains(&peer_key) {
-                        self.transport
-                            .register_connection(
-                                &session.session_id,
-                                peer_key.as_slice(),
-                            )
-                            .await?;
-                    }
+                    peer_connected(
+                        &mut self.transport,
+                        self.attestation.as_mut(),
+                        session,
+                        peer_key,
+                    )
+                    .await?;
                 } else {
-                    tracing::warn!(
-                        "peer connected event without session"
-                    );
+                    // Peers may connect before the session is
+                    // ready for this participant
+                    self.early_peers.push(peer_key);
                 }
             }
+            event @ Event::JsonMessage { .. } => {
+                let state = self.session_state.lock().await;
+                message_received(
+                    &mut self.transport,
+                    self.attestation.as_mut(),
+                    state.as_ref(),
+                    event,
+                )
+                .await?;
+            }
             Event::SessionActive(session) => {
+                self.transport.notify(ProgressEvent::SessionActive {
+                    session_id: session.session_id,
+                });
                 return Ok(Some(session));
             }
             _ => {}
@@ -230,6 +361,111 @@ impl From<SessionParticipant> for Transport {
     }
 }
 
+/// Connect to the peers for this participant in a session.
+///
+/// Connection requests are issued concurrently; the handshakes
+/// complete in any order as peer connected events arrive.
+async fn connect_peers(
+    transport: &Transport,
+    session: &SessionState,
+) -> Result<()> {
+    let requests = session
+        .connections(transport.public_key())
+        .iter()
+        .map(|key| {
+            let mut transport = transport.clone();
+            async move { transport.connect_peer(key).await }
+        });
+    try_join_all(requests).await?;
+    Ok(())
+}
+
+/// Handle the peers that connected before the session
+/// state was known.
+async fn early_peers_connected(
+    transport: &mut Transport,
+    mut attestation: Option<&mut AttestationExchange>,
+    session: &SessionState,
+    early_peers: &mut Vec<Vec<u8>>,
+) -> Result<()> {
+    for peer_key in early_peers.drain(..) {
+        peer_connected(
+            transport,
+            attestation.as_deref_mut(),
+            session,
+            peer_key,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Handle a connection to a peer in a session.
+///
+/// When an attestation is assigned evidence is sent to every
+/// peer and the connection is registered once the peer is
+/// attested.
+async fn peer_connected(
+    transport: &mut Transport,
+    attestation: Option<&mut AttestationExchange>,
+    session: &SessionState,
+    peer_key: Vec<u8>,
+) -> Result<()> {
+    if let Some(attestation) = attestation {
+        if !session.all_participants.contains(&peer_key)
+            || !attestation
+                .peer_connected(transport, session, &peer_key)
+                .await?
+        {
+            return Ok(());
+        }
+    }
+    register_connection(transport, session, peer_key).await
+}
+
+/// Handle an attestation message sent by a peer.
+async fn message_received(
+    transport: &mut Transport,
+    attestation: Option<&mut AttestationExchange>,
+    session: Option<&SessionState>,
+    event: Event,
+) -> Result<()> {
+    let Some(attestation) = attestation else {
+        return Ok(());
+    };
+    if let (Some(peer_key), Some(session)) = (
+        attestation
+            .message_received(transport, session, event)
+            .await?,
+        session,
+    ) {
+        register_connection(transport, session, peer_key).await?;
+    }
+    Ok(())
+}
+
+/// Register a connection when the peer is one of the
+/// connections for this participant.
+async fn register_connection(
+    transport: &mut Transport,
+    session: &SessionState,
+    peer_key: Vec<u8>,
+) -> Result<()> {
+    if !session
+        .connections(transport.public_key())
+        .contains(&peer_key)
+    {
+        return Ok(());
+    }
+    transport
+        .register_connection(&session.session_id, peer_key.as_slice())
+        .await?;
+    transport.notify(ProgressEvent::PeerConnected {
+        public_key: peer_key,
+    });
+    Ok(())
+}
+
 /// Wait for a session to become active.
 pub async fn wait_for_session<S>(
     stream: &mut EventStream,
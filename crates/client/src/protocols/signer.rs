@@ -0,0 +1,294 @@
+//! Threshold key shares exposed as signers.
+//!
+//! The signers implement [AsyncSigner] so code written against
+//! the single party signers can switch to threshold signing.
+//! Every call to `sign` runs a signing session so the other
+//! signers must sign the same message at the same time.
+//!
+//! Messages are hashed in the same way as the single party
+//! signer for the algorithm so signatures can be verified
+//! using [Algorithm::verify].
+use async_trait::async_trait;
+#[cfg(feature = "frost-secp256k1-tr")]
+use polysig_driver::frost::secp256k1_tr::taproot;
+#[cfg(all(feature = "cggmp", feature = "ecdsa"))]
+use polysig_driver::signers::ecdsa::eth_tx::UnsignedTransaction;
+use polysig_driver::signers::{Algorithm, AsyncSigner};
+#[cfg(any(
+    all(feature = "cggmp", feature = "ecdsa"),
+    feature = "frost-secp256k1-tr"
+))]
+use sha2::{Digest, Sha256};
+
+use crate::{Error, SessionOptions};
+
+fn signer_error(error: impl Into<Error>) -> polysig_driver::Error {
+    polysig_driver::Error::Signer(Box::new(error.into()))
+}
+
+/// CGGMP key share as an ECDSA signer.
+///
+/// The session identifier for each signature is derived
+/// from the message digest.
+#[cfg(all(feature = "cggmp", feature = "ecdsa"))]
+pub struct CggmpSigner<P: polysig_driver::synedrion::SchemeParams> {
+    options: SessionOptions,
+    participant: polysig_driver::cggmp::Participant,
+    key_share: polysig_driver::synedrion::KeyShare<
+        P,
+        polysig_driver::k256::ecdsa::VerifyingKey,
+    >,
+    public_key: Vec<u8>,
+}
+
+#[cfg(all(feature = "cggmp", feature = "ecdsa"))]
+impl<P: polysig_driver::synedrion::SchemeParams + 'static>
+    CggmpSigner<P>
+{
+    /// Create a signer for a key share.
+    ///
+    /// The key share must be created for the verifiers in the
+    /// party options of the participant.
+    pub fn new(
+        options: SessionOptions,
+        participant: polysig_driver::cggmp::Participant,
+        key_share: polysig_driver::synedrion::KeyShare<
+            P,
+            polysig_driver::k256::ecdsa::VerifyingKey,
+        >,
+    ) -> crate::Result<Self> {
+        let public_key = key_share
+            .verifying_key()
+            .map(|k| k.to_sec1_bytes().to_vec())
+            .ok_or(Error::KeyShareVerifyingKey)?;
+        Ok(Self {
+            options,
+            participant,
+            key_share,
+            public_key,
+        })
+    }
+
+    /// Sign an Ethereum transaction, returning the raw signed
+    /// transaction ready to be broadcast.
+    pub async fn sign_transaction(
+        &self,
+        transaction: &UnsignedTransaction,
+    ) -> crate::Result<Vec<u8>> {
+        let hash = transaction.signing_hash();
+        let session_id =
+            polysig_driver::synedrion::SessionId::from_seed(&hash);
+        let signature = super::cggmp::sign::<P>(
+            self.options.clone(),
+            self.participant.clone(),
+            session_id,
+            &self.key_share,
+            &hash,
+        )
+        .await?;
+        Ok(transaction.encode_signed(&signature)?)
+    }
+}
+
+#[cfg(all(feature = "cggmp", feature = "ecdsa"))]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<P: polysig_driver::synedrion::SchemeParams + 'static> AsyncSigner
+    for CggmpSigner<P>
+{
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Ecdsa
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    async fn sign(
+        &self,
+        message: &[u8],
+    ) -> polysig_driver::Result<Vec<u8>> {
+        let digest: [u8; 32] = Sha256::digest(message).into();
+        let session_id =
+            polysig_driver::synedrion::SessionId::from_seed(&digest);
+        let signature = super::cggmp::sign::<P>(
+            self.options.clone(),
+            self.participant.clone(),
+            session_id,
+            &self.key_share,
+            &digest,
+        )
+        .await
+        .map_err(signer_error)?;
+        Ok(signature.normalize_s()?.bytes)
+    }
+}
+
+/// FROST Ed25519 key share as an EdDSA signer.
+#[cfg(feature = "frost-ed25519")]
+pub struct FrostEd25519Signer {
+    options: SessionOptions,
+    participant: polysig_driver::frost::ed25519::Participant,
+    identifiers: Vec<polysig_driver::frost::ed25519::Identifier>,
+    key_share: polysig_driver::frost::ed25519::KeyShare,
+    public_key: Vec<u8>,
+}
+
+#[cfg(feature = "frost-ed25519")]
+impl FrostEd25519Signer {
+    /// Create a signer for a key share.
+    ///
+    /// Identifiers must match the key share identifiers
+    /// of the signers.
+    pub fn new(
+        options: SessionOptions,
+        participant: polysig_driver::frost::ed25519::Participant,
+        identifiers: Vec<polysig_driver::frost::ed25519::Identifier>,
+        key_share: polysig_driver::frost::ed25519::KeyShare,
+    ) -> crate::Result<Self> {
+        let public_key = key_share.1.verifying_key().serialize()?;
+        Ok(Self {
+            options,
+            participant,
+            identifiers,
+            key_share,
+            public_key,
+        })
+    }
+}
+
+#[cfg(feature = "frost-ed25519")]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AsyncSigner for FrostEd25519Signer {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Eddsa
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    async fn sign(
+        &self,
+        message: &[u8],
+    ) -> polysig_driver::Result<Vec<u8>> {
+        let signature = super::frost::ed25519::sign(
+            self.options.clone(),
+            self.participant.clone(),
+            self.identifiers.clone(),
+            self.key_share.clone(),
+            message.to_vec(),
+        )
+        .await
+        .map_err(signer_error)?;
+        signature.serialize().map_err(signer_error)
+    }
+}
+
+/// FROST Taproot key share as a BIP-340 Schnorr signer.
+///
+/// The public key is the BIP-86 taproot output key as
+/// signatures are created for a key path spend.
+#[cfg(feature = "frost-secp256k1-tr")]
+pub struct FrostTaprootSigner {
+    options: SessionOptions,
+    participant: polysig_driver::frost::secp256k1_tr::Participant,
+    identifiers: Vec<polysig_driver::frost::secp256k1_tr::Identifier>,
+    key_share: polysig_driver::frost::secp256k1_tr::KeyShare,
+    public_key: Vec<u8>,
+}
+
+#[cfg(feature = "frost-secp256k1-tr")]
+impl FrostTaprootSigner {
+    /// Create a signer for a key share.
+    ///
+    /// Identifiers must match the key share identifiers
+    /// of the signers.
+    pub fn new(
+        options: SessionOptions,
+        participant: polysig_driver::frost::secp256k1_tr::Participant,
+        identifiers: Vec<
+            polysig_driver::frost::secp256k1_tr::Identifier,
+        >,
+        key_share: polysig_driver::frost::secp256k1_tr::KeyShare,
+    ) -> crate::Result<Self> {
+        let public_key = taproot::output_key(&key_share.1)?.to_vec();
+        Ok(Self {
+            options,
+            participant,
+            identifiers,
+            key_share,
+            public_key,
+        })
+    }
+
+    /// Sign taproot inputs of a PSBT that are spent using a
+    /// key path spend of the group key, returning the indices
+    /// of the signed inputs.
+    ///
+    /// Inputs that commit to a script tree are not signed as
+    /// the signing protocol only supports the BIP-86 tweak;
+    /// a signing session is run for each input.
+    #[cfg(feature = "bitcoin")]
+    pub async fn sign_psbt(
+        &self,
+        psbt: &mut polysig_driver::bitcoin::Psbt,
+    ) -> crate::Result<Vec<usize>> {
+        use polysig_driver::bitcoin;
+        let internal_key = taproot::internal_key(&self.key_share.1)?;
+        let sighashes =
+            bitcoin::taproot_sighashes(psbt, &internal_key)?;
+        let mut signed = Vec::new();
+        for sighash in sighashes {
+            if sighash.merkle_root.is_some() {
+                continue;
+            }
+            let signature = super::frost::secp256k1_tr::sign(
+                self.options.clone(),
+                self.participant.clone(),
+                self.identifiers.clone(),
+                self.key_share.clone(),
+                sighash.sighash.to_vec(),
+            )
+            .await?;
+            bitcoin::insert_taproot_signature(
+                psbt,
+                &sighash,
+                &taproot::signature_bytes(&signature)?,
+            )?;
+            signed.push(sighash.input_index);
+        }
+        Ok(signed)
+    }
+}
+
+#[cfg(feature = "frost-secp256k1-tr")]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AsyncSigner for FrostTaprootSigner {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Schnorr
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    async fn sign(
+        &self,
+        message: &[u8],
+    ) -> polysig_driver::Result<Vec<u8>> {
+        let digest = Sha256::digest(message).to_vec();
+        let signature = super::frost::secp256k1_tr::sign(
+            self.options.clone(),
+            self.participant.clone(),
+            self.identifiers.clone(),
+            self.key_share.clone(),
+            digest,
+        )
+        .await
+        .map_err(signer_error)?;
+        Ok(taproot::signature_bytes(&signature)?.to_vec())
+    }
+}
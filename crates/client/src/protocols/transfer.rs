@@ -0,0 +1,247 @@
+//! Transfer a key share to a new device.
+//!
+//! The device holding the key share calls [transfer_share]
+//! and the new device calls [receive_share]; the key share
+//! is sent over a relay session between the two devices.
+//!
+//! Both functions return a [TransferCertificate] which should
+//! be distributed to the other participants so they can
+//! update the roster for the key using
+//! [TransferCertificate::apply].
+use crate::{
+    protocols::{
+        new_client, wait_for_close, wait_for_driver,
+        wait_for_session, wait_for_session_finish, Bridge,
+        CeremonyReport, Driver, RoundStatus, SessionInitiator,
+        SessionOptions, SessionParticipant,
+    },
+    Error, NetworkTransport, Result, Transport,
+};
+use async_trait::async_trait;
+use polysig_driver::{
+    certificate::{
+        Binding, CertificateKey, ShareTransfer, TransferCertificate,
+        TransferDriver as Transfer,
+    },
+    KeyShare,
+};
+use polysig_protocol::{hex, Event, SessionState};
+
+/// Driver that transfers a key share between two devices.
+pub struct TransferDriver<V: CertificateKey> {
+    bridge: Bridge<Transfer<V>>,
+}
+
+impl<V: CertificateKey> TransferDriver<V> {
+    /// Create a new transfer driver.
+    ///
+    /// The device holding the key share must supply the
+    /// key share; the statement for the transfer is created
+    /// for the session.
+    pub fn new(
+        transport: Transport,
+        session: SessionState,
+        previous: Binding,
+        next: Binding,
+        signing_key: &V::SigningKey,
+        key_share: Option<KeyShare>,
+    ) -> Result<Self> {
+        let public_key = transport.public_key();
+        let peer_key = if key_share.is_some() {
+            next.public_key()
+        } else {
+            previous.public_key()
+        };
+        let party_number =
+            session.party_number(public_key).ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(public_key))
+            })?;
+        let peer =
+            session.party_number(peer_key).ok_or_else(|| {
+                Error::NotSessionParticipant(hex::encode(peer_key))
+            })?;
+
+        let transfer =
+            ShareTransfer::new(session.session_id, previous, next)?;
+        let driver = match key_share {
+            Some(key_share) => Transfer::send(
+                party_number,
+                peer,
+                transfer,
+                signing_key,
+                key_share,
+            ),
+            None => Transfer::receive(
+                party_number,
+                peer,
+                transfer,
+                signing_key,
+            ),
+        }?;
+
+        let bridge =
+            Bridge::new(transport, driver, session, party_number);
+        Ok(Self { bridge })
+    }
+}
+
+#[async_trait]
+impl<V> Driver for TransferDriver<V>
+where
+    V: CertificateKey + Send + Sync,
+{
+    type Output = (TransferCertificate, Option<KeyShare>);
+
+    async fn handle_event(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.handle_event(event).await?)
+    }
+
+    async fn execute(&mut self) -> Result<Option<Self::Output>> {
+        Ok(self.bridge.execute().await?)
+    }
+
+    fn into_transport(self) -> Transport {
+        self.bridge.transport
+    }
+
+    async fn replace_transport(
+        &mut self,
+        transport: Transport,
+    ) -> Result<Transport> {
+        self.bridge.replace_transport(transport).await
+    }
+
+    fn report(&self) -> CeremonyReport {
+        self.bridge.report()
+    }
+
+    fn round_status(&self) -> Option<RoundStatus> {
+        Some(self.bridge.round_status())
+    }
+
+    fn round_timeout(&self) -> Option<std::time::Duration> {
+        self.bridge.round_timeout()
+    }
+}
+
+impl<V: CertificateKey> From<TransferDriver<V>> for Transport {
+    fn from(value: TransferDriver<V>) -> Self {
+        value.bridge.transport
+    }
+}
+
+/// Transfer a key share to a new device.
+///
+/// Called by the device holding the key share which creates
+/// the session with the new device; the signing key must be
+/// the signing key for the previous binding.
+pub async fn transfer_share<V>(
+    options: SessionOptions,
+    previous: Binding,
+    next: Binding,
+    signing_key: &V::SigningKey,
+    key_share: KeyShare,
+) -> Result<TransferCertificate>
+where
+    V: CertificateKey + Send + Sync,
+    V::SigningKey: Sync,
+{
+    let (certificate, _) = run_transfer(
+        options,
+        previous,
+        next,
+        signing_key,
+        Some(key_share),
+    )
+    .await?;
+    Ok(certificate)
+}
+
+/// Receive a key share from the device holding the key share.
+///
+/// Called by the new device which joins the session created
+/// by the device holding the key share; the signing key must
+/// be the signing key for the next binding.
+pub async fn receive_share<V>(
+    options: SessionOptions,
+    previous: Binding,
+    next: Binding,
+    signing_key: &V::SigningKey,
+) -> Result<(KeyShare, TransferCertificate)>
+where
+    V: CertificateKey + Send + Sync,
+    V::SigningKey: Sync,
+{
+    let (certificate, key_share) =
+        run_transfer(options, previous, next, signing_key, None)
+            .await?;
+    Ok((key_share.unwrap(), certificate))
+}
+
+async fn run_transfer<V>(
+    options: SessionOptions,
+    previous: Binding,
+    next: Binding,
+    signing_key: &V::SigningKey,
+    key_share: Option<KeyShare>,
+) -> Result<(TransferCertificate, Option<KeyShare>)>
+where
+    V: CertificateKey + Send + Sync,
+    V::SigningKey: Sync,
+{
+    let is_initiator = key_share.is_some();
+
+    // Create the client
+    let (client, event_loop) = new_client(options).await?;
+
+    let mut transport: Transport = client.into();
+
+    // Handshake with the server
+    transport.connect().await?;
+
+    // Start the event stream
+    let mut stream = event_loop.run();
+
+    // Wait for the session to become active
+    let (transport, session) = if is_initiator {
+        let handler = SessionInitiator::new(
+            transport,
+            vec![
+                previous.public_key().to_vec(),
+                next.public_key().to_vec(),
+            ],
+        );
+        wait_for_session(&mut stream, handler).await?
+    } else {
+        let handler = SessionParticipant::new(transport);
+        wait_for_session(&mut stream, handler).await?
+    };
+
+    let protocol_session_id = session.session_id;
+
+    let driver = TransferDriver::<V>::new(
+        transport,
+        session,
+        previous,
+        next,
+        signing_key,
+        key_share,
+    )?;
+
+    let (mut transport, output) =
+        wait_for_driver(&mut stream, driver).await?;
+
+    // Close the session and socket
+    if is_initiator {
+        transport.close_session(protocol_session_id).await?;
+        wait_for_session_finish(&mut stream, protocol_session_id)
+            .await?;
+    }
+    transport.close().await?;
+    wait_for_close(&mut stream).await?;
+
+    Ok(output)
+}
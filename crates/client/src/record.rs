@@ -0,0 +1,315 @@
+//! Record and replay decrypted peer messages.
+//!
+//! Recording is opt-in; the `record` feature must be enabled
+//! and a [Recorder] assigned to the client options. Ceremonies
+//! started from session options record when the
+//! `POLYSIG_RECORD_DIR` environment variable names a directory,
+//! each client writes a transcript to a new file in that
+//! directory.
+//!
+//! Transcripts contain the plaintext of every message exchanged
+//! with peers, including secret shares sent in peer to peer
+//! rounds, so they must only be recorded for test or debugging
+//! ceremonies.
+//!
+//! A transcript is a JSON lines file; the first line is a
+//! header with the public key of the client and each following
+//! line is a [RecordedMessage].
+//!
+//! Use [Replay] to feed the transcripts of every party in a
+//! session back through new drivers without a network.
+use crate::{timestamp_millis, Error, Result};
+use polysig_protocol::{
+    hex, Encoding, Event, JsonMessage, SessionId,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+mod replay;
+
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+pub use replay::{Replay, ReplayOutput};
+
+/// Environment variable for the directory to record ceremony
+/// transcripts.
+pub const RECORD_DIR: &str = "POLYSIG_RECORD_DIR";
+
+/// Transcript format version.
+const VERSION: u16 = 1;
+
+/// Direction of a recorded message.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Message received from a peer.
+    Incoming,
+    /// Message sent to a peer.
+    Outgoing,
+}
+
+/// Decrypted message payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    tag = "encoding",
+    content = "payload",
+    rename_all = "lowercase"
+)]
+pub enum Payload {
+    /// JSON message.
+    Json(Value),
+    /// Binary message.
+    Blob(#[serde(with = "hex::serde")] Vec<u8>),
+}
+
+/// Message exchanged with a peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedMessage {
+    /// Position in the transcript starting at one.
+    pub sequence: u64,
+    /// Milliseconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// Whether the message was sent or received.
+    pub direction: Direction,
+    /// Public key of the peer.
+    #[serde(with = "hex::serde")]
+    pub peer_key: Vec<u8>,
+    /// Session identifier.
+    pub session_id: Option<SessionId>,
+    /// Message payload.
+    #[serde(flatten)]
+    pub payload: Payload,
+}
+
+/// First line of a transcript.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Header {
+    version: u16,
+    #[serde(with = "hex::serde")]
+    public_key: Vec<u8>,
+    created_at: u64,
+}
+
+/// Messages recorded by a client.
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    /// Transcript format version.
+    pub version: u16,
+    /// Public key of the client that recorded the transcript.
+    pub public_key: Vec<u8>,
+    /// Milliseconds since the UNIX epoch.
+    pub created_at: u64,
+    /// Recorded messages.
+    pub messages: Vec<RecordedMessage>,
+}
+
+impl Transcript {
+    /// Load a transcript file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+        let header: Header = match lines.next() {
+            Some(line) => serde_json::from_str(&line?)?,
+            None => {
+                return Err(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "transcript header is missing",
+                )))
+            }
+        };
+        let mut messages = Vec::new();
+        for line in lines {
+            let line = line?;
+            if !line.trim().is_empty() {
+                messages.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(Self {
+            version: header.version,
+            public_key: header.public_key,
+            created_at: header.created_at,
+            messages,
+        })
+    }
+
+    /// Messages recorded for a session.
+    pub fn session(
+        &self,
+        session_id: SessionId,
+    ) -> impl Iterator<Item = &RecordedMessage> {
+        self.messages
+            .iter()
+            .filter(move |m| m.session_id == Some(session_id))
+    }
+}
+
+/// Records decrypted peer messages to a transcript file.
+///
+/// Each message is flushed as it is recorded so the transcript
+/// is complete up to the point of a crash.
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    writer: BufWriter<File>,
+    sequence: u64,
+}
+
+impl Recorder {
+    /// Create a transcript file for the client with
+    /// the given public key.
+    pub fn create(
+        path: impl AsRef<Path>,
+        public_key: &[u8],
+    ) -> Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = Header {
+            version: VERSION,
+            public_key: public_key.to_vec(),
+            created_at: timestamp_millis(),
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                writer,
+                sequence: 0,
+            })),
+        })
+    }
+
+    /// Create a recorder in the directory named by the
+    /// `POLYSIG_RECORD_DIR` environment variable.
+    ///
+    /// Returns `None` when the variable is not set.
+    pub fn from_env(public_key: &[u8]) -> Result<Option<Self>> {
+        let Some(dir) = std::env::var_os(RECORD_DIR) else {
+            return Ok(None);
+        };
+        let dir = Path::new(&dir);
+        std::fs::create_dir_all(dir)?;
+        let name = format!(
+            "{}-{}.jsonl",
+            hex::encode(&public_key[..public_key.len().min(8)]),
+            timestamp_millis(),
+        );
+        Ok(Some(Self::create(dir.join(name), public_key)?))
+    }
+
+    /// Record a message.
+    pub fn record(
+        &self,
+        direction: Direction,
+        peer_key: &[u8],
+        session_id: Option<SessionId>,
+        payload: Payload,
+    ) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.sequence += 1;
+        let message = RecordedMessage {
+            sequence: inner.sequence,
+            timestamp: timestamp_millis(),
+            direction,
+            peer_key: peer_key.to_vec(),
+            session_id,
+            payload,
+        };
+        serde_json::to_writer(&mut inner.writer, &message)?;
+        inner.writer.write_all(b"\n")?;
+        inner.writer.flush()?;
+        Ok(())
+    }
+
+    /// Record a message sent to a peer.
+    ///
+    /// Failures are logged so recording never interrupts
+    /// a ceremony.
+    pub(crate) fn outgoing(
+        &self,
+        peer_key: &[u8],
+        payload: &[u8],
+        encoding: Encoding,
+        session_id: Option<SessionId>,
+    ) {
+        let result =
+            into_payload(encoding, payload).and_then(|payload| {
+                self.record(
+                    Direction::Outgoing,
+                    peer_key,
+                    session_id,
+                    payload,
+                )
+            });
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "record outgoing message");
+        }
+    }
+
+    /// Record a message received from a peer.
+    pub(crate) fn incoming(&self, event: &Event) {
+        let result = match event {
+            Event::JsonMessage {
+                peer_key,
+                message,
+                session_id,
+            } => message.deserialize().map_err(Error::from).and_then(
+                |value| {
+                    self.record(
+                        Direction::Incoming,
+                        peer_key,
+                        *session_id,
+                        Payload::Json(value),
+                    )
+                },
+            ),
+            Event::BinaryMessage {
+                peer_key,
+                message,
+                session_id,
+            } => self.record(
+                Direction::Incoming,
+                peer_key,
+                *session_id,
+                Payload::Blob(message.clone()),
+            ),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "record incoming message");
+        }
+    }
+}
+
+fn into_payload(
+    encoding: Encoding,
+    payload: &[u8],
+) -> Result<Payload> {
+    Ok(if encoding.is_serialized() {
+        let message = JsonMessage::new(payload.to_vec(), encoding);
+        Payload::Json(message.deserialize()?)
+    } else {
+        Payload::Blob(payload.to_vec())
+    })
+}
@@ -0,0 +1,252 @@
+//! Replay recorded sessions through protocol drivers.
+use super::{Direction, Payload, Transcript};
+use crate::{Error, Result};
+use polysig_driver::{ProtocolDriver, Round};
+use polysig_protocol::{hex, SessionId};
+use std::collections::{HashMap, VecDeque};
+
+/// Messages waiting for delivery keyed by sender, receiver
+/// and round number.
+type Pending<M> = HashMap<(usize, usize, u16), VecDeque<M>>;
+
+/// Result of replaying a session.
+#[derive(Debug)]
+pub struct ReplayOutput<O> {
+    /// Output for each party in party number order; `None`
+    /// when the party did not complete the protocol.
+    pub outputs: Vec<Option<O>>,
+    /// Recorded deliveries that could not be replayed.
+    pub undelivered: usize,
+    /// Messages ignored because the receiver could already
+    /// finalize the round.
+    pub ignored: usize,
+}
+
+/// Replays a recorded session through new drivers.
+///
+/// Drivers generate fresh messages as protocols use random
+/// values; the transcripts only determine the order in which
+/// each party receives messages. Every message is delivered
+/// to the receiver in the recorded order by sender and round
+/// number and the drivers are advanced the same way as during
+/// the ceremony so ordering bugs reproduce deterministically.
+///
+/// Messages that do not decode as driver messages (for example
+/// the acknowledgements exchanged before a CGGMP ceremony)
+/// are not replayed.
+pub struct Replay<D: ProtocolDriver> {
+    parties: Vec<Party<D>>,
+}
+
+struct Party<D: ProtocolDriver> {
+    driver: D,
+    /// Recorded deliveries as the sequence number,
+    /// sender index and round number.
+    schedule: VecDeque<(u64, usize, u16)>,
+    output: Option<D::Output>,
+}
+
+impl<D: ProtocolDriver> Replay<D> {
+    /// Prepare to replay a session.
+    ///
+    /// Drivers are paired with the public key of the party and
+    /// must be in party number order; each driver should be
+    /// created with the same inputs used for the recorded
+    /// ceremony. The transcript for each party is located by
+    /// public key.
+    pub fn new(
+        session_id: SessionId,
+        drivers: Vec<(Vec<u8>, D)>,
+        transcripts: &[Transcript],
+    ) -> Result<Self> {
+        let keys: Vec<Vec<u8>> =
+            drivers.iter().map(|(key, _)| key.clone()).collect();
+        let mut parties = Vec::with_capacity(drivers.len());
+        for (public_key, driver) in drivers {
+            let transcript = transcripts
+                .iter()
+                .find(|t| t.public_key == public_key)
+                .ok_or_else(|| {
+                    Error::TranscriptNotFound(hex::encode(
+                        &public_key,
+                    ))
+                })?;
+
+            let mut schedule = VecDeque::new();
+            for message in transcript.session(session_id) {
+                if message.direction != Direction::Incoming {
+                    continue;
+                }
+                let Payload::Json(value) = &message.payload else {
+                    continue;
+                };
+                let Some(sender) = keys
+                    .iter()
+                    .position(|key| key == &message.peer_key)
+                else {
+                    continue;
+                };
+                match serde_json::from_value::<D::Message>(
+                    value.clone(),
+                ) {
+                    Ok(decoded) => schedule.push_back((
+                        message.sequence,
+                        sender,
+                        decoded.round_number().get(),
+                    )),
+                    Err(_) => tracing::debug!(
+                        sequence = message.sequence,
+                        "replay skip message"
+                    ),
+                }
+            }
+
+            parties.push(Party {
+                driver,
+                schedule,
+                output: None,
+            });
+        }
+        Ok(Self { parties })
+    }
+
+    /// Run the drivers until no recorded delivery can be made.
+    pub fn run(mut self) -> Result<ReplayOutput<D::Output>> {
+        let mut pending: Pending<D::Message> = HashMap::new();
+        let mut ignored = 0;
+
+        for (index, party) in self.parties.iter_mut().enumerate() {
+            let messages = party
+                .driver
+                .proceed()
+                .map_err(|e| replay_error(index, 0, e))?;
+            route(&mut pending, index, messages);
+            party.finalize_rounds(index, 0, &mut pending)?;
+        }
+
+        loop {
+            let mut progress = false;
+            for (index, party) in self.parties.iter_mut().enumerate()
+            {
+                if party.output.is_some() {
+                    continue;
+                }
+                let Some(&(sequence, sender, round)) =
+                    party.schedule.front()
+                else {
+                    continue;
+                };
+                let Some(message) = pending
+                    .get_mut(&(sender, index, round))
+                    .and_then(|queue| queue.pop_front())
+                else {
+                    continue;
+                };
+                party.schedule.pop_front();
+                progress = true;
+
+                tracing::debug!(
+                    party = index + 1,
+                    sequence,
+                    round,
+                    "replay message"
+                );
+
+                let round_info = party
+                    .driver
+                    .round_info()
+                    .map_err(|e| replay_error(index, sequence, e))?;
+                if round_info.can_finalize {
+                    ignored += 1;
+                    continue;
+                }
+                party
+                    .driver
+                    .handle_incoming(message)
+                    .map_err(|e| replay_error(index, sequence, e))?;
+                party.finalize_rounds(
+                    index,
+                    sequence,
+                    &mut pending,
+                )?;
+            }
+            if !progress {
+                break;
+            }
+        }
+
+        let undelivered =
+            self.parties.iter().map(|p| p.schedule.len()).sum();
+        Ok(ReplayOutput {
+            outputs: self
+                .parties
+                .into_iter()
+                .map(|p| p.output)
+                .collect(),
+            undelivered,
+            ignored,
+        })
+    }
+}
+
+impl<D: ProtocolDriver> Party<D> {
+    /// Finalize rounds the same way as the bridge.
+    fn finalize_rounds(
+        &mut self,
+        index: usize,
+        sequence: u64,
+        pending: &mut Pending<D::Message>,
+    ) -> Result<()> {
+        loop {
+            let round_info = self
+                .driver
+                .round_info()
+                .map_err(|e| replay_error(index, sequence, e))?;
+            if !round_info.can_finalize {
+                break;
+            }
+            if let Some(output) = self
+                .driver
+                .try_finalize_round()
+                .map_err(|e| replay_error(index, sequence, e))?
+            {
+                self.output = Some(output);
+                break;
+            }
+            let messages = self
+                .driver
+                .proceed()
+                .map_err(|e| replay_error(index, sequence, e))?;
+            route(pending, index, messages);
+        }
+        Ok(())
+    }
+}
+
+/// Queue messages for the receivers.
+fn route<M: Round>(
+    pending: &mut Pending<M>,
+    sender: usize,
+    messages: Vec<M>,
+) {
+    for message in messages {
+        let receiver = message.receiver().get() as usize - 1;
+        let round = message.round_number().get();
+        pending
+            .entry((sender, receiver, round))
+            .or_default()
+            .push_back(message);
+    }
+}
+
+fn replay_error(
+    index: usize,
+    sequence: u64,
+    error: impl std::error::Error + Send + Sync + 'static,
+) -> Error {
+    Error::Replay {
+        party: index as u16 + 1,
+        sequence,
+        source: Box::new(error),
+    }
+}
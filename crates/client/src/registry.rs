@@ -0,0 +1,237 @@
+//! Registry of accounts and the parties holding key shares.
+//!
+//! An [AccountRegistry] records for each account the threshold,
+//! the roster of parties, where the key share for this party is
+//! stored and when the key was last reshared so that an
+//! orchestrator or co-signer can determine which parties must be
+//! online to sign for an account.
+//!
+//! Accounts are keyed by the verifying key for the account and
+//! may also be found by address, for example `0xabc...`.
+//!
+//! On native platforms the registry is stored as a JSON file
+//! using [AccountRegistry::load] and [AccountRegistry::save];
+//! the registry does not contain any key material.
+use crate::{Error, Result};
+use polysig_protocol::{hex, Parameters};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Party that holds a share of the key for an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountParty {
+    /// Noise transport public key.
+    #[serde(with = "hex::serde")]
+    pub public_key: Vec<u8>,
+    /// Encoded verifying key for the party.
+    #[serde(with = "hex::serde")]
+    pub verifier: Vec<u8>,
+}
+
+/// Account in the registry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    /// Encoded verifying key for the account.
+    #[serde(with = "hex::serde")]
+    pub verifying_key: Vec<u8>,
+    /// Address for the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Parameters for the key.
+    pub parameters: Parameters,
+    /// Parties that hold a share of the key ordered
+    /// by party number.
+    pub parties: Vec<AccountParty>,
+    /// Location of the key share for this party, for example
+    /// the identifier of the key in a keystore.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_location: Option<String>,
+    /// Time the key was last reshared in seconds since
+    /// the UNIX epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reshared_at: Option<u64>,
+}
+
+impl Account {
+    /// Number of parties required to sign.
+    pub fn threshold(&self) -> u16 {
+        self.parameters.threshold
+    }
+
+    /// Party number for a noise transport public key.
+    pub fn party_number(&self, public_key: &[u8]) -> Option<u16> {
+        self.parties
+            .iter()
+            .position(|p| p.public_key == public_key)
+            .map(|index| index as u16 + 1)
+    }
+
+    /// Determine if enough of the parties are online to sign.
+    ///
+    /// Public keys that do not belong to a party are ignored.
+    pub fn has_quorum(&self, online: &[Vec<u8>]) -> bool {
+        let count = self
+            .parties
+            .iter()
+            .filter(|p| online.contains(&p.public_key))
+            .count();
+        count >= self.threshold() as usize
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.parameters
+            .validate_session(self.parties.len(), 1)
+            .map_err(Error::InvalidParameters)
+    }
+}
+
+/// Accounts keyed by verifying key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRegistry {
+    accounts: BTreeMap<String, Account>,
+}
+
+impl AccountRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Number of accounts.
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Determine if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Iterate the accounts.
+    pub fn accounts(&self) -> impl Iterator<Item = &Account> {
+        self.accounts.values()
+    }
+
+    /// Add an account replacing any account with the
+    /// same verifying key.
+    ///
+    /// The number of parties must match the parameters.
+    pub fn insert(
+        &mut self,
+        account: Account,
+    ) -> Result<Option<Account>> {
+        account.validate()?;
+        Ok(self
+            .accounts
+            .insert(hex::encode(&account.verifying_key), account))
+    }
+
+    /// Remove an account.
+    pub fn remove(
+        &mut self,
+        verifying_key: &[u8],
+    ) -> Option<Account> {
+        self.accounts.remove(&hex::encode(verifying_key))
+    }
+
+    /// Account for a verifying key.
+    pub fn get(&self, verifying_key: &[u8]) -> Option<&Account> {
+        self.accounts.get(&hex::encode(verifying_key))
+    }
+
+    /// Find an account by address or hex-encoded verifying key.
+    ///
+    /// Addresses are compared ignoring case.
+    pub fn find(&self, account: &str) -> Option<&Account> {
+        self.accounts
+            .values()
+            .find(|a| {
+                a.address.as_deref().is_some_and(|address| {
+                    address.eq_ignore_ascii_case(account)
+                })
+            })
+            .or_else(|| {
+                let key = account.trim_start_matches("0x");
+                self.accounts.get(&key.to_ascii_lowercase())
+            })
+    }
+
+    /// Threshold and parties that can sign for an account.
+    ///
+    /// The account is found by address or hex-encoded
+    /// verifying key.
+    pub fn signers(
+        &self,
+        account: &str,
+    ) -> Result<(u16, &[AccountParty])> {
+        let found = self.find(account).ok_or_else(|| {
+            Error::UnknownAccount(account.to_owned())
+        })?;
+        Ok((found.threshold(), found.parties.as_slice()))
+    }
+
+    /// Record the parameters and parties for an account after
+    /// the key has been reshared.
+    ///
+    /// The time is seconds since the UNIX epoch.
+    pub fn reshared(
+        &mut self,
+        verifying_key: &[u8],
+        parameters: Parameters,
+        parties: Vec<AccountParty>,
+        reshared_at: u64,
+    ) -> Result<()> {
+        let key = hex::encode(verifying_key);
+        let account = self
+            .accounts
+            .get(&key)
+            .ok_or_else(|| Error::UnknownAccount(key.clone()))?;
+        let account = Account {
+            parameters,
+            parties,
+            reshared_at: Some(reshared_at),
+            ..account.clone()
+        };
+        account.validate()?;
+        self.accounts.insert(key, account);
+        Ok(())
+    }
+
+    /// Load a registry from a file.
+    ///
+    /// An empty registry is returned when the file
+    /// does not exist.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.try_exists()? {
+            return Ok(Default::default());
+        }
+        let contents = std::fs::read(path)?;
+        let registry: Self = serde_json::from_slice(&contents)?;
+        for account in registry.accounts() {
+            account.validate()?;
+        }
+        Ok(registry)
+    }
+
+    /// Save the registry to a file.
+    ///
+    /// The registry is written to a temporary file which
+    /// replaces the file so a partial write never corrupts
+    /// an existing registry.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn save(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+}
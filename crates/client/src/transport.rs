@@ -1,8 +1,14 @@
-use crate::{Client, ClientOptions, EventLoop, Result};
+use crate::{
+    Attestation, Client, ClientOptions, EventLoop, ProgressEvent,
+    Result,
+};
 use async_trait::async_trait;
-use polysig_protocol::{PublicKeys, MeetingId, SessionId, UserId};
+use polysig_protocol::{
+    Encoding, MeetingId, Parameters, PublicKeys, SessionId,
+    SessionRequest, SessionState, SessionToken, UserId,
+};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 /// Enumeration of available transports.
 #[derive(Clone)]
@@ -68,6 +74,27 @@ impl NetworkTransport for Transport {
         }
     }
 
+    async fn send_serialized<S>(
+        &mut self,
+        public_key: &[u8],
+        payload: &S,
+        encoding: Encoding,
+        session_id: Option<SessionId>,
+    ) -> Result<()>
+    where
+        S: Serialize + Send + Sync,
+    {
+        match self {
+            Transport::Relay(client) => {
+                client
+                    .send_serialized(
+                        public_key, payload, encoding, session_id,
+                    )
+                    .await
+            }
+        }
+    }
+
     async fn send_blob(
         &mut self,
         public_key: &[u8],
@@ -83,6 +110,62 @@ impl NetworkTransport for Transport {
         }
     }
 
+    async fn create_group_key(
+        &mut self,
+        session: &SessionState,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client.create_group_key(session).await
+            }
+        }
+    }
+
+    async fn broadcast_json<S>(
+        &mut self,
+        session: &SessionState,
+        payload: &S,
+    ) -> Result<()>
+    where
+        S: Serialize + Send + Sync,
+    {
+        match self {
+            Transport::Relay(client) => {
+                client.broadcast_json(session, payload).await
+            }
+        }
+    }
+
+    async fn broadcast_serialized<S>(
+        &mut self,
+        session: &SessionState,
+        payload: &S,
+        encoding: Encoding,
+    ) -> Result<()>
+    where
+        S: Serialize + Send + Sync,
+    {
+        match self {
+            Transport::Relay(client) => {
+                client
+                    .broadcast_serialized(session, payload, encoding)
+                    .await
+            }
+        }
+    }
+
+    async fn broadcast_blob(
+        &mut self,
+        session: &SessionState,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client.broadcast_blob(session, payload).await
+            }
+        }
+    }
+
     async fn new_meeting(
         &mut self,
         owner_id: UserId,
@@ -119,6 +202,51 @@ impl NetworkTransport for Transport {
         }
     }
 
+    async fn new_session_with_parameters(
+        &mut self,
+        participant_keys: Vec<Vec<u8>>,
+        parameters: Parameters,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client
+                    .new_session_with_parameters(
+                        participant_keys,
+                        parameters,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn new_observed_session(
+        &mut self,
+        participant_keys: Vec<Vec<u8>>,
+        observer_keys: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client
+                    .new_observed_session(
+                        participant_keys,
+                        observer_keys,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn request_session(
+        &mut self,
+        request: SessionRequest,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client.request_session(request).await
+            }
+        }
+    }
+
     async fn register_connection(
         &mut self,
         session_id: &SessionId,
@@ -142,6 +270,28 @@ impl NetworkTransport for Transport {
         }
     }
 
+    async fn resume_session(
+        &mut self,
+        token: SessionToken,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client.resume_session(token).await
+            }
+        }
+    }
+
+    async fn session_digest(
+        &mut self,
+        session_id: SessionId,
+    ) -> Result<()> {
+        match self {
+            Transport::Relay(client) => {
+                client.session_digest(session_id).await
+            }
+        }
+    }
+
     async fn close(&self) -> Result<()> {
         match self {
             Transport::Relay(client) => client.close().await,
@@ -159,6 +309,27 @@ impl Transport {
             Client::new(server, options).await?;
         Ok((Self::Relay(client), event_loop))
     }
+
+    /// Notify the progress sender for the transport.
+    pub(crate) fn notify(&self, event: ProgressEvent) {
+        match self {
+            Transport::Relay(client) => client.notify(event),
+        }
+    }
+
+    /// Attestation exchanged with peers for the transport.
+    pub(crate) fn attestation(&self) -> Option<Arc<dyn Attestation>> {
+        match self {
+            Transport::Relay(client) => client.attestation(),
+        }
+    }
+
+    /// Timeout for each round of a ceremony.
+    pub(crate) fn round_timeout(&self) -> Option<Duration> {
+        match self {
+            Transport::Relay(client) => client.round_timeout(),
+        }
+    }
 }
 
 /// Trait for network clients.
@@ -191,6 +362,23 @@ pub trait NetworkTransport {
     where
         S: Serialize + Send + Sync;
 
+    /// Send a serialized message to a peer.
+    ///
+    /// The encoding must be a serialized encoding; peers
+    /// receive a JSON message event which deserializes the
+    /// payload with the same encoding. CBOR and MessagePack
+    /// are more compact than JSON for messages with binary
+    /// fields.
+    async fn send_serialized<S>(
+        &mut self,
+        public_key: &[u8],
+        payload: &S,
+        encoding: Encoding,
+        session_id: Option<SessionId>,
+    ) -> Result<()>
+    where
+        S: Serialize + Send + Sync;
+
     /// Send a binary message to a peer.
     async fn send_blob(
         &mut self,
@@ -199,6 +387,52 @@ pub trait NetworkTransport {
         session_id: Option<SessionId>,
     ) -> Result<()>;
 
+    /// Create a group key for the broadcasts of this
+    /// participant in a session.
+    ///
+    /// The key is sent to the other participants in the
+    /// session over the peer channels so peers must be
+    /// connected before creating a group key. Participants
+    /// only decrypt the broadcasts of this participant with
+    /// the key; other participants create their own key.
+    async fn create_group_key(
+        &mut self,
+        session: &SessionState,
+    ) -> Result<()>;
+
+    /// Broadcast a JSON message to the other participants in
+    /// a session.
+    ///
+    /// When a group key exists for the session the message is
+    /// encrypted once and fanned out by the server otherwise it
+    /// is sent to each peer.
+    async fn broadcast_json<S>(
+        &mut self,
+        session: &SessionState,
+        payload: &S,
+    ) -> Result<()>
+    where
+        S: Serialize + Send + Sync;
+
+    /// Broadcast a serialized message to the other participants
+    /// in a session.
+    async fn broadcast_serialized<S>(
+        &mut self,
+        session: &SessionState,
+        payload: &S,
+        encoding: Encoding,
+    ) -> Result<()>
+    where
+        S: Serialize + Send + Sync;
+
+    /// Broadcast a binary message to the other participants in
+    /// a session.
+    async fn broadcast_blob(
+        &mut self,
+        session: &SessionState,
+        payload: Vec<u8>,
+    ) -> Result<()>;
+
     /// Create a new meeting point.
     async fn new_meeting(
         &mut self,
@@ -223,6 +457,36 @@ pub trait NetworkTransport {
         participant_keys: Vec<Vec<u8>>,
     ) -> Result<()>;
 
+    /// Create a new session with protocol parameters.
+    ///
+    /// The server rejects the session when the parameters are
+    /// not valid for the participants.
+    async fn new_session_with_parameters(
+        &mut self,
+        participant_keys: Vec<Vec<u8>>,
+        parameters: Parameters,
+    ) -> Result<()>;
+
+    /// Create a new session with observers.
+    ///
+    /// Observers receive the session notifications and the
+    /// transcript hashes of the messages relayed in the session
+    /// but cannot send protocol messages to the participants.
+    async fn new_observed_session(
+        &mut self,
+        participant_keys: Vec<Vec<u8>>,
+        observer_keys: Vec<Vec<u8>>,
+    ) -> Result<()>;
+
+    /// Create a new session from a request.
+    ///
+    /// Use this to combine observers, protocol parameters and
+    /// a deadline for the session.
+    async fn request_session(
+        &mut self,
+        request: SessionRequest,
+    ) -> Result<()>;
+
     /// Register a peer connection in a session.
     async fn register_connection(
         &mut self,
@@ -236,6 +500,29 @@ pub trait NetworkTransport {
         session_id: SessionId,
     ) -> Result<()>;
 
+    /// Resume a session after reconnecting to the server.
+    ///
+    /// The token is issued to each participant in the
+    /// session created and session ready events; the server
+    /// responds with a session resumed event that includes
+    /// a fresh token.
+    async fn resume_session(
+        &mut self,
+        token: SessionToken,
+    ) -> Result<()>;
+
+    /// Request the digest of the messages relayed in a session.
+    ///
+    /// The server responds with a session digest event which
+    /// counts and hashes the encrypted messages relayed in the
+    /// session; use it to detect messages that were lost or
+    /// duplicated by the relay. The server must be configured
+    /// to compute the digest unless the session has observers.
+    async fn session_digest(
+        &mut self,
+        session_id: SessionId,
+    ) -> Result<()>;
+
     /// Close the socket connection.
     async fn close(&self) -> Result<()>;
 }
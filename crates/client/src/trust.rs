@@ -0,0 +1,76 @@
+//! Trust on first use for the server static key.
+//!
+//! Assign a [ServerTrust] to the client options to connect
+//! without knowing the static key of the relay server. The
+//! server must allow a noise pattern that transmits the static
+//! key of the responder such as
+//! [PATTERN_IX](polysig_protocol::PATTERN_IX) and the client
+//! must use the pattern.
+//!
+//! When no keys are pinned the key sent by the server in the
+//! first handshake is pinned and passed to the pin handler so
+//! it can be persisted; subsequent handshakes must present a
+//! pinned key. Pin multiple keys in advance to accept any of
+//! them, for example whilst rotating the identity of a relay.
+use crate::{Error, Result};
+use polysig_protocol::{fingerprint, hex};
+use std::sync::{Arc, Mutex};
+
+/// Handler called with a server key when it is pinned
+/// on first use.
+pub type PinHandler = Arc<dyn Fn(&[u8]) -> Result<()> + Send + Sync>;
+
+/// Server static keys trusted by a client.
+#[derive(Clone, Default)]
+pub struct ServerTrust {
+    keys: Arc<Mutex<Vec<Vec<u8>>>>,
+    on_pin: Option<PinHandler>,
+}
+
+impl ServerTrust {
+    /// Trust the first server key presented in a handshake.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Pin an acceptable server key.
+    pub fn pin(self, key: impl Into<Vec<u8>>) -> Self {
+        self.keys.lock().unwrap().push(key.into());
+        self
+    }
+
+    /// Set the handler called when a key is pinned on first use.
+    pub fn on_pin(
+        mut self,
+        handler: impl Fn(&[u8]) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_pin = Some(Arc::new(handler));
+        self
+    }
+
+    /// Server keys that are currently pinned.
+    pub fn pinned_keys(&self) -> Vec<Vec<u8>> {
+        self.keys.lock().unwrap().clone()
+    }
+
+    /// Verify the key presented by the server, pinning
+    /// the key if no keys are pinned yet.
+    pub(crate) fn verify(&self, server_key: &[u8]) -> Result<()> {
+        let mut keys = self.keys.lock().unwrap();
+        if keys.is_empty() {
+            if let Some(on_pin) = &self.on_pin {
+                on_pin(server_key)?;
+            }
+            tracing::info!(
+                server_key = %fingerprint(server_key),
+                "server key pinned",
+            );
+            keys.push(server_key.to_vec());
+            Ok(())
+        } else if keys.iter().any(|k| k == server_key) {
+            Ok(())
+        } else {
+            Err(Error::ServerKeyNotPinned(hex::encode(server_key)))
+        }
+    }
+}
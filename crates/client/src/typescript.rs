@@ -0,0 +1,55 @@
+//! TypeScript definitions for the types exchanged with
+//! the bindings.
+//!
+//! Declarations are derived from the Rust types so the field
+//! names always match the serde representation; the webassembly
+//! bindings include them in the generated `.d.ts` file.
+use crate::{
+    ErrorInfo, ErrorKind, KeepaliveOptions, ProgressEvent,
+    ServerOptions, SessionOptions, SigningContext,
+};
+use polysig_driver::{
+    Capabilities, CurveBackend, KeyShare, KeySharePolicy, PartyKeys,
+    PartyOptions,
+};
+use polysig_protocol::{KeyType, Keypair, Parameters, PublicKeys};
+use ts_rs::TS;
+
+#[cfg(any(feature = "ecdsa", feature = "cggmp"))]
+use polysig_driver::recoverable_signature::RecoverableSignature;
+
+/// TypeScript declarations for the serialized types.
+///
+/// Each declaration is exported so the output may be written
+/// directly to a `.d.ts` module.
+pub fn declarations() -> String {
+    #[allow(unused_mut)]
+    let mut decls = vec![
+        KeyType::decl(),
+        Keypair::decl(),
+        Parameters::decl(),
+        PublicKeys::decl(),
+        KeyShare::decl(),
+        KeySharePolicy::decl(),
+        PartyKeys::decl(),
+        PartyOptions::<Vec<u8>>::decl(),
+        ProgressEvent::decl(),
+        ErrorKind::decl(),
+        ErrorInfo::decl(),
+        ServerOptions::decl(),
+        SigningContext::decl(),
+        KeepaliveOptions::decl(),
+        SessionOptions::decl(),
+        CurveBackend::decl(),
+        Capabilities::decl(),
+    ];
+
+    #[cfg(any(feature = "ecdsa", feature = "cggmp"))]
+    decls.push(RecoverableSignature::decl());
+
+    decls
+        .into_iter()
+        .map(|decl| format!("export {}\n", decl))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
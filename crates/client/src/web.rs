@@ -6,15 +6,18 @@ use async_stream::stream;
 use futures::{stream::BoxStream, Sink, SinkExt, StreamExt};
 use serde::Serialize;
 use std::{collections::HashSet, pin::Pin, sync::Arc};
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, Mutex, RwLock};
 
 use polysig_protocol::{
-    channel::encrypt_server_channel, decode, encode, hex,
-    snow::Builder, zlib, Encoding, Event, HandshakeMessage,
-    JsonMessage, PublicKeys, MeetingId, MeetingRequest,
-    MeetingResponse, OpaqueMessage, ProtocolState, RequestMessage,
+    channel::encrypt_server_channel, decode, encode, fingerprint,
+    hex, is_zero_rtt, redacted, snow::Builder, zlib, Encoding, Event,
+    HandshakeMessage, JsonMessage, PublicKeys, MeetingId,
+    MeetingRequest,
+    MeetingResponse, OpaqueMessage, Parameters, ProtocolState,
+    RequestMessage,
     ResponseMessage, ServerMessage, SessionId, SessionRequest,
-    TransparentMessage, UserId,
+    SessionState, SessionToken, TransparentMessage, UserId,
+    SUBPROTOCOL,
 };
 
 use crate::{
@@ -23,7 +26,9 @@ use crate::{
         event_loop_run_impl, EventLoop, EventStream, IncomingMessage,
         InternalMessage,
     },
-    ClientOptions, Error, Peers, Result, Server,
+    group::Groups,
+    keepalive::Keepalive,
+    with_peer, ClientOptions, Error, Peers, Result, Server,
 };
 
 type WsMessage = Vec<u8>;
@@ -43,6 +48,7 @@ pub struct WebClient {
     outbound_tx: mpsc::UnboundedSender<InternalMessage>,
     server: Server,
     peers: Peers,
+    groups: Groups,
     ptr: *mut mpsc::Sender<Result<Vec<u8>>>,
 }
 
@@ -52,9 +58,11 @@ impl WebClient {
         server: &str,
         options: ClientOptions,
     ) -> Result<(WebClient, WebEventLoop)> {
+        let handshake = options.server_handshake()?;
+
         tracing::info!("web::websocket {}", server);
 
-        let ws = WebSocket::new(server)?;
+        let ws = WebSocket::new_with_str(server, SUBPROTOCOL)?;
         ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
 
         tracing::info!("web::websocket::created");
@@ -83,24 +91,14 @@ impl WebClient {
                                     buffer,
                                 )) = e
                                 {
-                                    let inflated =
-                                        zlib::inflate(&buffer)
-                                            .unwrap();
-                                    let message: ResponseMessage =
-                                        decode(&inflated)
-                                            .await
-                                            .unwrap();
                                     tracing::error!(
-                                        "send error {:#?}",
-                                        message
+                                        message = %redacted(&buffer),
+                                        "send error",
                                     );
                                 }
                             }
                         } else {
-                            tracing::warn!(
-                                "unknown message event: {:?}",
-                                e.data()
-                            );
+                            tracing::warn!("unknown message event");
                         }
                     });
                 },
@@ -144,24 +142,11 @@ impl WebClient {
         let (outbound_tx, outbound_rx) =
             mpsc::unbounded_channel::<InternalMessage>();
 
-        let server = if let (Some(keypair), Some(server_public_key)) =
-            (&options.keypair, &options.server_public_key)
-        {
-            let builder = Builder::new(options.params()?);
-            let handshake = builder
-                .local_private_key(keypair.private_key())
-                .remote_public_key(server_public_key)
-                .build_initiator()?;
-
-            // State for the server transport
-            Arc::new(RwLock::new(Some(ProtocolState::Handshake(
-                Box::new(handshake),
-            ))))
-        } else {
-            Arc::new(RwLock::new(None))
-        };
+        // State for the server transport
+        let server = Arc::new(RwLock::new(handshake));
 
         let peers = Arc::new(RwLock::new(Default::default()));
+        let groups = Arc::new(RwLock::new(Default::default()));
         let options = Arc::new(options);
 
         tracing::info!("web::websocket::create_client");
@@ -172,6 +157,7 @@ impl WebClient {
             outbound_tx: outbound_tx.clone(),
             server: Arc::clone(&server),
             peers: Arc::clone(&peers),
+            groups: Arc::clone(&groups),
             ptr,
         };
 
@@ -190,15 +176,17 @@ impl WebClient {
             mpsc::unbounded_channel::<IncomingMessage>();
 
         let event_loop: WebEventLoop = EventLoop {
+            keepalive: Keepalive::new(&options),
             options,
             ws_reader,
             ws_writer,
             inbound_tx,
             inbound_rx,
             outbound_tx,
-            outbound_rx,
+            outbound_rx: outbound_rx.into(),
             server,
             peers,
+            groups,
         };
 
         Ok((client, event_loop))
@@ -292,6 +280,20 @@ impl EventLoop<WsMessage, WsError, WsReadStream, WsWriteStream> {
         Ok(())
     }
 
+    /// Release the socket when a ceremony is cancelled or
+    /// the connection is idle.
+    ///
+    /// The client is not closed by the caller when a ceremony
+    /// is cancelled so the socket is closed here instead.
+    async fn handle_cancel(self) -> Result<()> {
+        let ws = &self.ws_writer.ws;
+        ws.set_onopen(None);
+        ws.set_onmessage(None);
+        ws.set_onerror(None);
+        ws.close()?;
+        Ok(())
+    }
+
     event_loop_run_impl!();
 }
 
@@ -0,0 +1,92 @@
+//! Decrypt relayed peer messages off the event loop.
+//!
+//! Each peer has a worker task that decrypts the messages
+//! relayed from the peer in the order they were received; the
+//! cryptography for distinct peers runs in parallel on the
+//! blocking thread pool and the decrypted payloads are sent
+//! back to the event loop.
+use crate::{
+    decrypt_peer_channel, event_loop::IncomingMessage, with_peer,
+    Error, Peers, Result,
+};
+use polysig_protocol::{hex, Encoding, SealedEnvelope, SessionId};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+/// Relayed message waiting to be decrypted.
+type Relayed = (SealedEnvelope, Option<SessionId>);
+
+/// Worker tasks for the peers of a client.
+///
+/// Workers exit when this is dropped.
+#[derive(Default)]
+pub(crate) struct Workers {
+    queues: HashMap<Vec<u8>, mpsc::UnboundedSender<Relayed>>,
+}
+
+impl Workers {
+    /// Queue a relayed message for the worker of a peer.
+    pub fn decrypt(
+        &mut self,
+        peers: &Peers,
+        inbound_tx: &mpsc::UnboundedSender<IncomingMessage>,
+        public_key: Vec<u8>,
+        envelope: SealedEnvelope,
+        session_id: Option<SessionId>,
+    ) {
+        let queue = self
+            .queues
+            .entry(public_key.clone())
+            .or_insert_with(|| {
+                let (tx, rx) = mpsc::unbounded_channel();
+                tokio::task::spawn(run(
+                    peers.clone(),
+                    public_key,
+                    rx,
+                    inbound_tx.clone(),
+                ));
+                tx
+            });
+        if queue.send((envelope, session_id)).is_err() {
+            tracing::warn!("peer worker is not running");
+        }
+    }
+}
+
+/// Decrypt the messages relayed from a peer until the queue
+/// or the event loop is closed.
+async fn run(
+    peers: Peers,
+    public_key: Vec<u8>,
+    mut queue: mpsc::UnboundedReceiver<Relayed>,
+    inbound_tx: mpsc::UnboundedSender<IncomingMessage>,
+) {
+    while let Some((envelope, session_id)) = queue.recv().await {
+        let result = decrypt(&peers, &public_key, envelope).await;
+        let message = IncomingMessage::Decrypted {
+            public_key: public_key.clone(),
+            result: Box::new(result),
+            session_id,
+        };
+        if inbound_tx.send(message).is_err() {
+            break;
+        }
+    }
+}
+
+async fn decrypt(
+    peers: &Peers,
+    public_key: &[u8],
+    envelope: SealedEnvelope,
+) -> Result<(Encoding, Vec<u8>)> {
+    let peer = {
+        let peers = peers.read().await;
+        peers.get(public_key).cloned()
+    }
+    .ok_or_else(|| Error::PeerNotFound(hex::encode(public_key)))?;
+    let (_, contents) = with_peer(&peer, move |state| {
+        decrypt_peer_channel(state, envelope)
+    })
+    .await?;
+    Ok(contents)
+}
@@ -0,0 +1,445 @@
+//! Load testing tool for the relay server.
+//!
+//! Spins up simulated clients against a relay server, groups
+//! the clients into sessions and runs a workload in every
+//! session concurrently; latency percentiles and throughput
+//! are printed when all iterations complete to help plan
+//! relay capacity.
+//!
+//! Establish sessions between 300 clients in groups of three:
+//!
+//! ```no_run
+//! polysig-bench --clients 300 --parties 3 handshake
+//! ```
+//!
+//! Broadcast messages to every peer in each session:
+//!
+//! ```no_run
+//! polysig-bench --clients 60 broadcast --messages 100 --size 4096
+//! ```
+//!
+//! Run CGGMP key generation in each session:
+//!
+//! ```no_run
+//! polysig-bench --clients 9 --iterations 5 keygen --threshold 2
+//! ```
+//!
+//! The relay server URL and public key are read from the
+//! `POLYSIG_RELAY_SERVER` and `POLYSIG_RELAY_PUBLIC_KEY`
+//! environment variables or the corresponding options.
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use futures::{future::try_join_all, StreamExt};
+use polysig_client::{
+    cggmp, wait_for_close, wait_for_session, ClientOptions,
+    EventStream, NetworkTransport, ServerOptions, SessionInitiator,
+    SessionOptions, SessionParticipant, Transport,
+};
+use polysig_driver::{
+    cggmp::{Participant, PartyOptions},
+    synedrion::{self, ecdsa::SigningKey},
+};
+use polysig_protocol::{hex, Event, Keypair, Parameters, SessionId};
+use rand::{rngs::OsRng, RngCore};
+use std::time::{Duration, Instant};
+
+#[cfg(not(debug_assertions))]
+type Params = synedrion::ProductionParams;
+#[cfg(debug_assertions)]
+type Params = synedrion::TestParams;
+
+/// Relay server load testing tool.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct BenchService {
+    /// Relay server URL.
+    #[clap(long, env = "POLYSIG_RELAY_SERVER")]
+    relay_server: String,
+
+    /// Hex-encoded relay server public key.
+    #[clap(long, env = "POLYSIG_RELAY_PUBLIC_KEY")]
+    relay_public_key: String,
+
+    /// Number of simulated clients.
+    #[clap(short, long, default_value = "30")]
+    clients: usize,
+
+    /// Number of clients in each session.
+    #[clap(short, long, default_value = "3")]
+    parties: usize,
+
+    /// Number of times to run the workload.
+    #[clap(short, long, default_value = "1")]
+    iterations: usize,
+
+    #[clap(subcommand)]
+    workload: Workload,
+}
+
+/// Workloads to run in each session.
+#[derive(Debug, Clone, Copy, Subcommand)]
+enum Workload {
+    /// Connect to the relay and establish a session.
+    Handshake,
+    /// Establish a session and send messages to every peer.
+    Broadcast {
+        /// Number of messages each client sends to every peer.
+        #[clap(short, long, default_value = "10")]
+        messages: usize,
+
+        /// Size of each message in bytes.
+        #[clap(short, long, default_value = "1024")]
+        size: usize,
+    },
+    /// Run CGGMP distributed key generation.
+    Keygen {
+        /// Signing threshold.
+        #[clap(short, long, default_value = "2")]
+        threshold: u16,
+    },
+}
+
+/// Relay server connection details.
+#[derive(Debug, Clone)]
+struct Relay {
+    server: String,
+    public_key: Vec<u8>,
+}
+
+/// Client that is a member of an active session.
+struct Member {
+    transport: Transport,
+    stream: EventStream,
+    session_id: SessionId,
+}
+
+/// Result of running a workload in a session.
+struct Sample {
+    latency: Duration,
+    messages: usize,
+}
+
+impl Relay {
+    /// Connect a new client with a generated keypair.
+    async fn connect(
+        &self,
+    ) -> Result<(Transport, EventStream, Keypair)> {
+        let keypair = Keypair::generate()?;
+        let options = ClientOptions {
+            keypair: Some(keypair.clone()),
+            server_public_key: Some(self.public_key.clone()),
+            ..Default::default()
+        };
+        let url = options.url(&self.server);
+        let (mut transport, event_loop) =
+            Transport::new_relay(&url, options).await?;
+        transport.connect().await?;
+        Ok((transport, event_loop.run(), keypair))
+    }
+
+    /// Connect clients and wait for them to establish a session.
+    ///
+    /// The first client initiates the session.
+    async fn session(&self, parties: usize) -> Result<Vec<Member>> {
+        let clients =
+            try_join_all((0..parties).map(|_| self.connect()))
+                .await?;
+        let participants: Vec<Vec<u8>> = clients
+            .iter()
+            .map(|(_, _, keypair)| keypair.public_key().to_vec())
+            .collect();
+        let members = clients.into_iter().enumerate().map(
+            |(index, (transport, mut stream, _))| {
+                let participants = participants.clone();
+                async move {
+                    let (transport, session) = if index == 0 {
+                        let handler = SessionInitiator::new(
+                            transport,
+                            participants,
+                        );
+                        wait_for_session(&mut stream, handler).await?
+                    } else {
+                        let handler =
+                            SessionParticipant::new(transport);
+                        wait_for_session(&mut stream, handler).await?
+                    };
+                    Ok::<_, anyhow::Error>(Member {
+                        transport,
+                        stream,
+                        session_id: session.session_id,
+                    })
+                }
+            },
+        );
+        try_join_all(members).await
+    }
+
+    /// Close the session and the client connections.
+    async fn close(&self, members: Vec<Member>) -> Result<()> {
+        for (index, mut member) in members.into_iter().enumerate() {
+            if index == 0 {
+                member
+                    .transport
+                    .close_session(member.session_id)
+                    .await?;
+            }
+            member.transport.close().await?;
+            wait_for_close(&mut member.stream).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Workload {
+    /// Run the workload for a group of clients.
+    async fn run(
+        self,
+        relay: Relay,
+        parties: usize,
+    ) -> Result<Sample> {
+        let started = Instant::now();
+        let messages = match self {
+            Self::Handshake => {
+                let members = relay.session(parties).await?;
+                relay.close(members).await?;
+                0
+            }
+            Self::Broadcast { messages, size } => {
+                let members = relay.session(parties).await?;
+                let peers: Vec<Vec<u8>> = members
+                    .iter()
+                    .map(|m| m.transport.public_key().to_vec())
+                    .collect();
+                let members =
+                    try_join_all(members.into_iter().map(|member| {
+                        broadcast(member, &peers, messages, size)
+                    }))
+                    .await?;
+                relay.close(members).await?;
+                parties * (parties - 1) * messages
+            }
+            Self::Keygen { threshold } => {
+                keygen(&relay, parties, threshold).await?;
+                0
+            }
+        };
+        Ok(Sample {
+            latency: started.elapsed(),
+            messages,
+        })
+    }
+}
+
+/// Send messages to every peer in the session and wait
+/// to receive the messages from every peer.
+async fn broadcast(
+    mut member: Member,
+    peers: &[Vec<u8>],
+    messages: usize,
+    size: usize,
+) -> Result<Member> {
+    let public_key = member.transport.public_key().to_vec();
+    let payload = vec![0u8; size];
+    for peer in peers.iter().filter(|key| *key != &public_key) {
+        for _ in 0..messages {
+            member
+                .transport
+                .send_blob(
+                    peer,
+                    payload.clone(),
+                    Some(member.session_id),
+                )
+                .await?;
+        }
+    }
+
+    let mut remaining = (peers.len() - 1) * messages;
+    while remaining > 0 {
+        match member.stream.next().await {
+            Some(event) => {
+                if let Event::BinaryMessage { .. } = event? {
+                    remaining -= 1;
+                }
+            }
+            None => bail!("connection closed during broadcast"),
+        }
+    }
+    Ok(member)
+}
+
+/// Run distributed key generation between new participants.
+async fn keygen(
+    relay: &Relay,
+    parties: usize,
+    threshold: u16,
+) -> Result<()> {
+    let keypairs = (0..parties)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let signers: Vec<SigningKey> = (0..parties)
+        .map(|_| SigningKey::random(&mut OsRng))
+        .collect();
+    let public_keys: Vec<Vec<u8>> = keypairs
+        .iter()
+        .map(|keypair| keypair.public_key().to_vec())
+        .collect();
+    let verifiers: Vec<_> = signers
+        .iter()
+        .map(|signer| signer.verifying_key().clone())
+        .collect();
+
+    let mut seed = [0u8; 32];
+    OsRng.fill_bytes(&mut seed);
+    let session_id = synedrion::SessionId::from_seed(&seed);
+
+    let mut tasks = Vec::with_capacity(parties);
+    for (index, (keypair, signer)) in
+        keypairs.into_iter().zip(signers).enumerate()
+    {
+        let party = PartyOptions::new(
+            keypair.public_key().to_vec(),
+            public_keys.clone(),
+            index == 0,
+            verifiers.clone(),
+        )?;
+        let verifier = signer.verifying_key().clone();
+        let participant = Participant::new(signer, verifier, party)?;
+        let options = SessionOptions {
+            keypair,
+            server: ServerOptions {
+                server_url: relay.server.clone(),
+                server_public_key: relay.public_key.clone(),
+                pattern: None,
+            },
+            parameters: Parameters {
+                parties: parties as u16,
+                threshold,
+            },
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        };
+        tasks.push(tokio::task::spawn(cggmp::dkg::<Params>(
+            options,
+            participant,
+            session_id,
+        )));
+    }
+
+    for task in tasks {
+        task.await??;
+    }
+    Ok(())
+}
+
+/// Value at a percentile of sorted samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil();
+    let index = (rank as usize).clamp(1, sorted.len()) - 1;
+    sorted[index]
+}
+
+/// Print latency percentiles and throughput.
+fn report(workload: Workload, samples: &[Sample], elapsed: Duration) {
+    let mut latencies: Vec<Duration> =
+        samples.iter().map(|s| s.latency).collect();
+    latencies.sort();
+    let seconds = elapsed.as_secs_f64();
+    let messages: usize = samples.iter().map(|s| s.messages).sum();
+
+    println!("workload: {:?}", workload);
+    println!("sessions: {}", samples.len());
+    println!("elapsed: {:.3?}", elapsed);
+    for p in [50.0, 90.0, 99.0] {
+        println!("p{}: {:.3?}", p, percentile(&latencies, p));
+    }
+    println!("max: {:.3?}", latencies[latencies.len() - 1]);
+    println!("sessions/s: {:.2}", samples.len() as f64 / seconds);
+    if messages > 0 {
+        println!("messages: {}", messages);
+        println!("messages/s: {:.2}", messages as f64 / seconds);
+    }
+}
+
+/// Parse arguments and run the program.
+async fn run() -> Result<()> {
+    let args = BenchService::parse();
+    if args.parties < 2 {
+        bail!("sessions require at least two parties");
+    }
+    if args.clients < args.parties {
+        bail!(
+            "number of clients ({}) must be at least the \
+             number of parties ({})",
+            args.clients,
+            args.parties
+        );
+    }
+    if let Workload::Keygen { threshold } = args.workload {
+        if threshold == 0 || threshold as usize > args.parties {
+            bail!(
+                "threshold must be between 1 and the number \
+                 of parties"
+            );
+        }
+    }
+
+    let relay = Relay {
+        server: args.relay_server,
+        public_key: hex::decode(args.relay_public_key.trim())?,
+    };
+    let groups = args.clients / args.parties;
+    if args.clients % args.parties != 0 {
+        tracing::warn!(
+            unused = args.clients % args.parties,
+            "clients do not divide into sessions"
+        );
+    }
+
+    let mut samples = Vec::with_capacity(groups * args.iterations);
+    let started = Instant::now();
+    for iteration in 0..args.iterations {
+        tracing::info!(iteration, sessions = groups, "run workload");
+        let tasks = (0..groups).map(|_| {
+            tokio::task::spawn(
+                args.workload.run(relay.clone(), args.parties),
+            )
+        });
+        for task in try_join_all(tasks).await? {
+            samples.push(task?);
+        }
+    }
+
+    report(args.workload, &samples, started.elapsed());
+    Ok(())
+}
+
+#[doc(hidden)]
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    use tracing_subscriber::{
+        layer::SubscriberExt, util::SubscriberInitExt,
+    };
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG")
+                .unwrap_or_else(|_| "polysig_bench=info".into()),
+        ))
+        .with(tracing_subscriber::fmt::layer().without_time())
+        .init();
+
+    if let Err(e) = run().await {
+        tracing::error!("{}", e);
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Checks for server configuration.
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::path::Path;
+use url::Url;
+
+/// Check the TLS certificate and key files are PEM encoded.
+pub fn tls(cert: &Path, key: &Path) -> Result<()> {
+    for (name, path) in [("certificate", cert), ("key", key)] {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                bail!("TLS {} {}: {}", name, path.display(), e)
+            }
+        };
+        if !contents.contains("-----BEGIN ") {
+            bail!(
+                "TLS {} {} is not PEM encoded",
+                name,
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Check CORS origins only contain a scheme, host and port.
+pub fn origins(origins: &[Url]) -> Result<()> {
+    for origin in origins {
+        if !matches!(origin.scheme(), "http" | "https") {
+            bail!("CORS origin {} must use http or https", origin);
+        }
+        if origin.host().is_none() {
+            bail!("CORS origin {} must have a host", origin);
+        }
+        if origin.path() != "/"
+            || origin.query().is_some()
+            || origin.fragment().is_some()
+        {
+            bail!(
+                "CORS origin {} must not have a path or query",
+                origin
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print the effective configuration.
+pub fn print<T: Serialize>(config: &T) -> Result<()> {
+    print!("{}", toml::to_string_pretty(config)?);
+    Ok(())
+}
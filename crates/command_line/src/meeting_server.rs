@@ -9,7 +9,8 @@
 //!
 //! # Server
 //!
-//! Start the meeting room websocket service with a default config:
+//! Start the meeting room websocket service with a default config,
+//! settings may be overridden using environment variables:
 //!
 //! ```no_run
 //! polysig-meeting
@@ -20,20 +21,38 @@
 //! ```no_run
 //! polysig-meeting config.toml
 //! ```
+//!
+//! Check a config file before deploying; prints the
+//! effective configuration:
+//!
+//! ```no_run
+//! polysig-meeting check-config config.toml
+//! ```
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use anyhow::Result;
 use axum_server::Handle;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use polysig_meeting_server::{MeetingServer, ServerConfig};
 use std::path::PathBuf;
 use std::{net::SocketAddr, str::FromStr};
 
+mod check;
+
 /// Meeting room websocket server.
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true
+)]
 struct MeetingService {
+    #[clap(subcommand)]
+    cmd: Option<Command>,
+
     /// Override the interval to poll for expired meeting
     /// rooms in seconds.
     #[clap(long)]
@@ -51,6 +70,27 @@ struct MeetingService {
     config: Option<PathBuf>,
 }
 
+/// Program commands.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Validate a config file and print the effective
+    /// configuration.
+    CheckConfig {
+        /// Config file to check.
+        config: PathBuf,
+    },
+}
+
+/// Validate a config file and print the effective configuration.
+async fn check_config(path: PathBuf) -> Result<()> {
+    let config = ServerConfig::load(&path).await?;
+    if let Some(tls) = &config.tls {
+        check::tls(&tls.cert, &tls.key)?;
+    }
+    check::origins(&config.cors.origins)?;
+    check::print(&config)
+}
+
 /// Start the server.
 async fn start_server(
     bind: String,
@@ -61,7 +101,7 @@ async fn start_server(
     let mut config = if let Some(path) = config {
         ServerConfig::load(&path).await?
     } else {
-        Default::default()
+        ServerConfig::from_env()?
     };
 
     if let Some(interval) = interval {
@@ -82,6 +122,9 @@ async fn start_server(
 /// Parse arguments and run the program.
 async fn run() -> Result<()> {
     let args = MeetingService::parse();
+    if let Some(Command::CheckConfig { config }) = args.cmd {
+        return check_config(config).await;
+    }
     start_server(
         args.bind,
         args.config,
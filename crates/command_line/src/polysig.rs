@@ -0,0 +1,535 @@
+//! Command line client for the polysig library.
+//!
+//! Runs CGGMP key ceremonies against a relay server using
+//! a meeting room to exchange public keys; useful as a
+//! reference for the client library and to smoke test a
+//! deployment.
+//!
+//! # Participants
+//!
+//! Every participant needs a noise keypair for the encrypted
+//! transport and a signing key that identifies the participant
+//! in ceremonies:
+//!
+//! ```no_run
+//! polysig-keygen keypair alice.pem
+//! polysig signer alice.key
+//! ```
+//!
+//! # Meeting
+//!
+//! Create a meeting room for the named participants, the
+//! meeting identifier should be shared with each participant:
+//!
+//! ```no_run
+//! polysig meeting --initiator alice alice bob carol
+//! ```
+//!
+//! Each ceremony requires a new meeting room as the session
+//! identifier is derived from the meeting identifier.
+//!
+//! # Ceremonies
+//!
+//! Each participant runs the ceremony with the meeting
+//! identifier, their name and keys:
+//!
+//! ```no_run
+//! polysig dkg --meeting <ID> --user alice \
+//!   --keypair alice.pem --signer alice.key \
+//!   --threshold 1 alice.json
+//! ```
+//!
+//! Server URLs and the relay server public key are read
+//! from the `POLYSIG_MEETING_SERVER`, `POLYSIG_RELAY_SERVER`
+//! and `POLYSIG_RELAY_PUBLIC_KEY` environment variables or
+//! the corresponding options.
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+use anyhow::{bail, Result};
+use clap::{Args, Parser, Subcommand};
+use futures::StreamExt;
+use polysig_client::{
+    cggmp, meeting, progress_channel, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    cggmp::{Participant, PartyOptions},
+    sha3::{Digest, Keccak256},
+    synedrion::{
+        self,
+        ecdsa::{SigningKey, VerifyingKey},
+        SessionId,
+    },
+    KeyShare,
+};
+use polysig_protocol::{
+    hex, Keypair, MeetingId, Parameters, PublicKeys, UserId,
+};
+use rand::rngs::OsRng;
+use sha2::Sha256;
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+#[cfg(not(debug_assertions))]
+type Params = synedrion::ProductionParams;
+#[cfg(debug_assertions)]
+type Params = synedrion::TestParams;
+
+type ThresholdKeyShare = polysig_driver::cggmp::KeyShare<Params>;
+
+/// Polysig key ceremony client.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Polysig {
+    #[clap(subcommand)]
+    cmd: Command,
+}
+
+/// Program commands.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generate a signing key that identifies a participant.
+    Signer {
+        /// Force overwrite if the file exists.
+        #[clap(short, long)]
+        force: bool,
+
+        /// Write the hex-encoded signing key to this file.
+        file: PathBuf,
+    },
+    /// Create a meeting room for the participants.
+    Meeting {
+        /// Meeting room server URL.
+        #[clap(long, env = "POLYSIG_MEETING_SERVER")]
+        meeting_server: String,
+
+        /// Name of the participant that owns the meeting.
+        #[clap(long)]
+        initiator: String,
+
+        /// Names of all the participants.
+        #[clap(required = true, num_args = 2..)]
+        names: Vec<String>,
+    },
+    /// Run distributed key generation.
+    Dkg {
+        #[clap(flatten)]
+        party: PartyArgs,
+
+        /// Threshold for signing.
+        #[clap(short, long)]
+        threshold: u16,
+
+        /// Force overwrite if the file exists.
+        #[clap(short, long)]
+        force: bool,
+
+        /// Write the key share to this file.
+        file: PathBuf,
+    },
+    /// Sign a message, file digest or prehashed message.
+    ///
+    /// Messages and files are hashed using Keccak256.
+    Sign {
+        #[clap(flatten)]
+        party: PartyArgs,
+
+        /// Key share file.
+        #[clap(long)]
+        key_share: PathBuf,
+
+        #[clap(flatten)]
+        input: SignInput,
+    },
+    /// Reshare a key to a new set of participants.
+    Reshare {
+        #[clap(flatten)]
+        party: PartyArgs,
+
+        /// Key share file; omit for new participants.
+        #[clap(long)]
+        key_share: Option<PathBuf>,
+
+        /// Hex-encoded verifying key for the account.
+        #[clap(long)]
+        verifying_key: String,
+
+        /// Threshold for the existing key shares.
+        #[clap(long)]
+        old_threshold: usize,
+
+        /// Threshold for the new key shares.
+        #[clap(long)]
+        new_threshold: usize,
+
+        /// Force overwrite if the file exists.
+        #[clap(short, long)]
+        force: bool,
+
+        /// Write the new key share to this file.
+        file: PathBuf,
+    },
+    /// Print information about a key share.
+    Inspect {
+        /// Key share file.
+        file: PathBuf,
+    },
+}
+
+/// Options for a participant in a ceremony.
+#[derive(Debug, Args)]
+struct PartyArgs {
+    /// Meeting room server URL.
+    #[clap(long, env = "POLYSIG_MEETING_SERVER")]
+    meeting_server: String,
+
+    /// Relay server URL.
+    #[clap(long, env = "POLYSIG_RELAY_SERVER")]
+    relay_server: String,
+
+    /// Hex-encoded relay server public key.
+    #[clap(long, env = "POLYSIG_RELAY_PUBLIC_KEY")]
+    relay_public_key: String,
+
+    /// Meeting identifier.
+    #[clap(short, long)]
+    meeting: MeetingId,
+
+    /// Name of this participant in the meeting.
+    #[clap(short, long)]
+    user: String,
+
+    /// PEM-encoded noise keypair file.
+    #[clap(short, long)]
+    keypair: PathBuf,
+
+    /// Signing key file.
+    #[clap(short, long)]
+    signer: PathBuf,
+}
+
+/// Input to sign.
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+struct SignInput {
+    /// Message to sign.
+    #[clap(long)]
+    message: Option<String>,
+
+    /// Sign the digest of a file.
+    #[clap(long)]
+    file: Option<PathBuf>,
+
+    /// Hex-encoded 32 byte prehashed message.
+    #[clap(long)]
+    prehash: Option<String>,
+}
+
+/// Participant ready to run a ceremony.
+struct Party {
+    options: SessionOptions,
+    participant: Participant,
+    session_id: SessionId,
+}
+
+impl PartyArgs {
+    /// Join the meeting and prepare the session options.
+    ///
+    /// The first participant in the meeting initiates the
+    /// session and the session identifier is derived from
+    /// the meeting identifier.
+    async fn join(&self, threshold: u16) -> Result<Party> {
+        let keypair =
+            Keypair::decode_pem(fs::read_to_string(&self.keypair)?)?;
+        let signer = read_signer(&self.signer)?;
+        let verifier = signer.verifying_key().clone();
+
+        let data = PublicKeys {
+            public_key: keypair.public_key().to_vec(),
+            verifying_key: verifier.to_sec1_bytes().to_vec(),
+            associated_data: None,
+        };
+        tracing::info!(meeting_id = %self.meeting, "join meeting");
+        let participants = meeting::join(
+            &self.meeting_server,
+            self.meeting,
+            user_id(&self.user),
+            data,
+        )
+        .await?;
+
+        let public_keys: Vec<Vec<u8>> = participants
+            .iter()
+            .map(|(_, keys)| keys.public_key.clone())
+            .collect();
+        let verifiers = participants
+            .iter()
+            .map(|(_, keys)| {
+                VerifyingKey::from_sec1_bytes(&keys.verifying_key)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let is_initiator = public_keys
+            .first()
+            .is_some_and(|key| key == keypair.public_key());
+
+        let party = PartyOptions::new(
+            keypair.public_key().to_vec(),
+            public_keys,
+            is_initiator,
+            verifiers,
+        )?;
+        let participant = Participant::new(signer, verifier, party)?;
+
+        let (progress, mut events) = progress_channel();
+        tokio::task::spawn(async move {
+            while let Some(event) = events.next().await {
+                tracing::info!(event = ?event, "progress");
+            }
+        });
+
+        let options = SessionOptions {
+            keypair,
+            server: ServerOptions {
+                server_url: self.relay_server.clone(),
+                server_public_key: hex::decode(
+                    self.relay_public_key.trim(),
+                )?,
+                pattern: None,
+            },
+            parameters: Parameters {
+                parties: participants.len() as u16,
+                threshold,
+            },
+            policy: None,
+            progress: Some(progress),
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        };
+
+        Ok(Party {
+            options,
+            participant,
+            session_id: SessionId::from_seed(self.meeting.as_bytes()),
+        })
+    }
+}
+
+/// Parse arguments and run the program.
+async fn run() -> Result<()> {
+    let args = Polysig::parse();
+    match args.cmd {
+        Command::Signer { force, file } => {
+            ensure_writable(&file, force)?;
+            let signer = SigningKey::random(&mut OsRng);
+            fs::write(&file, hex::encode(signer.to_bytes()))?;
+            println!(
+                "{}",
+                hex::encode(signer.verifying_key().to_sec1_bytes())
+            );
+        }
+        Command::Meeting {
+            meeting_server,
+            initiator,
+            names,
+        } => {
+            let identifiers =
+                names.iter().map(|n| user_id(n)).collect();
+            let meeting_id = meeting::create(
+                &meeting_server,
+                identifiers,
+                user_id(&initiator),
+            )
+            .await?;
+            println!("{}", meeting_id);
+        }
+        Command::Dkg {
+            party,
+            threshold,
+            force,
+            file,
+        } => {
+            ensure_writable(&file, force)?;
+            let Party {
+                options,
+                participant,
+                session_id,
+            } = party.join(threshold).await?;
+            let key_share = cggmp::dkg::<Params>(
+                options,
+                participant,
+                session_id,
+            )
+            .await?;
+            write_key_share(&file, &key_share)?;
+            print_key_share(&key_share);
+        }
+        Command::Sign {
+            party,
+            key_share,
+            input,
+        } => {
+            let key_share = read_key_share(&key_share)?;
+            let message = input.prehash()?;
+            let Party {
+                options,
+                participant,
+                session_id,
+            } = party.join(key_share.threshold() as u16).await?;
+            let mut selected_parties = BTreeSet::new();
+            selected_parties
+                .extend(participant.party().verifiers().iter());
+            let key_share = key_share.to_key_share(&selected_parties);
+            let signature = cggmp::sign(
+                options,
+                participant,
+                session_id,
+                &key_share,
+                &message,
+            )
+            .await?;
+            let output = serde_json::json!({
+                "signature": hex::encode(&signature.bytes),
+                "recoveryId": signature.recovery_id,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Command::Reshare {
+            party,
+            key_share,
+            verifying_key,
+            old_threshold,
+            new_threshold,
+            force,
+            file,
+        } => {
+            ensure_writable(&file, force)?;
+            let key_share =
+                key_share.as_ref().map(read_key_share).transpose()?;
+            let verifying_key = VerifyingKey::from_sec1_bytes(
+                &hex::decode(verifying_key.trim())?,
+            )?;
+            let Party {
+                options,
+                participant,
+                session_id,
+            } = party.join(new_threshold as u16).await?;
+            let key_share = cggmp::reshare::<Params>(
+                options,
+                participant,
+                session_id,
+                verifying_key,
+                key_share,
+                old_threshold,
+                new_threshold,
+            )
+            .await?;
+            write_key_share(&file, &key_share)?;
+            print_key_share(&key_share);
+        }
+        Command::Inspect { file } => {
+            let key_share =
+                KeyShare::decode(&fs::read_to_string(&file)?)?;
+            println!("tag: {}", key_share.tag()?);
+            println!("version: {}", key_share.version);
+            let key_share: ThresholdKeyShare =
+                (&key_share).try_into()?;
+            print_key_share(&key_share);
+        }
+    }
+    Ok(())
+}
+
+impl SignInput {
+    /// Compute the prehash to sign.
+    fn prehash(&self) -> Result<[u8; 32]> {
+        if let Some(message) = &self.message {
+            Ok(Keccak256::digest(message.as_bytes()).into())
+        } else if let Some(file) = &self.file {
+            Ok(Keccak256::digest(fs::read(file)?).into())
+        } else if let Some(prehash) = &self.prehash {
+            Ok(hex::decode(prehash.trim())?.as_slice().try_into()?)
+        } else {
+            bail!("message, file or prehash is required")
+        }
+    }
+}
+
+/// Identifier for a named participant in a meeting.
+fn user_id(name: &str) -> UserId {
+    let id: [u8; 32] = Sha256::digest(name.as_bytes()).into();
+    id.into()
+}
+
+/// Read a hex-encoded signing key.
+fn read_signer(path: &PathBuf) -> Result<SigningKey> {
+    let bytes = hex::decode(fs::read_to_string(path)?.trim())?;
+    Ok(SigningKey::from_slice(&bytes)?)
+}
+
+/// Read a key share file.
+fn read_key_share(path: &PathBuf) -> Result<ThresholdKeyShare> {
+    let key_share = KeyShare::decode(&fs::read_to_string(path)?)?;
+    Ok((&key_share).try_into()?)
+}
+
+/// Write a key share file.
+fn write_key_share(
+    path: &PathBuf,
+    key_share: &ThresholdKeyShare,
+) -> Result<()> {
+    let key_share: KeyShare = key_share.try_into()?;
+    fs::write(path, key_share.encode()?)?;
+    Ok(())
+}
+
+/// Print the public information for a key share.
+fn print_key_share(key_share: &ThresholdKeyShare) {
+    let verifying_key = key_share.verifying_key();
+    println!("threshold: {}", key_share.threshold());
+    println!(
+        "verifying key: {}",
+        hex::encode(verifying_key.to_sec1_bytes())
+    );
+    println!(
+        "address: {}",
+        polysig_driver::address(
+            verifying_key.to_encoded_point(false).as_bytes()
+        )
+    );
+}
+
+/// Ensure a file can be written.
+fn ensure_writable(path: &PathBuf, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        bail!(
+            "file {} already exists, use --force to overwrite",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+#[doc(hidden)]
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    use tracing_subscriber::{
+        layer::SubscriberExt, util::SubscriberInitExt,
+    };
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG")
+                .unwrap_or_else(|_| "polysig=info".into()),
+        ))
+        .with(tracing_subscriber::fmt::layer().without_time())
+        .init();
+
+    if let Err(e) = run().await {
+        tracing::error!("{}", e);
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
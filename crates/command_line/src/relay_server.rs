@@ -33,20 +33,78 @@
 //! ```no_run
 //! polysig-relay config.toml
 //! ```
+//!
+//! Check a config file before deploying; prints the
+//! effective configuration and the server public key:
+//!
+//! ```no_run
+//! polysig-relay check-config config.toml
+//! ```
+//!
+//! Or configure the server using environment variables
+//! without a config file:
+//!
+//! ```no_run
+//! MPC_RELAY_KEY=server.pem polysig-relay
+//! ```
+//!
+//! # Key rotation
+//!
+//! To rotate the server key generate a new keypair and set
+//! the previous key as the secondary key; handshakes against
+//! either key are accepted and clients that use the previous
+//! key are sent the new public key:
+//!
+//! ```no_run
+//! key = "server-new.pem"
+//! secondary_key = "server.pem"
+//! ```
+//!
+//! Remove the secondary key once the clients have been
+//! updated.
+//!
+//! # Log filter
+//!
+//! To change the log filter of a running server configure a
+//! token for admin requests:
+//!
+//! ```no_run
+//! admin_token_file = "admin.token"
+//! ```
+//!
+//! Then send the filter directives to the admin endpoint:
+//!
+//! ```no_run
+//! curl -X PUT -H "Authorization: Bearer $TOKEN" \
+//!   -d "polysig_relay_server=debug" \
+//!   http://localhost:7007/admin/log-filter
+//! ```
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use anyhow::Result;
 use axum_server::Handle;
-use clap::Parser;
-use polysig_relay_server::{RelayServer, ServerConfig};
+use clap::{Parser, Subcommand};
+use polysig_protocol::hex;
+use polysig_relay_server::{LogFilter, RelayServer, ServerConfig};
 use std::path::PathBuf;
 use std::{net::SocketAddr, str::FromStr};
 
+mod check;
+
 /// Relay websocket server.
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true
+)]
 struct RelayService {
+    #[clap(subcommand)]
+    cmd: Option<Command>,
+
     /// Override the interval to poll for expired sessions in seconds.
     #[clap(long)]
     session_interval: Option<u64>,
@@ -60,17 +118,58 @@ struct RelayService {
     bind: String,
 
     /// Config file to load.
-    config: PathBuf,
+    ///
+    /// When no config file is given settings are read
+    /// from the environment.
+    config: Option<PathBuf>,
+}
+
+/// Program commands.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Validate a config file and print the effective
+    /// configuration.
+    CheckConfig {
+        /// Config file to check.
+        config: PathBuf,
+    },
+}
+
+/// Validate a config file and print the effective configuration.
+async fn check_config(path: PathBuf) -> Result<()> {
+    let (config, keypair) = ServerConfig::load(&path).await?;
+    if let Some(tls) = &config.tls {
+        check::tls(&tls.cert, &tls.key)?;
+    }
+    check::origins(&config.cors.origins)?;
+    if config.allow.as_ref().is_some_and(|allow| allow.is_empty()) {
+        tracing::warn!("allow list is empty, all clients are denied");
+    }
+
+    // Never print the keypair, only the public key
+    println!("# public key {}", hex::encode(keypair.public_key()));
+    if let Some(keypair) = config.secondary_keypair().await? {
+        println!(
+            "# secondary public key {}",
+            hex::encode(keypair.public_key())
+        );
+    }
+    check::print(&config)
 }
 
 /// Start the web server.
 async fn start_server(
     bind: String,
-    config: PathBuf,
+    config: Option<PathBuf>,
     interval: Option<u64>,
     session_timeout: Option<u64>,
+    log_filter: LogFilter,
 ) -> Result<()> {
-    let (mut config, keypair) = ServerConfig::load(&config).await?;
+    let (mut config, keypair) = if let Some(path) = config {
+        ServerConfig::load(&path).await?
+    } else {
+        ServerConfig::from_env().await?
+    };
 
     if let Some(interval) = interval {
         config.session.interval = interval;
@@ -82,19 +181,28 @@ async fn start_server(
 
     let handle = Handle::new();
     let addr = SocketAddr::from_str(&bind)?;
-    let server = RelayServer::new(config, keypair);
+    let server = RelayServer::builder()
+        .config(config)
+        .keypair(keypair)
+        .log_filter(log_filter)
+        .build()
+        .await?;
     server.start(addr, handle).await?;
     Ok(())
 }
 
 /// Parse arguments and run the program.
-async fn run() -> Result<()> {
+async fn run(log_filter: LogFilter) -> Result<()> {
     let args = RelayService::parse();
+    if let Some(Command::CheckConfig { config }) = args.cmd {
+        return check_config(config).await;
+    }
     start_server(
         args.bind,
         args.config,
         args.session_interval,
         args.session_timeout,
+        log_filter,
     )
     .await?;
     Ok(())
@@ -106,16 +214,16 @@ pub async fn main() -> anyhow::Result<()> {
     use tracing_subscriber::{
         layer::SubscriberExt, util::SubscriberInitExt,
     };
+    let (filter, log_filter) = LogFilter::new(
+        &std::env::var("RUST_LOG")
+            .unwrap_or_else(|_| "polysig_relay_server=info".into()),
+    )?;
     tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG").unwrap_or_else(|_| {
-                "polysig_relay_server=info".into()
-            }),
-        ))
+        .with(filter)
         .with(tracing_subscriber::fmt::layer().without_time())
         .init();
 
-    if let Err(e) = run().await {
+    if let Err(e) = run(log_filter).await {
         tracing::error!("{}", e);
     }
 
@@ -0,0 +1,84 @@
+//! Command line tool for the polysig co-signer service,
+//! see [polysig_signer_server::ServerConfig] for configuration details.
+//!
+//! # Installation
+//!
+//! ```no_run
+//! cargo install polysig-server
+//! ```
+//!
+//! # Server
+//!
+//! Start the co-signer service with a config file:
+//!
+//! ```no_run
+//! polysig-signer signer.toml
+//! ```
+//!
+//! Requests must send the token in the `auth_token_file`
+//! as a bearer token:
+//!
+//! ```text
+//! curl -H "Authorization: Bearer $TOKEN" \
+//!   http://localhost:9090/keys
+//! ```
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+use anyhow::Result;
+use axum_server::Handle;
+use clap::Parser;
+use polysig_signer_server::{ServerConfig, SignerServer};
+use std::path::PathBuf;
+use std::{net::SocketAddr, str::FromStr};
+
+/// Co-signer service.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct SignerService {
+    /// Bind to host:port.
+    #[clap(short, long, default_value = "127.0.0.1:9090")]
+    bind: String,
+
+    /// Config file to load.
+    config: PathBuf,
+}
+
+/// Start the server.
+async fn start_server(bind: String, config: PathBuf) -> Result<()> {
+    let (config, keys) = ServerConfig::load(&config).await?;
+    let handle = Handle::new();
+    let addr = SocketAddr::from_str(&bind)?;
+    let server = SignerServer::new(config, keys);
+    server.start(addr, handle).await?;
+    Ok(())
+}
+
+/// Parse arguments and run the program.
+async fn run() -> Result<()> {
+    let args = SignerService::parse();
+    start_server(args.bind, args.config).await?;
+    Ok(())
+}
+
+#[doc(hidden)]
+#[tokio::main]
+pub async fn main() -> anyhow::Result<()> {
+    use tracing_subscriber::{
+        layer::SubscriberExt, util::SubscriberInitExt,
+    };
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG").unwrap_or_else(|_| {
+                "polysig_signer_server=info".into()
+            }),
+        ))
+        .with(tracing_subscriber::fmt::layer().without_time())
+        .init();
+
+    if let Err(e) = run().await {
+        tracing::error!("{}", e);
+    }
+
+    Ok(())
+}
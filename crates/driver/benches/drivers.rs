@@ -0,0 +1,132 @@
+//! Benchmarks for single rounds of the protocol drivers.
+//!
+//! ```text
+//! cargo bench -p polysig-driver --features cggmp,frost-ed25519
+//! ```
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId,
+    Criterion,
+};
+use polysig_driver::{
+    cggmp::KeyGenDriver,
+    frost::ed25519::DkgDriver,
+    frost_ed25519::Identifier,
+    synedrion::{
+        ecdsa::{SigningKey, VerifyingKey},
+        SessionId, TestParams,
+    },
+    ProtocolDriver, Round,
+};
+use polysig_protocol::Parameters;
+use rand::rngs::OsRng;
+use std::num::NonZeroU16;
+
+/// Number of parties and threshold for each benchmark.
+const PARAMETERS: [(u16, u16); 2] = [(3, 2), (5, 3)];
+
+/// Run a single round for all parties.
+///
+/// Every driver proceeds, each message is delivered to the
+/// receiver and every driver finalizes the round.
+fn round<D: ProtocolDriver>(
+    drivers: &mut [D],
+) -> Vec<Option<D::Output>> {
+    let mut messages = Vec::new();
+    for driver in drivers.iter_mut() {
+        messages.extend(driver.proceed().unwrap());
+    }
+    for message in messages {
+        let index = message.receiver().get() as usize - 1;
+        drivers[index].handle_incoming(message).unwrap();
+    }
+    drivers
+        .iter_mut()
+        .map(|driver| {
+            assert!(driver.round_info().unwrap().can_finalize);
+            driver.try_finalize_round().unwrap()
+        })
+        .collect()
+}
+
+fn frost_dkg_drivers(n: u16, t: u16) -> Vec<DkgDriver> {
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    (1..=n)
+        .map(|i| {
+            DkgDriver::new(
+                NonZeroU16::new(i).unwrap(),
+                params.clone(),
+                identifiers.clone(),
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+fn cggmp_keygen_drivers(n: u16) -> Vec<KeyGenDriver<TestParams>> {
+    let signers: Vec<SigningKey> =
+        (0..n).map(|_| SigningKey::random(&mut OsRng)).collect();
+    let verifiers: Vec<VerifyingKey> =
+        signers.iter().map(|s| *s.verifying_key()).collect();
+    let session_id = SessionId::from_seed(b"polysig-bench");
+    signers
+        .into_iter()
+        .map(|signer| {
+            KeyGenDriver::new(
+                session_id.clone(),
+                signer,
+                verifiers.clone(),
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+fn frost_dkg(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frost_ed25519_dkg");
+    for (n, t) in PARAMETERS {
+        let id = format!("{}-of-{}", t, n);
+        group.bench_function(BenchmarkId::new("round_1", &id), |b| {
+            b.iter_batched_ref(
+                || frost_dkg_drivers(n, t),
+                |drivers| round(drivers),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function(BenchmarkId::new("round_2", &id), |b| {
+            b.iter_batched_ref(
+                || {
+                    let mut drivers = frost_dkg_drivers(n, t);
+                    round(&mut drivers);
+                    drivers
+                },
+                |drivers| round(drivers),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn cggmp_keygen(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cggmp_keygen");
+    group.sample_size(10);
+    for (n, _) in PARAMETERS {
+        let id = format!("{}-parties", n);
+        group.bench_function(BenchmarkId::new("round_1", &id), |b| {
+            b.iter_batched_ref(
+                || cggmp_keygen_drivers(n),
+                |drivers| round(drivers),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, frost_dkg, cggmp_keygen);
+criterion_main!(benches);
@@ -0,0 +1,159 @@
+//! Run multiple protocol drivers over a single session.
+use crate::{Error, ProtocolDriver, Round, RoundInfo};
+use polysig_protocol::{PartyNumber, RoundNumber};
+use serde::{Deserialize, Serialize};
+
+/// Message for a driver in a batch.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchMessage<M> {
+    /// Index of the driver in the batch.
+    pub(crate) index: usize,
+    /// Message for the driver.
+    pub(crate) message: M,
+}
+
+impl<M> BatchMessage<M> {
+    /// Index of the driver in the batch.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<M: Round> Round for BatchMessage<M> {
+    fn round_number(&self) -> RoundNumber {
+        self.message.round_number()
+    }
+
+    fn receiver(&self) -> &PartyNumber {
+        self.message.receiver()
+    }
+}
+
+/// Multiplexes a collection of protocol drivers so they
+/// can be run to completion in a single session.
+///
+/// All drivers must execute the same protocol so that the
+/// number of rounds is identical for every driver.
+pub struct BatchDriver<D: ProtocolDriver> {
+    drivers: Vec<D>,
+    outputs: Vec<Option<D::Output>>,
+}
+
+impl<D: ProtocolDriver> BatchDriver<D> {
+    /// Create a new batch driver.
+    pub fn new(drivers: Vec<D>) -> Self {
+        let outputs = drivers.iter().map(|_| None).collect();
+        Self { drivers, outputs }
+    }
+
+    /// Number of drivers in the batch.
+    pub fn len(&self) -> usize {
+        self.drivers.len()
+    }
+
+    /// Determine if the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.drivers.is_empty()
+    }
+
+    fn batch_error(index: usize, error: D::Error) -> Error {
+        Error::Batch(index, Box::new(error))
+    }
+}
+
+impl<D: ProtocolDriver> ProtocolDriver for BatchDriver<D> {
+    type Error = Error;
+    type Message = BatchMessage<D::Message>;
+    type Output = Vec<D::Output>;
+
+    fn culprit(&self, error: &Self::Error) -> Option<PartyNumber> {
+        match error {
+            Error::Batch(index, e) => {
+                let driver = self.drivers.get(*index)?;
+                driver.culprit(e.downcast_ref::<D::Error>()?)
+            }
+            _ => None,
+        }
+    }
+
+    fn round_info(&self) -> Result<RoundInfo, Self::Error> {
+        let mut info = RoundInfo {
+            can_finalize: true,
+            is_echo: false,
+            round_number: 0,
+        };
+        for (index, driver) in self.drivers.iter().enumerate() {
+            if self.outputs[index].is_some() {
+                continue;
+            }
+            let round_info = driver
+                .round_info()
+                .map_err(|e| Self::batch_error(index, e))?;
+            info.can_finalize =
+                info.can_finalize && round_info.can_finalize;
+            info.is_echo = info.is_echo || round_info.is_echo;
+            info.round_number =
+                info.round_number.max(round_info.round_number);
+        }
+        Ok(info)
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>, Self::Error> {
+        let mut messages = Vec::new();
+        for (index, driver) in self.drivers.iter_mut().enumerate() {
+            if self.outputs[index].is_some() {
+                continue;
+            }
+            let driver_messages = driver
+                .proceed()
+                .map_err(|e| Self::batch_error(index, e))?;
+            messages.extend(
+                driver_messages
+                    .into_iter()
+                    .map(|message| BatchMessage { index, message }),
+            );
+        }
+        Ok(messages)
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<(), Self::Error> {
+        let index = message.index;
+        let driver = self
+            .drivers
+            .get_mut(index)
+            .ok_or(Error::BatchIndex(index))?;
+        driver
+            .handle_incoming(message.message)
+            .map_err(|e| Self::batch_error(index, e))
+    }
+
+    fn try_finalize_round(
+        &mut self,
+    ) -> Result<Option<Self::Output>, Self::Error> {
+        for (index, driver) in self.drivers.iter_mut().enumerate() {
+            if self.outputs[index].is_some() {
+                continue;
+            }
+            if let Some(output) = driver
+                .try_finalize_round()
+                .map_err(|e| Self::batch_error(index, e))?
+            {
+                self.outputs[index] = Some(output);
+            }
+        }
+
+        if self.outputs.iter().all(|o| o.is_some()) {
+            Ok(Some(
+                self.outputs
+                    .iter_mut()
+                    .map(|o| o.take().unwrap())
+                    .collect(),
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+}
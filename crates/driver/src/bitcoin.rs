@@ -0,0 +1,288 @@
+//! Sign Bitcoin partially signed transactions ([BIP-174]).
+//!
+//! Taproot inputs are signed using a key path spend with
+//! the [SchnorrSigner] when the internal key of the input
+//! belongs to the signer; segwit v0 and legacy inputs that
+//! reference the public key of the [EcdsaSigner] are signed
+//! with ECDSA. Signatures are added to the PSBT so it can be
+//! finalized and extracted by a wallet.
+//!
+//! Threshold signers compute the signature hashes using
+//! [taproot_sighashes] or [ecdsa_sighashes], sign them with
+//! the signing protocol and add the signatures using
+//! [insert_taproot_signature] or [insert_ecdsa_signature].
+//!
+//! [BIP-174]: https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki
+use crate::{
+    signers::{
+        ecdsa::EcdsaSigner,
+        schnorr::{AuxRand, SchnorrSigner},
+    },
+    Error, Result,
+};
+use bitcoin::{
+    ecdsa, hashes::Hash, psbt::Input, secp256k1,
+    sighash::SighashCache, taproot, CompressedPublicKey,
+    EcdsaSighashType, PublicKey, ScriptBuf, TapSighashType, TxOut,
+};
+use k256::ecdsa::Signature;
+use std::borrow::Cow;
+
+pub use bitcoin::{sighash::Prevouts, Psbt};
+
+/// Signature hash for a taproot key path spend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaprootSighash {
+    /// Index of the input.
+    pub input_index: usize,
+    /// Message to sign.
+    pub sighash: [u8; 32],
+    /// Signature hash type.
+    pub sighash_type: TapSighashType,
+    /// Merkle root of the script tree committed to by
+    /// the output key.
+    pub merkle_root: Option<[u8; 32]>,
+}
+
+/// Signature hash for an ECDSA input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EcdsaSighash {
+    /// Index of the input.
+    pub input_index: usize,
+    /// Message to sign.
+    pub sighash: [u8; 32],
+    /// Signature hash type.
+    pub sighash_type: EcdsaSighashType,
+}
+
+/// Compute the signature hashes for taproot inputs that
+/// are spent using the given x-only internal key.
+pub fn taproot_sighashes(
+    psbt: &Psbt,
+    internal_key: &[u8; 32],
+) -> Result<Vec<TaprootSighash>> {
+    let inputs = psbt
+        .inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| {
+            input
+                .tap_internal_key
+                .is_some_and(|key| &key.serialize() == internal_key)
+        })
+        .collect::<Vec<_>>();
+    if inputs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Taproot signature hashes commit to every spent output
+    let prevouts = psbt
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| spent_output(psbt, index, input))
+        .collect::<Result<Vec<_>>>()?;
+    let prevouts = Prevouts::All(&prevouts);
+
+    let mut cache = SighashCache::new(&psbt.unsigned_tx);
+    let mut sighashes = Vec::new();
+    for (input_index, input) in inputs {
+        let sighash_type = input
+            .sighash_type
+            .map(|ty| ty.taproot_hash_ty())
+            .transpose()?
+            .unwrap_or(TapSighashType::Default);
+        let sighash = cache.taproot_key_spend_signature_hash(
+            input_index,
+            &prevouts,
+            sighash_type,
+        )?;
+        sighashes.push(TaprootSighash {
+            input_index,
+            sighash: sighash.to_byte_array(),
+            sighash_type,
+            merkle_root: input
+                .tap_merkle_root
+                .map(|root| root.to_byte_array()),
+        });
+    }
+    Ok(sighashes)
+}
+
+/// Add a BIP-340 signature for a taproot key path spend.
+pub fn insert_taproot_signature(
+    psbt: &mut Psbt,
+    sighash: &TaprootSighash,
+    signature: &[u8],
+) -> Result<()> {
+    let input = psbt
+        .inputs
+        .get_mut(sighash.input_index)
+        .ok_or(Error::PsbtInputIndex(sighash.input_index))?;
+    input.tap_key_sig = Some(taproot::Signature {
+        signature: secp256k1::schnorr::Signature::from_slice(
+            signature,
+        )?,
+        sighash_type: sighash.sighash_type,
+    });
+    Ok(())
+}
+
+/// Sign taproot inputs spent using the internal key of the
+/// signer, returning the indices of the signed inputs.
+pub fn sign_psbt_taproot(
+    psbt: &mut Psbt,
+    signer: &SchnorrSigner,
+    aux_rand: AuxRand,
+) -> Result<Vec<usize>> {
+    let sighashes =
+        taproot_sighashes(psbt, &signer.x_only_public_key())?;
+    let mut signed = Vec::new();
+    for sighash in sighashes {
+        let signing_key = signer
+            .taproot_tweak(sighash.merkle_root.as_ref())?
+            .into();
+        let tweaked = SchnorrSigner::new(Cow::Owned(signing_key));
+        let signature = tweaked
+            .sign_raw(&sighash.sighash, &aux_rand.to_bytes())?;
+        insert_taproot_signature(
+            psbt,
+            &sighash,
+            &signature.to_bytes(),
+        )?;
+        signed.push(sighash.input_index);
+    }
+    Ok(signed)
+}
+
+/// Compute the signature hashes for segwit v0 and legacy
+/// inputs that reference the compressed SEC1 public key.
+///
+/// Inputs reference the key when it is listed in the BIP-32
+/// derivations of the input, when the spent output pays to
+/// the key hash or when the witness or redeem script contains
+/// the key.
+pub fn ecdsa_sighashes(
+    psbt: &Psbt,
+    public_key: &[u8],
+) -> Result<Vec<EcdsaSighash>> {
+    let public_key = CompressedPublicKey::from_slice(public_key)?;
+    let mut cache = SighashCache::new(&psbt.unsigned_tx);
+    let mut sighashes = Vec::new();
+    for (input_index, input) in psbt.inputs.iter().enumerate() {
+        if input.tap_internal_key.is_some() {
+            continue;
+        }
+        let spent = spent_output(psbt, input_index, input)?;
+        if !references_key(input, &spent, &public_key) {
+            continue;
+        }
+        let (message, sighash_type) =
+            psbt.sighash_ecdsa(input_index, &mut cache)?;
+        let sighash: &[u8; 32] = message.as_ref();
+        sighashes.push(EcdsaSighash {
+            input_index,
+            sighash: *sighash,
+            sighash_type,
+        });
+    }
+    Ok(sighashes)
+}
+
+/// Add an ECDSA signature for the compressed SEC1 public key
+/// to the partial signatures of an input.
+///
+/// The signature is normalized to a low `s` value as
+/// required by the standardness rules.
+pub fn insert_ecdsa_signature(
+    psbt: &mut Psbt,
+    sighash: &EcdsaSighash,
+    public_key: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    let public_key: PublicKey =
+        CompressedPublicKey::from_slice(public_key)?.into();
+    let signature = Signature::from_slice(signature)?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    let input = psbt
+        .inputs
+        .get_mut(sighash.input_index)
+        .ok_or(Error::PsbtInputIndex(sighash.input_index))?;
+    input.partial_sigs.insert(
+        public_key,
+        ecdsa::Signature {
+            signature: secp256k1::ecdsa::Signature::from_compact(
+                &signature.to_bytes(),
+            )?,
+            sighash_type: sighash.sighash_type,
+        },
+    );
+    Ok(())
+}
+
+/// Sign segwit v0 and legacy inputs that reference the
+/// public key of the signer, returning the indices of the
+/// signed inputs.
+pub fn sign_psbt_ecdsa(
+    psbt: &mut Psbt,
+    signer: &EcdsaSigner,
+) -> Result<Vec<usize>> {
+    let public_key = signer.verifying_key().to_sec1_bytes();
+    let sighashes = ecdsa_sighashes(psbt, &public_key)?;
+    let mut signed = Vec::new();
+    for sighash in sighashes {
+        let (signature, _) =
+            signer.sign_prehash_recoverable(&sighash.sighash)?;
+        insert_ecdsa_signature(
+            psbt,
+            &sighash,
+            &public_key,
+            &signature.to_bytes(),
+        )?;
+        signed.push(sighash.input_index);
+    }
+    Ok(signed)
+}
+
+fn spent_output(
+    psbt: &Psbt,
+    input_index: usize,
+    input: &Input,
+) -> Result<TxOut> {
+    if let Some(output) = &input.witness_utxo {
+        return Ok(output.clone());
+    }
+    let previous_output = psbt
+        .unsigned_tx
+        .input
+        .get(input_index)
+        .ok_or(Error::PsbtInputIndex(input_index))?
+        .previous_output;
+    input
+        .non_witness_utxo
+        .as_ref()
+        .and_then(|tx| tx.output.get(previous_output.vout as usize))
+        .cloned()
+        .ok_or(Error::PsbtMissingUtxo(input_index))
+}
+
+fn references_key(
+    input: &Input,
+    spent: &TxOut,
+    public_key: &CompressedPublicKey,
+) -> bool {
+    let key_bytes = public_key.to_bytes();
+    let contains_key = |script: &ScriptBuf| {
+        script
+            .as_bytes()
+            .windows(key_bytes.len())
+            .any(|window| window == key_bytes)
+    };
+    input.bip32_derivation.contains_key(&public_key.0)
+        || spent.script_pubkey
+            == ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash())
+        || spent.script_pubkey
+            == ScriptBuf::new_p2pkh(&public_key.pubkey_hash())
+        || input.witness_script.as_ref().is_some_and(contains_key)
+        || input.redeem_script.as_ref().is_some_and(contains_key)
+}
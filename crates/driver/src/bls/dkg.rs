@@ -0,0 +1,309 @@
+//! Distributed key generation for threshold BLS.
+//!
+//! Round 1: each participant commits to a random polynomial,
+//!          proves knowledge of the constant term and sends a
+//!          secret share to every other participant.
+//!
+//! Participants verify each secret share against the sender's
+//! commitments before combining the shares into a key share.
+use blstrs::{G1Affine, G1Projective, Scalar};
+use ff::Field;
+use group::{Curve, Group};
+use polysig_protocol::Parameters;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, num::NonZeroU16};
+
+use crate::{
+    vss::{FeldmanCommitment, Polynomial, Share},
+    ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::{
+    key::{hash_to_scalar, PublicKey, SigningShare},
+    Error, Identifier, KeyShare, Result, ROUND_1, ROUND_2,
+};
+
+const PROOF_CONTEXT: &[u8] = b"polysig-bls12-381-dkg";
+
+/// Package sent to each participant during key generation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DkgPackage {
+    commitments: Vec<Vec<u8>>,
+    proof: (Vec<u8>, Vec<u8>),
+    secret_share: Vec<u8>,
+}
+
+/// BLS distributed key generation driver.
+pub struct DkgDriver {
+    id: Identifier,
+    params: Parameters,
+    round_number: u8,
+    polynomial: Option<Polynomial<Scalar>>,
+    commitments:
+        BTreeMap<Identifier, FeldmanCommitment<G1Projective>>,
+    secret_shares: BTreeMap<Identifier, Scalar>,
+}
+
+impl DkgDriver {
+    /// Create a key generation driver.
+    ///
+    /// The party number is used as the identifier for
+    /// the key share.
+    pub fn new(
+        party_number: NonZeroU16,
+        params: Parameters,
+    ) -> Result<Self> {
+        let Parameters { parties, threshold } = params;
+        if threshold == 0 || threshold > parties {
+            return Err(Error::InvalidThreshold(threshold, parties));
+        }
+        if party_number.get() > parties {
+            return Err(Error::IndexIdentifier(
+                party_number.get() as usize
+            ));
+        }
+
+        Ok(Self {
+            id: party_number,
+            params,
+            round_number: ROUND_1,
+            polynomial: None,
+            commitments: BTreeMap::new(),
+            secret_shares: BTreeMap::new(),
+        })
+    }
+
+    fn identifiers(&self) -> impl Iterator<Item = Identifier> {
+        (1..=self.params.parties).filter_map(NonZeroU16::new)
+    }
+
+    fn challenge(
+        identifier: &Identifier,
+        constant: &G1Projective,
+        nonce: &G1Projective,
+    ) -> Scalar {
+        hash_to_scalar(&[
+            PROOF_CONTEXT,
+            &identifier.get().to_be_bytes(),
+            &constant.to_affine().to_compressed(),
+            &nonce.to_affine().to_compressed(),
+        ])
+    }
+
+    fn verify_package(
+        &self,
+        sender: &Identifier,
+        package: DkgPackage,
+    ) -> Result<(FeldmanCommitment<G1Projective>, Scalar)> {
+        let threshold = self.params.threshold;
+        if package.commitments.len() != threshold as usize {
+            return Err(Error::CommitmentLength(
+                threshold,
+                package.commitments.len(),
+            ));
+        }
+
+        let commitments = package
+            .commitments
+            .iter()
+            .map(|c| {
+                point(c).ok_or(Error::InvalidProof(sender.get()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let nonce = point(&package.proof.0)
+            .ok_or(Error::InvalidProof(sender.get()))?;
+        let response = field(&package.proof.1)
+            .ok_or(Error::InvalidProof(sender.get()))?;
+        let challenge =
+            Self::challenge(sender, &commitments[0], &nonce);
+        if G1Projective::generator() * response
+            != nonce + commitments[0] * challenge
+        {
+            return Err(Error::InvalidProof(sender.get()));
+        }
+
+        let commitment = FeldmanCommitment::new(commitments)?;
+        let secret_share = field(&package.secret_share)
+            .ok_or(Error::InvalidSecretShare(sender.get()))?;
+        commitment
+            .verify(&Share::new(self.id, secret_share))
+            .map_err(|_| Error::InvalidSecretShare(sender.get()))?;
+
+        Ok((commitment, secret_share))
+    }
+
+    fn key_share(&self) -> Result<KeyShare> {
+        let signing_share =
+            self.secret_shares.values().copied().sum::<Scalar>();
+        let public_key = self
+            .commitments
+            .values()
+            .map(|c| c.public_key())
+            .sum::<G1Projective>();
+
+        let verifying_shares = self
+            .identifiers()
+            .map(|id| {
+                let share = self
+                    .commitments
+                    .values()
+                    .map(|c| c.evaluate(&id))
+                    .sum::<G1Projective>();
+                (id, PublicKey(share.to_affine()))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        if verifying_shares.get(&self.id).map(|k| k.0)
+            != Some(
+                (G1Projective::generator() * signing_share)
+                    .to_affine(),
+            )
+        {
+            return Err(Error::SigningShare);
+        }
+
+        Ok(KeyShare {
+            identifier: self.id,
+            threshold: self.params.threshold,
+            signing_share: SigningShare(signing_share),
+            public_key: PublicKey::from_bytes(
+                &public_key.to_affine().to_compressed(),
+            )?,
+            verifying_shares,
+        })
+    }
+}
+
+impl ProtocolDriver for DkgDriver {
+    type Error = Error;
+    type Message = RoundMessage<DkgPackage, Identifier>;
+    type Output = KeyShare;
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let round_number = self.round_number;
+        let is_echo = false;
+        let can_finalize = match self.round_number {
+            ROUND_2 => {
+                self.commitments.len() == self.params.parties as usize
+            }
+            _ => false,
+        };
+        Ok(RoundInfo {
+            round_number,
+            can_finalize,
+            is_echo,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        match self.round_number {
+            ROUND_1 => {
+                let polynomial = Polynomial::random(
+                    Scalar::random(&mut OsRng),
+                    self.params.threshold,
+                    &mut OsRng,
+                )?;
+                let commitment = polynomial.commit::<G1Projective>();
+                let commitments = commitment.commitments();
+
+                let k = Scalar::random(&mut OsRng);
+                let nonce = G1Projective::generator() * k;
+                let challenge = Self::challenge(
+                    &self.id,
+                    &commitments[0],
+                    &nonce,
+                );
+                let response = k + polynomial.secret() * challenge;
+                let proof = (
+                    nonce.to_affine().to_compressed().to_vec(),
+                    response.to_bytes_be().to_vec(),
+                );
+
+                let encoded = commitments
+                    .iter()
+                    .map(|c| c.to_affine().to_compressed().to_vec())
+                    .collect::<Vec<_>>();
+
+                let messages = self
+                    .identifiers()
+                    .filter(|id| id != &self.id)
+                    .map(|id| RoundMessage {
+                        round: NonZeroU16::new(
+                            self.round_number.into(),
+                        )
+                        .unwrap(),
+                        sender: self.id,
+                        receiver: id,
+                        body: DkgPackage {
+                            commitments: encoded.clone(),
+                            proof: proof.clone(),
+                            secret_share: polynomial
+                                .evaluate(&id)
+                                .to_bytes_be()
+                                .to_vec(),
+                        },
+                    })
+                    .collect();
+
+                self.secret_shares
+                    .insert(self.id, polynomial.evaluate(&self.id));
+                self.commitments.insert(self.id, commitment);
+                self.polynomial = Some(polynomial);
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+                Ok(messages)
+            }
+            _ => Err(Error::InvalidRound(self.round_number)),
+        }
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        let round_number = message.round.get() as u8;
+        match round_number {
+            ROUND_1 => {
+                let sender = message.sender;
+                if sender == self.id
+                    || sender.get() > self.params.parties
+                {
+                    return Err(Error::SenderVerifier);
+                }
+                let (commitments, secret_share) =
+                    self.verify_package(&sender, message.body)?;
+                self.commitments.insert(sender, commitments);
+                self.secret_shares.insert(sender, secret_share);
+                Ok(())
+            }
+            _ => Err(Error::InvalidRound(round_number)),
+        }
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        if self.round_number == ROUND_2
+            && self.commitments.len() == self.params.parties as usize
+        {
+            let key_share = self.key_share()?;
+            self.polynomial = None;
+            Ok(Some(key_share))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn point(bytes: &[u8]) -> Option<G1Projective> {
+    let bytes: [u8; 48] = bytes.try_into().ok()?;
+    let point: Option<G1Affine> =
+        G1Affine::from_compressed(&bytes).into();
+    point.map(G1Projective::from)
+}
+
+fn field(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Scalar::from_bytes_be(&bytes).into()
+}
@@ -0,0 +1,94 @@
+use thiserror::Error;
+
+/// Errors generated by the protocol.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated an invalid round number is encountered.
+    #[error("round {0} is not supported for this protocol")]
+    InvalidRound(u8),
+
+    /// Error generated locating an identifier for a party number.
+    #[error("party number is not a valid protocol identifier")]
+    IndexIdentifier(usize),
+
+    /// Error generated when a message sender is not a participant.
+    #[error("could not locate a verifier for the message sender")]
+    SenderVerifier,
+
+    /// Error generated when the threshold parameters are invalid.
+    #[error("threshold {0} is invalid for {1} parties")]
+    InvalidThreshold(u16, u16),
+
+    /// Error generated when there are fewer signers than the
+    /// threshold of the key share.
+    #[error("{0} signers is less than the threshold {1}")]
+    SignerThreshold(usize, u16),
+
+    /// Error generated when identifiers are not unique.
+    #[error("identifiers must be unique")]
+    DuplicateIdentifier,
+
+    /// Error generated when the commitments in a key generation
+    /// package do not match the threshold.
+    #[error("expected {0} commitments but got {1}")]
+    CommitmentLength(u16, usize),
+
+    /// Error generated when a proof of knowledge for the secret
+    /// of a participant is invalid.
+    #[error("invalid proof of knowledge from participant {0}")]
+    InvalidProof(u16),
+
+    /// Error generated when a secret share does not match the
+    /// commitments of the participant that sent the share.
+    #[error("invalid secret share from participant {0}")]
+    InvalidSecretShare(u16),
+
+    /// Error generated when a signature share is invalid.
+    #[error("invalid signature share from participant {0}")]
+    InvalidSignatureShare(u16),
+
+    /// Error generated when there are no keys or signatures
+    /// to aggregate.
+    #[error("nothing to aggregate")]
+    AggregateEmpty,
+
+    /// Error generated decoding a public key.
+    #[error("invalid public key")]
+    PublicKey,
+
+    /// Error generated decoding a signature.
+    #[error("invalid signature")]
+    Signature,
+
+    /// Error generated decoding a signing share.
+    #[error("invalid signing share")]
+    SigningShare,
+
+    /// Error generated decoding a scalar.
+    #[error("invalid scalar")]
+    Scalar,
+
+    /// Error generated when a signature does not verify.
+    #[error("signature verification failed")]
+    VerifySignature,
+
+    /// Verifiable secret sharing errors.
+    #[error(transparent)]
+    Vss(#[from] crate::vss::Error),
+
+    /// Protocol library errors.
+    #[error(transparent)]
+    Protocol(#[from] polysig_protocol::Error),
+
+    /// JSON error.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl From<Error> for wasm_bindgen::JsValue {
+    fn from(value: Error) -> Self {
+        let s = value.to_string();
+        wasm_bindgen::JsValue::from_str(&s)
+    }
+}
@@ -0,0 +1,278 @@
+//! Keys and signatures for threshold BLS.
+use blstrs::{
+    G1Affine, G1Projective, G2Affine, G2Projective, Scalar,
+};
+use group::{prime::PrimeCurveAffine, Curve, Group};
+use polysig_protocol::pem;
+use serde::{
+    de::Error as _, Deserialize, Deserializer, Serialize, Serializer,
+};
+use sha2::{Digest, Sha512};
+use std::collections::BTreeMap;
+
+use super::{Error, Identifier, Result};
+
+/// Domain separation tag for signatures.
+///
+/// Uses the proof of possession ciphersuite so signatures
+/// are compatible with Ethereum consensus validators.
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+const TAG: &str = "BLS12-381 KEY SHARE";
+const PEM_VERSION: u16 = 1;
+
+/// Public key in G1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(pub(crate) G1Affine);
+
+impl PublicKey {
+    /// Compressed public key bytes.
+    pub fn to_bytes(&self) -> [u8; 48] {
+        self.0.to_compressed()
+    }
+
+    /// Parse a compressed public key.
+    ///
+    /// The identity point is rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 48] =
+            bytes.try_into().map_err(|_| Error::PublicKey)?;
+        let point: Option<G1Affine> =
+            G1Affine::from_compressed(&bytes).into();
+        match point {
+            Some(point) if !bool::from(point.is_identity()) => {
+                Ok(Self(point))
+            }
+            _ => Err(Error::PublicKey),
+        }
+    }
+
+    /// Verify a signature for a message.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Result<()> {
+        let hash = hash_to_g2(message);
+        if blstrs::pairing(&G1Affine::generator(), &signature.0)
+            == blstrs::pairing(&self.0, &hash)
+        {
+            Ok(())
+        } else {
+            Err(Error::VerifySignature)
+        }
+    }
+}
+
+/// Signature in G2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub(crate) G2Affine);
+
+impl Signature {
+    /// Compressed signature bytes.
+    pub fn to_bytes(&self) -> [u8; 96] {
+        self.0.to_compressed()
+    }
+
+    /// Parse a compressed signature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 96] =
+            bytes.try_into().map_err(|_| Error::Signature)?;
+        let point: Option<G2Affine> =
+            G2Affine::from_compressed(&bytes).into();
+        point.map(Self).ok_or(Error::Signature)
+    }
+}
+
+/// Secret share of the group signing key.
+#[derive(Clone, Copy)]
+pub(crate) struct SigningShare(pub(crate) Scalar);
+
+impl SigningShare {
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 32] =
+            bytes.try_into().map_err(|_| Error::SigningShare)?;
+        let scalar: Option<Scalar> =
+            Scalar::from_bytes_be(&bytes).into();
+        scalar.map(Self).ok_or(Error::SigningShare)
+    }
+}
+
+macro_rules! serde_bytes {
+    ($type:ty, $to_bytes:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                let bytes: Vec<u8> = ($to_bytes)(self);
+                bytes.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                let bytes = Vec::<u8>::deserialize(deserializer)?;
+                <$type>::from_bytes(&bytes).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+serde_bytes!(PublicKey, |v: &PublicKey| v.to_bytes().to_vec());
+serde_bytes!(Signature, |v: &Signature| v.to_bytes().to_vec());
+serde_bytes!(SigningShare, |v: &SigningShare| v
+    .0
+    .to_bytes_be()
+    .to_vec());
+
+/// Key share generated by distributed key generation.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyShare {
+    pub(crate) identifier: Identifier,
+    pub(crate) threshold: u16,
+    pub(crate) signing_share: SigningShare,
+    pub(crate) public_key: PublicKey,
+    pub(crate) verifying_shares: BTreeMap<Identifier, PublicKey>,
+}
+
+impl KeyShare {
+    /// Identifier for this key share.
+    pub fn identifier(&self) -> Identifier {
+        self.identifier
+    }
+
+    /// Number of signers required to create a signature.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Group public key.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Public key for the signing share of each participant.
+    pub fn verifying_shares(
+        &self,
+    ) -> &BTreeMap<Identifier, PublicKey> {
+        &self.verifying_shares
+    }
+}
+
+impl std::fmt::Debug for KeyShare {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("identifier", &self.identifier)
+            .field("threshold", &self.threshold)
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryFrom<&KeyShare> for crate::KeyShare {
+    type Error = polysig_protocol::Error;
+
+    fn try_from(
+        value: &KeyShare,
+    ) -> std::result::Result<Self, Self::Error> {
+        let key_share = serde_json::to_vec(value)?;
+        let key_share = pem::Pem::new(TAG, key_share);
+        let key_share = pem::encode(&key_share);
+        Ok(Self {
+            version: PEM_VERSION,
+            contents: key_share,
+            policy: None,
+        })
+    }
+}
+
+impl TryFrom<&crate::KeyShare> for KeyShare {
+    type Error = polysig_protocol::Error;
+
+    fn try_from(
+        value: &crate::KeyShare,
+    ) -> std::result::Result<Self, Self::Error> {
+        let key_share = pem::parse(&value.contents)?;
+        if key_share.tag() != TAG {
+            return Err(polysig_protocol::Error::PemTag(
+                TAG.to_string(),
+                key_share.tag().to_string(),
+            ));
+        }
+        let key_share: KeyShare =
+            serde_json::from_slice(key_share.contents())?;
+        Ok(key_share)
+    }
+}
+
+/// Aggregate public keys into a single public key.
+///
+/// Callers must ensure each public key has a verified proof
+/// of possession to prevent rogue key attacks.
+pub fn aggregate_public_keys(
+    public_keys: &[PublicKey],
+) -> Result<PublicKey> {
+    if public_keys.is_empty() {
+        return Err(Error::AggregateEmpty);
+    }
+    let point = public_keys
+        .iter()
+        .map(|k| G1Projective::from(k.0))
+        .sum::<G1Projective>();
+    if bool::from(point.is_identity()) {
+        return Err(Error::PublicKey);
+    }
+    Ok(PublicKey(point.to_affine()))
+}
+
+/// Aggregate signatures into a single signature.
+pub fn aggregate_signatures(
+    signatures: &[Signature],
+) -> Result<Signature> {
+    if signatures.is_empty() {
+        return Err(Error::AggregateEmpty);
+    }
+    let point = signatures
+        .iter()
+        .map(|s| G2Projective::from(s.0))
+        .sum::<G2Projective>();
+    Ok(Signature(point.to_affine()))
+}
+
+/// Verify an aggregate signature where every public key
+/// signed the same message.
+pub fn verify_aggregate(
+    public_keys: &[PublicKey],
+    message: &[u8],
+    signature: &Signature,
+) -> Result<()> {
+    aggregate_public_keys(public_keys)?.verify(message, signature)
+}
+
+/// Hash a message to a point in G2.
+pub(crate) fn hash_to_g2(message: &[u8]) -> G2Affine {
+    G2Projective::hash_to_curve(message, DST, &[]).to_affine()
+}
+
+/// Hash to a scalar without bias by reducing a 512-bit digest.
+pub(crate) fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    let radix = Scalar::from(256u64);
+    hasher
+        .finalize()
+        .iter()
+        .fold(Scalar::from(0u64), |acc, byte| {
+            acc * radix + Scalar::from(*byte as u64)
+        })
+}
@@ -0,0 +1,33 @@
+//! Threshold BLS signatures over BLS12-381.
+//!
+//! Public keys are in G1 and signatures in G2 which matches
+//! the scheme used by Ethereum consensus validators.
+mod dkg;
+mod error;
+mod key;
+mod sign;
+
+pub use dkg::{DkgDriver, DkgPackage};
+pub use error::Error;
+pub use key::{
+    aggregate_public_keys, aggregate_signatures, verify_aggregate,
+    KeyShare, PublicKey, Signature, DST,
+};
+pub use sign::SignatureDriver;
+
+/// Result type for the BLS protocol.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Identifier for a key share.
+///
+/// Identifiers are the party numbers assigned during
+/// distributed key generation.
+pub type Identifier = std::num::NonZeroU16;
+
+/// Options for each party.
+///
+/// Parties are verified using their transport public keys.
+pub type PartyOptions = crate::PartyOptions<Vec<u8>>;
+
+pub(crate) const ROUND_1: u8 = 1;
+pub(crate) const ROUND_2: u8 = 2;
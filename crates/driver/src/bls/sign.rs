@@ -0,0 +1,218 @@
+//! Threshold signing for BLS.
+//!
+//! Round 1: each signer sends a signature share to every
+//!          other signer.
+//!
+//! Signature shares are verified on arrival and combined
+//! using Lagrange interpolation once a share has been
+//! received from every signer.
+use blstrs::{G1Affine, G2Affine, G2Projective, Scalar};
+use group::{prime::PrimeCurveAffine, Curve, Group};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroU16,
+};
+
+use crate::{vss::lagrange, ProtocolDriver, RoundInfo, RoundMessage};
+
+use super::{
+    key::hash_to_g2, Error, Identifier, KeyShare, Result, Signature,
+    ROUND_1, ROUND_2,
+};
+
+/// BLS signature generation driver.
+pub struct SignatureDriver {
+    identifiers: Vec<Identifier>,
+    id: Identifier,
+    round_number: u8,
+    key_share: KeyShare,
+    message: Vec<u8>,
+    hash: G2Affine,
+    shares: BTreeMap<Identifier, Signature>,
+}
+
+impl SignatureDriver {
+    /// Create a signature generation driver.
+    ///
+    /// The identifiers are the key share identifiers of each
+    /// signer ordered by session party number.
+    pub fn new(
+        party_number: NonZeroU16,
+        identifiers: Vec<Identifier>,
+        key_share: KeyShare,
+        message: Vec<u8>,
+    ) -> Result<Self> {
+        let party_index: usize = party_number.get() as usize;
+        let self_index = party_index - 1;
+        let id = *identifiers
+            .get(self_index)
+            .ok_or(Error::IndexIdentifier(party_index))?;
+        if id != key_share.identifier {
+            return Err(Error::IndexIdentifier(party_index));
+        }
+
+        if identifiers.len() < key_share.threshold as usize {
+            return Err(Error::SignerThreshold(
+                identifiers.len(),
+                key_share.threshold,
+            ));
+        }
+
+        let unique = identifiers.iter().collect::<BTreeSet<_>>();
+        if unique.len() != identifiers.len() {
+            return Err(Error::DuplicateIdentifier);
+        }
+        if identifiers
+            .iter()
+            .any(|id| !key_share.verifying_shares.contains_key(id))
+        {
+            return Err(Error::SenderVerifier);
+        }
+
+        let hash = hash_to_g2(&message);
+
+        Ok(Self {
+            identifiers,
+            id,
+            round_number: ROUND_1,
+            key_share,
+            message,
+            hash,
+            shares: BTreeMap::new(),
+        })
+    }
+
+    fn verify_share(
+        &self,
+        id: &Identifier,
+        share: &Signature,
+    ) -> Result<()> {
+        let verifying_share = self
+            .key_share
+            .verifying_shares
+            .get(id)
+            .ok_or(Error::SenderVerifier)?;
+        if blstrs::pairing(&G1Affine::generator(), &share.0)
+            == blstrs::pairing(&verifying_share.0, &self.hash)
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignatureShare(id.get()))
+        }
+    }
+
+    fn aggregate_signature(&self) -> Result<Signature> {
+        let mut point = G2Projective::identity();
+        for (id, share) in &self.shares {
+            let coefficient =
+                lagrange::<Scalar>(id, &self.identifiers)?;
+            point += G2Projective::from(share.0) * coefficient;
+        }
+        let signature = Signature(point.to_affine());
+        self.key_share
+            .public_key
+            .verify(&self.message, &signature)?;
+        Ok(signature)
+    }
+}
+
+impl ProtocolDriver for SignatureDriver {
+    type Error = Error;
+    type Message = RoundMessage<Signature, Identifier>;
+    type Output = Signature;
+
+    fn culprit(&self, error: &Error) -> Option<NonZeroU16> {
+        match error {
+            Error::InvalidSignatureShare(culprit) => {
+                let index = self
+                    .identifiers
+                    .iter()
+                    .position(|id| id.get() == *culprit)?;
+                NonZeroU16::new((index + 1) as u16)
+            }
+            _ => None,
+        }
+    }
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let round_number = self.round_number;
+        let is_echo = false;
+        let can_finalize = match self.round_number {
+            ROUND_2 => self.shares.len() == self.identifiers.len(),
+            _ => false,
+        };
+        Ok(RoundInfo {
+            round_number,
+            can_finalize,
+            is_echo,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        match self.round_number {
+            ROUND_1 => {
+                let share = Signature(
+                    (self.hash * self.key_share.signing_share.0)
+                        .to_affine(),
+                );
+
+                let mut messages =
+                    Vec::with_capacity(self.identifiers.len() - 1);
+                for (index, id) in self.identifiers.iter().enumerate()
+                {
+                    if id == &self.id {
+                        continue;
+                    }
+
+                    messages.push(RoundMessage {
+                        round: NonZeroU16::new(
+                            self.round_number.into(),
+                        )
+                        .unwrap(),
+                        sender: self.id,
+                        receiver: NonZeroU16::new((index + 1) as u16)
+                            .unwrap(),
+                        body: share,
+                    });
+                }
+
+                self.shares.insert(self.id, share);
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+                Ok(messages)
+            }
+            _ => Err(Error::InvalidRound(self.round_number)),
+        }
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        let round_number = message.round.get() as u8;
+        match round_number {
+            ROUND_1 => {
+                if message.sender == self.id
+                    || !self.identifiers.contains(&message.sender)
+                {
+                    return Err(Error::SenderVerifier);
+                }
+                self.verify_share(&message.sender, &message.body)?;
+                self.shares.insert(message.sender, message.body);
+                Ok(())
+            }
+            _ => Err(Error::InvalidRound(round_number)),
+        }
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        if self.round_number == ROUND_2
+            && self.shares.len() == self.identifiers.len()
+        {
+            Ok(Some(self.aggregate_signature()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Protocols, signers and curve backends compiled into a build.
+//!
+//! Participants in a ceremony may run builds with different
+//! features so orchestrating software can exchange the
+//! capabilities of each participant and check that they are
+//! compatible before starting a ceremony.
+use polysig_protocol::{Encoding, VERSION};
+use serde::{Deserialize, Serialize};
+
+/// Capabilities of a build of the library.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// Version of the library.
+    pub version: String,
+    /// Wire protocol encoding versions.
+    pub encoding_versions: Vec<u16>,
+    /// Encodings for peer message payloads.
+    pub payload_encodings: Vec<String>,
+    /// Threshold protocols.
+    pub protocols: Vec<String>,
+    /// Single-party signers.
+    pub signers: Vec<String>,
+    /// Curves and the backends that implement them.
+    pub curves: Vec<CurveBackend>,
+    /// Other optional features.
+    pub features: Vec<String>,
+}
+
+impl Capabilities {
+    /// Determine if this build supports a protocol.
+    pub fn supports_protocol(&self, protocol: &str) -> bool {
+        self.protocols.iter().any(|p| p == protocol)
+    }
+
+    /// Determine if this build can run a protocol with
+    /// another build.
+    ///
+    /// Both builds must support the protocol and share a
+    /// wire protocol encoding version.
+    pub fn is_compatible(
+        &self,
+        other: &Capabilities,
+        protocol: &str,
+    ) -> bool {
+        self.supports_protocol(protocol)
+            && other.supports_protocol(protocol)
+            && self
+                .encoding_versions
+                .iter()
+                .any(|v| other.encoding_versions.contains(v))
+    }
+}
+
+/// Curve and the library that implements it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct CurveBackend {
+    /// Name of the curve.
+    pub curve: String,
+    /// Name of the crate implementing the curve.
+    pub backend: String,
+}
+
+/// Capabilities compiled into this build.
+pub fn capabilities() -> Capabilities {
+    let protocols = [
+        ("bls", cfg!(feature = "bls")),
+        ("cggmp", cfg!(feature = "cggmp")),
+        ("elgamal", cfg!(feature = "elgamal")),
+        ("frost-ed25519", cfg!(feature = "frost-ed25519")),
+        ("frost-ed448", cfg!(feature = "frost-ed448")),
+        ("frost-p256", cfg!(feature = "frost-p256")),
+        ("frost-ristretto255", cfg!(feature = "frost-ristretto255")),
+        ("frost-secp256k1", cfg!(feature = "frost-secp256k1")),
+        ("frost-secp256k1-tr", cfg!(feature = "frost-secp256k1-tr")),
+        ("musig2", cfg!(feature = "musig2")),
+    ];
+    let signers = [
+        ("ecdsa", cfg!(feature = "ecdsa")),
+        ("eddsa", cfg!(feature = "eddsa")),
+        ("schnorr", cfg!(feature = "schnorr")),
+    ];
+    let curves = [
+        (
+            "secp256k1",
+            "k256",
+            cfg!(any(
+                feature = "cggmp",
+                feature = "ecdsa",
+                feature = "schnorr"
+            )),
+        ),
+        ("ed25519", "ed25519-dalek", cfg!(feature = "eddsa")),
+        ("ed448", "ed448-goldilocks", cfg!(feature = "frost-ed448")),
+        ("p256", "p256", cfg!(feature = "frost-p256")),
+        (
+            "ristretto255",
+            "curve25519-dalek",
+            cfg!(feature = "frost-ristretto255"),
+        ),
+        ("bls12-381", "blstrs", cfg!(feature = "bls")),
+    ];
+    let features = [
+        ("bitcoin", cfg!(feature = "bitcoin")),
+        ("encrypt", cfg!(feature = "encrypt")),
+        ("mlock", cfg!(feature = "mlock")),
+        ("pkcs11", cfg!(feature = "pkcs11")),
+        ("vss", cfg!(feature = "vss")),
+    ];
+
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+        encoding_versions: vec![VERSION],
+        payload_encodings: Encoding::supported()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect(),
+        protocols: enabled(&protocols),
+        signers: enabled(&signers),
+        curves: curves
+            .into_iter()
+            .filter(|(_, _, enabled)| *enabled)
+            .map(|(curve, backend, _)| CurveBackend {
+                curve: curve.to_owned(),
+                backend: backend.to_owned(),
+            })
+            .collect(),
+        features: enabled(&features),
+    }
+}
+
+fn enabled(names: &[(&str, bool)]) -> Vec<String> {
+    names
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
@@ -0,0 +1,239 @@
+//! Public verification bundles for third parties.
+//!
+//! A [VerificationBundle] packages the group verifying key, the
+//! verifying keys of the participants, the threshold and the
+//! ceremony certificate for a key together with the address for
+//! the key. The bundle is signed by one of the participants so
+//! that external systems can check an address against the group
+//! without connecting to any of the participants.
+//!
+//! A bundle is not signed by the group key, anyone can create a
+//! ceremony certificate and endorse it with keys they control, so
+//! verifiers must supply the verifying keys of the participants
+//! they expect; a bundle is only trusted when the participants
+//! in the certificate are exactly those keys.
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::{CeremonyCertificate, CertificateKey, Error, Result};
+
+/// Version of the verification bundle encoding.
+pub const BUNDLE_VERSION: u16 = 1;
+
+const DOMAIN: &[u8] = b"polysig-verification-bundle";
+
+/// Statement signed by the issuer of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleStatement {
+    version: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    verifying_key: Vec<u8>,
+    threshold: u16,
+    participants: Vec<String>,
+    certificate: CeremonyCertificate,
+    issuer: u16,
+    issued_at: u64,
+}
+
+impl BundleStatement {
+    fn digest(&self) -> Result<[u8; 32]> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(DOMAIN);
+        hasher.update(serde_json::to_vec(self)?);
+        Ok(hasher.finalize().into())
+    }
+}
+
+/// Signed bundle used to verify the group for a key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationBundle {
+    statement: BundleStatement,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    signature: Vec<u8>,
+}
+
+impl VerificationBundle {
+    /// Create a bundle for a ceremony certificate.
+    ///
+    /// The issuer is the party number of the participant
+    /// signing the bundle and the issue time is seconds since
+    /// the UNIX epoch.
+    pub fn new<V: CertificateKey>(
+        certificate: CeremonyCertificate,
+        address: Option<String>,
+        issuer: u16,
+        signing_key: &V::SigningKey,
+        issued_at: u64,
+    ) -> Result<Self> {
+        certificate.verify::<V>()?;
+        let ceremony = certificate.ceremony();
+        let statement = BundleStatement {
+            version: BUNDLE_VERSION,
+            address,
+            verifying_key: ceremony.verifying_key().to_vec(),
+            threshold: ceremony.threshold(),
+            participants: ceremony
+                .participants()
+                .iter()
+                .map(polysig_protocol::hex::encode)
+                .collect(),
+            certificate,
+            issuer,
+            issued_at,
+        };
+        let signature =
+            V::endorse(signing_key, &statement.digest()?)?;
+        let bundle = Self {
+            statement,
+            signature,
+        };
+        // Signing key must belong to the issuer
+        bundle.verify_signature::<V>()?;
+        Ok(bundle)
+    }
+
+    /// Address controlled by the group.
+    pub fn address(&self) -> Option<&str> {
+        self.statement.address.as_deref()
+    }
+
+    /// Group verifying key.
+    pub fn verifying_key(&self) -> &[u8] {
+        self.statement.verifying_key.as_slice()
+    }
+
+    /// Number of participants required to sign.
+    pub fn threshold(&self) -> u16 {
+        self.statement.threshold
+    }
+
+    /// Hex-encoded verifying keys of the participants
+    /// ordered by party number.
+    pub fn participants(&self) -> &[String] {
+        self.statement.participants.as_slice()
+    }
+
+    /// Ceremony certificate for the key.
+    pub fn certificate(&self) -> &CeremonyCertificate {
+        &self.statement.certificate
+    }
+
+    /// Party number of the participant that signed the bundle.
+    pub fn issuer(&self) -> u16 {
+        self.statement.issuer
+    }
+
+    /// Time the bundle was signed.
+    pub fn issued_at(&self) -> u64 {
+        self.statement.issued_at
+    }
+
+    /// Encode the bundle as JSON.
+    pub fn encode(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Decode a bundle from JSON.
+    pub fn decode(value: &str) -> Result<Self> {
+        Ok(serde_json::from_str(value)?)
+    }
+
+    /// Verify the bundle.
+    ///
+    /// The participants in the ceremony certificate must be the
+    /// expected participants ordered by party number and every
+    /// participant must have endorsed the certificate, the group
+    /// verifying key, participants and threshold must match the
+    /// certificate and the bundle must be signed by one of the
+    /// participants.
+    pub fn verify<V: CertificateKey>(
+        &self,
+        participants: &[V],
+    ) -> Result<()> {
+        let statement = &self.statement;
+        if statement.version != BUNDLE_VERSION {
+            return Err(Error::Version(statement.version));
+        }
+
+        let certificate = &statement.certificate;
+        certificate.verify_participants(participants)?;
+
+        let ceremony = certificate.ceremony();
+        let participants = ceremony
+            .participants()
+            .iter()
+            .map(polysig_protocol::hex::encode)
+            .collect::<Vec<_>>();
+        if statement.verifying_key != ceremony.verifying_key()
+            || statement.threshold != ceremony.threshold()
+            || statement.participants != participants
+        {
+            return Err(Error::BundleMismatch);
+        }
+
+        self.verify_signature::<V>()
+    }
+
+    /// Verify the bundle for an address.
+    ///
+    /// The address function derives the address from the
+    /// group verifying key, for example an Ethereum address
+    /// for an ECDSA key. Addresses are compared exactly as
+    /// base58 addresses are case-sensitive; the derive function
+    /// must return the address in the same form as the
+    /// address, for example with the checksum for Ethereum.
+    pub fn verify_address<V: CertificateKey>(
+        &self,
+        participants: &[V],
+        address: &str,
+        derive: impl FnOnce(&[u8]) -> String,
+    ) -> Result<()> {
+        self.verify(participants)?;
+        let derived = derive(self.verifying_key());
+        if derived != address
+            || self.address().is_some_and(|a| a != address)
+        {
+            return Err(Error::AddressMismatch(address.to_owned()));
+        }
+        Ok(())
+    }
+
+    fn verify_signature<V: CertificateKey>(&self) -> Result<()> {
+        let issuer = self.statement.issuer;
+        let verifiers =
+            self.statement.certificate.ceremony().verifiers::<V>()?;
+        let verifier = issuer
+            .checked_sub(1)
+            .and_then(|index| verifiers.get(index as usize))
+            .ok_or(Error::BundleIssuer(issuer))?;
+        verifier
+            .verify_endorsement(
+                &self.statement.digest()?,
+                &self.signature,
+            )
+            .map_err(|_| Error::BundleSignature(issuer))
+    }
+}
+
+/// Verify that an encoded bundle controls an address.
+///
+/// The participants are the verifying keys the caller expects
+/// for the group ordered by party number, the bundle is rejected
+/// when the certificate was created for any other keys.
+///
+/// Returns the decoded bundle so callers can check the
+/// threshold for the group.
+pub fn verify_bundle<V: CertificateKey>(
+    bundle: &str,
+    participants: &[V],
+    address: &str,
+    derive: impl FnOnce(&[u8]) -> String,
+) -> Result<VerificationBundle> {
+    let bundle = VerificationBundle::decode(bundle)?;
+    bundle.verify_address(participants, address, derive)?;
+    Ok(bundle)
+}
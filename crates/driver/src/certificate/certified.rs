@@ -0,0 +1,184 @@
+//! Run a protocol driver followed by a certificate endorsement.
+//!
+//! Endorsements may arrive from faster participants before
+//! the protocol completes so they are buffered until the
+//! endorsement round starts.
+use polysig_protocol::{PartyNumber, RoundNumber};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU16;
+
+use crate::{Error, ProtocolDriver, Round, RoundInfo, RoundMessage};
+
+use super::{
+    CeremonyCertificate, CertificateDriver, CertificateKey,
+    Endorsement,
+};
+
+/// Create an endorsement driver from the output of a protocol.
+pub type Endorse<O, V> = Box<
+    dyn FnOnce(&O) -> crate::Result<CertificateDriver<V>>
+        + Send
+        + Sync,
+>;
+
+/// Message for a certified protocol.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum CertifiedMessage<M> {
+    /// Message for the protocol.
+    Protocol(M),
+    /// Endorsement for the ceremony certificate.
+    Endorsement(RoundMessage<Endorsement, NonZeroU16>),
+}
+
+impl<M: Round> Round for CertifiedMessage<M> {
+    fn round_number(&self) -> RoundNumber {
+        match self {
+            Self::Protocol(message) => message.round_number(),
+            Self::Endorsement(message) => message.round_number(),
+        }
+    }
+
+    fn receiver(&self) -> &PartyNumber {
+        match self {
+            Self::Protocol(message) => message.receiver(),
+            Self::Endorsement(message) => message.receiver(),
+        }
+    }
+}
+
+/// Runs a protocol driver to completion and then endorses
+/// a ceremony certificate describing the output.
+pub struct CertifiedDriver<D, V>
+where
+    D: ProtocolDriver,
+    V: CertificateKey,
+{
+    driver: D,
+    endorse: Option<Endorse<D::Output, V>>,
+    output: Option<D::Output>,
+    endorser: Option<CertificateDriver<V>>,
+    pending: Vec<RoundMessage<Endorsement, NonZeroU16>>,
+}
+
+impl<D, V> CertifiedDriver<D, V>
+where
+    D: ProtocolDriver,
+    V: CertificateKey,
+{
+    /// Create a certified protocol driver.
+    ///
+    /// The endorse function is called with the output of
+    /// the protocol to create the endorsement driver.
+    pub fn new(driver: D, endorse: Endorse<D::Output, V>) -> Self {
+        Self {
+            driver,
+            endorse: Some(endorse),
+            output: None,
+            endorser: None,
+            pending: Vec::new(),
+        }
+    }
+
+    fn protocol_error(error: D::Error) -> Error {
+        Error::Certified(Box::new(error))
+    }
+}
+
+impl<D, V> ProtocolDriver for CertifiedDriver<D, V>
+where
+    D: ProtocolDriver,
+    V: CertificateKey,
+{
+    type Error = Error;
+    type Message = CertifiedMessage<D::Message>;
+    type Output = (D::Output, CeremonyCertificate);
+
+    fn culprit(&self, error: &Self::Error) -> Option<PartyNumber> {
+        match error {
+            Error::Certified(e) => {
+                self.driver.culprit(e.downcast_ref::<D::Error>()?)
+            }
+            _ => None,
+        }
+    }
+
+    fn round_info(&self) -> Result<RoundInfo, Self::Error> {
+        if let Some(endorser) = &self.endorser {
+            Ok(endorser.round_info()?)
+        } else {
+            self.driver.round_info().map_err(Self::protocol_error)
+        }
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>, Self::Error> {
+        if let Some(endorser) = &mut self.endorser {
+            Ok(endorser
+                .proceed()?
+                .into_iter()
+                .map(CertifiedMessage::Endorsement)
+                .collect())
+        } else {
+            Ok(self
+                .driver
+                .proceed()
+                .map_err(Self::protocol_error)?
+                .into_iter()
+                .map(CertifiedMessage::Protocol)
+                .collect())
+        }
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<(), Self::Error> {
+        match message {
+            CertifiedMessage::Protocol(message) => {
+                if self.endorser.is_some() {
+                    let round = message.round_number().get() as u8;
+                    return Err(Error::CertifiedRound(round));
+                }
+                self.driver
+                    .handle_incoming(message)
+                    .map_err(Self::protocol_error)
+            }
+            CertifiedMessage::Endorsement(message) => {
+                if let Some(endorser) = &mut self.endorser {
+                    Ok(endorser.handle_incoming(message)?)
+                } else {
+                    self.pending.push(message);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn try_finalize_round(
+        &mut self,
+    ) -> Result<Option<Self::Output>, Self::Error> {
+        if let Some(endorser) = &mut self.endorser {
+            return Ok(endorser.try_finalize_round()?.map(
+                |certificate| {
+                    (self.output.take().unwrap(), certificate)
+                },
+            ));
+        }
+
+        if let Some(output) = self
+            .driver
+            .try_finalize_round()
+            .map_err(Self::protocol_error)?
+        {
+            let endorse = self.endorse.take().unwrap();
+            let mut endorser = endorse(&output)?;
+            for message in self.pending.drain(..) {
+                endorser.handle_incoming(message)?;
+            }
+            self.output = Some(output);
+            self.endorser = Some(endorser);
+        }
+
+        // Endorsement round starts when the driver proceeds
+        Ok(None)
+    }
+}
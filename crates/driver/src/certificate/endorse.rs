@@ -0,0 +1,161 @@
+//! Driver to endorse a ceremony certificate.
+//!
+//! Round 1: each participant sends an endorsement for the
+//!          ceremony statement to every other participant.
+//!
+//! Endorsements are verified on arrival and the certificate
+//! is complete once every participant has endorsed the
+//! ceremony statement.
+use std::{collections::BTreeMap, num::NonZeroU16};
+
+use crate::{ProtocolDriver, RoundInfo, RoundMessage};
+
+use super::{
+    Ceremony, CeremonyCertificate, CertificateKey, Endorsement,
+    Error, Result, ROUND_1, ROUND_2,
+};
+
+/// Ceremony certificate endorsement driver.
+pub struct CertificateDriver<V: CertificateKey> {
+    party_number: NonZeroU16,
+    round_number: u8,
+    ceremony: Ceremony,
+    verifiers: Vec<V>,
+    endorsement: Option<Endorsement>,
+    endorsements: BTreeMap<u16, Endorsement>,
+}
+
+impl<V: CertificateKey> CertificateDriver<V> {
+    /// Create a driver that endorses a ceremony statement.
+    ///
+    /// Timestamps are seconds since the UNIX epoch.
+    pub fn new(
+        party_number: NonZeroU16,
+        ceremony: Ceremony,
+        signing_key: &V::SigningKey,
+        started_at: u64,
+        finished_at: u64,
+    ) -> Result<Self> {
+        let verifiers = ceremony.verifiers::<V>()?;
+        let verifier = verifiers
+            .get(party_number.get() as usize - 1)
+            .ok_or(Error::SenderVerifier)?;
+
+        let endorsement = Endorsement::new::<V>(
+            &ceremony,
+            signing_key,
+            started_at,
+            finished_at,
+        )?;
+        // Signing key must belong to this participant
+        endorsement.verify(
+            &ceremony,
+            party_number.get(),
+            verifier,
+        )?;
+
+        Ok(Self {
+            party_number,
+            round_number: ROUND_1,
+            ceremony,
+            verifiers,
+            endorsement: Some(endorsement),
+            endorsements: BTreeMap::new(),
+        })
+    }
+}
+
+impl<V: CertificateKey> ProtocolDriver for CertificateDriver<V> {
+    type Error = Error;
+    type Message = RoundMessage<Endorsement, NonZeroU16>;
+    type Output = CeremonyCertificate;
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let round_number = self.round_number;
+        let is_echo = false;
+        let can_finalize = match self.round_number {
+            ROUND_2 => {
+                self.endorsements.len() == self.verifiers.len()
+            }
+            _ => false,
+        };
+        Ok(RoundInfo {
+            round_number,
+            can_finalize,
+            is_echo,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        match self.round_number {
+            ROUND_1 => {
+                let endorsement = self
+                    .endorsement
+                    .take()
+                    .ok_or(Error::InvalidRound(self.round_number))?;
+
+                let messages = (1..=self.verifiers.len() as u16)
+                    .filter_map(NonZeroU16::new)
+                    .filter(|n| n != &self.party_number)
+                    .map(|receiver| RoundMessage {
+                        round: NonZeroU16::new(
+                            self.round_number.into(),
+                        )
+                        .unwrap(),
+                        sender: self.party_number,
+                        receiver,
+                        body: endorsement.clone(),
+                    })
+                    .collect();
+
+                self.endorsements
+                    .insert(self.party_number.get(), endorsement);
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+                Ok(messages)
+            }
+            _ => Err(Error::InvalidRound(self.round_number)),
+        }
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        let round_number = message.round.get() as u8;
+        match round_number {
+            ROUND_1 => {
+                let sender = message.sender;
+                if sender == self.party_number {
+                    return Err(Error::SenderVerifier);
+                }
+                let verifier = self
+                    .verifiers
+                    .get(sender.get() as usize - 1)
+                    .ok_or(Error::SenderVerifier)?;
+                message.body.verify(
+                    &self.ceremony,
+                    sender.get(),
+                    verifier,
+                )?;
+                self.endorsements.insert(sender.get(), message.body);
+                Ok(())
+            }
+            _ => Err(Error::InvalidRound(round_number)),
+        }
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        if self.round_number == ROUND_2
+            && self.endorsements.len() == self.verifiers.len()
+        {
+            Ok(Some(CeremonyCertificate {
+                ceremony: self.ceremony.clone(),
+                endorsements: std::mem::take(&mut self.endorsements),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
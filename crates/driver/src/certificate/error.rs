@@ -0,0 +1,125 @@
+use thiserror::Error;
+
+/// Errors generated by ceremony certificates.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated an invalid round number is encountered.
+    #[error("round {0} is not supported for this protocol")]
+    InvalidRound(u8),
+
+    /// Error generated when a message sender is not a participant.
+    #[error("could not locate a verifier for the message sender")]
+    SenderVerifier,
+
+    /// Error generated when the participants do not match the
+    /// parameters for the ceremony.
+    #[error("expected {0} participants but got {1}")]
+    ParticipantLength(u16, usize),
+
+    /// Error generated when the participants in a certificate
+    /// are not the expected participants.
+    #[error("certificate participants do not match")]
+    ParticipantMismatch,
+
+    /// Error generated when the threshold parameters are invalid.
+    #[error("threshold {0} is invalid for {1} parties")]
+    InvalidThreshold(u16, u16),
+
+    /// Error generated when the certificate version is not
+    /// supported.
+    #[error("certificate version {0} is not supported")]
+    Version(u16),
+
+    /// Error generated when a participant has not endorsed
+    /// a certificate.
+    #[error("missing endorsement from participant {0}")]
+    MissingEndorsement(u16),
+
+    /// Error generated when an endorsement is from a party
+    /// that is not a participant.
+    #[error("endorsement from unknown participant {0}")]
+    UnknownEndorsement(u16),
+
+    /// Error generated when an endorsement signature is invalid.
+    #[error("invalid endorsement from participant {0}")]
+    InvalidEndorsement(u16),
+
+    /// Error generated when the finish time of an endorsement
+    /// is before the start time.
+    #[error(
+        "invalid timestamps in endorsement from participant {0}"
+    )]
+    Timestamp(u16),
+
+    /// Error generated when a transfer does not bind a new
+    /// transport key.
+    #[error("transfer must bind a new transport key")]
+    TransferBinding,
+
+    /// Error generated when a transfer signature is invalid.
+    #[error("invalid signature for share transfer")]
+    TransferSignature,
+
+    /// Error generated when the participant for a transfer
+    /// is not in the roster.
+    #[error("transferred participant is not in the roster")]
+    TransferParticipant,
+
+    /// Error generated when the new transport key for a
+    /// transfer is already in the roster.
+    #[error(
+        "transport key for the new device is already a participant"
+    )]
+    TransferDuplicate,
+
+    /// Error generated when a key share is received before
+    /// the transfer has been endorsed.
+    #[error("key share received before the transfer was endorsed")]
+    TransferKeyShare,
+
+    /// Error generated when the keys or threshold in a
+    /// verification bundle do not match the certificate.
+    #[error(
+        "verification bundle does not match the ceremony certificate"
+    )]
+    BundleMismatch,
+
+    /// Error generated when the issuer of a verification
+    /// bundle is not a participant.
+    #[error("bundle issuer {0} is not a participant")]
+    BundleIssuer(u16),
+
+    /// Error generated when the signature for a verification
+    /// bundle is invalid.
+    #[error("invalid bundle signature from participant {0}")]
+    BundleSignature(u16),
+
+    /// Error generated when an address is not the address
+    /// for the key in a verification bundle.
+    #[error("address {0} is not controlled by the group")]
+    AddressMismatch(String),
+
+    /// Error generated creating an endorsement signature.
+    #[error("failed to sign certificate: {0}")]
+    Sign(String),
+
+    /// Error generated decoding a verifying key.
+    #[error("invalid verifying key")]
+    VerifyingKey,
+
+    /// Protocol library errors.
+    #[error(transparent)]
+    Protocol(#[from] polysig_protocol::Error),
+
+    /// JSON error.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl From<Error> for wasm_bindgen::JsValue {
+    fn from(value: Error) -> Self {
+        let s = value.to_string();
+        wasm_bindgen::JsValue::from_str(&s)
+    }
+}
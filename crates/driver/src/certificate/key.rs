@@ -0,0 +1,135 @@
+//! Keys used to endorse ceremony certificates.
+use super::{Error, Result};
+
+/// Verifying key for participants that endorse certificates.
+///
+/// Implemented for the verifying key types used to identify
+/// participants in the key generation protocols.
+pub trait CertificateKey: Sized {
+    /// Signing key used to create endorsements.
+    type SigningKey;
+
+    /// Sign a certificate digest.
+    fn endorse(
+        signing_key: &Self::SigningKey,
+        digest: &[u8],
+    ) -> Result<Vec<u8>>;
+
+    /// Verify an endorsement signature for a digest.
+    fn verify_endorsement(
+        &self,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<()>;
+
+    /// Encode the verifying key.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decode a verifying key.
+    fn decode(bytes: &[u8]) -> Result<Self>;
+}
+
+#[cfg(any(feature = "cggmp", feature = "ecdsa"))]
+impl CertificateKey for k256::ecdsa::VerifyingKey {
+    type SigningKey = k256::ecdsa::SigningKey;
+
+    fn endorse(
+        signing_key: &Self::SigningKey,
+        digest: &[u8],
+    ) -> Result<Vec<u8>> {
+        use k256::ecdsa::{signature::Signer, Signature};
+        let signature: Signature = signing_key
+            .try_sign(digest)
+            .map_err(|e| Error::Sign(e.to_string()))?;
+        Ok(signature.to_bytes().to_vec())
+    }
+
+    fn verify_endorsement(
+        &self,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        use k256::ecdsa::{signature::Verifier, Signature};
+        let signature = Signature::from_slice(signature)
+            .map_err(|_| Error::InvalidEndorsement(0))?;
+        self.verify(digest, &signature)
+            .map_err(|_| Error::InvalidEndorsement(0))
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.to_sec1_bytes().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        Self::from_sec1_bytes(bytes).map_err(|_| Error::VerifyingKey)
+    }
+}
+
+#[cfg(feature = "schnorr")]
+impl CertificateKey for k256::schnorr::VerifyingKey {
+    type SigningKey = k256::schnorr::SigningKey;
+
+    fn endorse(
+        signing_key: &Self::SigningKey,
+        digest: &[u8],
+    ) -> Result<Vec<u8>> {
+        use k256::schnorr::{signature::Signer, Signature};
+        let signature: Signature = signing_key
+            .try_sign(digest)
+            .map_err(|e| Error::Sign(e.to_string()))?;
+        Ok(signature.to_bytes().to_vec())
+    }
+
+    fn verify_endorsement(
+        &self,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        use k256::schnorr::{signature::Verifier, Signature};
+        let signature = Signature::try_from(signature)
+            .map_err(|_| Error::InvalidEndorsement(0))?;
+        self.verify(digest, &signature)
+            .map_err(|_| Error::InvalidEndorsement(0))
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes).map_err(|_| Error::VerifyingKey)
+    }
+}
+
+#[cfg(feature = "eddsa")]
+impl CertificateKey for ed25519_dalek::VerifyingKey {
+    type SigningKey = ed25519_dalek::SigningKey;
+
+    fn endorse(
+        signing_key: &Self::SigningKey,
+        digest: &[u8],
+    ) -> Result<Vec<u8>> {
+        use ed25519_dalek::Signer;
+        Ok(signing_key.sign(digest).to_bytes().to_vec())
+    }
+
+    fn verify_endorsement(
+        &self,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        use ed25519_dalek::{Signature, Verifier};
+        let signature = Signature::from_slice(signature)
+            .map_err(|_| Error::InvalidEndorsement(0))?;
+        self.verify(digest, &signature)
+            .map_err(|_| Error::InvalidEndorsement(0))
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes).map_err(|_| Error::VerifyingKey)
+    }
+}
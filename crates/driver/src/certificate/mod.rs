@@ -0,0 +1,321 @@
+//! Ceremony certificates for distributed key generation.
+//!
+//! Once key generation completes each participant signs a
+//! statement describing the ceremony with the signing key for
+//! the participant; the collected endorsements form a
+//! certificate that auditors can later verify to confirm which
+//! parties participated in creating a key.
+//!
+//! A participant moving to a new device transfers the key share
+//! with a [TransferDriver]; both devices sign a statement binding
+//! the keys of the new device to the participant which forms a
+//! [TransferCertificate] used to update the roster for the key.
+//!
+//! Share a [VerificationBundle] with external systems that know
+//! the keys of the participants so they can verify the address
+//! for the group.
+use polysig_protocol::{Parameters, SessionId};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::BTreeMap;
+
+mod bundle;
+mod certified;
+mod endorse;
+mod error;
+mod key;
+mod transfer;
+
+pub use bundle::{verify_bundle, VerificationBundle, BUNDLE_VERSION};
+pub use certified::{CertifiedDriver, CertifiedMessage, Endorse};
+pub use endorse::CertificateDriver;
+pub use error::Error;
+pub use key::CertificateKey;
+pub use transfer::{
+    Binding, ShareTransfer, TransferCertificate, TransferDriver,
+    TransferMessage, TRANSFER_VERSION,
+};
+
+/// Result type for ceremony certificates.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Version of the certificate encoding.
+pub const CERTIFICATE_VERSION: u16 = 1;
+
+const DOMAIN: &[u8] = b"polysig-ceremony-certificate";
+
+pub(crate) const ROUND_1: u8 = 1;
+pub(crate) const ROUND_2: u8 = 2;
+
+/// Statement describing a key generation ceremony.
+///
+/// Every participant must compute an identical statement
+/// otherwise the endorsements will not verify.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ceremony {
+    version: u16,
+    protocol: String,
+    session_id: SessionId,
+    parties: u16,
+    threshold: u16,
+    participants: Vec<Vec<u8>>,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    verifying_key: Vec<u8>,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    transcript_hash: [u8; 32],
+}
+
+impl Ceremony {
+    /// Create a ceremony statement.
+    ///
+    /// The participants are the verifying keys for each party
+    /// ordered by party number and the transcript is the public
+    /// output of key generation that every party agrees on.
+    pub fn new<V: CertificateKey>(
+        protocol: impl Into<String>,
+        session_id: SessionId,
+        parameters: Parameters,
+        participants: &[V],
+        verifying_key: Vec<u8>,
+        transcript: &[u8],
+    ) -> Result<Self> {
+        let Parameters { parties, threshold } = parameters;
+        if participants.len() != parties as usize {
+            return Err(Error::ParticipantLength(
+                parties,
+                participants.len(),
+            ));
+        }
+        if threshold == 0 || threshold > parties {
+            return Err(Error::InvalidThreshold(threshold, parties));
+        }
+
+        Ok(Self {
+            version: CERTIFICATE_VERSION,
+            protocol: protocol.into(),
+            session_id,
+            parties,
+            threshold,
+            participants: participants
+                .iter()
+                .map(|v| v.encode())
+                .collect(),
+            verifying_key,
+            transcript_hash: Sha3_256::digest(transcript).into(),
+        })
+    }
+
+    /// Name of the key generation protocol.
+    pub fn protocol(&self) -> &str {
+        &self.protocol
+    }
+
+    /// Session identifier for the ceremony.
+    pub fn session_id(&self) -> &SessionId {
+        &self.session_id
+    }
+
+    /// Number of parties.
+    pub fn parties(&self) -> u16 {
+        self.parties
+    }
+
+    /// Threshold for the key.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Encoded verifying keys of the participants ordered
+    /// by party number.
+    pub fn participants(&self) -> &[Vec<u8>] {
+        self.participants.as_slice()
+    }
+
+    /// Verifying key generated by the ceremony.
+    pub fn verifying_key(&self) -> &[u8] {
+        self.verifying_key.as_slice()
+    }
+
+    /// Hash of the public key generation transcript.
+    pub fn transcript_hash(&self) -> &[u8; 32] {
+        &self.transcript_hash
+    }
+
+    /// Digest signed by a participant.
+    pub(crate) fn digest(
+        &self,
+        started_at: u64,
+        finished_at: u64,
+    ) -> Result<[u8; 32]> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(DOMAIN);
+        hasher.update(serde_json::to_vec(self)?);
+        hasher.update(started_at.to_be_bytes());
+        hasher.update(finished_at.to_be_bytes());
+        Ok(hasher.finalize().into())
+    }
+
+    /// Decode the verifying keys of the participants.
+    pub(crate) fn verifiers<V: CertificateKey>(
+        &self,
+    ) -> Result<Vec<V>> {
+        self.participants
+            .iter()
+            .map(|bytes| V::decode(bytes))
+            .collect()
+    }
+}
+
+/// Signature by a participant over a ceremony statement.
+///
+/// Timestamps are seconds since the UNIX epoch as observed
+/// by the participant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Endorsement {
+    started_at: u64,
+    finished_at: u64,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    signature: Vec<u8>,
+}
+
+impl Endorsement {
+    /// Sign a ceremony statement.
+    pub fn new<V: CertificateKey>(
+        ceremony: &Ceremony,
+        signing_key: &V::SigningKey,
+        started_at: u64,
+        finished_at: u64,
+    ) -> Result<Self> {
+        let digest = ceremony.digest(started_at, finished_at)?;
+        Ok(Self {
+            started_at,
+            finished_at,
+            signature: V::endorse(signing_key, &digest)?,
+        })
+    }
+
+    /// Time the participant started the ceremony.
+    pub fn started_at(&self) -> u64 {
+        self.started_at
+    }
+
+    /// Time the participant finished the ceremony.
+    pub fn finished_at(&self) -> u64 {
+        self.finished_at
+    }
+
+    /// Signature over the ceremony statement.
+    pub fn signature(&self) -> &[u8] {
+        self.signature.as_slice()
+    }
+
+    /// Verify this endorsement for a party.
+    pub(crate) fn verify<V: CertificateKey>(
+        &self,
+        ceremony: &Ceremony,
+        party_number: u16,
+        verifier: &V,
+    ) -> Result<()> {
+        if self.finished_at < self.started_at {
+            return Err(Error::Timestamp(party_number));
+        }
+        let digest =
+            ceremony.digest(self.started_at, self.finished_at)?;
+        verifier
+            .verify_endorsement(&digest, &self.signature)
+            .map_err(|_| Error::InvalidEndorsement(party_number))
+    }
+}
+
+/// Certificate for a key generation ceremony.
+///
+/// Contains an endorsement from every participant keyed
+/// by party number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CeremonyCertificate {
+    ceremony: Ceremony,
+    endorsements: BTreeMap<u16, Endorsement>,
+}
+
+impl CeremonyCertificate {
+    /// Ceremony statement.
+    pub fn ceremony(&self) -> &Ceremony {
+        &self.ceremony
+    }
+
+    /// Endorsements keyed by party number.
+    pub fn endorsements(&self) -> &BTreeMap<u16, Endorsement> {
+        &self.endorsements
+    }
+
+    /// Earliest time a participant started the ceremony.
+    pub fn started_at(&self) -> Option<u64> {
+        self.endorsements.values().map(|e| e.started_at).min()
+    }
+
+    /// Latest time a participant finished the ceremony.
+    pub fn finished_at(&self) -> Option<u64> {
+        self.endorsements.values().map(|e| e.finished_at).max()
+    }
+
+    /// Verify the certificate.
+    ///
+    /// Every participant must have endorsed the ceremony
+    /// statement with the signing key for the participant.
+    pub fn verify<V: CertificateKey>(&self) -> Result<()> {
+        if self.ceremony.version != CERTIFICATE_VERSION {
+            return Err(Error::Version(self.ceremony.version));
+        }
+
+        let verifiers = self.ceremony.verifiers::<V>()?;
+        if verifiers.len() != self.ceremony.parties as usize {
+            return Err(Error::ParticipantLength(
+                self.ceremony.parties,
+                verifiers.len(),
+            ));
+        }
+
+        if let Some(party_number) = self
+            .endorsements
+            .keys()
+            .find(|n| **n == 0 || **n as usize > verifiers.len())
+        {
+            return Err(Error::UnknownEndorsement(*party_number));
+        }
+
+        for (index, verifier) in verifiers.iter().enumerate() {
+            let party_number = (index + 1) as u16;
+            let endorsement = self
+                .endorsements
+                .get(&party_number)
+                .ok_or(Error::MissingEndorsement(party_number))?;
+            endorsement.verify(
+                &self.ceremony,
+                party_number,
+                verifier,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Verify the certificate for a set of expected participants.
+    ///
+    /// Auditors use this to confirm that exactly the given
+    /// parties created the key.
+    pub fn verify_participants<V: CertificateKey>(
+        &self,
+        participants: &[V],
+    ) -> Result<()> {
+        let expected = participants
+            .iter()
+            .map(|v| v.encode())
+            .collect::<Vec<_>>();
+        if expected != self.ceremony.participants {
+            return Err(Error::ParticipantMismatch);
+        }
+        self.verify::<V>()
+    }
+}
@@ -0,0 +1,411 @@
+//! Transfer a key share to a new device.
+//!
+//! Round 1: the current holder of the key share and the new
+//!          device endorse a statement that binds the transport
+//!          key and verifying key of the new device to the
+//!          participant.
+//!
+//! Round 2: once the endorsement from the new device has been
+//!          verified the current holder sends the key share.
+//!
+//! The key share is sent over the peer channel so it is
+//! encrypted for the transport key of the new device. Both
+//! devices output a [TransferCertificate] which the other
+//! participants use to update the roster for the key.
+use polysig_protocol::SessionId;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::num::NonZeroU16;
+
+use crate::{
+    KeyShare, PartyOptions, ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::{CertificateKey, Error, Result, ROUND_1, ROUND_2};
+
+/// Version of the transfer statement encoding.
+pub const TRANSFER_VERSION: u16 = 1;
+
+const DOMAIN: &[u8] = b"polysig-share-transfer";
+const ROUND_3: u8 = 3;
+
+/// Transport public key and encoded verifying key
+/// of a participant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Binding {
+    #[serde(with = "polysig_protocol::hex::serde")]
+    public_key: Vec<u8>,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    verifier: Vec<u8>,
+}
+
+impl Binding {
+    /// Create a binding for a participant.
+    pub fn new<V: CertificateKey>(
+        public_key: Vec<u8>,
+        verifier: &V,
+    ) -> Self {
+        Self {
+            public_key,
+            verifier: verifier.encode(),
+        }
+    }
+
+    /// Transport public key.
+    pub fn public_key(&self) -> &[u8] {
+        self.public_key.as_slice()
+    }
+
+    /// Encoded verifying key.
+    pub fn verifier(&self) -> &[u8] {
+        self.verifier.as_slice()
+    }
+}
+
+/// Statement transferring a participant to a new device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareTransfer {
+    version: u16,
+    session_id: SessionId,
+    previous: Binding,
+    next: Binding,
+}
+
+impl ShareTransfer {
+    /// Create a transfer statement.
+    ///
+    /// The session identifier is the session used to
+    /// transfer the key share.
+    pub fn new(
+        session_id: SessionId,
+        previous: Binding,
+        next: Binding,
+    ) -> Result<Self> {
+        if previous.public_key == next.public_key {
+            return Err(Error::TransferBinding);
+        }
+        Ok(Self {
+            version: TRANSFER_VERSION,
+            session_id,
+            previous,
+            next,
+        })
+    }
+
+    /// Session identifier for the transfer.
+    pub fn session_id(&self) -> &SessionId {
+        &self.session_id
+    }
+
+    /// Binding for the device that held the key share.
+    pub fn previous(&self) -> &Binding {
+        &self.previous
+    }
+
+    /// Binding for the device that received the key share.
+    pub fn next(&self) -> &Binding {
+        &self.next
+    }
+
+    /// Digest signed by both devices.
+    fn digest(&self) -> Result<[u8; 32]> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(DOMAIN);
+        hasher.update(serde_json::to_vec(self)?);
+        Ok(hasher.finalize().into())
+    }
+
+    fn verify<V: CertificateKey>(
+        &self,
+        binding: &Binding,
+        signature: &[u8],
+    ) -> Result<()> {
+        let verifier = V::decode(&binding.verifier)?;
+        verifier
+            .verify_endorsement(&self.digest()?, signature)
+            .map_err(|_| Error::TransferSignature)
+    }
+}
+
+/// Transfer statement signed by both devices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferCertificate {
+    transfer: ShareTransfer,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    previous: Vec<u8>,
+    #[serde(with = "polysig_protocol::hex::serde")]
+    next: Vec<u8>,
+}
+
+impl TransferCertificate {
+    /// Transfer statement.
+    pub fn transfer(&self) -> &ShareTransfer {
+        &self.transfer
+    }
+
+    /// Verify the certificate.
+    ///
+    /// The previous device proves possession of the verifying
+    /// key registered for the participant and the new device
+    /// proves possession of the new verifying key.
+    pub fn verify<V: CertificateKey>(&self) -> Result<()> {
+        if self.transfer.version != TRANSFER_VERSION {
+            return Err(Error::Version(self.transfer.version));
+        }
+        self.transfer
+            .verify::<V>(&self.transfer.previous, &self.previous)?;
+        self.transfer.verify::<V>(&self.transfer.next, &self.next)
+    }
+
+    /// Update the options for a party with the transfer.
+    ///
+    /// The transport key and verifying key of the participant
+    /// are replaced by the keys of the new device; when the
+    /// options belong to the transferred participant the
+    /// public key for the party is also replaced.
+    pub fn apply<V: CertificateKey + Clone>(
+        &self,
+        party: &PartyOptions<V>,
+    ) -> Result<PartyOptions<V>> {
+        self.verify::<V>()?;
+        let ShareTransfer { previous, next, .. } = &self.transfer;
+        let index = party
+            .participants()
+            .iter()
+            .zip(party.verifiers())
+            .position(|(public_key, verifier)| {
+                public_key == &previous.public_key
+                    && verifier.encode() == previous.verifier
+            })
+            .ok_or(Error::TransferParticipant)?;
+        if party.participants().contains(&next.public_key) {
+            return Err(Error::TransferDuplicate);
+        }
+        let mut party = party.clone();
+        party.replace_participant(
+            index,
+            next.public_key.clone(),
+            V::decode(&next.verifier)?,
+        );
+        Ok(party)
+    }
+}
+
+/// Message sent to transfer a key share.
+#[derive(Serialize, Deserialize)]
+pub enum TransferMessage {
+    /// Signature over the transfer statement.
+    Endorse(#[serde(with = "polysig_protocol::hex::serde")] Vec<u8>),
+    /// Key share for the new device.
+    KeyShare(KeyShare),
+}
+
+impl std::fmt::Debug for TransferMessage {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Endorse(signature) => {
+                f.debug_tuple("Endorse").field(signature).finish()
+            }
+            Self::KeyShare(_) => f.write_str("KeyShare(..)"),
+        }
+    }
+}
+
+/// Key share transfer driver.
+///
+/// Outputs the transfer certificate and for the new device
+/// the key share.
+pub struct TransferDriver<V: CertificateKey> {
+    party_number: NonZeroU16,
+    peer: NonZeroU16,
+    round_number: u8,
+    transfer: ShareTransfer,
+    signature: Vec<u8>,
+    peer_signature: Option<Vec<u8>>,
+    key_share: Option<KeyShare>,
+    is_sender: bool,
+    marker: std::marker::PhantomData<V>,
+}
+
+impl<V: CertificateKey> TransferDriver<V> {
+    /// Create a driver for the device that holds the key share.
+    pub fn send(
+        party_number: NonZeroU16,
+        peer: NonZeroU16,
+        transfer: ShareTransfer,
+        signing_key: &V::SigningKey,
+        key_share: KeyShare,
+    ) -> Result<Self> {
+        Self::new(
+            party_number,
+            peer,
+            transfer,
+            signing_key,
+            Some(key_share),
+        )
+    }
+
+    /// Create a driver for the device receiving the key share.
+    pub fn receive(
+        party_number: NonZeroU16,
+        peer: NonZeroU16,
+        transfer: ShareTransfer,
+        signing_key: &V::SigningKey,
+    ) -> Result<Self> {
+        Self::new(party_number, peer, transfer, signing_key, None)
+    }
+
+    fn new(
+        party_number: NonZeroU16,
+        peer: NonZeroU16,
+        transfer: ShareTransfer,
+        signing_key: &V::SigningKey,
+        key_share: Option<KeyShare>,
+    ) -> Result<Self> {
+        let is_sender = key_share.is_some();
+        let signature = V::endorse(signing_key, &transfer.digest()?)?;
+        // Signing key must belong to this device
+        let binding = if is_sender {
+            &transfer.previous
+        } else {
+            &transfer.next
+        };
+        transfer.verify::<V>(binding, &signature)?;
+
+        Ok(Self {
+            party_number,
+            peer,
+            round_number: ROUND_1,
+            transfer,
+            signature,
+            peer_signature: None,
+            key_share,
+            is_sender,
+            marker: std::marker::PhantomData,
+        })
+    }
+
+    fn message(
+        &self,
+        body: TransferMessage,
+    ) -> RoundMessage<TransferMessage, NonZeroU16> {
+        RoundMessage {
+            round: NonZeroU16::new(self.round_number.into()).unwrap(),
+            sender: self.party_number,
+            receiver: self.peer,
+            body,
+        }
+    }
+
+    fn certificate(&mut self) -> Option<TransferCertificate> {
+        let peer_signature = self.peer_signature.take()?;
+        let signature = std::mem::take(&mut self.signature);
+        let (previous, next) = if self.is_sender {
+            (signature, peer_signature)
+        } else {
+            (peer_signature, signature)
+        };
+        Some(TransferCertificate {
+            transfer: self.transfer.clone(),
+            previous,
+            next,
+        })
+    }
+}
+
+impl<V: CertificateKey> ProtocolDriver for TransferDriver<V> {
+    type Error = Error;
+    type Message = RoundMessage<TransferMessage, NonZeroU16>;
+    type Output = (TransferCertificate, Option<KeyShare>);
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let round_number = self.round_number;
+        let is_echo = false;
+        let can_finalize = match self.round_number {
+            ROUND_2 if self.is_sender => {
+                self.peer_signature.is_some()
+            }
+            ROUND_2 => {
+                self.peer_signature.is_some()
+                    && self.key_share.is_some()
+            }
+            ROUND_3 => true,
+            _ => false,
+        };
+        Ok(RoundInfo {
+            round_number,
+            can_finalize,
+            is_echo,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        let body = match self.round_number {
+            ROUND_1 => {
+                TransferMessage::Endorse(self.signature.clone())
+            }
+            ROUND_2 if self.is_sender => TransferMessage::KeyShare(
+                self.key_share
+                    .take()
+                    .ok_or(Error::InvalidRound(self.round_number))?,
+            ),
+            _ => return Err(Error::InvalidRound(self.round_number)),
+        };
+        let message = self.message(body);
+        self.round_number = self.round_number.checked_add(1).unwrap();
+        Ok(vec![message])
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        if message.sender != self.peer {
+            return Err(Error::SenderVerifier);
+        }
+        let round_number = message.round.get() as u8;
+        match (round_number, message.body) {
+            (ROUND_1, TransferMessage::Endorse(signature)) => {
+                let binding = if self.is_sender {
+                    &self.transfer.next
+                } else {
+                    &self.transfer.previous
+                };
+                self.transfer.verify::<V>(binding, &signature)?;
+                self.peer_signature = Some(signature);
+                Ok(())
+            }
+            (ROUND_2, TransferMessage::KeyShare(key_share))
+                if !self.is_sender =>
+            {
+                if self.peer_signature.is_none() {
+                    return Err(Error::TransferKeyShare);
+                }
+                self.key_share = Some(key_share);
+                Ok(())
+            }
+            _ => Err(Error::InvalidRound(round_number)),
+        }
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        let is_complete = if self.is_sender {
+            self.round_number == ROUND_3
+        } else {
+            self.round_number == ROUND_2 && self.key_share.is_some()
+        };
+        if !is_complete {
+            return Ok(None);
+        }
+        let key_share = self.key_share.take();
+        Ok(self
+            .certificate()
+            .map(|certificate| (certificate, key_share)))
+    }
+}
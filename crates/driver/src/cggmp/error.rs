@@ -10,9 +10,10 @@ pub enum Error {
     LocalError(String),
 
     /// Error generated by the CGGMP protocol library
-    /// on a remote node.
-    #[error("{0}")]
-    RemoteError(String),
+    /// on a remote node; the verifying key of the remote
+    /// node is included.
+    #[error("{1}")]
+    RemoteError(VerifyingKey, String),
 
     /// Signature verification failed.
     #[error("failed to verify generated signature")]
@@ -26,10 +27,59 @@ pub enum Error {
     #[error("protocol is not finished, another round is available")]
     NotFinished,
 
+    /// Scalar in a key share is invalid.
+    #[error("key share scalar is invalid")]
+    ShareScalar,
+
+    /// Point in a key share is invalid.
+    #[error("key share point is invalid")]
+    SharePoint,
+
+    /// Threshold of a key share is invalid for the parties.
+    #[error("key share threshold {0} is invalid for {1} parties")]
+    ShareThreshold(u16, usize),
+
+    /// Key share identifiers must be unique and not zero.
+    #[error("key share identifiers must be unique and not zero")]
+    ShareIdentifier,
+
+    /// Owner of a key share is not one of the parties.
+    #[error("key share owner is not one of the parties")]
+    ShareOwner,
+
+    /// Secret share does not match the public share of the owner.
+    #[error("secret share does not match the public share")]
+    SharePublicKey,
+
+    /// Public shares are not consistent with the verifying key.
+    #[error("public shares do not match the verifying key")]
+    ShareCommitments,
+
+    /// Number of verifiers does not match the parties.
+    #[error("key share has {0} parties but got {1} verifiers")]
+    ShareVerifiers(usize, usize),
+
+    /// Session purpose is not supported.
+    #[error("unknown session purpose '{0}'")]
+    UnknownSessionPurpose(String),
+
+    /// Party options assign a weight greater than one to a
+    /// participant; weighted shares are only supported for FROST.
+    #[error("weighted participants are not supported for CGGMP")]
+    WeightedParty,
+
+    /// Key share serialized by synedrion has an unexpected shape.
+    #[error("unexpected synedrion key share field '{0}'")]
+    SynedrionKeyShare(String),
+
     /// Protocol library errors.
     #[error(transparent)]
     Protocol(#[from] polysig_protocol::Error),
 
+    /// JSON library errors.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
     /// Error generated converting integers.
     #[error(transparent)]
     FromInt(#[from] std::num::TryFromIntError),
@@ -49,7 +99,7 @@ impl From<synedrion::sessions::RemoteError<VerifyingKey>> for Error {
     fn from(
         value: synedrion::sessions::RemoteError<VerifyingKey>,
     ) -> Self {
-        Error::RemoteError(format!("{:#?}", value.error))
+        Error::RemoteError(value.party, format!("{:#?}", value.error))
     }
 }
 
@@ -1,8 +1,9 @@
 //! Helper functions for the CGGMP protocol drivers.
+use polysig_protocol::PartyNumber;
 use rand::rngs::OsRng;
 use std::num::NonZeroU16;
 
-use super::Result;
+use super::{Error, Result};
 use synedrion::{
     ecdsa::{Signature, SigningKey, VerifyingKey},
     sessions::{PreprocessedMessage, RoundAccumulator, Session},
@@ -29,6 +30,20 @@ where
     })
 }
 
+/// Party number of the remote node that caused an error.
+pub fn culprit(
+    verifiers: &[VerifyingKey],
+    error: &Error,
+) -> Option<PartyNumber> {
+    match error {
+        Error::RemoteError(party, _) => {
+            let index = verifiers.iter().position(|v| v == party)?;
+            PartyNumber::new((index + 1) as u16)
+        }
+        _ => None,
+    }
+}
+
 pub fn proceed<Res>(
     session: &mut Session<Res, Signature, SigningKey, VerifyingKey>,
     accum: &mut RoundAccumulator<Signature, VerifyingKey>,
@@ -0,0 +1,380 @@
+//! Import and export CGGMP key shares.
+//!
+//! Key shares are stored as the serde output of the synedrion
+//! [ThresholdKeyShare](synedrion::ThresholdKeyShare) in a PEM
+//! container; use [from_synedrion] and [to_synedrion] to
+//! convert to and from the raw serde output of synedrion.
+//!
+//! Other GG20 and CGGMP implementations store a Shamir share
+//! of the secret key with the public shares of every party;
+//! [ThresholdShare] is a plain JSON format for these shares so
+//! existing keys can be imported without a new key generation.
+//!
+//! Shares are verified before they are imported; the secret
+//! share must match the public share of the owner and the
+//! public shares must lie on a polynomial of degree
+//! `threshold - 1` whose constant term is the verifying key.
+//! Auxiliary information is not part of a key share so the
+//! parties must generate auxiliary information before they
+//! can sign with imported shares.
+use k256::{
+    ecdsa::VerifyingKey,
+    elliptic_curve::{sec1::ToEncodedPoint, Field, PrimeField},
+    ProjectivePoint, PublicKey, Scalar,
+};
+use polysig_protocol::hex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use synedrion::SchemeParams;
+use zeroize::Zeroize;
+
+use super::{Error, KeyShare, Result};
+
+/// Parse a key share from the serde output of synedrion.
+pub fn from_synedrion<P: SchemeParams>(
+    value: &[u8],
+) -> Result<KeyShare<P>> {
+    Ok(serde_json::from_slice(value)?)
+}
+
+/// Serialize a key share to the serde output of synedrion.
+pub fn to_synedrion<P: SchemeParams>(
+    key_share: &KeyShare<P>,
+) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(key_share)?)
+}
+
+/// Public share of a party.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicShare {
+    /// Shamir identifier of the party as a big-endian scalar.
+    #[serde(with = "hex::serde")]
+    pub share_id: Vec<u8>,
+    /// Public share of the party as a compressed SEC1 point.
+    #[serde(with = "hex::serde")]
+    pub public_share: Vec<u8>,
+}
+
+/// Shamir share of a secp256k1 key in a plain format.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdShare {
+    /// Number of parties required to sign.
+    pub threshold: u16,
+    /// Verifying key as a compressed SEC1 point.
+    #[serde(with = "hex::serde")]
+    pub verifying_key: Vec<u8>,
+    /// Shamir identifier of the owner of the share.
+    #[serde(with = "hex::serde")]
+    pub share_id: Vec<u8>,
+    /// Secret share of the owner as a big-endian scalar.
+    #[serde(with = "hex::serde")]
+    pub secret_share: Vec<u8>,
+    /// Public shares of every party.
+    pub parties: Vec<PublicShare>,
+    /// Hex-encoded Feldman commitments to the coefficients
+    /// of the sharing polynomial.
+    ///
+    /// When present the public shares are verified against
+    /// the commitments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commitments: Vec<String>,
+}
+
+impl std::fmt::Debug for ThresholdShare {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("ThresholdShare")
+            .field("threshold", &self.threshold)
+            .field("verifying_key", &hex::encode(&self.verifying_key))
+            .field("share_id", &hex::encode(&self.share_id))
+            .field("parties", &self.parties)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ThresholdShare {
+    fn drop(&mut self) {
+        self.secret_share.zeroize();
+    }
+}
+
+impl ThresholdShare {
+    /// Export a key share.
+    ///
+    /// The verifiers are the verifying keys of the parties
+    /// and determine the order of the public shares.
+    pub fn from_key_share<P: SchemeParams>(
+        key_share: &KeyShare<P>,
+        verifiers: &[VerifyingKey],
+    ) -> Result<Self> {
+        let value = serde_json::to_value(key_share)?;
+        let field = |name: &str| {
+            value.get(name).ok_or_else(|| {
+                Error::SynedrionKeyShare(name.to_owned())
+            })
+        };
+        let threshold = field("threshold")?
+            .as_u64()
+            .ok_or_else(|| {
+                Error::SynedrionKeyShare("threshold".to_owned())
+            })?
+            .try_into()?;
+        let owner = field("owner")?;
+        let secret_share = decode_hex(field("secret_share")?)?;
+        let share_ids = field("share_ids")?;
+        let public_shares = field("public_shares")?;
+
+        let mut share_id = None;
+        let mut parties = Vec::with_capacity(verifiers.len());
+        for verifier in verifiers {
+            let key = party_key(verifier)?;
+            let party = PublicShare {
+                share_id: decode_hex(lookup(share_ids, &key)?)?,
+                public_share: decode_hex(lookup(
+                    public_shares,
+                    &key,
+                )?)?,
+            };
+            if owner.as_str() == Some(key.as_str()) {
+                share_id = Some(party.share_id.clone());
+            }
+            parties.push(party);
+        }
+        let count = share_ids.as_object().map(|m| m.len());
+        if count != Some(verifiers.len()) {
+            return Err(Error::ShareVerifiers(
+                count.unwrap_or_default(),
+                verifiers.len(),
+            ));
+        }
+        let share_id = share_id.ok_or(Error::ShareOwner)?;
+
+        let (ids, points) = decode_parties(&parties)?;
+        let t = threshold as usize;
+        if t == 0 || t > parties.len() {
+            return Err(Error::ShareThreshold(
+                threshold,
+                parties.len(),
+            ));
+        }
+        let verifying_key =
+            interpolate(&ids[..t], &points[..t], &Scalar::ZERO)?;
+
+        let share = Self {
+            threshold,
+            verifying_key: encode_point(&verifying_key),
+            share_id,
+            secret_share,
+            parties,
+            commitments: Vec::new(),
+        };
+        share.verify()?;
+        Ok(share)
+    }
+
+    /// Import this share as a key share.
+    ///
+    /// The verifiers are the verifying keys of the parties
+    /// in the same order as the public shares.
+    pub fn into_key_share<P: SchemeParams>(
+        &self,
+        verifiers: &[VerifyingKey],
+    ) -> Result<KeyShare<P>> {
+        self.verify()?;
+        if verifiers.len() != self.parties.len() {
+            return Err(Error::ShareVerifiers(
+                self.parties.len(),
+                verifiers.len(),
+            ));
+        }
+
+        let mut owner = None;
+        let mut share_ids = Map::new();
+        let mut public_shares = Map::new();
+        for (verifier, party) in verifiers.iter().zip(&self.parties) {
+            let key = party_key(verifier)?;
+            if party.share_id == self.share_id {
+                owner = Some(serde_json::to_value(verifier)?);
+            }
+            share_ids.insert(
+                key.clone(),
+                Value::String(hex::encode(&party.share_id)),
+            );
+            public_shares.insert(
+                key,
+                Value::String(hex::encode(&party.public_share)),
+            );
+        }
+
+        let value = json!({
+            "owner": owner.ok_or(Error::ShareOwner)?,
+            "threshold": self.threshold,
+            "secret_share": hex::encode(&self.secret_share),
+            "share_ids": share_ids,
+            "public_shares": public_shares,
+            "phantom": null,
+        });
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Verify the share.
+    pub fn verify(&self) -> Result<()> {
+        let t = self.threshold as usize;
+        if t == 0 || t > self.parties.len() {
+            return Err(Error::ShareThreshold(
+                self.threshold,
+                self.parties.len(),
+            ));
+        }
+
+        let (ids, points) = decode_parties(&self.parties)?;
+        let owner = self
+            .parties
+            .iter()
+            .position(|p| p.share_id == self.share_id)
+            .ok_or(Error::ShareOwner)?;
+        let secret_share = decode_scalar(&self.secret_share)?;
+        if ProjectivePoint::GENERATOR * secret_share != points[owner]
+        {
+            return Err(Error::SharePublicKey);
+        }
+
+        let verifying_key = decode_point(&self.verifying_key)?;
+        if self.commitments.is_empty() {
+            // Public shares must be on the polynomial
+            // interpolated from the first threshold shares
+            let (base_ids, base_points) = (&ids[..t], &points[..t]);
+            let constant =
+                interpolate(base_ids, base_points, &Scalar::ZERO)?;
+            if constant != verifying_key {
+                return Err(Error::ShareCommitments);
+            }
+            for (id, point) in ids.iter().zip(&points).skip(t) {
+                if interpolate(base_ids, base_points, id)? != *point {
+                    return Err(Error::ShareCommitments);
+                }
+            }
+        } else {
+            if self.commitments.len() != t {
+                return Err(Error::ShareCommitments);
+            }
+            let commitments = self
+                .commitments
+                .iter()
+                .map(|c| {
+                    hex::decode(c)
+                        .map_err(|_| Error::SharePoint)
+                        .and_then(|c| decode_point(&c))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if commitments[0] != verifying_key {
+                return Err(Error::ShareCommitments);
+            }
+            for (id, point) in ids.iter().zip(&points) {
+                let mut power = Scalar::ONE;
+                let mut value = ProjectivePoint::IDENTITY;
+                for commitment in &commitments {
+                    value += *commitment * power;
+                    power *= id;
+                }
+                if value != *point {
+                    return Err(Error::ShareCommitments);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Map key for a party in the synedrion serde output.
+fn party_key(verifier: &VerifyingKey) -> Result<String> {
+    match serde_json::to_value(verifier)? {
+        Value::String(key) => Ok(key),
+        _ => Err(Error::SynedrionKeyShare("owner".to_owned())),
+    }
+}
+
+fn lookup<'a>(map: &'a Value, key: &str) -> Result<&'a Value> {
+    map.get(key)
+        .ok_or_else(|| Error::SynedrionKeyShare(key.to_owned()))
+}
+
+fn decode_hex(value: &Value) -> Result<Vec<u8>> {
+    value
+        .as_str()
+        .and_then(|value| hex::decode(value).ok())
+        .ok_or_else(|| Error::SynedrionKeyShare(value.to_string()))
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<Scalar> {
+    let bytes: [u8; 32] =
+        bytes.try_into().map_err(|_| Error::ShareScalar)?;
+    Option::from(Scalar::from_repr(bytes.into()))
+        .ok_or(Error::ShareScalar)
+}
+
+fn decode_point(bytes: &[u8]) -> Result<ProjectivePoint> {
+    PublicKey::from_sec1_bytes(bytes)
+        .map(|key| key.to_projective())
+        .map_err(|_| Error::SharePoint)
+}
+
+fn encode_point(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// Decode the identifiers and public shares of the parties.
+///
+/// Identifiers must be unique and not zero.
+fn decode_parties(
+    parties: &[PublicShare],
+) -> Result<(Vec<Scalar>, Vec<ProjectivePoint>)> {
+    let unique = parties
+        .iter()
+        .map(|p| p.share_id.as_slice())
+        .collect::<BTreeSet<_>>();
+    if unique.len() != parties.len() {
+        return Err(Error::ShareIdentifier);
+    }
+    let mut ids = Vec::with_capacity(parties.len());
+    let mut points = Vec::with_capacity(parties.len());
+    for party in parties {
+        let id = decode_scalar(&party.share_id)?;
+        if bool::from(id.is_zero()) {
+            return Err(Error::ShareIdentifier);
+        }
+        ids.push(id);
+        points.push(decode_point(&party.public_share)?);
+    }
+    Ok((ids, points))
+}
+
+/// Evaluate the polynomial through the points at `x`
+/// using Lagrange interpolation in the exponent.
+fn interpolate(
+    ids: &[Scalar],
+    points: &[ProjectivePoint],
+    x: &Scalar,
+) -> Result<ProjectivePoint> {
+    let mut value = ProjectivePoint::IDENTITY;
+    for (i, (id, point)) in ids.iter().zip(points).enumerate() {
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (j, other) in ids.iter().enumerate() {
+            if i != j {
+                numerator *= *x - other;
+                denominator *= *id - other;
+            }
+        }
+        let inverse: Option<Scalar> = denominator.invert().into();
+        let coefficient =
+            numerator * inverse.ok_or(Error::ShareIdentifier)?;
+        value += *point * coefficient;
+    }
+    Ok(value)
+}
@@ -1,4 +1,5 @@
 //! Key init generation for CGGMP.
+use polysig_protocol::PartyNumber;
 use rand::rngs::OsRng;
 use std::collections::BTreeSet;
 
@@ -107,6 +108,10 @@ where
         super::helpers::handle_incoming(session, accum, message)
     }
 
+    fn culprit(&self, error: &Error) -> Option<PartyNumber> {
+        super::helpers::culprit(&self.verifiers, error)
+    }
+
     fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
         let session = self.session.take().unwrap();
         let accum = self.accum.take().unwrap();
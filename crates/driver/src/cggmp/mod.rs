@@ -13,18 +13,26 @@ const PEM_V1: u16 = 1;
 mod aux_gen;
 mod error;
 mod helpers;
+mod interchange;
 mod key_gen;
 mod key_init;
 mod key_refresh;
 mod key_resharing;
+mod session;
 mod sign;
 
 pub use aux_gen::AuxGenDriver;
 pub use error::Error;
+pub use interchange::{
+    from_synedrion, to_synedrion, PublicShare, ThresholdShare,
+};
 pub use key_gen::KeyGenDriver;
 pub use key_init::KeyInitDriver;
 pub use key_refresh::KeyRefreshDriver;
 pub use key_resharing::KeyResharingDriver;
+pub use session::{
+    derive_session_id, session_id_seed, SessionPurpose,
+};
 pub use sign::SignatureDriver;
 
 type MessageOut = MessageBundle<ecdsa::Signature>;
@@ -47,6 +55,7 @@ where
         Ok(Self {
             version: PEM_V1,
             contents: key_share,
+            policy: None,
         })
     }
 }
@@ -80,8 +89,24 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub type Participant = crate::Participant<SigningKey, VerifyingKey>;
 
 /// Options for each party.
+///
+/// Weights are not supported, every participant in a CGGMP
+/// ceremony holds a single share; see [validate_party].
 pub type PartyOptions = crate::PartyOptions<VerifyingKey>;
 
+/// Validate the options for a party in a CGGMP ceremony.
+///
+/// Synedrion key shares hold exactly one share for each
+/// verifying key so options that give any participant a weight
+/// greater than one are rejected rather than silently treating
+/// every participant as holding a single share.
+pub fn validate_party(party: &PartyOptions) -> Result<()> {
+    if party.weights().is_weighted() {
+        return Err(Error::WeightedParty);
+    }
+    Ok(())
+}
+
 /// Derive a child key using the BIP32 algorithm.
 pub fn derive_bip32<P>(
     key_share: &ThresholdKeyShare<P, VerifyingKey>,
@@ -0,0 +1,112 @@
+//! Session identifiers bound to a roster and purpose.
+//!
+//! Every party derives the same [SessionId] from the verifying
+//! keys of the parties, the threshold, the purpose of the
+//! ceremony and a nonce agreed out of band, for example the
+//! meeting identifier, so round messages for one ceremony
+//! are never accepted by another ceremony.
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::{collections::BTreeSet, fmt, str::FromStr};
+use synedrion::{ecdsa::VerifyingKey, SessionId};
+
+use super::{Error, Result};
+
+const DOMAIN: &[u8] = b"polysig-cggmp-session-id";
+
+/// Purpose of a ceremony.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionPurpose {
+    /// Distributed key generation.
+    KeyGen,
+    /// Auxiliary information generation.
+    AuxGen,
+    /// Key refresh.
+    KeyRefresh,
+    /// Key resharing.
+    KeyResharing,
+    /// Signing.
+    Sign,
+}
+
+impl fmt::Display for SessionPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::KeyGen => "key-gen",
+                Self::AuxGen => "aux-gen",
+                Self::KeyRefresh => "key-refresh",
+                Self::KeyResharing => "key-resharing",
+                Self::Sign => "sign",
+            }
+        )
+    }
+}
+
+impl FromStr for SessionPurpose {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "key-gen" => Self::KeyGen,
+            "aux-gen" => Self::AuxGen,
+            "key-refresh" => Self::KeyRefresh,
+            "key-resharing" => Self::KeyResharing,
+            "sign" => Self::Sign,
+            _ => {
+                return Err(Error::UnknownSessionPurpose(
+                    s.to_owned(),
+                ))
+            }
+        })
+    }
+}
+
+/// Derive the seed for a session identifier.
+///
+/// The verifying keys are sorted so the order of the
+/// parties does not change the seed.
+pub fn session_id_seed(
+    verifiers: &[VerifyingKey],
+    threshold: u16,
+    purpose: SessionPurpose,
+    nonce: &[u8],
+) -> [u8; 32] {
+    let verifiers = verifiers
+        .iter()
+        .map(|v| v.to_encoded_point(true).as_bytes().to_vec())
+        .collect::<BTreeSet<_>>();
+    let purpose = purpose.to_string();
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(DOMAIN);
+    hasher.update((purpose.len() as u32).to_be_bytes());
+    hasher.update(purpose.as_bytes());
+    hasher.update(threshold.to_be_bytes());
+    hasher.update((verifiers.len() as u32).to_be_bytes());
+    for verifier in verifiers {
+        hasher.update(verifier);
+    }
+    hasher.update((nonce.len() as u32).to_be_bytes());
+    hasher.update(nonce);
+    hasher.finalize().into()
+}
+
+/// Derive a session identifier.
+///
+/// See [session_id_seed] for the seed of the identifier.
+pub fn derive_session_id(
+    verifiers: &[VerifyingKey],
+    threshold: u16,
+    purpose: SessionPurpose,
+    nonce: &[u8],
+) -> SessionId {
+    SessionId::from_seed(&session_id_seed(
+        verifiers, threshold, purpose, nonce,
+    ))
+}
@@ -0,0 +1,97 @@
+//! Digest algorithms applied to messages before signing.
+//!
+//! ECDSA protocols sign a 32 byte prehash so the digest must
+//! produce 32 bytes; the other protocols hash the message
+//! internally and accept a digest of any size.
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Digest algorithm for a message.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestAlgorithm {
+    /// Message is already a digest.
+    #[default]
+    Prehashed,
+    /// SHA-256.
+    Sha256,
+    /// Double SHA-256 as used by Bitcoin.
+    Sha256d,
+    /// Keccak-256 as used by Ethereum.
+    Keccak256,
+    /// SHA-512.
+    Sha512,
+    /// BLAKE2b with a 256 bit output as used by Substrate.
+    Blake2b256,
+}
+
+impl DigestAlgorithm {
+    /// Compute the digest of a message.
+    ///
+    /// Prehashed messages are returned unchanged.
+    pub fn digest(&self, message: &[u8]) -> Vec<u8> {
+        use blake2::{digest::consts::U32, Blake2b};
+        use sha2::{Digest, Sha256, Sha512};
+        use sha3::Keccak256;
+        match self {
+            Self::Prehashed => message.to_vec(),
+            Self::Sha256 => Sha256::digest(message).to_vec(),
+            Self::Sha256d => {
+                Sha256::digest(Sha256::digest(message)).to_vec()
+            }
+            Self::Keccak256 => Keccak256::digest(message).to_vec(),
+            Self::Sha512 => Sha512::digest(message).to_vec(),
+            Self::Blake2b256 => {
+                Blake2b::<U32>::digest(message).to_vec()
+            }
+        }
+    }
+
+    /// Compute the 32 byte prehash of a message for
+    /// ECDSA protocols.
+    ///
+    /// Returns an error when the algorithm does not produce
+    /// 32 bytes or a prehashed message is not 32 bytes.
+    pub fn prehash(&self, message: &[u8]) -> Result<[u8; 32]> {
+        let digest = self.digest(message);
+        digest.as_slice().try_into().map_err(|_| {
+            Error::IncompatibleDigest(self.to_string(), digest.len())
+        })
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Prehashed => "prehashed",
+                Self::Sha256 => "sha256",
+                Self::Sha256d => "sha256d",
+                Self::Keccak256 => "keccak256",
+                Self::Sha512 => "sha512",
+                Self::Blake2b256 => "blake2b256",
+            }
+        )
+    }
+}
+
+impl FromStr for DigestAlgorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "prehashed" => Self::Prehashed,
+            "sha256" => Self::Sha256,
+            "sha256d" => Self::Sha256d,
+            "keccak256" => Self::Keccak256,
+            "sha512" => Self::Sha512,
+            "blake2" | "blake2b256" => Self::Blake2b256,
+            _ => return Err(Error::UnknownDigest(s.to_owned())),
+        })
+    }
+}
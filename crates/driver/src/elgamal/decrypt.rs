@@ -0,0 +1,280 @@
+//! Threshold decryption.
+//!
+//! Round 1: each decryptor sends a decryption share and a proof
+//!          that the share was computed using the decryptor's
+//!          signing share to every other decryptor.
+//!
+//! Decryption shares are verified on arrival and combined once
+//! a share has been received from every decryptor.
+use k256::{
+    elliptic_curve::{Field, PrimeField},
+    ProjectivePoint, Scalar,
+};
+use polysig_protocol::hex;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroU16,
+};
+
+use crate::{ProtocolDriver, RoundInfo, RoundMessage};
+
+use super::{
+    challenge, compress, decompress, decrypt_with, scalar,
+    Ciphertext, Error, Identifier, KeyShare, Result, ROUND_1,
+    ROUND_2,
+};
+
+/// Decryption share sent to each decryptor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    share: Vec<u8>,
+    proof: (Vec<u8>, Vec<u8>),
+}
+
+/// Threshold decryption driver.
+pub struct DecryptionDriver {
+    identifiers: Vec<Identifier>,
+    id: Identifier,
+    round_number: u8,
+    signing_share: Scalar,
+    verifying_shares: BTreeMap<Identifier, ProjectivePoint>,
+    ephemeral_key: ProjectivePoint,
+    ciphertext: Ciphertext,
+    aad: Vec<u8>,
+    shares: BTreeMap<Identifier, ProjectivePoint>,
+}
+
+impl DecryptionDriver {
+    /// Create a threshold decryption driver.
+    ///
+    /// The identifiers are the key share identifiers of each
+    /// decryptor ordered by session party number.
+    pub fn new(
+        party_number: NonZeroU16,
+        identifiers: Vec<Identifier>,
+        key_share: &KeyShare,
+        ciphertext: Ciphertext,
+        aad: Vec<u8>,
+    ) -> Result<Self> {
+        let party_index: usize = party_number.get() as usize;
+        let self_index = party_index - 1;
+        let id = *identifiers
+            .get(self_index)
+            .ok_or(Error::IndexIdentifier(party_index))?;
+        if &id != key_share.0.identifier() {
+            return Err(Error::IndexIdentifier(party_index));
+        }
+
+        let threshold = *key_share.0.min_signers();
+        if identifiers.len() < threshold as usize {
+            return Err(Error::DecryptorThreshold(
+                identifiers.len(),
+                threshold,
+            ));
+        }
+        let unique = identifiers.iter().collect::<BTreeSet<_>>();
+        if unique.len() != identifiers.len() {
+            return Err(Error::DuplicateIdentifier);
+        }
+
+        let signing_share =
+            scalar(&key_share.0.signing_share().serialize())
+                .ok_or(Error::KeyShare)?;
+
+        let mut verifying_shares = BTreeMap::new();
+        for id in &identifiers {
+            let verifying_share = key_share
+                .1
+                .verifying_shares()
+                .get(id)
+                .ok_or(Error::SenderVerifier)?;
+            let point = decompress(&verifying_share.serialize()?)
+                .ok_or(Error::KeyShare)?;
+            verifying_shares.insert(*id, point);
+        }
+
+        let ephemeral_key = decompress(&ciphertext.ephemeral_key)
+            .ok_or(Error::EphemeralKey)?;
+
+        Ok(Self {
+            identifiers,
+            id,
+            round_number: ROUND_1,
+            signing_share,
+            verifying_shares,
+            ephemeral_key,
+            ciphertext,
+            aad,
+            shares: BTreeMap::new(),
+        })
+    }
+
+    /// Compute our decryption share and a Chaum-Pedersen proof
+    /// that it uses the same secret as our verifying share.
+    fn decryption_share(&self) -> (ProjectivePoint, DecryptionShare) {
+        let share = self.ephemeral_key * self.signing_share;
+        let verifying_share = self.verifying_shares[&self.id];
+
+        let k = Scalar::random(&mut OsRng);
+        let a1 = ProjectivePoint::GENERATOR * k;
+        let a2 = self.ephemeral_key * k;
+        let c = challenge(&[
+            &self.ephemeral_key,
+            &verifying_share,
+            &share,
+            &a1,
+            &a2,
+        ]);
+        let z = k + c * self.signing_share;
+
+        (
+            share,
+            DecryptionShare {
+                share: compress(&share),
+                proof: (c.to_repr().to_vec(), z.to_repr().to_vec()),
+            },
+        )
+    }
+
+    fn verify_share(
+        &self,
+        id: &Identifier,
+        package: &DecryptionShare,
+    ) -> Result<ProjectivePoint> {
+        let invalid = || {
+            Error::InvalidDecryptionShare(hex::encode(id.serialize()))
+        };
+        let verifying_share = self
+            .verifying_shares
+            .get(id)
+            .ok_or(Error::SenderVerifier)?;
+        let share = decompress(&package.share).ok_or_else(invalid)?;
+        let c = scalar(&package.proof.0).ok_or_else(invalid)?;
+        let z = scalar(&package.proof.1).ok_or_else(invalid)?;
+
+        let a1 =
+            ProjectivePoint::GENERATOR * z - *verifying_share * c;
+        let a2 = self.ephemeral_key * z - share * c;
+        if challenge(&[
+            &self.ephemeral_key,
+            verifying_share,
+            &share,
+            &a1,
+            &a2,
+        ]) != c
+        {
+            return Err(invalid());
+        }
+        Ok(share)
+    }
+
+    fn lagrange(&self, id: &Identifier) -> Result<Scalar> {
+        let x = scalar(&id.serialize()).ok_or(Error::KeyShare)?;
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for other in self.identifiers.iter().filter(|i| *i != id) {
+            let other =
+                scalar(&other.serialize()).ok_or(Error::KeyShare)?;
+            numerator *= other;
+            denominator *= other - x;
+        }
+        let inverse: Option<Scalar> = denominator.invert().into();
+        Ok(numerator * inverse.ok_or(Error::DuplicateIdentifier)?)
+    }
+
+    fn decrypt(&self) -> Result<Vec<u8>> {
+        let mut shared = ProjectivePoint::IDENTITY;
+        for (id, share) in &self.shares {
+            shared += *share * self.lagrange(id)?;
+        }
+        decrypt_with(&self.ciphertext, &shared, &self.aad)
+    }
+}
+
+impl ProtocolDriver for DecryptionDriver {
+    type Error = Error;
+    type Message = RoundMessage<DecryptionShare, Identifier>;
+    type Output = Vec<u8>;
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let round_number = self.round_number;
+        let is_echo = false;
+        let can_finalize = match self.round_number {
+            ROUND_2 => self.shares.len() == self.identifiers.len(),
+            _ => false,
+        };
+        Ok(RoundInfo {
+            round_number,
+            can_finalize,
+            is_echo,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        match self.round_number {
+            ROUND_1 => {
+                let (share, package) = self.decryption_share();
+
+                let mut messages =
+                    Vec::with_capacity(self.identifiers.len() - 1);
+                for (index, id) in self.identifiers.iter().enumerate()
+                {
+                    if id == &self.id {
+                        continue;
+                    }
+
+                    messages.push(RoundMessage {
+                        round: NonZeroU16::new(
+                            self.round_number.into(),
+                        )
+                        .unwrap(),
+                        sender: self.id,
+                        receiver: NonZeroU16::new((index + 1) as u16)
+                            .unwrap(),
+                        body: package.clone(),
+                    });
+                }
+
+                self.shares.insert(self.id, share);
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+                Ok(messages)
+            }
+            _ => Err(Error::InvalidRound(self.round_number)),
+        }
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        let round_number = message.round.get() as u8;
+        match round_number {
+            ROUND_1 => {
+                if message.sender == self.id
+                    || !self.identifiers.contains(&message.sender)
+                {
+                    return Err(Error::SenderVerifier);
+                }
+                let share = self
+                    .verify_share(&message.sender, &message.body)?;
+                self.shares.insert(message.sender, share);
+                Ok(())
+            }
+            _ => Err(Error::InvalidRound(round_number)),
+        }
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        if self.round_number == ROUND_2
+            && self.shares.len() == self.identifiers.len()
+        {
+            Ok(Some(self.decrypt()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
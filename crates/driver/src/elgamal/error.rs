@@ -0,0 +1,68 @@
+use thiserror::Error;
+
+/// Errors generated by the protocol.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated an invalid round number is encountered.
+    #[error("round {0} is not supported for this protocol")]
+    InvalidRound(u8),
+
+    /// Error generated locating an identifier for a party number.
+    #[error("party number is not a valid protocol identifier")]
+    IndexIdentifier(usize),
+
+    /// Error generated finding a verifier for a message sender.
+    #[error("could not locate a verifier for the message sender")]
+    SenderVerifier,
+
+    /// Error generated when there are fewer decryptors than the
+    /// threshold of the key share.
+    #[error("{0} decryptors is less than the threshold {1}")]
+    DecryptorThreshold(usize, u16),
+
+    /// Error generated when identifiers are not unique.
+    #[error("identifiers must be unique")]
+    DuplicateIdentifier,
+
+    /// Error generated when the ephemeral key of a ciphertext
+    /// is not a valid point.
+    #[error("invalid ephemeral key")]
+    EphemeralKey,
+
+    /// Error generated when a key share could not be decoded.
+    #[error("invalid key share")]
+    KeyShare,
+
+    /// Error generated when a decryption share or the proof
+    /// for the share is invalid.
+    #[error("invalid decryption share from participant {0}")]
+    InvalidDecryptionShare(String),
+
+    /// Error generated when encryption fails.
+    #[error("failed to encrypt message")]
+    Encrypt,
+
+    /// Error generated when decryption fails.
+    #[error("failed to decrypt message")]
+    Decrypt,
+
+    /// FROST driver errors.
+    #[error(transparent)]
+    Frost(#[from] crate::frost::Error),
+
+    /// FROST library error.
+    #[error(transparent)]
+    FrostSecp256k1(#[from] frost_secp256k1::Error),
+
+    /// Protocol library errors.
+    #[error(transparent)]
+    Protocol(#[from] polysig_protocol::Error),
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl From<Error> for wasm_bindgen::JsValue {
+    fn from(value: Error) -> Self {
+        let s = value.to_string();
+        wasm_bindgen::JsValue::from_str(&s)
+    }
+}
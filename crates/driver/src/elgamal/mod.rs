@@ -0,0 +1,150 @@
+//! Threshold ElGamal (ECIES) encryption over secp256k1.
+//!
+//! Keys are generated using the FROST Secp256k1 key generation
+//! drivers so a threshold of the key holders must cooperate to
+//! decrypt a message. Use a dedicated key generation ceremony;
+//! key shares should not be shared with signing.
+//!
+//! Encryption does not require any interaction and only needs
+//! the group public key.
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+use frost_secp256k1::keys::PublicKeyPackage;
+use k256::{
+    elliptic_curve::{
+        ops::Reduce, sec1::ToEncodedPoint, Field, PrimeField,
+    },
+    FieldBytes, ProjectivePoint, PublicKey, Scalar,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+mod decrypt;
+mod error;
+
+pub use crate::frost::secp256k1::{
+    Identifier, KeyShare, Participant, PartyOptions,
+};
+pub use decrypt::{DecryptionDriver, DecryptionShare};
+pub use error::Error;
+
+/// Result type for the ElGamal protocol.
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub(crate) const ROUND_1: u8 = 1;
+pub(crate) const ROUND_2: u8 = 2;
+
+const KDF_CONTEXT: &[u8] = b"polysig-elgamal-secp256k1-kdf";
+const PROOF_CONTEXT: &[u8] = b"polysig-elgamal-secp256k1-dleq";
+
+/// Message encrypted to a group public key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ciphertext {
+    /// Ephemeral public key as a compressed SEC1 point.
+    pub ephemeral_key: Vec<u8>,
+    /// Nonce for the symmetric cipher.
+    pub nonce: [u8; 12],
+    /// Encrypted message and authentication tag.
+    pub ciphertext: Vec<u8>,
+}
+
+/// Encrypt a message to the group public key.
+///
+/// Additional data is authenticated but not encrypted and
+/// must be given again to decrypt.
+pub fn encrypt(
+    public_key_package: &PublicKeyPackage,
+    message: &[u8],
+    aad: &[u8],
+) -> Result<Ciphertext> {
+    let public_key = crate::frost::secp256k1::export::public_key(
+        public_key_package,
+    )?;
+    encrypt_to(&public_key, message, aad)
+}
+
+/// Encrypt a message to a secp256k1 public key.
+pub fn encrypt_to(
+    public_key: &PublicKey,
+    message: &[u8],
+    aad: &[u8],
+) -> Result<Ciphertext> {
+    let secret = Scalar::random(&mut OsRng);
+    let ephemeral = ProjectivePoint::GENERATOR * secret;
+    let shared = public_key.to_projective() * secret;
+    let ephemeral_key = compress(&ephemeral);
+
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = cipher(&ephemeral_key, &shared);
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload { msg: message, aad },
+        )
+        .map_err(|_| Error::Encrypt)?;
+
+    Ok(Ciphertext {
+        ephemeral_key,
+        nonce,
+        ciphertext,
+    })
+}
+
+/// Decrypt a ciphertext using the combined shared secret.
+pub(crate) fn decrypt_with(
+    ciphertext: &Ciphertext,
+    shared: &ProjectivePoint,
+    aad: &[u8],
+) -> Result<Vec<u8>> {
+    let cipher = cipher(&ciphertext.ephemeral_key, shared);
+    cipher
+        .decrypt(
+            Nonce::from_slice(&ciphertext.nonce),
+            Payload {
+                msg: &ciphertext.ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| Error::Decrypt)
+}
+
+fn cipher(
+    ephemeral_key: &[u8],
+    shared: &ProjectivePoint,
+) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KDF_CONTEXT);
+    hasher.update(ephemeral_key);
+    hasher.update(compress(shared));
+    ChaCha20Poly1305::new(&hasher.finalize())
+}
+
+pub(crate) fn compress(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+pub(crate) fn decompress(bytes: &[u8]) -> Option<ProjectivePoint> {
+    PublicKey::from_sec1_bytes(bytes)
+        .ok()
+        .map(|k| k.to_projective())
+}
+
+pub(crate) fn scalar(bytes: &[u8]) -> Option<Scalar> {
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    Scalar::from_repr(FieldBytes::from(bytes)).into()
+}
+
+pub(crate) fn challenge(points: &[&ProjectivePoint]) -> Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(PROOF_CONTEXT);
+    for point in points {
+        hasher.update(compress(point));
+    }
+    <Scalar as Reduce<k256::U256>>::reduce_bytes(&hasher.finalize())
+}
@@ -0,0 +1,154 @@
+//! Encrypted containers for key shares and signing nonces.
+//!
+//! Key shares and pre-processed signing nonces are encoded as
+//! JSON and encrypted using XChaCha20-Poly1305 with a 32 byte
+//! key supplied by the application, for example derived from
+//! a passphrase or held in a platform keychain.
+use crate::{Error, KeyShare, Result};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Version of the encrypted container.
+pub const VERSION: u16 = 1;
+
+const NONCE_LEN: usize = 24;
+
+const NONCES_DOMAIN: &[u8] = b"polysig-nonces";
+
+/// Key share encrypted with a symmetric key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedKeyShare {
+    /// Container version.
+    pub version: u16,
+    /// Nonce for the cipher.
+    pub nonce: Vec<u8>,
+    /// Encrypted key share and authentication tag.
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedKeyShare {
+    /// Encrypt a key share.
+    pub fn encrypt(key_share: &KeyShare, key: &[u8]) -> Result<Self> {
+        let cipher = cipher(key)?;
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let plaintext = key_share.encode()?;
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: &VERSION.to_be_bytes(),
+                },
+            )
+            .map_err(|_| Error::EncryptKeyShare)?;
+
+        Ok(Self {
+            version: VERSION,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Decrypt the key share.
+    pub fn decrypt(&self, key: &[u8]) -> Result<KeyShare> {
+        if self.version != VERSION {
+            return Err(Error::EncryptedKeyShareVersion(
+                self.version,
+            ));
+        }
+        if self.nonce.len() != NONCE_LEN {
+            return Err(Error::DecryptKeyShare);
+        }
+
+        let plaintext = cipher(key)?
+            .decrypt(
+                XNonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &self.version.to_be_bytes(),
+                },
+            )
+            .map_err(|_| Error::DecryptKeyShare)?;
+        let plaintext = std::str::from_utf8(&plaintext)
+            .map_err(|_| Error::DecryptKeyShare)?;
+        KeyShare::decode(plaintext)
+    }
+}
+
+/// Pre-processed signing nonces encrypted with a symmetric key.
+///
+/// Created by encrypting a FROST nonce store; nonces are
+/// removed from the container as they are taken so the
+/// container must be persisted again after every signing
+/// session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedNonces {
+    /// Container version.
+    pub version: u16,
+    /// Nonce for the cipher.
+    pub nonce: Vec<u8>,
+    /// Encrypted signing nonces and authentication tag.
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedNonces {
+    pub(crate) fn seal(plaintext: &[u8], key: &[u8]) -> Result<Self> {
+        let cipher = cipher(key)?;
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &nonces_aad(VERSION),
+                },
+            )
+            .map_err(|_| Error::EncryptNonces)?;
+
+        Ok(Self {
+            version: VERSION,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    pub(crate) fn open(&self, key: &[u8]) -> Result<Vec<u8>> {
+        if self.version != VERSION {
+            return Err(Error::EncryptedNoncesVersion(self.version));
+        }
+        if self.nonce.len() != NONCE_LEN {
+            return Err(Error::DecryptNonces);
+        }
+
+        cipher(key)?
+            .decrypt(
+                XNonce::from_slice(&self.nonce),
+                Payload {
+                    msg: &self.ciphertext,
+                    aad: &nonces_aad(self.version),
+                },
+            )
+            .map_err(|_| Error::DecryptNonces)
+    }
+}
+
+fn nonces_aad(version: u16) -> Vec<u8> {
+    let mut aad = NONCES_DOMAIN.to_vec();
+    aad.extend_from_slice(&version.to_be_bytes());
+    aad
+}
+
+fn cipher(key: &[u8]) -> Result<XChaCha20Poly1305> {
+    XChaCha20Poly1305::new_from_slice(key)
+        .map_err(|_| Error::EncryptionKeyLength(key.len()))
+}
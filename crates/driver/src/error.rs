@@ -1,3 +1,4 @@
+use polysig_protocol::ErrorKind;
 use thiserror::Error;
 
 /// Errors generated by the driver.
@@ -21,20 +22,185 @@ pub enum Error {
     #[error("number of participants '{0}' does not match number of verifying keys '{1}'")]
     ParticipantVerifierLength(usize, usize),
 
+    /// Error when the same public key is given for more
+    /// than one participant.
+    #[error("participant at index {0} is a duplicate")]
+    DuplicateParticipant(usize),
+
+    /// Error when a batch message index is out of bounds.
+    #[error("batch index {0} is out of bounds")]
+    BatchIndex(usize),
+
+    /// Error generated by a driver in a batch.
+    #[error("batch driver {0} failed: {1}")]
+    Batch(usize, Box<dyn std::error::Error + Send + Sync>),
+
+    /// Error when the number of weights does not match the
+    /// number of participants.
+    #[error("number of participants '{0}' does not match number of weights '{1}'")]
+    ParticipantWeightLength(usize, usize),
+
+    /// Error when a participant is given a weight of zero.
+    #[error("participant at index {0} has zero weight")]
+    ZeroWeight(usize),
+
+    /// Error when the total weight of the participants
+    /// exceeds the maximum number of shares.
+    #[error("total weight exceeds the maximum number of shares")]
+    WeightOverflow,
+
+    /// Error when a share is not held by this party.
+    #[error("share {0} is not held by this party")]
+    WeightedShare(u16),
+
+    /// Error generated by the driver for a share of
+    /// a weighted party.
+    #[error("driver for share {0} failed: {1}")]
+    Weighted(u16, Box<dyn std::error::Error + Send + Sync>),
+
+    /// Error when a message is for a different plugin protocol.
+    #[error("expected message for protocol '{0}' but got '{1}'")]
+    PluginProtocol(&'static str, String),
+
+    /// Error generated by a plugin protocol driver.
+    #[error("protocol '{0}' failed: {1}")]
+    Plugin(&'static str, Box<dyn std::error::Error + Send + Sync>),
+
+    /// Error when a signature has the wrong length.
+    #[cfg(any(feature = "cggmp", feature = "ecdsa"))]
+    #[error("expected signature of {0} bytes but got {1}")]
+    SignatureLength(usize, usize),
+
+    /// Error when an Ethereum recovery value is invalid.
+    #[cfg(any(feature = "cggmp", feature = "ecdsa"))]
+    #[error("invalid ethereum recovery value {0}")]
+    EthRecoveryValue(u64),
+
+    /// Error when a digest algorithm does not produce the
+    /// prehash size expected by a protocol.
+    #[cfg(feature = "digest")]
+    #[error("digest '{0}' produces {1} bytes but a 32 byte prehash is required")]
+    IncompatibleDigest(String, usize),
+
+    /// Error when a digest algorithm is not supported.
+    #[cfg(feature = "digest")]
+    #[error("unknown digest algorithm '{0}'")]
+    UnknownDigest(String),
+
+    /// Error generated by a signer backend.
+    #[error(transparent)]
+    Signer(Box<dyn std::error::Error + Send + Sync>),
+
     /// JSON error.
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 
+    /// BLS driver errors.
+    #[cfg(feature = "bls")]
+    #[error(transparent)]
+    Bls(#[from] crate::bls::Error),
+
+    /// Ceremony certificate errors.
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    #[error(transparent)]
+    Certificate(#[from] crate::certificate::Error),
+
+    /// Error generated by a protocol driver that is
+    /// followed by a certificate endorsement.
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    #[error(transparent)]
+    Certified(Box<dyn std::error::Error + Send + Sync>),
+
+    /// Error generated when a protocol message is received
+    /// after the certificate endorsement round has started.
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    #[error("protocol message for round {0} received after the protocol completed")]
+    CertifiedRound(u8),
+
     /// CGGMP driver errors.
     #[cfg(feature = "cggmp")]
     #[error(transparent)]
     Cggmp(#[from] crate::cggmp::Error),
 
+    /// ElGamal driver errors.
+    #[cfg(feature = "elgamal")]
+    #[error(transparent)]
+    Elgamal(#[from] crate::elgamal::Error),
+
     /// FROST driver errors.
-    #[cfg(feature = "frost-ed25519")]
+    #[cfg(feature = "frost")]
     #[error(transparent)]
     Frost(#[from] crate::frost::Error),
 
+    /// MuSig2 driver errors.
+    #[cfg(feature = "musig2")]
+    #[error(transparent)]
+    Musig2(#[from] crate::musig2::Error),
+
+    /// Verifiable secret sharing errors.
+    #[cfg(feature = "vss")]
+    #[error(transparent)]
+    Vss(#[from] crate::vss::Error),
+
+    /// Error when an encryption key is not 32 bytes.
+    #[cfg(feature = "encrypt")]
+    #[error("encryption key must be 32 bytes but got {0}")]
+    EncryptionKeyLength(usize),
+
+    /// Error when a key share could not be encrypted.
+    #[cfg(feature = "encrypt")]
+    #[error("failed to encrypt key share")]
+    EncryptKeyShare,
+
+    /// Error when a key share could not be decrypted.
+    ///
+    /// Either the key is wrong or the container was modified.
+    #[cfg(feature = "encrypt")]
+    #[error("failed to decrypt key share")]
+    DecryptKeyShare,
+
+    /// Error when the version of an encrypted key share
+    /// is not supported.
+    #[cfg(feature = "encrypt")]
+    #[error("unsupported encrypted key share version {0}")]
+    EncryptedKeyShareVersion(u16),
+
+    /// Error when signing nonces could not be encrypted.
+    #[cfg(feature = "encrypt")]
+    #[error("failed to encrypt signing nonces")]
+    EncryptNonces,
+
+    /// Error when signing nonces could not be decrypted.
+    ///
+    /// Either the key is wrong or the container was modified.
+    #[cfg(feature = "encrypt")]
+    #[error("failed to decrypt signing nonces")]
+    DecryptNonces,
+
+    /// Error when the version of encrypted signing nonces
+    /// is not supported.
+    #[cfg(feature = "encrypt")]
+    #[error("unsupported encrypted nonces version {0}")]
+    EncryptedNoncesVersion(u16),
+
+    /// Error when a key share policy has expired.
+    #[error("key share expired at {0}")]
+    KeyShareExpired(u64),
+
+    /// Error when a derivation path is not allowed
+    /// by a key share policy.
+    #[error("derivation path '{0}' is not allowed by the key share policy")]
+    DerivationPathNotAllowed(String),
+
+    /// Error when a chain is not allowed by a key share policy.
+    #[error("chain '{0}' is not allowed by the key share policy")]
+    ChainNotAllowed(String),
+
+    /// Error when a key share policy restricts chains
+    /// and no chain was given.
+    #[error("key share policy requires a chain")]
+    ChainRequired,
+
     /// Protocol library errors.
     #[error(transparent)]
     Protocol(#[from] polysig_protocol::Error),
@@ -48,14 +214,173 @@ pub enum Error {
     #[error(transparent)]
     Ecdsa(#[from] k256::ecdsa::Error),
 
+    /// BIP-32 key derivation errors.
+    #[cfg(any(feature = "ecdsa", feature = "schnorr"))]
+    #[error(transparent)]
+    Bip32(#[from] bip32::Error),
+
+    /// BIP-39 mnemonic errors.
+    #[cfg(any(
+        feature = "ecdsa",
+        feature = "eddsa",
+        feature = "schnorr"
+    ))]
+    #[error(transparent)]
+    Bip39(#[from] bip39::Error),
+
+    /// Error when an EdDSA context is too long.
+    #[cfg(feature = "eddsa")]
+    #[error("context must be between 1 and 255 bytes but got {0}")]
+    ContextLength(usize),
+
+    /// Error when a SLIP-0010 derivation path is invalid.
+    ///
+    /// Ed25519 only supports hardened derivation so every
+    /// path component must be hardened.
+    #[cfg(feature = "eddsa")]
+    #[error("invalid hardened derivation path '{0}'")]
+    DerivationPath(String),
+
+    /// Error when a PSBT input does not exist.
+    #[cfg(feature = "bitcoin")]
+    #[error("PSBT input {0} does not exist")]
+    PsbtInputIndex(usize),
+
+    /// Error when the output spent by a PSBT input is missing.
+    #[cfg(feature = "bitcoin")]
+    #[error("PSBT input {0} is missing the spent output")]
+    PsbtMissingUtxo(usize),
+
+    /// Bitcoin PSBT signing errors.
+    #[cfg(feature = "bitcoin")]
+    #[error(transparent)]
+    PsbtSign(#[from] bitcoin::psbt::SignError),
+
+    /// Bitcoin taproot signature hash errors.
+    #[cfg(feature = "bitcoin")]
+    #[error(transparent)]
+    TaprootSighash(#[from] bitcoin::sighash::TaprootError),
+
+    /// Bitcoin signature hash type errors.
+    #[cfg(feature = "bitcoin")]
+    #[error(transparent)]
+    SighashType(#[from] bitcoin::sighash::InvalidSighashTypeError),
+
+    /// Secp256k1 library errors.
+    #[cfg(feature = "bitcoin")]
+    #[error(transparent)]
+    Secp256k1(#[from] bitcoin::secp256k1::Error),
+
+    /// Error when a key is not found on a PKCS#11 token.
+    #[cfg(feature = "pkcs11")]
+    #[error("key '{0}' not found on token")]
+    Pkcs11KeyNotFound(String),
+
+    /// Error when a PKCS#11 token is not found.
+    #[cfg(feature = "pkcs11")]
+    #[error("token '{0}' not found")]
+    Pkcs11TokenNotFound(String),
+
+    /// Error when a PKCS#11 key is for an unsupported algorithm.
+    #[cfg(feature = "pkcs11")]
+    #[error("algorithm '{0}' is not supported by PKCS#11 signers")]
+    Pkcs11Algorithm(crate::signers::Algorithm),
+
+    /// PKCS#11 library errors.
+    #[cfg(feature = "pkcs11")]
+    #[error(transparent)]
+    Pkcs11(#[from] cryptoki::error::Error),
+
+    /// EIP-712 typed data errors.
+    #[cfg(feature = "ecdsa")]
+    #[error(transparent)]
+    Eip712(#[from] crate::signers::ecdsa::eip712::Error),
+
+    /// Ethereum transaction errors.
+    #[cfg(feature = "ecdsa")]
+    #[error(transparent)]
+    EthTransaction(#[from] crate::signers::ecdsa::eth_tx::Error),
+
     /// Ed25519 library errors.
     // NOTE: must be boxed otherwise thiserror will compile two
     // NOTE: From implementations when the full feature is enabled
-    #[cfg(any(feature = "eddsa", feature = "frost-ed25519"))]
+    #[cfg(any(
+        feature = "eddsa",
+        feature = "frost-ed25519",
+        feature = "frost-ed448",
+        feature = "frost-ristretto255"
+    ))]
     #[error(transparent)]
     Ed25519(#[from] Box<ed25519::Error>),
 }
 
+impl Error {
+    /// Category of this error.
+    ///
+    /// Protocol library errors and boxed errors keep the
+    /// category of the underlying error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Protocol(e) => e.kind(),
+            _ => self
+                .boxed()
+                .and_then(classify)
+                .map(|(kind, _)| kind)
+                .unwrap_or(ErrorKind::Protocol),
+        }
+    }
+
+    /// Whether the operation may succeed if retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Protocol(e) => e.is_retryable(),
+            _ => self
+                .boxed()
+                .and_then(classify)
+                .map(|(_, retryable)| retryable)
+                .unwrap_or(false),
+        }
+    }
+
+    /// Boxed error wrapped by this error.
+    fn boxed(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Batch(_, e)
+            | Error::Weighted(_, e)
+            | Error::Plugin(_, e)
+            | Error::Signer(e) => Some(&**e),
+            #[cfg(any(feature = "cggmp", feature = "frost"))]
+            Error::Certified(e) => Some(&**e),
+            _ => None,
+        }
+    }
+}
+
+/// Category and retryability of the first driver, protocol
+/// or input/output error in the source chain of an error.
+///
+/// Used to classify the boxed errors of protocol drivers.
+pub fn classify(
+    error: &(dyn std::error::Error + 'static),
+) -> Option<(ErrorKind, bool)> {
+    let mut next = Some(error);
+    while let Some(error) = next {
+        if let Some(e) = error.downcast_ref::<Error>() {
+            return Some((e.kind(), e.is_retryable()));
+        }
+        if let Some(e) =
+            error.downcast_ref::<polysig_protocol::Error>()
+        {
+            return Some((e.kind(), e.is_retryable()));
+        }
+        if error.is::<std::io::Error>() {
+            return Some((ErrorKind::Transport, true));
+        }
+        next = error.source();
+    }
+    None
+}
+
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 impl From<Error> for wasm_bindgen::JsValue {
     fn from(value: Error) -> Self {
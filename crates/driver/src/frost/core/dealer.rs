@@ -0,0 +1,236 @@
+//! Macro to generate trusted dealer key generation driver for FROST.
+//!
+//! The dealer is one of the session participants and splits
+//! an existing or freshly generated signing key into shares.
+//!
+//! Round 1: the dealer sends a secret share and the public key
+//!          package to every other participant.
+//!
+//! Participants verify their secret share against the
+//! commitment before accepting the key share.
+macro_rules! frost_dealer_impl {
+    ($signing_key:ty, $id:ty) => {
+        /// Package sent by the dealer to each participant.
+        #[derive(Debug, Serialize, Deserialize)]
+        pub struct DealerPackage {
+            secret_share: SecretShare,
+            public_key_package: PublicKeyPackage,
+        }
+
+        /// FROST trusted dealer key generation driver.
+        pub struct DealerDriver {
+            #[allow(dead_code)]
+            party_number: NonZeroU16,
+            params: Parameters,
+            identifiers: Vec<$id>,
+            id: $id,
+            dealer: $id,
+            round_number: u8,
+            signing_key: Option<$signing_key>,
+            key_share: Option<KeyShare>,
+            package: Option<DealerPackage>,
+        }
+
+        impl DealerDriver {
+            /// Create a trusted dealer key generation driver.
+            ///
+            /// When this party is the dealer an existing signing
+            /// key may be given to be split into shares otherwise
+            /// a new signing key is generated; parties that are not
+            /// the dealer must not provide a signing key.
+            pub fn new(
+                party_number: NonZeroU16,
+                params: Parameters,
+                identifiers: Vec<$id>,
+                dealer: $id,
+                signing_key: Option<$signing_key>,
+            ) -> Result<Self> {
+                let party_index: usize = party_number.get() as usize;
+                let self_index = party_index - 1;
+                let id = *identifiers
+                    .get(self_index)
+                    .ok_or(Error::IndexIdentifier(party_index))?;
+
+                if !identifiers.contains(&dealer)
+                    || (id != dealer && signing_key.is_some())
+                {
+                    return Err(Error::DealerParticipant);
+                }
+
+                Ok(Self {
+                    party_number,
+                    params,
+                    identifiers,
+                    id,
+                    dealer,
+                    round_number: ROUND_1,
+                    signing_key,
+                    key_share: None,
+                    package: None,
+                })
+            }
+
+            fn is_dealer(&self) -> bool {
+                self.id == self.dealer
+            }
+
+            fn receiver(&self, id: &$id) -> NonZeroU16 {
+                let index = self
+                    .identifiers
+                    .iter()
+                    .position(|i| i == id)
+                    .unwrap();
+                NonZeroU16::new((index + 1) as u16).unwrap()
+            }
+
+            fn deal(
+                &mut self,
+            ) -> Result<Vec<RoundMessage<DealerPackage, $id>>> {
+                let Parameters { parties, threshold } = self.params;
+                let identifiers =
+                    IdentifierList::Custom(&self.identifiers);
+                let (mut shares, public_key_package) =
+                    match self.signing_key.take() {
+                        Some(signing_key) => split(
+                            &signing_key,
+                            parties,
+                            threshold,
+                            identifiers,
+                            &mut OsRng,
+                        )?,
+                        None => generate_with_dealer(
+                            parties,
+                            threshold,
+                            identifiers,
+                            &mut OsRng,
+                        )?,
+                    };
+
+                let own_share = shares.remove(&self.id).ok_or(
+                    Error::IndexIdentifier(
+                        self.party_number.get() as usize
+                    ),
+                )?;
+                self.key_share = Some((
+                    KeyPackage::try_from(own_share)?,
+                    public_key_package.clone(),
+                ));
+
+                Ok(shares
+                    .into_iter()
+                    .map(|(id, secret_share)| RoundMessage {
+                        round: NonZeroU16::new(
+                            self.round_number.into(),
+                        )
+                        .unwrap(),
+                        sender: self.id.clone(),
+                        receiver: self.receiver(&id),
+                        body: DealerPackage {
+                            secret_share,
+                            public_key_package: public_key_package
+                                .clone(),
+                        },
+                    })
+                    .collect())
+            }
+
+            fn accept(&mut self) -> Result<KeyShare> {
+                let DealerPackage {
+                    secret_share,
+                    public_key_package,
+                } = self
+                    .package
+                    .take()
+                    .ok_or(Error::Round2TooEarly)?;
+
+                let key_package = KeyPackage::try_from(secret_share)?;
+                let verifying_share = public_key_package
+                    .verifying_shares()
+                    .get(&self.id)
+                    .ok_or(Error::DealerVerifyingShare)?;
+                if verifying_share != key_package.verifying_share()
+                    || public_key_package.verifying_key()
+                        != key_package.verifying_key()
+                {
+                    return Err(Error::DealerVerifyingShare);
+                }
+
+                Ok((key_package, public_key_package))
+            }
+        }
+
+        impl ProtocolDriver for DealerDriver {
+            type Error = Error;
+            type Message = RoundMessage<DealerPackage, $id>;
+            type Output = KeyShare;
+
+            fn round_info(&self) -> Result<RoundInfo> {
+                let round_number = self.round_number;
+                let is_echo = false;
+                let can_finalize = match self.round_number {
+                    // Dealer is done once the shares are sent
+                    ROUND_2 if self.is_dealer() => true,
+                    ROUND_2 => self.package.is_some(),
+                    _ => false,
+                };
+                Ok(RoundInfo {
+                    round_number,
+                    can_finalize,
+                    is_echo,
+                })
+            }
+
+            fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+                let messages = match self.round_number {
+                    ROUND_1 if self.is_dealer() => self.deal()?,
+                    // Participants wait for the dealer package
+                    ROUND_1 => Vec::new(),
+                    _ => {
+                        return Err(Error::InvalidRound(
+                            self.round_number,
+                        ))
+                    }
+                };
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+                Ok(messages)
+            }
+
+            fn handle_incoming(
+                &mut self,
+                message: Self::Message,
+            ) -> Result<()> {
+                let round_number = message.round.get() as u8;
+                if message.sender != self.dealer {
+                    return Err(Error::SenderVerifier);
+                }
+                match round_number {
+                    ROUND_1 if !self.is_dealer() => {
+                        self.package = Some(message.body);
+                        Ok(())
+                    }
+                    ROUND_1 => Err(Error::RoundPayload(round_number)),
+                    _ => Err(Error::InvalidRound(round_number)),
+                }
+            }
+
+            fn try_finalize_round(
+                &mut self,
+            ) -> Result<Option<Self::Output>> {
+                if self.round_number != ROUND_2 {
+                    return Ok(None);
+                }
+                if self.is_dealer() {
+                    Ok(self.key_share.take())
+                } else if self.package.is_some() {
+                    Ok(Some(self.accept()?))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use frost_dealer_impl;
@@ -0,0 +1,45 @@
+//! Macro to generate key export functions for FROST.
+//!
+//! Key packages are encoded as JSON using the serde
+//! representation from the ZF FROST libraries so they
+//! can be used with the reference tooling.
+macro_rules! frost_export_impl {
+    () => {
+        /// Group verifying key encoded for the ciphersuite.
+        pub fn verifying_key_bytes(
+            public_key_package: &PublicKeyPackage,
+        ) -> Result<Vec<u8>> {
+            Ok(public_key_package.verifying_key().serialize()?)
+        }
+
+        /// Encode a key package as JSON.
+        pub fn key_package_to_json(
+            key_package: &KeyPackage,
+        ) -> Result<String> {
+            Ok(serde_json::to_string_pretty(key_package)?)
+        }
+
+        /// Decode a key package from JSON.
+        pub fn key_package_from_json(
+            json: &str,
+        ) -> Result<KeyPackage> {
+            Ok(serde_json::from_str(json)?)
+        }
+
+        /// Encode a public key package as JSON.
+        pub fn public_key_package_to_json(
+            public_key_package: &PublicKeyPackage,
+        ) -> Result<String> {
+            Ok(serde_json::to_string_pretty(public_key_package)?)
+        }
+
+        /// Decode a public key package from JSON.
+        pub fn public_key_package_from_json(
+            json: &str,
+        ) -> Result<PublicKeyPackage> {
+            Ok(serde_json::from_str(json)?)
+        }
+    };
+}
+
+pub(crate) use frost_export_impl;
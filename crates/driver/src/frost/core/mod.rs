@@ -1,5 +1,10 @@
 //! Macros for the FROST protocol.
+pub(crate) mod dealer;
 pub(crate) mod dkg;
+pub(crate) mod export;
+pub(crate) mod preprocess;
+pub(crate) mod refresh;
+pub(crate) mod repair;
 pub(crate) mod sign;
 
 macro_rules! key_share_pem {
@@ -16,6 +21,7 @@ macro_rules! key_share_pem {
                 Ok(Self {
                     version: PEM_VERSION,
                     contents: key_share,
+                    policy: None,
                 })
             }
         }
@@ -0,0 +1,351 @@
+//! Macro to generate nonce pre-processing driver for FROST.
+//!
+//! Round 1: each participant generates a batch of signing nonces
+//!          and sends the commitments to every other participant.
+//!
+//! The nonces and commitments are kept in a nonce store so that
+//! a later signing session can skip the commitment round and
+//! only needs to exchange signature shares.
+//!
+//! A signing nonce must never be used for more than one
+//! signature, signing two different messages with the same
+//! nonce reveals the signing share of the participant. The
+//! nonce store therefore cannot be serialized directly; it
+//! is persisted as an encrypted container and each nonce is
+//! deleted from the container when it is taken.
+macro_rules! frost_preprocess_impl {
+    ($r1pub:ty, $r1priv:ty, $id:ty) => {
+        /// Nonce and commitments for a single signing session.
+        #[derive(Debug)]
+        pub struct PreprocessedNonce {
+            pub(crate) nonces: $r1priv,
+            pub(crate) commitments: BTreeMap<$id, $r1pub>,
+        }
+
+        /// Signing nonces and commitments generated ahead of time.
+        ///
+        /// Each nonce may only be used once, reusing a nonce for
+        /// a different message reveals the signing share. Nonces
+        /// are removed from the store when they are taken and the
+        /// store can only be persisted encrypted, use
+        /// `NonceStore::take_encrypted` to take a nonce from an
+        /// encrypted container and store the updated container
+        /// before signing with the nonce.
+        #[derive(Debug)]
+        pub struct NonceStore {
+            nonces: BTreeMap<u32, $r1priv>,
+            commitments: BTreeMap<u32, BTreeMap<$id, $r1pub>>,
+        }
+
+        /// Plaintext of an encrypted nonce store.
+        #[cfg(feature = "encrypt")]
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct StoredNonces {
+            nonces: Vec<(u32, $r1priv)>,
+            commitments: Vec<(u32, Vec<($id, $r1pub)>)>,
+        }
+
+        impl NonceStore {
+            /// Number of unused nonces.
+            pub fn len(&self) -> usize {
+                self.nonces.len()
+            }
+
+            /// Determine if all the nonces have been used.
+            pub fn is_empty(&self) -> bool {
+                self.nonces.is_empty()
+            }
+
+            /// Indices of the unused nonces.
+            pub fn indices(&self) -> Vec<u32> {
+                self.nonces.keys().copied().collect()
+            }
+
+            /// Remove the nonce at an index with the commitments
+            /// of the signers.
+            ///
+            /// Every signer must use the same index for a signing
+            /// session.
+            pub fn take(
+                &mut self,
+                index: u32,
+                signers: &[$id],
+            ) -> Result<PreprocessedNonce> {
+                let commitments = self
+                    .commitments
+                    .get(&index)
+                    .ok_or(Error::NonceIndex(index))?;
+                let commitments = signers
+                    .iter()
+                    .map(|id| {
+                        commitments
+                            .get(id)
+                            .map(|c| (*id, c.clone()))
+                            .ok_or(Error::NonceCommitments)
+                    })
+                    .collect::<Result<BTreeMap<_, _>>>()?;
+
+                let nonces = self
+                    .nonces
+                    .remove(&index)
+                    .ok_or(Error::NonceIndex(index))?;
+                self.commitments.remove(&index);
+
+                Ok(PreprocessedNonce {
+                    nonces,
+                    commitments,
+                })
+            }
+
+            /// Encrypt the nonce store with a 32 byte key.
+            ///
+            /// The store should be dropped once the container
+            /// has been persisted so that nonces are only taken
+            /// from the container.
+            #[cfg(feature = "encrypt")]
+            pub fn encrypt(
+                &self,
+                key: &[u8],
+            ) -> crate::Result<crate::encrypt::EncryptedNonces> {
+                let stored = StoredNonces {
+                    nonces: self
+                        .nonces
+                        .iter()
+                        .map(|(i, n)| (*i, n.clone()))
+                        .collect(),
+                    commitments: self
+                        .commitments
+                        .iter()
+                        .map(|(i, c)| {
+                            (
+                                *i,
+                                c.iter()
+                                    .map(|(id, c)| (*id, c.clone()))
+                                    .collect(),
+                            )
+                        })
+                        .collect(),
+                };
+                let plaintext = zeroize::Zeroizing::new(
+                    serde_json::to_vec(&stored)?,
+                );
+                crate::encrypt::EncryptedNonces::seal(&plaintext, key)
+            }
+
+            /// Decrypt a nonce store.
+            ///
+            /// Nonces taken from the decrypted store are not
+            /// removed from the container; prefer
+            /// [NonceStore::take_encrypted] when signing.
+            #[cfg(feature = "encrypt")]
+            pub fn decrypt(
+                container: &crate::encrypt::EncryptedNonces,
+                key: &[u8],
+            ) -> crate::Result<Self> {
+                let plaintext =
+                    zeroize::Zeroizing::new(container.open(key)?);
+                let stored: StoredNonces =
+                    serde_json::from_slice(&plaintext)?;
+                Ok(Self {
+                    nonces: stored.nonces.into_iter().collect(),
+                    commitments: stored
+                        .commitments
+                        .into_iter()
+                        .map(|(i, c)| (i, c.into_iter().collect()))
+                        .collect(),
+                })
+            }
+
+            /// Take the nonce at an index from an encrypted
+            /// container.
+            ///
+            /// The nonce is deleted and the container is replaced
+            /// with a new encryption of the remaining nonces; the
+            /// updated container must be persisted before the
+            /// nonce is used to sign so the nonce can never be
+            /// taken again.
+            #[cfg(feature = "encrypt")]
+            pub fn take_encrypted(
+                container: &mut crate::encrypt::EncryptedNonces,
+                key: &[u8],
+                index: u32,
+                signers: &[$id],
+            ) -> crate::Result<PreprocessedNonce> {
+                let mut store = Self::decrypt(container, key)?;
+                let nonce = store.take(index, signers)?;
+                *container = store.encrypt(key)?;
+                Ok(nonce)
+            }
+        }
+
+        /// FROST nonce pre-processing driver.
+        pub struct PreprocessDriver {
+            #[allow(dead_code)]
+            party_number: NonZeroU16,
+            identifiers: Vec<$id>,
+            id: $id,
+            count: u32,
+            round_number: u8,
+            key_share: KeyShare,
+            nonces: BTreeMap<u32, $r1priv>,
+            commitments: BTreeMap<$id, Vec<$r1pub>>,
+        }
+
+        impl PreprocessDriver {
+            /// Create a driver that generates a batch of
+            /// signing nonces.
+            pub fn new(
+                party_number: NonZeroU16,
+                identifiers: Vec<$id>,
+                key_share: KeyShare,
+                count: u32,
+            ) -> Result<Self> {
+                let party_index: usize = party_number.get() as usize;
+                let self_index = party_index - 1;
+                let id = *identifiers
+                    .get(self_index)
+                    .ok_or(Error::IndexIdentifier(party_index))?;
+
+                Ok(Self {
+                    party_number,
+                    identifiers,
+                    id,
+                    count,
+                    round_number: ROUND_1,
+                    key_share,
+                    nonces: BTreeMap::new(),
+                    commitments: BTreeMap::new(),
+                })
+            }
+        }
+
+        impl ProtocolDriver for PreprocessDriver {
+            type Error = Error;
+            type Message = RoundMessage<Vec<$r1pub>, $id>;
+            type Output = NonceStore;
+
+            fn round_info(&self) -> Result<RoundInfo> {
+                let round_number = self.round_number;
+                let is_echo = false;
+                let can_finalize = match self.round_number {
+                    ROUND_2 => {
+                        self.commitments.len()
+                            == self.identifiers.len()
+                    }
+                    _ => false,
+                };
+                Ok(RoundInfo {
+                    round_number,
+                    can_finalize,
+                    is_echo,
+                })
+            }
+
+            fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+                match self.round_number {
+                    ROUND_1 => {
+                        let mut commitments =
+                            Vec::with_capacity(self.count as usize);
+                        for index in 0..self.count {
+                            let (nonces, commitment) = round1::commit(
+                                self.key_share.0.signing_share(),
+                                &mut OsRng,
+                            );
+                            self.nonces.insert(index, nonces);
+                            commitments.push(commitment);
+                        }
+
+                        let mut messages = Vec::with_capacity(
+                            self.identifiers.len() - 1,
+                        );
+                        for (index, id) in
+                            self.identifiers.iter().enumerate()
+                        {
+                            if id == &self.id {
+                                continue;
+                            }
+
+                            messages.push(RoundMessage {
+                                round: NonZeroU16::new(
+                                    self.round_number.into(),
+                                )
+                                .unwrap(),
+                                sender: self.id.clone(),
+                                receiver: NonZeroU16::new(
+                                    (index + 1) as u16,
+                                )
+                                .unwrap(),
+                                body: commitments.clone(),
+                            });
+                        }
+
+                        self.commitments
+                            .insert(self.id.clone(), commitments);
+
+                        self.round_number =
+                            self.round_number.checked_add(1).unwrap();
+
+                        Ok(messages)
+                    }
+                    _ => Err(Error::InvalidRound(self.round_number)),
+                }
+            }
+
+            fn handle_incoming(
+                &mut self,
+                message: Self::Message,
+            ) -> Result<()> {
+                let round_number = message.round.get() as u8;
+                match round_number {
+                    ROUND_1 => {
+                        if !self.identifiers.contains(&message.sender)
+                        {
+                            return Err(Error::SenderVerifier);
+                        }
+                        if message.body.len() != self.count as usize {
+                            return Err(Error::NonceCount(
+                                message.body.len(),
+                            ));
+                        }
+                        self.commitments
+                            .insert(message.sender, message.body);
+                        Ok(())
+                    }
+                    _ => Err(Error::InvalidRound(round_number)),
+                }
+            }
+
+            fn try_finalize_round(
+                &mut self,
+            ) -> Result<Option<Self::Output>> {
+                if self.round_number == ROUND_2
+                    && self.commitments.len()
+                        == self.identifiers.len()
+                {
+                    let mut commitments = BTreeMap::new();
+                    for (id, batch) in
+                        std::mem::take(&mut self.commitments)
+                    {
+                        for (index, commitment) in
+                            batch.into_iter().enumerate()
+                        {
+                            commitments
+                                .entry(index as u32)
+                                .or_insert_with(BTreeMap::new)
+                                .insert(id, commitment);
+                        }
+                    }
+
+                    Ok(Some(NonceStore {
+                        nonces: std::mem::take(&mut self.nonces),
+                        commitments,
+                    }))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use frost_preprocess_impl;
@@ -0,0 +1,124 @@
+//! Macro to generate share refresh driver for FROST.
+//!
+//! Refreshing shares runs the same three round protocol as
+//! distributed key generation so the generated driver wraps
+//! a DKG driver that collects the round packages and then
+//! computes the refreshed key share from the existing key share.
+macro_rules! frost_refresh_impl {
+    ($r1pub:ty,
+     $r1priv:ty,
+     $r2pub:ty,
+     $r2priv:ty,
+     $id:ty,
+     $part1:ident,
+     $part2:ident,
+     $shares:ident) => {
+        /// Packages collected during the refresh rounds.
+        pub struct RefreshPackages {
+            round2_secret_package: $r2priv,
+            round1_packages: BTreeMap<$id, $r1pub>,
+            round2_packages: BTreeMap<$id, $r2pub>,
+        }
+
+        fn collect_packages(
+            round2_secret_package: &$r2priv,
+            round1_packages: &BTreeMap<$id, $r1pub>,
+            round2_packages: &BTreeMap<$id, $r2pub>,
+        ) -> Result<RefreshPackages> {
+            Ok(RefreshPackages {
+                round2_secret_package: round2_secret_package.clone(),
+                round1_packages: round1_packages.clone(),
+                round2_packages: round2_packages.clone(),
+            })
+        }
+
+        mod rounds {
+            use super::*;
+
+            frost_dkg_impl!(
+                $r1pub,
+                $r1priv,
+                $r2pub,
+                $r2priv,
+                $id,
+                RefreshPackages,
+                $part1,
+                $part2,
+                collect_packages
+            );
+        }
+
+        pub use rounds::DkgPackage as RefreshPackage;
+
+        /// FROST share refresh driver.
+        pub struct RefreshDriver {
+            inner: rounds::DkgDriver,
+            key_share: Option<KeyShare>,
+        }
+
+        impl RefreshDriver {
+            /// Create a share refresh driver.
+            pub fn new(
+                party_number: NonZeroU16,
+                params: Parameters,
+                identifiers: Vec<$id>,
+                key_share: KeyShare,
+            ) -> Result<Self> {
+                Ok(Self {
+                    inner: rounds::DkgDriver::new(
+                        party_number,
+                        params,
+                        identifiers,
+                    )?,
+                    key_share: Some(key_share),
+                })
+            }
+        }
+
+        impl ProtocolDriver for RefreshDriver {
+            type Error = Error;
+            type Message = RoundMessage<RefreshPackage, $id>;
+            type Output = KeyShare;
+
+            fn round_info(&self) -> Result<RoundInfo> {
+                self.inner.round_info()
+            }
+
+            fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+                self.inner.proceed()
+            }
+
+            fn handle_incoming(
+                &mut self,
+                message: Self::Message,
+            ) -> Result<()> {
+                self.inner.handle_incoming(message)
+            }
+
+            fn try_finalize_round(
+                &mut self,
+            ) -> Result<Option<Self::Output>> {
+                if let Some(packages) =
+                    self.inner.try_finalize_round()?
+                {
+                    let (key_package, public_key_package) = self
+                        .key_share
+                        .take()
+                        .ok_or(Error::Round3TooEarly)?;
+                    let result = $shares(
+                        &packages.round2_secret_package,
+                        &packages.round1_packages,
+                        &packages.round2_packages,
+                        public_key_package,
+                        key_package,
+                    )?;
+                    Ok(Some(result))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use frost_refresh_impl;
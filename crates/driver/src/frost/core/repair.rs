@@ -0,0 +1,344 @@
+//! Macro to generate share repair driver for FROST.
+//!
+//! The session participants are the helpers that hold key
+//! shares and the participant whose share is being repaired.
+//!
+//! Round 1: each helper computes a delta for every other helper.
+//! Round 2: each helper sums the received deltas and sends the
+//!          resulting sigma to the participant being repaired.
+//!
+//! The participant being repaired combines the sigmas to recover
+//! the signing share; the group key is never reconstructed.
+macro_rules! frost_repair_impl {
+    ($field:ty, $id:ty) => {
+        type Scalar = <$field as Field>::Scalar;
+
+        #[derive(Debug, Serialize, Deserialize)]
+        pub enum RepairPackage {
+            Delta(Vec<u8>),
+            Sigma {
+                sigma: Vec<u8>,
+                public_key_package: PublicKeyPackage,
+                min_signers: u16,
+            },
+        }
+
+        fn serialize_scalar(scalar: &Scalar) -> Vec<u8> {
+            <$field as Field>::serialize(scalar).as_ref().to_vec()
+        }
+
+        fn deserialize_scalar(bytes: &[u8]) -> Result<Scalar> {
+            let buf =
+                bytes.try_into().map_err(|_| Error::RepairScalar)?;
+            <$field as Field>::deserialize(&buf)
+                .map_err(|_| Error::RepairScalar)
+        }
+
+        /// FROST share repair driver.
+        pub struct RepairDriver {
+            #[allow(dead_code)]
+            party_number: NonZeroU16,
+            identifiers: Vec<$id>,
+            helpers: Vec<$id>,
+            id: $id,
+            participant: $id,
+            round_number: u8,
+            key_share: Option<KeyShare>,
+            deltas: BTreeMap<$id, Scalar>,
+            sigmas:
+                BTreeMap<$id, (Scalar, PublicKeyPackage, u16)>,
+        }
+
+        impl RepairDriver {
+            /// Create a share repair driver.
+            ///
+            /// The key share must be given for helpers and
+            /// omitted for the participant being repaired.
+            pub fn new(
+                party_number: NonZeroU16,
+                identifiers: Vec<$id>,
+                participant: $id,
+                key_share: Option<KeyShare>,
+            ) -> Result<Self> {
+                let party_index: usize = party_number.get() as usize;
+                let self_index = party_index - 1;
+                let id = *identifiers
+                    .get(self_index)
+                    .ok_or(Error::IndexIdentifier(party_index))?;
+
+                if !identifiers.contains(&participant) {
+                    return Err(Error::RepairParticipant);
+                }
+
+                // Helpers must have a key share and the
+                // participant being repaired must not
+                if (id == participant) == key_share.is_some() {
+                    return Err(Error::RepairParticipant);
+                }
+
+                let helpers = identifiers
+                    .iter()
+                    .filter(|i| *i != &participant)
+                    .copied()
+                    .collect();
+
+                Ok(Self {
+                    party_number,
+                    identifiers,
+                    helpers,
+                    id,
+                    participant,
+                    round_number: ROUND_1,
+                    key_share,
+                    deltas: BTreeMap::new(),
+                    sigmas: BTreeMap::new(),
+                })
+            }
+
+            fn is_helper(&self) -> bool {
+                self.id != self.participant
+            }
+
+            fn receiver(&self, id: &$id) -> NonZeroU16 {
+                let index = self
+                    .identifiers
+                    .iter()
+                    .position(|i| i == id)
+                    .unwrap();
+                NonZeroU16::new((index + 1) as u16).unwrap()
+            }
+
+            fn message(
+                &self,
+                receiver: &$id,
+                body: RepairPackage,
+            ) -> RoundMessage<RepairPackage, $id> {
+                RoundMessage {
+                    round: NonZeroU16::new(self.round_number.into())
+                        .unwrap(),
+                    sender: self.id.clone(),
+                    receiver: self.receiver(receiver),
+                    body,
+                }
+            }
+
+            fn repair(&mut self) -> Result<KeyShare> {
+                let mut packages = self.sigmas.values();
+                let (_, public_key_package, min_signers) =
+                    packages.next().ok_or(Error::Round3TooEarly)?;
+                if packages.any(|(_, p, m)| {
+                    p != public_key_package || m != min_signers
+                }) {
+                    return Err(Error::RepairPublicKeyPackage);
+                }
+
+                let sigmas = self
+                    .sigmas
+                    .values()
+                    .map(|(sigma, _, _)| *sigma)
+                    .collect::<Vec<_>>();
+
+                let commitment =
+                    VerifiableSecretSharingCommitment::deserialize(
+                        Vec::new(),
+                    )?;
+                let secret_share = repair_share_step_3(
+                    &sigmas,
+                    self.participant,
+                    &commitment,
+                );
+
+                let signing_share = *secret_share.signing_share();
+                let verifying_share = public_key_package
+                    .verifying_shares()
+                    .get(&self.participant)
+                    .ok_or(Error::RepairVerifyingShare)?;
+                if &VerifyingShare::from(signing_share)
+                    != verifying_share
+                {
+                    return Err(Error::RepairVerifyingShare);
+                }
+
+                let key_package = KeyPackage::new(
+                    self.participant,
+                    signing_share,
+                    *verifying_share,
+                    *public_key_package.verifying_key(),
+                    *min_signers,
+                );
+
+                Ok((key_package, public_key_package.clone()))
+            }
+        }
+
+        impl ProtocolDriver for RepairDriver {
+            type Error = Error;
+            type Message = RoundMessage<RepairPackage, $id>;
+            type Output = Option<KeyShare>;
+
+            fn round_info(&self) -> Result<RoundInfo> {
+                let round_number = self.round_number;
+                let is_echo = false;
+                let can_finalize = if self.is_helper() {
+                    match self.round_number {
+                        ROUND_2 => {
+                            self.deltas.len() == self.helpers.len()
+                        }
+                        // Helpers are done once the sigma is sent
+                        ROUND_3 => true,
+                        _ => false,
+                    }
+                } else {
+                    match self.round_number {
+                        ROUND_2 => {
+                            self.sigmas.len() == self.helpers.len()
+                        }
+                        _ => false,
+                    }
+                };
+                Ok(RoundInfo {
+                    round_number,
+                    can_finalize,
+                    is_echo,
+                })
+            }
+
+            fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+                let messages = match self.round_number {
+                    ROUND_1 if self.is_helper() => {
+                        let key_share =
+                            self.key_share.as_ref().unwrap();
+                        let commitment =
+                            VerifiableSecretSharingCommitment::deserialize(
+                                Vec::new(),
+                            )?;
+                        let secret_share = SecretShare::new(
+                            *key_share.0.identifier(),
+                            *key_share.0.signing_share(),
+                            commitment,
+                        );
+
+                        let mut deltas = repair_share_step_1(
+                            &self.helpers,
+                            &secret_share,
+                            &mut OsRng,
+                            self.participant,
+                        )?;
+
+                        let own_delta = deltas
+                            .remove(&self.id)
+                            .ok_or(Error::IndexIdentifier(
+                                self.party_number.get() as usize,
+                            ))?;
+                        self.deltas.insert(self.id, own_delta);
+
+                        deltas
+                            .iter()
+                            .map(|(id, delta)| {
+                                self.message(
+                                    id,
+                                    RepairPackage::Delta(
+                                        serialize_scalar(delta),
+                                    ),
+                                )
+                            })
+                            .collect()
+                    }
+                    // Participant being repaired waits for the sigmas
+                    ROUND_1 => Vec::new(),
+                    ROUND_2 if self.is_helper() => {
+                        let deltas = self
+                            .deltas
+                            .values()
+                            .copied()
+                            .collect::<Vec<_>>();
+                        let sigma = repair_share_step_2(&deltas);
+                        let key_share =
+                            self.key_share.as_ref().unwrap();
+                        vec![self.message(
+                            &self.participant,
+                            RepairPackage::Sigma {
+                                sigma: serialize_scalar(&sigma),
+                                public_key_package: key_share
+                                    .1
+                                    .clone(),
+                                min_signers: *key_share
+                                    .0
+                                    .min_signers(),
+                            },
+                        )]
+                    }
+                    _ => {
+                        return Err(Error::InvalidRound(
+                            self.round_number,
+                        ))
+                    }
+                };
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+                Ok(messages)
+            }
+
+            fn handle_incoming(
+                &mut self,
+                message: Self::Message,
+            ) -> Result<()> {
+                let round_number = message.round.get() as u8;
+                if !self.helpers.contains(&message.sender) {
+                    return Err(Error::SenderVerifier);
+                }
+                match (round_number, message.body) {
+                    (ROUND_1, RepairPackage::Delta(delta))
+                        if self.is_helper() =>
+                    {
+                        self.deltas.insert(
+                            message.sender,
+                            deserialize_scalar(&delta)?,
+                        );
+                        Ok(())
+                    }
+                    (
+                        ROUND_2,
+                        RepairPackage::Sigma {
+                            sigma,
+                            public_key_package,
+                            min_signers,
+                        },
+                    ) if !self.is_helper() => {
+                        self.sigmas.insert(
+                            message.sender,
+                            (
+                                deserialize_scalar(&sigma)?,
+                                public_key_package,
+                                min_signers,
+                            ),
+                        );
+                        Ok(())
+                    }
+                    (ROUND_1, _) | (ROUND_2, _) => {
+                        Err(Error::RoundPayload(round_number))
+                    }
+                    _ => Err(Error::InvalidRound(round_number)),
+                }
+            }
+
+            fn try_finalize_round(
+                &mut self,
+            ) -> Result<Option<Self::Output>> {
+                if self.is_helper() {
+                    if self.round_number == ROUND_3 {
+                        return Ok(Some(None));
+                    }
+                } else if self.round_number == ROUND_2
+                    && self.sigmas.len() == self.helpers.len()
+                {
+                    return Ok(Some(Some(self.repair()?)));
+                }
+                Ok(None)
+            }
+        }
+    };
+}
+
+pub(crate) use frost_repair_impl;
@@ -1,4 +1,21 @@
 //! Macro to generate signing driver for FROST.
+//!
+//! Signing supports two topologies:
+//!
+//! Broadcast: every signer sends commitments and signature
+//! shares to every other signer and each signer aggregates
+//! the signature (O(n²) messages).
+//!
+//! Coordinator: signers send commitments to a coordinator
+//! which replies with the signing package, collects the
+//! signature shares, aggregates the signature and sends it
+//! to every signer (O(n) messages).
+//!
+//! Signature shares are verified as they arrive so that an
+//! invalid share is attributed to the signer that sent it.
+//!
+//! When nonces are pre-processed the commitment round is
+//! skipped and signers only exchange signature shares.
 macro_rules! frost_sign_impl {
     ($r1pub:ty,
      $r1priv:ty,
@@ -13,6 +30,20 @@ macro_rules! frost_sign_impl {
         pub enum SignPackage {
             Round1($r1pub),
             Round2($r2pub),
+            SigningPackage($r2priv),
+            Signature($out),
+        }
+
+        /// Message topology for signing.
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        pub enum SignatureMode {
+            /// Every signer exchanges messages with every other
+            /// signer and aggregates the signature.
+            #[default]
+            Broadcast,
+            /// Signers only exchange messages with the coordinator
+            /// which aggregates the signature.
+            Coordinator($id),
         }
 
         /// FROST signature driver.
@@ -25,10 +56,12 @@ macro_rules! frost_sign_impl {
             round_number: u8,
             key_share: KeyShare,
             message: Vec<u8>,
+            mode: SignatureMode,
             nonces: Option<$r1priv>,
             commitments: BTreeMap<$id, $r1pub>,
             signing_package: Option<$r2priv>,
             signature_shares: BTreeMap<$id, $r2pub>,
+            signature: Option<$out>,
         }
 
         impl SignatureDriver {
@@ -39,6 +72,25 @@ macro_rules! frost_sign_impl {
                 min_signers: u16,
                 key_share: KeyShare,
                 message: Vec<u8>,
+            ) -> Result<Self> {
+                Self::new_with_mode(
+                    party_number,
+                    identifiers,
+                    min_signers,
+                    key_share,
+                    message,
+                    SignatureMode::Broadcast,
+                )
+            }
+
+            /// Create a driver using the given message topology.
+            pub fn new_with_mode(
+                party_number: NonZeroU16,
+                identifiers: Vec<Identifier>,
+                min_signers: u16,
+                key_share: KeyShare,
+                message: Vec<u8>,
+                mode: SignatureMode,
             ) -> Result<Self> {
                 let party_index: usize = party_number.get() as usize;
                 let self_index = party_index - 1;
@@ -46,6 +98,13 @@ macro_rules! frost_sign_impl {
                     .get(self_index)
                     .ok_or(Error::IndexIdentifier(party_index))?;
 
+                if let SignatureMode::Coordinator(coordinator) = &mode
+                {
+                    if !identifiers.contains(coordinator) {
+                        return Err(Error::SignCoordinator);
+                    }
+                }
+
                 Ok(Self {
                     party_number,
                     identifiers,
@@ -54,12 +113,352 @@ macro_rules! frost_sign_impl {
                     round_number: ROUND_1,
                     key_share,
                     message,
+                    mode,
                     nonces: None,
                     commitments: BTreeMap::new(),
                     signing_package: None,
                     signature_shares: BTreeMap::new(),
+                    signature: None,
                 })
             }
+
+            /// Create a driver using a nonce from pre-processing.
+            ///
+            /// Commitments were exchanged during pre-processing so
+            /// signers only need to exchange signature shares; every
+            /// identifier is a signer.
+            pub fn new_preprocessed(
+                party_number: NonZeroU16,
+                identifiers: Vec<Identifier>,
+                key_share: KeyShare,
+                message: Vec<u8>,
+                nonce: PreprocessedNonce,
+            ) -> Result<Self> {
+                let PreprocessedNonce {
+                    nonces,
+                    commitments,
+                } = nonce;
+                if commitments.len() != identifiers.len()
+                    || identifiers
+                        .iter()
+                        .any(|id| !commitments.contains_key(id))
+                {
+                    return Err(Error::NonceCommitments);
+                }
+
+                let min_signers = identifiers.len() as u16;
+                let mut driver = Self::new(
+                    party_number,
+                    identifiers,
+                    min_signers,
+                    key_share,
+                    message,
+                )?;
+                driver.nonces = Some(nonces);
+                driver.commitments = commitments;
+                driver.round_number = ROUND_2;
+                Ok(driver)
+            }
+
+            fn coordinator(&self) -> Option<&$id> {
+                match &self.mode {
+                    SignatureMode::Broadcast => None,
+                    SignatureMode::Coordinator(id) => Some(id),
+                }
+            }
+
+            fn is_coordinator(&self) -> bool {
+                self.coordinator() == Some(&self.id)
+            }
+
+            fn round_message(
+                &self,
+                receiver: &$id,
+                body: SignPackage,
+            ) -> RoundMessage<SignPackage, $id> {
+                let index = self
+                    .identifiers
+                    .iter()
+                    .position(|i| i == receiver)
+                    .unwrap();
+                RoundMessage {
+                    round: NonZeroU16::new(self.round_number.into())
+                        .unwrap(),
+                    sender: self.id.clone(),
+                    receiver: NonZeroU16::new((index + 1) as u16)
+                        .unwrap(),
+                    body,
+                }
+            }
+
+            fn sender_id(
+                &self,
+                round_number: u8,
+                sender: &$id,
+            ) -> Result<$id> {
+                let party_index = self
+                    .identifiers
+                    .iter()
+                    .position(|v| v == sender)
+                    .ok_or(Error::SenderVerifier)?;
+                self.identifiers.get(party_index).cloned().ok_or(
+                    Error::SenderIdentifier(
+                        round_number,
+                        party_index,
+                    ),
+                )
+            }
+
+            /// Ensure a signing package from the coordinator
+            /// signs our message and includes our commitments.
+            fn verify_signing_package(
+                &self,
+                signing_package: &$r2priv,
+            ) -> Result<()> {
+                if signing_package.message() != &self.message
+                    || signing_package.signing_commitments().len()
+                        != self.min_signers as usize
+                    || signing_package
+                        .signing_commitment(&self.id)
+                        .as_ref()
+                        != self.commitments.get(&self.id)
+                {
+                    return Err(Error::SigningPackage);
+                }
+                Ok(())
+            }
+
+            /// Verify a signature share against the signing package.
+            ///
+            /// Shares received before the signing package is known
+            /// are verified once the signing package is created.
+            fn verify_share(
+                &self,
+                id: &$id,
+                signature_share: &$r2pub,
+            ) -> Result<()> {
+                let signing_package = match &self.signing_package {
+                    Some(signing_package) => signing_package,
+                    None => return Ok(()),
+                };
+                let verifying_share = self
+                    .key_share
+                    .1
+                    .verifying_shares()
+                    .get(id)
+                    .ok_or(Error::SenderVerifier)?;
+                verify_signature_share(
+                    *id,
+                    verifying_share,
+                    signature_share,
+                    signing_package,
+                    self.key_share.1.verifying_key(),
+                )
+                .map_err(|_| {
+                    Error::InvalidSignatureShare(id.serialize())
+                })
+            }
+
+            /// Aggregate the signature shares attributing any
+            /// invalid share to the signer that sent it.
+            fn aggregate_signature(
+                &self,
+                signing_package: &$r2priv,
+            ) -> Result<$out> {
+                aggregate(
+                    signing_package,
+                    &self.signature_shares,
+                    &self.key_share.1,
+                )
+                .map_err(|e| match e {
+                    FrostError::InvalidSignatureShare { culprit } => {
+                        Error::InvalidSignatureShare(
+                            culprit.serialize(),
+                        )
+                    }
+                    e => e.into(),
+                })
+            }
+
+            fn proceed_coordinated(
+                &mut self,
+            ) -> Result<Vec<RoundMessage<SignPackage, $id>>> {
+                let coordinator = *self.coordinator().unwrap();
+                let messages = match self.round_number {
+                    ROUND_1 => {
+                        let (nonces, commitments) = round1::commit(
+                            self.key_share.0.signing_share(),
+                            &mut OsRng,
+                        );
+
+                        let messages = if self.is_coordinator() {
+                            Vec::new()
+                        } else {
+                            vec![self.round_message(
+                                &coordinator,
+                                SignPackage::Round1(
+                                    commitments.clone(),
+                                ),
+                            )]
+                        };
+
+                        self.nonces = Some(nonces);
+                        self.commitments
+                            .insert(self.id.clone(), commitments);
+                        messages
+                    }
+                    ROUND_2 => {
+                        let nonces = self
+                            .nonces
+                            .take()
+                            .ok_or(Error::Round3TooEarly)?;
+
+                        if self.is_coordinator() {
+                            let signing_package = SigningPackage::new(
+                                self.commitments.clone(),
+                                &self.message,
+                            );
+                            let signature_share = round2::sign(
+                                &signing_package,
+                                &nonces,
+                                &self.key_share.0,
+                            )?;
+
+                            let messages = self
+                                .commitments
+                                .keys()
+                                .filter(|id| *id != &self.id)
+                                .map(|id| {
+                                    self.round_message(
+                                        id,
+                                        SignPackage::SigningPackage(
+                                            signing_package.clone(),
+                                        ),
+                                    )
+                                })
+                                .collect();
+
+                            self.signing_package =
+                                Some(signing_package);
+                            self.signature_shares.insert(
+                                self.id.clone(),
+                                signature_share,
+                            );
+                            messages
+                        } else {
+                            let signing_package = self
+                                .signing_package
+                                .as_ref()
+                                .ok_or(Error::Round3TooEarly)?;
+                            let signature_share = round2::sign(
+                                signing_package,
+                                &nonces,
+                                &self.key_share.0,
+                            )?;
+                            vec![self.round_message(
+                                &coordinator,
+                                SignPackage::Round2(signature_share),
+                            )]
+                        }
+                    }
+                    ROUND_3 if self.is_coordinator() => {
+                        let signing_package = self
+                            .signing_package
+                            .take()
+                            .ok_or(Error::Round3TooEarly)?;
+
+                        let group_signature = self
+                            .aggregate_signature(&signing_package)?;
+
+                        let messages = self
+                            .signature_shares
+                            .keys()
+                            .filter(|id| *id != &self.id)
+                            .map(|id| {
+                                self.round_message(
+                                    id,
+                                    SignPackage::Signature(
+                                        group_signature.clone(),
+                                    ),
+                                )
+                            })
+                            .collect();
+
+                        self.signature = Some(group_signature);
+                        messages
+                    }
+                    _ => {
+                        return Err(Error::InvalidRound(
+                            self.round_number,
+                        ))
+                    }
+                };
+
+                self.round_number =
+                    self.round_number.checked_add(1).unwrap();
+
+                Ok(messages)
+            }
+
+            fn handle_coordinated(
+                &mut self,
+                message: RoundMessage<SignPackage, $id>,
+            ) -> Result<()> {
+                let round_number = message.round.get() as u8;
+                let from_coordinator =
+                    self.coordinator() == Some(&message.sender);
+                match (round_number, message.body) {
+                    (ROUND_1, SignPackage::Round1(commitments))
+                        if self.is_coordinator() =>
+                    {
+                        let id = self.sender_id(
+                            round_number,
+                            &message.sender,
+                        )?;
+                        self.commitments.insert(id, commitments);
+                        Ok(())
+                    }
+                    (
+                        ROUND_2,
+                        SignPackage::SigningPackage(signing_package),
+                    ) if !self.is_coordinator() => {
+                        if !from_coordinator {
+                            return Err(Error::SenderVerifier);
+                        }
+                        self.verify_signing_package(
+                            &signing_package,
+                        )?;
+                        self.signing_package = Some(signing_package);
+                        Ok(())
+                    }
+                    (
+                        ROUND_2,
+                        SignPackage::Round2(signature_share),
+                    ) if self.is_coordinator() => {
+                        let id = self.sender_id(
+                            round_number,
+                            &message.sender,
+                        )?;
+                        self.verify_share(&id, &signature_share)?;
+                        self.signature_shares
+                            .insert(id, signature_share);
+                        Ok(())
+                    }
+                    (ROUND_3, SignPackage::Signature(signature))
+                        if !self.is_coordinator() =>
+                    {
+                        if !from_coordinator {
+                            return Err(Error::SenderVerifier);
+                        }
+                        self.signature = Some(signature);
+                        Ok(())
+                    }
+                    (ROUND_1, _) | (ROUND_2, _) | (ROUND_3, _) => {
+                        Err(Error::RoundPayload(round_number))
+                    }
+                    _ => Err(Error::InvalidRound(round_number)),
+                }
+            }
         }
 
         impl ProtocolDriver for SignatureDriver {
@@ -67,20 +466,59 @@ macro_rules! frost_sign_impl {
             type Message = RoundMessage<SignPackage, Identifier>;
             type Output = Signature;
 
+            fn culprit(&self, error: &Error) -> Option<NonZeroU16> {
+                match error {
+                    Error::InvalidSignatureShare(culprit) => {
+                        let index =
+                            self.identifiers.iter().position(
+                                |id| &id.serialize() == culprit,
+                            )?;
+                        NonZeroU16::new((index + 1) as u16)
+                    }
+                    _ => None,
+                }
+            }
+
             fn round_info(&self) -> Result<RoundInfo> {
                 let round_number = self.round_number;
                 let is_echo = false;
-                let can_finalize = match self.round_number {
-                    ROUND_2 => {
-                        self.commitments.len()
-                            == self.min_signers as usize
+                let min_signers = self.min_signers as usize;
+                let can_finalize = match self.mode {
+                    SignatureMode::Broadcast => match self
+                        .round_number
+                    {
+                        ROUND_2 => {
+                            self.commitments.len() == min_signers
+                        }
+                        // ROUND_3 => self.signing_package.is_some(),
+                        ROUND_3 => {
+                            self.signature_shares.len() == min_signers
+                        }
+                        _ => false,
+                    },
+                    SignatureMode::Coordinator(_)
+                        if self.is_coordinator() =>
+                    {
+                        match self.round_number {
+                            ROUND_2 => {
+                                self.commitments.len() == min_signers
+                            }
+                            ROUND_3 => {
+                                self.signature_shares.len()
+                                    == min_signers
+                            }
+                            // Done once the signature is sent
+                            ROUND_4 => self.signature.is_some(),
+                            _ => false,
+                        }
                     }
-                    // ROUND_3 => self.signing_package.is_some(),
-                    ROUND_3 => {
-                        self.signature_shares.len()
-                            == self.min_signers as usize
+                    SignatureMode::Coordinator(_) => {
+                        match self.round_number {
+                            ROUND_2 => self.signing_package.is_some(),
+                            ROUND_3 => self.signature.is_some(),
+                            _ => false,
+                        }
                     }
-                    _ => false,
                 };
                 Ok(RoundInfo {
                     round_number,
@@ -90,6 +528,10 @@ macro_rules! frost_sign_impl {
             }
 
             fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+                if self.coordinator().is_some() {
+                    return self.proceed_coordinated();
+                }
+
                 match self.round_number {
                     ROUND_1 => {
                         let mut messages = Vec::with_capacity(
@@ -182,6 +624,13 @@ macro_rules! frost_sign_impl {
                         }
 
                         self.signing_package = Some(signing_package);
+
+                        // Verify shares received before the
+                        // signing package was created
+                        for (id, share) in &self.signature_shares {
+                            self.verify_share(id, share)?;
+                        }
+
                         self.signature_shares
                             .insert(self.id.clone(), signature_share);
 
@@ -198,6 +647,10 @@ macro_rules! frost_sign_impl {
                 &mut self,
                 message: Self::Message,
             ) -> Result<()> {
+                if self.coordinator().is_some() {
+                    return self.handle_coordinated(message);
+                }
+
                 let round_number = message.round.get() as u8;
                 match round_number {
                     ROUND_1 => match message.body {
@@ -232,6 +685,10 @@ macro_rules! frost_sign_impl {
                             if let Some(id) =
                                 self.identifiers.get(party_index)
                             {
+                                self.verify_share(
+                                    id,
+                                    &signature_share,
+                                )?;
                                 self.signature_shares.insert(
                                     id.clone(),
                                     signature_share,
@@ -253,6 +710,35 @@ macro_rules! frost_sign_impl {
             fn try_finalize_round(
                 &mut self,
             ) -> Result<Option<Self::Output>> {
+                match self.mode {
+                    SignatureMode::Broadcast => {}
+                    SignatureMode::Coordinator(_)
+                        if self.is_coordinator() =>
+                    {
+                        if self.round_number == ROUND_4 {
+                            return Ok(self.signature.take());
+                        }
+                        return Ok(None);
+                    }
+                    SignatureMode::Coordinator(_) => {
+                        if self.round_number == ROUND_3 {
+                            if let Some(signature) =
+                                self.signature.take()
+                            {
+                                self.key_share
+                                    .1
+                                    .verifying_key()
+                                    .verify(
+                                        &self.message,
+                                        &signature,
+                                    )?;
+                                return Ok(Some(signature));
+                            }
+                        }
+                        return Ok(None);
+                    }
+                }
+
                 if self.round_number == ROUND_3
                     && self.signature_shares.len()
                         == self.min_signers as usize
@@ -262,11 +748,8 @@ macro_rules! frost_sign_impl {
                         .take()
                         .ok_or(Error::Round3TooEarly)?;
 
-                    let group_signature = aggregate(
-                        &signing_package,
-                        &self.signature_shares,
-                        &self.key_share.1,
-                    )?;
+                    let group_signature =
+                        self.aggregate_signature(&signing_package)?;
 
                     Ok(Some(group_signature))
                 } else {
@@ -0,0 +1,24 @@
+//! Key export for FROST Ed25519.
+use ed25519_dalek::VerifyingKey;
+use frost_ed25519::keys::{KeyPackage, PublicKeyPackage};
+
+use crate::frost::{core::export::frost_export_impl, Error, Result};
+
+frost_export_impl!();
+
+/// Group verifying key as a 32-byte Ed25519 public key.
+pub fn ed25519_public_key(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 32]> {
+    verifying_key_bytes(public_key_package)?
+        .try_into()
+        .map_err(|_| Error::ExportPublicKey)
+}
+
+/// Group verifying key as an Ed25519 verifying key.
+pub fn verifying_key(
+    public_key_package: &PublicKeyPackage,
+) -> Result<VerifyingKey> {
+    VerifyingKey::from_bytes(&ed25519_public_key(public_key_package)?)
+        .map_err(|_| Error::ExportPublicKey)
+}
@@ -3,11 +3,23 @@ pub use ed25519_dalek::{SigningKey, VerifyingKey};
 use frost_ed25519::keys::{KeyPackage, PublicKeyPackage};
 use polysig_protocol::pem;
 
+mod dealer;
 mod dkg;
+pub mod export;
+mod preprocess;
+mod refresh;
+mod repair;
 mod sign;
+pub mod solana;
 
+pub use dealer::DealerDriver;
 pub use dkg::DkgDriver;
-pub use sign::SignatureDriver;
+pub use preprocess::{
+    NonceStore, PreprocessDriver, PreprocessedNonce,
+};
+pub use refresh::RefreshDriver;
+pub use repair::RepairDriver;
+pub use sign::{SignatureDriver, SignatureMode};
 
 /// Participant in the protocol.
 pub type Participant = crate::Participant<SigningKey, VerifyingKey>;
@@ -0,0 +1,28 @@
+//! Share repair for FROST Ed25519.
+use frost_ed25519::{
+    keys::{
+        repairable::{
+            repair_share_step_1, repair_share_step_2,
+            repair_share_step_3,
+        },
+        KeyPackage, PublicKeyPackage, SecretShare,
+        VerifiableSecretSharingCommitment, VerifyingShare,
+    },
+    Ed25519ScalarField, Field, Identifier,
+};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, num::NonZeroU16};
+
+use crate::{
+    frost::{Error, Result},
+    ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::KeyShare;
+
+use crate::frost::{
+    core::repair::frost_repair_impl, ROUND_1, ROUND_2, ROUND_3,
+};
+
+frost_repair_impl!(Ed25519ScalarField, Identifier);
@@ -0,0 +1,155 @@
+//! Solana helpers for FROST Ed25519.
+//!
+//! Solana accounts are Ed25519 public keys so the group
+//! verifying key is the account public key and signatures
+//! produced by the signing protocol can be used directly
+//! as transaction signatures.
+use ed25519_dalek::{Signature as DalekSignature, VerifyingKey};
+use frost_ed25519::keys::PublicKeyPackage;
+
+use crate::frost::{Error, Result};
+
+use super::Signature;
+
+/// Account of the system program.
+const SYSTEM_PROGRAM: [u8; 32] = [0; 32];
+
+/// System program instruction that transfers lamports.
+const TRANSFER_INSTRUCTION: u32 = 2;
+
+/// Prefix bit for versioned transaction messages.
+const VERSION_PREFIX: u8 = 0x80;
+
+/// Lamport transfer by the system program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    /// Account that funds the transfer.
+    pub from: [u8; 32],
+    /// Recipient account.
+    pub to: [u8; 32],
+    /// Amount in lamports.
+    pub lamports: u64,
+}
+
+/// Solana public key for the group verifying key.
+pub fn pubkey(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 32]> {
+    let bytes = public_key_package.verifying_key().serialize()?;
+    bytes.try_into().map_err(|_| Error::SolanaPublicKey)
+}
+
+/// Base58 encoded Solana address for the group verifying key.
+pub fn address(
+    public_key_package: &PublicKeyPackage,
+) -> Result<String> {
+    Ok(bs58::encode(pubkey(public_key_package)?).into_string())
+}
+
+/// Encode a signature as a 64-byte Solana signature.
+pub fn signature_bytes(signature: &Signature) -> Result<[u8; 64]> {
+    let bytes = signature.serialize()?;
+    bytes.try_into().map_err(|_| Error::SolanaSignature)
+}
+
+/// Verify a signature for a serialized transaction message.
+///
+/// Uses strict verification so a signature accepted here
+/// is also accepted by the Solana runtime.
+pub fn verify(
+    public_key_package: &PublicKeyPackage,
+    message: &[u8],
+    signature: &Signature,
+) -> Result<()> {
+    let verifying_key =
+        VerifyingKey::from_bytes(&pubkey(public_key_package)?)
+            .map_err(|_| Error::SolanaPublicKey)?;
+    let signature =
+        DalekSignature::from_bytes(&signature_bytes(signature)?);
+    verifying_key
+        .verify_strict(message, &signature)
+        .map_err(|_| Error::SolanaSignature)
+}
+
+/// Decode the transfers in a serialized legacy transaction
+/// message.
+///
+/// Every instruction must be a system program transfer so
+/// the transfers describe everything the message does once
+/// it is signed; other messages are rejected.
+pub fn transfers(message: &[u8]) -> Result<Vec<Transfer>> {
+    let mut data = message;
+    let header = take(&mut data, 3)?;
+    if header[0] & VERSION_PREFIX != 0 {
+        return Err(Error::SolanaMessage);
+    }
+
+    let mut accounts = Vec::new();
+    for _ in 0..compact_len(&mut data)? {
+        let account: [u8; 32] = take(&mut data, 32)?
+            .try_into()
+            .map_err(|_| Error::SolanaMessage)?;
+        accounts.push(account);
+    }
+    let account = |index: u8| {
+        accounts
+            .get(index as usize)
+            .copied()
+            .ok_or(Error::SolanaMessage)
+    };
+
+    // Recent blockhash
+    take(&mut data, 32)?;
+
+    let mut transfers = Vec::new();
+    for _ in 0..compact_len(&mut data)? {
+        let program = account(take(&mut data, 1)?[0])?;
+        let len = compact_len(&mut data)?;
+        let indices = take(&mut data, len)?;
+        let len = compact_len(&mut data)?;
+        let instruction = take(&mut data, len)?;
+
+        if program != SYSTEM_PROGRAM
+            || indices.len() != 2
+            || instruction.len() != 12
+            || instruction[..4] != TRANSFER_INSTRUCTION.to_le_bytes()
+        {
+            return Err(Error::SolanaInstruction);
+        }
+        let mut lamports = [0u8; 8];
+        lamports.copy_from_slice(&instruction[4..]);
+        transfers.push(Transfer {
+            from: account(indices[0])?,
+            to: account(indices[1])?,
+            lamports: u64::from_le_bytes(lamports),
+        });
+    }
+
+    if !data.is_empty() {
+        return Err(Error::SolanaMessage);
+    }
+    Ok(transfers)
+}
+
+/// Take bytes from the front of a message.
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if data.len() < len {
+        return Err(Error::SolanaMessage);
+    }
+    let (bytes, remainder) = data.split_at(len);
+    *data = remainder;
+    Ok(bytes)
+}
+
+/// Read a compact-u16 length from the front of a message.
+fn compact_len(data: &mut &[u8]) -> Result<usize> {
+    let mut len = 0usize;
+    for index in 0..3 {
+        let byte = take(data, 1)?[0];
+        len |= ((byte & 0x7f) as usize) << (index * 7);
+        if byte & 0x80 == 0 {
+            return Ok(len);
+        }
+    }
+    Err(Error::SolanaMessage)
+}
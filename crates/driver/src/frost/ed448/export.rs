@@ -0,0 +1,6 @@
+//! Key export for FROST Ed448.
+use frost_ed448::keys::{KeyPackage, PublicKeyPackage};
+
+use crate::frost::{core::export::frost_export_impl, Result};
+
+frost_export_impl!();
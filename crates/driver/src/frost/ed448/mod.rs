@@ -0,0 +1,41 @@
+//! Driver for the FROST Ed448 protocol.
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+use frost_ed448::keys::{KeyPackage, PublicKeyPackage};
+use polysig_protocol::pem;
+
+mod dealer;
+mod dkg;
+pub mod export;
+mod preprocess;
+mod refresh;
+mod repair;
+mod sign;
+
+pub use dealer::DealerDriver;
+pub use dkg::DkgDriver;
+pub use preprocess::{
+    NonceStore, PreprocessDriver, PreprocessedNonce,
+};
+pub use refresh::RefreshDriver;
+pub use repair::RepairDriver;
+pub use sign::{SignatureDriver, SignatureMode};
+
+/// Participant in the protocol.
+pub type Participant = crate::Participant<SigningKey, VerifyingKey>;
+
+/// Options for each party.
+pub type PartyOptions = crate::PartyOptions<VerifyingKey>;
+
+/// Key share for this protocol.
+pub type KeyShare = (KeyPackage, PublicKeyPackage);
+
+/// Signature for this protocol.
+pub type Signature = frost_ed448::Signature;
+
+/// Identifier for this protocol.
+pub type Identifier = frost_ed448::Identifier;
+
+const TAG: &str = "FROST ED448 KEY SHARE";
+const PEM_VERSION: u16 = 1;
+
+super::core::key_share_pem!();
@@ -0,0 +1,37 @@
+//! Share refresh for FROST Ed448.
+use frost_ed448::{
+    keys::{
+        dkg,
+        refresh::{
+            refresh_dkg_part2, refresh_dkg_part_1, refresh_dkg_shares,
+        },
+    },
+    Identifier,
+};
+use polysig_protocol::Parameters;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, num::NonZeroU16};
+
+use crate::{
+    frost::{Error, Result},
+    ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::KeyShare;
+
+use crate::frost::{
+    core::{dkg::frost_dkg_impl, refresh::frost_refresh_impl},
+    ROUND_1, ROUND_2, ROUND_3,
+};
+
+frost_refresh_impl!(
+    dkg::round1::Package,
+    dkg::round1::SecretPackage,
+    dkg::round2::Package,
+    dkg::round2::SecretPackage,
+    Identifier,
+    refresh_dkg_part_1,
+    refresh_dkg_part2,
+    refresh_dkg_shares
+);
@@ -0,0 +1,34 @@
+//! Signature generation for FROST.
+use frost_ed448::{
+    aggregate,
+    round1::{self, SigningCommitments, SigningNonces},
+    round2::{self, SignatureShare},
+    verify_signature_share, Error as FrostError, Identifier,
+    Signature, SigningPackage,
+};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::num::NonZeroU16;
+
+use crate::{
+    frost::{Error, Result},
+    ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::{preprocess::PreprocessedNonce, KeyShare};
+use crate::frost::{
+    core::sign::frost_sign_impl, ROUND_1, ROUND_2, ROUND_3, ROUND_4,
+};
+
+frost_sign_impl!(
+    SigningCommitments,
+    SigningNonces,
+    SignatureShare,
+    SigningPackage,
+    Identifier,
+    Signature,
+    round1,
+    round2,
+    aggregate
+);
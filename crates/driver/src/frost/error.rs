@@ -1,3 +1,4 @@
+use polysig_protocol::hex;
 use thiserror::Error;
 
 /// Errors generated by the protocol.
@@ -31,15 +32,150 @@ pub enum Error {
     #[error("attempt to proceed to round 3 without round 2 data")]
     Round3TooEarly,
 
+    /// Error generated when the participant being repaired is not
+    /// a session participant or when a key share is given for the
+    /// participant being repaired or omitted for a helper.
+    #[error("invalid participants for share repair")]
+    RepairParticipant,
+
+    /// Error generated when a repair scalar could not be decoded.
+    #[error("invalid scalar for share repair")]
+    RepairScalar,
+
+    /// Error generated when helpers disagree on the public key package.
+    #[error(
+        "share repair helpers sent different public key packages"
+    )]
+    RepairPublicKeyPackage,
+
+    /// Error generated when a repaired share does not match the
+    /// verifying share in the public key package.
+    #[error("repaired share does not match the verifying share")]
+    RepairVerifyingShare,
+
+    /// Error generated when the signing coordinator is not
+    /// one of the signers.
+    #[error("signing coordinator must be one of the signers")]
+    SignCoordinator,
+
+    /// Error generated when a signing package from the
+    /// coordinator does not match the message or commitments.
+    #[error("invalid signing package from the coordinator")]
+    SigningPackage,
+
+    /// Error generated when a signature share does not verify
+    /// against the signing package; the serialized identifier of
+    /// the signer that sent the share is included.
+    #[error(
+        "invalid signature share from participant {}",
+        hex::encode(.0)
+    )]
+    InvalidSignatureShare(Vec<u8>),
+
+    /// Error generated when a pre-processed nonce does not exist
+    /// or has already been used.
+    #[error("no unused pre-processed nonce for index {0}")]
+    NonceIndex(u32),
+
+    /// Error generated when the pre-processed commitments do not
+    /// match the signers.
+    #[error("pre-processed commitments do not match the signers")]
+    NonceCommitments,
+
+    /// Error generated when a participant sends the wrong number
+    /// of pre-processed commitments.
+    #[error("unexpected number of pre-processed commitments {0}")]
+    NonceCount(usize),
+
+    /// Error generated when the dealer is not a session participant
+    /// or when a signing key is given by a party that is not the
+    /// dealer.
+    #[error("invalid participants for trusted dealer")]
+    DealerParticipant,
+
+    /// Error generated when a secret share from the dealer does
+    /// not match the public key package.
+    #[error("dealer share does not match the public key package")]
+    DealerVerifyingShare,
+
+    /// Error generated when a signature is not a valid
+    /// BIP-340 signature.
+    #[error("taproot signature must be 64 bytes, got {0}")]
+    TaprootSignatureLength(usize),
+
+    /// Error generated when a public key could not be converted
+    /// to an x-only public key.
+    #[error("invalid public key for taproot")]
+    TaprootPublicKey,
+
+    /// Error generated when the input index for a taproot
+    /// signature hash is out of bounds.
+    #[error("taproot input index {0} is out of bounds")]
+    TaprootInputIndex(u32),
+
+    /// Error generated computing a taproot signature hash.
+    #[cfg(feature = "frost-secp256k1-tr")]
+    #[error(transparent)]
+    TaprootSighash(#[from] bitcoin::sighash::TaprootError),
+
+    /// Error generated when a group verifying key is not a
+    /// valid Solana public key.
+    #[error("invalid public key for solana")]
+    SolanaPublicKey,
+
+    /// Error generated when a signature is not a valid
+    /// Solana signature.
+    #[error("invalid signature for solana")]
+    SolanaSignature,
+
+    /// Error generated when a serialized transaction message
+    /// is not a valid legacy Solana message.
+    #[error("invalid solana transaction message")]
+    SolanaMessage,
+
+    /// Error generated when an instruction in a Solana
+    /// message is not a system program transfer.
+    #[error("solana instruction is not a system transfer")]
+    SolanaInstruction,
+
+    /// Error generated when a group public key could not be
+    /// converted for export.
+    #[error("invalid group public key for export")]
+    ExportPublicKey,
+
     /// Protocol library errors.
     #[error(transparent)]
     Protocol(#[from] polysig_protocol::Error),
 
+    /// JSON library error.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
     /// FROST library error.
     #[cfg(feature = "frost-ed25519")]
     #[error(transparent)]
     FrostEd25519(#[from] frost_ed25519::Error),
 
+    /// FROST library error.
+    #[cfg(feature = "frost-ed448")]
+    #[error(transparent)]
+    FrostEd448(#[from] frost_ed448::Error),
+
+    /// FROST library error.
+    #[cfg(feature = "frost-p256")]
+    #[error(transparent)]
+    FrostP256(#[from] frost_p256::Error),
+
+    /// FROST library error.
+    #[cfg(feature = "frost-ristretto255")]
+    #[error(transparent)]
+    FrostRistretto255(#[from] frost_ristretto255::Error),
+
+    /// FROST library error.
+    #[cfg(feature = "frost-secp256k1")]
+    #[error(transparent)]
+    FrostSecp256k1(#[from] frost_secp256k1::Error),
+
     /// FROST library error.
     #[cfg(feature = "frost-secp256k1-tr")]
     #[error(transparent)]
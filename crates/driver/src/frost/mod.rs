@@ -7,6 +7,18 @@ pub(crate) mod core;
 #[cfg(feature = "frost-ed25519")]
 pub mod ed25519;
 
+#[cfg(feature = "frost-ed448")]
+pub mod ed448;
+
+#[cfg(feature = "frost-p256")]
+pub mod p256;
+
+#[cfg(feature = "frost-ristretto255")]
+pub mod ristretto255;
+
+#[cfg(feature = "frost-secp256k1")]
+pub mod secp256k1;
+
 #[cfg(feature = "frost-secp256k1-tr")]
 pub mod secp256k1_tr;
 
@@ -18,3 +30,4 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub(crate) const ROUND_1: u8 = 1;
 pub(crate) const ROUND_2: u8 = 2;
 pub(crate) const ROUND_3: u8 = 3;
+pub(crate) const ROUND_4: u8 = 4;
@@ -0,0 +1,25 @@
+//! Trusted dealer key generation for FROST P-256.
+use frost_p256::{
+    keys::{
+        generate_with_dealer, split, IdentifierList, KeyPackage,
+        PublicKeyPackage, SecretShare,
+    },
+    Identifier, SigningKey,
+};
+use polysig_protocol::Parameters;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU16;
+
+use crate::{
+    frost::{Error, Result},
+    ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::KeyShare;
+
+use crate::frost::{
+    core::dealer::frost_dealer_impl, ROUND_1, ROUND_2,
+};
+
+frost_dealer_impl!(SigningKey, Identifier);
@@ -0,0 +1,15 @@
+//! Key export for FROST P-256.
+use frost_p256::keys::{KeyPackage, PublicKeyPackage};
+
+use crate::frost::{core::export::frost_export_impl, Error, Result};
+
+frost_export_impl!();
+
+/// Group verifying key as a compressed SEC1 point.
+pub fn sec1_compressed(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 33]> {
+    verifying_key_bytes(public_key_package)?
+        .try_into()
+        .map_err(|_| Error::ExportPublicKey)
+}
@@ -0,0 +1,6 @@
+//! Key export for FROST Ristretto255.
+use frost_ristretto255::keys::{KeyPackage, PublicKeyPackage};
+
+use crate::frost::{core::export::frost_export_impl, Result};
+
+frost_export_impl!();
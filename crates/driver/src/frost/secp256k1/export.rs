@@ -0,0 +1,35 @@
+//! Key export for FROST Secp256k1.
+use frost_secp256k1::keys::{KeyPackage, PublicKeyPackage};
+use k256::{elliptic_curve::sec1::ToEncodedPoint, PublicKey};
+
+use crate::frost::{core::export::frost_export_impl, Error, Result};
+
+frost_export_impl!();
+
+/// Group verifying key as a compressed SEC1 point.
+pub fn sec1_compressed(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 33]> {
+    verifying_key_bytes(public_key_package)?
+        .try_into()
+        .map_err(|_| Error::ExportPublicKey)
+}
+
+/// Group verifying key as an uncompressed SEC1 point.
+pub fn sec1_uncompressed(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 65]> {
+    public_key(public_key_package)?
+        .to_encoded_point(false)
+        .as_bytes()
+        .try_into()
+        .map_err(|_| Error::ExportPublicKey)
+}
+
+/// Group verifying key as a secp256k1 public key.
+pub fn public_key(
+    public_key_package: &PublicKeyPackage,
+) -> Result<PublicKey> {
+    PublicKey::from_sec1_bytes(&sec1_compressed(public_key_package)?)
+        .map_err(|_| Error::ExportPublicKey)
+}
@@ -0,0 +1,39 @@
+//! Driver for the FROST Secp256k1 protocol.
+use frost_secp256k1::keys::{KeyPackage, PublicKeyPackage};
+pub use k256::schnorr::{SigningKey, VerifyingKey};
+use polysig_protocol::pem;
+
+mod dealer;
+mod dkg;
+pub mod export;
+mod preprocess;
+mod refresh;
+mod repair;
+mod sign;
+
+pub use dealer::DealerDriver;
+pub use dkg::DkgDriver;
+pub use preprocess::{
+    NonceStore, PreprocessDriver, PreprocessedNonce,
+};
+pub use refresh::RefreshDriver;
+pub use repair::RepairDriver;
+pub use sign::{SignatureDriver, SignatureMode};
+
+/// Participant in the protocol.
+pub type Participant = crate::Participant<SigningKey, VerifyingKey>;
+
+/// Options for each party.
+pub type PartyOptions = crate::PartyOptions<VerifyingKey>;
+
+/// Key share for this protocol.
+pub type KeyShare = (KeyPackage, PublicKeyPackage);
+/// Signature for this protocol.
+pub type Signature = frost_secp256k1::Signature;
+/// Identifier for this protocol.
+pub type Identifier = frost_secp256k1::Identifier;
+
+const TAG: &str = "FROST SECP256K1 KEY SHARE";
+const PEM_VERSION: u16 = 1;
+
+super::core::key_share_pem!();
@@ -0,0 +1,21 @@
+//! Nonce pre-processing for FROST Secp256k1.
+use frost_secp256k1::{
+    round1::{self, SigningCommitments, SigningNonces},
+    Identifier,
+};
+use rand::rngs::OsRng;
+use std::collections::BTreeMap;
+use std::num::NonZeroU16;
+
+use crate::{
+    frost::{Error, Result},
+    ProtocolDriver, RoundInfo, RoundMessage,
+};
+
+use super::KeyShare;
+
+use crate::frost::{
+    core::preprocess::frost_preprocess_impl, ROUND_1, ROUND_2,
+};
+
+frost_preprocess_impl!(SigningCommitments, SigningNonces, Identifier);
@@ -0,0 +1,20 @@
+//! Key export for FROST Secp256k1 Taproot.
+//!
+//! The taproot output key for the group is exported by
+//! [output_key].
+use frost_secp256k1_tr::keys::{KeyPackage, PublicKeyPackage};
+
+use crate::frost::{core::export::frost_export_impl, Error, Result};
+
+frost_export_impl!();
+
+pub use super::taproot::{internal_key, output_key};
+
+/// Group verifying key as a compressed SEC1 point.
+pub fn sec1_compressed(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 33]> {
+    verifying_key_bytes(public_key_package)?
+        .try_into()
+        .map_err(|_| Error::ExportPublicKey)
+}
@@ -3,11 +3,23 @@ use frost_secp256k1_tr::keys::{KeyPackage, PublicKeyPackage};
 pub use k256::schnorr::{SigningKey, VerifyingKey};
 use polysig_protocol::pem;
 
+mod dealer;
 mod dkg;
+pub mod export;
+mod preprocess;
+mod refresh;
+mod repair;
 mod sign;
+pub mod taproot;
 
+pub use dealer::DealerDriver;
 pub use dkg::DkgDriver;
-pub use sign::SignatureDriver;
+pub use preprocess::{
+    NonceStore, PreprocessDriver, PreprocessedNonce,
+};
+pub use refresh::RefreshDriver;
+pub use repair::RepairDriver;
+pub use sign::{SignatureDriver, SignatureMode};
 
 /// Participant in the protocol.
 pub type Participant = crate::Participant<SigningKey, VerifyingKey>;
@@ -3,7 +3,8 @@ use frost_secp256k1_tr::{
     aggregate,
     round1::{self, SigningCommitments, SigningNonces},
     round2::{self, SignatureShare},
-    Identifier, Signature, SigningPackage,
+    verify_signature_share, Error as FrostError, Identifier,
+    Signature, SigningPackage,
 };
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
@@ -15,9 +16,9 @@ use crate::{
     ProtocolDriver, RoundInfo, RoundMessage,
 };
 
-use super::KeyShare;
+use super::{preprocess::PreprocessedNonce, KeyShare};
 use crate::frost::{
-    core::sign::frost_sign_impl, ROUND_1, ROUND_2, ROUND_3,
+    core::sign::frost_sign_impl, ROUND_1, ROUND_2, ROUND_3, ROUND_4,
 };
 
 frost_sign_impl!(
@@ -0,0 +1,153 @@
+//! BIP-340 and BIP-341 helpers for FROST Secp256k1 Taproot.
+//!
+//! Signatures produced by the signing protocol are valid for
+//! a taproot key path spend without a script tree so the
+//! outputs can be used directly in a taproot witness.
+use bitcoin::{
+    absolute::LockTime,
+    hashes::Hash,
+    sighash::{Prevouts, SighashCache},
+    transaction::Version,
+    Amount, OutPoint, ScriptBuf, Sequence, TapSighashType,
+    Transaction, TxIn, Txid, Witness,
+};
+use frost_secp256k1_tr::keys::{PublicKeyPackage, Tweak};
+
+use crate::frost::{Error, Result};
+
+use super::Signature;
+
+/// Encode a signature as a 64-byte BIP-340 signature.
+pub fn signature_bytes(signature: &Signature) -> Result<[u8; 64]> {
+    let bytes = signature.serialize()?;
+    let length = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| Error::TaprootSignatureLength(length))
+}
+
+/// Compute the 32-byte x-only group public key.
+///
+/// This is the internal key before the taproot tweak is applied.
+pub fn internal_key(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 32]> {
+    x_only(&public_key_package.verifying_key().serialize()?)
+}
+
+/// Compute the 32-byte x-only taproot output key.
+///
+/// The output key commits to the group public key without a
+/// script tree (BIP-86) and is the key that signatures verify
+/// against; use it to build the `OP_1 <key>` script pubkey.
+pub fn output_key(
+    public_key_package: &PublicKeyPackage,
+) -> Result<[u8; 32]> {
+    let tweaked = public_key_package.clone().tweak(None::<&[u8]>);
+    x_only(&tweaked.verifying_key().serialize()?)
+}
+
+fn x_only(compressed: &[u8]) -> Result<[u8; 32]> {
+    compressed
+        .get(1..33)
+        .and_then(|x| x.try_into().ok())
+        .ok_or(Error::TaprootPublicKey)
+}
+
+/// Output of a transaction.
+#[derive(Debug, Clone)]
+pub struct TxOut {
+    /// Amount in satoshis.
+    pub value: u64,
+    /// Script pubkey.
+    pub script_pubkey: Vec<u8>,
+}
+
+/// Previous output spent by a transaction input.
+#[derive(Debug, Clone)]
+pub struct Prevout {
+    /// Transaction identifier in internal byte order.
+    pub txid: [u8; 32],
+    /// Output index.
+    pub vout: u32,
+    /// Sequence number of the spending input.
+    pub sequence: u32,
+    /// Output being spent.
+    pub output: TxOut,
+}
+
+/// Transaction data for a taproot key path spend.
+///
+/// Only `SIGHASH_DEFAULT` without an annex is supported.
+#[derive(Debug, Clone)]
+pub struct KeySpend {
+    /// Transaction version.
+    pub version: i32,
+    /// Transaction lock time.
+    pub lock_time: u32,
+    /// Outputs spent by every transaction input.
+    pub prevouts: Vec<Prevout>,
+    /// Transaction outputs.
+    pub outputs: Vec<TxOut>,
+    /// Index of the input being signed.
+    pub input_index: u32,
+}
+
+impl KeySpend {
+    /// Compute the BIP-341 signature hash for the input.
+    ///
+    /// The returned hash is the message to sign with the
+    /// signing protocol.
+    pub fn sighash(&self) -> Result<[u8; 32]> {
+        if self.input_index as usize >= self.prevouts.len() {
+            return Err(Error::TaprootInputIndex(self.input_index));
+        }
+
+        let transaction = Transaction {
+            version: Version(self.version),
+            lock_time: LockTime::from_consensus(self.lock_time),
+            input: self
+                .prevouts
+                .iter()
+                .map(|prevout| TxIn {
+                    previous_output: OutPoint {
+                        txid: Txid::from_byte_array(prevout.txid),
+                        vout: prevout.vout,
+                    },
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence(prevout.sequence),
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: self
+                .outputs
+                .iter()
+                .map(TxOut::to_bitcoin)
+                .collect(),
+        };
+        let spent = self
+            .prevouts
+            .iter()
+            .map(|prevout| prevout.output.to_bitcoin())
+            .collect::<Vec<_>>();
+
+        let sighash = SighashCache::new(&transaction)
+            .taproot_key_spend_signature_hash(
+                self.input_index as usize,
+                &Prevouts::All(&spent),
+                TapSighashType::Default,
+            )?;
+        Ok(sighash.to_byte_array())
+    }
+}
+
+impl TxOut {
+    fn to_bitcoin(&self) -> bitcoin::TxOut {
+        bitcoin::TxOut {
+            value: Amount::from_sat(self.value),
+            script_pubkey: ScriptBuf::from_bytes(
+                self.script_pubkey.clone(),
+            ),
+        }
+    }
+}
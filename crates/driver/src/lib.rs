@@ -5,26 +5,136 @@
 #![cfg_attr(all(doc, CHANNEL_NIGHTLY), feature(doc_auto_cfg))]
 pub mod signers;
 
+mod capabilities;
 mod error;
 
+#[cfg(feature = "bitcoin")]
+pub mod bitcoin;
+
+#[cfg(feature = "bls")]
+pub mod bls;
+
+#[cfg(any(feature = "cggmp", feature = "frost"))]
+pub mod certificate;
+
+#[cfg(feature = "digest")]
+pub mod digest;
+
+#[cfg(feature = "elgamal")]
+pub mod elgamal;
+
+#[cfg(all(
+    feature = "encrypt",
+    any(
+        feature = "bls",
+        feature = "cggmp",
+        feature = "frost",
+        feature = "musig2"
+    )
+))]
+pub mod encrypt;
+
 #[cfg(feature = "frost")]
 pub mod frost;
 
+#[cfg(feature = "musig2")]
+pub mod musig2;
+
 #[cfg(any(feature = "ecdsa", feature = "cggmp"))]
 pub mod recoverable_signature;
 
-#[cfg(any(feature = "cggmp", feature = "frost"))]
+#[cfg(feature = "vss")]
+pub mod vss;
+
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+mod batch;
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+mod plugin;
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
 mod protocol;
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+pub mod simulator;
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+mod weighted;
 
-#[cfg(any(feature = "cggmp", feature = "frost"))]
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+pub use batch::{BatchDriver, BatchMessage};
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+pub use plugin::{PluginDriver, PluginMessage, Protocol};
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
 pub use protocol::*;
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+pub use weighted::{WeightedDriver, WeightedMessage, Weights};
 
 #[cfg(feature = "cggmp")]
 pub use synedrion::{self, bip32};
 
+#[cfg(feature = "bls")]
+pub use blstrs;
+
+#[cfg(feature = "vss")]
+pub use {ff, group};
+
 #[cfg(feature = "frost-ed25519")]
 pub use frost_ed25519;
 
+#[cfg(feature = "frost-ed448")]
+pub use frost_ed448;
+
+#[cfg(feature = "frost-p256")]
+pub use frost_p256;
+
+#[cfg(feature = "frost-ristretto255")]
+pub use frost_ristretto255;
+
+#[cfg(feature = "frost-secp256k1")]
+pub use frost_secp256k1;
+
 #[cfg(feature = "frost-secp256k1-tr")]
 pub use frost_secp256k1_tr;
 
@@ -32,14 +142,22 @@ pub use frost_secp256k1_tr;
     feature = "cggmp",
     feature = "ecdsa",
     feature = "schnorr",
+    feature = "frost-p256",
+    feature = "frost-secp256k1",
     feature = "frost-secp256k1-tr"
 ))]
 pub use k256;
 
-#[cfg(any(feature = "eddsa", feature = "frost-ed25519"))]
+#[cfg(any(
+    feature = "eddsa",
+    feature = "frost-ed25519",
+    feature = "frost-ed448",
+    feature = "frost-ristretto255"
+))]
 pub use ed25519_dalek;
 
-pub use error::Error;
+pub use capabilities::{capabilities, Capabilities, CurveBackend};
+pub use error::{classify, Error};
 
 /// Result type for the driver library.
 pub type Result<T> = std::result::Result<T, Error>;
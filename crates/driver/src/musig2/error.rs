@@ -0,0 +1,67 @@
+use thiserror::Error;
+
+/// Errors generated by the protocol.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated an invalid round number is encountered.
+    #[error("round {0} is not supported for this protocol")]
+    InvalidRound(u8),
+
+    /// Error generated an invalid round payload is encountered.
+    #[error("payload for round {0} is not of the correct type")]
+    RoundPayload(u8),
+
+    /// Error generated locating a verifying key for a party number.
+    #[error("party number {0} is not a valid participant")]
+    IndexVerifier(usize),
+
+    /// Error generated when the signing key does not match the
+    /// verifying key for this party.
+    #[error("signing key does not match verifying key")]
+    SigningKey,
+
+    /// Error generated when the verifying keys are not unique.
+    #[error("verifying keys must be unique")]
+    DuplicateVerifier,
+
+    /// Key aggregation error.
+    #[error(transparent)]
+    KeyAgg(#[from] musig2::errors::KeyAggError),
+
+    /// Taproot tweak error.
+    #[error(transparent)]
+    Tweak(#[from] musig2::errors::TweakError),
+
+    /// Signer index error.
+    #[error(transparent)]
+    SignerIndex(#[from] musig2::errors::SignerIndexError),
+
+    /// Error generated by an invalid nonce or partial signature
+    /// from a participant.
+    #[error(transparent)]
+    RoundContribution(#[from] musig2::errors::RoundContributionError),
+
+    /// Error generated finalizing a round.
+    #[error(transparent)]
+    RoundFinalize(#[from] musig2::errors::RoundFinalizeError),
+
+    /// Elliptic curve error.
+    #[error(transparent)]
+    EllipticCurve(#[from] k256::elliptic_curve::Error),
+
+    /// Signature error.
+    #[error(transparent)]
+    Signature(#[from] k256::schnorr::signature::Error),
+
+    /// Protocol library errors.
+    #[error(transparent)]
+    Protocol(#[from] polysig_protocol::Error),
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl From<Error> for wasm_bindgen::JsValue {
+    fn from(value: Error) -> Self {
+        let s = value.to_string();
+        wasm_bindgen::JsValue::from_str(&s)
+    }
+}
@@ -0,0 +1,84 @@
+//! MuSig2 multi-signatures for secp256k1 (BIP-327).
+//!
+//! Every participant must sign so this is a lighter-weight
+//! alternative to FROST when a threshold is not required.
+//! Signatures are BIP-340 Schnorr signatures and can be used
+//! for a taproot key path spend when the taproot mode is used.
+pub use k256::schnorr::{Signature, SigningKey, VerifyingKey};
+use musig2::KeyAggContext;
+
+mod error;
+mod sign;
+
+pub use error::Error;
+pub use sign::{SignatureDriver, SignaturePackage};
+
+/// Result type for the MuSig2 protocol.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Participant in the protocol.
+pub type Participant = crate::Participant<SigningKey, VerifyingKey>;
+
+/// Options for each party.
+pub type PartyOptions = crate::PartyOptions<VerifyingKey>;
+
+pub(crate) const ROUND_1: u8 = 1;
+pub(crate) const ROUND_2: u8 = 2;
+pub(crate) const ROUND_3: u8 = 3;
+
+/// Determines how the aggregated public key is computed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMode {
+    /// Aggregated key without a tweak.
+    #[default]
+    Plain,
+    /// Aggregated key is the internal key of a taproot output
+    /// without a script tree (BIP-86) and signatures are valid
+    /// for the output key.
+    Taproot,
+}
+
+/// Aggregate the public keys of every participant.
+///
+/// Keys are sorted (BIP-327 `KeySort`) so that the aggregated
+/// key does not depend on the order of the participants.
+pub fn key_agg_context(
+    verifiers: &[VerifyingKey],
+    mode: KeyMode,
+) -> Result<KeyAggContext> {
+    let mut public_keys = verifiers
+        .iter()
+        .map(|v| k256::PublicKey::from_affine(*v.as_affine()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    public_keys.sort_by_key(|k| k.to_sec1_bytes());
+
+    let context = KeyAggContext::new(public_keys)?;
+    Ok(match mode {
+        KeyMode::Plain => context,
+        KeyMode::Taproot => {
+            context.with_unspendable_taproot_tweak()?
+        }
+    })
+}
+
+/// Compute the x-only aggregated public key.
+///
+/// In taproot mode this is the output key used to build the
+/// `OP_1 <key>` script pubkey.
+pub fn aggregated_public_key(
+    verifiers: &[VerifyingKey],
+    mode: KeyMode,
+) -> Result<VerifyingKey> {
+    let context = key_agg_context(verifiers, mode)?;
+    let public_key: k256::PublicKey = context.aggregated_pubkey();
+    let compressed = public_key.to_sec1_bytes();
+    Ok(VerifyingKey::from_bytes(&compressed[1..])?)
+}
+
+/// Compute the x-only aggregated public key before the taproot
+/// tweak is applied.
+pub fn internal_key(verifiers: &[VerifyingKey]) -> Result<[u8; 32]> {
+    Ok(aggregated_public_key(verifiers, KeyMode::Plain)?
+        .to_bytes()
+        .into())
+}
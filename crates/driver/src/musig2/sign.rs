@@ -0,0 +1,279 @@
+//! Two-round MuSig2 signing.
+//!
+//! Round 1: each participant sends a public nonce to every
+//!          other participant.
+//! Round 2: each participant sends a partial signature to
+//!          every other participant.
+//!
+//! Every participant aggregates the final signature once
+//! all the partial signatures have been received.
+use musig2::{
+    FirstRound, LiftedSignature, PartialSignature, PubNonce,
+    SecNonceSpices, SecondRound,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroU16,
+};
+
+use crate::{ProtocolDriver, RoundInfo, RoundMessage};
+
+use super::{
+    key_agg_context, Error, KeyMode, Result, Signature, SigningKey,
+    VerifyingKey, ROUND_1, ROUND_2, ROUND_3,
+};
+
+/// Package sent to each participant during signing.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SignaturePackage {
+    /// Public nonce for the first round.
+    Nonce(PubNonce),
+    /// Partial signature for the second round.
+    PartialSignature(PartialSignature),
+}
+
+/// MuSig2 signature driver.
+pub struct SignatureDriver {
+    party_number: NonZeroU16,
+    /// Index in the key aggregation context for each party.
+    signer_indices: Vec<usize>,
+    round_number: u8,
+    signing_key: SigningKey,
+    message: Vec<u8>,
+    first_round: Option<FirstRound>,
+    second_round: Option<SecondRound<Vec<u8>>>,
+    /// Partial signatures received before the second round.
+    partial_signatures: BTreeMap<usize, PartialSignature>,
+}
+
+impl SignatureDriver {
+    /// Create a signature driver.
+    ///
+    /// The verifying keys must be ordered by session
+    /// party number.
+    pub fn new(
+        party_number: NonZeroU16,
+        signing_key: SigningKey,
+        verifiers: Vec<VerifyingKey>,
+        message: Vec<u8>,
+        mode: KeyMode,
+    ) -> Result<Self> {
+        let party_index = party_number.get() as usize;
+        let verifying_key = verifiers
+            .get(party_index - 1)
+            .ok_or(Error::IndexVerifier(party_index))?;
+        if signing_key.verifying_key() != verifying_key {
+            return Err(Error::SigningKey);
+        }
+
+        let unique = verifiers
+            .iter()
+            .map(|v| v.to_bytes())
+            .collect::<BTreeSet<_>>();
+        if unique.len() != verifiers.len() {
+            return Err(Error::DuplicateVerifier);
+        }
+
+        let mut sorted = verifiers
+            .iter()
+            .map(|v| v.to_bytes())
+            .collect::<Vec<_>>();
+        sorted.sort();
+        let signer_indices = verifiers
+            .iter()
+            .map(|v| {
+                let bytes = v.to_bytes();
+                sorted.iter().position(|k| k == &bytes).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let context = key_agg_context(&verifiers, mode)?;
+
+        let mut nonce_seed = [0u8; 32];
+        OsRng.fill_bytes(&mut nonce_seed);
+        let first_round = FirstRound::new(
+            context,
+            nonce_seed,
+            signer_indices[party_index - 1],
+            SecNonceSpices::new()
+                .with_seckey(*signing_key.as_nonzero_scalar())
+                .with_message(&message),
+        )?;
+
+        Ok(Self {
+            party_number,
+            signer_indices,
+            round_number: ROUND_1,
+            signing_key,
+            message,
+            first_round: Some(first_round),
+            second_round: None,
+            partial_signatures: BTreeMap::new(),
+        })
+    }
+
+    fn messages(
+        &self,
+        body: impl Fn() -> SignaturePackage,
+    ) -> Vec<RoundMessage<SignaturePackage, NonZeroU16>> {
+        (1..=self.signer_indices.len() as u16)
+            .filter_map(NonZeroU16::new)
+            .filter(|n| n != &self.party_number)
+            .map(|receiver| RoundMessage {
+                round: NonZeroU16::new(self.round_number.into())
+                    .unwrap(),
+                sender: self.party_number,
+                receiver,
+                body: body(),
+            })
+            .collect()
+    }
+
+    fn signer_index(&self, sender: &NonZeroU16) -> Result<usize> {
+        let index = sender.get() as usize;
+        if sender == &self.party_number {
+            return Err(Error::IndexVerifier(index));
+        }
+        self.signer_indices
+            .get(index - 1)
+            .copied()
+            .ok_or(Error::IndexVerifier(index))
+    }
+}
+
+impl ProtocolDriver for SignatureDriver {
+    type Error = Error;
+    type Message = RoundMessage<SignaturePackage, NonZeroU16>;
+    type Output = Signature;
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let round_number = self.round_number;
+        let is_echo = false;
+        let can_finalize = match self.round_number {
+            ROUND_2 => self
+                .first_round
+                .as_ref()
+                .map(|r| r.is_complete())
+                .unwrap_or(false),
+            ROUND_3 => self
+                .second_round
+                .as_ref()
+                .map(|r| r.is_complete())
+                .unwrap_or(false),
+            _ => false,
+        };
+        Ok(RoundInfo {
+            round_number,
+            can_finalize,
+            is_echo,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        let messages = match self.round_number {
+            ROUND_1 => {
+                let nonce = self
+                    .first_round
+                    .as_ref()
+                    .unwrap()
+                    .our_public_nonce();
+                self.messages(|| {
+                    SignaturePackage::Nonce(nonce.clone())
+                })
+            }
+            ROUND_2 => {
+                let first_round = self
+                    .first_round
+                    .take()
+                    .ok_or(Error::InvalidRound(self.round_number))?;
+                let second_round = first_round.finalize(
+                    *self.signing_key.as_nonzero_scalar(),
+                    self.message.clone(),
+                )?;
+                let partial_signature: PartialSignature =
+                    second_round.our_signature();
+                let mut second_round = second_round;
+                for (index, partial_signature) in
+                    std::mem::take(&mut self.partial_signatures)
+                {
+                    second_round.receive_signature(
+                        index,
+                        partial_signature,
+                    )?;
+                }
+                self.second_round = Some(second_round);
+                self.messages(|| {
+                    SignaturePackage::PartialSignature(
+                        partial_signature,
+                    )
+                })
+            }
+            _ => return Err(Error::InvalidRound(self.round_number)),
+        };
+
+        self.round_number = self.round_number.checked_add(1).unwrap();
+        Ok(messages)
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        let round_number = message.round.get() as u8;
+        let signer_index = self.signer_index(&message.sender)?;
+        match (round_number, message.body) {
+            (ROUND_1, SignaturePackage::Nonce(nonce)) => {
+                // Nonces may arrive before we have proceeded
+                // to the next round
+                let first_round = self
+                    .first_round
+                    .as_mut()
+                    .ok_or(Error::InvalidRound(round_number))?;
+                first_round.receive_nonce(signer_index, nonce)?;
+                Ok(())
+            }
+            (
+                ROUND_2,
+                SignaturePackage::PartialSignature(partial_signature),
+            ) => {
+                if let Some(second_round) = self.second_round.as_mut()
+                {
+                    second_round.receive_signature(
+                        signer_index,
+                        partial_signature,
+                    )?;
+                } else {
+                    // Partial signature arrived before we received
+                    // the nonces from every other participant
+                    self.partial_signatures
+                        .insert(signer_index, partial_signature);
+                }
+                Ok(())
+            }
+            (ROUND_1, _) | (ROUND_2, _) => {
+                Err(Error::RoundPayload(round_number))
+            }
+            _ => Err(Error::InvalidRound(round_number)),
+        }
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        if self.round_number != ROUND_3
+            || !self
+                .second_round
+                .as_ref()
+                .map(|r| r.is_complete())
+                .unwrap_or(false)
+        {
+            return Ok(None);
+        }
+
+        let second_round = self.second_round.take().unwrap();
+        let signature: LiftedSignature = second_round.finalize()?;
+        let signature =
+            Signature::try_from(signature.serialize().as_slice())?;
+        Ok(Some(signature))
+    }
+}
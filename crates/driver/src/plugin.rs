@@ -0,0 +1,108 @@
+//! Run threshold protocols implemented outside of this crate.
+use crate::{Error, ProtocolDriver, Round, RoundInfo};
+use polysig_protocol::{PartyNumber, RoundNumber};
+use serde::{Deserialize, Serialize};
+
+/// Registration for a threshold protocol.
+///
+/// Implement this trait to run a protocol driver that is not
+/// provided by this crate, for example a BLS key generation,
+/// using the same network machinery as the built-in protocols.
+pub trait Protocol {
+    /// Unique name of the protocol.
+    ///
+    /// Every message is tagged with the name so that messages
+    /// for a different protocol are rejected.
+    const NAME: &'static str;
+
+    /// Driver for the protocol.
+    type Driver: ProtocolDriver;
+}
+
+/// Message for a protocol registered as a plugin.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PluginMessage<M> {
+    /// Name of the protocol.
+    pub(crate) protocol: String,
+    /// Message for the driver.
+    pub(crate) message: M,
+}
+
+impl<M> PluginMessage<M> {
+    /// Name of the protocol.
+    pub fn protocol(&self) -> &str {
+        &self.protocol
+    }
+}
+
+impl<M: Round> Round for PluginMessage<M> {
+    fn round_number(&self) -> RoundNumber {
+        self.message.round_number()
+    }
+
+    fn receiver(&self) -> &PartyNumber {
+        self.message.receiver()
+    }
+}
+
+/// Wraps the driver for a protocol registered as a plugin.
+pub struct PluginDriver<P: Protocol> {
+    driver: P::Driver,
+}
+
+impl<P: Protocol> PluginDriver<P> {
+    /// Create a new plugin driver.
+    pub fn new(driver: P::Driver) -> Self {
+        Self { driver }
+    }
+
+    fn plugin_error(
+        error: <P::Driver as ProtocolDriver>::Error,
+    ) -> Error {
+        Error::Plugin(P::NAME, Box::new(error))
+    }
+}
+
+impl<P: Protocol> ProtocolDriver for PluginDriver<P> {
+    type Error = Error;
+    type Message =
+        PluginMessage<<P::Driver as ProtocolDriver>::Message>;
+    type Output = <P::Driver as ProtocolDriver>::Output;
+
+    fn round_info(&self) -> Result<RoundInfo, Self::Error> {
+        self.driver.round_info().map_err(Self::plugin_error)
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>, Self::Error> {
+        let messages =
+            self.driver.proceed().map_err(Self::plugin_error)?;
+        Ok(messages
+            .into_iter()
+            .map(|message| PluginMessage {
+                protocol: P::NAME.to_owned(),
+                message,
+            })
+            .collect())
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<(), Self::Error> {
+        if message.protocol != P::NAME {
+            return Err(Error::PluginProtocol(
+                P::NAME,
+                message.protocol,
+            ));
+        }
+        self.driver
+            .handle_incoming(message.message)
+            .map_err(Self::plugin_error)
+    }
+
+    fn try_finalize_round(
+        &mut self,
+    ) -> Result<Option<Self::Output>, Self::Error> {
+        self.driver.try_finalize_round().map_err(Self::plugin_error)
+    }
+}
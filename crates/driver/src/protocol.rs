@@ -1,7 +1,9 @@
 //! Types for the protocol drivers.
 
-use crate::{Error, Result};
-use polysig_protocol::{Keypair, PartyNumber, RoundNumber};
+use crate::{Error, Result, Weights};
+use polysig_protocol::{
+    Keypair, Parameters, PartyNumber, RoundNumber,
+};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// Generic threshold key share encoded as a PEM.
@@ -20,15 +22,147 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 /// A version number is included to allow us to recognize changes
 /// in the upstream library `ThresholdKeyShare` struct.
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct KeyShare {
     /// Protocol version.
     pub version: u16,
     /// PEM-encoded key share contents.
     pub contents: String,
+    /// Rules enforced before signing with the key share.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<KeySharePolicy>,
+}
+
+impl KeyShare {
+    /// Encode the key share as JSON.
+    ///
+    /// The encoding is the format used to store key shares
+    /// so it can be read by the signer server keystore.
+    pub fn encode(&self) -> Result<String> {
+        self.validate()?;
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Decode a key share from JSON.
+    pub fn decode(value: &str) -> Result<Self> {
+        let key_share: Self = serde_json::from_str(value)?;
+        key_share.validate()?;
+        Ok(key_share)
+    }
+
+    /// Tag for the PEM-encoded contents.
+    ///
+    /// Identifies the protocol for the key share.
+    pub fn tag(&self) -> Result<String> {
+        let pem = polysig_protocol::pem::parse(&self.contents)
+            .map_err(polysig_protocol::Error::from)?;
+        Ok(pem.tag().to_owned())
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.tag()?;
+        Ok(())
+    }
+}
+
+/// Governance rules stored with a key share.
+///
+/// The high-level sign functions in the client check the
+/// policy before participating in a signature. Rules are not
+/// part of the key material so anyone with write access to an
+/// unencrypted container can remove them; store containers
+/// encrypted to prevent tampering.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct KeySharePolicy {
+    /// Seconds since the UNIX epoch after which the key share
+    /// can no longer be used to sign.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Allowed derivation path prefixes such as `m/44'/60'`.
+    ///
+    /// When empty signing with any derivation path is allowed;
+    /// signing without a derivation path is treated as the
+    /// root path `m`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derivation_paths: Vec<String>,
+    /// Allowed chain identifiers such as `eip155:1`.
+    ///
+    /// When empty signing for any chain is allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chains: Vec<String>,
+}
+
+impl KeySharePolicy {
+    /// Check a signature is allowed by this policy.
+    ///
+    /// The current time is given in seconds since
+    /// the UNIX epoch.
+    pub fn check(
+        &self,
+        now: u64,
+        derivation_path: Option<&str>,
+        chain: Option<&str>,
+    ) -> Result<()> {
+        if let Some(expires_at) = self.expires_at {
+            if now >= expires_at {
+                return Err(Error::KeyShareExpired(expires_at));
+            }
+        }
+
+        if !self.derivation_paths.is_empty() {
+            let path = derivation_path.unwrap_or("m");
+            if !self
+                .derivation_paths
+                .iter()
+                .any(|prefix| is_path_prefix(prefix, path))
+            {
+                return Err(Error::DerivationPathNotAllowed(
+                    path.to_owned(),
+                ));
+            }
+        }
+
+        if !self.chains.is_empty() {
+            let chain = chain.ok_or(Error::ChainRequired)?;
+            if !self.chains.iter().any(|c| c == chain) {
+                return Err(Error::ChainNotAllowed(chain.to_owned()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Determine if a derivation path starts with the components
+/// of a prefix.
+fn is_path_prefix(prefix: &str, path: &str) -> bool {
+    let prefix = path_components(prefix);
+    let path = path_components(path);
+    path.len() >= prefix.len() && path[..prefix.len()] == prefix[..]
+}
+
+/// Components of a derivation path.
+///
+/// Hardened components may be written with `'`, `h` or `H`
+/// so they are normalized to use `'`.
+fn path_components(path: &str) -> Vec<String> {
+    path.trim()
+        .trim_end_matches('/')
+        .split('/')
+        .map(|c| match c.strip_suffix(['h', 'H']) {
+            Some(index) => format!("{}'", index),
+            None => c.to_owned(),
+        })
+        .collect()
 }
 
 /// Keys for a protocol participant.
 #[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct PartyKeys {
     /// Noise transport keypair.
@@ -92,6 +226,16 @@ pub trait ProtocolDriver {
     fn try_finalize_round(
         &mut self,
     ) -> std::result::Result<Option<Self::Output>, Self::Error>;
+
+    /// Party responsible for an error returned by the driver.
+    ///
+    /// Drivers that can attribute a failure to a participant,
+    /// for example an invalid signature share, return the
+    /// party number of the participant so it can be blamed.
+    fn culprit(&self, error: &Self::Error) -> Option<PartyNumber> {
+        let _ = error;
+        None
+    }
 }
 
 /// Trait for round messages.
@@ -183,8 +327,133 @@ where
     }
 }
 
+/// Builder for the options of a party.
+///
+/// Participants are ordered by the order their verifying
+/// keys are added so the index of a party always matches
+/// the index of its verifying key.
+pub struct PartyOptionsBuilder<Verifier> {
+    public_key: Vec<u8>,
+    participants: Vec<Vec<u8>>,
+    verifiers: Vec<Verifier>,
+    weights: Vec<u16>,
+    is_initiator: bool,
+    threshold: Option<u16>,
+}
+
+impl<Verifier> PartyOptionsBuilder<Verifier> {
+    /// Add a participant with the verifying key
+    /// for the participant.
+    pub fn verifier(
+        mut self,
+        public_key: Vec<u8>,
+        verifier: Verifier,
+    ) -> Self {
+        self.participants.push(public_key);
+        self.verifiers.push(verifier);
+        self
+    }
+
+    /// Add participants with the verifying keys in the
+    /// same order.
+    ///
+    /// The number of participants and verifying keys are
+    /// checked when the options are built.
+    pub fn verifiers(
+        mut self,
+        participants: Vec<Vec<u8>>,
+        verifiers: Vec<Verifier>,
+    ) -> Self {
+        self.participants.extend(participants);
+        self.verifiers.extend(verifiers);
+        self
+    }
+
+    /// Set the weight of each participant in the same order
+    /// as the participants.
+    ///
+    /// A participant with a weight greater than one holds
+    /// multiple shares; when the threshold is set it is the
+    /// total weight required to sign.
+    ///
+    /// Weights are only supported by the FROST protocols,
+    /// CGGMP ceremonies reject weighted participants.
+    pub fn weights(mut self, weights: Vec<u16>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Set whether this party is the session initiator.
+    pub fn initiator(mut self, is_initiator: bool) -> Self {
+        self.is_initiator = is_initiator;
+        self
+    }
+
+    /// Set the threshold for the protocol.
+    ///
+    /// The threshold is checked against the number of
+    /// participants when the options are built.
+    pub fn threshold(mut self, threshold: u16) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Validate the settings and build the party options.
+    pub fn build(self) -> Result<PartyOptions<Verifier>> {
+        if self.participants.len() != self.verifiers.len() {
+            return Err(Error::ParticipantVerifierLength(
+                self.participants.len(),
+                self.verifiers.len(),
+            ));
+        }
+
+        for (index, participant) in
+            self.participants.iter().enumerate()
+        {
+            if self.participants[..index].contains(participant) {
+                return Err(Error::DuplicateParticipant(index));
+            }
+        }
+
+        let party_index = self
+            .participants
+            .iter()
+            .position(|v| v == &self.public_key)
+            .ok_or(Error::NotVerifyingParty)?;
+
+        let mut parties = self.participants.len() as u16;
+        if !self.weights.is_empty() {
+            if self.weights.len() != self.participants.len() {
+                return Err(Error::ParticipantWeightLength(
+                    self.participants.len(),
+                    self.weights.len(),
+                ));
+            }
+            parties = Weights::new(self.weights.clone())?.total();
+        }
+
+        if let Some(threshold) = self.threshold {
+            Parameters { parties, threshold }.validate()?;
+        }
+
+        Ok(PartyOptions {
+            public_key: self.public_key,
+            participants: self.participants,
+            is_initiator: self.is_initiator,
+            party_index,
+            verifiers: self.verifiers,
+            weights: self.weights,
+        })
+    }
+}
+
 /// Options for a party participating in a protocol.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "typescript",
+    derive(ts_rs::TS),
+    ts(concrete(Verifier = Vec<u8>))
+)]
 #[serde(rename_all = "camelCase")]
 pub struct PartyOptions<Verifier> {
     /// Encryption public key for this party.
@@ -200,6 +469,11 @@ pub struct PartyOptions<Verifier> {
     party_index: usize,
     /// Verifying keys for all participants.
     verifiers: Vec<Verifier>,
+    /// Weights for all participants.
+    ///
+    /// When empty every participant has a weight of one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    weights: Vec<u16>,
 }
 
 impl<Verifier> PartyOptions<Verifier> {
@@ -210,25 +484,25 @@ impl<Verifier> PartyOptions<Verifier> {
         is_initiator: bool,
         verifiers: Vec<Verifier>,
     ) -> Result<Self> {
-        let party_index = participants
-            .iter()
-            .position(|v| v == &public_key)
-            .ok_or(Error::NotVerifyingParty)?;
-
-        if participants.len() != verifiers.len() {
-            return Err(Error::ParticipantVerifierLength(
-                participants.len(),
-                verifiers.len(),
-            ));
-        }
+        Self::builder(public_key)
+            .verifiers(participants, verifiers)
+            .initiator(is_initiator)
+            .build()
+    }
 
-        Ok(Self {
+    /// Create a builder for the options of the party
+    /// with the given public key.
+    pub fn builder(
+        public_key: Vec<u8>,
+    ) -> PartyOptionsBuilder<Verifier> {
+        PartyOptionsBuilder {
             public_key,
-            participants,
-            is_initiator,
-            party_index,
-            verifiers,
-        })
+            participants: Vec::new(),
+            verifiers: Vec::new(),
+            weights: Vec::new(),
+            is_initiator: false,
+            threshold: None,
+        }
     }
 
     /// Public key of this participant.
@@ -255,4 +529,33 @@ impl<Verifier> PartyOptions<Verifier> {
     pub fn verifiers(&self) -> &[Verifier] {
         self.verifiers.as_slice()
     }
+
+    /// Weight of this participant.
+    pub fn weight(&self) -> u16 {
+        self.weights.get(self.party_index).copied().unwrap_or(1)
+    }
+
+    /// Replace the keys of the participant at an index.
+    #[cfg(any(feature = "cggmp", feature = "frost"))]
+    pub(crate) fn replace_participant(
+        &mut self,
+        index: usize,
+        public_key: Vec<u8>,
+        verifier: Verifier,
+    ) {
+        if index == self.party_index {
+            self.public_key = public_key.clone();
+        }
+        self.participants[index] = public_key;
+        self.verifiers[index] = verifier;
+    }
+
+    /// Weights of all participants.
+    pub fn weights(&self) -> Weights {
+        if self.weights.is_empty() {
+            Weights::uniform(self.participants.len() as u16)
+        } else {
+            Weights(self.weights.clone())
+        }
+    }
 }
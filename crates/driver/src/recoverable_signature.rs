@@ -1,9 +1,26 @@
 //! Recoverable signature for ECDSA.
+//!
+//! Signatures may be converted to and from the DER, 64 byte
+//! compact and 65 byte Ethereum (`r || s || v`) encodings.
+use crate::{Error, Result};
 use k256::ecdsa::{RecoveryId, Signature};
 use serde::{Deserialize, Serialize};
 
+/// Length of a compact signature.
+const COMPACT_LEN: usize = 64;
+
+/// Length of an Ethereum signature.
+const ETH_LEN: usize = 65;
+
+/// Offset for legacy Ethereum recovery values.
+const ETH_V_OFFSET: u64 = 27;
+
+/// Offset for EIP-155 recovery values.
+const EIP155_V_OFFSET: u64 = 35;
+
 /// Recoverable signature.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct RecoverableSignature {
     /// Signature bytes.
@@ -12,6 +29,123 @@ pub struct RecoverableSignature {
     pub recovery_id: u8,
 }
 
+impl RecoverableSignature {
+    /// Create a recoverable signature from a DER encoded
+    /// signature and recovery identifier.
+    pub fn from_der(der: &[u8], recovery_id: u8) -> Result<Self> {
+        let signature = Signature::from_der(der)?;
+        let recovery_id = RecoveryId::try_from(recovery_id)?;
+        Ok((signature, recovery_id).into())
+    }
+
+    /// DER encoding of the signature.
+    pub fn to_der(&self) -> Result<Vec<u8>> {
+        let signature = Signature::from_slice(&self.bytes)?;
+        Ok(signature.to_der().as_bytes().to_vec())
+    }
+
+    /// Create a recoverable signature from a 64 byte compact
+    /// signature and recovery identifier.
+    pub fn from_compact(
+        bytes: &[u8],
+        recovery_id: u8,
+    ) -> Result<Self> {
+        if bytes.len() != COMPACT_LEN {
+            return Err(Error::SignatureLength(
+                COMPACT_LEN,
+                bytes.len(),
+            ));
+        }
+        let signature = Signature::from_slice(bytes)?;
+        let recovery_id = RecoveryId::try_from(recovery_id)?;
+        Ok((signature, recovery_id).into())
+    }
+
+    /// Compact encoding of the signature (`r || s`).
+    pub fn to_compact(&self) -> Result<[u8; COMPACT_LEN]> {
+        let signature = Signature::from_slice(&self.bytes)?;
+        let mut bytes = [0u8; COMPACT_LEN];
+        bytes.copy_from_slice(&signature.to_bytes());
+        Ok(bytes)
+    }
+
+    /// Create a recoverable signature from a 65 byte
+    /// Ethereum signature (`r || s || v`).
+    ///
+    /// The recovery value may be `0`, `1`, `27` or `28`.
+    pub fn from_eth_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != ETH_LEN {
+            return Err(Error::SignatureLength(ETH_LEN, bytes.len()));
+        }
+        let (recovery_id, _) =
+            Self::recovery_id_from_eth_v(bytes[COMPACT_LEN] as u64)?;
+        Self::from_compact(&bytes[..COMPACT_LEN], recovery_id)
+    }
+
+    /// Ethereum encoding of the signature (`r || s || v`)
+    /// using the legacy recovery value (`27` or `28`).
+    pub fn to_eth_bytes(&self) -> Result<[u8; ETH_LEN]> {
+        let mut bytes = [0u8; ETH_LEN];
+        bytes[..COMPACT_LEN].copy_from_slice(&self.to_compact()?);
+        bytes[COMPACT_LEN] =
+            (self.recovery_id as u64 + ETH_V_OFFSET) as u8;
+        Ok(bytes)
+    }
+
+    /// Ethereum recovery value for a chain according
+    /// to [EIP-155] (`recovery_id + chain_id * 2 + 35`).
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub fn to_eth_v(&self, chain_id: u64) -> u64 {
+        self.recovery_id as u64 + chain_id * 2 + EIP155_V_OFFSET
+    }
+
+    /// Recovery identifier and chain identifier for an
+    /// Ethereum recovery value.
+    ///
+    /// Legacy values (`27` or `28`) and raw recovery
+    /// identifiers (`0` or `1`) do not have a chain identifier.
+    pub fn recovery_id_from_eth_v(
+        v: u64,
+    ) -> Result<(u8, Option<u64>)> {
+        match v {
+            0 | 1 => Ok((v as u8, None)),
+            27 | 28 => Ok(((v - ETH_V_OFFSET) as u8, None)),
+            v if v >= EIP155_V_OFFSET => {
+                let v = v - EIP155_V_OFFSET;
+                Ok(((v % 2) as u8, Some(v / 2)))
+            }
+            _ => Err(Error::EthRecoveryValue(v)),
+        }
+    }
+
+    /// Whether the `s` value of the signature is in the
+    /// lower half of the curve order.
+    pub fn is_low_s(&self) -> Result<bool> {
+        let signature = Signature::from_slice(&self.bytes)?;
+        Ok(signature.normalize_s().is_none())
+    }
+
+    /// Normalize the signature to use a low `s` value.
+    ///
+    /// Negating `s` flips the parity of the recovered point
+    /// so the recovery identifier is updated to match.
+    pub fn normalize_s(&self) -> Result<Self> {
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            self.try_into()?;
+        Ok(match signature.normalize_s() {
+            Some(normalized) => {
+                let recovery_id = RecoveryId::new(
+                    !recovery_id.is_y_odd(),
+                    recovery_id.is_x_reduced(),
+                );
+                (normalized, recovery_id).into()
+            }
+            None => self.clone(),
+        })
+    }
+}
+
 impl From<(Signature, RecoveryId)> for RecoverableSignature {
     fn from(value: (Signature, RecoveryId)) -> Self {
         Self {
@@ -26,7 +160,7 @@ impl TryFrom<&RecoverableSignature> for (Signature, RecoveryId) {
 
     fn try_from(
         value: &RecoverableSignature,
-    ) -> Result<Self, Self::Error> {
+    ) -> std::result::Result<Self, Self::Error> {
         Ok((
             Signature::from_slice(&value.bytes)?,
             value.recovery_id.try_into()?,
@@ -39,7 +173,7 @@ impl TryFrom<RecoverableSignature> for (Signature, RecoveryId) {
 
     fn try_from(
         value: RecoverableSignature,
-    ) -> Result<Self, Self::Error> {
+    ) -> std::result::Result<Self, Self::Error> {
         (&value).try_into()
     }
 }
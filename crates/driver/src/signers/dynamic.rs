@@ -0,0 +1,341 @@
+//! Common interface for the single party signers.
+use crate::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fmt};
+
+#[cfg(feature = "ecdsa")]
+use super::ecdsa::EcdsaSigner;
+#[cfg(feature = "eddsa")]
+use super::eddsa::EddsaSigner;
+#[cfg(feature = "schnorr")]
+use super::schnorr::SchnorrSigner;
+
+/// Signature algorithm for a single party signer.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Algorithm {
+    /// ECDSA over secp256k1.
+    #[cfg(feature = "ecdsa")]
+    Ecdsa,
+    /// Ed25519.
+    #[cfg(feature = "eddsa")]
+    Eddsa,
+    /// BIP-340 Schnorr over secp256k1.
+    #[cfg(feature = "schnorr")]
+    Schnorr,
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                #[cfg(feature = "ecdsa")]
+                Self::Ecdsa => "ecdsa",
+                #[cfg(feature = "eddsa")]
+                Self::Eddsa => "eddsa",
+                #[cfg(feature = "schnorr")]
+                Self::Schnorr => "schnorr",
+            }
+        )
+    }
+}
+
+impl Algorithm {
+    /// Verify a signature for a message using an encoded
+    /// public key.
+    ///
+    /// Public keys and signatures use the same encodings
+    /// as the [Signer] trait.
+    pub fn verify(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        match self {
+            #[cfg(feature = "ecdsa")]
+            Self::Ecdsa => {
+                use k256::ecdsa::{
+                    signature::Verifier, Signature, VerifyingKey,
+                };
+                let key = VerifyingKey::from_sec1_bytes(public_key)?;
+                let signature = Signature::from_slice(signature)?;
+                Ok(key.verify(message, &signature)?)
+            }
+            #[cfg(feature = "eddsa")]
+            Self::Eddsa => {
+                use ed25519::signature::Verifier;
+                use ed25519_dalek::{Signature, VerifyingKey};
+                let key = VerifyingKey::try_from(public_key)
+                    .map_err(Box::from)?;
+                let signature = Signature::from_slice(signature)
+                    .map_err(Box::from)?;
+                Ok(key
+                    .verify(message, &signature)
+                    .map_err(Box::from)?)
+            }
+            #[cfg(feature = "schnorr")]
+            Self::Schnorr => {
+                use k256::schnorr::{
+                    signature::Verifier, Signature, VerifyingKey,
+                };
+                let key = VerifyingKey::from_bytes(public_key)?;
+                let signature = Signature::try_from(signature)?;
+                Ok(key.verify(message, &signature)?)
+            }
+        }
+    }
+}
+
+/// Signer that creates and verifies signatures as bytes.
+///
+/// Signatures use the 64 byte encoding for every algorithm;
+/// messages are hashed in the same way as the `sign` method
+/// of the underlying signer.
+pub trait Signer {
+    /// Signature algorithm.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Encoded public key.
+    ///
+    /// ECDSA keys use the compressed SEC1 encoding and
+    /// Schnorr keys use the x-only encoding.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// Sign a message.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
+
+    /// Verify a signature for a message.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()>;
+}
+
+#[cfg(feature = "ecdsa")]
+impl<'a> Signer for EcdsaSigner<'a> {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Ecdsa
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.verifying_key().to_sec1_bytes().to_vec()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Ok(EcdsaSigner::sign(self, message).to_vec())
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        let signature =
+            super::ecdsa::Signature::from_slice(signature)?;
+        EcdsaSigner::verify(self, message, &signature)
+    }
+}
+
+#[cfg(feature = "eddsa")]
+impl<'a> Signer for EddsaSigner<'a> {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Eddsa
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.verifying_key().to_bytes().to_vec()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Ok(EddsaSigner::sign(self, message).to_bytes().to_vec())
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        let signature =
+            super::eddsa::Signature::from_slice(signature)
+                .map_err(Box::from)?;
+        EddsaSigner::verify(self, message, &signature)
+    }
+}
+
+#[cfg(feature = "schnorr")]
+impl<'a> Signer for SchnorrSigner<'a> {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Schnorr
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.x_only_public_key().to_vec()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Ok(SchnorrSigner::sign(self, message).to_bytes().to_vec())
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        let signature =
+            super::schnorr::Signature::try_from(signature)?;
+        SchnorrSigner::verify(self, message, &signature)
+    }
+}
+
+/// Signer that creates signatures asynchronously.
+///
+/// Implemented for every [Signer] so code written against
+/// this trait can use single party signers and signers that
+/// run a threshold signing protocol interchangeably.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AsyncSigner {
+    /// Signature algorithm.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Encoded public key.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// Sign a message.
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
+
+    /// Verify a signature for a message.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        self.algorithm().verify(
+            &self.public_key(),
+            message,
+            signature,
+        )
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: Signer + Sync> AsyncSigner for T {
+    fn algorithm(&self) -> Algorithm {
+        Signer::algorithm(self)
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        Signer::public_key(self)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Signer::sign(self, message)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        Signer::verify(self, message, signature)
+    }
+}
+
+/// Signer for any of the supported algorithms.
+pub enum DynSigner<'a> {
+    /// ECDSA signer.
+    #[cfg(feature = "ecdsa")]
+    Ecdsa(EcdsaSigner<'a>),
+    /// EdDSA signer.
+    #[cfg(feature = "eddsa")]
+    Eddsa(EddsaSigner<'a>),
+    /// Schnorr signer.
+    #[cfg(feature = "schnorr")]
+    Schnorr(SchnorrSigner<'a>),
+}
+
+impl DynSigner<'static> {
+    /// Create a signer with a random private key.
+    pub fn random(algorithm: Algorithm) -> Self {
+        match algorithm {
+            #[cfg(feature = "ecdsa")]
+            Algorithm::Ecdsa => Self::Ecdsa(EcdsaSigner::new(
+                Cow::Owned(EcdsaSigner::random().into()),
+            )),
+            #[cfg(feature = "eddsa")]
+            Algorithm::Eddsa => Self::Eddsa(EddsaSigner::new(
+                Cow::Owned(EddsaSigner::random().into()),
+            )),
+            #[cfg(feature = "schnorr")]
+            Algorithm::Schnorr => Self::Schnorr(SchnorrSigner::new(
+                Cow::Owned(SchnorrSigner::random().into()),
+            )),
+        }
+    }
+
+    /// Create a signer from the bytes of a private key.
+    pub fn from_slice(
+        algorithm: Algorithm,
+        private_key: &[u8],
+    ) -> Result<Self> {
+        Ok(match algorithm {
+            #[cfg(feature = "ecdsa")]
+            Algorithm::Ecdsa => {
+                Self::Ecdsa(EcdsaSigner::new(Cow::Owned(
+                    EcdsaSigner::from_slice(private_key)?.into(),
+                )))
+            }
+            #[cfg(feature = "eddsa")]
+            Algorithm::Eddsa => {
+                let signing_key =
+                    ed25519_dalek::SigningKey::try_from(private_key)
+                        .map_err(Box::from)?;
+                Self::Eddsa(EddsaSigner::new(Cow::Owned(
+                    signing_key.into(),
+                )))
+            }
+            #[cfg(feature = "schnorr")]
+            Algorithm::Schnorr => {
+                Self::Schnorr(SchnorrSigner::new(Cow::Owned(
+                    SchnorrSigner::from_slice(private_key)?.into(),
+                )))
+            }
+        })
+    }
+}
+
+impl<'a> DynSigner<'a> {
+    fn inner(&self) -> &dyn Signer {
+        match self {
+            #[cfg(feature = "ecdsa")]
+            Self::Ecdsa(signer) => signer,
+            #[cfg(feature = "eddsa")]
+            Self::Eddsa(signer) => signer,
+            #[cfg(feature = "schnorr")]
+            Self::Schnorr(signer) => signer,
+        }
+    }
+}
+
+impl<'a> Signer for DynSigner<'a> {
+    fn algorithm(&self) -> Algorithm {
+        self.inner().algorithm()
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.inner().public_key()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        self.inner().sign(message)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        self.inner().verify(message, signature)
+    }
+}
+
+#[cfg(feature = "ecdsa")]
+impl<'a> From<EcdsaSigner<'a>> for DynSigner<'a> {
+    fn from(value: EcdsaSigner<'a>) -> Self {
+        Self::Ecdsa(value)
+    }
+}
+
+#[cfg(feature = "eddsa")]
+impl<'a> From<EddsaSigner<'a>> for DynSigner<'a> {
+    fn from(value: EddsaSigner<'a>) -> Self {
+        Self::Eddsa(value)
+    }
+}
+
+#[cfg(feature = "schnorr")]
+impl<'a> From<SchnorrSigner<'a>> for DynSigner<'a> {
+    fn from(value: SchnorrSigner<'a>) -> Self {
+        Self::Schnorr(value)
+    }
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+/// Errors generated hashing typed data.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated when a type is not defined.
+    #[error("type '{0}' is not defined")]
+    UnknownType(String),
+
+    /// Error generated when a struct value is missing a field.
+    #[error("value for '{0}' is missing field '{1}'")]
+    MissingField(String, String),
+
+    /// Error generated when a value does not match the type.
+    #[error("invalid value for type '{0}'")]
+    InvalidValue(String),
+
+    /// Error generated when a fixed size array has
+    /// the wrong number of elements.
+    #[error("expected {0} array elements but got {1}")]
+    ArrayLength(usize, usize),
+
+    /// Error generated when an integer is out of
+    /// range for the type.
+    #[error("integer is out of range for type '{0}'")]
+    IntegerRange(String),
+
+    /// Error generated decoding hex values.
+    #[error(transparent)]
+    Hex(#[from] polysig_protocol::hex::FromHexError),
+}
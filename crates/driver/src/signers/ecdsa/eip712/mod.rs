@@ -0,0 +1,448 @@
+//! Hash typed structured data according to [EIP-712].
+//!
+//! A [TypedData] document uses the JSON format accepted by
+//! the `eth_signTypedData_v4` RPC method; sign the document
+//! with [EcdsaSigner::sign_typed_data].
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+//! [EcdsaSigner::sign_typed_data]: super::EcdsaSigner::sign_typed_data
+use polysig_protocol::hex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha3::{Digest, Keccak256};
+use std::collections::{BTreeMap, BTreeSet};
+
+mod error;
+
+pub use error::Error;
+
+/// Result type for typed data hashing.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Name of the domain type.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Fields of the domain in the order required when the
+/// domain type is not declared.
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+/// Member of a struct type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedField {
+    /// Name of the member.
+    pub name: String,
+    /// Type of the member.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// Typed structured data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// Struct type definitions.
+    ///
+    /// When the `EIP712Domain` type is not declared it is
+    /// inferred from the fields of the domain.
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    /// Type of the message.
+    pub primary_type: String,
+    /// Domain values.
+    pub domain: Map<String, Value>,
+    /// Message values.
+    #[serde(default)]
+    pub message: Map<String, Value>,
+}
+
+impl TypedData {
+    /// Hash of the domain.
+    pub fn domain_separator(&self) -> Result<[u8; 32]> {
+        let domain = Value::Object(self.domain.clone());
+        if self.types.contains_key(DOMAIN_TYPE) {
+            self.hash_struct(DOMAIN_TYPE, &domain)
+        } else {
+            let mut types = self.types.clone();
+            types.insert(
+                DOMAIN_TYPE.to_owned(),
+                DOMAIN_FIELDS
+                    .iter()
+                    .filter(|(name, _)| {
+                        self.domain.contains_key(*name)
+                    })
+                    .map(|(name, kind)| TypedField {
+                        name: (*name).to_owned(),
+                        kind: (*kind).to_owned(),
+                    })
+                    .collect(),
+            );
+            Encoder { types: &types }
+                .hash_struct(DOMAIN_TYPE, &domain)
+        }
+    }
+
+    /// Hash of the message.
+    pub fn message_hash(&self) -> Result<[u8; 32]> {
+        self.hash_struct(
+            &self.primary_type,
+            &Value::Object(self.message.clone()),
+        )
+    }
+
+    /// Hash to sign for the typed data.
+    ///
+    /// Computes `keccak256("\x19\x01" ‖ domainSeparator ‖
+    /// hashStruct(message))`; when the primary type is the
+    /// domain type the message hash is omitted.
+    pub fn signing_hash(&self) -> Result<[u8; 32]> {
+        let mut hasher = Keccak256::new_with_prefix([0x19, 0x01]);
+        hasher.update(self.domain_separator()?);
+        if self.primary_type != DOMAIN_TYPE {
+            hasher.update(self.message_hash()?);
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// Encode a struct type including the referenced types.
+    pub fn encode_type(&self, name: &str) -> Result<String> {
+        Encoder { types: &self.types }.encode_type(name)
+    }
+
+    /// Hash of a struct type.
+    pub fn type_hash(&self, name: &str) -> Result<[u8; 32]> {
+        Ok(keccak256(self.encode_type(name)?.as_bytes()))
+    }
+
+    /// Hash of a struct value.
+    pub fn hash_struct(
+        &self,
+        name: &str,
+        value: &Value,
+    ) -> Result<[u8; 32]> {
+        Encoder { types: &self.types }.hash_struct(name, value)
+    }
+}
+
+struct Encoder<'a> {
+    types: &'a BTreeMap<String, Vec<TypedField>>,
+}
+
+impl<'a> Encoder<'a> {
+    fn fields(&self, name: &str) -> Result<&'a [TypedField]> {
+        self.types
+            .get(name)
+            .map(|fields| fields.as_slice())
+            .ok_or_else(|| Error::UnknownType(name.to_owned()))
+    }
+
+    fn encode_type(&self, name: &str) -> Result<String> {
+        let mut dependencies = BTreeSet::new();
+        self.dependencies(name, &mut dependencies)?;
+        dependencies.remove(name);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(name)
+            .chain(dependencies.iter().map(|s| s.as_str()))
+        {
+            let fields = self
+                .fields(name)?
+                .iter()
+                .map(|f| format!("{} {}", f.kind, f.name))
+                .collect::<Vec<_>>();
+            encoded.push_str(name);
+            encoded.push('(');
+            encoded.push_str(&fields.join(","));
+            encoded.push(')');
+        }
+        Ok(encoded)
+    }
+
+    fn dependencies(
+        &self,
+        name: &str,
+        found: &mut BTreeSet<String>,
+    ) -> Result<()> {
+        if !found.insert(name.to_owned()) {
+            return Ok(());
+        }
+        for field in self.fields(name)? {
+            let base = base_type(&field.kind);
+            if self.types.contains_key(base) {
+                self.dependencies(base, found)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn hash_struct(
+        &self,
+        name: &str,
+        value: &Value,
+    ) -> Result<[u8; 32]> {
+        let values = value
+            .as_object()
+            .ok_or_else(|| Error::InvalidValue(name.to_owned()))?;
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&keccak256(
+            self.encode_type(name)?.as_bytes(),
+        ));
+        for field in self.fields(name)? {
+            let value = values.get(&field.name).ok_or_else(|| {
+                Error::MissingField(
+                    name.to_owned(),
+                    field.name.clone(),
+                )
+            })?;
+            encoded.extend_from_slice(
+                &self.encode_value(&field.kind, value)?,
+            );
+        }
+        Ok(keccak256(&encoded))
+    }
+
+    fn encode_value(
+        &self,
+        kind: &str,
+        value: &Value,
+    ) -> Result<[u8; 32]> {
+        if let Some(element) = kind.strip_suffix(']') {
+            let (element, length) = element
+                .rsplit_once('[')
+                .ok_or_else(|| Error::UnknownType(kind.to_owned()))?;
+            let values = value.as_array().ok_or_else(|| {
+                Error::InvalidValue(kind.to_owned())
+            })?;
+            if !length.is_empty() {
+                let length: usize = length.parse().map_err(|_| {
+                    Error::UnknownType(kind.to_owned())
+                })?;
+                if length != values.len() {
+                    return Err(Error::ArrayLength(
+                        length,
+                        values.len(),
+                    ));
+                }
+            }
+            let mut encoded = Vec::with_capacity(values.len() * 32);
+            for value in values {
+                encoded.extend_from_slice(
+                    &self.encode_value(element, value)?,
+                );
+            }
+            return Ok(keccak256(&encoded));
+        }
+
+        if self.types.contains_key(kind) {
+            return self.hash_struct(kind, value);
+        }
+
+        let invalid = || Error::InvalidValue(kind.to_owned());
+        match kind {
+            "string" => {
+                let value = value.as_str().ok_or_else(invalid)?;
+                Ok(keccak256(value.as_bytes()))
+            }
+            "bytes" => {
+                let value = value.as_str().ok_or_else(invalid)?;
+                Ok(keccak256(&decode_hex(value)?))
+            }
+            "bool" => {
+                let value = value.as_bool().ok_or_else(invalid)?;
+                let mut encoded = [0u8; 32];
+                encoded[31] = value as u8;
+                Ok(encoded)
+            }
+            "address" => {
+                let value = value.as_str().ok_or_else(invalid)?;
+                let bytes = decode_hex(value)?;
+                if bytes.len() != 20 {
+                    return Err(invalid());
+                }
+                let mut encoded = [0u8; 32];
+                encoded[12..].copy_from_slice(&bytes);
+                Ok(encoded)
+            }
+            _ => {
+                if let Some(size) = kind.strip_prefix("bytes") {
+                    let size = parse_size(kind, size, 1, 32)?;
+                    let value = value.as_str().ok_or_else(invalid)?;
+                    let bytes = decode_hex(value)?;
+                    if bytes.len() != size {
+                        return Err(invalid());
+                    }
+                    let mut encoded = [0u8; 32];
+                    encoded[..size].copy_from_slice(&bytes);
+                    Ok(encoded)
+                } else if let Some(bits) = kind.strip_prefix("uint") {
+                    let bits = parse_bits(kind, bits)?;
+                    let (negative, magnitude) =
+                        parse_integer(kind, value)?;
+                    if negative && magnitude != [0u8; 32] {
+                        return Err(Error::IntegerRange(
+                            kind.to_owned(),
+                        ));
+                    }
+                    if bit_length(&magnitude) > bits {
+                        return Err(Error::IntegerRange(
+                            kind.to_owned(),
+                        ));
+                    }
+                    Ok(magnitude)
+                } else if let Some(bits) = kind.strip_prefix("int") {
+                    let bits = parse_bits(kind, bits)?;
+                    let (negative, magnitude) =
+                        parse_integer(kind, value)?;
+                    // Negative values may use the full width when
+                    // the magnitude is exactly 2^(bits - 1)
+                    let length = bit_length(&magnitude);
+                    let in_range = length < bits
+                        || (negative
+                            && length == bits
+                            && is_power_of_two(&magnitude));
+                    if !in_range {
+                        return Err(Error::IntegerRange(
+                            kind.to_owned(),
+                        ));
+                    }
+                    if negative {
+                        Ok(negate(magnitude))
+                    } else {
+                        Ok(magnitude)
+                    }
+                } else {
+                    Err(Error::UnknownType(kind.to_owned()))
+                }
+            }
+        }
+    }
+}
+
+/// Type name with any array suffixes removed.
+fn base_type(kind: &str) -> &str {
+    match kind.find('[') {
+        Some(index) => &kind[..index],
+        None => kind,
+    }
+}
+
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    let value = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    Ok(hex::decode(value)?)
+}
+
+fn parse_size(
+    kind: &str,
+    size: &str,
+    min: usize,
+    max: usize,
+) -> Result<usize> {
+    match size.parse::<usize>() {
+        Ok(size) if (min..=max).contains(&size) => Ok(size),
+        _ => Err(Error::UnknownType(kind.to_owned())),
+    }
+}
+
+fn parse_bits(kind: &str, bits: &str) -> Result<usize> {
+    let bits = parse_size(kind, bits, 8, 256)?;
+    if bits % 8 != 0 {
+        return Err(Error::UnknownType(kind.to_owned()));
+    }
+    Ok(bits)
+}
+
+/// Parse an integer from a JSON number, decimal string or
+/// hex string into a sign and 256-bit big-endian magnitude.
+fn parse_integer(
+    kind: &str,
+    value: &Value,
+) -> Result<(bool, [u8; 32])> {
+    let invalid = || Error::InvalidValue(kind.to_owned());
+    let range = || Error::IntegerRange(kind.to_owned());
+    let text = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.trim().to_owned(),
+        _ => return Err(invalid()),
+    };
+
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.as_str()),
+    };
+
+    let mut magnitude = [0u8; 32];
+    if let Some(digits) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        let digits = if digits.len() % 2 == 1 {
+            format!("0{}", digits)
+        } else {
+            digits.to_owned()
+        };
+        let bytes = hex::decode(digits)?;
+        let bytes = match bytes.iter().position(|b| *b != 0) {
+            Some(index) => &bytes[index..],
+            None => &[],
+        };
+        if bytes.len() > 32 {
+            return Err(range());
+        }
+        magnitude[32 - bytes.len()..].copy_from_slice(bytes);
+    } else {
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+        for digit in digits.chars() {
+            let digit = digit.to_digit(10).ok_or_else(invalid)?;
+            // Multiply by ten and add the digit
+            let mut carry = digit;
+            for byte in magnitude.iter_mut().rev() {
+                let product = *byte as u32 * 10 + carry;
+                *byte = product as u8;
+                carry = product >> 8;
+            }
+            if carry != 0 {
+                return Err(range());
+            }
+        }
+    }
+    Ok((negative, magnitude))
+}
+
+/// Number of bits required to represent a big-endian integer.
+fn bit_length(value: &[u8; 32]) -> usize {
+    match value.iter().position(|b| *b != 0) {
+        Some(index) => {
+            (32 - index) * 8 - value[index].leading_zeros() as usize
+        }
+        None => 0,
+    }
+}
+
+fn is_power_of_two(value: &[u8; 32]) -> bool {
+    value.iter().map(|b| b.count_ones()).sum::<u32>() == 1
+}
+
+/// Two's complement negation of a 256-bit integer.
+fn negate(value: [u8; 32]) -> [u8; 32] {
+    let mut negated = [0u8; 32];
+    let mut carry = 1u16;
+    for (i, byte) in value.iter().enumerate().rev() {
+        let sum = (!*byte) as u16 + carry;
+        negated[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    negated
+}
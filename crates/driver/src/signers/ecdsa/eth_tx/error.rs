@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+/// Errors generated decoding transactions.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated when RLP data is malformed.
+    #[error("invalid RLP encoding")]
+    InvalidRlp,
+
+    /// Error generated when a transaction type is
+    /// not supported.
+    #[error("transaction type {0:#04x} is not supported")]
+    TransactionType(u8),
+
+    /// Error generated when a transaction has the wrong
+    /// number of fields.
+    #[error("expected {0} transaction fields but got {1}")]
+    FieldCount(usize, usize),
+
+    /// Error generated when the chain identifier of an
+    /// unsigned transaction is invalid.
+    #[error("invalid chain identifier")]
+    ChainId,
+
+    /// Error generated when the recipient of a transaction
+    /// is not a valid address.
+    #[error("invalid recipient address")]
+    Address,
+
+    /// Error generated when the value of a transaction
+    /// is not a valid amount.
+    #[error("invalid transaction value")]
+    Value,
+}
@@ -0,0 +1,528 @@
+//! Encode and sign Ethereum transactions.
+//!
+//! Legacy ([EIP-155]), access list ([EIP-2930]) and dynamic
+//! fee ([EIP-1559]) transactions are supported. Create an
+//! [UnsignedTransaction] from the transaction fields or
+//! decode an unsigned payload and sign it with
+//! [EcdsaSigner::sign_transaction].
+//!
+//! Threshold signers sign the
+//! [signing hash](UnsignedTransaction::signing_hash) and
+//! attach the signature using
+//! [UnsignedTransaction::encode_signed]; the result is the
+//! raw transaction ready for `eth_sendRawTransaction`.
+//!
+//! [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+//! [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+//! [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+//! [EcdsaSigner::sign_transaction]: super::EcdsaSigner::sign_transaction
+use crate::recoverable_signature::RecoverableSignature;
+use sha3::{Digest, Keccak256};
+
+mod error;
+
+pub use error::Error;
+
+/// Result type for transaction decoding.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Ethereum address.
+pub type Address = [u8; 20];
+
+/// Offset for recovery values of legacy transactions
+/// without a chain identifier.
+const LEGACY_V_OFFSET: u64 = 27;
+
+/// Offset for RLP strings.
+const STRING_OFFSET: u8 = 0x80;
+
+/// Offset for RLP lists.
+const LIST_OFFSET: u8 = 0xc0;
+
+/// Maximum payload length for a single byte RLP header.
+const SHORT_LEN: usize = 55;
+
+/// Type of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionType {
+    /// Legacy transaction.
+    Legacy,
+    /// EIP-2930 access list transaction.
+    Eip2930,
+    /// EIP-1559 dynamic fee transaction.
+    Eip1559,
+}
+
+impl TransactionType {
+    /// Type prefix of the transaction envelope.
+    ///
+    /// Legacy transactions are not prefixed.
+    pub fn prefix(&self) -> Option<u8> {
+        match self {
+            Self::Legacy => None,
+            Self::Eip2930 => Some(0x01),
+            Self::Eip1559 => Some(0x02),
+        }
+    }
+
+    /// Index of the recipient field; the value
+    /// field follows the recipient.
+    fn recipient_index(&self) -> usize {
+        match self {
+            Self::Legacy => 3,
+            Self::Eip2930 => 4,
+            Self::Eip1559 => 5,
+        }
+    }
+}
+
+/// Entry in a transaction access list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessListItem {
+    /// Address of the account.
+    pub address: Address,
+    /// Storage keys of the account.
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+/// Legacy transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegacyTransaction {
+    /// Chain identifier; when set the transaction is
+    /// replay protected according to EIP-155.
+    pub chain_id: Option<u64>,
+    /// Nonce of the sender.
+    pub nonce: u64,
+    /// Gas price in wei.
+    pub gas_price: u128,
+    /// Gas limit.
+    pub gas_limit: u64,
+    /// Recipient; contract creation when not set.
+    pub to: Option<Address>,
+    /// Value in wei.
+    pub value: u128,
+    /// Call data.
+    pub data: Vec<u8>,
+}
+
+/// EIP-2930 access list transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip2930Transaction {
+    /// Chain identifier.
+    pub chain_id: u64,
+    /// Nonce of the sender.
+    pub nonce: u64,
+    /// Gas price in wei.
+    pub gas_price: u128,
+    /// Gas limit.
+    pub gas_limit: u64,
+    /// Recipient; contract creation when not set.
+    pub to: Option<Address>,
+    /// Value in wei.
+    pub value: u128,
+    /// Call data.
+    pub data: Vec<u8>,
+    /// Access list.
+    pub access_list: Vec<AccessListItem>,
+}
+
+/// EIP-1559 dynamic fee transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Eip1559Transaction {
+    /// Chain identifier.
+    pub chain_id: u64,
+    /// Nonce of the sender.
+    pub nonce: u64,
+    /// Maximum priority fee per gas in wei.
+    pub max_priority_fee_per_gas: u128,
+    /// Maximum fee per gas in wei.
+    pub max_fee_per_gas: u128,
+    /// Gas limit.
+    pub gas_limit: u64,
+    /// Recipient; contract creation when not set.
+    pub to: Option<Address>,
+    /// Value in wei.
+    pub value: u128,
+    /// Call data.
+    pub data: Vec<u8>,
+    /// Access list.
+    pub access_list: Vec<AccessListItem>,
+}
+
+/// Transaction ready to be signed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    transaction_type: TransactionType,
+    chain_id: Option<u64>,
+    to: Option<Address>,
+    value: u128,
+    /// Encoded fields without the signature or the
+    /// EIP-155 placeholder fields.
+    fields: Vec<Vec<u8>>,
+}
+
+impl UnsignedTransaction {
+    /// Decode an unsigned transaction payload.
+    ///
+    /// Typed transactions must include the type prefix;
+    /// legacy transactions may include the EIP-155 chain
+    /// identifier and placeholder fields.
+    pub fn decode(raw: &[u8]) -> Result<Self> {
+        let (transaction_type, body) = match raw.first() {
+            Some(0x01) => (TransactionType::Eip2930, &raw[1..]),
+            Some(0x02) => (TransactionType::Eip1559, &raw[1..]),
+            Some(&prefix) if prefix >= LIST_OFFSET => {
+                (TransactionType::Legacy, raw)
+            }
+            Some(&prefix) => {
+                return Err(Error::TransactionType(prefix))
+            }
+            None => return Err(Error::InvalidRlp),
+        };
+
+        let mut fields = decode_list(body)?;
+        let chain_id = match transaction_type {
+            TransactionType::Legacy => match fields.len() {
+                6 => None,
+                9 => {
+                    let trailer = fields.split_off(6);
+                    let empty = encode_bytes(&[]);
+                    if trailer[1] != empty || trailer[2] != empty {
+                        return Err(Error::ChainId);
+                    }
+                    Some(decode_u64(&trailer[0])?)
+                }
+                count => return Err(Error::FieldCount(9, count)),
+            },
+            TransactionType::Eip2930 => {
+                expect_fields(&fields, 8)?;
+                Some(decode_u64(&fields[0])?)
+            }
+            TransactionType::Eip1559 => {
+                expect_fields(&fields, 9)?;
+                Some(decode_u64(&fields[0])?)
+            }
+        };
+
+        let index = transaction_type.recipient_index();
+        let to = decode_address(&fields[index])?;
+        let value = decode_u128(&fields[index + 1])?;
+
+        Ok(Self {
+            transaction_type,
+            chain_id,
+            to,
+            value,
+            fields,
+        })
+    }
+
+    /// Type of the transaction.
+    pub fn transaction_type(&self) -> TransactionType {
+        self.transaction_type
+    }
+
+    /// Chain identifier of the transaction.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    /// Recipient of the transaction.
+    ///
+    /// For contract calls this is the contract address and
+    /// `None` when the transaction creates a contract.
+    pub fn to(&self) -> Option<Address> {
+        self.to
+    }
+
+    /// Value transferred in wei.
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    /// Encode the payload that is hashed for signing.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut fields = self.fields.clone();
+        if let (TransactionType::Legacy, Some(chain_id)) =
+            (self.transaction_type, self.chain_id)
+        {
+            fields.push(encode_uint(chain_id as u128));
+            fields.push(encode_bytes(&[]));
+            fields.push(encode_bytes(&[]));
+        }
+        self.envelope(encode_list(&fields))
+    }
+
+    /// Keccak256 hash of the payload to sign.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Keccak256::digest(self.encode()).into()
+    }
+
+    /// Encode the signed transaction.
+    ///
+    /// The signature is normalized to a low `s` value
+    /// as required by Ethereum.
+    pub fn encode_signed(
+        &self,
+        signature: &RecoverableSignature,
+    ) -> crate::Result<Vec<u8>> {
+        let signature = signature.normalize_s()?;
+        let recovery_id = signature.recovery_id as u64;
+        let v = match (self.transaction_type, self.chain_id) {
+            (TransactionType::Legacy, Some(chain_id)) => {
+                signature.to_eth_v(chain_id)
+            }
+            (TransactionType::Legacy, None) => {
+                recovery_id + LEGACY_V_OFFSET
+            }
+            _ => recovery_id,
+        };
+        let (r, s) = signature.bytes.split_at(32);
+
+        let mut fields = self.fields.clone();
+        fields.push(encode_uint(v as u128));
+        fields.push(encode_bytes(trim_leading_zeros(r)));
+        fields.push(encode_bytes(trim_leading_zeros(s)));
+        Ok(self.envelope(encode_list(&fields)))
+    }
+
+    fn envelope(&self, payload: Vec<u8>) -> Vec<u8> {
+        match self.transaction_type.prefix() {
+            Some(prefix) => {
+                let mut buffer =
+                    Vec::with_capacity(payload.len() + 1);
+                buffer.push(prefix);
+                buffer.extend_from_slice(&payload);
+                buffer
+            }
+            None => payload,
+        }
+    }
+}
+
+impl From<LegacyTransaction> for UnsignedTransaction {
+    fn from(value: LegacyTransaction) -> Self {
+        Self {
+            transaction_type: TransactionType::Legacy,
+            chain_id: value.chain_id,
+            to: value.to,
+            value: value.value,
+            fields: vec![
+                encode_uint(value.nonce as u128),
+                encode_uint(value.gas_price),
+                encode_uint(value.gas_limit as u128),
+                encode_address(&value.to),
+                encode_uint(value.value),
+                encode_bytes(&value.data),
+            ],
+        }
+    }
+}
+
+impl From<Eip2930Transaction> for UnsignedTransaction {
+    fn from(value: Eip2930Transaction) -> Self {
+        Self {
+            transaction_type: TransactionType::Eip2930,
+            chain_id: Some(value.chain_id),
+            to: value.to,
+            value: value.value,
+            fields: vec![
+                encode_uint(value.chain_id as u128),
+                encode_uint(value.nonce as u128),
+                encode_uint(value.gas_price),
+                encode_uint(value.gas_limit as u128),
+                encode_address(&value.to),
+                encode_uint(value.value),
+                encode_bytes(&value.data),
+                encode_access_list(&value.access_list),
+            ],
+        }
+    }
+}
+
+impl From<Eip1559Transaction> for UnsignedTransaction {
+    fn from(value: Eip1559Transaction) -> Self {
+        Self {
+            transaction_type: TransactionType::Eip1559,
+            chain_id: Some(value.chain_id),
+            to: value.to,
+            value: value.value,
+            fields: vec![
+                encode_uint(value.chain_id as u128),
+                encode_uint(value.nonce as u128),
+                encode_uint(value.max_priority_fee_per_gas),
+                encode_uint(value.max_fee_per_gas),
+                encode_uint(value.gas_limit as u128),
+                encode_address(&value.to),
+                encode_uint(value.value),
+                encode_bytes(&value.data),
+                encode_access_list(&value.access_list),
+            ],
+        }
+    }
+}
+
+fn expect_fields(fields: &[Vec<u8>], expected: usize) -> Result<()> {
+    if fields.len() != expected {
+        return Err(Error::FieldCount(expected, fields.len()));
+    }
+    Ok(())
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start =
+        bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn encode_header(len: usize, offset: u8) -> Vec<u8> {
+    if len <= SHORT_LEN {
+        vec![offset + len as u8]
+    } else {
+        let len = len.to_be_bytes();
+        let len = trim_leading_zeros(&len);
+        let mut header =
+            vec![offset + SHORT_LEN as u8 + len.len() as u8];
+        header.extend_from_slice(len);
+        header
+    }
+}
+
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < STRING_OFFSET {
+        return bytes.to_vec();
+    }
+    let mut buffer = encode_header(bytes.len(), STRING_OFFSET);
+    buffer.extend_from_slice(bytes);
+    buffer
+}
+
+fn encode_uint(value: u128) -> Vec<u8> {
+    encode_bytes(trim_leading_zeros(&value.to_be_bytes()))
+}
+
+fn encode_address(address: &Option<Address>) -> Vec<u8> {
+    match address {
+        Some(address) => encode_bytes(address),
+        None => encode_bytes(&[]),
+    }
+}
+
+fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let len = items.iter().map(|item| item.len()).sum();
+    let mut buffer = encode_header(len, LIST_OFFSET);
+    for item in items {
+        buffer.extend_from_slice(item);
+    }
+    buffer
+}
+
+fn encode_access_list(access_list: &[AccessListItem]) -> Vec<u8> {
+    let items = access_list
+        .iter()
+        .map(|item| {
+            let storage_keys = item
+                .storage_keys
+                .iter()
+                .map(|key| encode_bytes(key))
+                .collect::<Vec<_>>();
+            encode_list(&[
+                encode_bytes(&item.address),
+                encode_list(&storage_keys),
+            ])
+        })
+        .collect::<Vec<_>>();
+    encode_list(&items)
+}
+
+/// Decode the header of an item returning whether the item
+/// is a list, the offset of the payload and the payload length.
+fn decode_header(data: &[u8]) -> Result<(bool, usize, usize)> {
+    let prefix = *data.first().ok_or(Error::InvalidRlp)?;
+    let long_string = STRING_OFFSET + SHORT_LEN as u8;
+    let long_list = LIST_OFFSET + SHORT_LEN as u8;
+    let (is_list, offset, len) = if prefix < STRING_OFFSET {
+        (false, 0, 1)
+    } else if prefix <= long_string {
+        (false, 1, (prefix - STRING_OFFSET) as usize)
+    } else if prefix < LIST_OFFSET {
+        let size = (prefix - long_string) as usize;
+        (false, 1 + size, decode_len(&data[1..], size)?)
+    } else if prefix <= long_list {
+        (true, 1, (prefix - LIST_OFFSET) as usize)
+    } else {
+        let size = (prefix - long_list) as usize;
+        (true, 1 + size, decode_len(&data[1..], size)?)
+    };
+    let end = offset.checked_add(len).ok_or(Error::InvalidRlp)?;
+    if data.len() < end {
+        return Err(Error::InvalidRlp);
+    }
+    Ok((is_list, offset, len))
+}
+
+fn decode_len(data: &[u8], size: usize) -> Result<usize> {
+    if size > std::mem::size_of::<usize>() || data.len() < size {
+        return Err(Error::InvalidRlp);
+    }
+    Ok(data[..size]
+        .iter()
+        .fold(0usize, |len, b| (len << 8) | *b as usize))
+}
+
+/// Decode a list into the encoded items of the list.
+fn decode_list(data: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let (is_list, offset, len) = decode_header(data)?;
+    if !is_list || offset + len != data.len() {
+        return Err(Error::InvalidRlp);
+    }
+    let mut items = Vec::new();
+    let mut payload = &data[offset..];
+    while !payload.is_empty() {
+        let (_, offset, len) = decode_header(payload)?;
+        let (item, remainder) = payload.split_at(offset + len);
+        items.push(item.to_vec());
+        payload = remainder;
+    }
+    Ok(items)
+}
+
+fn decode_u64(item: &[u8]) -> Result<u64> {
+    let (is_list, offset, len) = decode_header(item)?;
+    let bytes = &item[offset..offset + len];
+    if is_list
+        || len > 8
+        || bytes.first() == Some(&0)
+        || offset + len != item.len()
+    {
+        return Err(Error::ChainId);
+    }
+    Ok(bytes.iter().fold(0u64, |value, b| (value << 8) | *b as u64))
+}
+
+/// Decode the payload of a string item.
+fn decode_string(item: &[u8]) -> Option<&[u8]> {
+    let (is_list, offset, len) = decode_header(item).ok()?;
+    if is_list || offset + len != item.len() {
+        return None;
+    }
+    Some(&item[offset..offset + len])
+}
+
+fn decode_address(item: &[u8]) -> Result<Option<Address>> {
+    let bytes = decode_string(item).ok_or(Error::Address)?;
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(bytes.try_into().map_err(|_| Error::Address)?))
+}
+
+fn decode_u128(item: &[u8]) -> Result<u128> {
+    let bytes = decode_string(item).ok_or(Error::Value)?;
+    if bytes.len() > 16 || bytes.first() == Some(&0) {
+        return Err(Error::Value);
+    }
+    Ok(bytes
+        .iter()
+        .fold(0u128, |value, b| (value << 8) | *b as u128))
+}
@@ -1,5 +1,18 @@
 //! Generate ECDSA signatures compatible with Ethereum.
-use crate::{recoverable_signature::RecoverableSignature, Result};
+//!
+//! Signing keys may be derived deterministically from a
+//! [BIP-39] mnemonic using [BIP-32] derivation paths; use
+//! [EcdsaSigner::bip44_path] to create paths for accounts
+//! according to [BIP-44].
+//!
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+//! [BIP-44]: https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+use crate::{
+    recoverable_signature::RecoverableSignature,
+    signers::SecretSigningKey, Result,
+};
+use bip32::{ChildNumber, XPrv};
 use k256::ecdsa::{
     signature::{hazmat::PrehashVerifier, Signer, Verifier},
     RecoveryId, SigningKey, VerifyingKey,
@@ -8,16 +21,29 @@ use rand::rngs::OsRng;
 use sha3::{Digest, Keccak256};
 use std::borrow::Cow;
 
+pub mod eip712;
+pub mod eth_tx;
+
+pub use bip32::DerivationPath;
+pub use bip39::{Language, Mnemonic};
 pub use k256::ecdsa::Signature;
 
+/// Purpose for BIP-44 derivation paths.
+const BIP44_PURPOSE: u32 = 44;
+
+/// Registered coin type for Ethereum in BIP-44 derivation paths.
+pub const ETHEREUM_COIN_TYPE: u32 = 60;
+
 /// Create a signer for ECDSA signatures.
 pub struct EcdsaSigner<'a> {
-    signing_key: Cow<'a, SigningKey>,
+    signing_key: Cow<'a, SecretSigningKey<SigningKey>>,
 }
 
 impl<'a> EcdsaSigner<'a> {
     /// Create a new signer.
-    pub fn new(signing_key: Cow<'a, SigningKey>) -> Self {
+    pub fn new(
+        signing_key: Cow<'a, SecretSigningKey<SigningKey>>,
+    ) -> Self {
         Self { signing_key }
     }
 
@@ -31,6 +57,61 @@ impl<'a> EcdsaSigner<'a> {
         SigningKey::random(&mut OsRng)
     }
 
+    /// Generate a random mnemonic with the given number of words.
+    ///
+    /// The word count must be 12, 15, 18, 21 or 24.
+    pub fn generate_mnemonic(word_count: usize) -> Result<Mnemonic> {
+        Ok(Mnemonic::generate(word_count)?)
+    }
+
+    /// Import a mnemonic phrase.
+    ///
+    /// The phrase is normalized and the checksum is verified.
+    pub fn parse_mnemonic(phrase: &str) -> Result<Mnemonic> {
+        Ok(Mnemonic::parse(phrase)?)
+    }
+
+    /// Derive a signing key from a seed and derivation path.
+    pub fn derive_from_seed(
+        seed: &[u8],
+        path: &DerivationPath,
+    ) -> Result<SigningKey> {
+        let xprv = XPrv::derive_from_path(seed, path)?;
+        Ok(xprv.private_key().clone())
+    }
+
+    /// Derive a signing key from a mnemonic and derivation path.
+    ///
+    /// The passphrase is combined with the mnemonic to create
+    /// the seed; use an empty string when there is no passphrase.
+    pub fn derive_from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        path: &DerivationPath,
+    ) -> Result<SigningKey> {
+        let seed = mnemonic.to_seed(passphrase);
+        Self::derive_from_seed(&seed, path)
+    }
+
+    /// Create a BIP-44 derivation path.
+    ///
+    /// The path has the form
+    /// `m/44'/coin_type'/account'/change/address_index`.
+    pub fn bip44_path(
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        address_index: u32,
+    ) -> Result<DerivationPath> {
+        let mut path = DerivationPath::default();
+        path.push(ChildNumber::new(BIP44_PURPOSE, true)?);
+        path.push(ChildNumber::new(coin_type, true)?);
+        path.push(ChildNumber::new(account, true)?);
+        path.push(ChildNumber::new(change, false)?);
+        path.push(ChildNumber::new(address_index, false)?);
+        Ok(path)
+    }
+
     /// Sign the given message, hashing it with the curve’s
     /// default digest function, and returning a signature
     /// and recovery ID.
@@ -97,6 +178,30 @@ impl<'a> EcdsaSigner<'a> {
         Ok(self.signing_key.sign_digest_recoverable(digest)?)
     }
 
+    /// Sign [EIP-712] typed data, returning a signature
+    /// and recovery ID.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub fn sign_typed_data(
+        &self,
+        data: &eip712::TypedData,
+    ) -> Result<(Signature, RecoveryId)> {
+        let hash = data.signing_hash()?;
+        self.sign_prehash_recoverable(&hash)
+    }
+
+    /// Sign an Ethereum transaction, returning the raw signed
+    /// transaction ready to be broadcast.
+    pub fn sign_transaction(
+        &self,
+        transaction: &eth_tx::UnsignedTransaction,
+    ) -> Result<Vec<u8>> {
+        let hash = transaction.signing_hash();
+        let signature: RecoverableSignature =
+            self.sign_prehash_recoverable(&hash)?.into();
+        transaction.encode_signed(&signature)
+    }
+
     /// Recover the public key from a signature and recovery identifier.
     pub fn recover(
         message: &[u8],
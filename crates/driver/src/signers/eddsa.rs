@@ -1,21 +1,50 @@
 //! Generate EdDSA signatures compatible with Solana.
-use crate::Result;
+//!
+//! In addition to pure Ed25519 the prehashed (Ed25519ph) and
+//! context (Ed25519ctx) variants from [RFC 8032] are supported
+//! for protocols that require domain separation.
+//!
+//! Signing keys may be derived from a seed using
+//! [SLIP-0010] hardened derivation.
+//!
+//! [RFC 8032]: https://www.rfc-editor.org/rfc/rfc8032
+//! [SLIP-0010]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+use crate::{signers::SecretSigningKey, Error, Result};
+use curve25519_dalek::{
+    edwards::CompressedEdwardsY, EdwardsPoint, Scalar,
+};
 use ed25519::signature::{Signer, Verifier};
 use ed25519_dalek::{SecretKey, SigningKey, VerifyingKey};
+use hmac::{Hmac, Mac};
 use rand::rngs::OsRng;
+use sha2::{Digest, Sha512};
 use std::borrow::Cow;
 
 pub use ed25519_dalek::Signature;
 
+/// Registered coin type for Solana in derivation paths.
+pub const SOLANA_COIN_TYPE: u32 = 501;
+
+/// Domain separation prefix for Ed25519ctx and Ed25519ph.
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+/// HMAC key for the SLIP-0010 master key.
+const SLIP10_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// Offset for hardened child indices.
+const HARDENED: u32 = 0x8000_0000;
+
 /// Create a signer for EdDSA signatures.
 pub struct EddsaSigner<'a> {
-    signing_key: Cow<'a, SigningKey>,
+    signing_key: Cow<'a, SecretSigningKey<SigningKey>>,
     verifying_key: VerifyingKey,
 }
 
 impl<'a> EddsaSigner<'a> {
     /// Create a new signer.
-    pub fn new(signing_key: Cow<'a, SigningKey>) -> Self {
+    pub fn new(
+        signing_key: Cow<'a, SecretSigningKey<SigningKey>>,
+    ) -> Self {
         let verifying_key = signing_key.verifying_key();
         Self {
             signing_key,
@@ -36,11 +65,147 @@ impl<'a> EddsaSigner<'a> {
     /// Sign a message.
     pub fn sign<B: AsRef<[u8]>>(&self, message: B) -> Signature {
         let signer = DalekSigner {
-            signing_key: self.signing_key.as_ref(),
+            signing_key: self.signing_key.expose_secret(),
         };
         signer.sign(message)
     }
 
+    /// Derive a signing key from a seed using SLIP-0010.
+    ///
+    /// Every component of the path must be hardened,
+    /// for example `m/44'/501'/0'/0'`.
+    pub fn derive_from_seed(
+        seed: &[u8],
+        path: &str,
+    ) -> Result<SigningKey> {
+        let invalid = || Error::DerivationPath(path.to_owned());
+        let mut components = path.split('/');
+        if components.next() != Some("m") {
+            return Err(invalid());
+        }
+
+        let (mut key, mut chain_code) =
+            slip10(SLIP10_SEED_KEY, &[seed]);
+        for component in components {
+            let index = component
+                .strip_suffix('\'')
+                .or_else(|| component.strip_suffix('h'))
+                .ok_or_else(invalid)?
+                .parse::<u32>()
+                .map_err(|_| invalid())?;
+            if index >= HARDENED {
+                return Err(invalid());
+            }
+            (key, chain_code) = slip10(
+                &chain_code,
+                &[&[0], &key, &(index + HARDENED).to_be_bytes()],
+            );
+        }
+        Ok(SigningKey::from_bytes(&key))
+    }
+
+    /// Sign a message using Ed25519ph.
+    ///
+    /// The message is hashed with SHA-512 before signing; the
+    /// optional context must be at most 255 bytes.
+    pub fn sign_prehashed<B: AsRef<[u8]>>(
+        &self,
+        message: B,
+        context: Option<&[u8]>,
+    ) -> Result<Signature> {
+        let prehash = Sha512::new_with_prefix(message.as_ref());
+        Ok(self
+            .signing_key
+            .sign_prehashed(prehash, context)
+            .map_err(Box::from)?)
+    }
+
+    /// Verify an Ed25519ph signature.
+    pub fn verify_prehashed<B: AsRef<[u8]>>(
+        &self,
+        message: B,
+        context: Option<&[u8]>,
+        signature: &Signature,
+    ) -> Result<()> {
+        let prehash = Sha512::new_with_prefix(message.as_ref());
+        Ok(self
+            .verifying_key
+            .verify_prehashed(prehash, context, signature)
+            .map_err(Box::from)?)
+    }
+
+    /// Sign a message using Ed25519ctx.
+    ///
+    /// The context must be between 1 and 255 bytes.
+    pub fn sign_with_context<B: AsRef<[u8]>>(
+        &self,
+        message: B,
+        context: &[u8],
+    ) -> Result<Signature> {
+        // ed25519-dalek does not implement Ed25519ctx so we
+        // follow RFC 8032 section 5.1.6 using dom2(0, context)
+        let dom = dom2(context)?;
+        let message = message.as_ref();
+        let hash = Sha512::digest(self.signing_key.to_bytes());
+        let mut lower = [0u8; 32];
+        lower.copy_from_slice(&hash[..32]);
+        lower[0] &= 248;
+        lower[31] &= 127;
+        lower[31] |= 64;
+        let secret = Scalar::from_bytes_mod_order(lower);
+
+        let r = hash_to_scalar(&[&dom, &hash[32..], message]);
+        let big_r = EdwardsPoint::mul_base(&r).compress();
+        let k = hash_to_scalar(&[
+            &dom,
+            big_r.as_bytes(),
+            self.verifying_key.as_bytes(),
+            message,
+        ]);
+        let s = r + k * secret;
+
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(big_r.as_bytes());
+        bytes[32..].copy_from_slice(s.as_bytes());
+        Ok(Signature::from_bytes(&bytes))
+    }
+
+    /// Verify an Ed25519ctx signature.
+    pub fn verify_with_context<B: AsRef<[u8]>>(
+        &self,
+        message: B,
+        context: &[u8],
+        signature: &Signature,
+    ) -> Result<()> {
+        let dom = dom2(context)?;
+        let invalid = || Error::from(Box::new(ed25519::Error::new()));
+        let s: Option<Scalar> =
+            Scalar::from_canonical_bytes(*signature.s_bytes()).into();
+        let s = s.ok_or_else(invalid)?;
+        let public_key =
+            CompressedEdwardsY(self.verifying_key.to_bytes())
+                .decompress()
+                .ok_or_else(invalid)?;
+
+        let k = hash_to_scalar(&[
+            &dom,
+            signature.r_bytes(),
+            self.verifying_key.as_bytes(),
+            message.as_ref(),
+        ]);
+        // R = [S]B - [k]A
+        let big_r = EdwardsPoint::vartime_double_scalar_mul_basepoint(
+            &k,
+            &-public_key,
+            &s,
+        );
+        if big_r.compress().as_bytes() == signature.r_bytes() {
+            Ok(())
+        } else {
+            Err(invalid())
+        }
+    }
+
     /// Verifying key for this signer.
     pub fn verifying_key(&self) -> &VerifyingKey {
         &self.verifying_key
@@ -97,3 +262,43 @@ where
             .map_err(Box::from)?)
     }
 }
+
+/// Domain separation prefix for Ed25519ctx.
+fn dom2(context: &[u8]) -> Result<Vec<u8>> {
+    if context.is_empty() || context.len() > 255 {
+        return Err(Error::ContextLength(context.len()));
+    }
+    let mut dom =
+        Vec::with_capacity(DOM2_PREFIX.len() + 2 + context.len());
+    dom.extend_from_slice(DOM2_PREFIX);
+    dom.push(0);
+    dom.push(context.len() as u8);
+    dom.extend_from_slice(context);
+    Ok(dom)
+}
+
+/// Reduce the SHA-512 hash of the parts to a scalar.
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+/// Compute a SLIP-0010 key and chain code.
+fn slip10(key: &[u8], parts: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key)
+        .expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&output[..32]);
+    chain_code.copy_from_slice(&output[32..]);
+    (key, chain_code)
+}
@@ -0,0 +1,34 @@
+//! [BIP-39] mnemonics used to derive signing keys.
+//!
+//! The seed for a mnemonic is passed to the `derive_from_seed`
+//! functions for each signer; ECDSA and Schnorr keys use
+//! [BIP-32] derivation and EdDSA keys use [SLIP-0010].
+//!
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [BIP-39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+//! [SLIP-0010]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+use crate::Result;
+
+pub use bip39::{Language, Mnemonic};
+
+/// Generate a random mnemonic with the given number of words.
+///
+/// The word count must be 12, 15, 18, 21 or 24.
+pub fn generate(word_count: usize) -> Result<Mnemonic> {
+    Ok(Mnemonic::generate(word_count)?)
+}
+
+/// Import a mnemonic phrase.
+///
+/// The phrase is normalized and the checksum is verified.
+pub fn parse(phrase: &str) -> Result<Mnemonic> {
+    Ok(Mnemonic::parse(phrase)?)
+}
+
+/// Compute the seed for a mnemonic phrase.
+///
+/// The passphrase is combined with the mnemonic to create
+/// the seed; use an empty string when there is no passphrase.
+pub fn to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64]> {
+    Ok(parse(phrase)?.to_seed(passphrase))
+}
@@ -1,4 +1,12 @@
 //! Single party signers.
+//!
+//! Each signer can also be used via the [Signer] trait and
+//! [DynSigner] so keys for different algorithms can be
+//! handled polymorphically; the [AsyncSigner] trait is also
+//! implemented by the threshold signers in the client.
+//!
+//! Signers hold their private keys in a [SecretSigningKey] so
+//! key material is wiped from memory when it is dropped.
 
 #[cfg(feature = "ecdsa")]
 pub mod ecdsa;
@@ -8,3 +16,41 @@ pub mod eddsa;
 
 #[cfg(feature = "schnorr")]
 pub mod schnorr;
+
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+pub mod mnemonic;
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+mod dynamic;
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+mod secret;
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+pub use dynamic::{Algorithm, AsyncSigner, DynSigner, Signer};
+
+#[cfg(any(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+pub use secret::SecretSigningKey;
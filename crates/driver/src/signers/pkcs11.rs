@@ -0,0 +1,240 @@
+//! Signer backed by a PKCS#11 token such as a YubiHSM2.
+//!
+//! Private keys remain on the token and signing operations
+//! are performed by the token so private key material is
+//! never loaded into process memory.
+//!
+//! Keys are located using the `CKA_LABEL` attribute; the
+//! private key and public key objects must share the label.
+//! ECDSA keys must use the secp256k1 curve.
+use crate::{
+    signers::{Algorithm, Signer},
+    Error, Result,
+};
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use ed25519::signature::Verifier;
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use sha2::{Digest, Sha256};
+use std::{path::PathBuf, sync::Mutex};
+
+/// Options for opening a PKCS#11 signer.
+#[derive(Debug, Clone)]
+pub struct Pkcs11Options {
+    /// Path to the PKCS#11 module, for example
+    /// `yubihsm_pkcs11.so`.
+    pub module: PathBuf,
+    /// Label of the token; when not set the first slot
+    /// with a token is used.
+    pub token_label: Option<String>,
+    /// User PIN for the token.
+    ///
+    /// For a YubiHSM2 this is the authentication key
+    /// identifier followed by the password, for example
+    /// `0001password`.
+    pub pin: String,
+    /// Label of the key.
+    pub key_label: String,
+    /// Signature algorithm for the key.
+    pub algorithm: Algorithm,
+}
+
+/// Key on a token.
+enum TokenKey {
+    Ecdsa(k256::ecdsa::VerifyingKey),
+    Eddsa(ed25519_dalek::VerifyingKey),
+}
+
+/// Signer that delegates signing to a PKCS#11 token.
+pub struct Pkcs11Signer {
+    session: Mutex<Session>,
+    private_key: ObjectHandle,
+    public_key: TokenKey,
+}
+
+impl Pkcs11Signer {
+    /// Open a session with the token and locate the key.
+    pub fn open(options: Pkcs11Options) -> Result<Self> {
+        if !matches!(
+            options.algorithm,
+            Algorithm::Ecdsa | Algorithm::Eddsa
+        ) {
+            return Err(Error::Pkcs11Algorithm(options.algorithm));
+        }
+
+        let pkcs11 = Pkcs11::new(&options.module)?;
+        pkcs11.initialize(CInitializeArgs::OsThreads)?;
+
+        let mut slot = None;
+        for candidate in pkcs11.get_slots_with_token()? {
+            match &options.token_label {
+                Some(label) => {
+                    let info = pkcs11.get_token_info(candidate)?;
+                    if info.label() == label {
+                        slot = Some(candidate);
+                        break;
+                    }
+                }
+                None => {
+                    slot = Some(candidate);
+                    break;
+                }
+            }
+        }
+        let slot = slot.ok_or_else(|| {
+            Error::Pkcs11TokenNotFound(
+                options.token_label.clone().unwrap_or_default(),
+            )
+        })?;
+
+        let session = pkcs11.open_ro_session(slot)?;
+        session.login(
+            UserType::User,
+            Some(&AuthPin::new(options.pin.clone())),
+        )?;
+
+        let private_key = find_key(
+            &session,
+            ObjectClass::PRIVATE_KEY,
+            &options.key_label,
+        )?;
+        let public_key_handle = find_key(
+            &session,
+            ObjectClass::PUBLIC_KEY,
+            &options.key_label,
+        )?;
+        let point = session
+            .get_attributes(
+                public_key_handle,
+                &[AttributeType::EcPoint],
+            )?
+            .into_iter()
+            .find_map(|attribute| match attribute {
+                Attribute::EcPoint(point) => Some(point),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::Pkcs11KeyNotFound(options.key_label.clone())
+            })?;
+        let point = unwrap_ec_point(&point);
+
+        let public_key = match options.algorithm {
+            Algorithm::Ecdsa => TokenKey::Ecdsa(
+                k256::ecdsa::VerifyingKey::from_sec1_bytes(point)?,
+            ),
+            _ => TokenKey::Eddsa(
+                ed25519_dalek::VerifyingKey::try_from(point)
+                    .map_err(Box::from)?,
+            ),
+        };
+
+        Ok(Self {
+            session: Mutex::new(session),
+            private_key,
+            public_key,
+        })
+    }
+}
+
+impl Signer for Pkcs11Signer {
+    fn algorithm(&self) -> Algorithm {
+        match &self.public_key {
+            TokenKey::Ecdsa(_) => Algorithm::Ecdsa,
+            TokenKey::Eddsa(_) => Algorithm::Eddsa,
+        }
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        match &self.public_key {
+            TokenKey::Ecdsa(key) => key.to_sec1_bytes().to_vec(),
+            TokenKey::Eddsa(key) => key.to_bytes().to_vec(),
+        }
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let session = self.session.lock().unwrap();
+        match &self.public_key {
+            TokenKey::Ecdsa(_) => {
+                // CKM_ECDSA signs a digest so hash the message
+                // in the same way as the ECDSA signer
+                let digest = Sha256::digest(message);
+                let signature = session.sign(
+                    &Mechanism::Ecdsa,
+                    self.private_key,
+                    &digest,
+                )?;
+                let signature =
+                    k256::ecdsa::Signature::from_slice(&signature)?;
+                let signature =
+                    signature.normalize_s().unwrap_or(signature);
+                Ok(signature.to_vec())
+            }
+            TokenKey::Eddsa(_) => Ok(session.sign(
+                &Mechanism::Eddsa,
+                self.private_key,
+                message,
+            )?),
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        match &self.public_key {
+            TokenKey::Ecdsa(key) => {
+                let signature =
+                    k256::ecdsa::Signature::from_slice(signature)?;
+                let digest = Sha256::digest(message);
+                Ok(key.verify_prehash(&digest, &signature)?)
+            }
+            TokenKey::Eddsa(key) => {
+                let signature =
+                    ed25519_dalek::Signature::from_slice(signature)
+                        .map_err(Box::from)?;
+                Ok(key
+                    .verify(message, &signature)
+                    .map_err(Box::from)?)
+            }
+        }
+    }
+}
+
+fn find_key(
+    session: &Session,
+    class: ObjectClass,
+    label: &str,
+) -> Result<ObjectHandle> {
+    let template = [
+        Attribute::Class(class),
+        Attribute::Label(label.as_bytes().to_vec()),
+    ];
+    session
+        .find_objects(&template)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::Pkcs11KeyNotFound(label.to_owned()))
+}
+
+/// Remove the DER octet string wrapper from a `CKA_EC_POINT`.
+///
+/// Some modules return the raw point so the wrapper is
+/// only removed when the length matches.
+fn unwrap_ec_point(point: &[u8]) -> &[u8] {
+    const OCTET_STRING: u8 = 0x04;
+    // Raw uncompressed secp256k1 point
+    if point.len() == 65 {
+        return point;
+    }
+    if point.len() > 2
+        && point[0] == OCTET_STRING
+        && point[1] < 0x80
+        && point[1] as usize == point.len() - 2
+    {
+        &point[2..]
+    } else {
+        point
+    }
+}
@@ -1,23 +1,82 @@
 //! Generate Schnorr signatures compatible with Bitcoin
 //! Taproot (BIP 340).
-use crate::Result;
-use k256::schnorr::{
-    signature::{hazmat::PrehashSigner, Signer, Verifier},
-    SigningKey,
+//!
+//! Signing keys may be derived from a seed using [BIP-32]
+//! derivation paths; use [SchnorrSigner::bip86_path] to create
+//! paths for Taproot accounts according to [BIP-86].
+//!
+//! [BIP-32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [BIP-86]: https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
+use crate::{signers::SecretSigningKey, Result};
+use bip32::{ChildNumber, XPrv};
+use k256::{
+    elliptic_curve::{
+        ops::Reduce, point::DecompressPoint, subtle::Choice, Field,
+        PrimeField,
+    },
+    schnorr::{
+        signature::{self, hazmat::PrehashSigner, Signer, Verifier},
+        SigningKey,
+    },
+    AffinePoint, FieldBytes, ProjectivePoint, Scalar,
 };
-use rand::rngs::OsRng;
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
 use std::borrow::Cow;
 
+pub use bip32::DerivationPath;
 pub use k256::schnorr::{Signature, VerifyingKey};
 
+/// Purpose for BIP-86 derivation paths.
+const BIP86_PURPOSE: u32 = 86;
+
+/// Registered coin type for Bitcoin in derivation paths.
+pub const BITCOIN_COIN_TYPE: u32 = 0;
+
+/// Tag for BIP-340 challenge hashes.
+const CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
+
+/// Tag for BIP-341 taproot tweak hashes.
+const TAP_TWEAK_TAG: &[u8] = b"TapTweak";
+
+/// Auxiliary randomness mixed into the nonce for
+/// BIP-340 signatures.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AuxRand {
+    /// Fresh randomness from the operating system.
+    #[default]
+    Random,
+    /// All zero bytes so signatures are deterministic.
+    Deterministic,
+    /// Randomness provided by the caller.
+    Provided([u8; 32]),
+}
+
+impl AuxRand {
+    /// Bytes for the auxiliary randomness.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        match self {
+            Self::Random => {
+                let mut bytes = [0u8; 32];
+                OsRng.fill_bytes(&mut bytes);
+                bytes
+            }
+            Self::Deterministic => [0u8; 32],
+            Self::Provided(bytes) => *bytes,
+        }
+    }
+}
+
 /// Create a signer for Taproot BIP-340 Schnorr signatures.
 pub struct SchnorrSigner<'a> {
-    signing_key: Cow<'a, SigningKey>,
+    signing_key: Cow<'a, SecretSigningKey<SigningKey>>,
 }
 
 impl<'a> SchnorrSigner<'a> {
     /// Create a new signer.
-    pub fn new(signing_key: Cow<'a, SigningKey>) -> Self {
+    pub fn new(
+        signing_key: Cow<'a, SecretSigningKey<SigningKey>>,
+    ) -> Self {
         Self { signing_key }
     }
 
@@ -31,6 +90,34 @@ impl<'a> SchnorrSigner<'a> {
         SigningKey::random(&mut OsRng)
     }
 
+    /// Derive a signing key from a seed and derivation path.
+    pub fn derive_from_seed(
+        seed: &[u8],
+        path: &DerivationPath,
+    ) -> Result<SigningKey> {
+        let xprv = XPrv::derive_from_path(seed, path)?;
+        Self::from_slice(&xprv.private_key().to_bytes())
+    }
+
+    /// Create a BIP-86 derivation path.
+    ///
+    /// The path has the form
+    /// `m/86'/coin_type'/account'/change/address_index`.
+    pub fn bip86_path(
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        address_index: u32,
+    ) -> Result<DerivationPath> {
+        let mut path = DerivationPath::default();
+        path.push(ChildNumber::new(BIP86_PURPOSE, true)?);
+        path.push(ChildNumber::new(coin_type, true)?);
+        path.push(ChildNumber::new(account, true)?);
+        path.push(ChildNumber::new(change, false)?);
+        path.push(ChildNumber::new(address_index, false)?);
+        Ok(path)
+    }
+
     /// Sign a message.
     pub fn sign(&self, message: &[u8]) -> Signature {
         self.signing_key.sign(message)
@@ -56,6 +143,48 @@ impl<'a> SchnorrSigner<'a> {
         Ok(self.signing_key.sign_raw(msg_digest, aux_rand)?)
     }
 
+    /// Sign a message using the given auxiliary randomness.
+    ///
+    /// The message is hashed with SHA-256 in the same way
+    /// as [SchnorrSigner::sign].
+    pub fn sign_with_aux_rand(
+        &self,
+        message: &[u8],
+        aux_rand: AuxRand,
+    ) -> Result<Signature> {
+        let digest = Sha256::digest(message);
+        self.sign_raw(&digest, &aux_rand.to_bytes())
+    }
+
+    /// X-only public key for this signer (BIP 340).
+    pub fn x_only_public_key(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&self.verifying_key().to_bytes());
+        bytes
+    }
+
+    /// Signing key tweaked for a taproot key path spend
+    /// according to BIP-341.
+    ///
+    /// Without a merkle root the output key commits to the
+    /// internal key only as described in BIP-86.
+    pub fn taproot_tweak(
+        &self,
+        merkle_root: Option<&[u8; 32]>,
+    ) -> Result<SigningKey> {
+        let mut hasher = tagged_hash(TAP_TWEAK_TAG);
+        hasher.update(self.x_only_public_key());
+        if let Some(merkle_root) = merkle_root {
+            hasher.update(merkle_root);
+        }
+        let tweak: Option<Scalar> =
+            Scalar::from_repr(hasher.finalize()).into();
+        let tweak = tweak.ok_or_else(signature::Error::new)?;
+        // Secret is already negated for an even y coordinate
+        let secret = *self.signing_key.as_nonzero_scalar().as_ref();
+        Ok(SigningKey::from_bytes(&(secret + tweak).to_bytes())?)
+    }
+
     /// Verifying key for this signer.
     pub fn verifying_key(&self) -> &VerifyingKey {
         self.signing_key.verifying_key()
@@ -84,4 +213,96 @@ impl<'a> SchnorrSigner<'a> {
     ) -> Result<()> {
         Ok(self.verifying_key().verify_raw(message, signature)?)
     }
+
+    /// Verify a batch of Schnorr signatures.
+    ///
+    /// Messages are hashed with SHA-256 in the same way
+    /// as [SchnorrSigner::verify].
+    pub fn verify_batch(
+        items: &[(&VerifyingKey, &[u8], &Signature)],
+    ) -> Result<()> {
+        let digests = items
+            .iter()
+            .map(|(_, message, _)| Sha256::digest(message))
+            .collect::<Vec<_>>();
+        let items = items
+            .iter()
+            .zip(digests.iter())
+            .map(|((key, _, signature), digest)| {
+                (*key, digest.as_slice(), *signature)
+            })
+            .collect::<Vec<_>>();
+        Self::verify_batch_raw(&items)
+    }
+
+    /// Verify a batch of Schnorr signatures over raw messages
+    /// using the BIP-340 batch verification algorithm.
+    ///
+    /// Each signature is weighted by a random scalar and
+    /// the batch is checked with a single equation, which is
+    /// faster than verifying each signature. An error is
+    /// returned if any signature is invalid; use
+    /// [SchnorrSigner::verify_raw] to find which one.
+    pub fn verify_batch_raw(
+        items: &[(&VerifyingKey, &[u8], &Signature)],
+    ) -> Result<()> {
+        let mut scalar = Scalar::ZERO;
+        let mut point = ProjectivePoint::IDENTITY;
+        for (index, (key, message, signature)) in
+            items.iter().enumerate()
+        {
+            let key_bytes = key.to_bytes();
+            let bytes = signature.to_bytes();
+            let (r, s) = bytes.split_at(32);
+
+            let public_key = lift_x(&key_bytes)?;
+            let r_point = lift_x(r)?;
+            let s: Option<Scalar> =
+                Scalar::from_repr(*FieldBytes::from_slice(s)).into();
+            let s = s.ok_or_else(signature::Error::new)?;
+
+            let mut hasher = tagged_hash(CHALLENGE_TAG);
+            hasher.update(r);
+            hasher.update(key_bytes);
+            hasher.update(message);
+            let e = <Scalar as Reduce<k256::U256>>::reduce_bytes(
+                &hasher.finalize(),
+            );
+
+            // The first weight is one as permitted by BIP 340
+            let weight = if index == 0 {
+                Scalar::ONE
+            } else {
+                Scalar::random(&mut OsRng)
+            };
+            scalar += weight * s;
+            point += ProjectivePoint::from(r_point) * weight
+                + ProjectivePoint::from(public_key) * (weight * e);
+        }
+
+        if ProjectivePoint::GENERATOR * scalar == point {
+            Ok(())
+        } else {
+            Err(signature::Error::new().into())
+        }
+    }
+}
+
+/// Point with an even Y coordinate for an X coordinate.
+fn lift_x(x: &[u8]) -> Result<AffinePoint> {
+    let point: Option<AffinePoint> = AffinePoint::decompress(
+        FieldBytes::from_slice(x),
+        Choice::from(0),
+    )
+    .into();
+    Ok(point.ok_or_else(signature::Error::new)?)
+}
+
+/// Create a hasher for a BIP-340 tagged hash.
+fn tagged_hash(tag: &[u8]) -> Sha256 {
+    let tag = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    hasher
 }
@@ -0,0 +1,101 @@
+//! Signing keys that are wiped from memory when dropped.
+//!
+//! Keys are moved into a dedicated heap allocation which is
+//! zeroized when the key is dropped. When the `mlock` feature
+//! is enabled on native targets the allocation is also locked
+//! so the key is never written to swap. Locking is best effort
+//! as the operating system may limit the amount of locked
+//! memory; use [SecretSigningKey::is_locked] to check.
+//!
+//! Locked memory is still written to core dumps so processes
+//! that hold keys should also disable core dumps, for example
+//! using `ulimit -c 0` or `prctl(PR_SET_DUMPABLE, 0)`.
+use std::{fmt, ops::Deref};
+use zeroize::ZeroizeOnDrop;
+
+/// Signing key that is wiped from memory when dropped.
+pub struct SecretSigningKey<K: ZeroizeOnDrop> {
+    // Fields are dropped in declaration order so the key
+    // is wiped before the memory is unlocked
+    key: Box<K>,
+    #[cfg(all(feature = "mlock", not(target_arch = "wasm32")))]
+    lock: Option<region::LockGuard>,
+}
+
+impl<K: ZeroizeOnDrop> SecretSigningKey<K> {
+    /// Move a signing key into protected memory.
+    pub fn new(key: K) -> Self {
+        let key = Box::new(key);
+        #[cfg(all(feature = "mlock", not(target_arch = "wasm32")))]
+        let lock = match region::lock(
+            key.as_ref() as *const K,
+            std::mem::size_of::<K>(),
+        ) {
+            Ok(guard) => Some(guard),
+            Err(error) => {
+                tracing::warn!(
+                    error = %error,
+                    "signing key memory could not be locked",
+                );
+                None
+            }
+        };
+        Self {
+            key,
+            #[cfg(all(
+                feature = "mlock",
+                not(target_arch = "wasm32")
+            ))]
+            lock,
+        }
+    }
+
+    /// Reference to the signing key.
+    pub fn expose_secret(&self) -> &K {
+        &self.key
+    }
+
+    /// Whether the memory for the key is locked.
+    pub fn is_locked(&self) -> bool {
+        #[cfg(all(feature = "mlock", not(target_arch = "wasm32")))]
+        {
+            self.lock.is_some()
+        }
+        #[cfg(not(all(
+            feature = "mlock",
+            not(target_arch = "wasm32")
+        )))]
+        {
+            false
+        }
+    }
+}
+
+impl<K: ZeroizeOnDrop> Deref for SecretSigningKey<K> {
+    type Target = K;
+
+    fn deref(&self) -> &Self::Target {
+        self.expose_secret()
+    }
+}
+
+impl<K: ZeroizeOnDrop + Clone> Clone for SecretSigningKey<K> {
+    fn clone(&self) -> Self {
+        Self::new(self.expose_secret().clone())
+    }
+}
+
+impl<K: ZeroizeOnDrop> From<K> for SecretSigningKey<K> {
+    fn from(value: K) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<K: ZeroizeOnDrop> fmt::Debug for SecretSigningKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretSigningKey")
+            .field("key", &"[REDACTED]")
+            .field("locked", &self.is_locked())
+            .finish()
+    }
+}
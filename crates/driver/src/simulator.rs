@@ -0,0 +1,345 @@
+//! Deterministic in-process simulator for protocol drivers.
+//!
+//! Runs a driver for every party without a network; messages
+//! are queued in memory and delivered by a scheduler seeded
+//! from [SimulatorOptions] so any reordering, delay or drop
+//! can be reproduced by running again with the same seed.
+//!
+//! Time is measured in steps and each step delivers at most one
+//! message. Drivers are advanced the same way as when they are
+//! connected to a network transport: a message is only handled
+//! when the receiver cannot finalize the current round and
+//! rounds are finalized after every message.
+use crate::{ProtocolDriver, Round};
+
+/// Options for the scheduler.
+#[derive(Debug, Clone)]
+pub struct SimulatorOptions {
+    /// Seed for the scheduler.
+    pub seed: u64,
+    /// Deliver ready messages in a random order rather
+    /// than the order they were sent.
+    pub reorder: bool,
+    /// Maximum number of steps a message may be delayed.
+    pub max_delay: u64,
+    /// Probability that a message is dropped between
+    /// `0.0` and `1.0`.
+    pub drop_rate: f64,
+    /// Drop messages matching any of these filters.
+    pub drop: Vec<MessageFilter>,
+    /// Stop the simulation after this number of steps.
+    pub max_steps: usize,
+}
+
+impl Default for SimulatorOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            reorder: false,
+            max_delay: 0,
+            drop_rate: 0.0,
+            drop: Vec::new(),
+            max_steps: 100_000,
+        }
+    }
+}
+
+/// Filter matching messages by sender, receiver and round.
+///
+/// Fields that are `None` match any value.
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    /// Party number of the sender.
+    pub sender: Option<u16>,
+    /// Party number of the receiver.
+    pub receiver: Option<u16>,
+    /// Round number.
+    pub round: Option<u16>,
+}
+
+impl MessageFilter {
+    fn matches(
+        &self,
+        sender: u16,
+        receiver: u16,
+        round: u16,
+    ) -> bool {
+        self.sender.map(|s| s == sender).unwrap_or(true)
+            && self.receiver.map(|r| r == receiver).unwrap_or(true)
+            && self.round.map(|r| r == round).unwrap_or(true)
+    }
+}
+
+/// Result of a simulation.
+#[derive(Debug)]
+pub struct Simulation<O> {
+    /// Output for each party in party number order; `None`
+    /// when the party did not complete the protocol.
+    pub outputs: Vec<Option<O>>,
+    /// Number of steps taken.
+    pub steps: usize,
+    /// Number of messages handled by drivers.
+    pub delivered: usize,
+    /// Number of messages dropped by the scheduler.
+    pub dropped: usize,
+    /// Number of messages ignored because the receiver could
+    /// already finalize the round or had completed.
+    pub ignored: usize,
+}
+
+impl<O> Simulation<O> {
+    /// Whether every party completed the protocol.
+    pub fn is_complete(&self) -> bool {
+        self.outputs.iter().all(|o| o.is_some())
+    }
+}
+
+/// Error returned when a driver fails during a simulation.
+#[derive(Debug, thiserror::Error)]
+#[error("party {party} failed at step {step}: {source}")]
+pub struct SimulationError<E: std::error::Error + 'static> {
+    /// Party number of the failed driver.
+    pub party: u16,
+    /// Step when the driver failed.
+    pub step: usize,
+    /// Driver error.
+    #[source]
+    pub source: E,
+}
+
+/// Message in flight.
+struct Envelope<M> {
+    deliver_at: u64,
+    sender: usize,
+    receiver: usize,
+    message: M,
+}
+
+/// Runs drivers for every party in-process.
+pub struct Simulator<D: ProtocolDriver> {
+    drivers: Vec<D>,
+    outputs: Vec<Option<D::Output>>,
+    options: SimulatorOptions,
+    rng: SplitMix64,
+    queue: Vec<Envelope<D::Message>>,
+    now: u64,
+    step: usize,
+    delivered: usize,
+    dropped: usize,
+    ignored: usize,
+}
+
+impl<D: ProtocolDriver> Simulator<D> {
+    /// Create a simulator.
+    ///
+    /// Drivers must be in party number order.
+    pub fn new(drivers: Vec<D>, options: SimulatorOptions) -> Self {
+        let outputs = drivers.iter().map(|_| None).collect();
+        Self {
+            drivers,
+            outputs,
+            rng: SplitMix64(options.seed),
+            options,
+            queue: Vec::new(),
+            now: 0,
+            step: 0,
+            delivered: 0,
+            dropped: 0,
+            ignored: 0,
+        }
+    }
+
+    /// Run until no messages are in flight or the
+    /// maximum number of steps is reached.
+    pub fn run(
+        mut self,
+    ) -> Result<Simulation<D::Output>, SimulationError<D::Error>>
+    {
+        for index in 0..self.drivers.len() {
+            let messages = self.drivers[index]
+                .proceed()
+                .map_err(|e| self.error(index, e))?;
+            self.send(index, messages);
+            self.finalize_rounds(index)?;
+        }
+
+        while !self.queue.is_empty()
+            && self.step < self.options.max_steps
+        {
+            self.step += 1;
+
+            // Advance time to the next delivery when nothing is ready
+            let next = self.queue.iter().map(|e| e.deliver_at).min();
+            if let Some(next) = next {
+                self.now = self.now.max(next);
+            }
+
+            let ready: Vec<usize> = self
+                .queue
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.deliver_at <= self.now)
+                .map(|(i, _)| i)
+                .collect();
+            let position = if self.options.reorder {
+                ready[self.rng.below(ready.len() as u64) as usize]
+            } else {
+                ready[0]
+            };
+            let envelope = self.queue.remove(position);
+            self.deliver(envelope)?;
+            self.now += 1;
+        }
+
+        Ok(Simulation {
+            outputs: self.outputs,
+            steps: self.step,
+            delivered: self.delivered,
+            dropped: self.dropped,
+            ignored: self.ignored,
+        })
+    }
+
+    /// Queue messages sent by a party applying drops and delays.
+    fn send(&mut self, sender: usize, messages: Vec<D::Message>) {
+        for message in messages {
+            let receiver = message.receiver().get() as usize - 1;
+            let round = message.round_number().get();
+            let filtered = self.options.drop.iter().any(|f| {
+                f.matches(
+                    party_number(sender),
+                    party_number(receiver),
+                    round,
+                )
+            });
+            if filtered || self.rng.chance(self.options.drop_rate) {
+                tracing::debug!(
+                    sender = party_number(sender),
+                    receiver = party_number(receiver),
+                    round,
+                    "simulator drop message"
+                );
+                self.dropped += 1;
+                continue;
+            }
+
+            let delay = if self.options.max_delay > 0 {
+                self.rng.below(self.options.max_delay + 1)
+            } else {
+                0
+            };
+            self.queue.push(Envelope {
+                deliver_at: self.now + delay,
+                sender,
+                receiver,
+                message,
+            });
+        }
+    }
+
+    /// Deliver a message to the receiver.
+    fn deliver(
+        &mut self,
+        envelope: Envelope<D::Message>,
+    ) -> Result<(), SimulationError<D::Error>> {
+        let index = envelope.receiver;
+        tracing::debug!(
+            step = self.step,
+            sender = party_number(envelope.sender),
+            receiver = party_number(index),
+            round = envelope.message.round_number().get(),
+            "simulator deliver message"
+        );
+
+        if self.outputs[index].is_some() {
+            self.ignored += 1;
+            return Ok(());
+        }
+        let round_info = self.drivers[index]
+            .round_info()
+            .map_err(|e| self.error(index, e))?;
+        if round_info.can_finalize {
+            self.ignored += 1;
+            return Ok(());
+        }
+
+        self.drivers[index]
+            .handle_incoming(envelope.message)
+            .map_err(|e| self.error(index, e))?;
+        self.delivered += 1;
+        self.finalize_rounds(index)
+    }
+
+    /// Finalize rounds while the driver can make progress.
+    fn finalize_rounds(
+        &mut self,
+        index: usize,
+    ) -> Result<(), SimulationError<D::Error>> {
+        loop {
+            let round_info = self.drivers[index]
+                .round_info()
+                .map_err(|e| self.error(index, e))?;
+            if !round_info.can_finalize {
+                break;
+            }
+            match self.drivers[index]
+                .try_finalize_round()
+                .map_err(|e| self.error(index, e))?
+            {
+                Some(output) => {
+                    self.outputs[index] = Some(output);
+                    break;
+                }
+                None => {
+                    let messages = self.drivers[index]
+                        .proceed()
+                        .map_err(|e| self.error(index, e))?;
+                    self.send(index, messages);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn error(
+        &self,
+        index: usize,
+        source: D::Error,
+    ) -> SimulationError<D::Error> {
+        SimulationError {
+            party: party_number(index),
+            step: self.step,
+            source,
+        }
+    }
+}
+
+fn party_number(index: usize) -> u16 {
+    index as u16 + 1
+}
+
+/// Small deterministic generator so schedules do not
+/// depend upon the version of the `rand` crate.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Value in the range `0..bound`.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Returns true with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        let bits = self.next_u64() >> 11;
+        let unit = bits as f64 / (1u64 << 53) as f64;
+        probability > 0.0 && unit < probability
+    }
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Errors generated by verifiable secret sharing.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated when the threshold parameters are invalid.
+    #[error("threshold {0} is invalid for {1} parties")]
+    Threshold(u16, u16),
+
+    /// Error generated when identifiers are not unique.
+    #[error("share identifiers must be unique")]
+    DuplicateIdentifier,
+
+    /// Error generated when a share does not match a commitment.
+    #[error("share {0} does not match the commitment")]
+    InvalidShare(u16),
+}
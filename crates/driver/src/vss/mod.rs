@@ -0,0 +1,349 @@
+//! Verifiable secret sharing.
+//!
+//! Feldman commitments reveal the public key for the secret
+//! whilst Pedersen commitments are perfectly hiding and require
+//! a second generator with an unknown discrete logarithm.
+//!
+//! Functions are generic over the [Group] trait so any prime
+//! order group such as secp256k1 or BLS12-381 may be used.
+use ff::{Field, PrimeField};
+use group::Group;
+use rand::{CryptoRng, RngCore};
+use std::{collections::BTreeSet, num::NonZeroU16};
+
+mod error;
+
+pub use error::Error;
+
+/// Result type for verifiable secret sharing.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Identifier for a share.
+///
+/// Shares are evaluated at the identifier so it must
+/// never be zero.
+pub type Identifier = NonZeroU16;
+
+/// Share of a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share<F> {
+    identifier: Identifier,
+    value: F,
+}
+
+impl<F: PrimeField> Share<F> {
+    /// Create a share.
+    pub fn new(identifier: Identifier, value: F) -> Self {
+        Self { identifier, value }
+    }
+
+    /// Identifier for the share.
+    pub fn identifier(&self) -> Identifier {
+        self.identifier
+    }
+
+    /// Value of the share.
+    pub fn value(&self) -> &F {
+        &self.value
+    }
+}
+
+/// Share of a secret with the blinding value for a
+/// Pedersen commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PedersenShare<F> {
+    share: Share<F>,
+    blinding: F,
+}
+
+impl<F: PrimeField> PedersenShare<F> {
+    /// Create a share.
+    pub fn new(share: Share<F>, blinding: F) -> Self {
+        Self { share, blinding }
+    }
+
+    /// Share of the secret.
+    pub fn share(&self) -> &Share<F> {
+        &self.share
+    }
+
+    /// Blinding value for the share.
+    pub fn blinding(&self) -> &F {
+        &self.blinding
+    }
+}
+
+/// Polynomial with the secret as the constant term.
+#[derive(Clone)]
+pub struct Polynomial<F> {
+    coefficients: Vec<F>,
+}
+
+impl<F: PrimeField> Polynomial<F> {
+    /// Create a random polynomial for a threshold.
+    pub fn random(
+        secret: F,
+        threshold: u16,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Result<Self> {
+        if threshold == 0 {
+            return Err(Error::Threshold(threshold, 0));
+        }
+        let mut coefficients = Vec::with_capacity(threshold as usize);
+        coefficients.push(secret);
+        for _ in 1..threshold {
+            coefficients.push(F::random(&mut *rng));
+        }
+        Ok(Self { coefficients })
+    }
+
+    /// Number of shares required to reconstruct the secret.
+    pub fn threshold(&self) -> u16 {
+        self.coefficients.len() as u16
+    }
+
+    /// Constant term of the polynomial.
+    pub fn secret(&self) -> &F {
+        &self.coefficients[0]
+    }
+
+    /// Evaluate the polynomial at an identifier.
+    pub fn evaluate(&self, identifier: &Identifier) -> F {
+        let x = scalar::<F>(identifier);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(F::ZERO, |acc, c| acc * x + c)
+    }
+
+    /// Share of the secret for an identifier.
+    pub fn share(&self, identifier: Identifier) -> Share<F> {
+        Share::new(identifier, self.evaluate(&identifier))
+    }
+
+    /// Feldman commitment to the coefficients.
+    pub fn commit<G>(&self) -> FeldmanCommitment<G>
+    where
+        G: Group<Scalar = F>,
+    {
+        FeldmanCommitment {
+            commitments: self
+                .coefficients
+                .iter()
+                .map(|c| G::generator() * c)
+                .collect(),
+        }
+    }
+}
+
+/// Feldman commitment to a polynomial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeldmanCommitment<G> {
+    commitments: Vec<G>,
+}
+
+impl<G: Group> FeldmanCommitment<G> {
+    /// Create a commitment from the coefficient commitments.
+    pub fn new(commitments: Vec<G>) -> Result<Self> {
+        if commitments.is_empty() {
+            return Err(Error::Threshold(0, 0));
+        }
+        Ok(Self { commitments })
+    }
+
+    /// Commitments to each coefficient.
+    pub fn commitments(&self) -> &[G] {
+        &self.commitments
+    }
+
+    /// Number of shares required to reconstruct the secret.
+    pub fn threshold(&self) -> u16 {
+        self.commitments.len() as u16
+    }
+
+    /// Public key for the secret.
+    pub fn public_key(&self) -> G {
+        self.commitments[0]
+    }
+
+    /// Public key for the share of an identifier.
+    pub fn evaluate(&self, identifier: &Identifier) -> G {
+        evaluate(&self.commitments, identifier)
+    }
+
+    /// Verify a share against this commitment.
+    pub fn verify(&self, share: &Share<G::Scalar>) -> Result<()> {
+        if G::generator() * share.value
+            == self.evaluate(&share.identifier)
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidShare(share.identifier.get()))
+        }
+    }
+}
+
+/// Pedersen commitment to a polynomial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PedersenCommitment<G> {
+    generator: G,
+    commitments: Vec<G>,
+}
+
+impl<G: Group> PedersenCommitment<G> {
+    /// Commitments to each coefficient.
+    pub fn commitments(&self) -> &[G] {
+        &self.commitments
+    }
+
+    /// Number of shares required to reconstruct the secret.
+    pub fn threshold(&self) -> u16 {
+        self.commitments.len() as u16
+    }
+
+    /// Generator for the blinding values.
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// Verify a share against this commitment.
+    pub fn verify(
+        &self,
+        share: &PedersenShare<G::Scalar>,
+    ) -> Result<()> {
+        let identifier = share.share.identifier;
+        if G::generator() * share.share.value
+            + self.generator * share.blinding
+            == evaluate(&self.commitments, &identifier)
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidShare(identifier.get()))
+        }
+    }
+}
+
+/// Split a secret into shares with a Feldman commitment.
+pub fn split_feldman<G: Group>(
+    secret: G::Scalar,
+    threshold: u16,
+    parties: u16,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<(Vec<Share<G::Scalar>>, FeldmanCommitment<G>)> {
+    check_threshold(threshold, parties)?;
+    let polynomial = Polynomial::random(secret, threshold, rng)?;
+    let shares = identifiers(parties)
+        .map(|id| polynomial.share(id))
+        .collect();
+    Ok((shares, polynomial.commit()))
+}
+
+/// Split a secret into shares with a Pedersen commitment.
+///
+/// The discrete logarithm of the blinding generator with
+/// respect to the group generator must not be known.
+pub fn split_pedersen<G: Group>(
+    secret: G::Scalar,
+    threshold: u16,
+    parties: u16,
+    generator: G,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<(Vec<PedersenShare<G::Scalar>>, PedersenCommitment<G>)> {
+    check_threshold(threshold, parties)?;
+    let polynomial =
+        Polynomial::random(secret, threshold, &mut *rng)?;
+    let blinding = Polynomial::random(
+        G::Scalar::random(&mut *rng),
+        threshold,
+        &mut *rng,
+    )?;
+
+    let shares = identifiers(parties)
+        .map(|id| {
+            PedersenShare::new(
+                polynomial.share(id),
+                blinding.evaluate(&id),
+            )
+        })
+        .collect();
+    let commitments = polynomial
+        .coefficients
+        .iter()
+        .zip(blinding.coefficients.iter())
+        .map(|(a, b)| G::generator() * a + generator * b)
+        .collect();
+
+    Ok((
+        shares,
+        PedersenCommitment {
+            generator,
+            commitments,
+        },
+    ))
+}
+
+/// Reconstruct a secret from shares.
+///
+/// The number of shares must meet the threshold otherwise
+/// the reconstructed secret will be incorrect.
+pub fn reconstruct<F: PrimeField>(shares: &[Share<F>]) -> Result<F> {
+    if shares.is_empty() {
+        return Err(Error::Threshold(0, 0));
+    }
+    let identifiers =
+        shares.iter().map(|s| s.identifier).collect::<Vec<_>>();
+    let mut secret = F::ZERO;
+    for share in shares {
+        secret += share.value
+            * lagrange::<F>(&share.identifier, &identifiers)?;
+    }
+    Ok(secret)
+}
+
+/// Lagrange coefficient at zero for an identifier.
+pub fn lagrange<F: PrimeField>(
+    identifier: &Identifier,
+    identifiers: &[Identifier],
+) -> Result<F> {
+    let unique = identifiers.iter().collect::<BTreeSet<_>>();
+    if unique.len() != identifiers.len() {
+        return Err(Error::DuplicateIdentifier);
+    }
+
+    let x = scalar::<F>(identifier);
+    let mut numerator = F::ONE;
+    let mut denominator = F::ONE;
+    for other in identifiers.iter().filter(|i| *i != identifier) {
+        let other = scalar::<F>(other);
+        numerator *= other;
+        denominator *= other - x;
+    }
+    let inverse: Option<F> = denominator.invert().into();
+    Ok(numerator * inverse.ok_or(Error::DuplicateIdentifier)?)
+}
+
+fn evaluate<G: Group>(
+    commitments: &[G],
+    identifier: &Identifier,
+) -> G {
+    let x = scalar::<G::Scalar>(identifier);
+    commitments
+        .iter()
+        .rev()
+        .fold(G::identity(), |acc, c| acc * x + c)
+}
+
+fn scalar<F: PrimeField>(identifier: &Identifier) -> F {
+    F::from(identifier.get() as u64)
+}
+
+fn identifiers(parties: u16) -> impl Iterator<Item = Identifier> {
+    (1..=parties).filter_map(NonZeroU16::new)
+}
+
+fn check_threshold(threshold: u16, parties: u16) -> Result<()> {
+    if threshold == 0 || threshold > parties {
+        return Err(Error::Threshold(threshold, parties));
+    }
+    Ok(())
+}
@@ -0,0 +1,349 @@
+//! Run the shares of a weighted party over a single session.
+//!
+//! A party with a weight greater than one holds multiple
+//! shares; shares are numbered consecutively in the order of
+//! the parties so a party with weight `w` holds `w` shares
+//! and a protocol is run with one share for every unit of
+//! weight.
+use crate::{Error, ProtocolDriver, Result, Round, RoundInfo};
+use polysig_protocol::{PartyNumber, RoundNumber};
+use serde::{Deserialize, Serialize};
+
+/// Weight of each party in the order of the parties.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Weights(pub(crate) Vec<u16>);
+
+impl Weights {
+    /// Create weights for parties.
+    ///
+    /// Every party must have a weight of at least one and
+    /// the total weight must fit in a share number.
+    pub fn new(weights: Vec<u16>) -> Result<Self> {
+        if let Some(index) = weights.iter().position(|w| *w == 0) {
+            return Err(Error::ZeroWeight(index));
+        }
+        weights
+            .iter()
+            .try_fold(0u16, |total, weight| {
+                total.checked_add(*weight)
+            })
+            .ok_or(Error::WeightOverflow)?;
+        Ok(Self(weights))
+    }
+
+    /// Weights where every party holds a single share.
+    pub fn uniform(parties: u16) -> Self {
+        Self(vec![1; parties as usize])
+    }
+
+    /// Number of parties.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Determine if there are no parties.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Determine if any party holds more than one share.
+    pub fn is_weighted(&self) -> bool {
+        self.0.iter().any(|w| *w > 1)
+    }
+
+    /// Weight of a party.
+    pub fn weight(&self, party: PartyNumber) -> Option<u16> {
+        self.0.get(party.get() as usize - 1).copied()
+    }
+
+    /// Total number of shares.
+    pub fn total(&self) -> u16 {
+        self.0.iter().sum()
+    }
+
+    /// Share numbers held by a party.
+    pub fn shares(&self, party: PartyNumber) -> Vec<PartyNumber> {
+        let index = party.get() as usize - 1;
+        let Some(weight) = self.0.get(index) else {
+            return Vec::new();
+        };
+        let first: u16 = self.0[..index].iter().sum();
+        (first + 1..=first + weight)
+            .map(|share| PartyNumber::new(share).unwrap())
+            .collect()
+    }
+
+    /// Party that holds each share in the order of the shares.
+    pub fn owners(&self) -> Vec<PartyNumber> {
+        self.0
+            .iter()
+            .enumerate()
+            .flat_map(|(index, weight)| {
+                let party =
+                    PartyNumber::new(index as u16 + 1).unwrap();
+                std::iter::repeat(party).take(*weight as usize)
+            })
+            .collect()
+    }
+}
+
+/// Message for a share held by a weighted party.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeightedMessage<M> {
+    /// Party that holds the receiving share.
+    pub(crate) receiver: PartyNumber,
+    /// Message for the share.
+    pub(crate) message: M,
+}
+
+impl<M: Round> WeightedMessage<M> {
+    /// Share number of the receiver.
+    pub fn share(&self) -> PartyNumber {
+        *self.message.receiver()
+    }
+}
+
+impl<M: Round> Round for WeightedMessage<M> {
+    fn round_number(&self) -> RoundNumber {
+        self.message.round_number()
+    }
+
+    fn receiver(&self) -> &PartyNumber {
+        &self.receiver
+    }
+}
+
+/// Runs a protocol driver for each share held by a party.
+///
+/// Drivers are created with the share number as the party
+/// number; messages between shares held by this party are
+/// delivered locally and messages for other shares are sent
+/// to the party that holds the share.
+pub struct WeightedDriver<D: ProtocolDriver> {
+    party_number: PartyNumber,
+    owners: Vec<PartyNumber>,
+    shares: Vec<PartyNumber>,
+    drivers: Vec<D>,
+    outputs: Vec<Option<D::Output>>,
+}
+
+impl<D: ProtocolDriver> WeightedDriver<D> {
+    /// Create a new weighted driver.
+    ///
+    /// The owners are the party that holds each share in the
+    /// order of the shares and the drivers are the share
+    /// numbers held by this party with the driver for
+    /// each share.
+    pub fn new(
+        party_number: PartyNumber,
+        owners: Vec<PartyNumber>,
+        drivers: Vec<(PartyNumber, D)>,
+    ) -> Result<Self> {
+        let (shares, drivers): (Vec<_>, Vec<_>) =
+            drivers.into_iter().unzip();
+        for share in &shares {
+            if owners.get(share.get() as usize - 1)
+                != Some(&party_number)
+            {
+                return Err(Error::WeightedShare(share.get()));
+            }
+        }
+        let outputs = drivers.iter().map(|_| None).collect();
+        Ok(Self {
+            party_number,
+            owners,
+            shares,
+            drivers,
+            outputs,
+        })
+    }
+
+    /// Share numbers held by this party.
+    pub fn shares(&self) -> &[PartyNumber] {
+        &self.shares
+    }
+
+    fn weighted_error(share: PartyNumber, error: D::Error) -> Error {
+        Error::Weighted(share.get(), Box::new(error))
+    }
+
+    /// Index of the driver for a share held by this party.
+    fn position(&self, share: &PartyNumber) -> Result<usize> {
+        self.shares
+            .iter()
+            .position(|s| s == share)
+            .ok_or(Error::WeightedShare(share.get()))
+    }
+}
+
+impl<D: ProtocolDriver> ProtocolDriver for WeightedDriver<D> {
+    type Error = Error;
+    type Message = WeightedMessage<D::Message>;
+    type Output = Vec<D::Output>;
+
+    /// Share numbers reported by the driver for a share
+    /// are mapped to the party that holds the share.
+    fn culprit(&self, error: &Self::Error) -> Option<PartyNumber> {
+        match error {
+            Error::Weighted(share, e) => {
+                let share = PartyNumber::new(*share)?;
+                let driver =
+                    &self.drivers[self.position(&share).ok()?];
+                let culprit =
+                    driver.culprit(e.downcast_ref::<D::Error>()?)?;
+                self.owners.get(culprit.get() as usize - 1).copied()
+            }
+            _ => None,
+        }
+    }
+
+    fn round_info(&self) -> Result<RoundInfo> {
+        let mut info = RoundInfo {
+            can_finalize: true,
+            is_echo: false,
+            round_number: 0,
+        };
+        for (index, driver) in self.drivers.iter().enumerate() {
+            if self.outputs[index].is_some() {
+                continue;
+            }
+            let round_info = driver.round_info().map_err(|e| {
+                Self::weighted_error(self.shares[index], e)
+            })?;
+            info.can_finalize =
+                info.can_finalize && round_info.can_finalize;
+            info.is_echo = info.is_echo || round_info.is_echo;
+            info.round_number =
+                info.round_number.max(round_info.round_number);
+        }
+        Ok(info)
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>> {
+        let mut local = Vec::new();
+        let mut messages = Vec::new();
+        for (index, driver) in self.drivers.iter_mut().enumerate() {
+            if self.outputs[index].is_some() {
+                continue;
+            }
+            let share = self.shares[index];
+            let driver_messages = driver
+                .proceed()
+                .map_err(|e| Self::weighted_error(share, e))?;
+            for message in driver_messages {
+                let share = *message.receiver();
+                let receiver = *self
+                    .owners
+                    .get(share.get() as usize - 1)
+                    .ok_or(Error::WeightedShare(share.get()))?;
+                if receiver == self.party_number {
+                    local.push(message);
+                } else {
+                    messages
+                        .push(WeightedMessage { receiver, message });
+                }
+            }
+        }
+
+        // Shares held by this party receive their
+        // messages without a round trip
+        for message in local {
+            let share = *message.receiver();
+            let index = self.position(&share)?;
+            self.drivers[index]
+                .handle_incoming(message)
+                .map_err(|e| Self::weighted_error(share, e))?;
+        }
+
+        Ok(messages)
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<()> {
+        let share = message.share();
+        let index = self.position(&share)?;
+        self.drivers[index]
+            .handle_incoming(message.message)
+            .map_err(|e| Self::weighted_error(share, e))
+    }
+
+    fn try_finalize_round(&mut self) -> Result<Option<Self::Output>> {
+        for (index, driver) in self.drivers.iter_mut().enumerate() {
+            if self.outputs[index].is_some() {
+                continue;
+            }
+            let share = self.shares[index];
+            if let Some(output) = driver
+                .try_finalize_round()
+                .map_err(|e| Self::weighted_error(share, e))?
+            {
+                self.outputs[index] = Some(output);
+            }
+        }
+
+        if self.outputs.iter().all(|o| o.is_some()) {
+            Ok(Some(
+                self.outputs
+                    .iter_mut()
+                    .map(|o| o.take().unwrap())
+                    .collect(),
+            ))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn party(n: u16) -> PartyNumber {
+        PartyNumber::new(n).unwrap()
+    }
+
+    #[test]
+    fn weights_shares() -> Result<()> {
+        let weights = Weights::new(vec![2, 1, 3])?;
+        assert!(weights.is_weighted());
+        assert_eq!(6, weights.total());
+        assert_eq!(
+            vec![party(1), party(2)],
+            weights.shares(party(1))
+        );
+        assert_eq!(vec![party(3)], weights.shares(party(2)));
+        assert_eq!(
+            vec![party(4), party(5), party(6)],
+            weights.shares(party(3))
+        );
+        assert!(weights.shares(party(4)).is_empty());
+        assert_eq!(
+            vec![
+                party(1),
+                party(1),
+                party(2),
+                party(3),
+                party(3),
+                party(3)
+            ],
+            weights.owners()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn weights_invalid() {
+        assert!(matches!(
+            Weights::new(vec![1, 0]),
+            Err(Error::ZeroWeight(1))
+        ));
+        assert!(matches!(
+            Weights::new(vec![u16::MAX, 1]),
+            Err(Error::WeightOverflow)
+        ));
+        assert!(!Weights::uniform(3).is_weighted());
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use polysig_client::{Account, AccountParty, AccountRegistry, Error};
+use polysig_protocol::{hex, Parameters};
+
+fn party(byte: u8) -> AccountParty {
+    AccountParty {
+        public_key: vec![byte; 32],
+        verifier: vec![byte; 33],
+    }
+}
+
+fn account() -> Account {
+    Account {
+        verifying_key: vec![0xab; 33],
+        address: Some("0xAbC123".to_owned()),
+        parameters: Parameters {
+            parties: 3,
+            threshold: 2,
+        },
+        parties: vec![party(1), party(2), party(3)],
+        share_location: Some("treasury".to_owned()),
+        reshared_at: None,
+    }
+}
+
+/// Find the parties required to sign for an account
+/// by address and verifying key.
+#[test]
+fn account_registry_signers() -> Result<()> {
+    let mut registry = AccountRegistry::new();
+    assert!(registry.insert(account())?.is_none());
+    assert_eq!(1, registry.len());
+
+    let (threshold, parties) = registry.signers("0xabc123")?;
+    assert_eq!(2, threshold);
+    assert_eq!(&[party(1), party(2), party(3)], parties);
+
+    let key = format!("0x{}", hex::encode(vec![0xab; 33]));
+    let found = registry.find(&key).unwrap();
+    assert_eq!(Some(2), found.party_number(&[2; 32]));
+    assert_eq!(Some("treasury"), found.share_location.as_deref());
+    assert!(found.has_quorum(&[vec![1; 32], vec![3; 32]]));
+    assert!(!found.has_quorum(&[vec![1; 32], vec![4; 32]]));
+
+    assert!(matches!(
+        registry.signers("0xdef"),
+        Err(Error::UnknownAccount(_))
+    ));
+
+    // Number of parties must match the parameters
+    let mut invalid = account();
+    invalid.parties.pop();
+    assert!(matches!(
+        registry.insert(invalid),
+        Err(Error::InvalidParameters(_))
+    ));
+
+    Ok(())
+}
+
+/// Record a reshare and persist the registry.
+#[test]
+fn account_registry_reshare_save() -> Result<()> {
+    let mut registry = AccountRegistry::new();
+    registry.insert(account())?;
+    registry.reshared(
+        &[0xab; 33],
+        Parameters {
+            parties: 4,
+            threshold: 3,
+        },
+        vec![party(1), party(2), party(3), party(4)],
+        1_700_000_000,
+    )?;
+
+    let dir = std::env::temp_dir()
+        .join(format!("polysig-registry-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("accounts.json");
+    assert!(AccountRegistry::load(&path)?.is_empty());
+    registry.save(&path)?;
+
+    let loaded = AccountRegistry::load(&path)?;
+    let account = loaded.get(&[0xab; 33]).unwrap();
+    assert_eq!(3, account.threshold());
+    assert_eq!(4, account.parties.len());
+    assert_eq!(Some(1_700_000_000), account.reshared_at);
+    assert_eq!(Some("0xAbC123"), account.address.as_deref());
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
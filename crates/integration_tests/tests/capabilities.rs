@@ -0,0 +1,50 @@
+use anyhow::Result;
+use polysig_client::{capabilities, Capabilities};
+use polysig_protocol::VERSION;
+
+#[test]
+fn capabilities_compiled_features() -> Result<()> {
+    let capabilities = capabilities();
+    assert_eq!(vec![VERSION], capabilities.encoding_versions);
+    for encoding in ["blob", "json", "cbor", "msgpack"] {
+        assert!(capabilities
+            .payload_encodings
+            .iter()
+            .any(|e| e == encoding));
+    }
+    for protocol in ["bls", "cggmp", "frost-ed25519", "musig2"] {
+        assert!(capabilities.supports_protocol(protocol));
+    }
+    assert_eq!(
+        vec!["ecdsa", "eddsa", "schnorr"],
+        capabilities.signers
+    );
+    assert!(capabilities
+        .curves
+        .iter()
+        .any(|c| c.curve == "secp256k1" && c.backend == "k256"));
+    // Client features are reported with the driver features
+    for feature in ["encrypt", "http2", "journal", "vss"] {
+        assert!(capabilities.features.iter().any(|f| f == feature));
+    }
+
+    // Capabilities are exchanged as JSON between participants
+    let value = serde_json::to_value(&capabilities)?;
+    assert!(value.get("encodingVersions").is_some());
+    let other: Capabilities = serde_json::from_value(value)?;
+    assert_eq!(capabilities, other);
+    Ok(())
+}
+
+#[test]
+fn capabilities_compatible() {
+    let capabilities = capabilities();
+    let mut other = capabilities.clone();
+    other.protocols.retain(|p| p != "musig2");
+    assert!(capabilities.is_compatible(&other, "cggmp"));
+    assert!(!capabilities.is_compatible(&other, "musig2"));
+
+    // Builds must share a wire protocol encoding version
+    other.encoding_versions = vec![VERSION + 1];
+    assert!(!capabilities.is_compatible(&other, "cggmp"));
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use polysig_protocol::{
+    compat::{fixtures, Message},
+    decode, RequestMessage, ResponseMessage, ServerMessage, VERSION,
+};
+use std::collections::HashSet;
+
+#[tokio::test]
+async fn wire_fixtures_decode() -> Result<()> {
+    for version in 1..=VERSION {
+        let fixtures = fixtures(version);
+        assert!(!fixtures.is_empty(), "no fixtures for v{}", version);
+        for fixture in fixtures {
+            fixture.verify().await?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn wire_fixtures_cover_variants() {
+    let mut requests = HashSet::new();
+    let mut responses = HashSet::new();
+    let mut server = HashSet::new();
+    for fixture in fixtures(VERSION) {
+        match &fixture.message {
+            Message::Request(message) => {
+                let inner = match message {
+                    RequestMessage::Transparent(message) => {
+                        u8::from(message)
+                    }
+                    RequestMessage::Opaque(message) => {
+                        u8::from(message)
+                    }
+                    RequestMessage::Noop => 0,
+                };
+                requests.insert((u8::from(message), inner));
+            }
+            Message::Response(message) => {
+                let inner = match message {
+                    ResponseMessage::Transparent(message) => {
+                        u8::from(message)
+                    }
+                    ResponseMessage::Opaque(message) => {
+                        u8::from(message)
+                    }
+                    ResponseMessage::Noop => 0,
+                };
+                responses.insert((u8::from(message), inner));
+            }
+            Message::Server(message) => {
+                server.insert(u8::from(message));
+            }
+        }
+    }
+
+    // Transparent error, server and peer handshakes;
+    // opaque server, peer and group messages; keepalive pings
+    let expected: HashSet<(u8, u8)> = [
+        (0, 0),
+        (128, 255),
+        (128, 1),
+        (128, 2),
+        (129, 1),
+        (129, 2),
+        (129, 3),
+    ]
+    .into();
+    assert_eq!(expected, requests);
+    assert_eq!(expected, responses);
+    let expected: HashSet<u8> =
+        [255, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18]
+            .into();
+    assert_eq!(expected, server);
+}
+
+#[tokio::test]
+async fn wire_fixtures_reject_unknown_version() -> Result<()> {
+    let fixture = fixtures(VERSION)
+        .into_iter()
+        .find(|f| f.name == "request-peer-message")
+        .unwrap();
+    let mut bytes = fixture.bytes.to_vec();
+    bytes[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+    let result = decode::<RequestMessage>(&bytes).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn wire_fixtures_ignore_unknown_session_stats() -> Result<()> {
+    let fixture = fixtures(VERSION)
+        .into_iter()
+        .find(|f| f.name == "server-session-finished-stats")
+        .unwrap();
+    // Version of the statistics follows the session identifier
+    let mut bytes = fixture.bytes.to_vec();
+    bytes[17] += 1;
+    let message = decode::<ServerMessage>(&bytes).await?;
+    assert!(matches!(
+        message,
+        ServerMessage::SessionFinished(_, None)
+    ));
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use polysig_driver::{digest::DigestAlgorithm, Error};
+
+const MESSAGE: &[u8] = b"abc";
+
+#[test]
+fn digest_algorithms() -> Result<()> {
+    let vectors = [
+        (
+            DigestAlgorithm::Sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            DigestAlgorithm::Sha256d,
+            "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358",
+        ),
+        (
+            DigestAlgorithm::Keccak256,
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ),
+        (
+            DigestAlgorithm::Blake2b256,
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+        ),
+    ];
+
+    for (algorithm, expected) in vectors {
+        let prehash = algorithm.prehash(MESSAGE)?;
+        assert_eq!(expected, hex::encode(prehash));
+        assert_eq!(algorithm, algorithm.to_string().parse()?);
+    }
+
+    // Prehashed messages are passed through
+    let prehash = [7u8; 32];
+    assert_eq!(
+        prehash,
+        DigestAlgorithm::Prehashed.prehash(&prehash)?
+    );
+    assert!(matches!(
+        DigestAlgorithm::Prehashed.prehash(MESSAGE),
+        Err(Error::IncompatibleDigest(_, 3))
+    ));
+
+    // SHA-512 cannot be used for a 32 byte prehash
+    assert_eq!(64, DigestAlgorithm::Sha512.digest(MESSAGE).len());
+    assert!(matches!(
+        DigestAlgorithm::Sha512.prehash(MESSAGE),
+        Err(Error::IncompatibleDigest(_, 64))
+    ));
+
+    assert_eq!(
+        DigestAlgorithm::Blake2b256,
+        "blake2".parse::<DigestAlgorithm>()?
+    );
+    assert!(matches!(
+        "md5".parse::<DigestAlgorithm>(),
+        Err(Error::UnknownDigest(_))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::test_utils::{new_client, server_public_key};
+use anyhow::{bail, Result};
+use axum_server::Handle;
+use futures::StreamExt;
+use polysig_client::NetworkTransport;
+use polysig_protocol::Event;
+use polysig_relay_server::{
+    axum::{routing::get, Router},
+    RelayServer, ServerConfig,
+};
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Embeds the relay server in an application that serves
+/// other routes on the same port.
+#[tokio::test]
+async fn integration_embedded_server() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (config, keypair) =
+        ServerConfig::load("tests/config.toml").await?;
+    let server = RelayServer::new(config, keypair);
+    let (relay, _embed) = server.embed().await?;
+    let app = Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .merge(relay);
+
+    let handle = Handle::new();
+    let addr: SocketAddr = "127.0.0.1:0".parse()?;
+    let server_handle = handle.clone();
+    tokio::task::spawn(async move {
+        axum_server::bind(addr)
+            .handle(server_handle)
+            .serve(app.into_make_service())
+            .await
+            .expect("failed to serve application");
+    });
+    let Some(addr) = handle.listening().await else {
+        bail!("application is not listening");
+    };
+
+    // Application routes are served alongside the relay
+    let mut stream = TcpStream::connect(addr).await?;
+    stream
+        .write_all(
+            b"GET /health HTTP/1.1\r\n\
+              Host: localhost\r\n\
+              Connection: close\r\n\r\n",
+        )
+        .await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    assert!(response.starts_with("HTTP/1.1 200"));
+    assert!(response.ends_with("ok"));
+
+    // Clients connect to the relay on the same port
+    let server = format!("ws://{}", addr);
+    let (mut client, event_loop, _) = new_client::<anyhow::Error>(
+        &server,
+        server_public_key().await?,
+    )
+    .await?;
+    client.connect().await?;
+    let mut events = event_loop.run();
+    let Some(event) = events.next().await else {
+        bail!("event stream closed");
+    };
+    assert!(matches!(event?, Event::ServerConnected { .. }));
+    client.close().await?;
+
+    handle.shutdown();
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use polysig_client::ErrorKind;
+use std::io;
+
+fn io_error() -> polysig_protocol::Error {
+    io::Error::from(io::ErrorKind::ConnectionReset).into()
+}
+
+/// Protocol and driver errors are classified by kind.
+#[test]
+fn error_kind_driver() {
+    let error = io_error();
+    assert_eq!(ErrorKind::Transport, error.kind());
+    assert!(error.is_retryable());
+
+    let error = polysig_protocol::Error::ThresholdTooLow(2, 1);
+    assert_eq!(ErrorKind::Protocol, error.kind());
+    assert!(!error.is_retryable());
+
+    let error = polysig_driver::Error::Protocol(io_error());
+    assert_eq!(ErrorKind::Transport, error.kind());
+    assert!(error.is_retryable());
+
+    // Boxed errors keep the kind of the underlying error
+    let error =
+        polysig_driver::Error::Plugin("test", Box::new(io_error()));
+    assert_eq!(ErrorKind::Transport, error.kind());
+    assert!(error.is_retryable());
+
+    let error = polysig_driver::Error::DuplicateParticipant(1);
+    assert_eq!(ErrorKind::Protocol, error.kind());
+    assert!(!error.is_retryable());
+}
+
+/// Client errors keep the kind of driver and protocol errors.
+#[test]
+fn error_kind_client() {
+    let error = polysig_client::Error::Driver(
+        polysig_driver::Error::Protocol(io_error()),
+    );
+    let info = error.info();
+    assert_eq!(ErrorKind::Transport, info.kind);
+    assert!(info.retryable);
+
+    // Driver errors are boxed by the protocol bridge
+    let error = polysig_client::Error::Generic(Box::new(
+        polysig_driver::Error::Protocol(io_error()),
+    ));
+    assert_eq!(ErrorKind::Transport, error.kind());
+    assert!(error.is_retryable());
+
+    let error = polysig_client::Error::Generic(Box::new(
+        polysig_driver::Error::SessionIdMismatch,
+    ));
+    assert_eq!(ErrorKind::Protocol, error.kind());
+    assert!(!error.is_retryable());
+
+    let error = polysig_client::Error::Protocol(
+        polysig_protocol::Error::PartiesMismatch(3, 2),
+    );
+    assert_eq!(ErrorKind::Protocol, error.kind());
+    assert!(!error.is_retryable());
+}
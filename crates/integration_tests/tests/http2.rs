@@ -0,0 +1,92 @@
+use crate::test_utils::{
+    server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::Result;
+use polysig_client::{
+    Client, ClientOptions, EventStream, Http2Connection,
+    NetworkTransport,
+};
+use polysig_protocol::{Event, Keypair};
+
+/// Clients for several sessions share a single HTTP/2
+/// connection to the relay server.
+#[tokio::test]
+async fn integration_http2_streams() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let connection = Http2Connection::connect(&server).await?;
+    let (mut client1, mut s1, key1) =
+        new_client(&server, &server_public_key, &connection).await?;
+    let (mut client2, mut s2, key2) =
+        new_client(&server, &server_public_key, &connection).await?;
+
+    client1.connect_peer(key2.public_key()).await?;
+    for stream in [&mut s1, &mut s2] {
+        wait_for_event(stream, |event| {
+            matches!(event, Event::PeerConnected { .. })
+        })
+        .await?;
+    }
+
+    // Messages are relayed between streams on the connection
+    client1.send_json(key2.public_key(), &"hello", None).await?;
+    let Event::JsonMessage {
+        peer_key, message, ..
+    } = wait_for_event(&mut s2, |event| {
+        matches!(event, Event::JsonMessage { .. })
+    })
+    .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(key1.public_key(), peer_key);
+    assert_eq!("hello", message.deserialize::<&str>()?);
+
+    client2.send_json(key1.public_key(), &"world", None).await?;
+    let Event::JsonMessage { message, .. } =
+        wait_for_event(&mut s1, |event| {
+            matches!(event, Event::JsonMessage { .. })
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!("world", message.deserialize::<&str>()?);
+
+    // Closing a client ends the stream but not the connection
+    client1.close().await?;
+    let (mut client3, _s3, _) =
+        new_client(&server, &server_public_key, &connection).await?;
+    client2.close().await?;
+    client3.close().await?;
+
+    Ok(())
+}
+
+async fn new_client(
+    server: &str,
+    server_public_key: &[u8],
+    connection: &Http2Connection,
+) -> Result<(Client, EventStream, Keypair)> {
+    let keypair = Keypair::generate()?;
+    let options = ClientOptions {
+        keypair: Some(keypair.clone()),
+        server_public_key: Some(server_public_key.to_vec()),
+        http2: Some(connection.clone()),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    let mut stream = event_loop.run();
+    client.connect().await?;
+    wait_for_event(&mut stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    Ok((client, stream, keypair))
+}
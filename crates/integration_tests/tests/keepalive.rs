@@ -0,0 +1,55 @@
+use crate::test_utils::{
+    server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    Client, ClientOptions, KeepaliveOptions, NetworkTransport,
+};
+use polysig_protocol::{Event, Keypair};
+use std::time::Duration;
+
+/// Connection stays open past the idle timeout when the
+/// server replies to pings.
+#[tokio::test]
+async fn integration_keepalive() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let keepalive = KeepaliveOptions {
+        ping_interval: 1,
+        idle_timeout: 2,
+    };
+    let options = ClientOptions {
+        keypair: Some(Keypair::generate()?),
+        server_public_key: Some(server_public_key),
+        keepalive: Some(keepalive),
+        ..Default::default()
+    };
+    let url = options.url(&server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    client.connect().await?;
+    let mut stream = event_loop.run();
+    wait_for_event(&mut stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // No messages other than the ping replies are received
+    // so the stream would fail with an idle timeout if the
+    // server did not reply
+    let idle = Duration::from_secs(4);
+    match tokio::time::timeout(idle, stream.next()).await {
+        Err(_) => {}
+        Ok(Some(Err(e))) => return Err(e.into()),
+        Ok(Some(Ok(event))) => bail!("unexpected event {:?}", event),
+        Ok(None) => bail!("event stream closed"),
+    }
+
+    client.close().await?;
+    Ok(())
+}
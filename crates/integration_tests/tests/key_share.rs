@@ -0,0 +1,144 @@
+use anyhow::Result;
+use polysig_driver::{
+    encrypt::EncryptedKeyShare,
+    frost::ed25519::{Identifier, KeyShare as ThresholdKeyShare},
+    frost_ed25519::keys::{
+        generate_with_dealer, IdentifierList, KeyPackage,
+    },
+    Error, KeyShare, KeySharePolicy,
+};
+use rand::{rngs::OsRng, RngCore};
+
+fn key_share() -> Result<KeyShare> {
+    let (shares, public_key_package) = generate_with_dealer(
+        3,
+        2,
+        IdentifierList::Default,
+        &mut OsRng,
+    )?;
+    let id: Identifier = 1u16.try_into()?;
+    let key_package =
+        KeyPackage::try_from(shares.get(&id).unwrap().clone())?;
+    let key_share: ThresholdKeyShare =
+        (key_package, public_key_package);
+    Ok((&key_share).try_into()?)
+}
+
+#[test]
+fn key_share_encode_decode() -> Result<()> {
+    let key_share = key_share()?;
+    assert_eq!("FROST ED25519 KEY SHARE", key_share.tag()?);
+
+    let encoded = key_share.encode()?;
+    let decoded = KeyShare::decode(&encoded)?;
+    assert_eq!(key_share.version, decoded.version);
+    assert_eq!(key_share.contents, decoded.contents);
+
+    let decoded: ThresholdKeyShare = (&decoded).try_into()?;
+    let id: Identifier = 1u16.try_into()?;
+    assert_eq!(&id, decoded.0.identifier());
+
+    // Contents must be PEM-encoded
+    let invalid = KeyShare {
+        version: key_share.version,
+        contents: "not a key share".to_owned(),
+        policy: None,
+    };
+    assert!(invalid.encode().is_err());
+    assert!(
+        KeyShare::decode(&serde_json::to_string(&invalid)?).is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn key_share_policy() -> Result<()> {
+    let mut key_share = key_share()?;
+
+    // Containers without a policy are unchanged
+    let encoded = serde_json::to_string(&key_share)?;
+    assert!(!encoded.contains("policy"));
+    assert_eq!(None, KeyShare::decode(&encoded)?.policy);
+
+    let policy = KeySharePolicy {
+        expires_at: Some(1_000),
+        derivation_paths: vec!["m/44'/60'".to_owned()],
+        chains: vec!["eip155:1".to_owned()],
+    };
+    key_share.policy = Some(policy.clone());
+    let decoded = KeyShare::decode(&key_share.encode()?)?;
+    assert_eq!(Some(&policy), decoded.policy.as_ref());
+
+    let path = Some("m/44h/60h/0h/0/0");
+    let chain = Some("eip155:1");
+    policy.check(999, path, chain)?;
+    assert!(matches!(
+        policy.check(1_000, path, chain),
+        Err(Error::KeyShareExpired(1_000))
+    ));
+    assert!(matches!(
+        policy.check(0, Some("m/44'/0'/0'"), chain),
+        Err(Error::DerivationPathNotAllowed(_))
+    ));
+    // Prefixes match whole path components
+    assert!(matches!(
+        policy.check(0, Some("m/44'/600'"), chain),
+        Err(Error::DerivationPathNotAllowed(_))
+    ));
+    // Signing without a derivation path uses the root key
+    assert!(matches!(
+        policy.check(0, None, chain),
+        Err(Error::DerivationPathNotAllowed(_))
+    ));
+    assert!(matches!(
+        policy.check(0, path, Some("eip155:137")),
+        Err(Error::ChainNotAllowed(_))
+    ));
+    assert!(matches!(
+        policy.check(0, path, None),
+        Err(Error::ChainRequired)
+    ));
+
+    // An empty policy allows everything
+    KeySharePolicy::default().check(u64::MAX, None, None)?;
+
+    Ok(())
+}
+
+#[test]
+fn key_share_encrypt_decrypt() -> Result<()> {
+    let key_share = key_share()?;
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+
+    let encrypted = EncryptedKeyShare::encrypt(&key_share, &key)?;
+    let decrypted = encrypted.decrypt(&key)?;
+    assert_eq!(key_share.contents, decrypted.contents);
+
+    // Containers can be stored as JSON
+    let encrypted: EncryptedKeyShare =
+        serde_json::from_str(&serde_json::to_string(&encrypted)?)?;
+    encrypted.decrypt(&key)?;
+
+    let mut wrong_key = key;
+    wrong_key[0] ^= 1;
+    assert!(matches!(
+        encrypted.decrypt(&wrong_key),
+        Err(Error::DecryptKeyShare)
+    ));
+
+    let mut tampered = encrypted.clone();
+    tampered.ciphertext[0] ^= 1;
+    assert!(matches!(
+        tampered.decrypt(&key),
+        Err(Error::DecryptKeyShare)
+    ));
+
+    assert!(matches!(
+        EncryptedKeyShare::encrypt(&key_share, &key[..16]),
+        Err(Error::EncryptionKeyLength(16))
+    ));
+
+    Ok(())
+}
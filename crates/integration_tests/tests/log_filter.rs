@@ -0,0 +1,151 @@
+use anyhow::{bail, Result};
+use axum_server::Handle;
+use polysig_client::LogFilter;
+use polysig_relay_server::{
+    LogFilter as ServerLogFilter, RelayServer, ServerConfig,
+    LOG_FILTER_PATH,
+};
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tracing::Level;
+use tracing_subscriber::layer::SubscriberExt;
+
+const TOKEN: &str = "admin-secret";
+
+/// Change the log filter of a client at runtime.
+#[test]
+fn log_filter_client() -> Result<()> {
+    let (layer, log_filter) = LogFilter::new("warn")?;
+    let _subscriber = tracing_subscriber::registry().with(layer);
+    assert_eq!("warn", log_filter.directives()?);
+
+    log_filter.set("polysig_client=debug")?;
+    assert_eq!("polysig_client=debug", log_filter.directives()?);
+
+    // Verbose logs for a single session
+    let session_id = polysig_protocol::uuid::Uuid::new_v4();
+    log_filter.enable_session(&session_id, Level::TRACE)?;
+    let directives = log_filter.directives()?;
+    assert!(directives.contains("polysig_client=debug"));
+    assert!(directives.contains(&session_id.to_string()));
+
+    assert!(matches!(
+        log_filter.set("polysig_client=loud"),
+        Err(polysig_client::Error::LogFilter(_))
+    ));
+    Ok(())
+}
+
+/// Change the log filter of a server with admin requests.
+#[tokio::test]
+async fn log_filter_server() -> Result<()> {
+    let dir = std::env::temp_dir()
+        .join(format!("polysig-admin-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let token_file = dir.join("admin.token");
+    std::fs::write(&token_file, format!("{}\n", TOKEN))?;
+
+    let (layer, log_filter) =
+        ServerLogFilter::new("polysig_relay_server=info")?;
+    let _subscriber = tracing_subscriber::registry().with(layer);
+
+    let (mut config, keypair) =
+        ServerConfig::load("tests/config.toml").await?;
+    config.admin_token_file = Some(token_file);
+    let server = RelayServer::builder()
+        .config(config)
+        .keypair(keypair)
+        .log_filter(log_filter.clone())
+        .build()
+        .await?;
+    let (relay, _embed) = server.embed().await?;
+
+    let handle = Handle::new();
+    let addr: SocketAddr = "127.0.0.1:0".parse()?;
+    let server_handle = handle.clone();
+    tokio::task::spawn(async move {
+        axum_server::bind(addr)
+            .handle(server_handle)
+            .serve(relay.into_make_service())
+            .await
+            .expect("failed to serve relay");
+    });
+    let Some(addr) = handle.listening().await else {
+        bail!("relay is not listening");
+    };
+
+    // Requests must present the admin token
+    let (status, _) = request(addr, "GET", None, "").await?;
+    assert_eq!(401, status);
+    let (status, _) = request(addr, "GET", Some("wrong"), "").await?;
+    assert_eq!(401, status);
+
+    let (status, body) =
+        request(addr, "GET", Some(TOKEN), "").await?;
+    assert_eq!(200, status);
+    assert_eq!("polysig_relay_server=info", body);
+
+    let (status, body) = request(
+        addr,
+        "PUT",
+        Some(TOKEN),
+        "polysig_relay_server=trace",
+    )
+    .await?;
+    assert_eq!(200, status);
+    assert_eq!("polysig_relay_server=trace", body);
+    assert_eq!(
+        "polysig_relay_server=trace",
+        log_filter.directives()?
+    );
+
+    // Invalid directives do not change the filter
+    let (status, _) =
+        request(addr, "PUT", Some(TOKEN), "polysig=loud").await?;
+    assert_eq!(400, status);
+    assert_eq!(
+        "polysig_relay_server=trace",
+        log_filter.directives()?
+    );
+
+    handle.shutdown();
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// Send an HTTP/1.1 request to the log filter endpoint.
+async fn request(
+    addr: SocketAddr,
+    method: &str,
+    token: Option<&str>,
+    body: &str,
+) -> Result<(u16, String)> {
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        method, LOG_FILTER_PATH, addr
+    );
+    if let Some(token) = token {
+        request.push_str(&format!(
+            "Authorization: Bearer {}\r\n",
+            token
+        ));
+    }
+    request
+        .push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+    request.push_str(body);
+
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+
+    let Some((head, body)) = response.split_once("\r\n\r\n") else {
+        bail!("invalid response");
+    };
+    let status =
+        head.split(' ').nth(1).unwrap_or_default().parse::<u16>()?;
+    Ok((status, body.to_owned()))
+}
@@ -1,7 +1,62 @@
-#[cfg(any(feature = "cggmp", feature = "frost-ed25519"))]
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
 mod protocols;
 
+#[cfg(feature = "frost-ed25519")]
+mod key_share;
+
+#[cfg(feature = "frost-ed25519")]
+mod signer_server;
+
+mod account_registry;
+
+mod capabilities;
+
+mod compat;
+
+mod embedded_server;
+
+mod error_kind;
+
+mod http2;
+
+mod keepalive;
+
+mod log_filter;
+
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
+mod digest;
+
+mod party_options;
+
+mod payload_encoding;
+
+mod server_config;
+
+mod server_key_rotation;
+
+mod server_trust;
+
+mod subprotocol;
+
+mod superseded;
+
 // Single-party signers.
 mod signers;
 
+#[cfg(all(feature = "cggmp", feature = "frost"))]
+mod typescript;
+
+#[cfg(all(feature = "bls", feature = "vss"))]
+mod vss;
+
 pub mod test_utils;
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use polysig_driver::{Error, PartyOptions};
+
+/// Builds party options with verifiers in participant order.
+#[test]
+fn party_options_builder() -> Result<()> {
+    let keys: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 32]).collect();
+
+    let party = PartyOptions::builder(keys[1].clone())
+        .verifier(keys[0].clone(), "a")
+        .verifier(keys[1].clone(), "b")
+        .verifier(keys[2].clone(), "c")
+        .initiator(true)
+        .threshold(2)
+        .build()?;
+    assert_eq!(1, party.party_index());
+    assert_eq!(keys.as_slice(), party.participants());
+    assert_eq!(&["a", "b", "c"], party.verifiers());
+    assert!(party.is_initiator());
+
+    // Participants and verifiers must have the same length
+    let result = PartyOptions::builder(keys[0].clone())
+        .verifiers(keys.clone(), vec!["a", "b"])
+        .build();
+    assert!(matches!(
+        result,
+        Err(Error::ParticipantVerifierLength(3, 2))
+    ));
+
+    // Participants must be unique
+    let result = PartyOptions::builder(keys[0].clone())
+        .verifier(keys[0].clone(), "a")
+        .verifier(keys[1].clone(), "b")
+        .verifier(keys[0].clone(), "c")
+        .build();
+    assert!(matches!(result, Err(Error::DuplicateParticipant(2))));
+
+    // Party must be a participant
+    let result = PartyOptions::builder(vec![9; 32])
+        .verifier(keys[0].clone(), "a")
+        .build();
+    assert!(matches!(result, Err(Error::NotVerifyingParty)));
+
+    // Threshold cannot exceed the number of participants
+    let result = PartyOptions::builder(keys[0].clone())
+        .verifiers(keys.clone(), vec!["a", "b", "c"])
+        .threshold(4)
+        .build();
+    assert!(matches!(
+        result,
+        Err(Error::Protocol(
+            polysig_protocol::Error::InvalidParameters(3, 4)
+        ))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use polysig_protocol::{Encoding, Error, JsonMessage};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct RoundMessage {
+    round: u16,
+    sender: String,
+    body: Vec<u8>,
+}
+
+fn message() -> RoundMessage {
+    RoundMessage {
+        round: 2,
+        sender: "party-1".to_owned(),
+        body: (0..=255).collect(),
+    }
+}
+
+/// Serialize payloads with each serialized encoding.
+#[test]
+fn payload_encoding_round_trip() -> Result<()> {
+    let value = message();
+    let json = JsonMessage::serialize_with(&value, Encoding::Json)?;
+    for encoding in
+        [Encoding::Json, Encoding::Cbor, Encoding::MessagePack]
+    {
+        assert!(encoding.is_serialized());
+        let contents = JsonMessage::serialize_with(&value, encoding)?;
+        if encoding != Encoding::Json {
+            assert!(contents.len() < json.len());
+        }
+        let message = JsonMessage::new(contents, encoding);
+        assert_eq!(encoding, message.encoding());
+        assert_eq!(value, message.deserialize::<RoundMessage>()?);
+    }
+
+    // Messages are JSON by default
+    let message: JsonMessage = json.into();
+    assert_eq!(Encoding::Json, message.encoding());
+    assert_eq!(value, message.deserialize::<RoundMessage>()?);
+
+    assert!(!Encoding::Blob.is_serialized());
+    assert!(matches!(
+        JsonMessage::serialize_with(&value, Encoding::Blob),
+        Err(Error::UnsupportedEncoding(Encoding::Blob))
+    ));
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use crate::test_utils::{server_public_key, spawn_server};
+use anyhow::Result;
+use polysig_client::{
+    bls::{dkg, sign},
+    ServerOptions, SessionOptions,
+};
+use polysig_driver::bls::{
+    aggregate_public_keys, aggregate_signatures, verify_aggregate,
+    Identifier, KeyShare, PartyOptions, Signature,
+};
+use polysig_protocol::{Keypair, Parameters};
+use std::net::SocketAddr;
+
+const MESSAGE: &[u8] = b"this is the message that is sent out";
+
+fn session_options(
+    n: u16,
+    params: &Parameters,
+    server: &ServerOptions,
+) -> Result<(Vec<SessionOptions>, Vec<Vec<u8>>)> {
+    let mut options = Vec::new();
+    let mut public_keys = Vec::new();
+    for _ in 0..n {
+        let keypair = Keypair::generate()?;
+        public_keys.push(keypair.public_key().to_vec());
+        options.push(SessionOptions {
+            keypair,
+            parameters: params.clone(),
+            server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        });
+    }
+    Ok((options, public_keys))
+}
+
+async fn run_dkg(
+    t: u16,
+    n: u16,
+    server: &ServerOptions,
+) -> Result<Vec<KeyShare>> {
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+    let (options, public_keys) = session_options(n, &params, server)?;
+
+    let mut tasks = Vec::new();
+    for (index, opts) in options.into_iter().enumerate() {
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            public_keys.clone(),
+        )?;
+        tasks.push(tokio::task::spawn(async move {
+            Ok::<_, anyhow::Error>(dkg(opts, party).await?)
+        }));
+    }
+
+    let mut key_shares = Vec::new();
+    for result in futures::future::try_join_all(tasks).await? {
+        key_shares.push(result?);
+    }
+    Ok(key_shares)
+}
+
+async fn run_sign(
+    key_shares: Vec<KeyShare>,
+    server: &ServerOptions,
+) -> Result<Vec<Signature>> {
+    let n = key_shares.len() as u16;
+    let params = Parameters {
+        parties: n,
+        threshold: key_shares[0].threshold(),
+    };
+    let (options, public_keys) = session_options(n, &params, server)?;
+    let identifiers: Vec<Identifier> =
+        key_shares.iter().map(|k| k.identifier()).collect();
+
+    let mut tasks = Vec::new();
+    for (index, (opts, key_share)) in
+        options.into_iter().zip(key_shares).enumerate()
+    {
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            public_keys.clone(),
+        )?;
+        let ids = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            Ok::<_, anyhow::Error>(
+                sign(opts, party, ids, key_share, MESSAGE.to_vec())
+                    .await?,
+            )
+        }));
+    }
+
+    let mut signatures = Vec::new();
+    for result in futures::future::try_join_all(tasks).await? {
+        signatures.push(result?);
+    }
+    Ok(signatures)
+}
+
+async fn server_options(addr: SocketAddr) -> Result<ServerOptions> {
+    Ok(ServerOptions {
+        server_url: format!("ws://{}", addr),
+        server_public_key: server_public_key().await?,
+        pattern: None,
+    })
+}
+
+/// BLS DKG followed by signing with a subset of the
+/// participants (2-of-3).
+#[tokio::test]
+async fn bls_dkg_sign_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = server_options(addr).await?;
+
+    let key_shares = run_dkg(2, 3, &server).await?;
+    assert_eq!(3, key_shares.len());
+
+    let public_key = *key_shares[0].public_key();
+    for key_share in &key_shares {
+        assert_eq!(&public_key, key_share.public_key());
+    }
+
+    // Sign with the first and last participants
+    let signers = vec![key_shares[0].clone(), key_shares[2].clone()];
+    let signatures = run_sign(signers, &server).await?;
+    assert_eq!(2, signatures.len());
+
+    let signature = signatures[0];
+    for other in &signatures {
+        assert_eq!(&signature, other);
+    }
+    public_key.verify(MESSAGE, &signature)?;
+
+    let encoded = signature.to_bytes();
+    assert_eq!(signature, Signature::from_bytes(&encoded)?);
+    assert!(public_key.verify(b"other message", &signature).is_err());
+
+    Ok(())
+}
+
+/// Aggregate signatures from two threshold groups that
+/// signed the same message.
+#[tokio::test]
+async fn bls_aggregate_2_2() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = server_options(addr).await?;
+
+    let first = run_dkg(2, 2, &server).await?;
+    let second = run_dkg(2, 2, &server).await?;
+    let public_keys =
+        vec![*first[0].public_key(), *second[0].public_key()];
+
+    let first = run_sign(first, &server).await?;
+    let second = run_sign(second, &server).await?;
+
+    let signature = aggregate_signatures(&[first[0], second[0]])?;
+    verify_aggregate(&public_keys, MESSAGE, &signature)?;
+
+    let public_key = aggregate_public_keys(&public_keys)?;
+    public_key.verify(MESSAGE, &signature)?;
+
+    Ok(())
+}
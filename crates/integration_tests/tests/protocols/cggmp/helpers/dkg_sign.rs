@@ -53,12 +53,31 @@ pub(super) async fn run_dkg(
     n: u16,
     server: &str,
     server_public_key: Vec<u8>,
+) -> Result<(ServerOptions, Vec<KeyShare>, Vec<SigningKey>)> {
+    let (signers, verifiers) = make_signers(n as usize);
+    run_dkg_with_signers(
+        t,
+        n,
+        server,
+        server_public_key,
+        signers,
+        verifiers,
+    )
+    .await
+}
+
+pub(super) async fn run_dkg_with_signers(
+    t: u16,
+    n: u16,
+    server: &str,
+    server_public_key: Vec<u8>,
+    signers: Vec<SigningKey>,
+    verifiers: Vec<VerifyingKey>,
 ) -> Result<(ServerOptions, Vec<KeyShare>, Vec<SigningKey>)> {
     let params = Parameters {
         parties: n,
         threshold: t,
     };
-    let (signers, verifiers) = make_signers(n as usize);
     let server = ServerOptions {
         server_url: server.to_owned(),
         server_public_key: server_public_key.clone(),
@@ -82,6 +101,14 @@ pub(super) async fn run_dkg(
             keypair,
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         });
     }
 
@@ -176,11 +203,27 @@ pub(super) async fn sign_t_2(
             keypair: keypairs.first().unwrap().clone(),
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         },
         SessionOptions {
             keypair: keypairs.last().unwrap().clone(),
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         },
     ];
 
@@ -17,11 +17,13 @@ use std::pin::Pin;
 mod derived_keys;
 mod dkg_sign;
 mod drivers;
+mod orchestrator;
 mod reshare;
 
 pub use derived_keys::*;
 pub use dkg_sign::*;
 pub use drivers::*;
+pub use orchestrator::*;
 pub use reshare::*;
 
 pub fn make_signing_message() -> Result<PrehashedMessage> {
@@ -0,0 +1,160 @@
+use super::{dkg_sign::run_dkg_with_signers, make_signers};
+use anyhow::Result;
+use polysig_client::{
+    cggmp::{
+        Orchestrator, OrchestratorOptions, SignOutcome, SignRequest,
+    },
+    SessionOptions,
+};
+use polysig_driver::{
+    cggmp::{Participant, PartyOptions},
+    k256::ecdsa::{self, signature::hazmat::PrehashVerifier},
+    synedrion::{PrehashedMessage, SessionId},
+};
+use polysig_protocol::{Keypair, Parameters};
+use rand::{rngs::OsRng, Rng};
+use std::collections::{BTreeSet, HashMap};
+
+const ACCOUNTS: [&str; 2] = ["cold", "hot"];
+
+pub async fn run_orchestrator_2_2(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let t = 2;
+    let n = 2;
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+
+    // Generate a key share for each account with the same signers
+    let (signers, verifiers) = make_signers(n as usize);
+    let mut accounts = Vec::new();
+    for _ in ACCOUNTS {
+        let (server, key_shares, _) = run_dkg_with_signers(
+            t,
+            n,
+            server,
+            server_public_key.clone(),
+            signers.clone(),
+            verifiers.clone(),
+        )
+        .await?;
+        accounts.push((server, key_shares));
+    }
+    let server = accounts[0].0.clone();
+
+    // Sign more messages than the concurrency limit
+    let rng = &mut OsRng;
+    let requests = (0..3)
+        .map(|index| {
+            let seed: [u8; 32] = rng.gen();
+            let message: PrehashedMessage = rng.gen();
+            SignRequest {
+                id: format!("request-{}", index),
+                account: ACCOUNTS[index % 2].to_owned(),
+                session_id: SessionId::from_seed(&seed),
+                message,
+            }
+        })
+        .collect::<Vec<_>>();
+    let options = OrchestratorOptions {
+        concurrency: 2,
+        ..Default::default()
+    };
+
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<Result<Vec<_>, _>>()?;
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+    let selected_parties =
+        verifiers.iter().cloned().collect::<BTreeSet<_>>();
+
+    let mut tasks = Vec::new();
+    for (index, (keypair, signer)) in
+        keypairs.into_iter().zip(signers.into_iter()).enumerate()
+    {
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            verifiers.clone(),
+        )?;
+        let verifier = signer.verifying_key().clone();
+        let participant = Participant::new(signer, verifier, party)?;
+        let key_shares = ACCOUNTS
+            .iter()
+            .zip(&accounts)
+            .map(|(account, (_, key_shares))| {
+                (
+                    account.to_string(),
+                    key_shares[index].to_key_share(&selected_parties),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let orchestrator = Orchestrator::new(
+            SessionOptions {
+                keypair,
+                parameters: params.clone(),
+                server: server.clone(),
+                policy: None,
+                progress: None,
+                cancel: None,
+                key_share_policy: None,
+                signing_context: None,
+                attestation: None,
+                round_timeout: None,
+                keepalive: None,
+            },
+            participant,
+            key_shares,
+            options,
+        );
+
+        // Requests must reference known accounts
+        let unknown = SignRequest {
+            account: "unknown".to_owned(),
+            ..requests[0].clone()
+        };
+        assert!(matches!(
+            orchestrator.sign(vec![unknown]).await,
+            Err(polysig_client::Error::UnknownAccount(_))
+        ));
+
+        let requests = requests.clone();
+        tasks.push(tokio::task::spawn(async move {
+            Ok::<_, anyhow::Error>(orchestrator.sign(requests).await?)
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    for result in results {
+        let outcomes = result?;
+        assert_eq!(requests.len(), outcomes.len());
+        for request in &requests {
+            let account = ACCOUNTS
+                .iter()
+                .position(|a| *a == request.account)
+                .unwrap();
+            let verifying_key =
+                accounts[account].1[0].verifying_key().clone();
+            let Some(SignOutcome::Signed(signature)) =
+                outcomes.get(&request.id)
+            else {
+                panic!("request {} was not signed", request.id);
+            };
+            let (signature, _): (
+                ecdsa::Signature,
+                ecdsa::RecoveryId,
+            ) = signature.clone().try_into()?;
+            verifying_key
+                .verify_prehash(&request.message, &signature)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use polysig_client::{
-    cggmp::{dkg, reshare, sign},
+    cggmp::{dkg, reshare, reshare_batch, sign, ReshareAccount},
     ServerOptions, SessionOptions,
 };
 use polysig_driver::{
@@ -58,6 +58,61 @@ pub async fn run_dkg_reshare_2_2_to_3_4(
     Ok(())
 }
 
+pub async fn run_dkg_reshare_batch_2_2_to_3_4(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 2;
+    let t = 2;
+    let accounts = 2;
+
+    let mut account_key_shares = Vec::new();
+    for _ in 0..accounts {
+        let key_shares =
+            run_dkg(t, n, server, &server_public_key).await?;
+        assert_eq!(n as usize, key_shares.len());
+        account_key_shares.push(key_shares);
+    }
+
+    let new_t = 3;
+    let new_n = 4;
+
+    let (new_account_key_shares, new_signers) = run_reshare_batch(
+        server,
+        &server_public_key,
+        account_key_shares.clone(),
+        t as usize,
+        new_t,
+        new_n,
+    )
+    .await?;
+
+    assert_eq!(accounts, new_account_key_shares.len());
+
+    for (old_key_shares, new_key_shares) in account_key_shares
+        .into_iter()
+        .zip(new_account_key_shares.into_iter())
+    {
+        assert_eq!(new_n, new_key_shares.len());
+        assert_eq!(
+            old_key_shares.first().unwrap().verifying_key(),
+            new_key_shares.first().unwrap().verifying_key()
+        );
+
+        run_sign(
+            new_t as u16,
+            new_n as u16,
+            server,
+            &server_public_key,
+            new_signers.clone(),
+            new_key_shares,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 async fn run_dkg(
     t: u16,
     n: u16,
@@ -92,6 +147,14 @@ async fn run_dkg(
             keypair,
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         });
     }
 
@@ -178,6 +241,14 @@ async fn run_reshare(
             keypair,
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         });
     }
 
@@ -228,6 +299,117 @@ async fn run_reshare(
     Ok((key_shares, signers))
 }
 
+async fn run_reshare_batch(
+    server: &str,
+    server_public_key: &[u8],
+    account_old_holders: Vec<Vec<KeyShare>>,
+    old_t: usize,
+    new_t: usize,
+    new_n: usize,
+) -> Result<(Vec<Vec<KeyShare>>, Vec<SigningKey>)> {
+    let params = Parameters {
+        parties: new_n as u16,
+        threshold: new_t as u16,
+    };
+
+    let (signers, verifiers) = make_signers(new_n as usize);
+    let server = ServerOptions {
+        server_url: server.to_owned(),
+        server_public_key: server_public_key.to_vec(),
+        pattern: None,
+    };
+
+    let rng = &mut OsRng;
+    let session_ids = account_old_holders
+        .iter()
+        .map(|_| {
+            let session_id: [u8; 32] = rng.gen();
+            SessionId::from_seed(&session_id)
+        })
+        .collect::<Vec<_>>();
+
+    let mut session_options = Vec::new();
+    let mut public_keys = Vec::new();
+
+    for _ in 0..new_n {
+        let keypair = Keypair::generate()?;
+        public_keys.push(keypair.public_key().to_vec());
+
+        session_options.push(SessionOptions {
+            keypair,
+            parameters: params.clone(),
+            server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        });
+    }
+
+    let mut tasks = Vec::new();
+
+    for (index, (opts, signer)) in session_options
+        .into_iter()
+        .zip(signers.clone().into_iter())
+        .enumerate()
+    {
+        let participants =
+            public_keys.iter().cloned().collect::<Vec<_>>();
+        let is_initiator = index == 0;
+        let public_key = participants.get(index).unwrap().to_vec();
+
+        let party = PartyOptions::new(
+            public_key,
+            participants,
+            is_initiator,
+            verifiers.clone(),
+        )?;
+
+        let accounts = account_old_holders
+            .iter()
+            .zip(session_ids.iter())
+            .map(|(old_keys, session_id)| ReshareAccount {
+                session_id: session_id.clone(),
+                account_verifying_key: old_keys
+                    .first()
+                    .unwrap()
+                    .verifying_key()
+                    .to_owned(),
+                key_share: old_keys.get(index).cloned(),
+            })
+            .collect::<Vec<_>>();
+
+        let verifier = signer.verifying_key().clone();
+        tasks.push(tokio::task::spawn(async move {
+            let key_shares = reshare_batch(
+                opts,
+                Participant::new(signer, verifier, party)?,
+                accounts,
+                old_t,
+                new_t,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(key_shares)
+        }));
+    }
+
+    // Gather the key shares for each account
+    let mut account_key_shares: Vec<Vec<KeyShare>> =
+        session_ids.iter().map(|_| Vec::new()).collect();
+    let results = futures::future::try_join_all(tasks).await?;
+    for result in results {
+        for (index, key_share) in result?.into_iter().enumerate() {
+            account_key_shares[index].push(key_share);
+        }
+    }
+
+    Ok((account_key_shares, signers))
+}
+
 async fn run_sign(
     t: u16,
     n: u16,
@@ -264,6 +446,14 @@ async fn run_sign(
             keypair,
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         });
     }
 
@@ -306,16 +496,40 @@ async fn run_sign(
             keypair: first_keypair.clone(),
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         },
         SessionOptions {
             keypair: second_keypair.clone(),
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         },
         SessionOptions {
             keypair: last_keypair.clone(),
             parameters: params.clone(),
             server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
         },
     ];
 
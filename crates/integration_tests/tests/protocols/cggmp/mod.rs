@@ -102,6 +102,26 @@ async fn cggmp_dkg_reshare_2_2_to_3_4() -> Result<()> {
     Ok(())
 }
 
+/// CGGMP DKG for multiple accounts followed by resharing
+/// all the accounts in a single session and signing.
+#[tokio::test]
+async fn cggmp_dkg_reshare_batch_2_2_to_3_4() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    helpers::run_dkg_reshare_batch_2_2_to_3_4(
+        &server,
+        server_public_key,
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// CGGMP DKG followed by signing (2-of-2) using
 /// derived child keys (BIP32).
 #[tokio::test]
@@ -117,3 +137,170 @@ async fn cggmp_dkg_derived_2_2() -> Result<()> {
 
     Ok(())
 }
+
+/// CGGMP DKG for multiple accounts followed by signing
+/// batches of messages using the orchestrator.
+#[tokio::test]
+async fn cggmp_orchestrator_2_2() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    helpers::run_orchestrator_2_2(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// CGGMP key share import and export.
+#[test]
+fn cggmp_key_share_interchange() -> Result<()> {
+    use polysig_driver::{
+        cggmp::{self, ThresholdShare},
+        k256::{
+            ecdsa::SigningKey, elliptic_curve::sec1::ToEncodedPoint,
+        },
+        synedrion::{TestParams, ThresholdKeyShare},
+    };
+    use rand::rngs::OsRng;
+    use std::collections::BTreeSet;
+
+    let (_, verifiers) = helpers::make_signers(3);
+    let ids = verifiers.iter().copied().collect::<BTreeSet<_>>();
+    let signing_key = SigningKey::random(&mut OsRng);
+    let key_shares =
+        ThresholdKeyShare::<TestParams, _>::new_centralized(
+            &mut OsRng,
+            &ids,
+            2,
+            Some(&signing_key),
+        );
+    let verifying_key = signing_key
+        .verifying_key()
+        .to_encoded_point(true)
+        .as_bytes()
+        .to_vec();
+
+    let mut shares = Vec::new();
+    for key_share in key_shares.values() {
+        // Raw synedrion serde output
+        let raw = cggmp::to_synedrion(key_share)?;
+        let imported = cggmp::from_synedrion::<TestParams>(&raw)?;
+        assert_eq!(raw, cggmp::to_synedrion(&imported)?);
+
+        // Plain threshold share
+        let share =
+            ThresholdShare::from_key_share(key_share, &verifiers)?;
+        assert_eq!(2, share.threshold);
+        assert_eq!(verifying_key, share.verifying_key);
+        assert_eq!(3, share.parties.len());
+
+        let encoded = serde_json::to_string(&share)?;
+        let share: ThresholdShare = serde_json::from_str(&encoded)?;
+        let imported =
+            share.into_key_share::<TestParams>(&verifiers)?;
+        assert_eq!(raw, cggmp::to_synedrion(&imported)?);
+        shares.push(share);
+    }
+
+    // Secret share must match the public share of the owner
+    let mut tampered = shares[0].clone();
+    tampered.secret_share = shares[1].secret_share.clone();
+    assert!(matches!(
+        tampered.verify(),
+        Err(cggmp::Error::SharePublicKey)
+    ));
+
+    // Public shares must match the verifying key
+    let mut tampered = shares[0].clone();
+    tampered.verifying_key =
+        verifiers[0].to_encoded_point(true).as_bytes().to_vec();
+    assert!(matches!(
+        tampered.verify(),
+        Err(cggmp::Error::ShareCommitments)
+    ));
+
+    Ok(())
+}
+
+/// CGGMP session identifiers bound to the roster and purpose.
+#[test]
+fn cggmp_session_id_derivation() -> Result<()> {
+    use polysig_driver::cggmp::{session_id_seed, SessionPurpose};
+
+    let (_, verifiers) = helpers::make_signers(3);
+    let nonce = b"meeting";
+    let seed =
+        session_id_seed(&verifiers, 2, SessionPurpose::KeyGen, nonce);
+
+    // Order of the parties does not change the seed
+    let mut reversed = verifiers.clone();
+    reversed.reverse();
+    assert_eq!(
+        seed,
+        session_id_seed(&reversed, 2, SessionPurpose::KeyGen, nonce)
+    );
+
+    // Roster, threshold, purpose and nonce are bound
+    assert_ne!(
+        seed,
+        session_id_seed(
+            &verifiers[..2],
+            2,
+            SessionPurpose::KeyGen,
+            nonce
+        )
+    );
+    assert_ne!(
+        seed,
+        session_id_seed(&verifiers, 3, SessionPurpose::KeyGen, nonce)
+    );
+    assert_ne!(
+        seed,
+        session_id_seed(&verifiers, 2, SessionPurpose::Sign, nonce)
+    );
+    assert_ne!(
+        seed,
+        session_id_seed(
+            &verifiers,
+            2,
+            SessionPurpose::KeyGen,
+            b"other"
+        )
+    );
+
+    let purpose: SessionPurpose = "key-resharing".parse()?;
+    assert_eq!(SessionPurpose::KeyResharing, purpose);
+    assert!("resharing".parse::<SessionPurpose>().is_err());
+
+    Ok(())
+}
+
+/// CGGMP rejects weighted participants.
+#[test]
+fn cggmp_weighted_party() -> Result<()> {
+    use polysig_driver::cggmp::{
+        validate_party, Error, PartyOptions,
+    };
+
+    let (_, verifiers) = helpers::make_signers(3);
+    let participants = (1..=3u8).map(|i| vec![i]).collect::<Vec<_>>();
+
+    let party = PartyOptions::builder(participants[0].clone())
+        .verifiers(participants.clone(), verifiers.clone())
+        .build()?;
+    validate_party(&party)?;
+
+    let party = PartyOptions::builder(participants[0].clone())
+        .verifiers(participants, verifiers)
+        .weights(vec![2, 1, 1])
+        .build()?;
+    assert!(matches!(
+        validate_party(&party),
+        Err(Error::WeightedParty)
+    ));
+
+    Ok(())
+}
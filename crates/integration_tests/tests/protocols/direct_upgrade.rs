@@ -0,0 +1,186 @@
+use crate::test_utils::{
+    server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::Result;
+use polysig_client::{
+    Client, ClientOptions, DirectOptions, EventStream,
+    NetworkTransport,
+};
+use polysig_protocol::{Event, Keypair};
+use std::net::SocketAddr;
+
+/// Payload larger than a single chunk.
+const BLOB_SIZE: usize = 100_000;
+
+/// Peers exchange candidates over the relay then upgrade
+/// to a direct connection.
+#[tokio::test]
+async fn integration_direct_upgrade() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    // Offering peer dials the answering peer
+    let (mut offerer, mut s_o, offerer_key) =
+        new_direct_client(&server, &server_public_key, Vec::new())
+            .await?;
+    let (mut answerer, mut s_a, answerer_key) =
+        new_direct_client(&server, &server_public_key, Vec::new())
+            .await?;
+    upgrade(
+        &mut offerer,
+        &mut s_o,
+        offerer_key.public_key(),
+        &mut answerer,
+        &mut s_a,
+        answerer_key.public_key(),
+    )
+    .await?;
+    offerer.close().await?;
+    answerer.close().await?;
+
+    // Candidates of the offering peer are not reachable so
+    // the answering peer sends candidates to dial instead
+    let unreachable: SocketAddr = "127.0.0.1:1".parse()?;
+    let (mut offerer, mut s_o, offerer_key) = new_direct_client(
+        &server,
+        &server_public_key,
+        vec![unreachable],
+    )
+    .await?;
+    let (mut answerer, mut s_a, answerer_key) =
+        new_direct_client(&server, &server_public_key, Vec::new())
+            .await?;
+    upgrade(
+        &mut offerer,
+        &mut s_o,
+        offerer_key.public_key(),
+        &mut answerer,
+        &mut s_a,
+        answerer_key.public_key(),
+    )
+    .await?;
+    offerer.close().await?;
+    answerer.close().await?;
+
+    Ok(())
+}
+
+/// Clients without direct options relay messages.
+#[tokio::test]
+async fn integration_direct_disabled() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let keypair = Keypair::generate()?;
+    let options = ClientOptions {
+        keypair: Some(keypair),
+        server_public_key: Some(server_public_key),
+        ..Default::default()
+    };
+    let url = options.url(&server);
+    let (mut client, _event_loop) =
+        Client::new(&url, options).await?;
+    let peer_key = Keypair::generate()?;
+    assert!(matches!(
+        client.upgrade_peer(peer_key.public_key()).await,
+        Err(polysig_client::Error::DirectDisabled)
+    ));
+    assert!(!client.is_direct(peer_key.public_key()).await);
+    Ok(())
+}
+
+async fn upgrade(
+    offerer: &mut Client,
+    s_o: &mut EventStream,
+    offerer_key: &[u8],
+    answerer: &mut Client,
+    s_a: &mut EventStream,
+    answerer_key: &[u8],
+) -> Result<()> {
+    offerer.connect_peer(answerer_key).await?;
+    wait_for_event(s_a, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+    wait_for_event(s_o, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    offerer.upgrade_peer(answerer_key).await?;
+    for stream in [&mut *s_o, &mut *s_a] {
+        wait_for_event(stream, |event| {
+            matches!(event, Event::PeerUpgraded { .. })
+        })
+        .await?;
+    }
+    assert!(offerer.is_direct(answerer_key).await);
+    assert!(answerer.is_direct(offerer_key).await);
+
+    // Messages in both directions use the direct connection
+    offerer.send_json(answerer_key, &"hello", None).await?;
+    let Event::JsonMessage {
+        peer_key, message, ..
+    } = wait_for_event(s_a, |event| {
+        matches!(event, Event::JsonMessage { .. })
+    })
+    .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(offerer_key, peer_key);
+    assert_eq!("hello", message.deserialize::<&str>()?);
+
+    answerer.send_blob(offerer_key, blob(), None).await?;
+    let Event::BinaryMessage {
+        peer_key, message, ..
+    } = wait_for_event(s_o, |event| {
+        matches!(event, Event::BinaryMessage { .. })
+    })
+    .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(answerer_key, peer_key);
+    assert_eq!(blob(), message);
+    Ok(())
+}
+
+async fn new_direct_client(
+    server: &str,
+    server_public_key: &[u8],
+    candidates: Vec<SocketAddr>,
+) -> Result<(Client, EventStream, Keypair)> {
+    let keypair = Keypair::generate()?;
+    let options = ClientOptions {
+        keypair: Some(keypair.clone()),
+        server_public_key: Some(server_public_key.to_vec()),
+        direct: Some(DirectOptions {
+            listen: "127.0.0.1:0".parse()?,
+            candidates,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    client.connect().await?;
+    let mut stream = event_loop.run();
+    wait_for_event(&mut stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    Ok((client, stream, keypair))
+}
+
+fn blob() -> Vec<u8> {
+    (0..BLOB_SIZE).map(|i| i as u8).collect()
+}
@@ -0,0 +1,353 @@
+use crate::test_utils::{
+    new_client_with_keypair, server_public_key, spawn_server,
+    wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    faults::{FaultConfig, Faults},
+    Client, ClientOptions, NetworkTransport,
+};
+use polysig_protocol::{Event, Keypair};
+use std::time::{Duration, Instant};
+
+/// Number of messages sent to the peer.
+const MESSAGES: usize = 4;
+
+/// Time to wait for another message from the peer.
+const WAIT: Duration = Duration::from_secs(1);
+
+/// Sends every message twice.
+#[tokio::test]
+async fn integration_faults_duplicate() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (received, _) =
+        send_messages(&server, server_public_key, |_| FaultConfig {
+            duplicate_rate: 1.0,
+            ..Default::default()
+        })
+        .await?;
+    assert_eq!(vec![0, 0, 1, 1, 2, 2, 3, 3], received);
+
+    Ok(())
+}
+
+/// Drops every message.
+#[tokio::test]
+async fn integration_faults_drop() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (received, _) =
+        send_messages(&server, server_public_key, |_| FaultConfig {
+            drop_rate: 1.0,
+            ..Default::default()
+        })
+        .await?;
+    assert!(received.is_empty());
+
+    Ok(())
+}
+
+/// Delays messages to the peer without reordering them.
+#[tokio::test]
+async fn integration_faults_delay() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let delay = Duration::from_millis(250);
+    let (received, elapsed) =
+        send_messages(&server, server_public_key, |peer_key| {
+            FaultConfig {
+                delay: [(peer_key.to_vec(), delay)].into(),
+                ..Default::default()
+            }
+        })
+        .await?;
+    assert_eq!((0..MESSAGES).collect::<Vec<_>>(), received);
+    assert!(elapsed >= delay);
+
+    Ok(())
+}
+
+/// Severs the connection of the initiator when FROST key
+/// generation reaches the second round.
+#[cfg(feature = "frost-ed25519")]
+#[tokio::test]
+async fn integration_faults_sever() -> Result<()> {
+    use polysig_client::{
+        frost::ed25519::dkg::new_driver, wait_for_driver,
+        wait_for_session, Error, ErrorKind, SessionInitiator,
+        SessionParticipant, Transport,
+    };
+    use polysig_driver::frost_ed25519::Identifier;
+    use polysig_protocol::Parameters;
+
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let n = 3;
+    let params = Parameters {
+        parties: n,
+        threshold: 2,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let participants: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+
+    let mut tasks = Vec::new();
+    for (index, keypair) in keypairs.into_iter().enumerate() {
+        let faults = (index == 0).then(|| {
+            Faults::new(FaultConfig {
+                sever_at_round: Some(2),
+                ..Default::default()
+            })
+        });
+        let options = ClientOptions {
+            keypair: Some(keypair),
+            server_public_key: Some(server_public_key.clone()),
+            faults,
+            ..Default::default()
+        };
+        let url = options.url(&server);
+        let participants = participants.clone();
+        let params = params.clone();
+        let identifiers = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let (client, event_loop) =
+                Client::new(&url, options).await?;
+            let mut transport: Transport = client.into();
+            transport.connect().await?;
+            let mut stream = event_loop.run();
+            let (transport, session) = if index == 0 {
+                let handler =
+                    SessionInitiator::new(transport, participants);
+                wait_for_session(&mut stream, handler).await?
+            } else {
+                let handler = SessionParticipant::new(transport);
+                wait_for_session(&mut stream, handler).await?
+            };
+            let driver =
+                new_driver(transport, session, params, identifiers)?;
+            let (_, key_share) =
+                wait_for_driver(&mut stream, driver).await?;
+            Ok::<_, Error>(key_share)
+        }));
+    }
+
+    let error = match tasks.remove(0).await? {
+        Ok(_) => bail!("expected the connection to be severed"),
+        Err(e) => e,
+    };
+    for task in tasks {
+        task.abort();
+    }
+
+    assert!(matches!(
+        &error,
+        Error::Ceremony { source, .. }
+            if matches!(**source, Error::ConnectionClosed)
+    ));
+    let report = error.report().unwrap();
+    assert_eq!(2, report.round_number);
+    assert_eq!(ErrorKind::Transport, error.kind());
+    assert!(error.is_retryable());
+
+    Ok(())
+}
+
+/// Fails FROST key generation when a party drops every
+/// message and the round timeout elapses.
+#[cfg(feature = "frost-ed25519")]
+#[tokio::test]
+async fn integration_faults_round_timeout() -> Result<()> {
+    use polysig_client::{
+        frost::ed25519::dkg::new_driver, wait_for_driver,
+        wait_for_session, Error, ErrorKind, SessionInitiator,
+        SessionParticipant, Transport,
+    };
+    use polysig_driver::frost_ed25519::Identifier;
+    use polysig_protocol::Parameters;
+
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let n = 3;
+    let params = Parameters {
+        parties: n,
+        threshold: 2,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let participants: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+
+    let mut tasks = Vec::new();
+    for (index, keypair) in keypairs.into_iter().enumerate() {
+        // The last party never sends a message to a peer
+        let faults = (index == 2).then(|| {
+            Faults::new(FaultConfig {
+                drop_rate: 1.0,
+                ..Default::default()
+            })
+        });
+        let options = ClientOptions {
+            keypair: Some(keypair),
+            server_public_key: Some(server_public_key.clone()),
+            round_timeout: Some(WAIT),
+            faults,
+            ..Default::default()
+        };
+        let url = options.url(&server);
+        let participants = participants.clone();
+        let params = params.clone();
+        let identifiers = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let (client, event_loop) =
+                Client::new(&url, options).await?;
+            let mut transport: Transport = client.into();
+            transport.connect().await?;
+            let mut stream = event_loop.run();
+            let (transport, session) = if index == 0 {
+                let handler =
+                    SessionInitiator::new(transport, participants);
+                wait_for_session(&mut stream, handler).await?
+            } else {
+                let handler = SessionParticipant::new(transport);
+                wait_for_session(&mut stream, handler).await?
+            };
+            let driver =
+                new_driver(transport, session, params, identifiers)?;
+            let (_, key_share) =
+                wait_for_driver(&mut stream, driver).await?;
+            Ok::<_, Error>(key_share)
+        }));
+    }
+
+    let error = match tasks.remove(0).await? {
+        Ok(_) => bail!("expected the round to time out"),
+        Err(e) => e,
+    };
+    for task in tasks {
+        task.abort();
+    }
+
+    let Error::Ceremony { source, .. } = &error else {
+        bail!("expected a ceremony error, got {:?}", error);
+    };
+    let Error::RoundTimeout { missing, .. } = &**source else {
+        bail!("expected a round timeout, got {:?}", source);
+    };
+    assert_eq!(&vec![3], missing);
+    assert_eq!(ErrorKind::Session, error.kind());
+    assert!(error.is_retryable());
+
+    Ok(())
+}
+
+/// Send numbered messages from a client with faults to a peer
+/// and collect the messages the peer receives.
+///
+/// Returns the received messages and the time until the first
+/// message was received.
+async fn send_messages(
+    server: &str,
+    server_public_key: Vec<u8>,
+    config: impl FnOnce(&[u8]) -> FaultConfig,
+) -> Result<(Vec<usize>, Duration)> {
+    let sender_key = Keypair::generate()?;
+    let receiver_key = Keypair::generate()?;
+
+    let options = ClientOptions {
+        keypair: Some(sender_key.clone()),
+        server_public_key: Some(server_public_key.clone()),
+        faults: Some(Faults::new(config(receiver_key.public_key()))),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut sender, event_loop) = Client::new(&url, options).await?;
+    sender.connect().await?;
+    let mut sender_stream = event_loop.run();
+    wait_for_event(&mut sender_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Drive the event loop for the sender so it
+    // responds to the peer handshake and sends messages
+    let sender_task = tokio::task::spawn(async move {
+        while let Some(event) = sender_stream.next().await {
+            event?;
+        }
+        Ok::<_, polysig_client::Error>(())
+    });
+
+    let (mut receiver, event_loop) =
+        new_client_with_keypair::<anyhow::Error>(
+            server,
+            server_public_key,
+            receiver_key,
+        )
+        .await?;
+    receiver.connect().await?;
+    let mut receiver_stream = event_loop.run();
+    wait_for_event(&mut receiver_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    receiver.connect_peer(sender_key.public_key()).await?;
+    wait_for_event(&mut receiver_stream, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    let started = Instant::now();
+    for value in 0..MESSAGES {
+        sender
+            .send_json(receiver.public_key(), &value, None)
+            .await?;
+    }
+
+    let mut received = Vec::new();
+    let mut elapsed = None;
+    while let Ok(Some(event)) =
+        tokio::time::timeout(WAIT, receiver_stream.next()).await
+    {
+        if let Event::JsonMessage { message, .. } = event? {
+            elapsed.get_or_insert_with(|| started.elapsed());
+            received.push(message.deserialize()?);
+        }
+    }
+
+    sender_task.abort();
+    Ok((received, elapsed.unwrap_or_default()))
+}
@@ -0,0 +1,151 @@
+macro_rules! frost_dealer_sign {
+    () => {
+        pub async fn run_dealer_sign_2_3(
+            server: &str,
+            server_public_key: Vec<u8>,
+        ) -> Result<()> {
+            let n = 3;
+            let t = 2;
+            let identifiers: Vec<Identifier> =
+                (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+            let (signers, _) = make_signers(n as usize);
+            let server = ServerOptions {
+                server_url: server.to_owned(),
+                server_public_key,
+                pattern: None,
+            };
+
+            // Split an existing signing key
+            let signing_key = GroupSigningKey::new(&mut OsRng);
+            let verifying_key = GroupVerifyingKey::from(&signing_key);
+
+            let key_shares = run_dealer(
+                t,
+                n,
+                server.clone(),
+                signers.clone(),
+                identifiers,
+                Some(signing_key),
+            )
+            .await?;
+
+            assert_eq!(n as usize, key_shares.len());
+            for key_share in &key_shares {
+                assert_eq!(
+                    &verifying_key,
+                    key_share.0.verifying_key()
+                );
+                assert_eq!(t, *key_share.0.min_signers());
+            }
+
+            let selected = SelectedSigners::new(
+                t,
+                &[1, 2],
+                signers,
+                key_shares.clone(),
+            )?;
+
+            check_sign(t, n, server, key_shares, selected).await?;
+
+            Ok(())
+        }
+
+        async fn run_dealer(
+            t: u16,
+            n: u16,
+            server: ServerOptions,
+            signers: Vec<SigningKey>,
+            identifiers: Vec<Identifier>,
+            mut signing_key: Option<GroupSigningKey>,
+        ) -> Result<Vec<KeyShare>> {
+            let params = Parameters {
+                parties: n,
+                threshold: t,
+            };
+
+            let verifiers = signers
+                .iter()
+                .map(|s| s.verifying_key().clone())
+                .collect::<Vec<_>>();
+
+            let mut session_options = Vec::new();
+            let mut public_keys = Vec::new();
+
+            for _ in 0..n {
+                let keypair = Keypair::generate()?;
+                public_keys.push(keypair.public_key().to_vec());
+
+                session_options.push(SessionOptions {
+                    keypair,
+                    parameters: params.clone(),
+                    server: server.clone(),
+                    policy: None,
+                    progress: None,
+                    cancel: None,
+                    key_share_policy: None,
+                    signing_context: None,
+                    attestation: None,
+                    round_timeout: None,
+                    keepalive: None,
+                });
+            }
+
+            // First participant is the dealer
+            let dealer = identifiers[0];
+
+            let mut tasks = Vec::new();
+
+            for (index, (opts, signer)) in session_options
+                .into_iter()
+                .zip(signers.into_iter())
+                .enumerate()
+            {
+                let participants =
+                    public_keys.iter().cloned().collect::<Vec<_>>();
+                let is_initiator = index == 0;
+                let public_key =
+                    participants.get(index).unwrap().to_vec();
+
+                let party = PartyOptions::new(
+                    public_key,
+                    participants,
+                    is_initiator,
+                    verifiers.clone(),
+                )?;
+
+                let signing_key = if index == 0 {
+                    signing_key.take()
+                } else {
+                    None
+                };
+
+                let verifier = signer.verifying_key().clone();
+                let ids = identifiers.clone();
+                tasks.push(tokio::task::spawn(async move {
+                    let key_share = deal(
+                        opts,
+                        Participant::new(signer, verifier, party)?,
+                        ids,
+                        dealer,
+                        signing_key,
+                    )
+                    .await?;
+                    Ok::<_, anyhow::Error>(key_share)
+                }));
+            }
+
+            // Gather the key shares
+            let mut key_shares = Vec::new();
+            let results =
+                futures::future::try_join_all(tasks).await?;
+            for result in results {
+                key_shares.push(result?);
+            }
+
+            Ok(key_shares)
+        }
+    };
+}
+
+pub(crate) use frost_dealer_sign;
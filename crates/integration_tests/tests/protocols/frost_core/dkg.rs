@@ -32,6 +32,14 @@ macro_rules! frost_dkg {
                     keypair,
                     parameters: params.clone(),
                     server: server.clone(),
+                    policy: None,
+                    progress: None,
+                    cancel: None,
+                    key_share_policy: None,
+                    signing_context: None,
+                    attestation: None,
+                    round_timeout: None,
+                    keepalive: None,
                 });
             }
 
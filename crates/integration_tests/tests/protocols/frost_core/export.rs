@@ -0,0 +1,48 @@
+macro_rules! frost_key_export {
+    () => {
+        /// Export the group verifying key and key packages
+        /// for key shares from a trusted dealer (2-of-3).
+        pub fn run_key_export_2_3() -> Result<keys::PublicKeyPackage>
+        {
+            let (shares, public_key_package) =
+                keys::generate_with_dealer(
+                    3,
+                    2,
+                    keys::IdentifierList::Default,
+                    &mut OsRng,
+                )?;
+
+            assert_eq!(
+                public_key_package.verifying_key().serialize()?,
+                verifying_key_bytes(&public_key_package)?,
+            );
+
+            for (_, secret_share) in shares {
+                let key_package =
+                    keys::KeyPackage::try_from(secret_share)?;
+                let json = key_package_to_json(&key_package)?;
+
+                // Interchange format identifies the ciphersuite
+                let value: serde_json::Value =
+                    serde_json::from_str(&json)?;
+                assert!(value["header"]["ciphersuite"].is_string());
+
+                assert_eq!(
+                    key_package,
+                    key_package_from_json(&json)?
+                );
+            }
+
+            let json =
+                public_key_package_to_json(&public_key_package)?;
+            assert_eq!(
+                public_key_package,
+                public_key_package_from_json(&json)?
+            );
+
+            Ok(public_key_package)
+        }
+    };
+}
+
+pub(crate) use frost_key_export;
@@ -1,4 +1,8 @@
+pub(crate) mod dealer;
 pub(crate) mod dkg;
+pub(crate) mod export;
+pub(crate) mod refresh;
+pub(crate) mod repair;
 pub(crate) mod sign;
 
 pub fn make_signing_message() -> Vec<u8> {
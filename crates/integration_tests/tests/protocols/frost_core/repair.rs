@@ -0,0 +1,151 @@
+macro_rules! frost_dkg_repair {
+    () => {
+        pub async fn run_dkg_repair_2_3(
+            server: &str,
+            server_public_key: Vec<u8>,
+        ) -> Result<()> {
+            let n = 3;
+            let t = 2;
+            let identifiers: Vec<Identifier> =
+                (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+            let (server, key_shares, signers) = run_dkg(
+                t,
+                n,
+                server,
+                server_public_key,
+                identifiers.clone(),
+            )
+            .await?;
+
+            // Last participant lost their key share
+            let repaired = run_repair(
+                t,
+                n,
+                server,
+                signers,
+                key_shares.clone(),
+                identifiers,
+                (n - 1) as usize,
+            )
+            .await?;
+
+            let lost = key_shares.last().unwrap();
+            assert_eq!(lost.0.identifier(), repaired.0.identifier());
+            assert_eq!(
+                lost.0.signing_share(),
+                repaired.0.signing_share()
+            );
+            assert_eq!(
+                lost.0.verifying_share(),
+                repaired.0.verifying_share()
+            );
+            assert_eq!(lost.1, repaired.1);
+
+            Ok(())
+        }
+
+        async fn run_repair(
+            t: u16,
+            n: u16,
+            server: ServerOptions,
+            signers: Vec<SigningKey>,
+            key_shares: Vec<KeyShare>,
+            identifiers: Vec<Identifier>,
+            lost_index: usize,
+        ) -> Result<KeyShare> {
+            let params = Parameters {
+                parties: n,
+                threshold: t,
+            };
+
+            let verifiers = signers
+                .iter()
+                .map(|s| s.verifying_key().clone())
+                .collect::<Vec<_>>();
+
+            let mut session_options = Vec::new();
+            let mut public_keys = Vec::new();
+
+            for _ in 0..n {
+                let keypair = Keypair::generate()?;
+                public_keys.push(keypair.public_key().to_vec());
+
+                session_options.push(SessionOptions {
+                    keypair,
+                    parameters: params.clone(),
+                    server: server.clone(),
+                    policy: None,
+                    progress: None,
+                    cancel: None,
+                    key_share_policy: None,
+                    signing_context: None,
+                    attestation: None,
+                    round_timeout: None,
+                    keepalive: None,
+                });
+            }
+
+            let repair_identifier = identifiers[lost_index];
+
+            let mut tasks = Vec::new();
+
+            for (index, ((opts, signer), key_share)) in
+                session_options
+                    .into_iter()
+                    .zip(signers.into_iter())
+                    .zip(key_shares.into_iter())
+                    .enumerate()
+            {
+                let participants =
+                    public_keys.iter().cloned().collect::<Vec<_>>();
+                let is_initiator = index == 0;
+                let public_key =
+                    participants.get(index).unwrap().to_vec();
+
+                let party = PartyOptions::new(
+                    public_key,
+                    participants,
+                    is_initiator,
+                    verifiers.clone(),
+                )?;
+
+                let key_share = if index == lost_index {
+                    None
+                } else {
+                    Some(key_share)
+                };
+
+                let verifier = signer.verifying_key().clone();
+                let ids = identifiers.clone();
+                tasks.push(tokio::task::spawn(async move {
+                    let key_share = repair_share(
+                        opts,
+                        Participant::new(signer, verifier, party)?,
+                        ids,
+                        repair_identifier,
+                        key_share,
+                    )
+                    .await?;
+                    Ok::<_, anyhow::Error>(key_share)
+                }));
+            }
+
+            let results =
+                futures::future::try_join_all(tasks).await?;
+            let mut repaired = None;
+            for (index, result) in results.into_iter().enumerate() {
+                let result = result?;
+                if index == lost_index {
+                    repaired = result;
+                } else {
+                    assert!(result.is_none());
+                }
+            }
+
+            Ok(repaired.unwrap())
+        }
+    };
+}
+
+pub(crate) use frost_dkg_repair;
@@ -121,6 +121,107 @@ macro_rules! frost_dkg_sign {
             .await
         }
 
+        pub async fn run_dkg_sign_coordinator_3_5(
+            server: &str,
+            server_public_key: Vec<u8>,
+        ) -> Result<()> {
+            let n = 5;
+            let t = 3;
+            let identifiers: Vec<Identifier> =
+                (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+            let (server, key_shares, signers) =
+                run_dkg(t, n, server, server_public_key, identifiers)
+                    .await?;
+
+            let selected = SelectedSigners::new(
+                t,
+                &[0, 2, 4],
+                signers,
+                key_shares.clone(),
+            )?;
+
+            // Coordinator does not need to be the session initiator
+            let coordinator = selected.identifiers[1];
+            check_sign_with_mode(
+                t,
+                n,
+                server,
+                key_shares,
+                selected,
+                SignatureMode::Coordinator(coordinator),
+            )
+            .await
+        }
+
+        pub async fn run_dkg_sign_roster_3_5(
+            server: &str,
+            server_public_key: Vec<u8>,
+        ) -> Result<()> {
+            let n = 5;
+            let t = 3;
+            let identifiers: Vec<Identifier> =
+                (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+            let (server, key_shares, signers) =
+                run_dkg(t, n, server, server_public_key, identifiers)
+                    .await?;
+
+            let selected = SelectedSigners::new(
+                t,
+                &[1, 3, 4],
+                signers,
+                key_shares.clone(),
+            )?;
+
+            // Roster order is independent of the session order
+            let roster = Roster::new(
+                selected
+                    .public_keys
+                    .iter()
+                    .cloned()
+                    .zip(selected.identifiers.iter().copied())
+                    .rev()
+                    .collect(),
+            );
+
+            // Signers below the threshold are rejected
+            let (public_key, identifier) = (
+                selected.public_keys[0].clone(),
+                selected.identifiers[0],
+            );
+            let below_threshold =
+                Roster::new(vec![(public_key.clone(), identifier)]);
+            assert!(matches!(
+                below_threshold.validate(t, &public_key, &identifier),
+                Err(polysig_client::Error::RosterThreshold(1, 3))
+            ));
+
+            let message = make_signing_message();
+            let signatures = sign_message_with_roster(
+                t,
+                n,
+                server,
+                selected,
+                message.clone(),
+                SignatureMode::Broadcast,
+                Some(roster),
+            )
+            .await?;
+            assert_eq!(t as usize, signatures.len());
+
+            for signature in &signatures {
+                for key_share in &key_shares {
+                    key_share
+                        .1
+                        .verifying_key()
+                        .verify(&message, signature)?;
+                }
+            }
+
+            Ok(())
+        }
+
         pub async fn run_dkg_sign(
             t: u16,
             n: u16,
@@ -158,6 +259,25 @@ macro_rules! frost_dkg_sign {
             server: ServerOptions,
             all_key_shares: Vec<KeyShare>,
             selected: SelectedSigners,
+        ) -> Result<()> {
+            check_sign_with_mode(
+                t,
+                n,
+                server,
+                all_key_shares,
+                selected,
+                SignatureMode::Broadcast,
+            )
+            .await
+        }
+
+        pub async fn check_sign_with_mode(
+            t: u16,
+            n: u16,
+            server: ServerOptions,
+            all_key_shares: Vec<KeyShare>,
+            selected: SelectedSigners,
+            mode: SignatureMode,
         ) -> Result<()> {
             // Prepare group public key for verification after signing
             let verifying_keys = all_key_shares
@@ -180,36 +300,147 @@ macro_rules! frost_dkg_sign {
                 verifying_key,
             );
 
+            let message = make_signing_message();
+            let signatures = sign_message(
+                t,
+                n,
+                server,
+                selected,
+                message.clone(),
+                mode,
+            )
+            .await?;
+            assert_eq!(t as usize, signatures.len());
+
+            for sig in &signatures {
+                // Check that the threshold signature can be verified by
+                // the group public key (the verification key).
+                pubkey_package
+                    .verifying_key()
+                    .verify(&message, &sig)?;
+                // Check that the threshold signature can be verified by
+                // the group public key (the verification key) from
+                // KeyPackage.verifying_key
+                for key_share in &all_key_shares {
+                    key_share
+                        .1
+                        .verifying_key()
+                        .verify(&message, sig)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        pub async fn sign_message(
+            t: u16,
+            n: u16,
+            server: ServerOptions,
+            selected: SelectedSigners,
+            message: Vec<u8>,
+            mode: SignatureMode,
+        ) -> Result<Vec<Signature>> {
+            sign_message_with_roster(
+                t, n, server, selected, message, mode, None,
+            )
+            .await
+        }
+
+        pub async fn sign_message_with_roster(
+            t: u16,
+            n: u16,
+            server: ServerOptions,
+            selected: SelectedSigners,
+            message: Vec<u8>,
+            mode: SignatureMode,
+            roster: Option<Roster>,
+        ) -> Result<Vec<Signature>> {
+            let identifiers = selected.identifiers.clone();
+
+            let mut tasks = Vec::new();
+            for (opts, participant, key_share) in
+                signer_sessions(t, n, server, selected)?
+            {
+                let msg = message.clone();
+                let ids = identifiers.clone();
+                let roster = roster.clone();
+
+                tasks.push(tokio::task::spawn(async move {
+                    let signature = if let Some(roster) = roster {
+                        sign_with_roster(
+                            opts,
+                            participant,
+                            roster,
+                            key_share,
+                            msg,
+                            mode,
+                        )
+                        .await?
+                    } else {
+                        sign_with_mode(
+                            opts,
+                            participant,
+                            ids,
+                            key_share,
+                            msg,
+                            mode,
+                        )
+                        .await?
+                    };
+                    Ok::<_, anyhow::Error>(signature)
+                }));
+            }
+
+            // Gather the signatures
+            let mut signatures = Vec::new();
+            let results =
+                futures::future::try_join_all(tasks).await?;
+            for result in results {
+                signatures.push(result?);
+            }
+
+            Ok(signatures)
+        }
+
+        /// Session options, participant and key share
+        /// for each selected signer.
+        pub fn signer_sessions(
+            t: u16,
+            n: u16,
+            server: ServerOptions,
+            selected: SelectedSigners,
+        ) -> Result<Vec<(SessionOptions, Participant, KeyShare)>> {
             let params = Parameters {
                 parties: n,
                 threshold: t,
             };
 
-            let message = make_signing_message();
-
-            let session_options = selected
+            let mut sessions = Vec::new();
+            for (
+                index,
+                ((keypair, key_share), (signer, verifiers)),
+            ) in selected
                 .keypairs
-                .iter()
-                .map(|keypair| SessionOptions {
-                    keypair: keypair.clone(),
+                .into_iter()
+                .zip(selected.key_shares.into_iter())
+                .zip(selected.signers.into_iter())
+                .enumerate()
+            {
+                let opts = SessionOptions {
+                    keypair,
                     parameters: params.clone(),
                     server: server.clone(),
-                })
-                .collect::<Vec<_>>();
+                    policy: None,
+                    progress: None,
+                    cancel: None,
+                    key_share_policy: None,
+                    signing_context: None,
+                    attestation: None,
+                    round_timeout: None,
+                    keepalive: None,
+                };
 
-            let mut tasks = Vec::new();
-            for (index, ((opts, key_share), (signer, verifiers))) in
-                session_options
-                    .into_iter()
-                    .zip(selected.key_shares.into_iter())
-                    .zip(selected.signers.into_iter())
-                    .enumerate()
-            {
-                let participants = selected
-                    .public_keys
-                    .iter()
-                    .cloned()
-                    .collect::<Vec<_>>();
+                let participants = selected.public_keys.clone();
                 let is_initiator = index == 0;
                 let public_key =
                     participants.get(index).unwrap().to_vec();
@@ -224,41 +455,264 @@ macro_rules! frost_dkg_sign {
                 let verifier = signer.verifying_key().clone();
                 let participant =
                     Participant::new(signer, verifier, party)?;
-                let msg = message.clone();
-                let ids = selected.identifiers.clone();
+                sessions.push((opts, participant, key_share));
+            }
+
+            Ok(sessions)
+        }
+
+        pub async fn run_dkg_sign_batch_2_3(
+            server: &str,
+            server_public_key: Vec<u8>,
+        ) -> Result<()> {
+            let n = 3;
+            let t = 2;
+            let identifiers: Vec<Identifier> =
+                (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+            let (server, key_shares, signers) =
+                run_dkg(t, n, server, server_public_key, identifiers)
+                    .await?;
+
+            let selected = SelectedSigners::new(
+                t,
+                &[0, 2],
+                signers,
+                key_shares.clone(),
+            )?;
+            let identifiers = selected.identifiers.clone();
+
+            let messages = (0..5)
+                .map(|i| format!("vote {}", i).into_bytes())
+                .collect::<Vec<_>>();
 
+            let mut tasks = Vec::new();
+            for (opts, participant, key_share) in
+                signer_sessions(t, n, server, selected)?
+            {
+                let ids = identifiers.clone();
+                let msgs = messages.clone();
                 tasks.push(tokio::task::spawn(async move {
-                    let signature =
-                        sign(opts, participant, ids, key_share, msg)
-                            .await?;
-                    Ok::<_, anyhow::Error>(signature)
+                    let signatures = sign_batch(
+                        opts,
+                        participant,
+                        ids,
+                        key_share,
+                        msgs,
+                        SignatureMode::Broadcast,
+                    )
+                    .await?;
+                    Ok::<_, anyhow::Error>(signatures)
                 }));
             }
 
-            // Gather the signatures
-            let mut signatures = Vec::new();
             let results =
                 futures::future::try_join_all(tasks).await?;
             for result in results {
-                signatures.push(result?);
+                let signatures = result?;
+                assert_eq!(messages.len(), signatures.len());
+                // Signatures are in the same order as the messages
+                for (message, signature) in
+                    messages.iter().zip(signatures.iter())
+                {
+                    key_shares[0]
+                        .1
+                        .verifying_key()
+                        .verify(message, signature)?;
+                }
             }
-            assert_eq!(t as usize, signatures.len());
 
-            for sig in &signatures {
-                // Check that the threshold signature can be verified by
-                // the group public key (the verification key).
-                pubkey_package
-                    .verifying_key()
-                    .verify(&message, &sig)?;
-                // Check that the threshold signature can be verified by
-                // the group public key (the verification key) from
-                // KeyPackage.verifying_key
-                for key_share in &all_key_shares {
-                    key_share
+            Ok(())
+        }
+
+        pub async fn run_dkg_preprocess_sign_2_3(
+            server: &str,
+            server_public_key: Vec<u8>,
+        ) -> Result<()> {
+            let n = 3;
+            let t = 2;
+            let count = 2;
+            let identifiers: Vec<Identifier> =
+                (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+            let (server, key_shares, signers) =
+                run_dkg(t, n, server, server_public_key, identifiers)
+                    .await?;
+
+            // Exchange commitments ahead of time
+            let selected = SelectedSigners::new(
+                t,
+                &[0, 2],
+                signers.clone(),
+                key_shares.clone(),
+            )?;
+            let identifiers = selected.identifiers.clone();
+
+            let mut tasks = Vec::new();
+            for (opts, participant, key_share) in
+                signer_sessions(t, n, server.clone(), selected)?
+            {
+                let ids = identifiers.clone();
+                tasks.push(tokio::task::spawn(async move {
+                    let nonce_store = preprocess(
+                        opts,
+                        participant,
+                        ids,
+                        key_share,
+                        count,
+                    )
+                    .await?;
+                    Ok::<_, anyhow::Error>(nonce_store)
+                }));
+            }
+
+            // Persist the nonces encrypted
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            let mut nonce_stores = Vec::new();
+            for result in futures::future::try_join_all(tasks).await?
+            {
+                let nonce_store = result?;
+                assert_eq!(count as usize, nonce_store.len());
+                nonce_stores.push(nonce_store.encrypt(&key)?);
+            }
+
+            // Sign using each pre-processed nonce
+            for index in 0..count {
+                let selected = SelectedSigners::new(
+                    t,
+                    &[0, 2],
+                    signers.clone(),
+                    key_shares.clone(),
+                )?;
+                let message = format!("vote {}", index).into_bytes();
+
+                let mut tasks = Vec::new();
+                for ((opts, participant, key_share), nonce_store) in
+                    signer_sessions(t, n, server.clone(), selected)?
+                        .into_iter()
+                        .zip(nonce_stores.iter_mut())
+                {
+                    let nonce = NonceStore::take_encrypted(
+                        nonce_store,
+                        &key,
+                        index,
+                        &identifiers,
+                    )?;
+                    let ids = identifiers.clone();
+                    let msg = message.clone();
+                    tasks.push(tokio::task::spawn(async move {
+                        let signature = sign_preprocessed(
+                            opts,
+                            participant,
+                            ids,
+                            key_share,
+                            msg,
+                            nonce,
+                        )
+                        .await?;
+                        Ok::<_, anyhow::Error>(signature)
+                    }));
+                }
+
+                for result in
+                    futures::future::try_join_all(tasks).await?
+                {
+                    let signature = result?;
+                    key_shares[0]
                         .1
                         .verifying_key()
-                        .verify(&message, sig)?;
+                        .verify(&message, &signature)?;
+                }
+            }
+
+            // Nonces must not be used more than once
+            for nonce_store in nonce_stores.iter_mut() {
+                assert!(NonceStore::decrypt(nonce_store, &key)?
+                    .is_empty());
+                assert!(matches!(
+                    NonceStore::take_encrypted(
+                        nonce_store,
+                        &key,
+                        0,
+                        &identifiers,
+                    ),
+                    Err(polysig_driver::Error::Frost(
+                        Error::NonceIndex(0)
+                    ))
+                ));
+            }
+
+            Ok(())
+        }
+
+        /// Sign in memory (2-of-3) where one signer sends the
+        /// signature share of another signer and check the
+        /// invalid share is attributed to the sender.
+        pub fn run_sign_invalid_share_2_3() -> Result<()> {
+            let (shares, public_key_package) =
+                keys::generate_with_dealer(
+                    3,
+                    2,
+                    keys::IdentifierList::Default,
+                    &mut OsRng,
+                )?;
+
+            let mut identifiers = Vec::new();
+            let mut drivers = Vec::new();
+            for (index, (id, secret_share)) in
+                shares.into_iter().take(2).enumerate()
+            {
+                let key_share = (
+                    keys::KeyPackage::try_from(secret_share)?,
+                    public_key_package.clone(),
+                );
+                identifiers.push(id);
+                drivers.push((
+                    NonZeroU16::new((index + 1) as u16).unwrap(),
+                    key_share,
+                ));
+            }
+
+            let mut drivers = drivers
+                .into_iter()
+                .map(|(party_number, key_share)| {
+                    SignatureDriver::new(
+                        party_number,
+                        identifiers.clone(),
+                        2,
+                        key_share,
+                        make_signing_message(),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Exchange commitments
+            let mut messages = Vec::new();
+            for driver in drivers.iter_mut() {
+                messages.extend(driver.proceed()?);
+            }
+            for message in messages {
+                let index = message.receiver().get() as usize - 1;
+                drivers[index].handle_incoming(message)?;
+            }
+
+            // First signer sends to the second signer and
+            // the second signer sends to the first signer
+            let mut messages = Vec::new();
+            for driver in drivers.iter_mut() {
+                messages.extend(driver.proceed()?);
+            }
+            let honest = serde_json::to_value(&messages[0])?;
+            let mut forged = serde_json::to_value(&messages[1])?;
+            forged["body"] = honest["body"].clone();
+            let forged = serde_json::from_value(forged)?;
+
+            match drivers[0].handle_incoming(forged) {
+                Err(Error::InvalidSignatureShare(culprit)) => {
+                    assert_eq!(identifiers[1].serialize(), culprit);
                 }
+                _ => panic!("expected invalid signature share"),
             }
 
             Ok(())
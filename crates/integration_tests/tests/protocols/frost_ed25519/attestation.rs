@@ -0,0 +1,161 @@
+use super::make_signers;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::future::{select_all, try_join_all};
+use polysig_client::{
+    attestation::{Attestation, Evidence},
+    frost::ed25519::dkg,
+    Error, ErrorKind, ServerOptions, SessionOptions,
+};
+use polysig_driver::frost::ed25519::{
+    Identifier, KeyShare, Participant, PartyOptions,
+};
+use polysig_protocol::{Keypair, Parameters};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+type DkgTask = JoinHandle<polysig_client::Result<KeyShare>>;
+
+/// Approved measurement for the test environment.
+const APPROVED: &[u8] = b"approved";
+
+/// Quote is the report data followed by a measurement.
+struct TestAttestation {
+    measurement: &'static [u8],
+}
+
+#[async_trait]
+impl Attestation for TestAttestation {
+    async fn attest(
+        &self,
+        report_data: &[u8; 32],
+    ) -> polysig_client::Result<Evidence> {
+        let mut quote = report_data.to_vec();
+        quote.extend_from_slice(self.measurement);
+        Ok(Evidence {
+            format: "test".to_owned(),
+            quote,
+        })
+    }
+
+    async fn verify(
+        &self,
+        _peer_key: &[u8],
+        evidence: &Evidence,
+        report_data: &[u8; 32],
+    ) -> polysig_client::Result<()> {
+        if evidence.format != "test"
+            || evidence.quote.len() < 32
+            || &evidence.quote[..32] != report_data
+        {
+            return Err(Error::Generic("invalid quote".into()));
+        }
+        if &evidence.quote[32..] != APPROVED {
+            return Err(Error::Generic(
+                "measurement is not approved".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub async fn run_dkg_attestation(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    // Every participant runs in an approved environment
+    let tasks = dkg_tasks(
+        server,
+        server_public_key.clone(),
+        [APPROVED, APPROVED, APPROVED],
+    )?;
+    let key_shares = try_join_all(tasks).await?;
+    for key_share in key_shares {
+        key_share?;
+    }
+
+    // Last participant is not in an approved environment
+    let tasks = dkg_tasks(
+        server,
+        server_public_key,
+        [APPROVED, APPROVED, b"unapproved"],
+    )?;
+
+    // Peers reject the evidence so the session never becomes
+    // active; the unapproved participant keeps waiting
+    let (result, index, remaining) = select_all(tasks).await;
+    for task in remaining {
+        task.abort();
+    }
+    assert_ne!(2, index);
+    let error = result?.unwrap_err();
+    assert!(matches!(error, Error::AttestationRejected(_, _)));
+    let info = error.info();
+    assert_eq!("ATTESTATION_REJECTED", info.code);
+    assert_eq!(ErrorKind::Session, info.kind);
+
+    Ok(())
+}
+
+fn dkg_tasks(
+    server: &str,
+    server_public_key: Vec<u8>,
+    measurements: [&'static [u8]; 3],
+) -> Result<Vec<DkgTask>> {
+    let n = measurements.len() as u16;
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let (signers, verifiers) = make_signers(n as usize);
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    let mut tasks = Vec::new();
+    for (index, ((keypair, signer), measurement)) in keypairs
+        .into_iter()
+        .zip(signers)
+        .zip(measurements)
+        .enumerate()
+    {
+        let options = SessionOptions {
+            keypair,
+            server: ServerOptions {
+                server_url: server.to_owned(),
+                server_public_key: server_public_key.clone(),
+                pattern: None,
+            },
+            parameters: Parameters {
+                parties: n,
+                threshold: 2,
+            },
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: Some(Arc::new(TestAttestation {
+                measurement,
+            })),
+            round_timeout: None,
+            keepalive: None,
+        };
+        let verifier = signer.verifying_key();
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            verifiers.clone(),
+        )?;
+        let participant = Participant::new(signer, verifier, party)?;
+        tasks.push(tokio::task::spawn(dkg(
+            options,
+            participant,
+            identifiers.clone(),
+        )));
+    }
+    Ok(tasks)
+}
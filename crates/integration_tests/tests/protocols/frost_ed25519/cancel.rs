@@ -0,0 +1,91 @@
+use super::make_signers;
+use anyhow::Result;
+use futures::StreamExt;
+use polysig_client::{
+    frost::ed25519::dkg, progress_channel, CancellationToken, Error,
+    ErrorKind, ProgressEvent, ServerOptions, SessionOptions,
+};
+use polysig_driver::frost::ed25519::{
+    Identifier, Participant, PartyOptions,
+};
+use polysig_protocol::{Keypair, Parameters};
+
+pub async fn run_dkg_cancel(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+    let (mut signers, verifiers) = make_signers(n as usize);
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    let cancel = CancellationToken::new();
+    let (progress, mut events) = progress_channel();
+    let options = SessionOptions {
+        keypair: keypairs[0].clone(),
+        server: ServerOptions {
+            server_url: server.to_owned(),
+            server_public_key,
+            pattern: None,
+        },
+        parameters: Parameters {
+            parties: n,
+            threshold: 2,
+        },
+        policy: None,
+        progress: Some(progress),
+        cancel: Some(cancel.clone()),
+        key_share_policy: None,
+        signing_context: None,
+        attestation: None,
+        round_timeout: None,
+        keepalive: None,
+    };
+
+    let signer = signers.remove(0);
+    let verifier = signer.verifying_key();
+    let party = PartyOptions::new(
+        public_keys[0].clone(),
+        public_keys.clone(),
+        true,
+        verifiers,
+    )?;
+    let participant = Participant::new(signer, verifier, party)?;
+
+    // Only the initiator joins so the session never
+    // becomes active
+    let task = tokio::task::spawn(dkg(
+        options.clone(),
+        participant.clone(),
+        identifiers.clone(),
+    ));
+    while let Some(event) = events.next().await {
+        if let ProgressEvent::SessionCreated { .. } = event {
+            break;
+        }
+    }
+    cancel.cancel();
+
+    let result = task.await?;
+    assert!(matches!(result, Err(Error::Cancelled)));
+
+    let info = result.unwrap_err().info();
+    assert_eq!("CANCELLED", info.code);
+    assert_eq!(ErrorKind::Abort, info.kind);
+    assert_eq!(None, info.party);
+    assert!(!info.retryable);
+
+    // Cancelled tokens fail before connecting
+    let result = dkg(options, participant, identifiers).await;
+    assert!(matches!(result, Err(Error::Cancelled)));
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use polysig_driver::{
+    frost::ed25519::export::{
+        ed25519_public_key, key_package_from_json,
+        key_package_to_json, public_key_package_from_json,
+        public_key_package_to_json, verifying_key,
+        verifying_key_bytes,
+    },
+    frost_ed25519::keys,
+};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::export::frost_key_export;
+
+frost_key_export!();
+
+pub fn check_key_export() -> Result<()> {
+    let public_key_package = run_key_export_2_3()?;
+    let public_key = ed25519_public_key(&public_key_package)?;
+    assert_eq!(
+        public_key,
+        verifying_key(&public_key_package)?.to_bytes()
+    );
+    Ok(())
+}
@@ -4,8 +4,18 @@ use ed25519_dalek::{SigningKey, VerifyingKey};
 use polysig_driver::frost_ed25519::Identifier;
 use rand::rngs::OsRng;
 
+mod attestation;
+mod cancel;
+mod dealer;
 mod dkg;
+mod export;
+mod policy;
+mod progress;
+mod refresh;
+mod repair;
 mod sign;
+mod solana;
+mod weighted;
 
 pub fn make_signers(
     num_parties: usize,
@@ -91,3 +101,210 @@ async fn frost_ed25519_dkg_sign_5_9() -> Result<()> {
 
     Ok(())
 }
+
+/// FROST DKG followed by signing with a coordinator (3-of-5).
+#[tokio::test]
+async fn frost_ed25519_dkg_sign_coordinator_3_5() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    sign::run_dkg_sign_coordinator_3_5(&server, server_public_key)
+        .await?;
+
+    Ok(())
+}
+
+/// FROST DKG followed by signing with a roster of signers (3-of-5).
+#[tokio::test]
+async fn frost_ed25519_dkg_sign_roster_3_5() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    sign::run_dkg_sign_roster_3_5(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// FROST weighted DKG followed by signing (weight 3-of-4).
+#[tokio::test]
+async fn frost_ed25519_dkg_sign_weighted() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    weighted::run_dkg_sign_weighted(&server, server_public_key)
+        .await?;
+
+    Ok(())
+}
+
+/// FROST DKG followed by signing a batch of messages (2-of-3).
+#[tokio::test]
+async fn frost_ed25519_dkg_sign_batch_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    sign::run_dkg_sign_batch_2_3(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// FROST DKG followed by nonce pre-processing and signing (2-of-3).
+#[tokio::test]
+async fn frost_ed25519_dkg_preprocess_sign_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    sign::run_dkg_preprocess_sign_2_3(&server, server_public_key)
+        .await?;
+
+    Ok(())
+}
+
+/// FROST signing attributes an invalid signature share (2-of-3).
+#[test]
+fn frost_ed25519_sign_invalid_share_2_3() -> Result<()> {
+    sign::run_sign_invalid_share_2_3()
+}
+
+/// FROST DKG followed by share refresh and signing (2-of-3).
+#[tokio::test]
+async fn frost_ed25519_dkg_refresh_sign_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    refresh::run_dkg_refresh_sign_2_3(&server, server_public_key)
+        .await?;
+
+    Ok(())
+}
+
+/// FROST DKG followed by repairing a lost key share (2-of-3).
+#[tokio::test]
+async fn frost_ed25519_dkg_repair_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    repair::run_dkg_repair_2_3(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// FROST trusted dealer key generation followed by signing (2-of-3).
+#[tokio::test]
+async fn frost_ed25519_dealer_sign_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    dealer::run_dealer_sign_2_3(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// FROST group key and key package export.
+#[test]
+fn frost_ed25519_key_export() -> Result<()> {
+    export::check_key_export()
+}
+
+/// Signing policy is consulted before joining a session.
+#[tokio::test]
+async fn frost_ed25519_signing_policy() -> Result<()> {
+    policy::check_signing_policy().await
+}
+
+/// Attestation evidence is verified before the session
+/// becomes active.
+#[tokio::test]
+async fn frost_ed25519_attestation() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+    attestation::run_dkg_attestation(&server, server_public_key).await
+}
+
+/// Key share policy is checked before joining a session.
+#[tokio::test]
+async fn frost_ed25519_key_share_policy() -> Result<()> {
+    policy::check_key_share_policy().await
+}
+
+/// Progress events are emitted during key generation.
+#[tokio::test]
+async fn frost_ed25519_dkg_progress() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    progress::run_dkg_progress(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// Cancelling a ceremony closes the session and socket.
+#[tokio::test]
+async fn frost_ed25519_dkg_cancel() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    cancel::run_dkg_cancel(&server, server_public_key).await?;
+
+    Ok(())
+}
+
+/// FROST DKG followed by signing a Solana transaction (2-of-3).
+#[tokio::test]
+async fn frost_ed25519_dkg_sign_solana_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    solana::run_dkg_sign_solana_2_3(&server, server_public_key)
+        .await?;
+
+    Ok(())
+}
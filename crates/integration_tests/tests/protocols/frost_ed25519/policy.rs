@@ -0,0 +1,243 @@
+use super::make_signers;
+use anyhow::Result;
+use async_trait::async_trait;
+use polysig_client::{
+    frost::ed25519::sign, Error, PolicyDecision, ServerOptions,
+    SessionOptions, SigningContext, SigningPolicy, SigningRequest,
+};
+use polysig_driver::{
+    frost::ed25519::{Participant, PartyOptions},
+    frost_ed25519::keys::{
+        generate_with_dealer, IdentifierList, KeyPackage,
+    },
+    KeySharePolicy,
+};
+use polysig_protocol::{Keypair, Parameters};
+use rand::rngs::OsRng;
+use std::sync::{Arc, Mutex};
+
+/// Rejects messages that ask to be rejected and defers
+/// everything else.
+#[derive(Default)]
+struct DeferPolicy {
+    requests: Mutex<Vec<(String, Vec<u8>, usize)>>,
+}
+
+#[async_trait]
+impl SigningPolicy for DeferPolicy {
+    async fn evaluate(
+        &self,
+        request: &SigningRequest<'_>,
+    ) -> PolicyDecision {
+        self.requests.lock().unwrap().push((
+            request.protocol.to_owned(),
+            request.message.to_vec(),
+            request.session.party_index,
+        ));
+        if request.message == b"reject" {
+            PolicyDecision::Reject("not allowed".to_owned())
+        } else {
+            PolicyDecision::Defer("awaiting approval".to_owned())
+        }
+    }
+}
+
+pub async fn check_signing_policy() -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let (shares, public_key_package) = generate_with_dealer(
+        n,
+        t,
+        IdentifierList::Default,
+        &mut OsRng,
+    )?;
+    let identifier = 2u16.try_into()?;
+    let key_package = KeyPackage::try_from(
+        shares.get(&identifier).unwrap().clone(),
+    )?;
+    let key_share = (key_package, public_key_package);
+
+    let (mut signers, verifiers) = make_signers(2);
+    let keypairs = vec![Keypair::generate()?, Keypair::generate()?];
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    let policy = Arc::new(DeferPolicy::default());
+    let options = SessionOptions {
+        keypair: keypairs[1].clone(),
+        // Policy is evaluated before connecting to the server
+        server: ServerOptions {
+            server_url: "ws://127.0.0.1:0".to_owned(),
+            server_public_key: vec![],
+            pattern: None,
+        },
+        parameters: Parameters {
+            parties: n,
+            threshold: t,
+        },
+        policy: Some(policy.clone()),
+        progress: None,
+        cancel: None,
+        key_share_policy: None,
+        signing_context: None,
+        attestation: None,
+        round_timeout: None,
+        keepalive: None,
+    };
+
+    let signer = signers.remove(1);
+    let verifier = signer.verifying_key();
+    let party = PartyOptions::new(
+        public_keys[1].clone(),
+        public_keys.clone(),
+        false,
+        verifiers,
+    )?;
+    let participant = Participant::new(signer, verifier, party)?;
+    let identifiers = vec![1u16.try_into()?, identifier];
+
+    let result = sign(
+        options.clone(),
+        participant.clone(),
+        identifiers.clone(),
+        key_share.clone(),
+        b"reject".to_vec(),
+    )
+    .await;
+    assert!(matches!(result, Err(Error::PolicyRejected(_))));
+
+    let result = sign(
+        options,
+        participant,
+        identifiers,
+        key_share,
+        b"transfer".to_vec(),
+    )
+    .await;
+    assert!(matches!(result, Err(Error::PolicyDeferred(_))));
+
+    let requests = policy.requests.lock().unwrap();
+    assert_eq!(
+        vec![
+            ("frost-ed25519".to_owned(), b"reject".to_vec(), 1),
+            ("frost-ed25519".to_owned(), b"transfer".to_vec(), 1),
+        ],
+        *requests
+    );
+
+    Ok(())
+}
+
+pub async fn check_key_share_policy() -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let (shares, public_key_package) = generate_with_dealer(
+        n,
+        t,
+        IdentifierList::Default,
+        &mut OsRng,
+    )?;
+    let identifier = 2u16.try_into()?;
+    let key_package = KeyPackage::try_from(
+        shares.get(&identifier).unwrap().clone(),
+    )?;
+    let key_share = (key_package, public_key_package);
+
+    let (mut signers, verifiers) = make_signers(2);
+    let keypairs = vec![Keypair::generate()?, Keypair::generate()?];
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    let policy = Arc::new(DeferPolicy::default());
+    let mut options = SessionOptions {
+        keypair: keypairs[1].clone(),
+        // Policies are evaluated before connecting to the server
+        server: ServerOptions {
+            server_url: "ws://127.0.0.1:0".to_owned(),
+            server_public_key: vec![],
+            pattern: None,
+        },
+        parameters: Parameters {
+            parties: n,
+            threshold: t,
+        },
+        policy: Some(policy.clone()),
+        progress: None,
+        cancel: None,
+        key_share_policy: Some(KeySharePolicy {
+            expires_at: Some(1),
+            ..Default::default()
+        }),
+        signing_context: None,
+        attestation: None,
+        round_timeout: None,
+        keepalive: None,
+    };
+
+    let signer = signers.remove(1);
+    let verifier = signer.verifying_key();
+    let party = PartyOptions::new(
+        public_keys[1].clone(),
+        public_keys.clone(),
+        false,
+        verifiers,
+    )?;
+    let participant = Participant::new(signer, verifier, party)?;
+    let identifiers = vec![1u16.try_into()?, identifier];
+
+    // Expired key share
+    let result = sign(
+        options.clone(),
+        participant.clone(),
+        identifiers.clone(),
+        key_share.clone(),
+        b"transfer".to_vec(),
+    )
+    .await;
+    assert!(matches!(result, Err(Error::PolicyRejected(_))));
+
+    // Chain is not allowed
+    options.key_share_policy = Some(KeySharePolicy {
+        expires_at: None,
+        derivation_paths: vec!["m/44'/60'".to_owned()],
+        chains: vec!["eip155:1".to_owned()],
+    });
+    options.signing_context = Some(SigningContext {
+        derivation_path: Some("m/44'/60'/0'/0/0".to_owned()),
+        chain: Some("eip155:137".to_owned()),
+    });
+    let result = sign(
+        options.clone(),
+        participant.clone(),
+        identifiers.clone(),
+        key_share.clone(),
+        b"transfer".to_vec(),
+    )
+    .await;
+    assert!(matches!(result, Err(Error::PolicyRejected(_))));
+
+    // Key share rules were checked before the signing policy
+    assert!(policy.requests.lock().unwrap().is_empty());
+
+    // Allowed by the key share so the signing policy decides
+    options.signing_context = Some(SigningContext {
+        derivation_path: Some("m/44'/60'/0'/0/0".to_owned()),
+        chain: Some("eip155:1".to_owned()),
+    });
+    let result = sign(
+        options,
+        participant,
+        identifiers,
+        key_share,
+        b"transfer".to_vec(),
+    )
+    .await;
+    assert!(matches!(result, Err(Error::PolicyDeferred(_))));
+    assert_eq!(1, policy.requests.lock().unwrap().len());
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use super::make_signers;
+use anyhow::Result;
+use polysig_client::{
+    frost::ed25519::dkg, progress_channel, ProgressEvent,
+    ServerOptions, SessionOptions,
+};
+use polysig_driver::frost::ed25519::{
+    Identifier, Participant, PartyOptions,
+};
+use polysig_protocol::{Keypair, Parameters};
+
+pub async fn run_dkg_progress(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let t = 2;
+    let n = 3;
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+    let (signers, verifiers) = make_signers(n as usize);
+    let server = ServerOptions {
+        server_url: server.to_owned(),
+        server_public_key,
+        pattern: None,
+    };
+
+    let mut session_options = Vec::new();
+    let mut public_keys = Vec::new();
+    let mut receivers = Vec::new();
+    for _ in 0..n {
+        let keypair = Keypair::generate()?;
+        public_keys.push(keypair.public_key().to_vec());
+
+        let (progress, receiver) = progress_channel();
+        receivers.push(receiver);
+        session_options.push(SessionOptions {
+            keypair,
+            parameters: params.clone(),
+            server: server.clone(),
+            policy: None,
+            progress: Some(progress),
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        });
+    }
+
+    let mut tasks = Vec::new();
+    for (index, (opts, signer)) in
+        session_options.into_iter().zip(signers).enumerate()
+    {
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            verifiers.clone(),
+        )?;
+        let verifier = signer.verifying_key();
+        let ids = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let key_share = dkg(
+                opts,
+                Participant::new(signer, verifier, party)?,
+                ids,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(key_share)
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    for result in results {
+        result?;
+    }
+
+    for (index, mut receiver) in receivers.into_iter().enumerate() {
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = receiver.try_next() {
+            events.push(event);
+        }
+
+        assert_eq!(
+            Some(&ProgressEvent::ServerConnected),
+            events.first()
+        );
+        assert_eq!(Some(&ProgressEvent::Completed), events.last());
+        assert!(events.iter().any(|e| matches!(
+            e,
+            ProgressEvent::SessionActive { .. }
+        )));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ProgressEvent::Round { .. })));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            ProgressEvent::MessageReceived { party: Some(_), .. }
+        )));
+        assert_eq!(
+            index == 0,
+            events.iter().any(|e| matches!(
+                e,
+                ProgressEvent::SessionCreated { .. }
+            ))
+        );
+    }
+
+    Ok(())
+}
@@ -1,16 +1,28 @@
 use super::dkg::run_dkg;
 use anyhow::Result;
 use polysig_client::{
-    frost::ed25519::sign, ServerOptions, SessionOptions,
+    frost::ed25519::{
+        preprocess, sign_batch, sign_preprocessed, sign_with_mode,
+        sign_with_roster, Roster, SignatureMode,
+    },
+    ServerOptions, SessionOptions,
 };
 use polysig_driver::{
-    frost::ed25519::{KeyShare, Participant, PartyOptions},
-    frost_ed25519::{keys, Identifier},
+    frost::{
+        ed25519::{
+            KeyShare, NonceStore, Participant, PartyOptions,
+            SignatureDriver,
+        },
+        Error,
+    },
+    frost_ed25519::{keys, Identifier, Signature},
+    ProtocolDriver, Round,
 };
 
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use polysig_protocol::{Keypair, Parameters};
-use std::collections::BTreeMap;
+use rand::{rngs::OsRng, RngCore};
+use std::{collections::BTreeMap, num::NonZeroU16};
 
 use crate::protocols::frost_core::{
     make_signing_message, sign::frost_dkg_sign,
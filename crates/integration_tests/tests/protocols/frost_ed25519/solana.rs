@@ -0,0 +1,71 @@
+use super::{
+    dkg::run_dkg,
+    sign::{sign_message, SelectedSigners},
+};
+use crate::test_utils::solana_transfer_message;
+use anyhow::Result;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use polysig_client::frost::ed25519::SignatureMode;
+use polysig_driver::{
+    frost::ed25519::solana::{
+        address, pubkey, signature_bytes, transfers, verify, Transfer,
+    },
+    frost_ed25519::Identifier,
+};
+
+pub async fn run_dkg_sign_solana_2_3(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+    let (server, key_shares, signers) =
+        run_dkg(t, n, server, server_public_key, identifiers).await?;
+
+    let public_key_package = &key_shares[0].1;
+    let payer = pubkey(public_key_package)?;
+    let payer_address = address(public_key_package)?;
+    assert!((32..=44).contains(&payer_address.len()));
+
+    let message =
+        solana_transfer_message(payer, [3u8; 32], 1_000_000);
+    assert_eq!(
+        vec![Transfer {
+            from: payer,
+            to: [3u8; 32],
+            lamports: 1_000_000,
+        }],
+        transfers(&message)?
+    );
+    assert!(transfers(&message[..message.len() - 1]).is_err());
+
+    let selected = SelectedSigners::new(
+        t,
+        &[0, 2],
+        signers,
+        key_shares.clone(),
+    )?;
+    let signatures = sign_message(
+        t,
+        n,
+        server,
+        selected,
+        message.clone(),
+        SignatureMode::Broadcast,
+    )
+    .await?;
+
+    let verifying_key = VerifyingKey::from_bytes(&payer)?;
+    for signature in &signatures {
+        verify(public_key_package, &message, signature)?;
+
+        let signature =
+            Signature::from_bytes(&signature_bytes(signature)?);
+        verifying_key.verify(&message, &signature)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use polysig_client::{
+    frost::ed25519::{dkg_weighted, sign_weighted, WeightedRoster},
+    ServerOptions, SessionOptions,
+};
+use polysig_driver::frost::ed25519::{
+    KeyShare, Participant, PartyOptions,
+};
+use polysig_protocol::{Keypair, Parameters};
+
+use super::make_signers;
+use crate::protocols::frost_core::make_signing_message;
+
+/// Weighted key generation where the first party holds two
+/// shares followed by signing with parties that meet the
+/// weight threshold.
+pub async fn run_dkg_sign_weighted(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let t = 3;
+    let weights = vec![2, 1, 1];
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+    let server = ServerOptions {
+        server_url: server.to_owned(),
+        server_public_key,
+        pattern: None,
+    };
+
+    let (signers, verifiers) = make_signers(n as usize);
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let public_keys: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+
+    let mut tasks = Vec::new();
+    for (index, (keypair, signer)) in keypairs
+        .into_iter()
+        .zip(signers.clone().into_iter())
+        .enumerate()
+    {
+        let opts =
+            SessionOptions::builder(keypair, server.clone(), params)
+                .build()?;
+        let party = PartyOptions::builder(public_keys[index].clone())
+            .verifiers(public_keys.clone(), verifiers.clone())
+            .weights(weights.clone())
+            .threshold(t)
+            .initiator(index == 0)
+            .build()?;
+        let verifier = signer.verifying_key();
+        tasks.push(tokio::task::spawn(async move {
+            let key_shares = dkg_weighted(
+                opts,
+                Participant::new(signer, verifier, party)?,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(key_shares)
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    let key_shares = results
+        .into_iter()
+        .collect::<Result<Vec<Vec<KeyShare>>>>()?;
+    let shares =
+        key_shares.iter().map(|k| k.len()).collect::<Vec<_>>();
+    assert_eq!(vec![2, 1, 1], shares);
+    let verifying_key = key_shares[0][0].1.verifying_key().clone();
+    for key_share in key_shares.iter().flatten() {
+        assert_eq!(&verifying_key, key_share.1.verifying_key());
+        assert_eq!(t, *key_share.0.min_signers());
+    }
+
+    // First and last party meet the threshold
+    let indices = [0, 2];
+    let keypairs = indices
+        .iter()
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let public_keys: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+    let selected_verifiers =
+        indices.iter().map(|i| verifiers[*i]).collect::<Vec<_>>();
+    let roster = WeightedRoster::new(
+        indices
+            .iter()
+            .zip(public_keys.iter())
+            .map(|(i, public_key)| {
+                let identifiers = key_shares[*i]
+                    .iter()
+                    .map(|k| *k.0.identifier())
+                    .collect();
+                (public_key.clone(), identifiers)
+            })
+            .collect(),
+    );
+    assert_eq!(t as usize, roster.weight());
+
+    let message = make_signing_message();
+    let mut tasks = Vec::new();
+    for (index, keypair) in keypairs.into_iter().enumerate() {
+        let signer = signers[indices[index]].clone();
+        let opts =
+            SessionOptions::builder(keypair, server.clone(), params)
+                .build()?;
+        let party = PartyOptions::builder(public_keys[index].clone())
+            .verifiers(
+                public_keys.clone(),
+                selected_verifiers.clone(),
+            )
+            .initiator(index == 0)
+            .build()?;
+        let verifier = signer.verifying_key();
+        let roster = roster.clone();
+        let key_shares = key_shares[indices[index]].clone();
+        let message = message.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let signature = sign_weighted(
+                opts,
+                Participant::new(signer, verifier, party)?,
+                roster,
+                key_shares,
+                message,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(signature)
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    for result in results {
+        verifying_key.verify(&message, &result?)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use super::{
+    make_signers,
+    sign::{check_sign, SelectedSigners},
+};
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+use polysig_client::{
+    frost::ed448::deal, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    frost::ed448::{Identifier, KeyShare, Participant, PartyOptions},
+    frost_ed448::{
+        SigningKey as GroupSigningKey,
+        VerifyingKey as GroupVerifyingKey,
+    },
+};
+use polysig_protocol::{Keypair, Parameters};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::dealer::frost_dealer_sign;
+
+frost_dealer_sign!();
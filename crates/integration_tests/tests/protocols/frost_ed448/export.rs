@@ -0,0 +1,19 @@
+use anyhow::Result;
+use polysig_driver::{
+    frost::ed448::export::{
+        key_package_from_json, key_package_to_json,
+        public_key_package_from_json, public_key_package_to_json,
+        verifying_key_bytes,
+    },
+    frost_ed448::keys,
+};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::export::frost_key_export;
+
+frost_key_export!();
+
+pub fn check_key_export() -> Result<()> {
+    run_key_export_2_3()?;
+    Ok(())
+}
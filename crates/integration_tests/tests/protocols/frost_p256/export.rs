@@ -0,0 +1,21 @@
+use anyhow::Result;
+use polysig_driver::{
+    frost::p256::export::{
+        key_package_from_json, key_package_to_json,
+        public_key_package_from_json, public_key_package_to_json,
+        sec1_compressed, verifying_key_bytes,
+    },
+    frost_p256::keys,
+};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::export::frost_key_export;
+
+frost_key_export!();
+
+pub fn check_key_export() -> Result<()> {
+    let public_key_package = run_key_export_2_3()?;
+    let point = sec1_compressed(&public_key_package)?;
+    assert!(point[0] == 0x02 || point[0] == 0x03);
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use super::{
+    dkg::run_dkg,
+    sign::{check_sign, SelectedSigners},
+};
+use anyhow::Result;
+use polysig_client::{
+    frost::p256::refresh, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    frost::p256::{Identifier, KeyShare, Participant, PartyOptions},
+    k256::schnorr::SigningKey,
+};
+use polysig_protocol::{Keypair, Parameters};
+
+use crate::protocols::frost_core::refresh::frost_dkg_refresh_sign;
+
+frost_dkg_refresh_sign!();
@@ -0,0 +1,14 @@
+use super::dkg::run_dkg;
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+use polysig_client::{
+    frost::ristretto255::repair_share, ServerOptions, SessionOptions,
+};
+use polysig_driver::frost::ristretto255::{
+    Identifier, KeyShare, Participant, PartyOptions,
+};
+use polysig_protocol::{Keypair, Parameters};
+
+use crate::protocols::frost_core::repair::frost_dkg_repair;
+
+frost_dkg_repair!();
@@ -0,0 +1,177 @@
+use super::make_signers;
+use anyhow::Result;
+use polysig_client::{
+    frost::secp256k1::dkg_certificate, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    certificate::{verify_bundle, Error, VerificationBundle},
+    frost::secp256k1::{Participant, PartyOptions, VerifyingKey},
+    frost_secp256k1::Identifier,
+};
+use polysig_protocol::{hex, Keypair, Parameters};
+
+pub async fn run_dkg_certificate_2_3(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+    let (signers, verifiers) = make_signers(n as usize);
+    let server = ServerOptions {
+        server_url: server.to_owned(),
+        server_public_key,
+        pattern: None,
+    };
+
+    let mut keypairs = Vec::new();
+    for _ in 0..n {
+        keypairs.push(Keypair::generate()?);
+    }
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    let mut tasks = Vec::new();
+    for (index, (keypair, signer)) in
+        keypairs.into_iter().zip(signers.clone()).enumerate()
+    {
+        let opts = SessionOptions {
+            keypair,
+            parameters: params.clone(),
+            server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        };
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            verifiers.clone(),
+        )?;
+        let verifier = signer.verifying_key().clone();
+        let ids = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let result = dkg_certificate(
+                opts,
+                Participant::new(signer, verifier, party)?,
+                ids,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(result)
+        }));
+    }
+
+    let mut certificates = Vec::new();
+    for result in futures::future::try_join_all(tasks).await? {
+        let (key_share, certificate) = result?;
+        let verifying_key =
+            key_share.1.verifying_key().serialize()?;
+        assert_eq!(
+            verifying_key.as_slice(),
+            certificate.ceremony().verifying_key()
+        );
+        certificates.push(certificate);
+    }
+
+    for certificate in &certificates {
+        assert_eq!(
+            certificates[0].ceremony(),
+            certificate.ceremony()
+        );
+        assert_eq!(n as usize, certificate.endorsements().len());
+        certificate.verify::<VerifyingKey>()?;
+        certificate.verify_participants(&verifiers)?;
+    }
+
+    // Certificate for a different set of participants
+    let (_, other_verifiers) = make_signers(n as usize);
+    assert!(certificates[0]
+        .verify_participants(&other_verifiers)
+        .is_err());
+
+    // Certificate survives encoding for auditors
+    let encoded = serde_json::to_vec(&certificates[0])?;
+    let decoded: polysig_driver::certificate::CeremonyCertificate =
+        serde_json::from_slice(&encoded)?;
+    decoded.verify_participants(&verifiers)?;
+
+    // Bundle for third parties to verify the group for an address
+    let derive = |key: &[u8]| format!("0x{}", hex::encode(key));
+    let address = derive(certificates[0].ceremony().verifying_key());
+    let bundle = VerificationBundle::new::<VerifyingKey>(
+        certificates[0].clone(),
+        Some(address.clone()),
+        1,
+        &signers[0],
+        1_700_000_000,
+    )?;
+    let encoded = bundle.encode()?;
+    let decoded =
+        verify_bundle(&encoded, &verifiers, &address, derive)?;
+    assert_eq!(t, decoded.threshold());
+    assert_eq!(n as usize, decoded.participants().len());
+    assert_eq!(1, decoded.issuer());
+    assert!(matches!(
+        verify_bundle(&encoded, &verifiers, "0xabc", derive),
+        Err(Error::AddressMismatch(_))
+    ));
+
+    // Addresses are compared exactly
+    assert!(matches!(
+        verify_bundle(
+            &encoded,
+            &verifiers,
+            &address.to_uppercase().replace("0X", "0x"),
+            derive,
+        ),
+        Err(Error::AddressMismatch(_))
+    ));
+
+    // Bundle for a group with unexpected participants is rejected
+    assert!(matches!(
+        verify_bundle(&encoded, &other_verifiers, &address, derive),
+        Err(Error::ParticipantMismatch)
+    ));
+
+    // Issuer must sign with the key for the party number
+    assert!(matches!(
+        VerificationBundle::new::<VerifyingKey>(
+            certificates[0].clone(),
+            None,
+            2,
+            &signers[0],
+            1_700_000_000,
+        ),
+        Err(Error::BundleSignature(2))
+    ));
+
+    // Tampered threshold is rejected
+    let mut value: serde_json::Value =
+        serde_json::from_str(&encoded)?;
+    value["statement"]["threshold"] = serde_json::json!(1);
+    assert!(matches!(
+        verify_bundle(
+            &value.to_string(),
+            &verifiers,
+            &address,
+            derive,
+        ),
+        Err(Error::BundleMismatch)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use super::{
+    make_signers,
+    sign::{check_sign, SelectedSigners},
+};
+use anyhow::Result;
+use polysig_client::{
+    frost::secp256k1::deal, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    frost::secp256k1::{
+        Identifier, KeyShare, Participant, PartyOptions,
+    },
+    frost_secp256k1::{
+        SigningKey as GroupSigningKey,
+        VerifyingKey as GroupVerifyingKey,
+    },
+    k256::schnorr::SigningKey,
+};
+use polysig_protocol::{Keypair, Parameters};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::dealer::frost_dealer_sign;
+
+frost_dealer_sign!();
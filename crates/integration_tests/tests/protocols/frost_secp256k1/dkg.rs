@@ -0,0 +1,17 @@
+use anyhow::Result;
+use polysig_client::{
+    frost::secp256k1::dkg, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    frost::secp256k1::{
+        Identifier, KeyShare, Participant, PartyOptions,
+    },
+    k256::schnorr::SigningKey,
+};
+
+use polysig_protocol::{Keypair, Parameters};
+
+use super::make_signers;
+use crate::protocols::frost_core::dkg::frost_dkg;
+
+frost_dkg!();
@@ -0,0 +1,56 @@
+use super::{
+    dkg::run_dkg,
+    sign::{signer_sessions, SelectedSigners},
+};
+use anyhow::Result;
+use polysig_client::elgamal::decrypt;
+use polysig_driver::{elgamal::encrypt, frost_secp256k1::Identifier};
+
+const MESSAGE: &[u8] = b"this is the message that is encrypted";
+const AAD: &[u8] = b"mailbox";
+
+pub async fn run_dkg_decrypt_2_3(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+    let (server, key_shares, signers) =
+        run_dkg(t, n, server, server_public_key, identifiers).await?;
+
+    let ciphertext = encrypt(&key_shares[0].1, MESSAGE, AAD)?;
+
+    let selected =
+        SelectedSigners::new(t, &[0, 2], signers, key_shares)?;
+    let identifiers = selected.identifiers.clone();
+
+    let mut tasks = Vec::new();
+    for (opts, participant, key_share) in
+        signer_sessions(t, n, server, selected)?
+    {
+        let ids = identifiers.clone();
+        let ciphertext = ciphertext.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let message = decrypt(
+                opts,
+                participant,
+                ids,
+                &key_share,
+                ciphertext,
+                AAD.to_vec(),
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(message)
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    for result in results {
+        assert_eq!(MESSAGE, result?.as_slice());
+    }
+
+    Ok(())
+}
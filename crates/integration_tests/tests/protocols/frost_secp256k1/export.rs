@@ -0,0 +1,31 @@
+use anyhow::Result;
+use polysig_driver::{
+    frost::secp256k1::export::{
+        key_package_from_json, key_package_to_json, public_key,
+        public_key_package_from_json, public_key_package_to_json,
+        sec1_compressed, sec1_uncompressed, verifying_key_bytes,
+    },
+    frost_secp256k1::keys,
+    k256::elliptic_curve::sec1::ToEncodedPoint,
+};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::export::frost_key_export;
+
+frost_key_export!();
+
+pub fn check_key_export() -> Result<()> {
+    let public_key_package = run_key_export_2_3()?;
+    let compressed = sec1_compressed(&public_key_package)?;
+    let uncompressed = sec1_uncompressed(&public_key_package)?;
+    let group_key = public_key(&public_key_package)?;
+    assert_eq!(
+        &compressed[..],
+        group_key.to_encoded_point(true).as_bytes()
+    );
+    assert_eq!(
+        &uncompressed[..],
+        group_key.to_encoded_point(false).as_bytes()
+    );
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use super::dkg::run_dkg;
+use anyhow::Result;
+use polysig_client::{
+    frost::secp256k1::repair_share, ServerOptions, SessionOptions,
+};
+use polysig_driver::{
+    frost::secp256k1::{
+        Identifier, KeyShare, Participant, PartyOptions,
+    },
+    k256::schnorr::SigningKey,
+};
+use polysig_protocol::{Keypair, Parameters};
+
+use crate::protocols::frost_core::repair::frost_dkg_repair;
+
+frost_dkg_repair!();
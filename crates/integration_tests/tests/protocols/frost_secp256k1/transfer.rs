@@ -0,0 +1,129 @@
+use super::dkg::run_dkg;
+use anyhow::Result;
+use polysig_client::{receive_share, transfer_share, SessionOptions};
+use polysig_driver::{
+    certificate::{Binding, Error, TransferCertificate},
+    frost::secp256k1::{KeyShare, PartyOptions},
+    frost_secp256k1::Identifier,
+    k256::schnorr::{SigningKey, VerifyingKey},
+};
+use polysig_protocol::{Keypair, Parameters};
+use rand::rngs::OsRng;
+
+/// Transfer the key share of the first participant
+/// to a new device and update the roster.
+pub async fn run_dkg_transfer_2_3(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let (server, key_shares, signers) =
+        run_dkg(t, n, server, server_public_key, identifiers).await?;
+    let verifiers = signers
+        .iter()
+        .map(|s| s.verifying_key().clone())
+        .collect::<Vec<_>>();
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    // New device for the first participant
+    let new_keypair = Keypair::generate()?;
+    let new_signer = SigningKey::random(&mut OsRng);
+    let new_verifier = new_signer.verifying_key().clone();
+    let previous =
+        Binding::new(public_keys[0].clone(), &verifiers[0]);
+    let next = Binding::new(
+        new_keypair.public_key().to_vec(),
+        &new_verifier,
+    );
+
+    let params = Parameters {
+        parties: 2,
+        threshold: 2,
+    };
+    let old_options = SessionOptions::builder(
+        keypairs[0].clone(),
+        server.clone(),
+        params,
+    )
+    .build()?;
+    let new_options =
+        SessionOptions::builder(new_keypair.clone(), server, params)
+            .build()?;
+
+    let key_share: polysig_driver::KeyShare =
+        (&key_shares[0]).try_into()?;
+    let signer = signers[0].clone();
+    let (previous_binding, next_binding) =
+        (previous.clone(), next.clone());
+    let sender = tokio::task::spawn(async move {
+        let certificate = transfer_share::<VerifyingKey>(
+            old_options,
+            previous_binding,
+            next_binding,
+            &signer,
+            key_share,
+        )
+        .await?;
+        Ok::<_, anyhow::Error>(certificate)
+    });
+    let receiver = tokio::task::spawn(async move {
+        let output = receive_share::<VerifyingKey>(
+            new_options,
+            previous,
+            next,
+            &new_signer,
+        )
+        .await?;
+        Ok::<_, anyhow::Error>(output)
+    });
+
+    let certificate = sender.await??;
+    let (key_share, received_certificate) = receiver.await??;
+    let key_share: KeyShare = (&key_share).try_into()?;
+    assert_eq!(key_shares[0], key_share);
+    assert_eq!(certificate, received_certificate);
+    certificate.verify::<VerifyingKey>()?;
+
+    // Other participants update the roster
+    let party = PartyOptions::new(
+        public_keys[1].clone(),
+        public_keys.clone(),
+        false,
+        verifiers.clone(),
+    )?;
+    let updated = certificate.apply(&party)?;
+    assert_eq!(new_keypair.public_key(), &updated.participants()[0]);
+    assert_eq!(&new_verifier, &updated.verifiers()[0]);
+    assert_eq!(party.public_key(), updated.public_key());
+    assert!(matches!(
+        certificate.apply(&updated),
+        Err(Error::TransferParticipant)
+    ));
+
+    // Roster of the transferred participant
+    let party = PartyOptions::new(
+        public_keys[0].clone(),
+        public_keys.clone(),
+        true,
+        verifiers.clone(),
+    )?;
+    let updated = certificate.apply(&party)?;
+    assert_eq!(new_keypair.public_key(), updated.public_key());
+
+    // Certificate survives encoding for other participants
+    let encoded = serde_json::to_vec(&certificate)?;
+    let decoded: TransferCertificate =
+        serde_json::from_slice(&encoded)?;
+    decoded.verify::<VerifyingKey>()?;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use polysig_driver::{
+    frost::secp256k1_tr::export::{
+        internal_key, key_package_from_json, key_package_to_json,
+        output_key, public_key_package_from_json,
+        public_key_package_to_json, sec1_compressed,
+        verifying_key_bytes,
+    },
+    frost_secp256k1_tr::keys,
+};
+use rand::rngs::OsRng;
+
+use crate::protocols::frost_core::export::frost_key_export;
+
+frost_key_export!();
+
+pub fn check_key_export() -> Result<()> {
+    let public_key_package = run_key_export_2_3()?;
+    let compressed = sec1_compressed(&public_key_package)?;
+    assert_eq!(&compressed[1..], &internal_key(&public_key_package)?);
+    assert_eq!(32, output_key(&public_key_package)?.len());
+    Ok(())
+}
@@ -1,16 +1,28 @@
 use super::dkg::run_dkg;
 use anyhow::Result;
 use polysig_client::{
-    frost::secp256k1_tr::sign, ServerOptions, SessionOptions,
+    frost::secp256k1_tr::{
+        preprocess, sign_batch, sign_preprocessed, sign_with_mode,
+        sign_with_roster, Roster, SignatureMode,
+    },
+    ServerOptions, SessionOptions,
 };
 use polysig_driver::{
-    frost::secp256k1_tr::{KeyShare, Participant, PartyOptions},
-    frost_secp256k1_tr::{keys, Identifier},
+    frost::{
+        secp256k1_tr::{
+            KeyShare, NonceStore, Participant, PartyOptions,
+            SignatureDriver,
+        },
+        Error,
+    },
+    frost_secp256k1_tr::{keys, Identifier, Signature},
     k256::schnorr::{SigningKey, VerifyingKey},
+    ProtocolDriver, Round,
 };
 
 use polysig_protocol::{Keypair, Parameters};
-use std::collections::BTreeMap;
+use rand::{rngs::OsRng, RngCore};
+use std::{collections::BTreeMap, num::NonZeroU16};
 
 use crate::protocols::frost_core::{
     make_signing_message, sign::frost_dkg_sign,
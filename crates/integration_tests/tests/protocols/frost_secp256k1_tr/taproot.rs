@@ -0,0 +1,77 @@
+use super::{
+    dkg::run_dkg,
+    sign::{sign_message, SelectedSigners},
+};
+use anyhow::Result;
+use polysig_client::frost::secp256k1_tr::SignatureMode;
+use polysig_driver::{
+    frost::secp256k1_tr::taproot::{
+        output_key, signature_bytes, KeySpend, Prevout, TxOut,
+    },
+    frost_secp256k1_tr::Identifier,
+    k256::schnorr,
+};
+
+pub async fn run_dkg_sign_taproot_2_3(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<()> {
+    let n = 3;
+    let t = 2;
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+
+    let (server, key_shares, signers) =
+        run_dkg(t, n, server, server_public_key, identifiers).await?;
+
+    let key = output_key(&key_shares[0].1)?;
+    let mut script_pubkey = vec![0x51, 0x20];
+    script_pubkey.extend_from_slice(&key);
+
+    // Spend an output paying to the group taproot key
+    let spend = KeySpend {
+        version: 2,
+        lock_time: 0,
+        prevouts: vec![Prevout {
+            txid: [7; 32],
+            vout: 0,
+            sequence: 0xffff_fffd,
+            output: TxOut {
+                value: 100_000,
+                script_pubkey: script_pubkey.clone(),
+            },
+        }],
+        outputs: vec![TxOut {
+            value: 90_000,
+            script_pubkey,
+        }],
+        input_index: 0,
+    };
+    let sighash = spend.sighash()?;
+
+    let selected = SelectedSigners::new(
+        t,
+        &[0, 1],
+        signers,
+        key_shares.clone(),
+    )?;
+    let signatures = sign_message(
+        t,
+        n,
+        server,
+        selected,
+        sighash.to_vec(),
+        SignatureMode::Broadcast,
+    )
+    .await?;
+
+    // Signatures must verify as BIP-340 signatures
+    let verifying_key = schnorr::VerifyingKey::from_bytes(&key)?;
+    for signature in &signatures {
+        let signature = signature_bytes(signature)?;
+        let signature = schnorr::Signature::try_from(&signature[..])?;
+        verifying_key.verify_raw(&sighash, &signature)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use crate::test_utils::{
+    new_client, server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::Result;
+use polysig_client::{EventStream, NetworkTransport};
+use polysig_protocol::{Event, SessionId};
+
+/// Payload larger than a single chunk.
+const BLOB_SIZE: usize = 100_000;
+
+/// Creates group keys for a session then broadcasts messages
+/// that the server fans out to the other participants.
+///
+/// Each participant broadcasts with its own group key.
+#[tokio::test]
+async fn integration_group_broadcast() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut participant_1, event_loop_1, participant_1_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut participant_2, event_loop_2, participant_2_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+
+    initiator.connect().await?;
+    participant_1.connect().await?;
+    participant_2.connect().await?;
+    let mut s_i = event_loop_i.run();
+    let mut s_1 = event_loop_1.run();
+    let mut s_2 = event_loop_2.run();
+    for stream in [&mut s_i, &mut s_1, &mut s_2] {
+        wait_for_event(stream, |event| {
+            matches!(event, Event::ServerConnected { .. })
+        })
+        .await?;
+    }
+
+    initiator
+        .new_session(vec![
+            initiator_key.public_key().to_vec(),
+            participant_1_key.public_key().to_vec(),
+            participant_2_key.public_key().to_vec(),
+        ])
+        .await?;
+    let Event::SessionCreated(session) =
+        wait_for_event(&mut s_i, |event| {
+            matches!(event, Event::SessionCreated(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+
+    let sender = async {
+        initiator
+            .connect_peer(participant_1_key.public_key())
+            .await?;
+        initiator
+            .connect_peer(participant_2_key.public_key())
+            .await?;
+        for _ in 0..2 {
+            wait_for_event(&mut s_i, |event| {
+                matches!(event, Event::PeerConnected { .. })
+            })
+            .await?;
+        }
+
+        initiator.create_group_key(&session).await?;
+        initiator.broadcast_json(&session, &"hello").await?;
+        initiator.broadcast_blob(&session, blob()).await?;
+        Ok::<_, anyhow::Error>(())
+    };
+
+    let sender_key = initiator_key.public_key();
+    let (sent, received_1, received_2) = futures::join!(
+        sender,
+        receive(&mut s_1, sender_key, session.session_id),
+        receive(&mut s_2, sender_key, session.session_id),
+    );
+    sent?;
+    received_1?;
+    received_2?;
+
+    // Group messages from another participant are decrypted
+    // with the group key of that participant
+    let sender = async {
+        participant_1
+            .connect_peer(participant_2_key.public_key())
+            .await?;
+        wait_for_event(&mut s_1, |event| {
+            matches!(event, Event::PeerConnected { .. })
+        })
+        .await?;
+
+        participant_1.create_group_key(&session).await?;
+        participant_1.broadcast_json(&session, &"hello").await?;
+        participant_1.broadcast_blob(&session, blob()).await?;
+        Ok::<_, anyhow::Error>(())
+    };
+
+    let sender_key = participant_1_key.public_key();
+    let (sent, received_i, received_2) = futures::join!(
+        sender,
+        receive(&mut s_i, sender_key, session.session_id),
+        receive(&mut s_2, sender_key, session.session_id),
+    );
+    sent?;
+    received_i?;
+    received_2?;
+
+    initiator.close().await?;
+    participant_1.close().await?;
+    participant_2.close().await?;
+    Ok(())
+}
+
+/// Wait for the broadcast messages on a participant stream.
+///
+/// The group key message is consumed by the event loop so
+/// the first message received is the JSON broadcast.
+async fn receive(
+    stream: &mut EventStream,
+    sender_key: &[u8],
+    session_id: SessionId,
+) -> Result<()> {
+    let Event::JsonMessage {
+        peer_key,
+        message,
+        session_id: id,
+    } = wait_for_event(stream, |event| {
+        matches!(event, Event::JsonMessage { .. })
+    })
+    .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(sender_key, peer_key);
+    assert_eq!(Some(session_id), id);
+    assert_eq!("hello", message.deserialize::<&str>()?);
+
+    let Event::BinaryMessage {
+        peer_key,
+        message,
+        session_id: id,
+    } = wait_for_event(stream, |event| {
+        matches!(event, Event::BinaryMessage { .. })
+    })
+    .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(sender_key, peer_key);
+    assert_eq!(Some(session_id), id);
+    assert_eq!(blob(), message);
+    Ok(())
+}
+
+fn blob() -> Vec<u8> {
+    (0..BLOB_SIZE).map(|i| i as u8).collect()
+}
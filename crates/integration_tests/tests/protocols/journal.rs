@@ -0,0 +1,178 @@
+use crate::test_utils::{
+    new_client_with_keypair, server_public_key, spawn_server,
+    wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    journal::{Journal, JournalEvent},
+    Client, ClientOptions, EventStream, NetworkTransport,
+};
+use polysig_protocol::{uuid::Uuid, Event, Keypair, SessionId};
+use serde_json::json;
+use std::io::Write;
+
+/// Number of messages sent to the peer.
+const MESSAGES: usize = 4;
+
+/// Journals messages for a session then resumes the event
+/// stream on a new connection from the committed offset.
+#[tokio::test]
+async fn integration_journal_resume() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let dir = std::env::temp_dir()
+        .join(format!("polysig-journal-{}", Uuid::new_v4()));
+    let journal = Journal::open(&dir)?;
+    let session_id = SessionId::new_v4();
+
+    let sender_key = Keypair::generate()?;
+    let (mut sender, event_loop) =
+        new_client_with_keypair::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+            sender_key.clone(),
+        )
+        .await?;
+    sender.connect().await?;
+    let mut sender_stream = event_loop.run();
+    wait_for_event(&mut sender_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Drive the event loop for the sender so it
+    // responds to the peer handshake
+    let sender_task = tokio::task::spawn(async move {
+        while let Some(event) = sender_stream.next().await {
+            event?;
+        }
+        Ok::<_, polysig_client::Error>(())
+    });
+
+    let (mut receiver, mut receiver_stream) =
+        new_journal_client(&server, &server_public_key, &journal)
+            .await?;
+    wait_for_event(&mut receiver_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    receiver.connect_peer(sender_key.public_key()).await?;
+    wait_for_event(&mut receiver_stream, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    for value in 0..MESSAGES {
+        sender
+            .send_json(
+                receiver.public_key(),
+                &value,
+                Some(session_id),
+            )
+            .await?;
+    }
+    for _ in 0..MESSAGES {
+        wait_for_event(&mut receiver_stream, |event| {
+            matches!(event, Event::JsonMessage { .. })
+        })
+        .await?;
+    }
+
+    // Peer messages without a session are not journaled
+    sender
+        .send_json(receiver.public_key(), &"ping", None)
+        .await?;
+    wait_for_event(&mut receiver_stream, |event| {
+        matches!(event, Event::JsonMessage { .. })
+    })
+    .await?;
+
+    let entries = journal.entries(session_id, 0)?;
+    assert_eq!(MESSAGES, entries.len());
+    assert_eq!(MESSAGES as u64, journal.offset(session_id)?);
+    for (index, entry) in entries.iter().enumerate() {
+        assert_eq!(index as u64 + 1, entry.offset);
+        let JournalEvent::JsonMessage { peer_key, message } =
+            &entry.event
+        else {
+            bail!("expected a JSON message");
+        };
+        assert_eq!(sender_key.public_key(), peer_key);
+        assert_eq!(&json!(index), message);
+    }
+    assert_eq!(vec![session_id], journal.sessions()?);
+
+    // The application processed the first two messages
+    // before it exited
+    journal.commit(session_id, 2)?;
+    drop(receiver_stream);
+    drop(receiver);
+
+    // Simulate an entry that was partially written
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(dir.join(format!("{}.jsonl", session_id)))?;
+    file.write_all(b"{\"offset\":5,\"times")?;
+    drop(file);
+
+    let (mut receiver, receiver_stream) =
+        new_journal_client(&server, &server_public_key, &journal)
+            .await?;
+    let mut resumed = journal.resume(session_id, receiver_stream)?;
+
+    // Unprocessed events from the journal
+    for value in 2..MESSAGES {
+        let Some(Ok(Event::JsonMessage {
+            message,
+            session_id: Some(id),
+            ..
+        })) = resumed.next().await
+        else {
+            bail!("expected a journaled message");
+        };
+        assert_eq!(session_id, id);
+        assert_eq!(value, message.deserialize::<usize>()?);
+    }
+    // Requesting the last message committed the one before
+    assert_eq!(3, journal.committed(session_id)?);
+
+    // Events from the new connection follow the journal
+    let Some(Ok(Event::ServerConnected { .. })) =
+        resumed.next().await
+    else {
+        bail!("expected the server connection event");
+    };
+    assert_eq!(MESSAGES as u64, journal.committed(session_id)?);
+
+    receiver.close().await?;
+    sender_task.abort();
+
+    journal.remove(session_id)?;
+    assert!(journal.sessions()?.is_empty());
+    assert_eq!(0, journal.committed(session_id)?);
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+async fn new_journal_client(
+    server: &str,
+    server_public_key: &[u8],
+    journal: &Journal,
+) -> Result<(Client, EventStream)> {
+    let options = ClientOptions {
+        keypair: Some(Keypair::generate()?),
+        server_public_key: Some(server_public_key.to_vec()),
+        journal: Some(journal.clone()),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    client.connect().await?;
+    Ok((client, event_loop.run()))
+}
@@ -1,13 +1,41 @@
+#[cfg(feature = "bls")]
+mod bls;
 #[cfg(feature = "cggmp")]
 mod cggmp;
+mod direct_upgrade;
+mod faults;
 #[cfg(feature = "frost")]
 mod frost_core;
 #[cfg(feature = "frost-ed25519")]
 mod frost_ed25519;
+#[cfg(feature = "frost-ed448")]
+mod frost_ed448;
+#[cfg(feature = "frost-p256")]
+mod frost_p256;
+#[cfg(feature = "frost-ristretto255")]
+mod frost_ristretto255;
+#[cfg(feature = "frost-secp256k1")]
+mod frost_secp256k1;
 #[cfg(feature = "frost-secp256k1-tr")]
 mod frost_secp256k1_tr;
+mod group_broadcast;
+mod journal;
 mod meeting_point;
+#[cfg(feature = "musig2")]
+mod musig2;
 mod peer_channel;
+mod plugin;
+mod record;
+#[cfg(feature = "frost-ed25519")]
+mod replace_transport;
+#[cfg(feature = "frost-ed25519")]
+mod session_deadline;
+mod session_digest;
 mod session_handshake;
+mod session_observer;
+mod session_parameters;
+mod session_resume;
 mod session_timeout;
+#[cfg(feature = "frost-ed25519")]
+mod simulator;
 mod socket_close;
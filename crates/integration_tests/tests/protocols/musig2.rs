@@ -0,0 +1,137 @@
+use crate::test_utils::{server_public_key, spawn_server};
+use anyhow::Result;
+use polysig_client::{musig2::sign, ServerOptions, SessionOptions};
+use polysig_driver::{
+    k256::schnorr::SigningKey,
+    musig2::{
+        aggregated_public_key, internal_key, KeyMode, Participant,
+        PartyOptions, Signature,
+    },
+};
+use polysig_protocol::{Keypair, Parameters};
+use rand::rngs::OsRng;
+
+const MESSAGE: &[u8] = b"this is the message that is sent out";
+
+async fn run_sign(
+    n: u16,
+    server: &str,
+    server_public_key: Vec<u8>,
+    mode: KeyMode,
+) -> Result<()> {
+    let params = Parameters {
+        parties: n,
+        threshold: n,
+    };
+    let server = ServerOptions {
+        server_url: server.to_owned(),
+        server_public_key,
+        pattern: None,
+    };
+
+    let signers = (0..n)
+        .map(|_| SigningKey::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let verifiers = signers
+        .iter()
+        .map(|s| *s.verifying_key())
+        .collect::<Vec<_>>();
+
+    let mut session_options = Vec::new();
+    let mut public_keys = Vec::new();
+    for _ in 0..n {
+        let keypair = Keypair::generate()?;
+        public_keys.push(keypair.public_key().to_vec());
+        session_options.push(SessionOptions {
+            keypair,
+            parameters: params.clone(),
+            server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        });
+    }
+
+    let mut tasks = Vec::new();
+    for (index, (opts, signer)) in
+        session_options.into_iter().zip(signers).enumerate()
+    {
+        let party = PartyOptions::new(
+            public_keys[index].clone(),
+            public_keys.clone(),
+            index == 0,
+            verifiers.clone(),
+        )?;
+        let verifier = *signer.verifying_key();
+        tasks.push(tokio::task::spawn(async move {
+            let signature = sign(
+                opts,
+                Participant::new(signer, verifier, party)?,
+                MESSAGE.to_vec(),
+                mode,
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(signature)
+        }));
+    }
+
+    let mut signatures: Vec<Signature> = Vec::new();
+    for result in futures::future::try_join_all(tasks).await? {
+        signatures.push(result?);
+    }
+
+    let public_key = aggregated_public_key(&verifiers, mode)?;
+    for signature in &signatures {
+        assert_eq!(&signatures[0], signature);
+        public_key.verify_raw(MESSAGE, signature)?;
+    }
+
+    // Aggregated key must not depend on the participant order
+    let mut reversed = verifiers.clone();
+    reversed.reverse();
+    assert_eq!(public_key, aggregated_public_key(&reversed, mode)?);
+
+    if let KeyMode::Taproot = mode {
+        assert_ne!(
+            internal_key(&verifiers)?,
+            <[u8; 32]>::from(public_key.to_bytes())
+        );
+    }
+
+    Ok(())
+}
+
+/// MuSig2 signing (3-of-3).
+#[tokio::test]
+async fn musig2_sign_3_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    run_sign(3, &server, server_public_key, KeyMode::Plain).await?;
+
+    Ok(())
+}
+
+/// MuSig2 signing for a taproot key path spend (2-of-2).
+#[tokio::test]
+async fn musig2_sign_taproot_2_2() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+
+    let server_public_key = server_public_key().await?;
+    run_sign(2, &server, server_public_key, KeyMode::Taproot).await?;
+
+    Ok(())
+}
@@ -0,0 +1,237 @@
+//! Example of a protocol implemented outside of the
+//! library and run as a plugin.
+//!
+//! Each party contributes a number in the first round
+//! and the protocol outputs the sum of all contributions.
+use crate::test_utils::{server_public_key, spawn_server};
+use anyhow::Result;
+use polysig_client::{run_protocol, ServerOptions, SessionOptions};
+use polysig_driver::{
+    PartyOptions, Protocol, ProtocolDriver, Round, RoundInfo,
+};
+use polysig_protocol::{
+    Keypair, Parameters, PartyNumber, RoundNumber,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const ROUND_1: u8 = 1;
+const ROUND_2: u8 = 2;
+
+#[derive(Debug)]
+enum SumError {
+    InvalidRound(u8),
+    UnknownSender(PartyNumber),
+    Protocol(polysig_protocol::Error),
+}
+
+impl std::fmt::Display for SumError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::InvalidRound(round) => {
+                write!(f, "invalid round {}", round)
+            }
+            Self::UnknownSender(sender) => {
+                write!(f, "unknown sender {}", sender)
+            }
+            Self::Protocol(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SumError {}
+
+impl From<polysig_protocol::Error> for SumError {
+    fn from(value: polysig_protocol::Error) -> Self {
+        Self::Protocol(value)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SumMessage {
+    round: RoundNumber,
+    sender: PartyNumber,
+    receiver: PartyNumber,
+    value: u64,
+}
+
+impl Round for SumMessage {
+    fn round_number(&self) -> RoundNumber {
+        self.round
+    }
+
+    fn receiver(&self) -> &PartyNumber {
+        &self.receiver
+    }
+}
+
+struct SumDriver {
+    party_number: PartyNumber,
+    parties: u16,
+    value: u64,
+    round_number: u8,
+    values: BTreeMap<PartyNumber, u64>,
+}
+
+impl SumDriver {
+    fn new(
+        party_number: PartyNumber,
+        parties: u16,
+        value: u64,
+    ) -> Self {
+        Self {
+            party_number,
+            parties,
+            value,
+            round_number: ROUND_1,
+            values: BTreeMap::new(),
+        }
+    }
+}
+
+impl ProtocolDriver for SumDriver {
+    type Error = SumError;
+    type Message = SumMessage;
+    type Output = u64;
+
+    fn round_info(&self) -> Result<RoundInfo, Self::Error> {
+        Ok(RoundInfo {
+            round_number: self.round_number,
+            can_finalize: self.round_number == ROUND_2
+                && self.values.len() == self.parties as usize,
+            is_echo: false,
+        })
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Self::Message>, Self::Error> {
+        if self.round_number != ROUND_1 {
+            return Err(SumError::InvalidRound(self.round_number));
+        }
+
+        self.values.insert(self.party_number, self.value);
+        let messages = (1..=self.parties)
+            .map(|n| PartyNumber::new(n).unwrap())
+            .filter(|n| n != &self.party_number)
+            .map(|receiver| SumMessage {
+                round: RoundNumber::new(ROUND_1.into()).unwrap(),
+                sender: self.party_number,
+                receiver,
+                value: self.value,
+            })
+            .collect();
+        self.round_number = ROUND_2;
+        Ok(messages)
+    }
+
+    fn handle_incoming(
+        &mut self,
+        message: Self::Message,
+    ) -> Result<(), Self::Error> {
+        if message.round.get() != ROUND_1 as u16 {
+            return Err(SumError::InvalidRound(
+                message.round.get() as u8
+            ));
+        }
+        if message.sender.get() > self.parties {
+            return Err(SumError::UnknownSender(message.sender));
+        }
+        self.values.insert(message.sender, message.value);
+        Ok(())
+    }
+
+    fn try_finalize_round(
+        &mut self,
+    ) -> Result<Option<Self::Output>, Self::Error> {
+        if self.round_number == ROUND_2
+            && self.values.len() == self.parties as usize
+        {
+            Ok(Some(self.values.values().sum()))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+struct Sum;
+
+impl Protocol for Sum {
+    const NAME: &'static str = "example-sum";
+    type Driver = SumDriver;
+}
+
+/// Run a protocol that is not part of the library.
+#[tokio::test]
+async fn plugin_protocol_sum() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = ServerOptions {
+        server_url: format!("ws://{}", addr),
+        server_public_key: server_public_key().await?,
+        pattern: None,
+    };
+
+    let n = 3u16;
+    let params = Parameters {
+        parties: n,
+        threshold: n,
+    };
+
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<Result<Vec<_>, _>>()?;
+    let public_keys = keypairs
+        .iter()
+        .map(|k| k.public_key().to_vec())
+        .collect::<Vec<_>>();
+
+    let mut tasks = Vec::new();
+    for (index, keypair) in keypairs.into_iter().enumerate() {
+        let party = PartyOptions::new(
+            keypair.public_key().to_vec(),
+            public_keys.clone(),
+            index == 0,
+            public_keys.clone(),
+        )?;
+        let options = SessionOptions {
+            keypair,
+            parameters: params.clone(),
+            server: server.clone(),
+            policy: None,
+            progress: None,
+            cancel: None,
+            key_share_policy: None,
+            signing_context: None,
+            attestation: None,
+            round_timeout: None,
+            keepalive: None,
+        };
+        let value = (index as u64 + 1) * 10;
+        tasks.push(tokio::task::spawn(async move {
+            let sum = run_protocol::<Sum, _, _>(
+                options,
+                &party,
+                |party_number, session| {
+                    Ok(SumDriver::new(
+                        party_number,
+                        session.len() as u16,
+                        value,
+                    ))
+                },
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(sum)
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    for result in results {
+        assert_eq!(60, result?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use crate::test_utils::{
+    peer_channel, server_public_key, spawn_server,
+};
+use anyhow::Result;
+use polysig_client::{
+    record::{Direction, Payload, Recorder, Transcript},
+    Client, ClientOptions, EventLoop,
+};
+use polysig_protocol::{uuid::Uuid, Keypair};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+
+/// Creates two recording clients that exchange "ping" and
+/// "pong" messages and checks both transcripts.
+#[tokio::test]
+async fn integration_record_peer_channel() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let dir = transcript_dir()?;
+    let initiator_path = dir.join("initiator.jsonl");
+    let participant_path = dir.join("participant.jsonl");
+    let initiator_key = Keypair::generate()?;
+    let participant_key = Keypair::generate()?;
+
+    let (initiator, event_loop_i) = new_recorded_client(
+        &server,
+        server_public_key.clone(),
+        initiator_key.clone(),
+        &initiator_path,
+    )
+    .await?;
+    let (participant, event_loop_p) = new_recorded_client(
+        &server,
+        server_public_key.clone(),
+        participant_key.clone(),
+        &participant_path,
+    )
+    .await?;
+
+    let (shutdown_tx, shutdown_rx) = mpsc::channel::<()>(1);
+    let (res_i, res_p) = futures::join!(
+        peer_channel::initiator_client::<anyhow::Error>(
+            initiator,
+            event_loop_i,
+            shutdown_tx,
+        ),
+        peer_channel::participant_client::<anyhow::Error>(
+            participant,
+            event_loop_p,
+            initiator_key.public_key(),
+            shutdown_rx,
+        ),
+    );
+    res_i?;
+    res_p?;
+
+    let transcript = Transcript::load(&initiator_path)?;
+    assert_eq!(initiator_key.public_key(), &transcript.public_key);
+    assert_eq!(
+        vec![
+            (
+                Direction::Outgoing,
+                participant_key.public_key().to_vec(),
+                json!("ping")
+            ),
+            (
+                Direction::Incoming,
+                participant_key.public_key().to_vec(),
+                json!("pong")
+            ),
+        ],
+        messages(&transcript),
+    );
+
+    let transcript = Transcript::load(&participant_path)?;
+    assert_eq!(participant_key.public_key(), &transcript.public_key);
+    assert_eq!(
+        vec![
+            (
+                Direction::Incoming,
+                initiator_key.public_key().to_vec(),
+                json!("ping")
+            ),
+            (
+                Direction::Outgoing,
+                initiator_key.public_key().to_vec(),
+                json!("pong")
+            ),
+        ],
+        messages(&transcript),
+    );
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// Records a FROST key generation run in-process and replays
+/// the transcripts through new drivers.
+#[cfg(feature = "frost-ed25519")]
+#[test]
+fn record_replay_frost_dkg() -> Result<()> {
+    use polysig_client::record::Replay;
+    use polysig_driver::{
+        frost::ed25519::{DkgDriver, KeyShare},
+        frost_ed25519::Identifier,
+        ProtocolDriver, Round,
+    };
+    use polysig_protocol::{Parameters, SessionId};
+    use std::{collections::VecDeque, num::NonZeroU16};
+
+    let n = 3;
+    let params = Parameters {
+        parties: n,
+        threshold: 2,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let session_id = SessionId::new_v4();
+    let keys = (0..n)
+        .map(|_| Ok(Keypair::generate()?.public_key().to_vec()))
+        .collect::<Result<Vec<_>>>()?;
+    let drivers = || {
+        keys.iter()
+            .enumerate()
+            .map(|(index, key)| {
+                let party_number =
+                    NonZeroU16::new(index as u16 + 1).unwrap();
+                let driver = DkgDriver::new(
+                    party_number,
+                    params.clone(),
+                    identifiers.clone(),
+                )?;
+                Ok((key.clone(), driver))
+            })
+            .collect::<Result<Vec<_>>>()
+    };
+
+    // Record a run delivering every message in the order sent
+    let dir = transcript_dir()?;
+    let paths: Vec<PathBuf> = (0..n)
+        .map(|index| dir.join(format!("party-{}.jsonl", index + 1)))
+        .collect();
+    let recorders = paths
+        .iter()
+        .zip(keys.iter())
+        .map(|(path, key)| Recorder::create(path, key))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut parties = drivers()?;
+    let mut queue = VecDeque::new();
+    for (index, (_, driver)) in parties.iter_mut().enumerate() {
+        for message in driver.proceed()? {
+            queue.push_back((index, message));
+        }
+    }
+    while let Some((sender, message)) = queue.pop_front() {
+        let receiver = message.receiver().get() as usize - 1;
+        recorders[receiver].record(
+            Direction::Incoming,
+            &keys[sender],
+            Some(session_id),
+            Payload::Json(serde_json::to_value(&message)?),
+        )?;
+
+        let driver = &mut parties[receiver].1;
+        driver.handle_incoming(message)?;
+        if driver.round_info()?.can_finalize
+            && driver.try_finalize_round()?.is_none()
+        {
+            for message in driver.proceed()? {
+                queue.push_back((receiver, message));
+            }
+        }
+    }
+
+    let transcripts = paths
+        .iter()
+        .map(Transcript::load)
+        .collect::<Result<Vec<_>, _>>()?;
+    let output =
+        Replay::new(session_id, drivers()?, &transcripts)?.run()?;
+    assert_eq!(0, output.undelivered);
+    assert_eq!(0, output.ignored);
+
+    let key_shares: Vec<KeyShare> =
+        output.outputs.into_iter().collect::<Option<_>>().unwrap();
+    assert_eq!(n as usize, key_shares.len());
+    let verifying_key = key_shares[0].1.verifying_key();
+    assert!(key_shares
+        .iter()
+        .all(|(_, public)| public.verifying_key() == verifying_key));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+async fn new_recorded_client(
+    server: &str,
+    server_public_key: Vec<u8>,
+    keypair: Keypair,
+    path: &Path,
+) -> Result<(Client, EventLoop)> {
+    let recorder = Recorder::create(path, keypair.public_key())?;
+    let options = ClientOptions {
+        keypair: Some(keypair),
+        server_public_key: Some(server_public_key),
+        recorder: Some(recorder),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    Ok(Client::new(&url, options).await?)
+}
+
+fn transcript_dir() -> Result<PathBuf> {
+    let dir = std::env::temp_dir()
+        .join(format!("polysig-record-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn messages(
+    transcript: &Transcript,
+) -> Vec<(Direction, Vec<u8>, Value)> {
+    transcript
+        .messages
+        .iter()
+        .map(|message| {
+            let value = match &message.payload {
+                Payload::Json(value) => value.clone(),
+                Payload::Blob(_) => Value::Null,
+            };
+            (message.direction, message.peer_key.clone(), value)
+        })
+        .collect()
+}
@@ -0,0 +1,108 @@
+use crate::test_utils::{server_public_key, spawn_server};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    faults::{FaultConfig, Faults},
+    frost::ed25519::dkg::new_driver,
+    wait_for_driver, wait_for_session, Client, ClientOptions, Driver,
+    NetworkTransport, SessionInitiator, SessionParticipant,
+    Transport,
+};
+use polysig_driver::frost_ed25519::Identifier;
+use polysig_protocol::{Keypair, Parameters};
+
+/// Replaces the transport of a FROST key generation driver
+/// after the first round so the messages for the round are
+/// sent again; peers discard the repeated messages.
+///
+/// The first party also sends every message twice.
+#[tokio::test]
+async fn integration_replace_transport() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let n = 3;
+    let params = Parameters {
+        parties: n,
+        threshold: 2,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let participants: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+
+    let mut tasks = Vec::new();
+    for (index, keypair) in keypairs.into_iter().enumerate() {
+        let faults = (index == 0).then(|| {
+            Faults::new(FaultConfig {
+                duplicate_rate: 1.0,
+                ..Default::default()
+            })
+        });
+        let options = ClientOptions {
+            keypair: Some(keypair),
+            server_public_key: Some(server_public_key.clone()),
+            faults,
+            ..Default::default()
+        };
+        let url = options.url(&server);
+        let participants = participants.clone();
+        let params = params.clone();
+        let identifiers = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let (client, event_loop) =
+                Client::new(&url, options).await?;
+            let mut transport: Transport = client.into();
+            transport.connect().await?;
+            let mut stream = event_loop.run();
+            let (transport, session) = if index == 0 {
+                let handler =
+                    SessionInitiator::new(transport, participants);
+                wait_for_session(&mut stream, handler).await?
+            } else {
+                let handler = SessionParticipant::new(transport);
+                wait_for_session(&mut stream, handler).await?
+            };
+
+            let replacement = transport.clone();
+            let mut driver =
+                new_driver(transport, session, params, identifiers)?;
+            if index != 0 {
+                let (_, key_share) =
+                    wait_for_driver(&mut stream, driver).await?;
+                return Ok::<_, anyhow::Error>(key_share);
+            }
+
+            // Send the first round again using the replacement
+            if driver.execute().await?.is_some() {
+                bail!("expected key generation to need messages");
+            }
+            driver.replace_transport(replacement).await?;
+            while let Some(event) = stream.next().await {
+                if let Some(key_share) =
+                    driver.handle_event(event?).await?
+                {
+                    return Ok(key_share);
+                }
+            }
+            bail!("event stream closed")
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    let key_shares =
+        results.into_iter().collect::<Result<Vec<_>>>()?;
+    let verifying_key = key_shares[0].1.verifying_key();
+    for key_share in &key_shares {
+        assert_eq!(verifying_key, key_share.1.verifying_key());
+    }
+
+    Ok(())
+}
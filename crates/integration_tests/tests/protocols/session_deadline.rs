@@ -0,0 +1,164 @@
+use crate::test_utils::{
+    new_client, server_public_key, spawn_server,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    frost::ed25519::dkg::new_driver, wait_for_driver,
+    wait_for_session, Client, ClientOptions, Error, NetworkTransport,
+    SessionInitiator, SessionParticipant, Transport,
+};
+use polysig_driver::frost_ed25519::Identifier;
+use polysig_protocol::{
+    http::StatusCode, Event, Keypair, Parameters, SessionRequest,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the UNIX epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Runs a FROST key generation where one party joins the
+/// session but never starts the driver; the server times out
+/// the session at the deadline and the drivers of the other
+/// parties return a deadline error.
+#[tokio::test]
+async fn integration_session_deadline() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let n = 3;
+    let params = Parameters {
+        parties: n,
+        threshold: 2,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    let keypairs = (0..n)
+        .map(|_| Keypair::generate())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let participants: Vec<Vec<u8>> =
+        keypairs.iter().map(|k| k.public_key().to_vec()).collect();
+    let deadline = now() + 5;
+
+    let mut tasks = Vec::new();
+    for (index, keypair) in keypairs.into_iter().enumerate() {
+        let options = ClientOptions {
+            keypair: Some(keypair),
+            server_public_key: Some(server_public_key.clone()),
+            ..Default::default()
+        };
+        let url = options.url(&server);
+        let participants = participants.clone();
+        let params = params.clone();
+        let identifiers = identifiers.clone();
+        tasks.push(tokio::task::spawn(async move {
+            let (client, event_loop) =
+                Client::new(&url, options).await?;
+            let mut transport: Transport = client.into();
+            transport.connect().await?;
+            let mut stream = event_loop.run();
+            let (transport, session) = if index == 0 {
+                let handler =
+                    SessionInitiator::new(transport, participants)
+                        .with_parameters(params.clone())
+                        .with_deadline(deadline);
+                wait_for_session(&mut stream, handler).await?
+            } else {
+                let handler = SessionParticipant::new(transport);
+                wait_for_session(&mut stream, handler).await?
+            };
+
+            // Stuck party never sends the first round
+            if index == n as usize - 1 {
+                while let Some(event) = stream.next().await {
+                    if let Event::SessionTimeout(id) = event? {
+                        assert_eq!(session.session_id, id);
+                        assert!(now() >= deadline);
+                        return Ok(());
+                    }
+                }
+                bail!("event stream closed");
+            }
+
+            let driver =
+                new_driver(transport, session, params, identifiers)?;
+            match wait_for_driver(&mut stream, driver).await {
+                Err(Error::Ceremony { source, .. }) => {
+                    assert!(matches!(
+                        *source,
+                        Error::SessionDeadline(_)
+                    ));
+                    assert_eq!("SESSION_DEADLINE", source.code());
+                    assert!(source.is_retryable());
+                    Ok::<_, anyhow::Error>(())
+                }
+                Err(e) => Err(e.into()),
+                Ok(_) => bail!("expected session deadline error"),
+            }
+        }));
+    }
+
+    let results = futures::future::try_join_all(tasks).await?;
+    results.into_iter().collect::<Result<Vec<_>>>()?;
+
+    Ok(())
+}
+
+/// Server rejects a session with a deadline in the past.
+#[tokio::test]
+async fn integration_session_deadline_passed() -> Result<()> {
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (_participant, _event_loop_p, participant_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+
+    initiator.connect().await?;
+    let mut s_i = event_loop_i.run();
+    while let Some(event) = s_i.next().await {
+        if let Event::ServerConnected { .. } = event? {
+            break;
+        }
+    }
+
+    initiator
+        .request_session(SessionRequest {
+            participant_keys: vec![
+                initiator_key.public_key().to_vec(),
+                participant_key.public_key().to_vec(),
+            ],
+            deadline: Some(now() - 1),
+            ..Default::default()
+        })
+        .await?;
+    match s_i.next().await {
+        Some(Err(Error::ServerError(status, _))) => {
+            assert_eq!(StatusCode::BAD_REQUEST, status);
+        }
+        _ => bail!("expected server error"),
+    }
+
+    initiator.close().await?;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::test_utils::{
+    new_client, server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::Result;
+use futures::StreamExt;
+use polysig_client::{EventStream, NetworkTransport};
+use polysig_protocol::{Event, SessionTranscript};
+
+/// Requests the digest of the messages relayed in a session
+/// without observers from the server.
+#[tokio::test]
+async fn integration_session_digest() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut participant, event_loop_p, participant_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut outsider, event_loop_o, _) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let session_participants = vec![
+        initiator_key.public_key().to_vec(),
+        participant_key.public_key().to_vec(),
+    ];
+
+    initiator.connect().await?;
+    participant.connect().await?;
+    outsider.connect().await?;
+    let mut s_i = event_loop_i.run();
+    let mut s_p = event_loop_p.run();
+    let mut s_o = event_loop_o.run();
+    for stream in [&mut s_i, &mut s_p, &mut s_o] {
+        wait_for_event(stream, |event| {
+            matches!(event, Event::ServerConnected { .. })
+        })
+        .await?;
+    }
+
+    initiator.new_session(session_participants).await?;
+    let Event::SessionCreated(session) =
+        wait_for_event(&mut s_i, |event| {
+            matches!(event, Event::SessionCreated(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    let session_id = session.session_id;
+
+    // Digest is empty before any messages are relayed
+    participant.session_digest(session_id).await?;
+    let digest = next_digest(&mut s_p).await?;
+    assert_eq!(session_id, digest.session_id);
+    assert_eq!(0, digest.messages);
+
+    initiator.connect_peer(participant_key.public_key()).await?;
+    wait_for_event(&mut s_i, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    for message in ["first", "second"] {
+        initiator
+            .send_json(
+                participant_key.public_key(),
+                &message,
+                Some(session_id),
+            )
+            .await?;
+        wait_for_event(&mut s_p, |event| {
+            matches!(event, Event::JsonMessage { .. })
+        })
+        .await?;
+    }
+
+    // Participants receive the same digest
+    initiator.session_digest(session_id).await?;
+    participant.session_digest(session_id).await?;
+    let first = next_digest(&mut s_i).await?;
+    let second = next_digest(&mut s_p).await?;
+    assert_eq!(2, first.messages);
+    assert_ne!(digest.hash, first.hash);
+    assert_eq!(first, second);
+
+    // Only participants and observers may request the digest
+    outsider.session_digest(session_id).await?;
+    let mut denied = false;
+    while let Some(event) = s_o.next().await {
+        match event {
+            Err(polysig_client::Error::ServerError(_, _)) => {
+                denied = true;
+                break;
+            }
+            Ok(Event::SessionDigest(_)) => break,
+            _ => {}
+        }
+    }
+    assert!(denied);
+
+    initiator.close_session(session_id).await?;
+    initiator.close().await?;
+    participant.close().await?;
+    outsider.close().await?;
+    Ok(())
+}
+
+async fn next_digest(
+    stream: &mut EventStream,
+) -> Result<SessionTranscript> {
+    let Event::SessionDigest(digest) =
+        wait_for_event(stream, |event| {
+            matches!(event, Event::SessionDigest(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    Ok(digest)
+}
@@ -0,0 +1,217 @@
+use crate::test_utils::{
+    new_client, server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{EventStream, NetworkTransport};
+use polysig_protocol::{Event, SessionTranscript};
+
+/// Creates a session with an observer that receives the session
+/// notifications and transcript hashes but cannot send peer
+/// messages whilst the session is live.
+#[tokio::test]
+async fn integration_session_observer() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut participant, event_loop_p, participant_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut observer, event_loop_o, observer_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let session_participants = vec![
+        initiator_key.public_key().to_vec(),
+        participant_key.public_key().to_vec(),
+    ];
+
+    initiator.connect().await?;
+    participant.connect().await?;
+    observer.connect().await?;
+    let mut s_i = event_loop_i.run();
+    let mut s_p = event_loop_p.run();
+    let mut s_o = event_loop_o.run();
+    for stream in [&mut s_i, &mut s_p, &mut s_o] {
+        wait_for_event(stream, |event| {
+            matches!(event, Event::ServerConnected { .. })
+        })
+        .await?;
+    }
+
+    // Peer channel opened before the observer is added to
+    // a session
+    observer.connect_peer(participant_key.public_key()).await?;
+    wait_for_event(&mut s_o, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    // Observers cannot also be participants
+    initiator
+        .new_observed_session(
+            session_participants.clone(),
+            vec![participant_key.public_key().to_vec()],
+        )
+        .await?;
+    assert!(next_error(&mut s_i).await?);
+
+    initiator
+        .new_observed_session(
+            session_participants.clone(),
+            vec![observer_key.public_key().to_vec()],
+        )
+        .await?;
+    let Event::SessionCreated(session) =
+        wait_for_event(&mut s_i, |event| {
+            matches!(event, Event::SessionCreated(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(session_participants, session.all_participants);
+
+    // Observer is notified with a token but is not a participant
+    let Event::SessionReady(ready) =
+        wait_for_event(&mut s_o, |event| {
+            matches!(event, Event::SessionReady(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(session.session_id, ready.session_id);
+    assert_eq!(session_participants, ready.all_participants);
+    assert!(ready.party_number(observer_key.public_key()).is_none());
+    assert_eq!(
+        Some(observer_key.public_key()),
+        ready.token.as_ref().map(|t| t.public_key.as_slice())
+    );
+
+    initiator.connect_peer(participant_key.public_key()).await?;
+    wait_for_event(&mut s_i, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+    wait_for_event(&mut s_p, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    // Each message relayed in the session extends the transcript
+    let mut transcripts = Vec::new();
+    for message in ["first", "second"] {
+        initiator
+            .send_json(
+                participant_key.public_key(),
+                &message,
+                Some(session.session_id),
+            )
+            .await?;
+        wait_for_event(&mut s_p, |event| {
+            matches!(event, Event::JsonMessage { .. })
+        })
+        .await?;
+        transcripts.push(next_transcript(&mut s_o).await?);
+    }
+    assert_eq!(session.session_id, transcripts[0].session_id);
+    assert_eq!(1, transcripts[0].messages);
+    assert_eq!(2, transcripts[1].messages);
+    assert_ne!(transcripts[0].hash, transcripts[1].hash);
+
+    // Observers cannot send messages in the session context
+    observer
+        .send_json(
+            participant_key.public_key(),
+            &"forged",
+            Some(session.session_id),
+        )
+        .await?;
+    assert!(next_error(&mut s_o).await?);
+
+    // Nor outside of the session context
+    observer
+        .send_json(participant_key.public_key(), &"forged", None)
+        .await?;
+    assert!(next_error(&mut s_o).await?);
+
+    // Nor connect to the session participants
+    observer.connect_peer(initiator_key.public_key()).await?;
+    assert!(next_error(&mut s_o).await?);
+
+    // Observers are notified when the session is finished
+    initiator.close_session(session.session_id).await?;
+    let Event::SessionFinished(session_id, stats) =
+        wait_for_event(&mut s_o, |event| {
+            matches!(event, Event::SessionFinished(..))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(session.session_id, session_id);
+
+    // Statistics count the frames relayed from each participant
+    let stats = stats.expect("session statistics");
+    assert_eq!(2, stats.participants.len());
+    assert_eq!(
+        initiator_key.public_key(),
+        stats.participants[0].public_key.as_slice()
+    );
+    assert_eq!(2, stats.participants[0].frames);
+    assert!(stats.participants[0].bytes > 0);
+    assert_eq!(0, stats.participants[1].frames);
+    assert_eq!(2, stats.frames());
+
+    initiator.close().await?;
+    participant.close().await?;
+    observer.close().await?;
+    Ok(())
+}
+
+async fn next_transcript(
+    stream: &mut EventStream,
+) -> Result<SessionTranscript> {
+    let Event::SessionTranscript(transcript) =
+        wait_for_event(stream, |event| {
+            matches!(event, Event::SessionTranscript(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    Ok(transcript)
+}
+
+/// Determine if the next message from the server is an error.
+///
+/// The session is never active so the timeout notification
+/// may arrive first.
+async fn next_error(stream: &mut EventStream) -> Result<bool> {
+    while let Some(event) = stream.next().await {
+        match event {
+            Err(polysig_client::Error::ServerError(_, _)) => {
+                return Ok(true)
+            }
+            Ok(Event::SessionTimeout(_)) => {}
+            _ => return Ok(false),
+        }
+    }
+    bail!("event stream closed")
+}
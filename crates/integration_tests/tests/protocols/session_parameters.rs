@@ -0,0 +1,127 @@
+use crate::test_utils::{
+    new_client, server_public_key, spawn_server, wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{EventStream, NetworkTransport};
+use polysig_protocol::{http::StatusCode, Event, Parameters};
+
+/// Creates sessions with parameters that the server validates
+/// against the session participants.
+#[tokio::test]
+async fn integration_session_parameters() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (_participant, _event_loop_p, participant_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let session_participants = vec![
+        initiator_key.public_key().to_vec(),
+        participant_key.public_key().to_vec(),
+    ];
+
+    initiator.connect().await?;
+    let mut s_i = event_loop_i.run();
+    wait_for_event(&mut s_i, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Number of parties must match the participants
+    initiator
+        .new_session_with_parameters(
+            session_participants.clone(),
+            Parameters {
+                parties: 3,
+                threshold: 2,
+            },
+        )
+        .await?;
+    assert_eq!(
+        Some(StatusCode::BAD_REQUEST),
+        next_error(&mut s_i).await?
+    );
+
+    // Threshold cannot exceed the number of parties
+    initiator
+        .new_session_with_parameters(
+            session_participants.clone(),
+            Parameters {
+                parties: 2,
+                threshold: 3,
+            },
+        )
+        .await?;
+    assert_eq!(
+        Some(StatusCode::BAD_REQUEST),
+        next_error(&mut s_i).await?
+    );
+
+    initiator
+        .new_session_with_parameters(
+            session_participants.clone(),
+            Parameters {
+                parties: 2,
+                threshold: 2,
+            },
+        )
+        .await?;
+    let Event::SessionCreated(session) =
+        wait_for_event(&mut s_i, |event| {
+            matches!(event, Event::SessionCreated(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(session_participants, session.all_participants);
+
+    initiator.close().await?;
+    Ok(())
+}
+
+/// Protocols with a minimum threshold reject parameters
+/// before connecting to the server.
+#[test]
+fn session_parameters_min_threshold() {
+    let parameters = Parameters {
+        parties: 2,
+        threshold: 1,
+    };
+    assert!(parameters.validate_session(2, 1).is_ok());
+    assert!(matches!(
+        parameters.validate_session(2, 2),
+        Err(polysig_protocol::Error::ThresholdTooLow(2, 1))
+    ));
+    assert!(matches!(
+        parameters.validate_session(3, 1),
+        Err(polysig_protocol::Error::PartiesMismatch(2, 3))
+    ));
+}
+
+/// Status code of the next error from the server.
+async fn next_error(
+    stream: &mut EventStream,
+) -> Result<Option<StatusCode>> {
+    match stream.next().await {
+        Some(Err(polysig_client::Error::ServerError(status, _))) => {
+            Ok(Some(status))
+        }
+        Some(_) => Ok(None),
+        None => bail!("event stream closed"),
+    }
+}
@@ -0,0 +1,282 @@
+use crate::test_utils::{
+    new_client, new_client_with_keypair, server_public_key,
+    spawn_server, wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    Client, ClientOptions, EventStream, NetworkTransport,
+};
+use polysig_protocol::{
+    Event, Keypair, SessionState, SessionToken, PATTERN_IK,
+};
+
+/// Creates a session then reconnects a participant and
+/// re-attaches it to the session using the session token.
+#[tokio::test]
+async fn integration_session_resume() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let (mut participant, event_loop_p, participant_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let session_participants = vec![
+        initiator_key.public_key().to_vec(),
+        participant_key.public_key().to_vec(),
+    ];
+
+    initiator.connect().await?;
+    participant.connect().await?;
+    let mut s_i = event_loop_i.run();
+    let mut s_p = event_loop_p.run();
+
+    wait_for_event(&mut s_p, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    wait_for_event(&mut s_i, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    initiator.new_session(session_participants.clone()).await?;
+
+    // Owner receives a token when the session is created
+    let Event::SessionCreated(created) =
+        wait_for_event(&mut s_i, |event| {
+            matches!(event, Event::SessionCreated(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    let owner_token = token(&created)?;
+    assert_eq!(initiator_key.public_key(), owner_token.public_key);
+
+    // Participants receive their own token when the session is ready
+    let Event::SessionReady(ready) =
+        wait_for_event(&mut s_p, |event| {
+            matches!(event, Event::SessionReady(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    let participant_token = token(&ready)?;
+    assert_eq!(created.session_id, participant_token.session_id);
+    assert_eq!(
+        participant_key.public_key(),
+        participant_token.public_key
+    );
+
+    // Reconnect the participant with the same keypair
+    participant.close().await?;
+    drop(s_p);
+    let (mut participant, event_loop_p) =
+        new_client_with_keypair::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+            participant_key.clone(),
+        )
+        .await?;
+    participant.connect().await?;
+    let mut s_p = event_loop_p.run();
+    wait_for_event(&mut s_p, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Tampered tokens are rejected
+    let mut tampered = participant_token.clone();
+    tampered.expires_at += 3600;
+    participant.resume_session(tampered).await?;
+    assert!(next_error(&mut s_p).await?);
+
+    // Tokens issued to another participant are rejected
+    participant.resume_session(owner_token).await?;
+    assert!(next_error(&mut s_p).await?);
+
+    participant.resume_session(participant_token).await?;
+    let Event::SessionResumed(resumed) =
+        wait_for_event(&mut s_p, |event| {
+            matches!(event, Event::SessionResumed(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(created.session_id, resumed.session_id);
+    assert_eq!(session_participants, resumed.all_participants);
+    let resumed_token = token(&resumed)?;
+    assert_eq!(
+        participant_key.public_key(),
+        resumed_token.public_key
+    );
+
+    initiator.close().await?;
+    participant.close().await?;
+    Ok(())
+}
+
+/// Reconnects a participant using the IK pattern and sends
+/// the session token with the first handshake message.
+#[tokio::test]
+async fn integration_session_resume_zero_rtt() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut initiator, event_loop_i, initiator_key) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    let participant_key = Keypair::generate()?;
+    let (mut participant, mut s_p) = new_ik_client(
+        &server,
+        &server_public_key,
+        participant_key.clone(),
+        None,
+    )
+    .await?;
+    let session_participants = vec![
+        initiator_key.public_key().to_vec(),
+        participant_key.public_key().to_vec(),
+    ];
+
+    initiator.connect().await?;
+    let mut s_i = event_loop_i.run();
+    wait_for_event(&mut s_i, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    wait_for_event(&mut s_p, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    initiator.new_session(session_participants.clone()).await?;
+
+    let Event::SessionReady(ready) =
+        wait_for_event(&mut s_p, |event| {
+            matches!(event, Event::SessionReady(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    let participant_token = token(&ready)?;
+
+    // Reconnect and resume in the first handshake message
+    participant.close().await?;
+    drop(s_p);
+    let (mut participant, mut s_p) = new_ik_client(
+        &server,
+        &server_public_key,
+        participant_key.clone(),
+        Some(participant_token),
+    )
+    .await?;
+    wait_for_event(&mut s_p, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+    let Event::SessionResumed(resumed) =
+        wait_for_event(&mut s_p, |event| {
+            matches!(event, Event::SessionResumed(_))
+        })
+        .await?
+    else {
+        unreachable!();
+    };
+    assert_eq!(ready.session_id, resumed.session_id);
+    assert_eq!(session_participants, resumed.all_participants);
+
+    // Session tokens require a zero round trip pattern
+    let options = ClientOptions {
+        keypair: Some(participant_key),
+        server_public_key: Some(server_public_key.clone()),
+        session_token: resumed.token,
+        ..Default::default()
+    };
+    let url = options.url(&server);
+    let (mut client, _event_loop) =
+        Client::new(&url, options).await?;
+    assert!(matches!(
+        client.connect().await,
+        Err(polysig_client::Error::ZeroRttPattern)
+    ));
+
+    // Patterns must be allowed by the server
+    let options = ClientOptions {
+        keypair: Some(Keypair::generate()?),
+        server_public_key: Some(server_public_key.clone()),
+        pattern: Some("Noise_XX_25519_ChaChaPoly_BLAKE2s".to_owned()),
+        ..Default::default()
+    };
+    let url = options.url(&server);
+    assert!(Client::new(&url, options).await.is_err());
+
+    initiator.close().await?;
+    participant.close().await?;
+    Ok(())
+}
+
+async fn new_ik_client(
+    server: &str,
+    server_public_key: &[u8],
+    keypair: Keypair,
+    session_token: Option<SessionToken>,
+) -> Result<(Client, EventStream)> {
+    let options = ClientOptions {
+        keypair: Some(keypair),
+        server_public_key: Some(server_public_key.to_vec()),
+        pattern: Some(PATTERN_IK.to_owned()),
+        session_token,
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    client.connect().await?;
+    Ok((client, event_loop.run()))
+}
+
+fn token(session: &SessionState) -> Result<SessionToken> {
+    match &session.token {
+        Some(token) => Ok(token.clone()),
+        None => bail!("expected a session token"),
+    }
+}
+
+/// Determine if the next message from the server is an error.
+///
+/// The session is never active so the timeout notification
+/// may arrive first.
+async fn next_error(stream: &mut EventStream) -> Result<bool> {
+    while let Some(event) = stream.next().await {
+        match event {
+            Err(polysig_client::Error::ServerError(_, _)) => {
+                return Ok(true)
+            }
+            Ok(Event::SessionTimeout(_)) => {}
+            _ => return Ok(false),
+        }
+    }
+    bail!("event stream closed")
+}
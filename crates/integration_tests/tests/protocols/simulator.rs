@@ -0,0 +1,115 @@
+use anyhow::Result;
+use polysig_driver::{
+    frost::ed25519::{DkgDriver, KeyShare},
+    frost_ed25519::Identifier,
+    simulator::{
+        MessageFilter, Simulation, Simulator, SimulatorOptions,
+    },
+};
+use polysig_protocol::Parameters;
+use std::num::NonZeroU16;
+
+fn drivers(n: u16, t: u16) -> Result<Vec<DkgDriver>> {
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+    let identifiers: Vec<Identifier> =
+        (1..=n).map(|i| i.try_into().unwrap()).collect();
+    (1..=n)
+        .map(|i| {
+            Ok(DkgDriver::new(
+                NonZeroU16::new(i).unwrap(),
+                params.clone(),
+                identifiers.clone(),
+            )?)
+        })
+        .collect()
+}
+
+fn assert_same_key(simulation: &Simulation<KeyShare>) {
+    let verifying_keys: Vec<_> = simulation
+        .outputs
+        .iter()
+        .flatten()
+        .map(|(_, public)| public.verifying_key())
+        .collect();
+    assert!(verifying_keys.windows(2).all(|w| w[0] == w[1]));
+}
+
+#[test]
+fn simulator_frost_dkg_in_order() -> Result<()> {
+    let simulation =
+        Simulator::new(drivers(3, 2)?, Default::default()).run()?;
+    assert!(simulation.is_complete());
+    assert_eq!(0, simulation.dropped);
+    assert_eq!(0, simulation.ignored);
+    assert_same_key(&simulation);
+    Ok(())
+}
+
+#[test]
+fn simulator_frost_dkg_reorder() -> Result<()> {
+    for seed in 0..32 {
+        let options = SimulatorOptions {
+            seed,
+            reorder: true,
+            max_delay: 4,
+            ..Default::default()
+        };
+        let simulation =
+            Simulator::new(drivers(4, 3)?, options).run()?;
+        assert!(simulation.is_complete(), "seed {}", seed);
+        assert_same_key(&simulation);
+    }
+    Ok(())
+}
+
+#[test]
+fn simulator_frost_dkg_drop() -> Result<()> {
+    // Party 2 never receives the round 2 package from party 1
+    let options = SimulatorOptions {
+        drop: vec![MessageFilter {
+            sender: Some(1),
+            receiver: Some(2),
+            round: Some(2),
+        }],
+        ..Default::default()
+    };
+    let simulation = Simulator::new(drivers(3, 2)?, options).run()?;
+    assert_eq!(1, simulation.dropped);
+    assert!(simulation.outputs[0].is_some());
+    assert!(simulation.outputs[1].is_none());
+    assert!(simulation.outputs[2].is_some());
+    Ok(())
+}
+
+#[test]
+fn simulator_deterministic_schedule() -> Result<()> {
+    let options = SimulatorOptions {
+        seed: 7,
+        reorder: true,
+        max_delay: 3,
+        drop_rate: 0.2,
+        ..Default::default()
+    };
+    let first =
+        Simulator::new(drivers(4, 3)?, options.clone()).run()?;
+    let second = Simulator::new(drivers(4, 3)?, options).run()?;
+    assert_eq!(first.steps, second.steps);
+    assert_eq!(first.delivered, second.delivered);
+    assert_eq!(first.dropped, second.dropped);
+    assert_eq!(
+        first
+            .outputs
+            .iter()
+            .map(Option::is_some)
+            .collect::<Vec<_>>(),
+        second
+            .outputs
+            .iter()
+            .map(Option::is_some)
+            .collect::<Vec<_>>(),
+    );
+    Ok(())
+}
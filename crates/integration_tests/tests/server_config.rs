@@ -0,0 +1,89 @@
+use anyhow::Result;
+use polysig_protocol::{Keypair, PATTERN, PATTERN_IK};
+
+#[test]
+fn relay_server_config_builder() -> Result<()> {
+    use polysig_relay_server::{Error, ServerConfig};
+
+    let public_key = vec![1u8; 32];
+    let config = ServerConfig::builder()
+        .key("server.pem")
+        .secondary_key("previous.pem")
+        .session_timeout(60)
+        .session_interval(120)
+        .session_wait_interval(5)
+        .session_wait_timeout(30)
+        .session_token_ttl(900)
+        .allow_pattern(PATTERN_IK)
+        .allow(public_key.clone())
+        .cors_origin("https://example.com".parse()?)
+        .build()?;
+
+    assert_eq!(
+        Some(std::path::PathBuf::from("previous.pem")),
+        config.secondary_key
+    );
+    assert_eq!(60, config.session.timeout);
+    assert_eq!(120, config.session.interval);
+    assert_eq!(900, config.session.token_ttl);
+    assert_eq!(1, config.cors.origins.len());
+    assert!(config.is_allowed_access(&public_key));
+    assert!(!config.is_allowed_access([2u8; 32]));
+    assert_eq!(PATTERN, config.default_pattern());
+    assert!(config.is_allowed_pattern(PATTERN));
+    assert!(config.is_allowed_pattern(PATTERN_IK));
+    assert!(!config
+        .is_allowed_pattern("Noise_XX_25519_ChaChaPoly_BLAKE2s"));
+
+    let result = ServerConfig::builder()
+        .session_timeout(120)
+        .session_interval(60)
+        .build();
+    assert!(matches!(result, Err(Error::SessionTimeoutConfig)));
+
+    let result = ServerConfig::builder()
+        .session_wait_interval(30)
+        .session_wait_timeout(5)
+        .build();
+    assert!(matches!(result, Err(Error::SessionWaitConfig)));
+
+    let result =
+        ServerConfig::builder().allow_pattern("Noise_ZZ").build();
+    assert!(matches!(result, Err(Error::InvalidPattern(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn relay_server_builder() -> Result<()> {
+    use polysig_relay_server::{RelayServer, ServerConfig};
+
+    let config =
+        ServerConfig::builder().session_timeout(60).build()?;
+    RelayServer::builder()
+        .config(config)
+        .keypair(Keypair::generate()?)
+        .build()
+        .await?;
+    Ok(())
+}
+
+#[test]
+fn meeting_server_config_builder() -> Result<()> {
+    use polysig_meeting_server::{Error, ServerConfig};
+
+    let config = ServerConfig::builder()
+        .session_timeout(60)
+        .session_interval(120)
+        .build()?;
+    assert_eq!(60, config.session.timeout);
+    assert_eq!(120, config.session.interval);
+
+    let result = ServerConfig::builder()
+        .session_timeout(120)
+        .session_interval(60)
+        .build();
+    assert!(matches!(result, Err(Error::SessionTimeoutConfig)));
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::test_utils::server_public_key;
+use anyhow::{bail, Result};
+use axum_server::Handle;
+use futures::StreamExt;
+use polysig_client::{
+    Client, ClientOptions, EventStream, NetworkTransport,
+};
+use polysig_protocol::{Event, Keypair, PATTERN_IK};
+use polysig_relay_server::{RelayServer, ServerConfig};
+use std::net::SocketAddr;
+use tokio::fs;
+
+/// Rotates the server key whilst accepting handshakes
+/// against the previous key.
+#[tokio::test]
+async fn server_key_rotation() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (config, _) = ServerConfig::load("tests/config.toml").await?;
+    let contents = fs::read_to_string("tests/test.pem").await?;
+    let previous = Keypair::decode_pem(&contents)?;
+    let keypair = Keypair::generate()?;
+    let server = RelayServer::builder()
+        .config(config)
+        .keypair(keypair.clone())
+        .secondary_keypair(previous)
+        .build()
+        .await?;
+    let (relay, _embed) = server.embed().await?;
+
+    let handle = Handle::new();
+    let addr: SocketAddr = "127.0.0.1:0".parse()?;
+    let server_handle = handle.clone();
+    tokio::task::spawn(async move {
+        axum_server::bind(addr)
+            .handle(server_handle)
+            .serve(relay.into_make_service())
+            .await
+            .expect("failed to serve relay");
+    });
+    let Some(addr) = handle.listening().await else {
+        bail!("relay is not listening");
+    };
+    let server = format!("ws://{}", addr);
+
+    // Clients using the previous key are sent the new key
+    let (mut client, mut stream) =
+        connect(&server, server_public_key().await?).await?;
+    let Some(event) = stream.next().await else {
+        bail!("event stream closed");
+    };
+    assert!(matches!(event?, Event::ServerConnected { .. }));
+    let Some(event) = stream.next().await else {
+        bail!("event stream closed");
+    };
+    let Event::ServerKeyRotated { server_key } = event? else {
+        bail!("expected server key rotation event");
+    };
+    assert_eq!(keypair.public_key(), server_key.as_slice());
+    client.close().await?;
+
+    // Clients using the new key connect as usual
+    let (mut client, mut stream) =
+        connect(&server, keypair.public_key().to_vec()).await?;
+    let Some(event) = stream.next().await else {
+        bail!("event stream closed");
+    };
+    let Event::ServerConnected { server_key } = event? else {
+        bail!("expected server connected event");
+    };
+    assert_eq!(Some(keypair.public_key().to_vec()), server_key);
+    client.close().await?;
+
+    handle.shutdown();
+    Ok(())
+}
+
+async fn connect(
+    server: &str,
+    server_public_key: Vec<u8>,
+) -> Result<(Client, EventStream)> {
+    let options = ClientOptions {
+        keypair: Some(Keypair::generate()?),
+        server_public_key: Some(server_public_key),
+        pattern: Some(PATTERN_IK.to_owned()),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    client.connect().await?;
+    Ok((client, event_loop.run()))
+}
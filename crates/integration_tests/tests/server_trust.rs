@@ -0,0 +1,96 @@
+use crate::test_utils::{server_public_key, spawn_server};
+use anyhow::Result;
+use futures::StreamExt;
+use polysig_client::{
+    Client, ClientOptions, EventStream, NetworkTransport, ServerTrust,
+};
+use polysig_protocol::{Event, Keypair, PATTERN_IX};
+use std::sync::{Arc, Mutex};
+
+/// Connects without a server public key, pinning the key
+/// of the server on first use.
+#[tokio::test]
+async fn server_trust_on_first_use() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let pinned = Arc::new(Mutex::new(None));
+    let store = Arc::clone(&pinned);
+    let trust = ServerTrust::new().on_pin(move |key| {
+        *store.lock().unwrap() = Some(key.to_vec());
+        Ok(())
+    });
+
+    let server_key = connect(&server, trust.clone()).await?;
+    assert_eq!(Some(server_public_key.clone()), server_key);
+    assert_eq!(
+        Some(server_public_key.clone()),
+        pinned.lock().unwrap().clone()
+    );
+    assert_eq!(vec![server_public_key.clone()], trust.pinned_keys());
+
+    // Pinned key is accepted on the next connection
+    connect(&server, trust).await?;
+
+    // Any of the pre-pinned keys are accepted
+    let trust = ServerTrust::new()
+        .pin(vec![1u8; 32])
+        .pin(server_public_key.clone());
+    connect(&server, trust).await?;
+
+    // Keys that are not pinned are rejected
+    let trust = ServerTrust::new().pin(vec![1u8; 32]);
+    assert!(matches!(
+        connect(&server, trust).await,
+        Err(polysig_client::Error::ServerKeyNotPinned(_))
+    ));
+
+    // Pattern must transmit the server static key
+    let options = ClientOptions {
+        keypair: Some(Keypair::generate()?),
+        server_trust: Some(ServerTrust::new()),
+        ..Default::default()
+    };
+    let url = options.url(&server);
+    assert!(matches!(
+        Client::new(&url, options).await,
+        Err(polysig_client::Error::ServerKeyPattern(_))
+    ));
+
+    Ok(())
+}
+
+/// Connect to the server and return the server key.
+async fn connect(
+    server: &str,
+    trust: ServerTrust,
+) -> polysig_client::Result<Option<Vec<u8>>> {
+    let options = ClientOptions {
+        keypair: Some(Keypair::generate()?),
+        pattern: Some(PATTERN_IX.to_owned()),
+        server_trust: Some(trust),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    let (mut client, event_loop) = Client::new(&url, options).await?;
+    client.connect().await?;
+    let mut stream = event_loop.run();
+    let server_key = wait_for_connected(&mut stream).await?;
+    client.close().await?;
+    Ok(server_key)
+}
+
+async fn wait_for_connected(
+    stream: &mut EventStream,
+) -> polysig_client::Result<Option<Vec<u8>>> {
+    while let Some(event) = stream.next().await {
+        if let Event::ServerConnected { server_key } = event? {
+            return Ok(server_key);
+        }
+    }
+    Err(polysig_client::Error::ConnectionClosed)
+}
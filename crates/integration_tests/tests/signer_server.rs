@@ -0,0 +1,382 @@
+use anyhow::{bail, Result};
+use axum_server::Handle;
+use polysig_client::{
+    frost::ed25519::sign, Error as ClientError, ServerOptions,
+    SessionOptions,
+};
+use polysig_driver::{
+    ed25519_dalek::SigningKey,
+    frost::ed25519::{
+        solana::pubkey, Identifier, KeyShare, Participant,
+        PartyOptions,
+    },
+    frost_ed25519::{
+        keys::{generate_with_dealer, IdentifierList, KeyPackage},
+        Signature,
+    },
+    KeySharePolicy, PartyKeys,
+};
+use polysig_protocol::{KeyType, Keypair, Parameters};
+use polysig_signer_server::{
+    Error, KeyParty, KeyProtocol, KeyStore, Payload, Policy,
+    PolicyConfig, PolicyViolation, RelayConfig, ServerConfig,
+    SignRequest, Signer, SignerServer, StoredKey,
+};
+use rand::rngs::OsRng;
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use crate::test_utils::{
+    server_public_key, solana_transfer_message, spawn_server,
+};
+
+const RECIPIENT: [u8; 32] = [3u8; 32];
+const TOKEN: &str = "signer-secret";
+
+/// Co-signer participates in a FROST signing session (2-of-3).
+#[tokio::test]
+async fn signer_server_frost_ed25519_sign_2_3() -> Result<()> {
+    // crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let n = 3;
+    let t = 2;
+    let params = Parameters {
+        parties: n,
+        threshold: t,
+    };
+
+    let (shares, public_key_package) = generate_with_dealer(
+        n,
+        t,
+        IdentifierList::Default,
+        &mut OsRng,
+    )?;
+
+    let mut party_keys = Vec::new();
+    let mut parties = Vec::new();
+    for _ in 0..n {
+        let signer = SigningKey::generate(&mut OsRng);
+        let verifier = signer.verifying_key().to_bytes().to_vec();
+        let encrypt = Keypair::generate()?;
+        parties.push(KeyParty {
+            public_key: encrypt.public_key().to_vec(),
+            verifier: verifier.clone(),
+        });
+        party_keys.push(PartyKeys {
+            encrypt,
+            sign: Keypair::new(
+                signer.to_bytes().to_vec(),
+                verifier,
+                KeyType::Ed25519,
+            ),
+        });
+    }
+
+    let key_shares = (1..=n)
+        .map(|i| {
+            let id: Identifier = i.try_into()?;
+            let key_package = KeyPackage::try_from(
+                shares.get(&id).unwrap().clone(),
+            )?;
+            Ok((key_package, public_key_package.clone()))
+        })
+        .collect::<Result<Vec<KeyShare>>>()?;
+
+    // Keystore for the co-signer which is party 2
+    let dir = std::env::temp_dir().join(format!(
+        "polysig-signer-{}",
+        polysig_protocol::uuid::Uuid::new_v4()
+    ));
+    std::fs::create_dir_all(&dir)?;
+    let keystore = KeyStore::new(&dir);
+    keystore
+        .save(
+            "treasury",
+            &StoredKey {
+                protocol: KeyProtocol::FrostEd25519,
+                parameters: params,
+                parties: parties.clone(),
+                key_share: (&key_shares[1]).try_into()?,
+            },
+        )
+        .await?;
+    assert_eq!(vec!["treasury".to_string()], keystore.list().await?);
+
+    // Policy stored with the key share is enforced
+    let mut key_share: polysig_driver::KeyShare =
+        (&key_shares[1]).try_into()?;
+    key_share.policy = Some(KeySharePolicy {
+        expires_at: Some(1),
+        ..Default::default()
+    });
+    keystore
+        .save(
+            "expired",
+            &StoredKey {
+                protocol: KeyProtocol::FrostEd25519,
+                parameters: params,
+                parties: parties.clone(),
+                key_share,
+            },
+        )
+        .await?;
+
+    let relay = RelayConfig {
+        url: server.clone(),
+        public_key: server_public_key.clone(),
+        pattern: None,
+    };
+    let recipient = bs58::encode(RECIPIENT).into_string();
+    let policy = Policy::new(PolicyConfig {
+        require_transaction: true,
+        allowed_addresses: vec![recipient],
+        max_amount: Some(100),
+        rate_limit: None,
+    });
+    let mut party_keys = party_keys.into_iter();
+    let initiator_keys = party_keys.next().unwrap();
+    let signer = Signer::new(
+        party_keys.next().unwrap(),
+        relay,
+        keystore,
+        policy,
+    );
+
+    // Policy is checked against the decoded transaction
+    // message that is signed
+    let payer = pubkey(&public_key_package)?;
+    let message = solana_transfer_message(payer, RECIPIENT, 50);
+    let request = SignRequest {
+        key_id: "treasury".to_owned(),
+        signers: vec![1, 2],
+        payload: Payload::Solana {
+            message: message.clone(),
+        },
+        session_seed: None,
+    };
+
+    // Requests denied by the policy do not join a session
+    let mut denied = request.clone();
+    denied.payload = Payload::Solana {
+        message: solana_transfer_message(payer, RECIPIENT, 500),
+    };
+    let result = signer.sign(denied).await;
+    assert!(matches!(
+        result,
+        Err(Error::PolicyDenied(PolicyViolation::AmountLimit(
+            500, 100
+        )))
+    ));
+
+    let mut denied = request.clone();
+    denied.payload = Payload::Solana {
+        message: solana_transfer_message(payer, [4u8; 32], 50),
+    };
+    let result = signer.sign(denied).await;
+    assert!(matches!(
+        result,
+        Err(Error::PolicyDenied(PolicyViolation::AddressNotAllowed(
+            _
+        )))
+    ));
+
+    // Messages cannot be checked against the limits
+    let mut denied = request.clone();
+    denied.payload = Payload::Message {
+        message: message.clone(),
+    };
+    let result = signer.sign(denied).await;
+    assert!(matches!(
+        result,
+        Err(Error::PolicyDenied(PolicyViolation::MissingTransaction))
+    ));
+
+    let mut denied = request.clone();
+    denied.key_id = "expired".to_owned();
+    let result = signer.sign(denied).await;
+    assert!(matches!(
+        result,
+        Err(Error::Client(ClientError::PolicyRejected(_)))
+    ));
+
+    let mut denied = request.clone();
+    denied.payload = Payload::Ethereum {
+        transaction: message.clone(),
+    };
+    let result = signer.sign(denied).await;
+    assert!(matches!(
+        result,
+        Err(Error::PayloadProtocol(KeyProtocol::FrostEd25519))
+    ));
+
+    // Co-signer joins the session started by party 1
+    let cosigner =
+        tokio::task::spawn(async move { signer.sign(request).await });
+
+    let signer = SigningKey::from_bytes(
+        initiator_keys.sign.private_key().try_into()?,
+    );
+    let verifiers = parties[0..2]
+        .iter()
+        .map(|p| p.verifier.as_slice().try_into())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let party = PartyOptions::new(
+        parties[0].public_key.clone(),
+        parties[0..2].iter().map(|p| p.public_key.clone()).collect(),
+        true,
+        verifiers,
+    )?;
+    let verifier = signer.verifying_key();
+    let options = SessionOptions {
+        keypair: initiator_keys.encrypt,
+        server: ServerOptions {
+            server_url: server,
+            server_public_key,
+            pattern: None,
+        },
+        parameters: params,
+        policy: None,
+        progress: None,
+        cancel: None,
+        key_share_policy: None,
+        signing_context: None,
+        attestation: None,
+        round_timeout: None,
+        keepalive: None,
+    };
+    let signature = sign(
+        options,
+        Participant::new(signer, verifier, party)?,
+        vec![1u16.try_into()?, 2u16.try_into()?],
+        key_shares[0].clone(),
+        message.clone(),
+    )
+    .await?;
+
+    let response = cosigner.await??;
+    assert_eq!(KeyProtocol::FrostEd25519, response.protocol);
+    assert_eq!(signature.serialize()?, response.signature);
+
+    let signature = Signature::deserialize(&response.signature)?;
+    public_key_package
+        .verifying_key()
+        .verify(&message, &signature)?;
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+/// Requests to the co-signer must present the auth token.
+#[tokio::test]
+async fn signer_server_authorization() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!(
+        "polysig-signer-{}",
+        polysig_protocol::uuid::Uuid::new_v4()
+    ));
+    let keystore = dir.join("keystore");
+    std::fs::create_dir_all(&keystore)?;
+    let party_keys = || -> Result<PartyKeys> {
+        let signer = SigningKey::generate(&mut OsRng);
+        Ok(PartyKeys {
+            encrypt: Keypair::generate()?,
+            sign: Keypair::new(
+                signer.to_bytes().to_vec(),
+                signer.verifying_key().to_bytes().to_vec(),
+                KeyType::Ed25519,
+            ),
+        })
+    };
+
+    // Server does not start without a token
+    let config = ServerConfig {
+        keystore: keystore.clone(),
+        ..Default::default()
+    };
+    let server = SignerServer::new(config, party_keys()?);
+    let addr: SocketAddr = "127.0.0.1:0".parse()?;
+    let result = server.start(addr, Handle::new()).await;
+    assert!(matches!(result, Err(Error::AuthToken)));
+
+    let token_file = dir.join("signer.token");
+    std::fs::write(&token_file, format!("{}\n", TOKEN))?;
+    let config = ServerConfig {
+        keystore,
+        auth_token_file: token_file,
+        ..Default::default()
+    };
+    let server = SignerServer::new(config, party_keys()?);
+    let handle = Handle::new();
+    let server_handle = handle.clone();
+    tokio::task::spawn(async move {
+        server
+            .start(addr, server_handle)
+            .await
+            .expect("failed to serve signer");
+    });
+    let Some(addr) = handle.listening().await else {
+        bail!("signer is not listening");
+    };
+
+    let (status, _) = request(addr, "GET", "/keys", None, "").await?;
+    assert_eq!(401, status);
+    let (status, _) =
+        request(addr, "GET", "/keys", Some("wrong"), "").await?;
+    assert_eq!(401, status);
+    let (status, _) =
+        request(addr, "POST", "/sign", None, "{}").await?;
+    assert_eq!(401, status);
+
+    let (status, body) =
+        request(addr, "GET", "/keys", Some(TOKEN), "").await?;
+    assert_eq!(200, status);
+    assert_eq!("[]", body);
+
+    handle.shutdown();
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// Send an HTTP/1.1 request to the co-signer.
+async fn request(
+    addr: SocketAddr,
+    method: &str,
+    path: &str,
+    token: Option<&str>,
+    body: &str,
+) -> Result<(u16, String)> {
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        method, path, addr
+    );
+    if let Some(token) = token {
+        request.push_str(&format!(
+            "Authorization: Bearer {}\r\n",
+            token
+        ));
+    }
+    request.push_str("Content-Type: application/json\r\n");
+    request
+        .push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+    request.push_str(body);
+
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_all(request.as_bytes()).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+
+    let Some((head, body)) = response.split_once("\r\n\r\n") else {
+        bail!("invalid response");
+    };
+    let status =
+        head.split(' ').nth(1).unwrap_or_default().parse::<u16>()?;
+    Ok((status, body.to_owned()))
+}
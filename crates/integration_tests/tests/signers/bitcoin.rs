@@ -0,0 +1,108 @@
+use anyhow::Result;
+use bitcoin::{
+    absolute::LockTime,
+    hashes::Hash,
+    key::{Secp256k1, TapTweak, XOnlyPublicKey},
+    secp256k1::Message,
+    transaction::Version,
+    Amount, CompressedPublicKey, OutPoint, PublicKey, ScriptBuf,
+    Transaction, TxIn, TxOut, Txid,
+};
+use polysig_driver::{
+    bitcoin::{
+        ecdsa_sighashes, sign_psbt_ecdsa, sign_psbt_taproot,
+        taproot_sighashes, Psbt,
+    },
+    signers::{
+        ecdsa::EcdsaSigner,
+        schnorr::{AuxRand, SchnorrSigner},
+    },
+};
+use std::borrow::Cow;
+
+#[test]
+fn bitcoin_sign_psbt() -> Result<()> {
+    let secp = Secp256k1::verification_only();
+    let schnorr = SchnorrSigner::new(Cow::Owned(
+        SchnorrSigner::random().into(),
+    ));
+    let ecdsa =
+        EcdsaSigner::new(Cow::Owned(EcdsaSigner::random().into()));
+
+    let internal_key =
+        XOnlyPublicKey::from_slice(&schnorr.x_only_public_key())?;
+    let taproot_script =
+        ScriptBuf::new_p2tr(&secp, internal_key, None);
+    let public_key = CompressedPublicKey::from_slice(
+        &ecdsa.verifying_key().to_sec1_bytes(),
+    )?;
+    let wpkh_script =
+        ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash());
+
+    let transaction = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: (0..2)
+            .map(|vout| TxIn {
+                previous_output: OutPoint {
+                    txid: Txid::all_zeros(),
+                    vout,
+                },
+                ..Default::default()
+            })
+            .collect(),
+        output: vec![TxOut {
+            value: Amount::from_sat(90_000),
+            script_pubkey: wpkh_script.clone(),
+        }],
+    };
+    let mut psbt = Psbt::from_unsigned_tx(transaction)?;
+    psbt.inputs[0].witness_utxo = Some(TxOut {
+        value: Amount::from_sat(50_000),
+        script_pubkey: taproot_script,
+    });
+    psbt.inputs[0].tap_internal_key = Some(internal_key);
+    psbt.inputs[1].witness_utxo = Some(TxOut {
+        value: Amount::from_sat(50_000),
+        script_pubkey: wpkh_script,
+    });
+
+    // Key path spend signs with the BIP-86 tweaked key
+    let (output_key, _) = internal_key.tap_tweak(&secp, None);
+    assert_eq!(
+        output_key.serialize(),
+        schnorr.taproot_tweak(None)?.verifying_key().to_bytes()[..]
+    );
+
+    let sighashes =
+        taproot_sighashes(&psbt, &schnorr.x_only_public_key())?;
+    assert_eq!(1, sighashes.len());
+    let signed =
+        sign_psbt_taproot(&mut psbt, &schnorr, AuxRand::Random)?;
+    assert_eq!(vec![0], signed);
+    let signature = psbt.inputs[0].tap_key_sig.as_ref().unwrap();
+    secp.verify_schnorr(
+        &signature.signature,
+        &Message::from_digest(sighashes[0].sighash),
+        &output_key.to_inner(),
+    )?;
+
+    let sighashes = ecdsa_sighashes(
+        &psbt,
+        &ecdsa.verifying_key().to_sec1_bytes(),
+    )?;
+    assert_eq!(1, sighashes.len());
+    let signed = sign_psbt_ecdsa(&mut psbt, &ecdsa)?;
+    assert_eq!(vec![1], signed);
+    let signature = psbt.inputs[1]
+        .partial_sigs
+        .get(&PublicKey::from(public_key))
+        .unwrap();
+    secp.verify_ecdsa(
+        &Message::from_digest(sighashes[0].sighash),
+        &signature.signature,
+        &public_key.0,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use polysig_driver::signers::{
+    ecdsa::EcdsaSigner, Algorithm, DynSigner, SecretSigningKey,
+    Signer,
+};
+use std::borrow::Cow;
+
+#[test]
+fn dyn_signer_sign_verify() -> Result<()> {
+    let message = b"example message";
+    let signers = vec![
+        DynSigner::random(Algorithm::Ecdsa),
+        DynSigner::random(Algorithm::Eddsa),
+        DynSigner::random(Algorithm::Schnorr),
+    ];
+
+    for (signer, (algorithm, key_len)) in signers.iter().zip([
+        (Algorithm::Ecdsa, 33),
+        (Algorithm::Eddsa, 32),
+        (Algorithm::Schnorr, 32),
+    ]) {
+        assert_eq!(algorithm, signer.algorithm());
+        assert_eq!(key_len, signer.public_key().len());
+
+        let signature = signer.sign(message)?;
+        assert_eq!(64, signature.len());
+        signer.verify(message, &signature)?;
+        assert!(signer.verify(b"other message", &signature).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn dyn_signer_from_slice() -> Result<()> {
+    let signing_key = SecretSigningKey::from(EcdsaSigner::random());
+    let signer: DynSigner<'_> =
+        EcdsaSigner::new(Cow::Borrowed(&signing_key)).into();
+    let imported = DynSigner::from_slice(
+        Algorithm::Ecdsa,
+        &signing_key.to_bytes(),
+    )?;
+    assert_eq!(signer.public_key(), imported.public_key());
+
+    let signature = imported.sign(b"example message")?;
+    signer.verify(b"example message", &signature)?;
+
+    assert!(
+        DynSigner::from_slice(Algorithm::Eddsa, &[0u8; 16]).is_err()
+    );
+
+    Ok(())
+}
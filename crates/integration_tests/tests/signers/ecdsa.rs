@@ -1,7 +1,13 @@
 use anyhow::Result;
 use polysig_driver::{
     recoverable_signature::RecoverableSignature,
-    signers::ecdsa::EcdsaSigner,
+    signers::{
+        ecdsa::{
+            eip712::TypedData, DerivationPath, EcdsaSigner,
+            ETHEREUM_COIN_TYPE,
+        },
+        SecretSigningKey,
+    },
 };
 use serde::Deserialize;
 use std::borrow::Cow;
@@ -53,7 +59,7 @@ fn ecdsa_sign_message() -> Result<()> {
 
     let signing_key =
         EcdsaSigner::from_slice(spec.private_key.as_slice())?;
-    let signer = EcdsaSigner::new(Cow::Owned(signing_key));
+    let signer = EcdsaSigner::new(Cow::Owned(signing_key.into()));
 
     let (signature, recid) = signer.sign_eth_message(spec.message)?;
     let (r, s) = signature.split_bytes();
@@ -76,7 +82,7 @@ fn ecdsa_sign_verify_recover() -> Result<()> {
     use sha3::{Digest, Keccak256};
 
     let signing_key = EcdsaSigner::random();
-    let signer = EcdsaSigner::new(Cow::Owned(signing_key));
+    let signer = EcdsaSigner::new(Cow::Owned(signing_key.into()));
     let verifying_key = signer.verifying_key();
     let message = "example message";
     let (signature, recovery_id) =
@@ -98,3 +104,332 @@ fn ecdsa_sign_verify_recover() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn ecdsa_bip39_seed() -> Result<()> {
+    // BIP-39 test vector using the "TREZOR" passphrase.
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mnemonic = EcdsaSigner::parse_mnemonic(phrase)?;
+    let seed = mnemonic.to_seed("TREZOR");
+    assert_eq!(
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        hex::encode(seed),
+    );
+
+    // Invalid checksum
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+    assert!(EcdsaSigner::parse_mnemonic(phrase).is_err());
+
+    let mnemonic = EcdsaSigner::generate_mnemonic(24)?;
+    assert_eq!(24, mnemonic.word_count());
+    let imported =
+        EcdsaSigner::parse_mnemonic(&mnemonic.to_string())?;
+    assert_eq!(mnemonic, imported);
+
+    Ok(())
+}
+
+#[test]
+fn ecdsa_bip44_derive_accounts() -> Result<()> {
+    // Well-known development mnemonic, the expected keys
+    // are the first accounts for Ethereum.
+    let phrase =
+        "test test test test test test test test test test test junk";
+    let mnemonic = EcdsaSigner::parse_mnemonic(phrase)?;
+
+    let path = EcdsaSigner::bip44_path(ETHEREUM_COIN_TYPE, 0, 0, 0)?;
+    assert_eq!("m/44'/60'/0'/0/0", path.to_string());
+    let signing_key =
+        EcdsaSigner::derive_from_mnemonic(&mnemonic, "", &path)?;
+    assert_eq!(
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        hex::encode(signing_key.to_bytes()),
+    );
+
+    let path: DerivationPath = "m/44'/60'/0'/0/1".parse()?;
+    let signing_key =
+        EcdsaSigner::derive_from_seed(&mnemonic.to_seed(""), &path)?;
+    assert_eq!(
+        "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+        hex::encode(signing_key.to_bytes()),
+    );
+
+    let signer = EcdsaSigner::new(Cow::Owned(signing_key.into()));
+    let message = b"example message";
+    let signature = signer.sign(message);
+    signer.verify(message, &signature)?;
+
+    Ok(())
+}
+
+/// Example from the EIP-712 specification.
+const TYPED_DATA: &str = r#"
+{
+  "types": {
+    "EIP712Domain": [
+      { "name": "name", "type": "string" },
+      { "name": "version", "type": "string" },
+      { "name": "chainId", "type": "uint256" },
+      { "name": "verifyingContract", "type": "address" }
+    ],
+    "Person": [
+      { "name": "name", "type": "string" },
+      { "name": "wallet", "type": "address" }
+    ],
+    "Mail": [
+      { "name": "from", "type": "Person" },
+      { "name": "to", "type": "Person" },
+      { "name": "contents", "type": "string" }
+    ]
+  },
+  "primaryType": "Mail",
+  "domain": {
+    "name": "Ether Mail",
+    "version": "1",
+    "chainId": 1,
+    "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+  },
+  "message": {
+    "from": {
+      "name": "Cow",
+      "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+    },
+    "to": {
+      "name": "Bob",
+      "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+    },
+    "contents": "Hello, Bob!"
+  }
+}
+"#;
+
+#[test]
+fn ecdsa_sign_typed_data() -> Result<()> {
+    use sha3::{Digest, Keccak256};
+
+    let data: TypedData = serde_json::from_str(TYPED_DATA)?;
+    assert_eq!(
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+        data.encode_type("Mail")?,
+    );
+    assert_eq!(
+        "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2",
+        hex::encode(data.type_hash("Mail")?),
+    );
+    assert_eq!(
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+        hex::encode(data.domain_separator()?),
+    );
+    assert_eq!(
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e",
+        hex::encode(data.message_hash()?),
+    );
+    assert_eq!(
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+        hex::encode(data.signing_hash()?),
+    );
+
+    // Domain type is inferred when not declared
+    let mut inferred = data.clone();
+    inferred.types.remove("EIP712Domain");
+    assert_eq!(
+        data.domain_separator()?,
+        inferred.domain_separator()?
+    );
+
+    let private_key = Keccak256::digest("cow");
+    let signing_key = EcdsaSigner::from_slice(&private_key)?;
+    let signer = EcdsaSigner::new(Cow::Owned(signing_key.into()));
+    let (signature, recid) = signer.sign_typed_data(&data)?;
+    let (r, s) = signature.split_bytes();
+    assert_eq!(
+        "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+        hex::encode(r),
+    );
+    assert_eq!(
+        "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+        hex::encode(s),
+    );
+    assert_eq!(28 - 27, u8::from(recid));
+
+    Ok(())
+}
+
+#[test]
+fn ecdsa_signature_encodings() -> Result<()> {
+    use polysig_driver::k256::ecdsa::{RecoveryId, Signature};
+
+    let signer =
+        EcdsaSigner::new(Cow::Owned(EcdsaSigner::random().into()));
+    let message = b"example message";
+    let (signature, recovery_id) = signer.sign_eth(message)?;
+    let recoverable: RecoverableSignature =
+        (signature, recovery_id).into();
+    assert!(recoverable.is_low_s()?);
+
+    let der = recoverable.to_der()?;
+    assert_eq!(
+        recoverable,
+        RecoverableSignature::from_der(
+            &der,
+            recoverable.recovery_id
+        )?
+    );
+
+    let compact = recoverable.to_compact()?;
+    assert_eq!(
+        recoverable,
+        RecoverableSignature::from_compact(
+            &compact,
+            recoverable.recovery_id
+        )?
+    );
+    assert!(RecoverableSignature::from_compact(&der, 0).is_err());
+
+    let eth = recoverable.to_eth_bytes()?;
+    assert_eq!(27 + recoverable.recovery_id, eth[64]);
+    assert_eq!(
+        recoverable,
+        RecoverableSignature::from_eth_bytes(&eth)?
+    );
+
+    // EIP-155 recovery values
+    let v = recoverable.to_eth_v(1);
+    assert_eq!(37 + recoverable.recovery_id as u64, v);
+    assert_eq!(
+        (recoverable.recovery_id, Some(1)),
+        RecoverableSignature::recovery_id_from_eth_v(v)?
+    );
+    assert_eq!(
+        (1, Some(137)),
+        RecoverableSignature::recovery_id_from_eth_v(310)?
+    );
+    let invalid = RecoverableSignature::recovery_id_from_eth_v(29);
+    assert!(invalid.is_err());
+
+    // High s values are normalized and the recovery
+    // identifier is updated so the key can be recovered
+    let high_s = Signature::from_scalars(
+        signature.r().to_bytes(),
+        (-*signature.s()).to_bytes(),
+    )?;
+    let high_s: RecoverableSignature = (
+        high_s,
+        RecoveryId::new(
+            !recovery_id.is_y_odd(),
+            recovery_id.is_x_reduced(),
+        ),
+    )
+        .into();
+    assert!(!high_s.is_low_s()?);
+    let normalized = high_s.normalize_s()?;
+    assert_eq!(recoverable, normalized);
+    let public_key = EcdsaSigner::recover(message, normalized)?;
+    assert_eq!(signer.verifying_key(), &public_key);
+
+    Ok(())
+}
+
+#[test]
+fn ecdsa_secret_signing_key() -> Result<()> {
+    use sha3::{Digest, Keccak256};
+
+    let private_key = Keccak256::digest("cow");
+    let signing_key = SecretSigningKey::from(
+        EcdsaSigner::from_slice(&private_key)?,
+    );
+
+    // Key material is never written to debug output
+    let debug = format!("{:?}", signing_key);
+    assert!(debug.contains("[REDACTED]"));
+    assert!(!debug.contains(&hex::encode(private_key)));
+
+    let copy = signing_key.clone();
+    assert_eq!(
+        signing_key.expose_secret().to_bytes(),
+        copy.expose_secret().to_bytes()
+    );
+
+    let signer = EcdsaSigner::new(Cow::Borrowed(&copy));
+    let message = b"example message";
+    let signature = signer.sign(message);
+    EcdsaSigner::new(Cow::Owned(signing_key))
+        .verify(message, &signature)?;
+
+    Ok(())
+}
+
+#[test]
+fn ecdsa_sign_transaction() -> Result<()> {
+    use polysig_driver::signers::ecdsa::eth_tx::{
+        AccessListItem, Eip1559Transaction, LegacyTransaction,
+        TransactionType, UnsignedTransaction,
+    };
+
+    // Example from EIP-155
+    let signing_key = EcdsaSigner::from_slice(&[0x46; 32])?;
+    let signer = EcdsaSigner::new(Cow::Owned(signing_key.into()));
+    let transaction: UnsignedTransaction = LegacyTransaction {
+        chain_id: Some(1),
+        nonce: 9,
+        gas_price: 20_000_000_000,
+        gas_limit: 21_000,
+        to: Some([0x35; 20]),
+        value: 1_000_000_000_000_000_000,
+        data: vec![],
+    }
+    .into();
+    assert_eq!(
+        hex::decode(
+            "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+        )?,
+        transaction.encode()
+    );
+    assert_eq!(
+        hex::decode(
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        )?,
+        transaction.signing_hash()
+    );
+    let expected = hex::decode(
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+    )?;
+    assert_eq!(expected, signer.sign_transaction(&transaction)?);
+
+    // Raw unsigned payloads decode to the same transaction
+    let decoded = UnsignedTransaction::decode(&transaction.encode())?;
+    assert_eq!(transaction, decoded);
+    assert_eq!(Some([0x35; 20]), decoded.to());
+    assert_eq!(1_000_000_000_000_000_000, decoded.value());
+    assert_eq!(expected, signer.sign_transaction(&decoded)?);
+
+    let transaction: UnsignedTransaction = Eip1559Transaction {
+        chain_id: 137,
+        nonce: 1,
+        max_priority_fee_per_gas: 30_000_000_000,
+        max_fee_per_gas: 100_000_000_000,
+        gas_limit: 50_000,
+        to: Some([0x35; 20]),
+        value: 0,
+        data: vec![0xa9, 0x05, 0x9c, 0xbb],
+        access_list: vec![AccessListItem {
+            address: [0x35; 20],
+            storage_keys: vec![[0u8; 32]],
+        }],
+    }
+    .into();
+    let payload = transaction.encode();
+    assert_eq!(0x02, payload[0]);
+    let decoded = UnsignedTransaction::decode(&payload)?;
+    assert_eq!(TransactionType::Eip1559, decoded.transaction_type());
+    assert_eq!(Some(137), decoded.chain_id());
+    assert_eq!(Some([0x35; 20]), decoded.to());
+    assert_eq!(0, decoded.value());
+
+    let signed = signer.sign_transaction(&decoded)?;
+    assert_eq!(0x02, signed[0]);
+    assert!(UnsignedTransaction::decode(&signed).is_err());
+    assert!(UnsignedTransaction::decode(&[0x05, 0xc0]).is_err());
+
+    Ok(())
+}
@@ -32,7 +32,7 @@ fn eddsa_sign() -> Result<()> {
         let private_key: [u8; 32] =
             test.secret_key.as_slice().try_into()?;
         let signing_key = EddsaSigner::from_bytes(&private_key);
-        let signer = EddsaSigner::new(Cow::Owned(signing_key));
+        let signer = EddsaSigner::new(Cow::Owned(signing_key.into()));
         assert_eq!(
             test.public_key,
             signer.verifying_key().to_bytes()
@@ -45,3 +45,101 @@ fn eddsa_sign() -> Result<()> {
 
     Ok(())
 }
+
+/// Ed25519ph test vector from RFC 8032 section 7.3.
+#[test]
+fn eddsa_sign_prehashed() -> Result<()> {
+    let secret_key: [u8; 32] = hex::decode(
+        "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+    )?
+    .as_slice()
+    .try_into()?;
+    let signer = EddsaSigner::new(Cow::Owned(
+        EddsaSigner::from_bytes(&secret_key).into(),
+    ));
+    assert_eq!(
+        "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+        hex::encode(signer.verifying_key().to_bytes()),
+    );
+
+    let message = b"abc";
+    let signature = signer.sign_prehashed(message, None)?;
+    assert_eq!(
+        "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+        hex::encode(signature.to_bytes()),
+    );
+    signer.verify_prehashed(message, None, &signature)?;
+    assert!(signer.verify(message, &signature).is_err());
+
+    let context = b"context";
+    let signature = signer.sign_prehashed(message, Some(context))?;
+    signer.verify_prehashed(message, Some(context), &signature)?;
+    assert!(signer
+        .verify_prehashed(message, None, &signature)
+        .is_err());
+
+    Ok(())
+}
+
+/// Ed25519ctx test vector from RFC 8032 section 7.2.
+#[test]
+fn eddsa_sign_with_context() -> Result<()> {
+    let secret_key: [u8; 32] = hex::decode(
+        "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+    )?
+    .as_slice()
+    .try_into()?;
+    let signer = EddsaSigner::new(Cow::Owned(
+        EddsaSigner::from_bytes(&secret_key).into(),
+    ));
+    assert_eq!(
+        "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+        hex::encode(signer.verifying_key().to_bytes()),
+    );
+
+    let message = hex::decode("f726936d19c800494e3fdaff20b276a8")?;
+    let signature = signer.sign_with_context(&message, b"foo")?;
+    assert_eq!(
+        "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7f85b0",
+        hex::encode(signature.to_bytes()),
+    );
+    signer.verify_with_context(&message, b"foo", &signature)?;
+    assert!(signer
+        .verify_with_context(&message, b"bar", &signature)
+        .is_err());
+    assert!(signer.verify(&message, &signature).is_err());
+    assert!(signer.sign_with_context(&message, b"").is_err());
+
+    Ok(())
+}
+
+/// SLIP-0010 test vector 1 for ed25519.
+#[test]
+fn eddsa_derive_slip10() -> Result<()> {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f")?;
+
+    let signing_key = EddsaSigner::derive_from_seed(&seed, "m")?;
+    assert_eq!(
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+        hex::encode(signing_key.to_bytes()),
+    );
+
+    let signing_key = EddsaSigner::derive_from_seed(&seed, "m/0'")?;
+    assert_eq!(
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        hex::encode(signing_key.to_bytes()),
+    );
+
+    let signing_key =
+        EddsaSigner::derive_from_seed(&seed, "m/0'/1h")?;
+    assert_eq!(
+        "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        hex::encode(signing_key.to_bytes()),
+    );
+
+    // Only hardened derivation is supported
+    assert!(EddsaSigner::derive_from_seed(&seed, "m/0'/1").is_err());
+    assert!(EddsaSigner::derive_from_seed(&seed, "0'").is_err());
+
+    Ok(())
+}
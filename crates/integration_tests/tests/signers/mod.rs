@@ -6,3 +6,13 @@ mod eddsa;
 
 #[cfg(feature = "schnorr")]
 mod schnorr;
+
+#[cfg(all(
+    feature = "ecdsa",
+    feature = "eddsa",
+    feature = "schnorr"
+))]
+mod dynamic;
+
+#[cfg(feature = "bitcoin")]
+mod bitcoin;
@@ -1,5 +1,12 @@
 use anyhow::Result;
-use polysig_driver::signers::schnorr::{SchnorrSigner, VerifyingKey};
+use polysig_driver::signers::{
+    mnemonic,
+    schnorr::{
+        AuxRand, SchnorrSigner, Signature, VerifyingKey,
+        BITCOIN_COIN_TYPE,
+    },
+    SecretSigningKey,
+};
 use serde::Deserialize;
 use std::borrow::Cow;
 
@@ -42,8 +49,8 @@ fn schnorr_sign() -> Result<()> {
 
         // Sign and verify
         if !test.secret_key.is_empty() {
-            let signing_key =
-                SchnorrSigner::from_slice(&test.secret_key)?;
+            let signing_key: SecretSigningKey<_> =
+                SchnorrSigner::from_slice(&test.secret_key)?.into();
             let signer =
                 SchnorrSigner::new(Cow::Borrowed(&signing_key));
 
@@ -82,3 +89,114 @@ fn schnorr_sign() -> Result<()> {
     assert_eq!(all_tests, completed);
     Ok(())
 }
+
+#[test]
+fn schnorr_sign_aux_rand() -> Result<()> {
+    use sha2::{Digest, Sha256};
+
+    let signing_key = SecretSigningKey::from(SchnorrSigner::random());
+    let signer = SchnorrSigner::new(Cow::Borrowed(&signing_key));
+    let message = b"example message";
+
+    let first =
+        signer.sign_with_aux_rand(message, AuxRand::Deterministic)?;
+    let second =
+        signer.sign_with_aux_rand(message, AuxRand::Deterministic)?;
+    assert_eq!(first, second);
+    signer.verify(message, &first)?;
+
+    let aux_rand = [7u8; 32];
+    let signature = signer
+        .sign_with_aux_rand(message, AuxRand::Provided(aux_rand))?;
+    let expected =
+        signer.sign_raw(&Sha256::digest(message), &aux_rand)?;
+    assert_eq!(expected, signature);
+    assert_ne!(first, signature);
+
+    let signature =
+        signer.sign_with_aux_rand(message, AuxRand::Random)?;
+    signer.verify(message, &signature)?;
+
+    let x_only = signer.x_only_public_key();
+    assert_eq!(
+        signer.verifying_key(),
+        &VerifyingKey::from_bytes(&x_only)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn schnorr_verify_batch() -> Result<()> {
+    let mut rdr = csv::Reader::from_reader(TEST_VECTORS);
+    let mut valid = Vec::new();
+    for result in rdr.deserialize() {
+        let test: SchnorrTestVector = result?;
+        if test.should_verify() {
+            let signature: Signature =
+                test.signature.as_slice().try_into()?;
+            let verifying_key =
+                VerifyingKey::from_bytes(&test.public_key)?;
+            valid.push((verifying_key, test.message, signature));
+        }
+    }
+    assert!(!valid.is_empty());
+
+    let items = valid
+        .iter()
+        .map(|(key, message, signature)| {
+            (key, message.as_slice(), signature)
+        })
+        .collect::<Vec<_>>();
+    SchnorrSigner::verify_batch_raw(&items)?;
+
+    let signers = (0..4)
+        .map(|_| SecretSigningKey::from(SchnorrSigner::random()))
+        .collect::<Vec<_>>();
+    let messages = (0..4u8).map(|i| vec![i; 32]).collect::<Vec<_>>();
+    let signatures = signers
+        .iter()
+        .zip(messages.iter())
+        .map(|(key, message)| {
+            SchnorrSigner::new(Cow::Borrowed(key)).sign(message)
+        })
+        .collect::<Vec<_>>();
+
+    let mut items = signers
+        .iter()
+        .zip(messages.iter())
+        .zip(signatures.iter())
+        .map(|((key, message), signature)| {
+            (key.verifying_key(), message.as_slice(), signature)
+        })
+        .collect::<Vec<_>>();
+    SchnorrSigner::verify_batch(&items)?;
+
+    // Swapping a message invalidates the batch
+    items[1].1 = messages[2].as_slice();
+    assert!(SchnorrSigner::verify_batch(&items).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn schnorr_bip86_derive() -> Result<()> {
+    // BIP-86 test vector for the first receiving address.
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let seed = mnemonic::to_seed(phrase, "")?;
+
+    let path = SchnorrSigner::bip86_path(BITCOIN_COIN_TYPE, 0, 0, 0)?;
+    assert_eq!("m/86'/0'/0'/0/0", path.to_string());
+    let signing_key = SchnorrSigner::derive_from_seed(&seed, &path)?;
+    assert_eq!(
+        "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+        hex::encode(signing_key.verifying_key().to_bytes()),
+    );
+
+    let signer = SchnorrSigner::new(Cow::Owned(signing_key.into()));
+    let message = b"example message";
+    let signature = signer.sign(message);
+    signer.verify(message, &signature)?;
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::test_utils::spawn_server;
+use anyhow::Result;
+use polysig_protocol::{Keypair, SUBPROTOCOL};
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Upgrades are negotiated using the websocket subprotocol
+/// for the wire encoding version.
+#[tokio::test]
+async fn integration_subprotocol() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+
+    // Server selects the subprotocol offered by the client
+    let response = upgrade(addr, Some(SUBPROTOCOL)).await?;
+    assert!(response.starts_with("http/1.1 101"));
+    assert!(response.contains(&format!(
+        "sec-websocket-protocol: {}",
+        SUBPROTOCOL
+    )));
+
+    // Clients that predate negotiation are still accepted
+    let response = upgrade(addr, None).await?;
+    assert!(response.starts_with("http/1.1 101"));
+    assert!(!response.contains("sec-websocket-protocol"));
+
+    // Incompatible versions are rejected before the handshake
+    let response = upgrade(addr, Some("polysig.v2")).await?;
+    assert!(response.starts_with("http/1.1 426"));
+
+    Ok(())
+}
+
+/// Send a websocket upgrade request and read the
+/// response head.
+async fn upgrade(
+    addr: SocketAddr,
+    protocol: Option<&str>,
+) -> Result<String> {
+    let keypair = Keypair::generate()?;
+    let mut request = format!(
+        "GET /?public_key={} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Connection: Upgrade\r\n\
+         Upgrade: websocket\r\n\
+         Sec-WebSocket-Version: 13\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n",
+        hex::encode(keypair.public_key()),
+        addr,
+    );
+    if let Some(protocol) = protocol {
+        request.push_str(&format!(
+            "Sec-WebSocket-Protocol: {}\r\n",
+            protocol
+        ));
+    }
+    request.push_str("\r\n");
+
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !response.ends_with(b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..read]);
+    }
+    Ok(String::from_utf8(response)?.to_lowercase())
+}
@@ -0,0 +1,233 @@
+use crate::test_utils::{
+    new_client, new_client_with_keypair, server_public_key,
+    spawn_server, wait_for_event,
+};
+use anyhow::{bail, Result};
+use futures::StreamExt;
+use polysig_client::{
+    Client, ClientOptions, Error, ErrorKind, EventLoop,
+    NetworkTransport,
+};
+use polysig_protocol::{
+    http::StatusCode, Event, Keypair, PATTERN_IK,
+};
+use std::time::Duration;
+
+/// Time to wait for an event.
+const WAIT: Duration = Duration::from_secs(5);
+
+/// Time the server waits for a pong when a client reconnects
+/// without proving it holds the key, with some margin.
+const PROBE: Duration = Duration::from_secs(3);
+
+/// New connection for a public key supersedes the previous
+/// connection and receives the messages for the key when the
+/// handshake proves the client holds the key.
+#[tokio::test]
+async fn integration_superseded() -> Result<()> {
+    //crate::test_utils::init_tracing();
+    superseded(Some(PATTERN_IK)).await
+}
+
+/// New connection for a public key supersedes the previous
+/// connection when the handshake does not prove the client
+/// holds the key and the previous connection does not answer
+/// pings.
+///
+/// The event loop for the previous connection is not polled
+/// whilst the client reconnects so pings are not answered.
+#[tokio::test]
+async fn integration_superseded_reconnect() -> Result<()> {
+    //crate::test_utils::init_tracing();
+    superseded(None).await
+}
+
+/// New connection for a public key is rejected when the
+/// handshake does not prove the client holds the key and the
+/// previous connection answers pings; the previous connection
+/// keeps the messages for the key.
+#[tokio::test]
+async fn integration_superseded_unverified() -> Result<()> {
+    //crate::test_utils::init_tracing();
+
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let (mut client, event_loop, keypair) =
+        new_client::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+        )
+        .await?;
+    client.connect().await?;
+    let mut client_stream = event_loop.run();
+    wait_for_event(&mut client_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Drive the event loop so the connection answers pings
+    let client_task = tokio::task::spawn(async move {
+        while let Some(event) = client_stream.next().await {
+            event?;
+        }
+        Ok::<_, Error>(())
+    });
+
+    // Connect with the same public key without proving
+    // the key is held
+    let (mut duplicate, event_loop) =
+        new_client_with_keypair::<anyhow::Error>(
+            &server,
+            server_public_key.clone(),
+            keypair,
+        )
+        .await?;
+    duplicate.connect().await?;
+    let mut duplicate_stream = event_loop.run();
+    let error = loop {
+        match tokio::time::timeout(WAIT, duplicate_stream.next())
+            .await?
+        {
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => break e,
+            None => bail!("expected a server error"),
+        }
+    };
+    assert!(matches!(
+        error,
+        Error::ServerError(StatusCode::CONFLICT, _)
+    ));
+    drop(duplicate_stream);
+
+    // Peers still reach the previous connection
+    let (mut peer, event_loop, _) =
+        new_client::<anyhow::Error>(&server, server_public_key)
+            .await?;
+    peer.connect().await?;
+    let mut peer_stream = event_loop.run();
+    wait_for_event(&mut peer_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    peer.connect_peer(client.public_key()).await?;
+    wait_for_event(&mut peer_stream, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    client_task.abort();
+    Ok(())
+}
+
+/// Reconnect whilst the previous connection is open and
+/// check the new connection supersedes it.
+async fn superseded(pattern: Option<&str>) -> Result<()> {
+    let (rx, _handle) = spawn_server()?;
+    let addr = rx.await?;
+    let server = format!("ws://{}", addr);
+    let server_public_key = server_public_key().await?;
+
+    let keypair = Keypair::generate()?;
+    let (mut previous, event_loop) = new_client_with_pattern(
+        &server,
+        server_public_key.clone(),
+        keypair.clone(),
+        pattern,
+    )
+    .await?;
+    previous.connect().await?;
+    let mut previous_stream = event_loop.run();
+    wait_for_event(&mut previous_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Reconnect with the same key whilst the previous
+    // connection is still open
+    let (mut client, event_loop) = new_client_with_pattern(
+        &server,
+        server_public_key.clone(),
+        keypair,
+        pattern,
+    )
+    .await?;
+    client.connect().await?;
+    let mut client_stream = event_loop.run();
+    wait_for_event(&mut client_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Without proof of the key the server waits for the
+    // previous connection to miss a pong before it is
+    // superseded so do not answer pings until then
+    if pattern.is_none() {
+        tokio::time::sleep(PROBE).await;
+    }
+
+    let error = loop {
+        match tokio::time::timeout(WAIT, previous_stream.next())
+            .await?
+        {
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => break e,
+            None => bail!("expected a superseded error"),
+        }
+    };
+    assert!(matches!(error, Error::Superseded));
+    assert_eq!("SUPERSEDED", error.code());
+    assert_eq!(ErrorKind::Transport, error.kind());
+    assert!(!error.is_retryable());
+    drop(previous_stream);
+
+    // Peers reach the new connection once the previous
+    // connection has gone
+    let (mut peer, event_loop, _) =
+        new_client::<anyhow::Error>(&server, server_public_key)
+            .await?;
+    peer.connect().await?;
+    let mut peer_stream = event_loop.run();
+    wait_for_event(&mut peer_stream, |event| {
+        matches!(event, Event::ServerConnected { .. })
+    })
+    .await?;
+
+    // Drive the event loop for the new connection so it
+    // responds to the peer handshake
+    let client_task = tokio::task::spawn(async move {
+        while let Some(event) = client_stream.next().await {
+            event?;
+        }
+        Ok::<_, Error>(())
+    });
+
+    peer.connect_peer(client.public_key()).await?;
+    wait_for_event(&mut peer_stream, |event| {
+        matches!(event, Event::PeerConnected { .. })
+    })
+    .await?;
+
+    client_task.abort();
+    Ok(())
+}
+
+/// Create a client with a noise pattern.
+async fn new_client_with_pattern(
+    server: &str,
+    server_public_key: Vec<u8>,
+    keypair: Keypair,
+    pattern: Option<&str>,
+) -> Result<(Client, EventLoop)> {
+    let options = ClientOptions {
+        keypair: Some(keypair),
+        server_public_key: Some(server_public_key),
+        pattern: pattern.map(str::to_owned),
+        ..Default::default()
+    };
+    let url = options.url(server);
+    Ok(Client::new(&url, options).await?)
+}
@@ -1,6 +1,11 @@
 pub(crate) mod meeting_point;
 pub(crate) mod peer_channel;
-#[cfg(any(feature = "cggmp", feature = "frost-ed25519"))]
+#[cfg(any(
+    feature = "bls",
+    feature = "cggmp",
+    feature = "frost",
+    feature = "musig2"
+))]
 pub(crate) mod session_handshake;
 pub(crate) mod session_timeout;
 pub(crate) mod socket_close;
@@ -11,8 +16,13 @@ pub use meeting_server::spawn_meeting_server;
 pub(crate) mod relay_server;
 pub use relay_server::{server_public_key, spawn_server};
 
-use polysig_client::{Client, ClientOptions, EventLoop};
-use polysig_protocol::Keypair;
+use futures::StreamExt;
+use polysig_client::{Client, ClientOptions, EventLoop, EventStream};
+use polysig_protocol::{Event, Keypair};
+use std::time::Duration;
+
+/// Time to wait for each event when waiting for an event.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[allow(dead_code)]
 pub fn init_tracing() {
@@ -54,9 +64,69 @@ pub async fn new_client_with_keypair<
     let options = ClientOptions {
         keypair: Some(keypair),
         server_public_key: Some(server_public_key),
+        server_trust: None,
         pattern: None,
+        session_token: None,
+        progress: None,
+        cancel: None,
+        attestation: None,
+        round_timeout: None,
+        keepalive: None,
+        direct: None,
+        recorder: None,
+        journal: None,
+        faults: None,
+        http2: None,
     };
     let url = options.url(server);
     let (client, event_loop) = Client::new(&url, options).await?;
     Ok((client, event_loop))
 }
+
+/// Wait for an event that matches a predicate.
+///
+/// Fails when the stream closes or yields an error or when
+/// no event is received in time.
+pub async fn wait_for_event(
+    stream: &mut EventStream,
+    predicate: impl Fn(&Event) -> bool,
+) -> anyhow::Result<Event> {
+    while let Some(event) =
+        tokio::time::timeout(EVENT_TIMEOUT, stream.next()).await?
+    {
+        let event = event?;
+        if predicate(&event) {
+            return Ok(event);
+        }
+    }
+    anyhow::bail!("event stream closed")
+}
+
+/// Serialize a legacy transaction message that transfers
+/// lamports from the fee payer using the system program.
+pub fn solana_transfer_message(
+    payer: [u8; 32],
+    recipient: [u8; 32],
+    lamports: u64,
+) -> Vec<u8> {
+    let system_program = [0u8; 32];
+    let recent_blockhash = [9u8; 32];
+
+    let mut message = Vec::new();
+    // Required signatures, read-only signed and read-only unsigned
+    message.extend_from_slice(&[1, 0, 1]);
+    message.push(3);
+    message.extend_from_slice(&payer);
+    message.extend_from_slice(&recipient);
+    message.extend_from_slice(&system_program);
+    message.extend_from_slice(&recent_blockhash);
+    // Single transfer instruction
+    message.push(1);
+    message.push(2);
+    message.extend_from_slice(&[2, 0, 1]);
+    let mut data = 2u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    message.push(data.len() as u8);
+    message.extend_from_slice(&data);
+    message
+}
@@ -0,0 +1,56 @@
+use polysig_client::typescript::declarations;
+
+#[test]
+fn typescript_declarations() {
+    let decls = declarations();
+
+    for name in [
+        "KeyType",
+        "Keypair",
+        "Parameters",
+        "PublicKeys",
+        "KeyShare",
+        "PartyKeys",
+        "PartyOptions",
+        "ProgressEvent",
+        "ErrorKind",
+        "ErrorInfo",
+        "ServerOptions",
+        "SessionOptions",
+        "KeepaliveOptions",
+        "RecoverableSignature",
+        "CurveBackend",
+        "Capabilities",
+    ] {
+        assert!(
+            decls.contains(&format!("export type {} =", name)),
+            "missing declaration for {}",
+            name
+        );
+    }
+
+    // Field names match the serde representation
+    for field in [
+        "type: KeyType",
+        "serverUrl:",
+        "serverPublicKey:",
+        "publicKey:",
+        "verifyingKey:",
+        "associatedData: unknown",
+        "isInitiator:",
+        "partyIndex:",
+        "verifiers: Array<Array<number>>",
+        "recoveryId:",
+        "sessionId:",
+        "retryable:",
+        "encodingVersions:",
+        "payloadEncodings:",
+    ] {
+        assert!(decls.contains(field), "missing field {}", field);
+    }
+
+    // Fields skipped by serde are not declared
+    for field in ["policy:", "progress:", "cancel:", "is_initiator"] {
+        assert!(!decls.contains(field), "unexpected field {}", field);
+    }
+}
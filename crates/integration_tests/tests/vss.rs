@@ -0,0 +1,91 @@
+use anyhow::Result;
+use polysig_driver::{
+    blstrs::{G1Projective, Scalar},
+    ff::Field,
+    group::Group,
+    vss::{self, Share},
+};
+use rand::rngs::OsRng;
+
+#[test]
+fn vss_feldman_split_reconstruct() -> Result<()> {
+    let secret = Scalar::random(&mut OsRng);
+    let (shares, commitment) =
+        vss::split_feldman::<G1Projective>(secret, 2, 3, &mut OsRng)?;
+
+    assert_eq!(3, shares.len());
+    assert_eq!(2, commitment.threshold());
+    assert_eq!(
+        G1Projective::generator() * secret,
+        commitment.public_key()
+    );
+
+    for share in &shares {
+        commitment.verify(share)?;
+    }
+
+    assert_eq!(secret, vss::reconstruct(&shares[0..2])?);
+    assert_eq!(secret, vss::reconstruct(&shares[1..3])?);
+    assert_eq!(secret, vss::reconstruct(&shares)?);
+
+    // Below the threshold the secret is not recovered
+    assert_ne!(secret, vss::reconstruct(&shares[0..1])?);
+
+    // Tampered share must not verify
+    let share = &shares[0];
+    let tampered =
+        Share::new(share.identifier(), *share.value() + Scalar::ONE);
+    assert!(commitment.verify(&tampered).is_err());
+
+    // Duplicate identifiers cannot be reconstructed
+    assert!(vss::reconstruct(&[shares[0], shares[0]]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn vss_pedersen_split_reconstruct() -> Result<()> {
+    let secret = Scalar::random(&mut OsRng);
+    let generator = G1Projective::hash_to_curve(
+        b"polysig-vss-test",
+        b"polysig-vss-generator",
+        &[],
+    );
+    let (shares, commitment) = vss::split_pedersen::<G1Projective>(
+        secret, 3, 5, generator, &mut OsRng,
+    )?;
+
+    assert_eq!(5, shares.len());
+    assert_eq!(3, commitment.threshold());
+
+    for share in &shares {
+        commitment.verify(share)?;
+    }
+
+    let secret_shares =
+        shares.iter().map(|s| *s.share()).collect::<Vec<_>>();
+    assert_eq!(secret, vss::reconstruct(&secret_shares[2..5])?);
+
+    // Tampered blinding must not verify
+    let share = &shares[1];
+    let tampered = vss::PedersenShare::new(
+        *share.share(),
+        *share.blinding() + Scalar::ONE,
+    );
+    assert!(commitment.verify(&tampered).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn vss_invalid_threshold() {
+    let secret = Scalar::random(&mut OsRng);
+    assert!(vss::split_feldman::<G1Projective>(
+        secret, 0, 3, &mut OsRng
+    )
+    .is_err());
+    assert!(vss::split_feldman::<G1Projective>(
+        secret, 4, 3, &mut OsRng
+    )
+    .is_err());
+}
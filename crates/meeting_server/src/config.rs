@@ -1,12 +1,30 @@
 //! Server configuration.
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use tokio::fs;
 use url::Url;
 
 use crate::{Error, Result};
 
+/// Prefix for environment variables that override settings.
+const ENV_PREFIX: &str = "MPC_MEETING_";
+
 /// Configuration for the web server.
+///
+/// Settings may be loaded from a TOML file, created with
+/// a [ServerConfigBuilder] or read from the environment.
+/// Environment variables take precedence over the values
+/// in a config file:
+///
+/// * `MPC_MEETING_SESSION_TIMEOUT` - Meeting room timeout.
+/// * `MPC_MEETING_SESSION_INTERVAL` - Interval to reap rooms.
+/// * `MPC_MEETING_TLS_CERT` - Path to the TLS certificate.
+/// * `MPC_MEETING_TLS_KEY` - Path to the TLS certificate key.
+/// * `MPC_MEETING_CORS_ORIGINS` - Comma separated CORS origins.
 #[derive(Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ServerConfig {
@@ -57,7 +75,16 @@ impl Default for SessionConfig {
 }
 
 impl ServerConfig {
+    /// Create a builder for a server config.
+    pub fn builder() -> ServerConfigBuilder {
+        Default::default()
+    }
+
     /// Load a server config from a file path.
+    ///
+    /// Relative paths in the file are resolved against the
+    /// directory containing the file and environment
+    /// variables override the values in the file.
     pub async fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         if !fs::try_exists(path.as_ref()).await? {
             return Err(Error::NotFile(path.as_ref().to_path_buf()));
@@ -66,10 +93,6 @@ impl ServerConfig {
         let contents = fs::read_to_string(path.as_ref()).await?;
         let mut config: ServerConfig = toml::from_str(&contents)?;
 
-        if config.session.interval <= config.session.timeout {
-            return Err(Error::SessionTimeoutConfig);
-        }
-
         let dir = Self::directory(path.as_ref())?;
         if let Some(tls) = config.tls.as_mut() {
             if tls.cert.is_relative() {
@@ -80,9 +103,57 @@ impl ServerConfig {
             }
         }
 
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load a server config from environment variables.
+    ///
+    /// Settings that are not set in the environment use
+    /// the default values.
+    pub fn from_env() -> Result<Self> {
+        let mut config = ServerConfig::default();
+        config.apply_env()?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Override settings with values from environment variables.
+    pub fn apply_env(&mut self) -> Result<()> {
+        if let Some(timeout) = env_parse("SESSION_TIMEOUT")? {
+            self.session.timeout = timeout;
+        }
+        if let Some(interval) = env_parse("SESSION_INTERVAL")? {
+            self.session.interval = interval;
+        }
+        if let Some(cert) = env_var("TLS_CERT") {
+            self.tls.get_or_insert_with(Default::default).cert =
+                cert.into();
+        }
+        if let Some(key) = env_var("TLS_KEY") {
+            self.tls.get_or_insert_with(Default::default).key =
+                key.into();
+        }
+        if let Some(origins) = env_var("CORS_ORIGINS") {
+            self.cors.origins = origins
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| parse("CORS_ORIGINS", item))
+                .collect::<Result<_>>()?;
+        }
+        Ok(())
+    }
+
+    /// Validate the settings.
+    pub fn validate(&self) -> Result<()> {
+        if self.session.interval <= self.session.timeout {
+            return Err(Error::SessionTimeoutConfig);
+        }
+        Ok(())
+    }
+
     /// Parent directory of the configuration file.
     fn directory(file: impl AsRef<Path>) -> Result<PathBuf> {
         file.as_ref()
@@ -92,6 +163,87 @@ impl ServerConfig {
     }
 }
 
+/// Builder for a server config.
+#[derive(Default)]
+pub struct ServerConfigBuilder {
+    config: ServerConfig,
+}
+
+impl ServerConfigBuilder {
+    /// Set the meeting room timeout in seconds.
+    pub fn session_timeout(mut self, timeout: u64) -> Self {
+        self.config.session.timeout = timeout;
+        self
+    }
+
+    /// Set the interval in seconds to reap expired meeting rooms.
+    pub fn session_interval(mut self, interval: u64) -> Self {
+        self.config.session.interval = interval;
+        self
+    }
+
+    /// Enable TLS with a certificate and key.
+    pub fn tls(
+        mut self,
+        cert: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+    ) -> Self {
+        self.config.tls = Some(TlsConfig {
+            cert: cert.into(),
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Add a CORS origin.
+    pub fn cors_origin(mut self, origin: Url) -> Self {
+        self.config.cors.origins.push(origin);
+        self
+    }
+
+    /// Override settings with values from environment variables.
+    pub fn env(mut self) -> Result<Self> {
+        self.config.apply_env()?;
+        Ok(self)
+    }
+
+    /// Validate the settings and build the server config.
+    pub fn build(self) -> Result<ServerConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Read a setting from the environment.
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(format!("{}{}", ENV_PREFIX, name)).ok()
+}
+
+/// Parse a setting from the environment.
+fn env_parse<T>(name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    env_var(name)
+        .map(|value| parse(name, value.trim()))
+        .transpose()
+}
+
+/// Parse an environment variable value.
+fn parse<T>(name: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| {
+        Error::EnvVar(
+            format!("{}{}", ENV_PREFIX, name),
+            e.to_string(),
+        )
+    })
+}
+
 /// Configuration for CORS.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CorsConfig {
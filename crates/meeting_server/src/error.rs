@@ -32,6 +32,11 @@ pub enum Error {
     #[error("session timeout must be greater than the interval")]
     SessionTimeoutConfig,
 
+    /// Error generated when an environment variable has
+    /// an invalid value.
+    #[error(r#"invalid value for environment variable "{0}": {1}"#)]
+    EnvVar(String, String),
+
     /// Error generated by input/output.
     #[error(transparent)]
     Io(#[from] std::io::Error),
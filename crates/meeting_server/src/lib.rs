@@ -9,9 +9,12 @@ mod meeting_manager;
 mod server;
 mod websocket;
 
-pub use config::ServerConfig;
+pub use config::{
+    CorsConfig, ServerConfig, ServerConfigBuilder, SessionConfig,
+    TlsConfig,
+};
 pub use error::Error;
-pub use server::MeetingServer;
+pub use server::{EmbedHandle, MeetingServer};
 
 pub use axum;
 
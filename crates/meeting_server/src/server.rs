@@ -2,7 +2,10 @@ use futures::StreamExt;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
 use std::{net::SocketAddr, sync::Arc, time::Duration};
-use tokio::sync::{Mutex, RwLock};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+};
 use tokio_stream::wrappers::IntervalStream;
 
 use axum::{
@@ -40,6 +43,17 @@ async fn purge_expired(state: State, interval_secs: u64) {
     }
 }
 
+/// Handle for a server embedded in another application.
+///
+/// Stops reaping expired meetings when dropped.
+pub struct EmbedHandle(JoinHandle<()>);
+
+impl Drop for EmbedHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 pub struct ServerState {
     /// Server config.
     pub(crate) config: ServerConfig,
@@ -73,6 +87,28 @@ impl MeetingServer {
         }
     }
 
+    /// Create a router to embed the server in another
+    /// axum application.
+    ///
+    /// The router is not bound to a listener so it can be
+    /// merged with the routes of the application to share the
+    /// same port and middleware; TLS settings in the config
+    /// are ignored as the application owns the listener.
+    ///
+    /// Expired meetings are reaped until the handle is dropped.
+    pub async fn embed(&self) -> Result<(Router, EmbedHandle)> {
+        let interval = {
+            let reader = self.state.read().await;
+            reader.config.session.interval
+        };
+        let app = self.router(Arc::clone(&self.state)).await?;
+        let handle = EmbedHandle(tokio::task::spawn(purge_expired(
+            Arc::clone(&self.state),
+            interval,
+        )));
+        Ok((app, handle))
+    }
+
     /// Start the server.
     pub async fn start(
         &self,
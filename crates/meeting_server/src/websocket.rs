@@ -3,7 +3,7 @@ use axum::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         Extension,
     },
-    http::StatusCode,
+    http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderMap, StatusCode},
     response::Response,
 };
 use futures::{
@@ -20,7 +20,10 @@ use tokio::sync::Mutex;
 //use axum_macros::debug_handler;
 
 use crate::{server::State, Result};
-use polysig_protocol::{zlib, MeetingRequest, MeetingResponse};
+use polysig_protocol::{
+    is_subprotocol_supported, zlib, MeetingRequest, MeetingResponse,
+    SUBPROTOCOL,
+};
 
 pub type Connection = Arc<Mutex<WebSocketConnection>>;
 
@@ -54,10 +57,21 @@ impl WebSocketConnection {
 //#[debug_handler]
 pub async fn upgrade(
     Extension(state): Extension<State>,
+    headers: HeaderMap,
     ws: WebSocketUpgrade,
 ) -> std::result::Result<Response, StatusCode> {
     tracing::debug!("websocket upgrade request");
 
+    // Clients that offer subprotocols must support the
+    // wire encoding version of the server
+    let offered = headers
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .map(|value| value.to_str().unwrap_or_default());
+    if !is_subprotocol_supported(offered) {
+        return Err(StatusCode::UPGRADE_REQUIRED);
+    }
+    let ws = ws.protocols([SUBPROTOCOL]);
+
     let socket_state = Arc::clone(&state);
     Ok(ws.on_upgrade(move |socket| {
         let (ws_writer, ws_reader) = socket.split();
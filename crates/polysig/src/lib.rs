@@ -8,3 +8,5 @@ pub use polysig_driver as driver;
 pub use polysig_meeting_server as meeting;
 pub use polysig_protocol as protocol;
 pub use polysig_relay_server as relay;
+
+pub use polysig_client::{capabilities, Capabilities, CurveBackend};
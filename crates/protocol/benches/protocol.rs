@@ -0,0 +1,206 @@
+//! Benchmarks for the wire encoding and noise transport.
+//!
+//! ```text
+//! cargo bench -p polysig-protocol
+//! ```
+use criterion::{
+    criterion_group, criterion_main, BenchmarkId, Criterion,
+    Throughput,
+};
+use futures::executor::block_on;
+use polysig_protocol::{
+    channel::{decrypt_server_channel, encrypt_server_channel},
+    decode, encode, snow, Chunk, Encoding, OpaqueMessage,
+    ProtocolState, RequestMessage, SealedEnvelope, SessionId,
+    PATTERN,
+};
+
+/// Payload sizes; the larger sizes span multiple chunks.
+const SIZES: [usize; 4] = [1024, 65_536, 1_048_576, 4_194_304];
+
+fn payload(size: usize) -> Vec<u8> {
+    (0..size).map(|i| (i % 251) as u8).collect()
+}
+
+/// Complete a noise handshake and return the transport
+/// states for the initiator and responder.
+fn transports() -> (ProtocolState, ProtocolState) {
+    let params: snow::params::NoiseParams = PATTERN.parse().unwrap();
+    let builder = snow::Builder::new(params.clone());
+    let initiator_keypair = builder.generate_keypair().unwrap();
+    let responder_keypair = builder.generate_keypair().unwrap();
+
+    let mut initiator = snow::Builder::new(params.clone())
+        .local_private_key(&initiator_keypair.private)
+        .remote_public_key(&responder_keypair.public)
+        .build_initiator()
+        .unwrap();
+    let mut responder = snow::Builder::new(params)
+        .local_private_key(&responder_keypair.private)
+        .remote_public_key(&initiator_keypair.public)
+        .build_responder()
+        .unwrap();
+
+    let (mut read_buf, mut first_msg, mut second_msg) =
+        ([0u8; 1024], [0u8; 1024], [0u8; 1024]);
+    let len = initiator.write_message(&[], &mut first_msg).unwrap();
+    responder
+        .read_message(&first_msg[..len], &mut read_buf)
+        .unwrap();
+    let len = responder.write_message(&[], &mut second_msg).unwrap();
+    initiator
+        .read_message(&second_msg[..len], &mut read_buf)
+        .unwrap();
+
+    (
+        ProtocolState::Transport(
+            initiator.into_transport_mode().unwrap(),
+        ),
+        ProtocolState::Transport(
+            responder.into_transport_mode().unwrap(),
+        ),
+    )
+}
+
+/// Peer message with an envelope of chunks for a payload.
+///
+/// Chunks are not encrypted, only the size matters when
+/// measuring the encoding.
+fn peer_message(size: usize) -> RequestMessage {
+    let chunks = payload(size)
+        .chunks(65_535)
+        .map(|chunk| Chunk {
+            length: chunk.len(),
+            contents: chunk.to_vec(),
+        })
+        .collect();
+    RequestMessage::Opaque(OpaqueMessage::PeerMessage {
+        public_key: vec![1; 32],
+        session_id: Some(SessionId::new_v4()),
+        envelope: SealedEnvelope {
+            encoding: Encoding::Blob,
+            chunks,
+            broadcast: false,
+        },
+    })
+}
+
+fn codec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("codec");
+    for size in SIZES {
+        let message = peer_message(size);
+        let encoded = block_on(encode(&message)).unwrap();
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("encode", size),
+            &message,
+            |b, message| {
+                b.iter(|| block_on(encode(message)).unwrap())
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("decode", size),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    block_on(decode::<RequestMessage>(encoded))
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn chunks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunks");
+    for size in SIZES {
+        let payload = payload(size);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("split", size),
+            &payload,
+            |b, payload| {
+                let (mut initiator, _) = transports();
+                let ProtocolState::Transport(transport) =
+                    &mut initiator
+                else {
+                    unreachable!();
+                };
+                b.iter(|| Chunk::split(payload, transport).unwrap())
+            },
+        );
+        // Chunks must be joined in the order they were split
+        // so each iteration splits and joins the payload
+        group.bench_with_input(
+            BenchmarkId::new("split_join", size),
+            &payload,
+            |b, payload| {
+                let (mut initiator, mut responder) = transports();
+                let (
+                    ProtocolState::Transport(initiator),
+                    ProtocolState::Transport(responder),
+                ) = (&mut initiator, &mut responder)
+                else {
+                    unreachable!();
+                };
+                b.iter(|| {
+                    let chunks =
+                        Chunk::split(payload, initiator).unwrap();
+                    Chunk::join(chunks, responder).unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Encrypt and encode a message then decode and decrypt,
+/// the same steps taken for every message sent to a peer.
+fn channel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("channel");
+    for size in SIZES {
+        let payload = payload(size);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("round_trip", size),
+            &payload,
+            |b, payload| {
+                let (mut initiator, mut responder) = transports();
+                b.iter(|| {
+                    block_on(async {
+                        let envelope = encrypt_server_channel(
+                            &mut initiator,
+                            payload,
+                            false,
+                        )
+                        .await
+                        .unwrap();
+                        let message = RequestMessage::Opaque(
+                            OpaqueMessage::ServerMessage(envelope),
+                        );
+                        let encoded = encode(&message).await.unwrap();
+                        let RequestMessage::Opaque(
+                            OpaqueMessage::ServerMessage(envelope),
+                        ) = decode::<RequestMessage>(&encoded)
+                            .await
+                            .unwrap()
+                        else {
+                            unreachable!();
+                        };
+                        decrypt_server_channel(
+                            &mut responder,
+                            envelope,
+                        )
+                        .await
+                        .unwrap()
+                    })
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, codec, chunks, channel);
+criterion_main!(benches);
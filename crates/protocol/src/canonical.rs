@@ -0,0 +1,199 @@
+//! Canonical JSON encoding (RFC 8785).
+//!
+//! Object members are sorted by the UTF-16 code units of
+//! their names, numbers use the ECMAScript serialization and
+//! strings only escape the characters JSON requires so a
+//! value always encodes to the same bytes; use this encoding
+//! when hashing structured messages so the digest matches
+//! other implementations of the JSON Canonicalization Scheme.
+use crate::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Encode a value as canonical JSON.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&mut out, &value);
+    Ok(out)
+}
+
+/// Encode a value as canonical JSON bytes.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    Ok(to_string(value)?.into_bytes())
+}
+
+/// Canonicalize a JSON document.
+pub fn canonicalize(json: &str) -> Result<String> {
+    let value: Value = serde_json::from_str(json)?;
+    to_string(&value)
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => {
+            out.push_str(if *value { "true" } else { "false" })
+        }
+        // All JSON numbers are IEEE 754 doubles in the
+        // canonical form, large integers lose precision
+        Value::Number(number) => {
+            write_number(out, number.as_f64().unwrap_or_default())
+        }
+        Value::String(value) => write_string(out, value),
+        Value::Array(values) => {
+            out.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(out, value);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| {
+                a.encode_utf16().cmp(b.encode_utf16())
+            });
+            out.push('{');
+            for (index, (name, value)) in
+                members.into_iter().enumerate()
+            {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(out, name);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < '\u{20}' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Write a number using the ECMAScript `Number.prototype.toString()`
+/// algorithm.
+fn write_number(out: &mut String, value: f64) {
+    // Also covers negative zero
+    if value == 0.0 {
+        out.push('0');
+        return;
+    }
+    if value < 0.0 {
+        out.push('-');
+    }
+
+    // Shortest digits that round trip
+    let repr = format!("{:e}", value.abs());
+    let (mantissa, exponent) =
+        repr.split_once('e').unwrap_or((&repr, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or_default();
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat('0').take((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n as usize);
+        out.push_str(integer);
+        out.push('.');
+        out.push_str(fraction);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat('0').take(-n as usize));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        let exponent = n - 1;
+        out.push('e');
+        out.push(if exponent < 0 { '-' } else { '+' });
+        let _ = write!(out, "{}", exponent.abs());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn canonical_primitives() -> Result<()> {
+        let json = r#"{"numbers":[333333333.33333329,1E30,4.50,2e-3,0.000000000000000000000000001],"string":"\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/","literals":[null,true,false]}"#;
+        let expected = r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#;
+        assert_eq!(expected, canonicalize(json)?);
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_sort_utf16() -> Result<()> {
+        let json = r#"{"\u20ac":"Euro Sign","\r":"Carriage Return","\ufb33":"Hebrew Letter Dalet With Dagesh","1":"One","\ud83d\ude00":"Emoji: Grinning Face","\u0080":"Control","\u00f6":"Latin Small Letter O With Diaeresis"}"#;
+        let canonical = canonicalize(json)?;
+        let expected = [
+            "Carriage Return",
+            "One",
+            "Control",
+            "Latin Small Letter O With Diaeresis",
+            "Euro Sign",
+            "Emoji: Grinning Face",
+            "Hebrew Letter Dalet With Dagesh",
+        ];
+        let positions: Vec<_> = expected
+            .iter()
+            .filter_map(|name| canonical.find(name))
+            .collect();
+        assert_eq!(expected.len(), positions.len());
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_numbers() -> Result<()> {
+        let cases = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (1e20, "100000000000000000000"),
+            (1e21, "1e+21"),
+            (1e-6, "0.000001"),
+            (1e-7, "1e-7"),
+            (123.456e-10, "1.23456e-8"),
+            (9007199254740992.0, "9007199254740992"),
+            (f64::MAX, "1.7976931348623157e+308"),
+            (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(expected, to_string(&value)?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,466 @@
+//! Golden fixtures for wire protocol compatibility tests.
+//!
+//! Every request, response and server message variant is
+//! encoded as a fixture for each encoding version so changes
+//! that would break older clients are caught by tests. The
+//! fixtures for a released version must never change; a
+//! breaking change requires a new encoding version with a
+//! new set of fixtures.
+//!
+//! Fixtures are stored in the `fixtures` directory of this
+//! crate with one file for each fixture so bindings for other
+//! languages can check their encoders and decoders against
+//! the same bytes.
+use crate::{
+    decode, encode, Chunk, Encoding, Error, HandshakeMessage,
+    OpaqueMessage, Parameters, ParticipantStats, RequestMessage,
+    ResponseMessage, Result, SealedEnvelope, ServerMessage,
+    SessionId, SessionRequest, SessionState, SessionStats,
+    SessionToken, SessionTranscript, TransparentMessage,
+};
+use http::StatusCode;
+
+/// Message encoded by a fixture.
+#[derive(Debug)]
+pub enum Message {
+    /// Request message.
+    Request(RequestMessage),
+    /// Response message.
+    Response(ResponseMessage),
+    /// Server message sent inside an encrypted envelope.
+    Server(ServerMessage),
+}
+
+/// Encoded message for an encoding version.
+#[derive(Debug)]
+pub struct Fixture {
+    /// Name of the fixture file without the extension.
+    pub name: &'static str,
+    /// Encoding version.
+    pub version: u16,
+    /// Message the fixture encodes.
+    pub message: Message,
+    /// Encoded bytes.
+    pub bytes: &'static [u8],
+}
+
+impl Fixture {
+    /// Verify the message encodes to the fixture bytes and
+    /// the fixture bytes decode to the same message.
+    pub async fn verify(&self) -> Result<()> {
+        let (encoded, decoded) = match &self.message {
+            Message::Request(message) => (
+                encode(message).await?,
+                encode(&decode::<RequestMessage>(self.bytes).await?)
+                    .await?,
+            ),
+            Message::Response(message) => (
+                encode(message).await?,
+                encode(&decode::<ResponseMessage>(self.bytes).await?)
+                    .await?,
+            ),
+            Message::Server(message) => (
+                encode(message).await?,
+                encode(&decode::<ServerMessage>(self.bytes).await?)
+                    .await?,
+            ),
+        };
+        if encoded != self.bytes {
+            return Err(Error::FixtureEncode(self.name.to_owned()));
+        }
+        if decoded != self.bytes {
+            return Err(Error::FixtureDecode(self.name.to_owned()));
+        }
+        Ok(())
+    }
+}
+
+macro_rules! fixture {
+    ($version:literal, $name:literal, $message:expr) => {
+        Fixture {
+            name: $name,
+            version: $version,
+            message: $message,
+            bytes: include_bytes!(concat!(
+                "../fixtures/v",
+                $version,
+                "/",
+                $name,
+                ".bin"
+            )),
+        }
+    };
+}
+
+/// Fixtures for an encoding version.
+///
+/// Returns an empty list for unknown versions.
+pub fn fixtures(version: u16) -> Vec<Fixture> {
+    match version {
+        1 => v1(),
+        _ => Vec::new(),
+    }
+}
+
+fn v1() -> Vec<Fixture> {
+    vec![
+        fixture!(
+            1,
+            "request-error",
+            Message::Request(RequestMessage::Transparent(error()))
+        ),
+        fixture!(
+            1,
+            "request-server-handshake",
+            Message::Request(RequestMessage::Transparent(
+                TransparentMessage::ServerHandshake(initiator())
+            ))
+        ),
+        fixture!(
+            1,
+            "request-peer-handshake",
+            Message::Request(RequestMessage::Transparent(
+                TransparentMessage::PeerHandshake {
+                    public_key: key(2),
+                    message: initiator(),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "request-server-message",
+            Message::Request(RequestMessage::Opaque(
+                OpaqueMessage::ServerMessage(envelope(
+                    Encoding::Blob,
+                    false
+                ))
+            ))
+        ),
+        fixture!(
+            1,
+            "request-peer-message",
+            Message::Request(RequestMessage::Opaque(
+                OpaqueMessage::PeerMessage {
+                    public_key: key(2),
+                    session_id: Some(session_id()),
+                    envelope: envelope(Encoding::Json, true),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "request-peer-message-no-session",
+            Message::Request(RequestMessage::Opaque(
+                OpaqueMessage::PeerMessage {
+                    public_key: key(2),
+                    session_id: None,
+                    envelope: envelope(Encoding::Blob, false),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "request-group-message",
+            Message::Request(RequestMessage::Opaque(
+                OpaqueMessage::GroupMessage {
+                    public_key: key(1),
+                    session_id: session_id(),
+                    envelope: envelope(Encoding::Json, true),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "request-noop",
+            Message::Request(RequestMessage::Noop)
+        ),
+        fixture!(
+            1,
+            "response-error",
+            Message::Response(ResponseMessage::Transparent(error()))
+        ),
+        fixture!(
+            1,
+            "response-server-handshake",
+            Message::Response(ResponseMessage::Transparent(
+                TransparentMessage::ServerHandshake(responder())
+            ))
+        ),
+        fixture!(
+            1,
+            "response-peer-handshake",
+            Message::Response(ResponseMessage::Transparent(
+                TransparentMessage::PeerHandshake {
+                    public_key: key(1),
+                    message: responder(),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "response-server-message",
+            Message::Response(ResponseMessage::Opaque(
+                OpaqueMessage::ServerMessage(envelope(
+                    Encoding::Blob,
+                    false
+                ))
+            ))
+        ),
+        fixture!(
+            1,
+            "response-peer-message",
+            Message::Response(ResponseMessage::Opaque(
+                OpaqueMessage::PeerMessage {
+                    public_key: key(1),
+                    session_id: Some(session_id()),
+                    envelope: envelope(Encoding::Json, true),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "response-group-message",
+            Message::Response(ResponseMessage::Opaque(
+                OpaqueMessage::GroupMessage {
+                    public_key: key(1),
+                    session_id: session_id(),
+                    envelope: envelope(Encoding::Json, true),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "response-noop",
+            Message::Response(ResponseMessage::Noop)
+        ),
+        fixture!(
+            1,
+            "server-error",
+            Message::Server(ServerMessage::Error(
+                StatusCode::NOT_FOUND,
+                "session not found".to_owned()
+            ))
+        ),
+        fixture!(
+            1,
+            "server-new-session",
+            Message::Server(ServerMessage::NewSession(
+                SessionRequest {
+                    participant_keys: vec![key(2), key(3)],
+                    observer_keys: vec![],
+                    parameters: None,
+                    deadline: None,
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "server-new-session-observers",
+            Message::Server(ServerMessage::NewSession(
+                SessionRequest {
+                    participant_keys: vec![key(2), key(3)],
+                    observer_keys: vec![key(4)],
+                    parameters: None,
+                    deadline: None,
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "server-new-session-parameters",
+            Message::Server(ServerMessage::NewSession(
+                SessionRequest {
+                    participant_keys: vec![key(2), key(3)],
+                    observer_keys: vec![],
+                    parameters: Some(Parameters {
+                        parties: 2,
+                        threshold: 2,
+                    }),
+                    deadline: None,
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "server-new-session-deadline",
+            Message::Server(ServerMessage::NewSession(
+                SessionRequest {
+                    participant_keys: vec![key(2), key(3)],
+                    observer_keys: vec![],
+                    parameters: None,
+                    deadline: Some(1_700_000_000),
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "server-session-connection",
+            Message::Server(ServerMessage::SessionConnection {
+                session_id: session_id(),
+                peer_key: key(2),
+            })
+        ),
+        fixture!(
+            1,
+            "server-session-created",
+            Message::Server(ServerMessage::SessionCreated(state()))
+        ),
+        fixture!(
+            1,
+            "server-session-ready",
+            Message::Server(ServerMessage::SessionReady(state()))
+        ),
+        fixture!(
+            1,
+            "server-session-active",
+            Message::Server(ServerMessage::SessionActive(state()))
+        ),
+        fixture!(
+            1,
+            "server-session-timeout",
+            Message::Server(ServerMessage::SessionTimeout(
+                session_id()
+            ))
+        ),
+        fixture!(
+            1,
+            "server-close-session",
+            Message::Server(
+                ServerMessage::CloseSession(session_id())
+            )
+        ),
+        fixture!(
+            1,
+            "server-session-finished",
+            Message::Server(ServerMessage::SessionFinished(
+                session_id(),
+                None
+            ))
+        ),
+        fixture!(
+            1,
+            "server-session-finished-stats",
+            Message::Server(ServerMessage::SessionFinished(
+                session_id(),
+                Some(SessionStats {
+                    duration: 1500,
+                    participants: vec![
+                        ParticipantStats {
+                            public_key: key(1),
+                            frames: 4,
+                            bytes: 1024,
+                        },
+                        ParticipantStats {
+                            public_key: key(2),
+                            frames: 2,
+                            bytes: 512,
+                        },
+                    ],
+                })
+            ))
+        ),
+        fixture!(
+            1,
+            "server-resume-session",
+            Message::Server(ServerMessage::ResumeSession(token()))
+        ),
+        fixture!(
+            1,
+            "server-session-resumed",
+            Message::Server(ServerMessage::SessionResumed(
+                SessionState {
+                    token: Some(token()),
+                    ..state()
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "server-session-transcript",
+            Message::Server(ServerMessage::SessionTranscript(
+                SessionTranscript {
+                    session_id: session_id(),
+                    messages: 3,
+                    hash: [0xBB; 32],
+                }
+            ))
+        ),
+        fixture!(
+            1,
+            "server-server-key",
+            Message::Server(ServerMessage::ServerKey(key(5)))
+        ),
+        fixture!(
+            1,
+            "server-get-session-digest",
+            Message::Server(ServerMessage::GetSessionDigest(
+                session_id()
+            ))
+        ),
+        fixture!(
+            1,
+            "server-session-digest",
+            Message::Server(ServerMessage::SessionDigest(
+                SessionTranscript {
+                    session_id: session_id(),
+                    messages: 3,
+                    hash: [0xBB; 32],
+                }
+            ))
+        ),
+    ]
+}
+
+fn session_id() -> SessionId {
+    SessionId::from_u128(0x6c0f5b2e_9a41_4c3d_8e27_1f5a9d3b7c60)
+}
+
+fn key(party: u8) -> Vec<u8> {
+    vec![party; 32]
+}
+
+fn error() -> TransparentMessage {
+    TransparentMessage::Error(
+        StatusCode::BAD_REQUEST,
+        "bad request".to_owned(),
+    )
+}
+
+fn initiator() -> HandshakeMessage {
+    let payload: Vec<u8> = (0..32).collect();
+    HandshakeMessage::Initiator(payload.len(), payload)
+}
+
+fn responder() -> HandshakeMessage {
+    let payload: Vec<u8> = (0..32).collect();
+    HandshakeMessage::Responder(payload.len(), payload)
+}
+
+fn envelope(encoding: Encoding, broadcast: bool) -> SealedEnvelope {
+    let contents: Vec<u8> = (0x40..0x54).collect();
+    SealedEnvelope {
+        encoding,
+        chunks: vec![Chunk {
+            length: contents.len(),
+            contents,
+        }],
+        broadcast,
+    }
+}
+
+fn state() -> SessionState {
+    SessionState {
+        session_id: session_id(),
+        all_participants: vec![key(1), key(2), key(3)],
+        token: None,
+    }
+}
+
+fn token() -> SessionToken {
+    SessionToken {
+        session_id: session_id(),
+        public_key: key(1),
+        expires_at: 1_700_000_000,
+        signature: vec![0xAA; 32],
+    }
+}
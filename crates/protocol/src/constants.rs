@@ -3,6 +3,34 @@
 /// Noise protocol pattern.
 pub const PATTERN: &str = "Noise_NN_25519_ChaChaPoly_BLAKE2s";
 
+/// Noise protocol pattern for clients that know the static
+/// key of the responder.
+///
+/// The first handshake message is encrypted to the responder
+/// and authenticates the initiator so it may carry a payload.
+pub const PATTERN_IK: &str = "Noise_IK_25519_ChaChaPoly_BLAKE2s";
+
+/// Noise protocol pattern for clients that do not know the
+/// static key of the responder.
+///
+/// The responder transmits its static key in the handshake
+/// reply so the initiator can pin the key on first use.
+pub const PATTERN_IX: &str = "Noise_IX_25519_ChaChaPoly_BLAKE2s";
+
+/// Path for relay connections over HTTP/2 streams.
+pub const STREAM_PATH: &str = "/stream";
+
+/// Header used to offer and select the subprotocol for
+/// relay connections over HTTP/2 streams.
+pub const SUBPROTOCOL_HEADER: &str = "polysig-subprotocol";
+
+/// Close code sent by the relay server to a connection that
+/// is replaced by a new connection for the same public key.
+///
+/// Clients should not reconnect when a connection is closed
+/// with this code as another client is using the key.
+pub const CLOSE_SUPERSEDED: u16 = 4001;
+
 /// Tag for PEM encoding of noise pattern.
 pub const PEM_PATTERN: &str = "NOISE PATTERN";
 
@@ -1,7 +1,7 @@
 //! Binary encoding implementation.
 
 mod v1;
-pub use v1::VERSION;
+pub use v1::{SUBPROTOCOL, VERSION};
 
 use crate::Error;
 use binary_stream::{
@@ -17,6 +17,21 @@ pub(crate) fn encoding_error(
     std::io::Error::new(std::io::ErrorKind::Other, e)
 }
 
+/// Determine if the websocket subprotocols offered by a
+/// client in the `Sec-WebSocket-Protocol` header include
+/// the subprotocol for this encoding version.
+///
+/// Clients that do not offer any subprotocol predate
+/// negotiation and are treated as compatible.
+pub fn is_subprotocol_supported(offered: Option<&str>) -> bool {
+    match offered {
+        Some(offered) => offered
+            .split(',')
+            .any(|protocol| protocol.trim() == SUBPROTOCOL),
+        None => true,
+    }
+}
+
 /// Maximum buffer size for encoding and decoding.
 pub(crate) const MAX_BUFFER_SIZE: usize = 65535;
 
@@ -91,6 +106,7 @@ pub(crate) mod types {
 
     pub const OPAQUE_SERVER: u8 = 1;
     pub const OPAQUE_PEER: u8 = 2;
+    pub const OPAQUE_GROUP: u8 = 3;
 
     pub const SESSION_NEW: u8 = 5;
     pub const SESSION_CREATED: u8 = 6;
@@ -100,7 +116,17 @@ pub(crate) mod types {
     pub const SESSION_TIMEOUT: u8 = 10;
     pub const SESSION_CLOSE: u8 = 11;
     pub const SESSION_FINISHED: u8 = 12;
+    pub const SESSION_RESUME: u8 = 13;
+    pub const SESSION_RESUMED: u8 = 14;
+    pub const SESSION_TRANSCRIPT: u8 = 15;
+    pub const SERVER_KEY: u8 = 16;
+    pub const SESSION_DIGEST_GET: u8 = 17;
+    pub const SESSION_DIGEST: u8 = 18;
+
+    pub const SESSION_STATS_V1: u8 = 1;
 
     pub const ENCODING_BLOB: u8 = 1;
     pub const ENCODING_JSON: u8 = 2;
+    pub const ENCODING_CBOR: u8 = 3;
+    pub const ENCODING_MSGPACK: u8 = 4;
 }
@@ -11,13 +11,18 @@ use crate::{
         MAX_BUFFER_SIZE,
     },
     Chunk, Encoding, Error, HandshakeMessage, OpaqueMessage,
-    RequestMessage, ResponseMessage, SealedEnvelope, ServerMessage,
-    SessionId, SessionRequest, SessionState, TransparentMessage,
+    Parameters, ParticipantStats, RequestMessage, ResponseMessage,
+    SealedEnvelope, ServerMessage, SessionId, SessionRequest,
+    SessionState, SessionStats, SessionToken, SessionTranscript,
+    TransparentMessage,
 };
 
 /// Version for binary encoding.
 pub const VERSION: u16 = 1;
 
+/// Websocket subprotocol for the binary encoding version.
+pub const SUBPROTOCOL: &str = "polysig.v1";
+
 /// Encode a length-prefixed buffer.
 async fn encode_buffer<W: AsyncWrite + AsyncSeek + Unpin + Send>(
     writer: &mut BinaryWriter<W>,
@@ -242,9 +247,34 @@ impl Encodable for ServerMessage {
             Self::CloseSession(session_id) => {
                 writer.write_bytes(session_id.as_bytes()).await?;
             }
-            Self::SessionFinished(session_id) => {
+            Self::SessionFinished(session_id, stats) => {
+                writer.write_bytes(session_id.as_bytes()).await?;
+                // Statistics are trailing data prefixed with the
+                // version of the statistics so clients ignore
+                // statistics in a format they do not understand
+                if let Some(stats) = stats {
+                    writer.write_u8(types::SESSION_STATS_V1).await?;
+                    stats.encode(writer).await?;
+                }
+            }
+            Self::ResumeSession(token) => {
+                token.encode(writer).await?;
+            }
+            Self::SessionResumed(response) => {
+                response.encode(writer).await?;
+            }
+            Self::SessionTranscript(transcript) => {
+                transcript.encode(writer).await?;
+            }
+            Self::ServerKey(public_key) => {
+                encode_buffer(writer, public_key).await?;
+            }
+            Self::GetSessionDigest(session_id) => {
                 writer.write_bytes(session_id.as_bytes()).await?;
             }
+            Self::SessionDigest(transcript) => {
+                transcript.encode(writer).await?;
+            }
             Self::Noop => unreachable!(),
         }
         Ok(())
@@ -338,7 +368,57 @@ impl Decodable for ServerMessage {
                         .try_into()
                         .map_err(encoding_error)?,
                 );
-                *self = ServerMessage::SessionFinished(session_id);
+                let mut stats = None;
+                if reader.stream_position().await?
+                    < reader.len().await?
+                {
+                    let version = reader.read_u8().await?;
+                    if version == types::SESSION_STATS_V1 {
+                        let mut value: SessionStats =
+                            Default::default();
+                        value.decode(reader).await?;
+                        stats = Some(value);
+                    }
+                }
+                *self =
+                    ServerMessage::SessionFinished(session_id, stats);
+            }
+            types::SESSION_RESUME => {
+                let mut token: SessionToken = Default::default();
+                token.decode(reader).await?;
+                *self = ServerMessage::ResumeSession(token);
+            }
+            types::SESSION_RESUMED => {
+                let mut session: SessionState = Default::default();
+                session.decode(reader).await?;
+                *self = ServerMessage::SessionResumed(session);
+            }
+            types::SESSION_TRANSCRIPT => {
+                let mut transcript: SessionTranscript =
+                    Default::default();
+                transcript.decode(reader).await?;
+                *self = ServerMessage::SessionTranscript(transcript);
+            }
+            types::SERVER_KEY => {
+                let public_key = decode_buffer(reader).await?;
+                *self = ServerMessage::ServerKey(public_key);
+            }
+            types::SESSION_DIGEST_GET => {
+                let session_id = SessionId::from_bytes(
+                    reader
+                        .read_bytes(16)
+                        .await?
+                        .as_slice()
+                        .try_into()
+                        .map_err(encoding_error)?,
+                );
+                *self = ServerMessage::GetSessionDigest(session_id);
+            }
+            types::SESSION_DIGEST => {
+                let mut transcript: SessionTranscript =
+                    Default::default();
+                transcript.decode(reader).await?;
+                *self = ServerMessage::SessionDigest(transcript);
             }
             _ => {
                 return Err(encoding_error(
@@ -377,6 +457,15 @@ impl Encodable for OpaqueMessage {
                 }
                 envelope.encode(writer).await?;
             }
+            Self::GroupMessage {
+                public_key,
+                session_id,
+                envelope,
+            } => {
+                encode_buffer(writer, public_key).await?;
+                writer.write_bytes(session_id.as_bytes()).await?;
+                envelope.encode(writer).await?;
+            }
             Self::Noop => unreachable!(),
         }
         Ok(())
@@ -425,6 +514,26 @@ impl Decodable for OpaqueMessage {
                     envelope,
                 };
             }
+            types::OPAQUE_GROUP => {
+                let public_key = decode_buffer(reader).await?;
+                let session_id = SessionId::from_bytes(
+                    reader
+                        .read_bytes(16)
+                        .await?
+                        .as_slice()
+                        .try_into()
+                        .map_err(encoding_error)?,
+                );
+
+                let mut envelope: SealedEnvelope = Default::default();
+                envelope.decode(reader).await?;
+
+                *self = OpaqueMessage::GroupMessage {
+                    public_key,
+                    session_id,
+                    envelope,
+                };
+            }
             _ => {
                 return Err(encoding_error(
                     crate::Error::EncodingKind(id),
@@ -454,7 +563,7 @@ impl Encodable for RequestMessage {
             Self::Opaque(message) => {
                 message.encode(writer).await?;
             }
-            Self::Noop => unreachable!(),
+            Self::Noop => {}
         }
         Ok(())
     }
@@ -483,6 +592,9 @@ impl Decodable for RequestMessage {
                 message.decode(reader).await?;
                 *self = RequestMessage::Opaque(message);
             }
+            types::NOOP => {
+                *self = RequestMessage::Noop;
+            }
             _ => {
                 return Err(encoding_error(
                     crate::Error::EncodingKind(id),
@@ -512,7 +624,7 @@ impl Encodable for ResponseMessage {
             Self::Opaque(message) => {
                 message.encode(&mut *writer).await?;
             }
-            Self::Noop => unreachable!(),
+            Self::Noop => {}
         }
         Ok(())
     }
@@ -541,6 +653,9 @@ impl Decodable for ResponseMessage {
                 message.decode(reader).await?;
                 *self = ResponseMessage::Opaque(message);
             }
+            types::NOOP => {
+                *self = ResponseMessage::Noop;
+            }
             _ => {
                 return Err(encoding_error(
                     crate::Error::EncodingKind(id),
@@ -607,6 +722,12 @@ impl Decodable for SealedEnvelope {
             types::ENCODING_JSON => {
                 self.encoding = Encoding::Json;
             }
+            types::ENCODING_CBOR => {
+                self.encoding = Encoding::Cbor;
+            }
+            types::ENCODING_MSGPACK => {
+                self.encoding = Encoding::MessagePack;
+            }
             _ => {
                 return Err(encoding_error(
                     crate::Error::EncodingKind(id),
@@ -637,6 +758,30 @@ impl Encodable for SessionRequest {
         for key in self.participant_keys.iter() {
             encode_buffer(writer, key).await?;
         }
+        // Observers, parameters and the deadline are trailing
+        // data so requests without them are encoded the same as
+        // before they were introduced
+        let has_deadline = self.deadline.is_some();
+        if !self.observer_keys.is_empty()
+            || self.parameters.is_some()
+            || has_deadline
+        {
+            writer.write_u16(self.observer_keys.len() as u16).await?;
+            for key in self.observer_keys.iter() {
+                encode_buffer(writer, key).await?;
+            }
+        }
+        if let Some(parameters) = &self.parameters {
+            writer.write_u16(parameters.parties).await?;
+            writer.write_u16(parameters.threshold).await?;
+        } else if has_deadline {
+            // Zero parties indicates there are no parameters
+            writer.write_u16(0).await?;
+            writer.write_u16(0).await?;
+        }
+        if let Some(deadline) = self.deadline {
+            writer.write_u64(deadline).await?;
+        }
         Ok(())
     }
 }
@@ -652,6 +797,24 @@ impl Decodable for SessionRequest {
             let key = decode_buffer(reader).await?;
             self.participant_keys.push(key);
         }
+        if reader.stream_position().await? < reader.len().await? {
+            let size = reader.read_u16().await? as usize;
+            for _ in 0..size {
+                let key = decode_buffer(reader).await?;
+                self.observer_keys.push(key);
+            }
+        }
+        if reader.stream_position().await? < reader.len().await? {
+            let parties = reader.read_u16().await?;
+            let threshold = reader.read_u16().await?;
+            if parties > 0 {
+                self.parameters =
+                    Some(Parameters { parties, threshold });
+            }
+        }
+        if reader.stream_position().await? < reader.len().await? {
+            self.deadline = Some(reader.read_u64().await?);
+        }
         Ok(())
     }
 }
@@ -667,6 +830,12 @@ impl Encodable for SessionState {
         for key in &self.all_participants {
             encode_buffer(writer, key).await?;
         }
+        // The token is trailing data so messages without
+        // a token are encoded the same as before tokens
+        // were introduced
+        if let Some(token) = &self.token {
+            token.encode(writer).await?;
+        }
         Ok(())
     }
 }
@@ -690,6 +859,123 @@ impl Decodable for SessionState {
             let key = decode_buffer(reader).await?;
             self.all_participants.push(key);
         }
+        if reader.stream_position().await? < reader.len().await? {
+            let mut token: SessionToken = Default::default();
+            token.decode(reader).await?;
+            self.token = Some(token);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Encodable for SessionToken {
+    async fn encode<W: AsyncWrite + AsyncSeek + Unpin + Send>(
+        &self,
+        writer: &mut BinaryWriter<W>,
+    ) -> Result<()> {
+        writer.write_bytes(self.session_id.as_bytes()).await?;
+        encode_buffer(writer, &self.public_key).await?;
+        writer.write_u64(self.expires_at).await?;
+        encode_buffer(writer, &self.signature).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Decodable for SessionToken {
+    async fn decode<R: AsyncRead + AsyncSeek + Unpin + Send>(
+        &mut self,
+        reader: &mut BinaryReader<R>,
+    ) -> Result<()> {
+        self.session_id = SessionId::from_bytes(
+            reader
+                .read_bytes(16)
+                .await?
+                .as_slice()
+                .try_into()
+                .map_err(encoding_error)?,
+        );
+        self.public_key = decode_buffer(reader).await?;
+        self.expires_at = reader.read_u64().await?;
+        self.signature = decode_buffer(reader).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Encodable for SessionTranscript {
+    async fn encode<W: AsyncWrite + AsyncSeek + Unpin + Send>(
+        &self,
+        writer: &mut BinaryWriter<W>,
+    ) -> Result<()> {
+        writer.write_bytes(self.session_id.as_bytes()).await?;
+        writer.write_u64(self.messages).await?;
+        writer.write_bytes(&self.hash).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Decodable for SessionTranscript {
+    async fn decode<R: AsyncRead + AsyncSeek + Unpin + Send>(
+        &mut self,
+        reader: &mut BinaryReader<R>,
+    ) -> Result<()> {
+        self.session_id = SessionId::from_bytes(
+            reader
+                .read_bytes(16)
+                .await?
+                .as_slice()
+                .try_into()
+                .map_err(encoding_error)?,
+        );
+        self.messages = reader.read_u64().await?;
+        self.hash = reader
+            .read_bytes(32)
+            .await?
+            .as_slice()
+            .try_into()
+            .map_err(encoding_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Encodable for SessionStats {
+    async fn encode<W: AsyncWrite + AsyncSeek + Unpin + Send>(
+        &self,
+        writer: &mut BinaryWriter<W>,
+    ) -> Result<()> {
+        writer.write_u64(self.duration).await?;
+        writer.write_u16(self.participants.len() as u16).await?;
+        for participant in &self.participants {
+            encode_buffer(writer, &participant.public_key).await?;
+            writer.write_u64(participant.frames).await?;
+            writer.write_u64(participant.bytes).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Decodable for SessionStats {
+    async fn decode<R: AsyncRead + AsyncSeek + Unpin + Send>(
+        &mut self,
+        reader: &mut BinaryReader<R>,
+    ) -> Result<()> {
+        self.duration = reader.read_u64().await?;
+        let size = reader.read_u16().await? as usize;
+        for _ in 0..size {
+            let public_key = decode_buffer(reader).await?;
+            let frames = reader.read_u64().await?;
+            let bytes = reader.read_u64().await?;
+            self.participants.push(ParticipantStats {
+                public_key,
+                frames,
+                bytes,
+            });
+        }
         Ok(())
     }
 }
@@ -1,5 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
+/// Category of an error.
+///
+/// Shared by the protocol, driver and client errors so callers
+/// can distinguish transient network failures from protocol
+/// aborts without matching on the error type.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorKind {
+    /// Connection to the server failed or was lost.
+    Transport,
+    /// Session or meeting could not be established.
+    Session,
+    /// Protocol ceremony failed or inputs were invalid.
+    Protocol,
+    /// Ceremony was cancelled.
+    Abort,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ErrorKind::Transport => "transport",
+                ErrorKind::Session => "session",
+                ErrorKind::Protocol => "protocol",
+                ErrorKind::Abort => "abort",
+            }
+        )
+    }
+}
+
 /// Errors generated by the relay protocol.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -42,6 +80,41 @@ pub enum Error {
     #[error("wrong PEM tag, expected '{0}' but got '{1}'")]
     PemTag(String, String),
 
+    /// Error generated when the threshold is not between one
+    /// and the number of parties.
+    #[error("threshold {1} is not valid for {0} parties")]
+    InvalidParameters(u16, u16),
+
+    /// Error generated when the threshold is below the
+    /// minimum threshold supported by a protocol.
+    #[error("threshold {1} is below the minimum threshold {0}")]
+    ThresholdTooLow(u16, u16),
+
+    /// Error generated when the number of parties does not
+    /// match the number of session participants.
+    #[error("expected {0} parties but got {1} participants")]
+    PartiesMismatch(u16, usize),
+
+    /// Error generated when a payload encoding is not
+    /// a serialized encoding or the feature for the
+    /// encoding is not enabled.
+    #[error("payload encoding {0:?} is not supported")]
+    UnsupportedEncoding(crate::Encoding),
+
+    /// Error generated when a message does not encode
+    /// to the bytes of a compatibility fixture.
+    #[cfg(feature = "compat")]
+    #[error(
+        "message for fixture {0} does not encode to the fixture"
+    )]
+    FixtureEncode(String),
+
+    /// Error generated when a compatibility fixture does not
+    /// decode to the message for the fixture.
+    #[cfg(feature = "compat")]
+    #[error("fixture {0} does not decode to the fixture message")]
+    FixtureDecode(String),
+
     /// Error generated by input/output.
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -61,4 +134,43 @@ pub enum Error {
     /// Error generated serializing or deserializing JSON.
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+
+    /// Error generated serializing or deserializing CBOR.
+    #[cfg(feature = "cbor")]
+    #[error("cbor: {0}")]
+    Cbor(String),
+
+    /// Error generated serializing MessagePack.
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+
+    /// Error generated deserializing MessagePack.
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+}
+
+impl Error {
+    /// Category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Io(_) => ErrorKind::Transport,
+            Error::PatternMismatch(_)
+            | Error::BadKeypairPem
+            | Error::NotTransportState
+            | Error::UnknownKeyType(_)
+            | Error::PemTag(_, _)
+            | Error::Snow(_)
+            | Error::Pem(_) => ErrorKind::Session,
+            _ => ErrorKind::Protocol,
+        }
+    }
+
+    /// Whether the operation may succeed if retried.
+    ///
+    /// Only input/output errors are transient.
+    pub fn is_retryable(&self) -> bool {
+        self.kind() == ErrorKind::Transport
+    }
 }
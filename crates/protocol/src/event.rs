@@ -1,4 +1,7 @@
-use crate::{MeetingResponse, Result, SessionId, SessionState};
+use crate::{
+    Encoding, Error, MeetingResponse, Result, SessionId,
+    SessionState, SessionStats, SessionTranscript,
+};
 /// Events dispatched by the event loop stream.
 #[derive(Debug)]
 pub enum Event {
@@ -14,6 +17,15 @@ pub enum Event {
         /// Public key of the peer.
         peer_key: Vec<u8>,
     },
+    /// Event dispatched when a direct connection to a peer
+    /// has been established.
+    ///
+    /// Messages sent to the peer are no longer relayed by the
+    /// server unless the direct connection is lost.
+    PeerUpgraded {
+        /// Public key of the peer.
+        peer_key: Vec<u8>,
+    },
     /// Binary message received from a peer.
     BinaryMessage {
         /// Public key of the peer.
@@ -60,26 +72,70 @@ pub enum Event {
     /// Event dispatched when a session has been finished.
     ///
     /// A session can only be finished when the session owner
-    /// explicitly closes the session.
-    SessionFinished(SessionId),
+    /// explicitly closes the session; statistics for the
+    /// session are included when the server computes them.
+    SessionFinished(SessionId, Option<SessionStats>),
+
+    /// Event dispatched when a session has been resumed
+    /// after reconnecting to the server.
+    ///
+    /// Peer connections are not restored; the participant
+    /// must handshake with the session peers again.
+    SessionResumed(SessionState),
+
+    /// Event dispatched to the observers of a session when
+    /// a message has been relayed between the participants.
+    SessionTranscript(SessionTranscript),
+
+    /// Event dispatched in response to a request for the
+    /// digest of the messages relayed in a session.
+    ///
+    /// The digest is computed by the server independently
+    /// of the participants so it can be compared with the
+    /// messages sent and received to detect lost or
+    /// duplicated messages.
+    SessionDigest(SessionTranscript),
+
+    /// Event dispatched when the server is rotating its
+    /// static key.
+    ///
+    /// Clients should use the new key for new connections;
+    /// the server continues to accept the previous key until
+    /// the rotation is complete.
+    ServerKeyRotated {
+        /// New public key of the server.
+        server_key: Vec<u8>,
+    },
 
     /// Event dispatched when the socket is closed.
     Close,
 }
 
-/// JSON message received from a peer.
+/// Serialized message received from a peer.
+///
+/// Messages are JSON unless the sender chose another
+/// serialized [Encoding] for the payload.
 #[derive(Debug)]
 pub struct JsonMessage {
     contents: Vec<u8>,
+    encoding: Encoding,
 }
 
 impl From<Vec<u8>> for JsonMessage {
     fn from(contents: Vec<u8>) -> Self {
-        Self { contents }
+        Self {
+            contents,
+            encoding: Encoding::Json,
+        }
     }
 }
 
 impl JsonMessage {
+    /// Create a message from serialized contents.
+    pub fn new(contents: Vec<u8>, encoding: Encoding) -> Self {
+        Self { contents, encoding }
+    }
+
     /// Serialize a message.
     pub fn serialize<T: serde::ser::Serialize>(
         value: &T,
@@ -87,10 +143,62 @@ impl JsonMessage {
         Ok(serde_json::to_vec(value)?)
     }
 
+    /// Serialize a message with an encoding.
+    pub fn serialize_with<T: serde::ser::Serialize>(
+        value: &T,
+        encoding: Encoding,
+    ) -> Result<Vec<u8>> {
+        match encoding {
+            Encoding::Json => Self::serialize(value),
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => {
+                let mut contents = Vec::new();
+                ciborium::into_writer(value, &mut contents)
+                    .map_err(|e| Error::Cbor(e.to_string()))?;
+                Ok(contents)
+            }
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => {
+                Ok(rmp_serde::to_vec_named(value)?)
+            }
+            _ => Err(Error::UnsupportedEncoding(encoding)),
+        }
+    }
+
     /// Deserialize this message.
     pub fn deserialize<'a, T: serde::de::Deserialize<'a>>(
         &'a self,
     ) -> Result<T> {
-        Ok(serde_json::from_slice::<T>(&self.contents)?)
+        match self.encoding {
+            Encoding::Json => {
+                Ok(serde_json::from_slice::<T>(&self.contents)?)
+            }
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => {
+                // Decode to a value first as the reader does not
+                // support borrowing from the contents
+                let value: ciborium::Value =
+                    ciborium::from_reader(self.contents.as_slice())
+                        .map_err(|e| Error::Cbor(e.to_string()))?;
+                value
+                    .deserialized()
+                    .map_err(|e| Error::Cbor(e.to_string()))
+            }
+            #[cfg(feature = "msgpack")]
+            Encoding::MessagePack => {
+                Ok(rmp_serde::from_slice::<T>(&self.contents)?)
+            }
+            _ => Err(Error::UnsupportedEncoding(self.encoding)),
+        }
+    }
+
+    /// Encoding of this message.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Serialized contents of this message.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.contents
     }
 }
@@ -10,6 +10,7 @@ use std::fmt;
 
 /// Type of supported keys.
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub enum KeyType {
     /// Noise protocol encryption key.
@@ -53,6 +54,7 @@ impl std::str::FromStr for KeyType {
 
 /// Key pair used by the noise protocol.
 #[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Keypair {
     private: Vec<u8>,
     public: Vec<u8>,
@@ -140,11 +142,38 @@ impl Keypair {
     }
 }
 
+/// Handshake pattern name of noise parameters, eg: `IK`
+/// for `Noise_IK_25519_ChaChaPoly_BLAKE2s`.
+fn handshake_name(pattern: &str) -> &str {
+    pattern.split('_').nth(1).unwrap_or_default()
+}
+
+/// Determine if the responder must know the static key of
+/// the initiator before the handshake (`K` patterns).
+pub fn is_initiator_key_known(pattern: &str) -> bool {
+    handshake_name(pattern).starts_with('K')
+}
+
+/// Determine if the first handshake message is encrypted to
+/// the static key of the responder so that it may carry a
+/// payload (`NK`, `KK`, `XK` and `IK` patterns).
+pub fn is_zero_rtt(pattern: &str) -> bool {
+    handshake_name(pattern).chars().nth(1) == Some('K')
+}
+
+/// Determine if the responder transmits its static key in
+/// the handshake so the initiator does not need to know it
+/// in advance (`NX` and `IX` patterns).
+pub fn is_responder_key_transmitted(pattern: &str) -> bool {
+    handshake_name(pattern).chars().nth(1) == Some('X')
+}
+
 #[cfg(test)]
 mod tests {
     use super::Keypair;
     use crate::{
-        Error, PATTERN, PEM_PATTERN, PEM_PRIVATE, PEM_PUBLIC, TAGLEN,
+        Error, PATTERN, PATTERN_IK, PATTERN_IX, PEM_PATTERN,
+        PEM_PRIVATE, PEM_PUBLIC, TAGLEN,
     };
     use anyhow::Result;
     use pem::Pem;
@@ -247,4 +276,93 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn noise_ik_handshake() -> Result<()> {
+        let builder_1 = snow::Builder::new(PATTERN_IK.parse()?);
+        let builder_2 = snow::Builder::new(PATTERN_IK.parse()?);
+
+        let keypair1 = Keypair::generate()?;
+        let keypair2 = Keypair::generate()?;
+
+        let mut initiator = builder_1
+            .local_private_key(keypair1.private_key())
+            .remote_public_key(keypair2.public_key())
+            .build_initiator()?;
+
+        // Responder learns the initiator static key
+        let mut responder = builder_2
+            .local_private_key(keypair2.private_key())
+            .build_responder()?;
+
+        let (mut read_buf, mut first_msg, mut second_msg) =
+            ([0u8; 1024], [0u8; 1024], [0u8; 1024]);
+
+        // -> e, es, s, ss with an early payload
+        let early = b"early";
+        let len = initiator.write_message(early, &mut first_msg)?;
+        let read = responder
+            .read_message(&first_msg[..len], &mut read_buf)?;
+        assert_eq!(early, &read_buf[..read]);
+        assert_eq!(
+            Some(keypair1.public_key()),
+            responder.get_remote_static()
+        );
+
+        // <- e, ee, se
+        let len = responder.write_message(&[], &mut second_msg)?;
+        initiator.read_message(&second_msg[..len], &mut read_buf)?;
+
+        assert!(initiator.is_handshake_finished());
+        assert!(responder.is_handshake_finished());
+
+        assert!(super::is_zero_rtt(PATTERN_IK));
+        assert!(!super::is_zero_rtt(PATTERN));
+        assert!(!super::is_initiator_key_known(PATTERN_IK));
+        assert!(super::is_initiator_key_known(
+            "Noise_KK_25519_ChaChaPoly_BLAKE2s"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn noise_ix_handshake() -> Result<()> {
+        let builder_1 = snow::Builder::new(PATTERN_IX.parse()?);
+        let builder_2 = snow::Builder::new(PATTERN_IX.parse()?);
+
+        let keypair1 = Keypair::generate()?;
+        let keypair2 = Keypair::generate()?;
+
+        // Initiator learns the responder static key
+        let mut initiator = builder_1
+            .local_private_key(keypair1.private_key())
+            .build_initiator()?;
+        let mut responder = builder_2
+            .local_private_key(keypair2.private_key())
+            .build_responder()?;
+
+        let (mut read_buf, mut first_msg, mut second_msg) =
+            ([0u8; 1024], [0u8; 1024], [0u8; 1024]);
+
+        // -> e, s
+        let len = initiator.write_message(&[], &mut first_msg)?;
+        responder.read_message(&first_msg[..len], &mut read_buf)?;
+
+        // <- e, ee, se, s, es
+        let len = responder.write_message(&[], &mut second_msg)?;
+        initiator.read_message(&second_msg[..len], &mut read_buf)?;
+
+        assert!(initiator.is_handshake_finished());
+        assert_eq!(
+            Some(keypair2.public_key()),
+            initiator.get_remote_static()
+        );
+
+        assert!(super::is_responder_key_transmitted(PATTERN_IX));
+        assert!(!super::is_responder_key_transmitted(PATTERN_IK));
+        assert!(!super::is_responder_key_transmitted(PATTERN));
+
+        Ok(())
+    }
 }
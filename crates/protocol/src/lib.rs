@@ -5,13 +5,31 @@
 //! The maximum size of a [noise protocol](https://noiseprotocol.org/)
 //! message is 65535 and we further limit the size of buffers
 //! for encoding to 32KB.
+//!
+//! # Compatibility
+//!
+//! Enable the `compat` feature for golden fixtures of every
+//! message variant; use them to test that changes do not
+//! break the wire protocol, see the [compat] module.
+//!
+//! Clients offer the [SUBPROTOCOL] for the encoding version when
+//! upgrading to a websocket so that servers can reject
+//! incompatible clients before any message is decoded.
+//!
+//! # Logging
+//!
+//! Log public keys using [fingerprint] and never log payloads
+//! or key material directly, use [redacted] instead.
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 #![cfg_attr(all(doc, CHANNEL_NIGHTLY), feature(doc_auto_cfg))]
 #![allow(clippy::len_without_is_empty)]
 
+pub mod canonical;
 #[doc(hidden)]
 pub mod channel;
+#[cfg(feature = "compat")]
+pub mod compat;
 mod constants;
 pub(crate) mod encoding;
 mod error;
@@ -19,15 +37,20 @@ mod event;
 mod keypair;
 mod meetings;
 mod protocol;
+mod redact;
+pub mod stream;
 pub mod zlib;
 
 pub use constants::*;
-pub use encoding::{decode, encode, VERSION};
-pub use error::Error;
+pub use encoding::{
+    decode, encode, is_subprotocol_supported, SUBPROTOCOL, VERSION,
+};
+pub use error::{Error, ErrorKind};
 pub use event::{Event, JsonMessage};
 pub use keypair::*;
 pub use meetings::*;
 pub use protocol::*;
+pub use redact::*;
 
 pub use hex;
 pub use http;
@@ -8,6 +8,7 @@ pub type MeetingId = uuid::Uuid;
 
 /// Public keys for a participant.
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 #[serde(rename_all = "camelCase")]
 pub struct PublicKeys {
     /// Public key for the noise transport.
@@ -15,6 +16,7 @@ pub struct PublicKeys {
     /// Verifying key.
     pub verifying_key: Vec<u8>,
     /// Optional application specific associated data.
+    #[cfg_attr(feature = "typescript", ts(type = "unknown"))]
     pub associated_data: Option<Value>,
 }
 
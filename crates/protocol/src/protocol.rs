@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use snow::{HandshakeState, TransportState};
 use std::{
     collections::{HashMap, HashSet},
+    fmt,
     time::{Duration, SystemTime},
 };
 
@@ -31,6 +32,7 @@ impl From<[u8; 32]> for UserId {
 
 /// Parameters used during key generation.
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
 pub struct Parameters {
     /// Number of parties `n`.
     pub parties: u16,
@@ -50,6 +52,44 @@ impl Default for Parameters {
     }
 }
 
+impl Parameters {
+    /// Validate the threshold is between one and the
+    /// number of parties.
+    pub fn validate(&self) -> Result<()> {
+        if self.threshold == 0 || self.threshold > self.parties {
+            return Err(crate::Error::InvalidParameters(
+                self.parties,
+                self.threshold,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate the parameters for a session with the given
+    /// number of participants and the minimum threshold
+    /// supported by a protocol.
+    pub fn validate_session(
+        &self,
+        participants: usize,
+        min_threshold: u16,
+    ) -> Result<()> {
+        self.validate()?;
+        if self.threshold < min_threshold {
+            return Err(crate::Error::ThresholdTooLow(
+                min_threshold,
+                self.threshold,
+            ));
+        }
+        if self.parties as usize != participants {
+            return Err(crate::Error::PartiesMismatch(
+                self.parties,
+                participants,
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Enumeration of protocol states.
 pub enum ProtocolState {
     /// Noise handshake state.
@@ -152,7 +192,33 @@ pub enum ServerMessage {
     /// Request to close a session.
     CloseSession(SessionId),
     /// Message sent when a session was closed.
-    SessionFinished(SessionId),
+    ///
+    /// Servers that compute statistics for the session
+    /// include them with the message.
+    SessionFinished(SessionId, Option<SessionStats>),
+    /// Request to re-attach to a session using the token
+    /// issued when the session was created or ready.
+    ResumeSession(SessionToken),
+    /// Response to a resume session request.
+    SessionResumed(SessionState),
+    /// Notification dispatched to the observers of a session
+    /// after a message is relayed between the participants.
+    SessionTranscript(SessionTranscript),
+    /// Notification sent after the server handshake when the
+    /// server is rotating its static key.
+    ///
+    /// Contains the public key clients should use for
+    /// new connections.
+    ServerKey(Vec<u8>),
+    /// Request the running hash of the messages relayed
+    /// in a session.
+    ///
+    /// Participants and observers of the session may request
+    /// the digest; the server must be configured to compute
+    /// the digest for sessions without observers.
+    GetSessionDigest(SessionId),
+    /// Response to a session digest request.
+    SessionDigest(SessionTranscript),
 }
 
 impl From<&ServerMessage> for u8 {
@@ -173,9 +239,21 @@ impl From<&ServerMessage> for u8 {
                 types::SESSION_TIMEOUT
             }
             ServerMessage::CloseSession(_) => types::SESSION_CLOSE,
-            ServerMessage::SessionFinished(_) => {
+            ServerMessage::SessionFinished(..) => {
                 types::SESSION_FINISHED
             }
+            ServerMessage::ResumeSession(_) => types::SESSION_RESUME,
+            ServerMessage::SessionResumed(_) => {
+                types::SESSION_RESUMED
+            }
+            ServerMessage::SessionTranscript(_) => {
+                types::SESSION_TRANSCRIPT
+            }
+            ServerMessage::ServerKey(_) => types::SERVER_KEY,
+            ServerMessage::GetSessionDigest(_) => {
+                types::SESSION_DIGEST_GET
+            }
+            ServerMessage::SessionDigest(_) => types::SESSION_DIGEST,
         }
     }
 }
@@ -201,6 +279,20 @@ pub enum OpaqueMessage {
         /// Message envelope.
         envelope: SealedEnvelope,
     },
+
+    /// Broadcast a message encrypted with the group key
+    /// of a session to all the other session participants.
+    GroupMessage {
+        /// Public key of the sender.
+        ///
+        /// The server replaces the value in requests with the
+        /// public key of the connection.
+        public_key: Vec<u8>,
+        /// Session identifier.
+        session_id: SessionId,
+        /// Message envelope.
+        envelope: SealedEnvelope,
+    },
 }
 
 impl From<&OpaqueMessage> for u8 {
@@ -209,6 +301,7 @@ impl From<&OpaqueMessage> for u8 {
             OpaqueMessage::Noop => types::NOOP,
             OpaqueMessage::ServerMessage(_) => types::OPAQUE_SERVER,
             OpaqueMessage::PeerMessage { .. } => types::OPAQUE_PEER,
+            OpaqueMessage::GroupMessage { .. } => types::OPAQUE_GROUP,
         }
     }
 }
@@ -262,7 +355,12 @@ impl From<&ResponseMessage> for u8 {
 }
 
 /// Encoding for message payloads.
-#[derive(Default, Clone, Copy, Debug)]
+///
+/// Serialized payloads are JSON by default; the CBOR and
+/// MessagePack encodings require the `cbor` and `msgpack`
+/// features and are more compact for messages with
+/// binary fields.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Encoding {
     #[default]
     #[doc(hidden)]
@@ -271,6 +369,48 @@ pub enum Encoding {
     Blob,
     /// JSON encoding.
     Json,
+    /// CBOR encoding.
+    Cbor,
+    /// MessagePack encoding.
+    MessagePack,
+}
+
+impl Encoding {
+    /// Determine if this is a serialized payload encoding.
+    pub fn is_serialized(&self) -> bool {
+        matches!(
+            self,
+            Encoding::Json | Encoding::Cbor | Encoding::MessagePack
+        )
+    }
+
+    /// Payload encodings compiled into the library.
+    pub fn supported() -> Vec<Encoding> {
+        let mut encodings = vec![Encoding::Blob, Encoding::Json];
+        if cfg!(feature = "cbor") {
+            encodings.push(Encoding::Cbor);
+        }
+        if cfg!(feature = "msgpack") {
+            encodings.push(Encoding::MessagePack);
+        }
+        encodings
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Noop => "noop",
+                Self::Blob => "blob",
+                Self::Json => "json",
+                Self::Cbor => "cbor",
+                Self::MessagePack => "msgpack",
+            }
+        )
+    }
 }
 
 impl From<Encoding> for u8 {
@@ -279,6 +419,8 @@ impl From<Encoding> for u8 {
             Encoding::Noop => types::NOOP,
             Encoding::Blob => types::ENCODING_BLOB,
             Encoding::Json => types::ENCODING_JSON,
+            Encoding::Cbor => types::ENCODING_CBOR,
+            Encoding::MessagePack => types::ENCODING_MSGPACK,
         }
     }
 }
@@ -349,6 +491,75 @@ pub struct SealedEnvelope {
     pub broadcast: bool,
 }
 
+/// Priority class for relayed frames.
+///
+/// Frames are written in priority order so that handshakes and
+/// control messages are not delayed by round messages and
+/// round messages are not delayed by large payloads split
+/// into multiple chunks; frames with the same priority are
+/// written in the order they were queued.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Priority {
+    /// Payloads split into more than one chunk.
+    Bulk,
+    /// Protocol round messages.
+    Round,
+    /// Handshakes and server messages.
+    Control,
+}
+
+impl Priority {
+    /// Priorities from highest to lowest.
+    pub const ALL: [Priority; 3] =
+        [Priority::Control, Priority::Round, Priority::Bulk];
+}
+
+impl From<&SealedEnvelope> for Priority {
+    fn from(value: &SealedEnvelope) -> Self {
+        if value.chunks.len() > 1 {
+            Priority::Bulk
+        } else {
+            Priority::Round
+        }
+    }
+}
+
+impl From<&OpaqueMessage> for Priority {
+    fn from(value: &OpaqueMessage) -> Self {
+        match value {
+            OpaqueMessage::PeerMessage { envelope, .. }
+            | OpaqueMessage::GroupMessage { envelope, .. } => {
+                envelope.into()
+            }
+            OpaqueMessage::Noop | OpaqueMessage::ServerMessage(_) => {
+                Priority::Control
+            }
+        }
+    }
+}
+
+impl RequestMessage {
+    /// Priority for sending this message.
+    pub fn priority(&self) -> Priority {
+        match self {
+            RequestMessage::Opaque(message) => message.into(),
+            _ => Priority::Control,
+        }
+    }
+}
+
+impl ResponseMessage {
+    /// Priority for sending this message.
+    pub fn priority(&self) -> Priority {
+        match self {
+            ResponseMessage::Opaque(message) => message.into(),
+            _ => Priority::Control,
+        }
+    }
+}
+
 /// Session is a namespace for a group of participants
 /// to communicate for a series of rounds.
 ///
@@ -364,10 +575,30 @@ pub struct Session {
     /// Public keys of the other session participants.
     participant_keys: HashSet<Vec<u8>>,
 
+    /// Public keys of the participants in the order
+    /// given when the session was created.
+    all_participants: Vec<Vec<u8>>,
+
+    /// Public keys of the session observers.
+    ///
+    /// Observers receive the session notifications and
+    /// transcript hashes but cannot send or receive
+    /// protocol messages.
+    observer_keys: HashSet<Vec<u8>>,
+
+    /// Running hash of the messages relayed in this session.
+    transcript: SessionTranscript,
+
+    /// Frames and bytes relayed from each participant.
+    relayed: HashMap<Vec<u8>, (u64, u64)>,
+
     /// Connections between peers established in this
     /// session context.
     connections: HashSet<(Vec<u8>, Vec<u8>)>,
 
+    /// Time the session was created.
+    created: SystemTime,
+
     /// Last access time so the server can reap
     /// stale sessions.
     last_access: SystemTime,
@@ -379,6 +610,80 @@ impl Session {
         self.owner_key.as_slice()
     }
 
+    /// Public keys of the participants in the order
+    /// given when the session was created.
+    pub fn all_participants(&self) -> &[Vec<u8>] {
+        self.all_participants.as_slice()
+    }
+
+    /// Public keys of the session observers.
+    pub fn observers(&self) -> Vec<Vec<u8>> {
+        self.observer_keys.iter().cloned().collect()
+    }
+
+    /// Determine if a public key is a session observer.
+    pub fn is_observer(&self, public_key: &[u8]) -> bool {
+        self.observer_keys.contains(public_key)
+    }
+
+    /// Current transcript of the messages relayed in
+    /// this session.
+    pub fn transcript(&self) -> &SessionTranscript {
+        &self.transcript
+    }
+
+    /// Append the hash of a relayed message to the transcript.
+    ///
+    /// The hash must be computed over the previous transcript
+    /// hash and the relayed message.
+    pub fn append_transcript(
+        &mut self,
+        hash: [u8; 32],
+    ) -> &SessionTranscript {
+        self.transcript.messages += 1;
+        self.transcript.hash = hash;
+        &self.transcript
+    }
+
+    /// Record frames relayed from a participant.
+    pub fn record_relayed(
+        &mut self,
+        public_key: &[u8],
+        frames: u64,
+        bytes: u64,
+    ) {
+        let relayed =
+            self.relayed.entry(public_key.to_vec()).or_default();
+        relayed.0 += frames;
+        relayed.1 += bytes;
+    }
+
+    /// Statistics for the messages relayed in this session.
+    pub fn stats(&self) -> SessionStats {
+        let duration = SystemTime::now()
+            .duration_since(self.created)
+            .unwrap_or_default();
+        SessionStats {
+            duration: duration.as_millis() as u64,
+            participants: self
+                .all_participants
+                .iter()
+                .map(|public_key| {
+                    let (frames, bytes) = self
+                        .relayed
+                        .get(public_key)
+                        .copied()
+                        .unwrap_or_default();
+                    ParticipantStats {
+                        public_key: public_key.clone(),
+                        frames,
+                        bytes,
+                    }
+                })
+                .collect(),
+        }
+    }
+
     /// Get all participant's public keys
     pub fn public_keys(&self) -> Vec<&[u8]> {
         let mut keys = vec![self.owner_key.as_slice()];
@@ -457,13 +762,23 @@ impl SessionManager {
         &mut self,
         owner_key: Vec<u8>,
         participant_keys: Vec<Vec<u8>>,
+        observer_keys: Vec<Vec<u8>>,
     ) -> SessionId {
         let session_id = SessionId::new_v4();
+        let now = SystemTime::now();
         let session = Session {
             owner_key,
+            all_participants: participant_keys.clone(),
             participant_keys: participant_keys.into_iter().collect(),
+            observer_keys: observer_keys.into_iter().collect(),
+            transcript: SessionTranscript {
+                session_id,
+                ..Default::default()
+            },
+            relayed: Default::default(),
             connections: Default::default(),
-            last_access: SystemTime::now(),
+            created: now,
+            last_access: now,
         };
         self.sessions.insert(session_id, session);
         session_id
@@ -503,6 +818,29 @@ impl SessionManager {
         }
     }
 
+    /// Find a session observed by a public key when the key is
+    /// not a participant with a peer in any session.
+    ///
+    /// Observers are read-only so peer messages from an observer
+    /// are only relayed to peers it shares a session with.
+    pub fn observed_session(
+        &self,
+        public_key: &[u8],
+        peer_key: &[u8],
+    ) -> Option<SessionId> {
+        let is_participant = self.sessions.values().any(|s| {
+            s.all_participants.iter().any(|k| k == public_key)
+                && s.all_participants.iter().any(|k| k == peer_key)
+        });
+        if is_participant {
+            return None;
+        }
+        self.sessions
+            .iter()
+            .find(|(_, s)| s.is_observer(public_key))
+            .map(|(id, _)| *id)
+    }
+
     /// Get the keys of sessions that have expired.
     pub fn expired_keys(&self, timeout: u64) -> Vec<SessionId> {
         self.sessions
@@ -530,6 +868,25 @@ impl SessionManager {
 pub struct SessionRequest {
     /// Public keys of the session participants.
     pub participant_keys: Vec<Vec<u8>>,
+    /// Public keys of the session observers.
+    ///
+    /// Observers are notified of the session lifecycle and
+    /// receive the transcript hashes but are not parties
+    /// to the protocol.
+    pub observer_keys: Vec<Vec<u8>>,
+    /// Parameters for the protocol run in the session.
+    ///
+    /// When given the server validates the parameters
+    /// against the participants before creating the session.
+    pub parameters: Option<Parameters>,
+    /// Deadline for the session in seconds since the
+    /// UNIX epoch.
+    ///
+    /// When given the server notifies the participants that
+    /// the session timed out at the deadline and removes the
+    /// session so a participant that stops responding cannot
+    /// hold the session open indefinitely.
+    pub deadline: Option<u64>,
 }
 
 /// Response from creating new session.
@@ -539,6 +896,11 @@ pub struct SessionState {
     pub session_id: SessionId,
     /// Public keys of all participants.
     pub all_participants: Vec<Vec<u8>>,
+    /// Token for the receiver to resume the session.
+    ///
+    /// Only set for the session created, ready and
+    /// resumed messages.
+    pub token: Option<SessionToken>,
 }
 
 impl SessionState {
@@ -600,9 +962,108 @@ impl SessionState {
     }
 }
 
+/// Signed and time-limited token issued by the server that
+/// re-attaches a participant to their slot in a session.
+///
+/// Clients present the token after reconnecting so the
+/// session does not need to be negotiated again. Tokens are
+/// signed with a key derived from the server keypair so they
+/// are accepted by every server in a cluster that shares the
+/// keypair; load balancers may route on the session identifier
+/// to keep the affinity with the server that owns the session.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SessionToken {
+    /// Session identifier.
+    pub session_id: SessionId,
+    /// Public key of the participant.
+    pub public_key: Vec<u8>,
+    /// Expiry as seconds since the UNIX epoch.
+    pub expires_at: u64,
+    /// Signature computed by the server.
+    pub signature: Vec<u8>,
+}
+
+impl SessionToken {
+    /// Bytes covered by the signature.
+    pub fn signed_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(16 + self.public_key.len() + 8);
+        bytes.extend_from_slice(self.session_id.as_bytes());
+        bytes.extend_from_slice(&self.public_key);
+        bytes.extend_from_slice(&self.expires_at.to_be_bytes());
+        bytes
+    }
+
+    /// Determine if this token has expired at a time
+    /// in seconds since the UNIX epoch.
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Running hash of the messages relayed in a session.
+///
+/// The server cannot read the messages exchanged by the
+/// participants so the hash is chained over the encrypted
+/// messages it relays; observers can follow the progress of
+/// a session and record what was relayed without being
+/// trusted with the messages.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SessionTranscript {
+    /// Session identifier.
+    pub session_id: SessionId,
+    /// Number of messages relayed in the session.
+    pub messages: u64,
+    /// SHA-256 hash chained over the relayed messages.
+    pub hash: [u8; 32],
+}
+
+/// Statistics for a session computed by the server.
+///
+/// Sent with the session finished message so the cost of
+/// a ceremony can be recorded; only messages relayed in the
+/// context of the session are counted.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Milliseconds between creating and finishing
+    /// the session.
+    pub duration: u64,
+    /// Statistics for each participant in the order given
+    /// when the session was created.
+    pub participants: Vec<ParticipantStats>,
+}
+
+impl SessionStats {
+    /// Total number of frames relayed in the session.
+    pub fn frames(&self) -> u64 {
+        self.participants.iter().map(|p| p.frames).sum()
+    }
+
+    /// Total number of bytes relayed in the session.
+    pub fn bytes(&self) -> u64 {
+        self.participants.iter().map(|p| p.bytes).sum()
+    }
+}
+
+/// Messages relayed from a session participant.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ParticipantStats {
+    /// Public key of the participant.
+    pub public_key: Vec<u8>,
+    /// Number of frames relayed from the participant.
+    ///
+    /// A group message counts once for each recipient.
+    pub frames: u64,
+    /// Number of bytes relayed from the participant.
+    pub bytes: u64,
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Chunk;
+    use super::{
+        Chunk, OpaqueMessage, Priority, RequestMessage,
+        SealedEnvelope, TransparentMessage,
+    };
     use crate::PATTERN;
     use anyhow::Result;
 
@@ -655,4 +1116,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn message_priority() {
+        let peer = |chunks: Vec<Chunk>| {
+            RequestMessage::Opaque(OpaqueMessage::PeerMessage {
+                public_key: vec![],
+                session_id: None,
+                envelope: SealedEnvelope {
+                    chunks,
+                    ..Default::default()
+                },
+            })
+        };
+
+        assert_eq!(
+            Priority::Control,
+            RequestMessage::Transparent(TransparentMessage::Noop)
+                .priority()
+        );
+        assert_eq!(
+            Priority::Control,
+            RequestMessage::Opaque(OpaqueMessage::ServerMessage(
+                Default::default()
+            ))
+            .priority()
+        );
+        assert_eq!(
+            Priority::Round,
+            peer(vec![Chunk::default()]).priority()
+        );
+        assert_eq!(
+            Priority::Bulk,
+            peer(vec![Chunk::default(), Chunk::default()]).priority()
+        );
+        assert!(Priority::Control > Priority::Round);
+        assert!(Priority::Round > Priority::Bulk);
+    }
 }
@@ -0,0 +1,82 @@
+//! Redaction helpers for log output.
+//!
+//! Logs must be safe to ship to a log aggregator so public keys
+//! are logged as short [fingerprint]s and payloads that may
+//! contain plaintext or key material are logged as their
+//! length using [redacted].
+//!
+//! ```
+//! use polysig_protocol::{fingerprint, redacted};
+//! let key = [0xab; 32];
+//! assert_eq!("abababababab", fingerprint(&key).to_string());
+//! assert_eq!("<redacted 32 bytes>", redacted(&key).to_string());
+//! ```
+use std::fmt;
+
+/// Number of bytes of a public key included in a fingerprint.
+pub const FINGERPRINT_LEN: usize = 6;
+
+/// Short fingerprint of a public key for log output.
+#[derive(Clone, Copy)]
+pub struct Fingerprint<'a>(&'a [u8]);
+
+impl fmt::Display for Fingerprint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.0.len().min(FINGERPRINT_LEN);
+        for byte in &self.0[..len] {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Fingerprint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Fingerprint of a public key for log output.
+pub fn fingerprint(public_key: &[u8]) -> Fingerprint<'_> {
+    Fingerprint(public_key)
+}
+
+/// Bytes that must not be logged, formats as the length only.
+#[derive(Clone, Copy)]
+pub struct Redacted<'a>(&'a [u8]);
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted {} bytes>", self.0.len())
+    }
+}
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Redact a payload for log output.
+pub fn redacted(bytes: &[u8]) -> Redacted<'_> {
+    Redacted(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_short_key() {
+        assert_eq!("0102", fingerprint(&[1, 2]).to_string());
+        assert_eq!("", fingerprint(&[]).to_string());
+    }
+
+    #[test]
+    fn redacted_debug() {
+        let secret = b"plaintext";
+        let output = format!("{:?}", redacted(secret));
+        assert_eq!("<redacted 9 bytes>", output);
+        assert!(!output.contains("plaintext"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Framing for relay connections over HTTP/2 streams.
+//!
+//! Native clients may multiplex the connections for several
+//! sessions over a single HTTP/2 connection to the relay
+//! server using a stream for each connection. The request
+//! body carries the messages sent by the client and the
+//! response body the messages sent by the server; every
+//! message is the same deflated buffer sent in a websocket
+//! binary message prefixed with the length as a 32 bit
+//! big-endian integer.
+use crate::{Error, Result};
+
+/// Maximum size of a frame.
+pub const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// Size of the length prefix for a frame.
+const PREFIX: usize = 4;
+
+/// Encode a frame.
+pub fn encode_frame(buffer: &[u8]) -> Result<Vec<u8>> {
+    if buffer.len() > MAX_FRAME_SIZE {
+        return Err(Error::MaxBufferSize(MAX_FRAME_SIZE));
+    }
+    let mut frame = Vec::with_capacity(PREFIX + buffer.len());
+    frame.extend_from_slice(&(buffer.len() as u32).to_be_bytes());
+    frame.extend_from_slice(buffer);
+    Ok(frame)
+}
+
+/// Decode frames from chunks of a stream.
+///
+/// Chunks do not need to align with the frames.
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    /// Append a chunk received from the stream.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Next complete frame.
+    ///
+    /// Returns an error when the length of a frame
+    /// exceeds the maximum frame size.
+    pub fn next_frame(&mut self) -> Result<Option<Vec<u8>>> {
+        if self.buffer.len() < PREFIX {
+            return Ok(None);
+        }
+        let mut prefix = [0u8; PREFIX];
+        prefix.copy_from_slice(&self.buffer[..PREFIX]);
+        let length = u32::from_be_bytes(prefix) as usize;
+        if length > MAX_FRAME_SIZE {
+            return Err(Error::MaxBufferSize(MAX_FRAME_SIZE));
+        }
+        if self.buffer.len() < PREFIX + length {
+            return Ok(None);
+        }
+        let frame = self.buffer[PREFIX..PREFIX + length].to_vec();
+        self.buffer.drain(..PREFIX + length);
+        Ok(Some(frame))
+    }
+
+    /// Determine if a partial frame has been received.
+    pub fn is_partial(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn decode_split_frames() -> Result<()> {
+        let mut bytes = encode_frame(b"first")?;
+        bytes.extend(encode_frame(b"")?);
+        bytes.extend(encode_frame(b"second")?);
+
+        let mut decoder = FrameDecoder::default();
+        let mut frames = Vec::new();
+        for chunk in bytes.chunks(3) {
+            decoder.push(chunk);
+            while let Some(frame) = decoder.next_frame()? {
+                frames.push(frame);
+            }
+        }
+        assert!(!decoder.is_partial());
+        assert_eq!(
+            vec![b"first".to_vec(), Vec::new(), b"second".to_vec()],
+            frames
+        );
+
+        decoder.push(&u32::MAX.to_be_bytes());
+        assert!(decoder.next_frame().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! Admin endpoints.
+//!
+//! Admin requests must send the admin token from the server
+//! config as a bearer token in the authorization header; the
+//! endpoints are not available when no token is configured.
+//!
+//! Use `GET /admin/log-filter` to read the log filter and
+//! `PUT /admin/log-filter` with the filter directives in the
+//! body to change the log filter without restarting the
+//! server, for example:
+//!
+//! ```text
+//! curl -X PUT -H "Authorization: Bearer $TOKEN" \
+//!   -d "polysig_relay_server=debug" \
+//!   http://localhost:7007/admin/log-filter
+//! ```
+use axum::{
+    extract::Extension,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::{server::State, Error, Result};
+
+/// Path for the log filter endpoint.
+pub const LOG_FILTER_PATH: &str = "/admin/log-filter";
+
+/// Handle used to change the log filter at runtime.
+///
+/// Install the layer as the first layer of the tracing
+/// subscriber and assign the handle to the server, see
+/// [crate::RelayServerBuilder::log_filter].
+#[derive(Clone)]
+pub struct LogFilter(reload::Handle<EnvFilter, Registry>);
+
+impl LogFilter {
+    /// Create a log filter layer for the filter directives
+    /// and a handle to change the filter.
+    pub fn new(
+        directives: &str,
+    ) -> Result<(reload::Layer<EnvFilter, Registry>, Self)> {
+        let filter = EnvFilter::try_new(directives)
+            .map_err(|e| Error::LogFilter(e.to_string()))?;
+        let (layer, handle) = reload::Layer::new(filter);
+        Ok((layer, Self(handle)))
+    }
+
+    /// Directives for the current filter.
+    pub fn directives(&self) -> Result<String> {
+        self.0
+            .with_current(|filter| filter.to_string())
+            .map_err(|e| Error::LogFilter(e.to_string()))
+    }
+
+    /// Replace the filter.
+    pub fn set(&self, directives: &str) -> Result<()> {
+        let filter = EnvFilter::try_new(directives)
+            .map_err(|e| Error::LogFilter(e.to_string()))?;
+        self.0
+            .reload(filter)
+            .map_err(|e| Error::LogFilter(e.to_string()))
+    }
+}
+
+/// Digest of an admin token.
+pub(crate) fn token_digest(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+/// Read the log filter.
+pub async fn get_log_filter(
+    Extension(state): Extension<State>,
+    headers: HeaderMap,
+) -> std::result::Result<Response, StatusCode> {
+    let log_filter = authorize(&state, &headers).await?;
+    let directives = log_filter
+        .directives()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok((StatusCode::OK, directives).into_response())
+}
+
+/// Change the log filter.
+pub async fn set_log_filter(
+    Extension(state): Extension<State>,
+    headers: HeaderMap,
+    directives: String,
+) -> std::result::Result<Response, StatusCode> {
+    let log_filter = authorize(&state, &headers).await?;
+    match log_filter.set(directives.trim()) {
+        Ok(_) => {
+            tracing::info!(
+                directives = %directives.trim(),
+                "admin::log_filter",
+            );
+            let directives = log_filter
+                .directives()
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok((StatusCode::OK, directives).into_response())
+        }
+        Err(e) => {
+            Ok((StatusCode::BAD_REQUEST, e.to_string())
+                .into_response())
+        }
+    }
+}
+
+/// Verify the admin token for a request.
+///
+/// Returns the log filter when the request is authorized.
+async fn authorize(
+    state: &State,
+    headers: &HeaderMap,
+) -> std::result::Result<LogFilter, StatusCode> {
+    let reader = state.read().await;
+    let Some(admin_token) = &reader.admin_token else {
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    // Compare digests so the comparison does not
+    // reveal the length of the token
+    if &token_digest(token.trim()) != admin_token {
+        tracing::warn!("admin::unauthorized");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    reader.log_filter.clone().ok_or(StatusCode::NOT_FOUND)
+}
@@ -1,7 +1,13 @@
 //! Server configuration.
-use polysig_protocol::{hex, Keypair};
+use polysig_protocol::{
+    hex, snow::params::NoiseParams, Keypair, PATTERN,
+};
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use tokio::fs;
 use url::Url;
 
@@ -10,16 +16,72 @@ use crate::{Error, Result};
 /// Environment variable name for a keypair.
 const ENV_PEM: &str = "MPC_RELAY_KEYPAIR";
 
+/// Environment variable name for a secondary keypair.
+const ENV_SECONDARY_PEM: &str = "MPC_RELAY_SECONDARY_KEYPAIR";
+
+/// Environment variable name for the admin token.
+const ENV_ADMIN_TOKEN: &str = "MPC_RELAY_ADMIN_TOKEN";
+
+/// Prefix for environment variables that override settings.
+const ENV_PREFIX: &str = "MPC_RELAY_";
+
 /// Configuration for the web server.
+///
+/// Settings may be loaded from a TOML file, created with
+/// a [ServerConfigBuilder] or read from the environment.
+/// Environment variables take precedence over the values
+/// in a config file:
+///
+/// * `MPC_RELAY_KEYPAIR` - PEM encoded server keypair.
+/// * `MPC_RELAY_KEY` - Path to the server key.
+/// * `MPC_RELAY_SECONDARY_KEYPAIR` - PEM encoded secondary keypair.
+/// * `MPC_RELAY_SECONDARY_KEY` - Path to the secondary server key.
+/// * `MPC_RELAY_PATTERN` - Noise parameters pattern.
+/// * `MPC_RELAY_PATTERNS` - Comma separated noise patterns.
+/// * `MPC_RELAY_SESSION_TIMEOUT` - Session timeout in seconds.
+/// * `MPC_RELAY_SESSION_INTERVAL` - Interval to reap sessions.
+/// * `MPC_RELAY_SESSION_WAIT_INTERVAL` - Session poll interval.
+/// * `MPC_RELAY_SESSION_WAIT_TIMEOUT` - Session wait timeout.
+/// * `MPC_RELAY_SESSION_TOKEN_TTL` - Session token lifetime.
+/// * `MPC_RELAY_TLS_CERT` - Path to the TLS certificate.
+/// * `MPC_RELAY_TLS_KEY` - Path to the TLS certificate key.
+/// * `MPC_RELAY_ALLOW` - Comma separated hex public keys.
+/// * `MPC_RELAY_DENY` - Comma separated hex public keys.
+/// * `MPC_RELAY_CORS_ORIGINS` - Comma separated CORS origins.
+/// * `MPC_RELAY_HTTP2` - Accept connections over HTTP/2 streams.
+/// * `MPC_RELAY_ADMIN_TOKEN` - Token for admin requests.
+/// * `MPC_RELAY_ADMIN_TOKEN_FILE` - Path to the admin token.
 #[derive(Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ServerConfig {
     /// Path to the server key.
     pub key: PathBuf,
 
+    /// Path to a secondary server key.
+    ///
+    /// Handshakes against the secondary key are also accepted
+    /// so the server key can be rotated without updating every
+    /// client at once; set the new key as the server key and
+    /// the previous key as the secondary key.
+    ///
+    /// Only patterns that encrypt the first handshake message
+    /// to the server key fall back to the secondary key; clients
+    /// using patterns where the server transmits its static key,
+    /// such as `XX`, always receive the new key and must update
+    /// a pinned key before connecting.
+    pub secondary_key: Option<PathBuf>,
+
     /// Optional noise parameters pattern.
     pub pattern: Option<String>,
 
+    /// Additional noise parameters patterns that clients
+    /// may request when connecting.
+    ///
+    /// For example, allow `Noise_IK_25519_ChaChaPoly_BLAKE2s`
+    /// so clients that know the server public key
+    /// authenticate in the first handshake message.
+    pub patterns: Vec<String>,
+
     /// Settings for session management.
     pub session: SessionConfig,
 
@@ -36,9 +98,34 @@ pub struct ServerConfig {
 
     /// Configuration for CORS.
     pub cors: CorsConfig,
+
+    /// Accept relay connections over HTTP/2 streams.
+    ///
+    /// Native clients may multiplex the connections for
+    /// several sessions over a single HTTP/2 connection
+    /// instead of opening a websocket for each session.
+    pub http2: bool,
+
+    /// Path to a file containing the token for admin requests.
+    ///
+    /// The admin endpoints are only available when a token
+    /// is configured.
+    pub admin_token_file: Option<PathBuf>,
 }
 
 impl ServerConfig {
+    /// Noise parameters pattern used when a client does not
+    /// request a pattern.
+    pub fn default_pattern(&self) -> &str {
+        self.pattern.as_deref().unwrap_or(PATTERN)
+    }
+
+    /// Determine if a client may request a pattern.
+    pub fn is_allowed_pattern(&self, pattern: &str) -> bool {
+        self.default_pattern() == pattern
+            || self.patterns.iter().any(|p| p == pattern)
+    }
+
     /// Determine if a public key is allowed access.
     pub fn is_allowed_access(&self, key: impl AsRef<[u8]>) -> bool {
         //let restricted = self.allow.is_some() || self.deny.is_some();
@@ -60,6 +147,7 @@ impl ServerConfig {
     }
 }
 
+/// Public key for access control.
 #[derive(Default, Serialize, Deserialize)]
 pub struct AccessKey {
     #[serde(with = "hex::serde")]
@@ -77,7 +165,7 @@ pub struct TlsConfig {
 
 /// Configuration for server sessions.
 #[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(default, rename_all = "kebab-case")]
 pub struct SessionConfig {
     /// Timeout for sessions in seconds.
     ///
@@ -107,6 +195,21 @@ pub struct SessionConfig {
     ///
     /// Default is 5 minutes.
     pub wait_timeout: u64,
+
+    /// Lifetime in seconds of the tokens issued to session
+    /// participants to resume a session after reconnecting.
+    ///
+    /// Default is 10 minutes.
+    pub token_ttl: u64,
+
+    /// Compute the digest of the messages relayed in every
+    /// session so participants can request the digest.
+    ///
+    /// The digest is always computed for sessions
+    /// with observers.
+    ///
+    /// Default is false.
+    pub digest: bool,
 }
 
 impl Default for SessionConfig {
@@ -116,12 +219,23 @@ impl Default for SessionConfig {
             interval: 900,
             wait_interval: 15,
             wait_timeout: 300,
+            token_ttl: 600,
+            digest: false,
         }
     }
 }
 
 impl ServerConfig {
+    /// Create a builder for a server config.
+    pub fn builder() -> ServerConfigBuilder {
+        Default::default()
+    }
+
     /// Load a server config from a file path.
+    ///
+    /// Relative paths in the file are resolved against the
+    /// directory containing the file and environment
+    /// variables override the values in the file.
     pub async fn load<P: AsRef<Path>>(
         path: P,
     ) -> Result<(Self, Keypair)> {
@@ -132,49 +246,198 @@ impl ServerConfig {
         let contents = fs::read_to_string(path.as_ref()).await?;
         let mut config: ServerConfig = toml::from_str(&contents)?;
 
-        if config.session.interval <= config.session.timeout {
+        let dir = Self::directory(path.as_ref())?;
+        if config.key != PathBuf::default()
+            && config.key.is_relative()
+        {
+            config.key = dir.join(&config.key);
+        }
+        if let Some(file) = config.admin_token_file.as_mut() {
+            if file.is_relative() {
+                *file = dir.join(&*file);
+            }
+        }
+        if let Some(key) = config.secondary_key.as_mut() {
+            if key.is_relative() {
+                *key = dir.join(&*key);
+            }
+        }
+
+        if let Some(tls) = config.tls.as_mut() {
+            if tls.cert.is_relative() {
+                tls.cert = dir.join(&tls.cert).canonicalize()?;
+            }
+            if tls.key.is_relative() {
+                tls.key = dir.join(&tls.key).canonicalize()?;
+            }
+        }
+
+        config.apply_env()?;
+        config.validate()?;
+        let keypair = config.keypair().await?;
+        Ok((config, keypair))
+    }
+
+    /// Load a server config from environment variables.
+    ///
+    /// Settings that are not set in the environment use
+    /// the default values.
+    pub async fn from_env() -> Result<(Self, Keypair)> {
+        let mut config = ServerConfig::default();
+        config.apply_env()?;
+        config.validate()?;
+        let keypair = config.keypair().await?;
+        Ok((config, keypair))
+    }
+
+    /// Override settings with values from environment variables.
+    pub fn apply_env(&mut self) -> Result<()> {
+        if let Some(key) = env_var("KEY") {
+            self.key = key.into();
+        }
+        if let Some(key) = env_var("SECONDARY_KEY") {
+            self.secondary_key = Some(key.into());
+        }
+        if let Some(pattern) = env_var("PATTERN") {
+            self.pattern = Some(pattern);
+        }
+        if let Some(patterns) = env_list("PATTERNS")? {
+            self.patterns = patterns;
+        }
+        if let Some(timeout) = env_parse("SESSION_TIMEOUT")? {
+            self.session.timeout = timeout;
+        }
+        if let Some(interval) = env_parse("SESSION_INTERVAL")? {
+            self.session.interval = interval;
+        }
+        if let Some(interval) = env_parse("SESSION_WAIT_INTERVAL")? {
+            self.session.wait_interval = interval;
+        }
+        if let Some(timeout) = env_parse("SESSION_WAIT_TIMEOUT")? {
+            self.session.wait_timeout = timeout;
+        }
+        if let Some(ttl) = env_parse("SESSION_TOKEN_TTL")? {
+            self.session.token_ttl = ttl;
+        }
+        if let Some(digest) = env_parse("SESSION_DIGEST")? {
+            self.session.digest = digest;
+        }
+        if let Some(cert) = env_var("TLS_CERT") {
+            self.tls.get_or_insert_with(Default::default).cert =
+                cert.into();
+        }
+        if let Some(key) = env_var("TLS_KEY") {
+            self.tls.get_or_insert_with(Default::default).key =
+                key.into();
+        }
+        if let Some(allow) = env_list("ALLOW")? {
+            self.allow = Some(allow);
+        }
+        if let Some(deny) = env_list("DENY")? {
+            self.deny = Some(deny);
+        }
+        if let Some(origins) = env_list("CORS_ORIGINS")? {
+            self.cors.origins = origins;
+        }
+        if let Some(http2) = env_parse("HTTP2")? {
+            self.http2 = http2;
+        }
+        if let Some(file) = env_var("ADMIN_TOKEN_FILE") {
+            self.admin_token_file = Some(file.into());
+        }
+        Ok(())
+    }
+
+    /// Validate the settings.
+    pub fn validate(&self) -> Result<()> {
+        if self.session.interval <= self.session.timeout {
             return Err(Error::SessionTimeoutConfig);
         }
 
-        if config.session.wait_timeout <= config.session.wait_interval
-        {
+        if self.session.wait_timeout <= self.session.wait_interval {
             return Err(Error::SessionWaitConfig);
         }
 
-        let dir = Self::directory(path.as_ref())?;
+        for pattern in std::iter::once(self.default_pattern())
+            .chain(self.patterns.iter().map(|p| &p[..]))
+        {
+            if pattern.parse::<NoiseParams>().is_err() {
+                return Err(Error::InvalidPattern(
+                    pattern.to_owned(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
 
+    /// Load the server keypair.
+    ///
+    /// A keypair in the environment takes precedence
+    /// over the key file.
+    pub(crate) async fn keypair(&self) -> Result<Keypair> {
         let contents = if let Ok(env_keypair) = std::env::var(ENV_PEM)
         {
             tracing::info!("use environmet keypair PEM");
             env_keypair.to_owned()
         } else {
-            if config.key == PathBuf::default() {
+            if self.key == PathBuf::default() {
                 return Err(Error::KeyFileRequired);
             }
 
-            if config.key.is_relative() {
-                config.key = dir.join(&config.key).canonicalize()?;
-            }
-
-            if !fs::try_exists(&config.key).await? {
-                return Err(Error::KeyNotFound(config.key.clone()));
+            if !fs::try_exists(&self.key).await? {
+                return Err(Error::KeyNotFound(self.key.clone()));
             }
 
-            fs::read_to_string(&config.key).await?
+            fs::read_to_string(&self.key).await?
         };
 
-        let keypair = Keypair::decode_pem(contents)?;
+        Ok(Keypair::decode_pem(contents)?)
+    }
 
-        if let Some(tls) = config.tls.as_mut() {
-            if tls.cert.is_relative() {
-                tls.cert = dir.join(&tls.cert).canonicalize()?;
+    /// Load the secondary server keypair.
+    ///
+    /// A keypair in the environment takes precedence
+    /// over the secondary key file.
+    pub async fn secondary_keypair(&self) -> Result<Option<Keypair>> {
+        let contents = if let Ok(env_keypair) =
+            std::env::var(ENV_SECONDARY_PEM)
+        {
+            env_keypair
+        } else if let Some(key) = &self.secondary_key {
+            if !fs::try_exists(key).await? {
+                return Err(Error::KeyNotFound(key.clone()));
             }
-            if tls.key.is_relative() {
-                tls.key = dir.join(&tls.key).canonicalize()?;
+            fs::read_to_string(key).await?
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(Keypair::decode_pem(contents)?))
+    }
+
+    /// Load the token for admin requests.
+    ///
+    /// A token in the environment takes precedence
+    /// over the token file.
+    pub(crate) async fn admin_token(&self) -> Result<Option<String>> {
+        let token = if let Ok(token) = std::env::var(ENV_ADMIN_TOKEN)
+        {
+            token
+        } else if let Some(file) = &self.admin_token_file {
+            if !fs::try_exists(file).await? {
+                return Err(Error::NotFile(file.clone()));
             }
-        }
+            fs::read_to_string(file).await?
+        } else {
+            return Ok(None);
+        };
 
-        Ok((config, keypair))
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(Error::AdminTokenEmpty);
+        }
+        Ok(Some(token.to_owned()))
     }
 
     /// Parent directory of the configuration file.
@@ -186,6 +449,204 @@ impl ServerConfig {
     }
 }
 
+/// Builder for a server config.
+#[derive(Default)]
+pub struct ServerConfigBuilder {
+    config: ServerConfig,
+}
+
+impl ServerConfigBuilder {
+    /// Set the path to the server key.
+    pub fn key(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.key = path.into();
+        self
+    }
+
+    /// Set the path to the secondary server key.
+    pub fn secondary_key(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.secondary_key = Some(path.into());
+        self
+    }
+
+    /// Set the noise parameters pattern.
+    pub fn pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.config.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Allow clients to request an additional noise
+    /// parameters pattern.
+    pub fn allow_pattern(
+        mut self,
+        pattern: impl Into<String>,
+    ) -> Self {
+        self.config.patterns.push(pattern.into());
+        self
+    }
+
+    /// Set the session timeout in seconds.
+    pub fn session_timeout(mut self, timeout: u64) -> Self {
+        self.config.session.timeout = timeout;
+        self
+    }
+
+    /// Set the interval in seconds to reap expired sessions.
+    pub fn session_interval(mut self, interval: u64) -> Self {
+        self.config.session.interval = interval;
+        self
+    }
+
+    /// Set the interval in seconds to poll session states.
+    pub fn session_wait_interval(mut self, interval: u64) -> Self {
+        self.config.session.wait_interval = interval;
+        self
+    }
+
+    /// Set the timeout in seconds when waiting for sessions
+    /// to become active.
+    pub fn session_wait_timeout(mut self, timeout: u64) -> Self {
+        self.config.session.wait_timeout = timeout;
+        self
+    }
+
+    /// Set the lifetime in seconds of session tokens.
+    pub fn session_token_ttl(mut self, ttl: u64) -> Self {
+        self.config.session.token_ttl = ttl;
+        self
+    }
+
+    /// Compute the digest of the messages relayed
+    /// in every session.
+    pub fn session_digest(mut self, digest: bool) -> Self {
+        self.config.session.digest = digest;
+        self
+    }
+
+    /// Enable TLS with a certificate and key.
+    pub fn tls(
+        mut self,
+        cert: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+    ) -> Self {
+        self.config.tls = Some(TlsConfig {
+            cert: cert.into(),
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Allow access to a client public key.
+    pub fn allow(mut self, public_key: impl Into<Vec<u8>>) -> Self {
+        self.config.allow.get_or_insert_with(Vec::new).push(
+            AccessKey {
+                public_key: public_key.into(),
+            },
+        );
+        self
+    }
+
+    /// Deny access to a client public key.
+    pub fn deny(mut self, public_key: impl Into<Vec<u8>>) -> Self {
+        self.config.deny.get_or_insert_with(Vec::new).push(
+            AccessKey {
+                public_key: public_key.into(),
+            },
+        );
+        self
+    }
+
+    /// Add a CORS origin.
+    pub fn cors_origin(mut self, origin: Url) -> Self {
+        self.config.cors.origins.push(origin);
+        self
+    }
+
+    /// Accept relay connections over HTTP/2 streams.
+    pub fn http2(mut self, http2: bool) -> Self {
+        self.config.http2 = http2;
+        self
+    }
+
+    /// Set the path to the token for admin requests.
+    pub fn admin_token_file(
+        mut self,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.config.admin_token_file = Some(path.into());
+        self
+    }
+
+    /// Override settings with values from environment variables.
+    pub fn env(mut self) -> Result<Self> {
+        self.config.apply_env()?;
+        Ok(self)
+    }
+
+    /// Validate the settings and build the server config.
+    pub fn build(self) -> Result<ServerConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+impl FromStr for AccessKey {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self {
+            public_key: hex::decode(s)?,
+        })
+    }
+}
+
+/// Read a setting from the environment.
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(format!("{}{}", ENV_PREFIX, name)).ok()
+}
+
+/// Parse a setting from the environment.
+fn env_parse<T>(name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    env_var(name)
+        .map(|value| parse(name, value.trim()))
+        .transpose()
+}
+
+/// Parse a comma separated list from the environment.
+fn env_list<T>(name: &str) -> Result<Option<Vec<T>>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    env_var(name)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| parse(name, item))
+                .collect()
+        })
+        .transpose()
+}
+
+/// Parse an environment variable value.
+fn parse<T>(name: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| {
+        Error::EnvVar(
+            format!("{}{}", ENV_PREFIX, name),
+            e.to_string(),
+        )
+    })
+}
+
 /// Configuration for CORS.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CorsConfig {
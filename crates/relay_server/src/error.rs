@@ -45,6 +45,31 @@ pub enum Error {
     #[error("session owner is not participant")]
     SessionOwnerNotParticipant,
 
+    /// Error generated when a session observer is also in the
+    /// list of participants.
+    #[error("session observer is participant")]
+    SessionObserverParticipant,
+
+    /// Error generated when the parameters for a new session
+    /// are not valid for the session participants.
+    #[error("invalid session parameters: {0}")]
+    SessionParameters(#[source] polysig_protocol::Error),
+
+    /// Error generated when the deadline for a new session
+    /// has already passed.
+    #[error("session deadline {0} has passed")]
+    SessionDeadline(u64),
+
+    /// Error generated when an observer of a session attempts
+    /// to send a message in the context of the session.
+    #[error(r#"observer of session "{0}" is read-only"#)]
+    SessionObserverReadOnly(SessionId),
+
+    /// Error generated when the digest is requested for a
+    /// session and the server does not compute the digest.
+    #[error(r#"digest for session "{0}" is not available"#)]
+    SessionDigestUnavailable(SessionId),
+
     /// Error generated when a meeting could not be found.
     #[error(r#"meeting "{0}" not found"#)]
     MeetingNotFound(MeetingId),
@@ -63,6 +88,15 @@ pub enum Error {
     #[error(r#"session "{0}" does not have participant "{1}""#)]
     NotSessionParticipant(SessionId, String),
 
+    /// Error generated when a session token signature is invalid
+    /// or the token was issued to another participant.
+    #[error("invalid session token")]
+    InvalidSessionToken,
+
+    /// Error generated when a session token has expired.
+    #[error(r#"token for session "{0}" has expired"#)]
+    SessionTokenExpired(SessionId),
+
     /// Error generated when the session timeout is not greater
     /// than the interval.
     #[error("session timeout must be greater than the interval")]
@@ -75,6 +109,36 @@ pub enum Error {
     )]
     SessionWaitConfig,
 
+    /// Error generated when a noise parameters pattern in
+    /// the config is invalid.
+    #[error(r#"invalid noise pattern "{0}""#)]
+    InvalidPattern(String),
+
+    /// Error generated when the static key sent during the
+    /// server handshake does not match the connection public key.
+    #[error("handshake static key does not match public key")]
+    PublicKeyMismatch,
+
+    /// Error generated when a connection for a public key is
+    /// already active and answers pings and the server handshake
+    /// does not prove the client holds the key.
+    #[error("connection for public key already exists")]
+    ConnectionExists,
+
+    /// Error generated when the admin token is empty.
+    #[error("admin token must not be empty")]
+    AdminTokenEmpty,
+
+    /// Error generated when log filter directives are invalid
+    /// or the filter could not be changed.
+    #[error("log filter: {0}")]
+    LogFilter(String),
+
+    /// Error generated when an environment variable has
+    /// an invalid value.
+    #[error(r#"invalid value for environment variable "{0}": {1}"#)]
+    EnvVar(String, String),
+
     /// Error generated by input/output.
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -5,15 +5,22 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+mod admin;
 mod config;
 mod error;
 mod server;
 mod service;
+mod stream;
+mod token;
 mod websocket;
 
-pub use config::ServerConfig;
+pub use admin::{LogFilter, LOG_FILTER_PATH};
+pub use config::{
+    AccessKey, CorsConfig, ServerConfig, ServerConfigBuilder,
+    SessionConfig, TlsConfig,
+};
 pub use error::Error;
-pub use server::RelayServer;
+pub use server::{EmbedHandle, RelayServer, RelayServerBuilder};
 
 pub use axum;
 
@@ -2,28 +2,33 @@ use futures::StreamExt;
 use std::{
     collections::HashMap, net::SocketAddr, sync::Arc, time::Duration,
 };
-use tokio::sync::RwLock;
+use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_stream::wrappers::IntervalStream;
 
 use axum::{
     extract::Extension,
     http::{HeaderValue, Method, StatusCode},
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{get, post},
     Router,
 };
 use axum_server::{tls_rustls::RustlsConfig, Handle};
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use uuid::Uuid;
 
-use polysig_protocol::{hex, uuid, Keypair, SessionManager};
+use polysig_protocol::{
+    fingerprint, hex, uuid, Keypair, SessionManager, STREAM_PATH,
+};
 
 use crate::{
+    admin::{token_digest, LogFilter, LOG_FILTER_PATH},
     config::{ServerConfig, TlsConfig},
     Result,
 };
 
-use crate::{service::RelayService, websocket::Connection};
+use crate::{
+    service::RelayService, token::TokenSigner, websocket::Connection,
+};
 
 pub type State = Arc<RwLock<ServerState>>;
 pub(crate) type Service = Arc<RelayService>;
@@ -46,10 +51,25 @@ async fn purge_expired(state: State, interval_secs: u64) {
     }
 }
 
+/// Handle for a server embedded in another application.
+///
+/// Stops reaping expired sessions when dropped.
+pub struct EmbedHandle(JoinHandle<()>);
+
+impl Drop for EmbedHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 pub struct ServerState {
     /// Server keypair.
     pub(crate) keypair: Keypair,
 
+    /// Secondary keypair accepted whilst rotating the
+    /// server key.
+    pub(crate) secondary_keypair: Option<Keypair>,
+
     /// Server config.
     pub(crate) config: ServerConfig,
 
@@ -63,6 +83,15 @@ pub struct ServerState {
 
     /// Session manager.
     pub(crate) sessions: SessionManager,
+
+    /// Issues and verifies session tokens.
+    pub(crate) tokens: TokenSigner,
+
+    /// Digest of the token for admin requests.
+    pub(crate) admin_token: Option<[u8; 32]>,
+
+    /// Handle to change the log filter.
+    pub(crate) log_filter: Option<LogFilter>,
 }
 
 /// Relay web server.
@@ -70,20 +99,142 @@ pub struct RelayServer {
     state: State,
 }
 
+/// Builder for a relay server.
+#[derive(Default)]
+pub struct RelayServerBuilder {
+    config: ServerConfig,
+    keypair: Option<Keypair>,
+    secondary_keypair: Option<Keypair>,
+    log_filter: Option<LogFilter>,
+}
+
+impl RelayServerBuilder {
+    /// Set the server config.
+    pub fn config(mut self, config: ServerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the server keypair.
+    ///
+    /// When no keypair is given it is read from the
+    /// environment or the key file in the server config.
+    pub fn keypair(mut self, keypair: Keypair) -> Self {
+        self.keypair = Some(keypair);
+        self
+    }
+
+    /// Set the secondary server keypair.
+    ///
+    /// Handshakes against either keypair are accepted so the
+    /// server key can be rotated; clients that handshake against
+    /// the secondary keypair are sent the primary public key.
+    /// Session tokens issued with the secondary keypair are
+    /// also accepted.
+    ///
+    /// Only patterns where the client encrypts the first
+    /// handshake message to the server key (`NK`, `KK`, `XK`
+    /// and `IK`) fall back to the secondary keypair. For patterns
+    /// where the server transmits its static key (`XX`, `NX` and
+    /// `IX`) the server can not tell which key a client expects
+    /// so the primary key is always sent; clients that pinned the
+    /// previous key must pin the primary key before connecting.
+    ///
+    /// When no secondary keypair is given it is read from the
+    /// environment or the secondary key file in the server
+    /// config if either is set.
+    pub fn secondary_keypair(mut self, keypair: Keypair) -> Self {
+        self.secondary_keypair = Some(keypair);
+        self
+    }
+
+    /// Set the handle used to change the log filter.
+    ///
+    /// The log filter may be changed by admin requests when
+    /// an admin token is configured.
+    pub fn log_filter(mut self, log_filter: LogFilter) -> Self {
+        self.log_filter = Some(log_filter);
+        self
+    }
+
+    /// Override settings with values from environment variables.
+    pub fn env(mut self) -> Result<Self> {
+        self.config.apply_env()?;
+        Ok(self)
+    }
+
+    /// Validate the settings and build the relay server.
+    pub async fn build(self) -> Result<RelayServer> {
+        self.config.validate()?;
+        let keypair = match self.keypair {
+            Some(keypair) => keypair,
+            None => self.config.keypair().await?,
+        };
+        let secondary_keypair = match self.secondary_keypair {
+            Some(keypair) => Some(keypair),
+            None => self.config.secondary_keypair().await?,
+        };
+        let admin_token = self.config.admin_token().await?;
+        let server = RelayServer::new(self.config, keypair);
+        {
+            let mut writer = server.state.write().await;
+            writer.tokens.set_secondary(secondary_keypair.as_ref());
+            writer.secondary_keypair = secondary_keypair;
+            writer.admin_token =
+                admin_token.as_deref().map(token_digest);
+            writer.log_filter = self.log_filter;
+        }
+        Ok(server)
+    }
+}
+
 impl RelayServer {
+    /// Create a builder for a relay server.
+    pub fn builder() -> RelayServerBuilder {
+        Default::default()
+    }
+
     /// Create a new relay server.
     pub fn new(config: ServerConfig, keypair: Keypair) -> Self {
+        let tokens =
+            TokenSigner::new(&keypair, config.session.token_ttl);
         Self {
             state: Arc::new(RwLock::new(ServerState {
                 keypair,
+                secondary_keypair: None,
                 config,
                 pending: Default::default(),
                 active: Default::default(),
                 sessions: Default::default(),
+                tokens,
+                admin_token: None,
+                log_filter: None,
             })),
         }
     }
 
+    /// Create a router to embed the server in another
+    /// axum application.
+    ///
+    /// The router is not bound to a listener so it can be
+    /// merged with the routes of the application to share the
+    /// same port and middleware; TLS settings in the config
+    /// are ignored as the application owns the listener.
+    ///
+    /// Expired sessions are reaped until the handle is dropped.
+    pub async fn embed(&self) -> Result<(Router, EmbedHandle)> {
+        let interval = {
+            let reader = self.state.read().await;
+            reader.config.session.interval
+        };
+        let app = self.router(Arc::clone(&self.state)).await?;
+        let handle = EmbedHandle(tokio::task::spawn(purge_expired(
+            Arc::clone(&self.state),
+            interval,
+        )));
+        Ok((app, handle))
+    }
+
     /// Start the server.
     pub async fn start(
         &self,
@@ -123,7 +274,7 @@ impl RelayServer {
             reader.keypair.public_key().to_vec()
         };
         tracing::info!("listening on {}", addr);
-        tracing::info!("public key {}", hex::encode(&public_key));
+        tracing::info!("public key {}", fingerprint(&public_key));
         axum_server::bind_rustls(addr, tls)
             .handle(handle)
             .serve(app.into_make_service())
@@ -143,7 +294,7 @@ impl RelayServer {
             reader.keypair.public_key().to_vec()
         };
         tracing::info!("listening on {}", addr);
-        tracing::info!("public key {}", hex::encode(&public_key));
+        tracing::info!("public key {}", fingerprint(&public_key));
         axum_server::bind(addr)
             .handle(handle)
             .serve(app.into_make_service())
@@ -170,10 +321,25 @@ impl RelayServer {
             //.expose_headers(vec![])
             .allow_origin(origins);
 
+        let (http2, admin) = {
+            let reader = state.read().await;
+            (reader.config.http2, reader.admin_token.is_some())
+        };
+
         let service = Arc::new(RelayService::new(Arc::clone(&state)));
         let mut app = Router::new()
             .route("/", get(crate::websocket::upgrade))
             .route("/public-key", get(public_key));
+        if http2 {
+            app = app.route(STREAM_PATH, post(crate::stream::stream));
+        }
+        if admin {
+            app = app.route(
+                LOG_FILTER_PATH,
+                get(crate::admin::get_log_filter)
+                    .put(crate::admin::set_log_filter),
+            );
+        }
         app = app
             .layer(cors)
             .layer(TraceLayer::new_for_http())
@@ -1,20 +1,26 @@
 use axum::http::StatusCode;
 use futures::StreamExt;
+use sha2::{Digest, Sha256};
 use std::{
     sync::Arc,
-    time::{Duration, SystemTime},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::IntervalStream;
 
 use polysig_protocol::{
     channel::{decrypt_server_channel, encrypt_server_channel},
-    decode, encode, hex, Encoding, HandshakeMessage, OpaqueMessage,
-    ProtocolState, RequestMessage, ResponseMessage, ServerMessage,
-    SessionState, TransparentMessage,
+    decode, encode, fingerprint, hex, Encoding, HandshakeMessage,
+    OpaqueMessage, ProtocolState, RequestMessage, ResponseMessage,
+    ServerMessage, SessionId, SessionState, SessionToken,
+    TransparentMessage,
 };
 
-use crate::{server::State, websocket::Connection, Error, Result};
+use crate::{
+    server::State,
+    websocket::{is_alive, reject, supersede, Connection},
+    Error, Result,
+};
 
 pub struct RelayService {
     state: State,
@@ -73,16 +79,49 @@ async fn handle_request(
             ),
         ) => {
             let mut writer = conn.write().await;
-            let (len, payload) = match &mut writer.state {
+            let public_key = writer.public_key.clone();
+            let fallback = writer.fallback.take();
+            // Patterns that transmit the initiator static key
+            // prove the client holds the public key
+            let mut verified = false;
+            let (len, payload, early, rotated) = match &mut writer
+                .state
+            {
                 Some(ProtocolState::Handshake(responder)) => {
                     let mut reply = vec![0u8; 1024];
                     let mut read_buf = vec![0u8; 1024];
-                    responder
-                        .read_message(&buf[..len], &mut read_buf)?;
+
+                    // Clients that have not updated the server key
+                    // handshake against the secondary key
+                    let (read, rotated) = match responder
+                        .read_message(&buf[..len], &mut read_buf)
+                    {
+                        Ok(read) => (read, false),
+                        Err(e) => {
+                            let Some(mut fallback) = fallback else {
+                                return Err(e.into());
+                            };
+                            let read = fallback.read_message(
+                                &buf[..len],
+                                &mut read_buf,
+                            )?;
+                            *responder = fallback;
+                            (read, true)
+                        }
+                    };
+                    if let Some(remote) =
+                        responder.get_remote_static()
+                    {
+                        if remote != public_key {
+                            return Err(Error::PublicKeyMismatch);
+                        }
+                        verified = true;
+                    }
+                    read_buf.truncate(read);
                     let len =
                         responder.write_message(&[], &mut reply)?;
 
-                    (len, reply)
+                    (len, reply, read_buf, rotated)
                 }
                 _ => return Err(Error::NotHandshakeState),
             };
@@ -108,8 +147,40 @@ async fn handle_request(
             drop(writer);
 
             // Now move from pending to transport active
-            promote_connection(Arc::clone(&state), Arc::clone(&conn))
-                .await;
+            if let Err(error) = promote_connection(
+                Arc::clone(&state),
+                Arc::clone(&conn),
+                verified,
+            )
+            .await
+            {
+                handle_error(Arc::clone(&conn), error).await?;
+                reject(conn).await;
+                return Ok(());
+            }
+
+            // Advertise the primary key to clients that used
+            // the secondary key
+            if rotated {
+                let server_key = {
+                    let reader = state.read().await;
+                    reader.keypair.public_key().to_vec()
+                };
+                let message = ServerMessage::ServerKey(server_key);
+                send_message(Arc::clone(&conn), &message, false)
+                    .await?;
+            }
+
+            // Zero round trip patterns may carry a session token
+            // to resume a session in the first handshake message
+            if !early.is_empty() {
+                let token: SessionToken = decode(&early).await?;
+                if let Some(message) =
+                    resume_session(state, &public_key, token).await?
+                {
+                    send_message(conn, &message, true).await?;
+                }
+            }
         }
         RequestMessage::Transparent(
             TransparentMessage::PeerHandshake {
@@ -122,8 +193,16 @@ async fn handle_request(
                 reader.public_key.clone()
             };
 
+            // Observers cannot connect to peers outside of the
+            // sessions they participate in
             let peer = {
                 let reader = state.read().await;
+                if let Some(id) = reader
+                    .sessions
+                    .observed_session(&from_public_key, &public_key)
+                {
+                    return Err(Error::SessionObserverReadOnly(id));
+                }
                 reader.active.get(&public_key).map(Arc::clone)
             };
 
@@ -131,8 +210,8 @@ async fn handle_request(
                 let mut writer = peer.write().await;
 
                 tracing::debug!(
-                    to = ?hex::encode(&public_key),
-                    from = ?hex::encode(&from_public_key),
+                    to = %fingerprint(&public_key),
+                    from = %fingerprint(&from_public_key),
                     "relay",
                 );
 
@@ -156,6 +235,11 @@ async fn handle_request(
             session_id,
             envelope,
         }) => {
+            let from_public_key = {
+                let reader = conn.read().await;
+                reader.public_key.clone()
+            };
+
             // When we have a session identifier check the session
             // is valid and the target peer is a session participant.
             if let Some(id) = session_id {
@@ -163,6 +247,12 @@ async fn handle_request(
                 if let Some(session) =
                     writer.sessions.touch_session(&id)
                 {
+                    if session.is_observer(&from_public_key) {
+                        return Err(Error::SessionObserverReadOnly(
+                            id,
+                        ));
+                    }
+
                     let public_keys = session.public_keys();
                     let is_participant = public_keys
                         .into_iter()
@@ -177,13 +267,18 @@ async fn handle_request(
                 } else {
                     return Err(Error::SessionNotFound(id));
                 }
+            } else {
+                // Messages without a session are not recorded in
+                // a transcript so observers must give a session
+                let reader = state.read().await;
+                if let Some(id) = reader
+                    .sessions
+                    .observed_session(&from_public_key, &public_key)
+                {
+                    return Err(Error::SessionObserverReadOnly(id));
+                }
             }
 
-            let from_public_key = {
-                let reader = conn.read().await;
-                reader.public_key.clone()
-            };
-
             let peer = {
                 let reader = state.read().await;
                 reader.active.get(&public_key).map(Arc::clone)
@@ -193,8 +288,8 @@ async fn handle_request(
                 let mut writer = peer.write().await;
 
                 tracing::debug!(
-                    to = ?hex::encode(&public_key),
-                    from = ?hex::encode(&from_public_key),
+                    to = %fingerprint(&public_key),
+                    from = %fingerprint(&from_public_key),
                     "relay",
                 );
 
@@ -206,14 +301,103 @@ async fn handle_request(
                     },
                 );
 
+                let priority = relayed.priority();
                 let buffer = encode(&relayed).await?;
-                writer.send(buffer).await?;
+                writer
+                    .send_priority(buffer.clone(), priority)
+                    .await?;
+                drop(writer);
+
+                if let Some(id) = session_id {
+                    record_relayed(
+                        state,
+                        id,
+                        &from_public_key,
+                        1,
+                        &buffer,
+                    )
+                    .await?;
+                }
             } else {
                 return Err(Error::PeerNotFound(hex::encode(
                     public_key,
                 )));
             }
         }
+        RequestMessage::Opaque(OpaqueMessage::GroupMessage {
+            session_id,
+            envelope,
+            ..
+        }) => {
+            let from_public_key = {
+                let reader = conn.read().await;
+                reader.public_key.clone()
+            };
+
+            let recipients = {
+                let mut writer = state.write().await;
+                if let Some(session) =
+                    writer.sessions.touch_session(&session_id)
+                {
+                    let all_participants = session.all_participants();
+                    if !all_participants
+                        .iter()
+                        .any(|k| k == &from_public_key)
+                    {
+                        return Err(Error::NotSessionParticipant(
+                            session_id,
+                            hex::encode(&from_public_key),
+                        ));
+                    }
+                    all_participants
+                        .iter()
+                        .filter(|k| *k != &from_public_key)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                } else {
+                    return Err(Error::SessionNotFound(session_id));
+                }
+            };
+
+            tracing::debug!(
+                from = %fingerprint(&from_public_key),
+                session_id = %session_id,
+                recipients = %recipients.len(),
+                "relay_group",
+            );
+
+            // Encode once and fan out to the other participants
+            let relayed = ResponseMessage::Opaque(
+                OpaqueMessage::GroupMessage {
+                    public_key: from_public_key.clone(),
+                    session_id,
+                    envelope,
+                },
+            );
+            let priority = relayed.priority();
+            let buffer = encode(&relayed).await?;
+            let mut frames = 0;
+            {
+                let reader = state.read().await;
+                for key in &recipients {
+                    if let Some(peer) = reader.active.get(key) {
+                        let mut writer = peer.write().await;
+                        writer
+                            .send_priority(buffer.clone(), priority)
+                            .await?;
+                        frames += 1;
+                    }
+                }
+            }
+            record_relayed(
+                state,
+                session_id,
+                &from_public_key,
+                frames,
+                &buffer,
+            )
+            .await?;
+        }
         RequestMessage::Opaque(OpaqueMessage::ServerMessage(
             envelope,
         )) => {
@@ -256,6 +440,12 @@ async fn handle_request(
                 )));
             }
         }
+        // Reply to keepalive pings
+        RequestMessage::Noop => {
+            let mut writer = conn.write().await;
+            let buffer = encode(&ResponseMessage::Noop).await?;
+            writer.send(buffer).await?;
+        }
         _ => {}
     }
     Ok(())
@@ -274,6 +464,14 @@ async fn wait_for_session_ready(
     while stream.next().await.is_some() {
         let (ready, wait_timeout) = {
             let reader = state.read().await;
+            // Session was removed, eg: the deadline was reached
+            if reader
+                .sessions
+                .get_session(&session.session_id)
+                .is_none()
+            {
+                break;
+            }
             (
                 session.all_participants.iter().all(|public_key| {
                     reader.active.get(public_key).is_some()
@@ -319,13 +517,25 @@ async fn notify_session_ready(
     state: State,
     session: SessionState,
 ) -> Result<()> {
-    let public_keys: Vec<_> = session
-        .all_participants
-        .iter()
-        .map(|key| key.to_vec())
-        .collect();
-    let message = ServerMessage::SessionReady(session);
-    notify_peers(state, public_keys, message).await?;
+    // Each participant and observer receives their own
+    // session token
+    let reader = state.read().await;
+    let observers = reader
+        .sessions
+        .get_session(&session.session_id)
+        .map(|s| s.observers())
+        .unwrap_or_default();
+    for key in session.all_participants.iter().chain(&observers) {
+        if let Some(conn) = reader.active.get(key).map(Arc::clone) {
+            let message = ServerMessage::SessionReady(SessionState {
+                token: Some(
+                    reader.tokens.issue(session.session_id, key),
+                ),
+                ..session.clone()
+            });
+            send_message(conn, &message, true).await?;
+        }
+    }
     Ok(())
 }
 
@@ -377,15 +587,42 @@ async fn wait_for_session_active(
     }
 }
 
+/// Time out a session when the deadline for the session
+/// is reached.
+async fn wait_for_session_deadline(
+    state: State,
+    session: SessionState,
+    remaining: Duration,
+) {
+    tokio::time::sleep(remaining).await;
+    {
+        let reader = state.read().await;
+        if reader.sessions.get_session(&session.session_id).is_none()
+        {
+            return;
+        }
+    }
+
+    let session_id = session.session_id;
+    tracing::debug!(
+        session_id = %session_id,
+        "session deadline reached",
+    );
+    if let Err(e) =
+        notify_session_timeout(Arc::clone(&state), session).await
+    {
+        tracing::error!("{:#?}", e);
+    }
+    let mut writer = state.write().await;
+    writer.sessions.remove_session(&session_id);
+}
+
 async fn notify_session_active(
     state: State,
     session: SessionState,
 ) -> Result<()> {
-    let public_keys: Vec<_> = session
-        .all_participants
-        .iter()
-        .map(|key| key.to_vec())
-        .collect();
+    let public_keys =
+        notification_keys(Arc::clone(&state), &session).await;
     let message = ServerMessage::SessionActive(session);
     notify_peers(state, public_keys, message).await?;
     Ok(())
@@ -395,17 +632,75 @@ async fn notify_session_timeout(
     state: State,
     session: SessionState,
 ) -> Result<()> {
-    let public_keys: Vec<_> = session
-        .all_participants
-        .iter()
-        .map(|key| key.to_vec())
-        .collect();
+    let public_keys =
+        notification_keys(Arc::clone(&state), &session).await;
     let message = ServerMessage::SessionTimeout(session.session_id);
     notify_peers(state, public_keys, message).await?;
 
     Ok(())
 }
 
+/// Public keys of the participants and observers of a session
+/// that should receive a session notification.
+async fn notification_keys(
+    state: State,
+    session: &SessionState,
+) -> Vec<Vec<u8>> {
+    let reader = state.read().await;
+    let mut public_keys = session.all_participants.clone();
+    if let Some(session) =
+        reader.sessions.get_session(&session.session_id)
+    {
+        public_keys.append(&mut session.observers());
+    }
+    public_keys
+}
+
+/// Record a relayed message in the statistics and transcript
+/// of a session and notify the session observers.
+///
+/// The transcript is only computed for sessions that
+/// have observers unless the server is configured to
+/// compute the digest for every session.
+async fn record_relayed(
+    state: State,
+    session_id: SessionId,
+    from_public_key: &[u8],
+    frames: u64,
+    buffer: &[u8],
+) -> Result<()> {
+    let (observers, transcript) = {
+        let mut writer = state.write().await;
+        let digest = writer.config.session.digest;
+        let Some(session) =
+            writer.sessions.get_session_mut(&session_id)
+        else {
+            return Ok(());
+        };
+        session.record_relayed(
+            from_public_key,
+            frames,
+            frames * buffer.len() as u64,
+        );
+        let observers = session.observers();
+        if observers.is_empty() && !digest {
+            return Ok(());
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(session.transcript().hash);
+        hasher.update(buffer);
+        let transcript =
+            session.append_transcript(hasher.finalize().into());
+        (observers, transcript.clone())
+    };
+
+    if observers.is_empty() {
+        return Ok(());
+    }
+    let message = ServerMessage::SessionTranscript(transcript);
+    notify_peers(state, observers, message).await
+}
+
 async fn service(
     state: State,
     conn: Connection,
@@ -430,18 +725,54 @@ async fn service(
                 return Err(Error::SessionOwnerNotParticipant);
             }
 
-            let (session_id, wait_interval) = {
+            if request
+                .observer_keys
+                .iter()
+                .any(|k| all_participants.contains(k))
+            {
+                return Err(Error::SessionObserverParticipant);
+            }
+
+            if let Some(parameters) = &request.parameters {
+                parameters
+                    .validate_session(all_participants.len(), 1)
+                    .map_err(Error::SessionParameters)?;
+            }
+
+            let remaining = if let Some(deadline) = request.deadline {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                let remaining = Duration::from_secs(deadline)
+                    .checked_sub(now)
+                    .filter(|d| !d.is_zero())
+                    .ok_or(Error::SessionDeadline(deadline))?;
+                Some(remaining)
+            } else {
+                None
+            };
+
+            let (session_id, wait_interval, token) = {
                 let mut writer = state.write().await;
                 let session_id = writer.sessions.new_session(
                     public_key.as_ref().to_vec(),
                     request.participant_keys,
+                    request.observer_keys,
                 );
-                (session_id, writer.config.session.wait_interval)
+                let token = writer
+                    .tokens
+                    .issue(session_id, public_key.as_ref());
+                (
+                    session_id,
+                    writer.config.session.wait_interval,
+                    token,
+                )
             };
 
-            let response = SessionState {
+            let session = SessionState {
                 session_id,
                 all_participants,
+                token: None,
             };
 
             tokio::task::spawn(wait_for_session_ready(
@@ -449,10 +780,21 @@ async fn service(
                 Arc::clone(&state),
                 Arc::clone(&conn),
                 SystemTime::now(),
-                response.clone(),
+                session.clone(),
             ));
 
-            Ok(Some(ServerMessage::SessionCreated(response)))
+            if let Some(remaining) = remaining {
+                tokio::task::spawn(wait_for_session_deadline(
+                    Arc::clone(&state),
+                    session.clone(),
+                    remaining,
+                ));
+            }
+
+            Ok(Some(ServerMessage::SessionCreated(SessionState {
+                token: Some(token),
+                ..session
+            })))
         }
         ServerMessage::SessionConnection {
             session_id,
@@ -467,6 +809,11 @@ async fn service(
             if let Some(session) =
                 writer.sessions.get_session_mut(&session_id)
             {
+                if session.is_observer(&from_public_key) {
+                    return Err(Error::SessionObserverReadOnly(
+                        session_id,
+                    ));
+                }
                 session
                     .register_connection(from_public_key, peer_key);
                 Ok(None)
@@ -488,15 +835,120 @@ async fn service(
                 }
             }
 
-            let mut writer = state.write().await;
-            writer.sessions.remove_session(&session_id);
+            let (observers, stats) = {
+                let mut writer = state.write().await;
+                let Some(session) =
+                    writer.sessions.remove_session(&session_id)
+                else {
+                    return Err(Error::SessionNotFound(session_id));
+                };
+                (session.observers(), session.stats())
+            };
+
+            tracing::info!(
+                session_id = %session_id,
+                duration_ms = %stats.duration,
+                frames = %stats.frames(),
+                bytes = %stats.bytes(),
+                "session_finished",
+            );
+
+            notify_peers(
+                state,
+                observers,
+                ServerMessage::SessionFinished(
+                    session_id,
+                    Some(stats.clone()),
+                ),
+            )
+            .await?;
 
-            Ok(Some(ServerMessage::SessionFinished(session_id)))
+            Ok(Some(ServerMessage::SessionFinished(
+                session_id,
+                Some(stats),
+            )))
+        }
+        ServerMessage::ResumeSession(token) => {
+            resume_session(state, public_key.as_ref(), token).await
+        }
+        ServerMessage::GetSessionDigest(session_id) => {
+            let reader = state.read().await;
+            let Some(session) =
+                reader.sessions.get_session(&session_id)
+            else {
+                return Err(Error::SessionNotFound(session_id));
+            };
+            let public_key = public_key.as_ref();
+            if !session
+                .all_participants()
+                .iter()
+                .any(|k| k == public_key)
+                && !session.is_observer(public_key)
+            {
+                return Err(Error::PermissionDenied);
+            }
+            if !reader.config.session.digest
+                && session.observers().is_empty()
+            {
+                return Err(Error::SessionDigestUnavailable(
+                    session_id,
+                ));
+            }
+            Ok(Some(ServerMessage::SessionDigest(
+                session.transcript().clone(),
+            )))
         }
         _ => Ok(None),
     }
 }
 
+/// Re-attach a participant to their slot in a session.
+///
+/// The token must have been issued to the public key of the
+/// connection; the participant receives the session state
+/// with a fresh token without negotiating the session again.
+async fn resume_session(
+    state: State,
+    public_key: &[u8],
+    token: SessionToken,
+) -> Result<Option<ServerMessage>> {
+    let mut writer = state.write().await;
+    writer.tokens.verify(&token, public_key)?;
+
+    let session_id = token.session_id;
+    let (all_participants, is_observer) = if let Some(session) =
+        writer.sessions.touch_session(&session_id)
+    {
+        (
+            session.all_participants().to_vec(),
+            session.is_observer(public_key),
+        )
+    } else {
+        return Err(Error::SessionNotFound(session_id));
+    };
+
+    if !is_observer
+        && !all_participants.iter().any(|k| k == public_key)
+    {
+        return Err(Error::NotSessionParticipant(
+            session_id,
+            hex::encode(public_key),
+        ));
+    }
+
+    tracing::debug!(
+        session_id = %session_id,
+        public_key = %fingerprint(public_key),
+        "resume_session",
+    );
+
+    Ok(Some(ServerMessage::SessionResumed(SessionState {
+        session_id,
+        all_participants,
+        token: Some(writer.tokens.issue(session_id, public_key)),
+    })))
+}
+
 /// Send a message to a collection of peers.
 async fn notify_peers(
     state: State,
@@ -514,6 +966,15 @@ async fn notify_peers(
 
 /// Handle a server error.
 async fn handle_error(conn: Connection, error: Error) -> Result<()> {
+    let status = match &error {
+        Error::SessionParameters(_)
+        | Error::SessionDeadline(_)
+        | Error::SessionDigestUnavailable(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        Error::ConnectionExists => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
     let is_transport = {
         let reader = conn.read().await;
         matches!(reader.state, Some(ProtocolState::Transport(_)))
@@ -522,17 +983,13 @@ async fn handle_error(conn: Connection, error: Error) -> Result<()> {
     // Connection is in transport mode so we can
     // send over the encrypted server channel
     if is_transport {
-        let response = ServerMessage::Error(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            error.to_string(),
-        );
+        let response =
+            ServerMessage::Error(status, error.to_string());
         send_message(Arc::clone(&conn), &response, false).await?;
     } else {
-        let response =
-            ResponseMessage::Transparent(TransparentMessage::Error(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                error.to_string(),
-            ));
+        let response = ResponseMessage::Transparent(
+            TransparentMessage::Error(status, error.to_string()),
+        );
 
         let mut writer = conn.write().await;
         let buffer = encode(&response).await?;
@@ -568,12 +1025,54 @@ async fn send_message(
 /// Promote a connection from pending to active state.
 ///
 /// Called once the server handshake has been initiated.
-async fn promote_connection(state: State, conn: Connection) {
+///
+/// An active connection for the same public key is superseded
+/// when the handshake proved the client holds the key or the
+/// active connection no longer answers pings, otherwise the
+/// new connection is rejected and the active connection is
+/// left in place.
+async fn promote_connection(
+    state: State,
+    conn: Connection,
+    verified: bool,
+) -> Result<()> {
     let (id, public_key) = {
         let reader = conn.read().await;
         (reader.id, reader.public_key.clone())
     };
-    let mut writer = state.write().await;
-    writer.pending.remove(&id);
-    writer.active.insert(public_key, conn);
+
+    // Without proof of the key only a connection that was
+    // lost may be taken over
+    if !verified {
+        let active = {
+            let reader = state.read().await;
+            reader
+                .active
+                .get(&public_key)
+                .filter(|active| !Arc::ptr_eq(active, &conn))
+                .map(Arc::clone)
+        };
+        if let Some(active) = active {
+            if is_alive(active).await {
+                let mut writer = state.write().await;
+                writer.pending.remove(&id);
+                return Err(Error::ConnectionExists);
+            }
+        }
+    }
+
+    let previous = {
+        let mut writer = state.write().await;
+        writer.pending.remove(&id);
+        writer.active.insert(public_key, Arc::clone(&conn))
+    };
+
+    // Clients that reconnect before the server notices the
+    // previous connection was lost take over the connection
+    if let Some(previous) = previous {
+        if !Arc::ptr_eq(&previous, &conn) {
+            supersede(previous, conn).await;
+        }
+    }
+    Ok(())
 }
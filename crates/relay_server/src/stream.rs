@@ -0,0 +1,143 @@
+//! Relay connections over HTTP/2 streams.
+//!
+//! Each stream is a connection for a single client so
+//! native clients can multiplex the connections for several
+//! sessions over one HTTP/2 connection; the framing is
+//! described in [polysig_protocol::stream].
+use axum::{
+    body::{Body, Bytes},
+    extract::{ws::Message, Extension, Query},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+};
+use futures::{Stream, StreamExt};
+use polysig_protocol::{
+    is_subprotocol_supported,
+    stream::{encode_frame, FrameDecoder},
+    zlib, Priority, SUBPROTOCOL, SUBPROTOCOL_HEADER,
+};
+use std::{convert::Infallible, sync::Arc};
+
+use crate::{
+    server::{Service, State},
+    websocket::{
+        accept, disconnect, Accepted, Connection, Outgoing,
+        OutgoingReceiver, WebSocketQuery,
+    },
+};
+
+/// Accept a relay connection over an HTTP/2 stream.
+///
+/// Messages from the client are read from the request body
+/// and messages for the client are written to the response
+/// body until either side ends the stream.
+pub async fn stream(
+    Extension(state): Extension<State>,
+    Extension(service): Extension<Service>,
+    Query(query): Query<WebSocketQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> std::result::Result<Response, StatusCode> {
+    tracing::debug!("stream request");
+
+    // Clients that offer subprotocols must support the
+    // wire encoding version of the server
+    let offered = headers
+        .get(SUBPROTOCOL_HEADER)
+        .map(|value| value.to_str().unwrap_or_default());
+    if !is_subprotocol_supported(offered) {
+        return Err(StatusCode::UPGRADE_REQUIRED);
+    }
+
+    let Accepted {
+        conn,
+        outgoing,
+        outgoing_rx,
+        incoming_rx,
+    } = accept(&state, query).await?;
+
+    service.listen_socket(Arc::clone(&conn), incoming_rx);
+    tokio::spawn(read(body, state, conn, outgoing));
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(SUBPROTOCOL_HEADER, SUBPROTOCOL)
+        .body(Body::from_stream(write(outgoing_rx)))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Read frames from the request body.
+async fn read(
+    body: Body,
+    state: State,
+    conn: Connection,
+    outgoing: Outgoing,
+) {
+    let tx = {
+        let reader = conn.read().await;
+        reader.incoming.clone()
+    };
+
+    let mut decoder = FrameDecoder::default();
+    let mut chunks = body.into_data_stream();
+    'read: while let Some(chunk) = chunks.next().await {
+        match chunk {
+            Ok(chunk) => decoder.push(&chunk),
+            Err(e) => {
+                tracing::warn!(error = %e, "stream::read_error");
+                break;
+            }
+        }
+        loop {
+            match decoder.next_frame() {
+                Ok(Some(frame)) => {
+                    if let Ok(inflated) = zlib::inflate(&frame) {
+                        if tx.send(inflated).await.is_err() {
+                            break 'read;
+                        }
+                    } else {
+                        tracing::warn!(
+                            "could not inflate message buffer"
+                        );
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!(error = %e, "stream::read_error");
+                    break 'read;
+                }
+            }
+        }
+    }
+
+    // End the response body once the client stops sending
+    let _ =
+        outgoing.send(Message::Close(None), Priority::Control).await;
+    disconnect(state, conn).await;
+}
+
+/// Frames written to the response body.
+fn write(
+    outgoing_rx: OutgoingReceiver,
+) -> impl Stream<Item = std::result::Result<Bytes, Infallible>> {
+    futures::stream::unfold(
+        outgoing_rx,
+        |mut outgoing_rx| async move {
+            loop {
+                let buffer = match outgoing_rx.recv().await? {
+                    Message::Binary(buffer) => buffer,
+                    Message::Close(_) => return None,
+                    _ => continue,
+                };
+                match encode_frame(&buffer) {
+                    Ok(frame) => {
+                        return Some((Ok(frame.into()), outgoing_rx))
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "stream::frame")
+                    }
+                }
+            }
+        },
+    )
+}
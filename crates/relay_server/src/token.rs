@@ -0,0 +1,145 @@
+//! Session resumption tokens.
+use hmac::{Hmac, Mac};
+use polysig_protocol::{Keypair, SessionId, SessionToken};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{Error, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separator for the token signing key.
+const DOMAIN: &[u8] = b"polysig-session-token";
+
+/// Issue and verify session tokens.
+///
+/// The signing key is derived from the server keypair so
+/// every server in a cluster that shares the keypair
+/// accepts the tokens issued by the other servers.
+///
+/// Whilst the server key is rotated tokens are issued with
+/// the key for the primary keypair and tokens issued before
+/// the rotation are verified with the key for the secondary
+/// keypair.
+pub(crate) struct TokenSigner {
+    key: [u8; 32],
+    secondary: Option<[u8; 32]>,
+    ttl: u64,
+}
+
+impl TokenSigner {
+    /// Create a token signer.
+    pub fn new(keypair: &Keypair, ttl: u64) -> Self {
+        Self {
+            key: derive_key(keypair),
+            secondary: None,
+            ttl,
+        }
+    }
+
+    /// Set the secondary server keypair.
+    pub fn set_secondary(&mut self, keypair: Option<&Keypair>) {
+        self.secondary = keypair.map(derive_key);
+    }
+
+    /// Issue a token for a participant in a session.
+    pub fn issue(
+        &self,
+        session_id: SessionId,
+        public_key: &[u8],
+    ) -> SessionToken {
+        let mut token = SessionToken {
+            session_id,
+            public_key: public_key.to_vec(),
+            expires_at: now().saturating_add(self.ttl),
+            signature: Vec::new(),
+        };
+        let mut mac = mac(&self.key);
+        mac.update(&token.signed_bytes());
+        token.signature = mac.finalize().into_bytes().to_vec();
+        token
+    }
+
+    /// Verify a token presented by a participant.
+    pub fn verify(
+        &self,
+        token: &SessionToken,
+        public_key: &[u8],
+    ) -> Result<()> {
+        let signed_bytes = token.signed_bytes();
+        let is_valid = |key: &[u8; 32]| {
+            let mut mac = mac(key);
+            mac.update(&signed_bytes);
+            mac.verify_slice(&token.signature).is_ok()
+        };
+        if !is_valid(&self.key)
+            && !self.secondary.as_ref().is_some_and(is_valid)
+        {
+            return Err(Error::InvalidSessionToken);
+        }
+        if token.public_key != public_key {
+            return Err(Error::InvalidSessionToken);
+        }
+        if token.is_expired(now()) {
+            return Err(Error::SessionTokenExpired(token.session_id));
+        }
+        Ok(())
+    }
+}
+
+/// Derive the token signing key for a server keypair.
+fn derive_key(keypair: &Keypair) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(DOMAIN);
+    hasher.update(keypair.private_key());
+    hasher.finalize().into()
+}
+
+fn mac(key: &[u8; 32]) -> HmacSha256 {
+    HmacSha256::new_from_slice(key)
+        .expect("HMAC accepts keys of any size")
+}
+
+/// Seconds since the UNIX epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenSigner;
+    use crate::Error;
+    use polysig_protocol::{Keypair, SessionId};
+
+    #[test]
+    fn verify_secondary_token() -> anyhow::Result<()> {
+        let previous = Keypair::generate()?;
+        let keypair = Keypair::generate()?;
+        let public_key = [1u8; 32];
+        let session_id = SessionId::new_v4();
+
+        let token = TokenSigner::new(&previous, 60)
+            .issue(session_id, &public_key);
+
+        let mut signer = TokenSigner::new(&keypair, 60);
+        assert!(matches!(
+            signer.verify(&token, &public_key),
+            Err(Error::InvalidSessionToken)
+        ));
+
+        // Tokens issued before the rotation are accepted
+        signer.set_secondary(Some(&previous));
+        signer.verify(&token, &public_key)?;
+
+        // New tokens are issued with the primary key
+        let token = signer.issue(session_id, &public_key);
+        TokenSigner::new(&keypair, 60).verify(&token, &public_key)?;
+        assert!(TokenSigner::new(&previous, 60)
+            .verify(&token, &public_key)
+            .is_err());
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
 use axum::{
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{
+            close_code, CloseFrame, Message, WebSocket,
+            WebSocketUpgrade,
+        },
         Extension, Query,
     },
-    http::StatusCode,
+    http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderMap, StatusCode},
     response::Response,
 };
 use futures::{
@@ -13,8 +16,8 @@ use futures::{
 
 use serde::Deserialize;
 
-use std::{fmt, sync::Arc};
-use tokio::sync::{mpsc, RwLock};
+use std::{fmt, sync::Arc, time::Duration};
+use tokio::sync::{mpsc, oneshot, Notify, RwLock};
 
 //use axum_macros::debug_handler;
 
@@ -23,20 +26,172 @@ use crate::{
     Result,
 };
 use polysig_protocol::{
-    hex,
-    snow::{params::NoiseParams, Builder},
+    fingerprint, hex, is_initiator_key_known,
+    is_subprotocol_supported, is_zero_rtt,
+    snow::{params::NoiseParams, Builder, HandshakeState},
     uuid::Uuid,
-    zlib, ProtocolState, PATTERN,
+    zlib, Keypair, Priority, ProtocolState, CLOSE_SUPERSEDED,
+    SUBPROTOCOL,
 };
 
 pub type Connection = Arc<RwLock<WebSocketConnection>>;
 
+/// Capacity of the outgoing channel for each priority.
+const OUTGOING_CAPACITY: usize = 32;
+
+/// Time to wait for the close frame to be written to a
+/// superseded connection that is blocked writing.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Time to wait for a pong when checking whether a connection
+/// is still alive.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outgoing channels for a connection by priority.
+#[derive(Clone)]
+pub(crate) struct Outgoing {
+    control: mpsc::Sender<Message>,
+    round: mpsc::Sender<Message>,
+    bulk: mpsc::Sender<Message>,
+}
+
+impl Outgoing {
+    /// Queue a message to be written with a priority.
+    pub(crate) async fn send(
+        &self,
+        message: Message,
+        priority: Priority,
+    ) -> Result<()> {
+        let channel = match priority {
+            Priority::Control => &self.control,
+            Priority::Round => &self.round,
+            Priority::Bulk => &self.bulk,
+        };
+        channel.send(message).await?;
+        Ok(())
+    }
+}
+
+/// Receiver for the outgoing channels of a connection.
+///
+/// Messages are received in priority order so that a large
+/// payload relayed for one session does not delay the
+/// control messages for another session on the connection.
+pub(crate) struct OutgoingReceiver {
+    control: mpsc::Receiver<Message>,
+    round: mpsc::Receiver<Message>,
+    bulk: mpsc::Receiver<Message>,
+    /// Receives the outgoing channels of the connection
+    /// that supersedes this connection.
+    superseded: Option<oneshot::Receiver<Outgoing>>,
+}
+
+impl OutgoingReceiver {
+    /// Receive the next message with the highest priority.
+    ///
+    /// When the connection is superseded the close frame
+    /// for the connection is received.
+    pub(crate) async fn recv(&mut self) -> Option<Message> {
+        tokio::select! {
+            biased;
+            Some(outgoing) = takeover(&mut self.superseded) => {
+                Some(self.transfer(outgoing).await)
+            }
+            Some(message) = self.control.recv() => Some(message),
+            Some(message) = self.round.recv() => Some(message),
+            Some(message) = self.bulk.recv() => Some(message),
+            else => None,
+        }
+    }
+
+    /// Wait until the connection is superseded.
+    ///
+    /// Returns the close frame for the connection once the
+    /// queued messages have been moved to the new connection.
+    pub(crate) async fn superseded(&mut self) -> Option<Message> {
+        let outgoing = takeover(&mut self.superseded).await?;
+        Some(self.transfer(outgoing).await)
+    }
+
+    /// Move the queued messages relayed from peers to the
+    /// outgoing channels of the new connection.
+    ///
+    /// Control messages are discarded as server messages are
+    /// encrypted for the server channel of this connection.
+    async fn transfer(&mut self, outgoing: Outgoing) -> Message {
+        self.control.close();
+        self.round.close();
+        self.bulk.close();
+
+        let mut transferred = 0;
+        for (channel, priority) in [
+            (&mut self.round, Priority::Round),
+            (&mut self.bulk, Priority::Bulk),
+        ] {
+            while let Ok(message) = channel.try_recv() {
+                if outgoing.send(message, priority).await.is_err() {
+                    break;
+                }
+                transferred += 1;
+            }
+        }
+        while self.control.try_recv().is_ok() {}
+
+        tracing::debug!(
+            transferred = %transferred,
+            "superseded::transfer",
+        );
+
+        Message::Close(Some(CloseFrame {
+            code: CLOSE_SUPERSEDED,
+            reason: "superseded".into(),
+        }))
+    }
+}
+
+/// Wait for the outgoing channels of a superseding connection.
+///
+/// Resolves to `None` once the channel has been used.
+async fn takeover(
+    superseded: &mut Option<oneshot::Receiver<Outgoing>>,
+) -> Option<Outgoing> {
+    let receiver = superseded.as_mut()?;
+    let outgoing = receiver.await.ok();
+    *superseded = None;
+    outgoing
+}
+
+/// Create the outgoing channels for a connection.
+fn outgoing(
+) -> (Outgoing, OutgoingReceiver, oneshot::Sender<Outgoing>) {
+    let (control, control_rx) = mpsc::channel(OUTGOING_CAPACITY);
+    let (round, round_rx) = mpsc::channel(OUTGOING_CAPACITY);
+    let (bulk, bulk_rx) = mpsc::channel(OUTGOING_CAPACITY);
+    let (supersede, superseded) = oneshot::channel();
+    (
+        Outgoing {
+            control,
+            round,
+            bulk,
+        },
+        OutgoingReceiver {
+            control: control_rx,
+            round: round_rx,
+            bulk: bulk_rx,
+            superseded: Some(superseded),
+        },
+        supersede,
+    )
+}
+
 /// Query string for initiating websocket connections.
 #[derive(Debug, Deserialize)]
 pub struct WebSocketQuery {
     /// Public key offered by the client socket.
     #[serde(with = "hex::serde")]
     pub public_key: Vec<u8>,
+    /// Noise parameters pattern requested by the client.
+    pub pattern: Option<String>,
 }
 
 /// State for the websocket  connection for a single
@@ -46,8 +201,8 @@ pub struct WebSocketConnection {
     pub(crate) id: Uuid,
     /// User supplied public key.
     pub(crate) public_key: Vec<u8>,
-    /// Outoing channel for messages sent to clients.
-    pub(crate) outgoing: mpsc::Sender<Message>,
+    /// Outoing channels for messages sent to clients.
+    pub(crate) outgoing: Outgoing,
     // Incoming channel for messages received from clients.
     pub(crate) incoming: mpsc::Sender<Vec<u8>>,
     /// Protocol state for this connection.
@@ -57,36 +212,90 @@ pub struct WebSocketConnection {
     /// so we move out of the option and convert to
     /// transport mode and then put it back.
     pub(crate) state: Option<ProtocolState>,
+    /// Handshake state for the secondary server key.
+    ///
+    /// Used when the first handshake message does not match
+    /// the primary key whilst the server key is rotated.
+    pub(crate) fallback: Option<Box<HandshakeState>>,
+    /// Hands the outgoing channels of a new connection for
+    /// the same public key to this connection.
+    pub(crate) supersede: Option<oneshot::Sender<Outgoing>>,
+    /// Notified when a pong is received from the client.
+    ///
+    /// Only websocket connections answer pings.
+    pub(crate) pong: Option<Arc<Notify>>,
 }
 
 impl fmt::Debug for WebSocketConnection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("WebSocketConnection")
             .field("id", &self.id)
-            .field("public_key", &hex::encode(&self.public_key))
+            .field("public_key", &fingerprint(&self.public_key))
             .finish()
     }
 }
 
 impl WebSocketConnection {
     /// Send a buffer to the client at this socket.
+    ///
+    /// The buffer is sent with the control priority.
     pub async fn send(&mut self, buffer: Vec<u8>) -> Result<()> {
+        self.send_priority(buffer, Priority::Control).await
+    }
+
+    /// Send a buffer to the client at this socket with
+    /// a priority.
+    pub async fn send_priority(
+        &mut self,
+        buffer: Vec<u8>,
+        priority: Priority,
+    ) -> Result<()> {
         let deflated = zlib::deflate(&buffer)?;
-        self.outgoing.send(Message::Binary(deflated)).await?;
-        Ok(())
+        self.outgoing
+            .send(Message::Binary(deflated), priority)
+            .await
     }
 }
 
-/// Upgrade to a websocket connection.
-//#[debug_handler]
-pub async fn upgrade(
-    Extension(state): Extension<State>,
-    Extension(service): Extension<Service>,
-    Query(query): Query<WebSocketQuery>,
-    ws: WebSocketUpgrade,
-) -> std::result::Result<Response, StatusCode> {
-    tracing::debug!("websocket upgrade request");
+/// Build the handshake responder for a server key.
+fn build_responder(
+    pattern: &str,
+    keypair: &Keypair,
+    public_key: &[u8],
+) -> Result<HandshakeState> {
+    let params: NoiseParams = pattern.parse()?;
 
+    // Patterns that transmit the initiator static key are
+    // checked against the public key once the handshake
+    // message has been read
+    let builder =
+        Builder::new(params).local_private_key(keypair.private_key());
+    let builder = if is_initiator_key_known(pattern) {
+        builder.remote_public_key(public_key)
+    } else {
+        builder
+    };
+    Ok(builder.build_responder()?)
+}
+
+/// Pending connection for a client.
+pub(crate) struct Accepted {
+    /// Connection state.
+    pub(crate) conn: Connection,
+    /// Outgoing channels for the connection.
+    pub(crate) outgoing: Outgoing,
+    /// Receiver for the outgoing channels.
+    pub(crate) outgoing_rx: OutgoingReceiver,
+    /// Receiver for the messages read from the client.
+    pub(crate) incoming_rx: mpsc::Receiver<Vec<u8>>,
+}
+
+/// Check the access lists and create a pending connection
+/// for a client.
+pub(crate) async fn accept(
+    state: &State,
+    query: WebSocketQuery,
+) -> std::result::Result<Accepted, StatusCode> {
     let mut writer = state.write().await;
 
     // Check access lists
@@ -96,26 +305,36 @@ pub async fn upgrade(
         return Err(StatusCode::FORBIDDEN);
     }
 
-    let pattern = if let Some(pattern) = &writer.config.pattern {
+    let pattern = if let Some(pattern) = &query.pattern {
+        if !writer.config.is_allowed_pattern(pattern) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
         pattern
     } else {
-        PATTERN
+        writer.config.default_pattern()
     };
 
-    let params: NoiseParams = pattern
-        .parse()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let responder = Builder::new(params)
-        .local_private_key(writer.keypair.private_key())
-        .remote_public_key(&query.public_key)
-        .build_responder()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let responder =
+        build_responder(pattern, &writer.keypair, &query.public_key)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     let protocol_state =
         ProtocolState::Handshake(Box::new(responder));
 
+    // The first handshake message is only encrypted to the
+    // server static key for zero round trip patterns; for
+    // patterns that transmit the server static key the client
+    // does not reveal the key it expects so the primary key is
+    // always used and clients must pin the new key
+    let fallback = match &writer.secondary_keypair {
+        Some(keypair) if is_zero_rtt(pattern) => Some(Box::new(
+            build_responder(pattern, keypair, &query.public_key)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        )),
+        _ => None,
+    };
+
     let id = Uuid::new_v4();
-    let (outgoing_tx, outgoing_rx) = mpsc::channel::<Message>(32);
+    let (outgoing_tx, outgoing_rx, supersede) = outgoing();
     let (incoming, service_reader) = mpsc::channel::<Vec<u8>>(32);
 
     let conn = Arc::new(RwLock::new(WebSocketConnection {
@@ -124,10 +343,46 @@ pub async fn upgrade(
         outgoing: outgoing_tx.clone(),
         incoming,
         state: Some(protocol_state),
+        fallback,
+        supersede: Some(supersede),
+        pong: None,
     }));
-    let socket_conn = Arc::clone(&conn);
-    writer.pending.insert(id, conn);
-    drop(writer);
+    writer.pending.insert(id, Arc::clone(&conn));
+    Ok(Accepted {
+        conn,
+        outgoing: outgoing_tx,
+        outgoing_rx,
+        incoming_rx: service_reader,
+    })
+}
+
+/// Upgrade to a websocket connection.
+//#[debug_handler]
+pub async fn upgrade(
+    Extension(state): Extension<State>,
+    Extension(service): Extension<Service>,
+    Query(query): Query<WebSocketQuery>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> std::result::Result<Response, StatusCode> {
+    tracing::debug!("websocket upgrade request");
+
+    // Clients that offer subprotocols must support the
+    // wire encoding version of the server
+    let offered = headers
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .map(|value| value.to_str().unwrap_or_default());
+    if !is_subprotocol_supported(offered) {
+        return Err(StatusCode::UPGRADE_REQUIRED);
+    }
+    let ws = ws.protocols([SUBPROTOCOL]);
+
+    let Accepted {
+        conn: socket_conn,
+        outgoing: outgoing_tx,
+        outgoing_rx,
+        incoming_rx: service_reader,
+    } = accept(&state, query).await?;
 
     let socket_state = Arc::clone(&state);
     Ok(ws.on_upgrade(move |socket| {
@@ -143,23 +398,95 @@ pub async fn upgrade(
     }))
 }
 
-async fn disconnect(state: State, conn: Connection) {
+pub(crate) async fn disconnect(state: State, conn: Connection) {
     let (id, public_key) = {
         let reader = conn.read().await;
         (reader.id, reader.public_key.clone())
     };
-    tracing::debug!(public_key = ?hex::encode(&public_key), "disconnect");
+    tracing::debug!(
+        public_key = %fingerprint(&public_key),
+        "disconnect",
+    );
     let mut writer = state.write().await;
     writer.pending.remove(&id);
-    writer.active.remove(&public_key);
+
+    // A superseded connection must not remove the
+    // connection that replaced it
+    let is_active = match writer.active.get(&public_key) {
+        Some(active) => Arc::ptr_eq(active, &conn),
+        None => false,
+    };
+    if is_active {
+        writer.active.remove(&public_key);
+    }
+}
+
+/// Replace the active connection for a public key with
+/// a new connection.
+///
+/// Messages queued for the previous connection are moved to
+/// the new connection and the previous connection is sent a
+/// close frame with the [CLOSE_SUPERSEDED] code.
+pub(crate) async fn supersede(
+    previous: Connection,
+    conn: Connection,
+) {
+    let outgoing = {
+        let reader = conn.read().await;
+        reader.outgoing.clone()
+    };
+    let (supersede, public_key) = {
+        let mut writer = previous.write().await;
+        (writer.supersede.take(), writer.public_key.clone())
+    };
+    tracing::info!(
+        public_key = %fingerprint(&public_key),
+        "superseded",
+    );
+    if let Some(supersede) = supersede {
+        let _ = supersede.send(outgoing);
+    }
+}
+
+/// Determine if a connection answers a ping.
+///
+/// Connections that cannot answer pings are assumed to be
+/// alive.
+pub(crate) async fn is_alive(conn: Connection) -> bool {
+    let (outgoing, pong) = {
+        let reader = conn.read().await;
+        (reader.outgoing.clone(), reader.pong.clone())
+    };
+    let Some(pong) = pong else {
+        return true;
+    };
+    let notified = pong.notified();
+    let ping = Message::Ping(Vec::new());
+    if outgoing.send(ping, Priority::Control).await.is_err() {
+        return false;
+    }
+    tokio::time::timeout(PROBE_TIMEOUT, notified).await.is_ok()
+}
+
+/// Close a connection that is rejected by the server.
+pub(crate) async fn reject(conn: Connection) {
+    let outgoing = {
+        let reader = conn.read().await;
+        reader.outgoing.clone()
+    };
+    let close = Message::Close(Some(CloseFrame {
+        code: close_code::POLICY,
+        reason: "rejected".into(),
+    }));
+    let _ = outgoing.send(close, Priority::Control).await;
 }
 
 async fn handle_socket(
     socket: WebSocket,
     state: State,
     conn: Connection,
-    outgoing_rx: mpsc::Receiver<Message>,
-    outgoing_tx: mpsc::Sender<Message>,
+    outgoing_rx: OutgoingReceiver,
+    outgoing_tx: Outgoing,
 ) {
     let (writer, reader) = socket.split();
 
@@ -181,11 +508,13 @@ async fn read(
     mut receiver: SplitStream<WebSocket>,
     state: State,
     conn: Connection,
-    outgoing_tx: mpsc::Sender<Message>,
+    outgoing_tx: Outgoing,
 ) -> Result<()> {
-    let tx = {
-        let reader = conn.read().await;
-        reader.incoming.clone()
+    let (tx, pong) = {
+        let mut writer = conn.write().await;
+        let pong = Arc::new(Notify::new());
+        writer.pong = Some(Arc::clone(&pong));
+        (writer.incoming.clone(), pong)
     };
 
     while let Some(msg) = receiver.next().await {
@@ -202,10 +531,14 @@ async fn read(
                     }
                 }
                 Message::Ping(_) => {}
-                Message::Pong(_) => {}
+                Message::Pong(_) => pong.notify_waiters(),
                 Message::Close(frame) => {
-                    let _ =
-                        outgoing_tx.send(Message::Close(frame)).await;
+                    let _ = outgoing_tx
+                        .send(
+                            Message::Close(frame),
+                            Priority::Control,
+                        )
+                        .await;
                     return Ok(());
                 }
             },
@@ -223,14 +556,32 @@ async fn write(
     mut sender: SplitSink<WebSocket, Message>,
     state: State,
     conn: Connection,
-    mut outgoing_rx: mpsc::Receiver<Message>,
+    mut outgoing_rx: OutgoingReceiver,
 ) -> Result<()> {
     while let Some(message) = outgoing_rx.recv().await {
-        if let Err(error) = sender.send(message).await {
+        let is_close = matches!(message, Message::Close(_));
+        // Writes to a half-dead socket may never complete
+        // so stop writing when the connection is superseded
+        let result = tokio::select! {
+            biased;
+            result = sender.send(message) => result,
+            Some(close) = outgoing_rx.superseded() => {
+                let _ = tokio::time::timeout(
+                    CLOSE_TIMEOUT,
+                    sender.send(close),
+                )
+                .await;
+                return Ok(());
+            }
+        };
+        if let Err(error) = result {
             tracing::warn!(error = %error, "ws_server::write_error");
             disconnect(state, Arc::clone(&conn)).await;
             return Ok(());
         }
+        if is_close {
+            break;
+        }
     }
     Ok(())
 }
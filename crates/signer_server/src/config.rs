@@ -0,0 +1,174 @@
+//! Server configuration.
+use polysig_driver::PartyKeys;
+use polysig_protocol::hex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use url::Url;
+
+use crate::{Error, Result};
+
+/// Configuration for the signer service.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Path to the keys for this signer.
+    ///
+    /// The file is JSON encoded party keys containing the
+    /// noise transport keypair and the signing keypair used
+    /// to identify this signer in protocol sessions.
+    pub keys: PathBuf,
+
+    /// Directory containing the key shares.
+    pub keystore: PathBuf,
+
+    /// Path to a file containing the token for requests.
+    ///
+    /// Requests must send the token as a bearer token in the
+    /// authorization header; the server does not start
+    /// without a token.
+    pub auth_token_file: PathBuf,
+
+    /// Relay server used for signing sessions.
+    pub relay: RelayConfig,
+
+    /// Policy applied to signing requests.
+    pub policy: PolicyConfig,
+
+    /// Configuration for TLS encryption.
+    pub tls: Option<TlsConfig>,
+
+    /// Configuration for CORS.
+    pub cors: CorsConfig,
+}
+
+impl ServerConfig {
+    /// Load a server config from a file path.
+    pub async fn load<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(Self, PartyKeys)> {
+        if !fs::try_exists(path.as_ref()).await? {
+            return Err(Error::NotFile(path.as_ref().to_path_buf()));
+        }
+
+        let contents = fs::read_to_string(path.as_ref()).await?;
+        let mut config: ServerConfig = toml::from_str(&contents)?;
+
+        let dir = Self::directory(path.as_ref())?;
+
+        if config.keys.is_relative() {
+            config.keys = dir.join(&config.keys);
+        }
+        if !fs::try_exists(&config.keys).await? {
+            return Err(Error::NotFile(config.keys.clone()));
+        }
+
+        if config.keystore.is_relative() {
+            config.keystore = dir.join(&config.keystore);
+        }
+        if !fs::metadata(&config.keystore).await?.is_dir() {
+            return Err(Error::NotDirectory(config.keystore.clone()));
+        }
+
+        if config.auth_token_file == PathBuf::default() {
+            return Err(Error::AuthToken);
+        }
+        if config.auth_token_file.is_relative() {
+            config.auth_token_file =
+                dir.join(&config.auth_token_file);
+        }
+        if !fs::try_exists(&config.auth_token_file).await? {
+            return Err(Error::NotFile(
+                config.auth_token_file.clone(),
+            ));
+        }
+
+        if let Some(tls) = config.tls.as_mut() {
+            if tls.cert.is_relative() {
+                tls.cert = dir.join(&tls.cert).canonicalize()?;
+            }
+            if tls.key.is_relative() {
+                tls.key = dir.join(&tls.key).canonicalize()?;
+            }
+        }
+
+        let contents = fs::read(&config.keys).await?;
+        let keys: PartyKeys = serde_json::from_slice(&contents)?;
+
+        Ok((config, keys))
+    }
+
+    /// Parent directory of the configuration file.
+    fn directory(file: impl AsRef<Path>) -> Result<PathBuf> {
+        file.as_ref()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .ok_or_else(|| Error::NoParentDir)
+    }
+}
+
+/// Relay server for signing sessions.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelayConfig {
+    /// URL of the relay server.
+    pub url: String,
+
+    /// Public key of the relay server.
+    #[serde(with = "hex::serde")]
+    pub public_key: Vec<u8>,
+
+    /// Optional noise parameters pattern.
+    pub pattern: Option<String>,
+}
+
+/// Policy for signing requests.
+///
+/// Address and amount limits are checked against the
+/// transaction decoded from the request payload; for
+/// Ethereum contract calls the recipient is the contract
+/// address. Message payloads are denied when limits are
+/// configured or `require-transaction` is enabled. The
+/// default policy accepts every request.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct PolicyConfig {
+    /// Require a transaction payload for every request.
+    pub require_transaction: bool,
+
+    /// Recipient addresses that may be paid.
+    ///
+    /// When empty any recipient is allowed.
+    pub allowed_addresses: Vec<String>,
+
+    /// Maximum total amount for a single request.
+    pub max_amount: Option<u128>,
+
+    /// Limit the number of accepted requests.
+    pub rate_limit: Option<RateLimitConfig>,
+}
+
+/// Limit for the number of requests in a time window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Maximum number of requests in the window.
+    pub requests: u32,
+    /// Window duration in seconds.
+    pub interval: u64,
+}
+
+/// Certificate and key for TLS.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// Path to the certificate.
+    pub cert: PathBuf,
+    /// Path to the certificate key file.
+    pub key: PathBuf,
+}
+
+/// Configuration for CORS.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// List of additional CORS origins for the server.
+    pub origins: Vec<Url>,
+}
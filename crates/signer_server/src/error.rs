@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::keystore::KeyProtocol;
+
+/// Errors generated by the signer service.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error generated when a file is expected.
+    #[error(r#"not a file "{0}""#)]
+    NotFile(PathBuf),
+
+    /// Error generated when a directory is expected.
+    #[error(r#"not a directory "{0}""#)]
+    NotDirectory(PathBuf),
+
+    /// Error generated when a file does not have a parent directory.
+    #[error("no parent directory")]
+    NoParentDir,
+
+    /// Error generated when a key identifier contains
+    /// characters that are not allowed.
+    #[error(r#"invalid key identifier "{0}""#)]
+    InvalidKeyId(String),
+
+    /// Error generated when a key could not be found in the keystore.
+    #[error(r#"key "{0}" not found"#)]
+    KeyNotFound(String),
+
+    /// Error generated when the signer is not one of the
+    /// parties for a key.
+    #[error(r#"signer is not a party for key "{0}""#)]
+    NotKeyParty(String),
+
+    /// Error generated when the signing key for the service
+    /// is not the correct type for the key protocol.
+    #[error(r#"key type "{0}" does not match protocol "{1}""#)]
+    KeyType(String, String),
+
+    /// Error generated when a party number is out of range.
+    #[error("party number {0} is out of range")]
+    PartyNumber(u16),
+
+    /// Error generated when the signers for a request are not
+    /// unique and ordered by party number.
+    #[error("signers must be unique and ordered by party number")]
+    SignerOrder,
+
+    /// Error generated when the number of signers is less
+    /// than the threshold for a key.
+    #[error("expected at least {0} signers but got {1}")]
+    SignerThreshold(u16, usize),
+
+    /// Error generated when the signer is not included
+    /// in the signers for a request.
+    #[error("signer is not included in the request signers")]
+    NotSigner,
+
+    /// Error generated when a CGGMP request does not include
+    /// a session seed.
+    #[error("session seed is required for the CGGMP protocol")]
+    SessionSeed,
+
+    /// Error generated when a CGGMP message is not a 32 byte digest.
+    #[error("message must be a 32 byte digest")]
+    Prehash,
+
+    /// Error generated when a payload is not supported for
+    /// the protocol of a key.
+    #[error(r#"payload is not supported for protocol "{0}""#)]
+    PayloadProtocol(KeyProtocol),
+
+    /// Error generated when a transaction payload could
+    /// not be decoded.
+    #[error("invalid payload: {0}")]
+    InvalidPayload(polysig_driver::Error),
+
+    /// Error generated when the auth token file is not
+    /// configured or empty.
+    #[error("auth token is required")]
+    AuthToken,
+
+    /// Error generated when a request does not send the
+    /// auth token.
+    #[error("unauthorized")]
+    Unauthorized,
+
+    /// Error generated when a request is denied by the policy.
+    #[error("policy denied request: {0}")]
+    PolicyDenied(#[from] PolicyViolation),
+
+    /// Error generated by input/output.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Error generated by JSON library.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// Error generated parsing TOML.
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+
+    /// Error generated decoding hex.
+    #[error(transparent)]
+    Hex(#[from] polysig_protocol::hex::FromHexError),
+
+    /// Error generated by the protocol library.
+    #[error(transparent)]
+    Protocol(#[from] polysig_protocol::Error),
+
+    /// Error generated by the driver library.
+    #[error(transparent)]
+    Driver(#[from] polysig_driver::Error),
+
+    /// Error generated by the client library.
+    #[error(transparent)]
+    Client(#[from] polysig_client::Error),
+
+    /// Error generated by the FROST ed25519 library.
+    #[error(transparent)]
+    FrostEd25519(#[from] polysig_driver::frost_ed25519::Error),
+
+    /// Error generated by the FROST secp256k1 library.
+    #[error(transparent)]
+    FrostSecp256k1(#[from] polysig_driver::frost_secp256k1::Error),
+
+    /// Error generated by the signature library.
+    #[error(transparent)]
+    Signature(#[from] polysig_driver::k256::ecdsa::Error),
+
+    /// Error generated by the web server library.
+    #[error(transparent)]
+    Axum(#[from] axum::Error),
+
+    /// Error generated when a header value is invalid.
+    #[error(transparent)]
+    HeaderValue(#[from] axum::http::header::InvalidHeaderValue),
+}
+
+/// Reasons a signing request is denied by the policy.
+#[derive(Debug, Error)]
+pub enum PolicyViolation {
+    /// Transaction payload is required by the policy.
+    #[error("transaction payload is required")]
+    MissingTransaction,
+
+    /// Recipient address is not in the allow list.
+    #[error(r#"address "{0}" is not allowed"#)]
+    AddressNotAllowed(String),
+
+    /// Recipient address is required by the policy.
+    #[error("recipient address is required")]
+    MissingAddress,
+
+    /// Total amount exceeds the limit for a single request.
+    #[error("amount {0} exceeds the limit {1}")]
+    AmountLimit(u128, u128),
+
+    /// Too many requests in the rate limit window.
+    #[error("rate limit of {0} requests in {1} seconds exceeded")]
+    RateLimit(u32, u64),
+}
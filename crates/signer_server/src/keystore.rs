@@ -0,0 +1,164 @@
+//! Key shares stored on disc.
+//!
+//! Each key is a JSON file in the keystore directory named
+//! using the key identifier, for example `treasury.json`.
+use polysig_driver::KeyShare;
+use polysig_protocol::{hex, Parameters};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+
+use crate::{Error, Result};
+
+const EXT: &str = "json";
+
+/// Protocol for a stored key share.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyProtocol {
+    /// CGGMP threshold ECDSA.
+    Cggmp,
+    /// FROST threshold EdDSA.
+    FrostEd25519,
+    /// FROST threshold Schnorr over secp256k1.
+    FrostSecp256k1,
+}
+
+impl fmt::Display for KeyProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Cggmp => "cggmp",
+                Self::FrostEd25519 => "frost-ed25519",
+                Self::FrostSecp256k1 => "frost-secp256k1",
+            }
+        )
+    }
+}
+
+/// Party that holds a share of a key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyParty {
+    /// Noise transport public key.
+    #[serde(with = "hex::serde")]
+    pub public_key: Vec<u8>,
+    /// Verifying key for the party.
+    #[serde(with = "hex::serde")]
+    pub verifier: Vec<u8>,
+}
+
+/// Key share and the parties for the key.
+///
+/// Parties are ordered by party number; FROST key shares
+/// must use the party number as the identifier.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredKey {
+    /// Protocol for the key share.
+    pub protocol: KeyProtocol,
+    /// Parameters used to generate the key.
+    pub parameters: Parameters,
+    /// Parties that hold a share of the key.
+    pub parties: Vec<KeyParty>,
+    /// Key share for this signer.
+    pub key_share: KeyShare,
+}
+
+impl StoredKey {
+    /// Party number for a noise transport public key.
+    pub fn party_number(&self, public_key: &[u8]) -> Option<u16> {
+        self.parties
+            .iter()
+            .position(|p| p.public_key == public_key)
+            .map(|index| index as u16 + 1)
+    }
+
+    /// Party for a party number.
+    pub fn party(&self, party_number: u16) -> Result<&KeyParty> {
+        party_number
+            .checked_sub(1)
+            .and_then(|index| self.parties.get(index as usize))
+            .ok_or(Error::PartyNumber(party_number))
+    }
+}
+
+/// Directory of stored key shares.
+pub struct KeyStore {
+    dir: PathBuf,
+}
+
+impl KeyStore {
+    /// Create a keystore for a directory.
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// List the identifiers of the stored keys.
+    pub async fn list(&self) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut entries = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(EXT)
+            {
+                continue;
+            }
+            if let Some(id) =
+                path.file_stem().and_then(|s| s.to_str())
+            {
+                if is_valid_id(id) {
+                    keys.push(id.to_owned());
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Load a stored key.
+    pub async fn load(&self, id: &str) -> Result<StoredKey> {
+        let path = self.path(id)?;
+        if !fs::try_exists(&path).await? {
+            return Err(Error::KeyNotFound(id.to_owned()));
+        }
+        let contents = fs::read(&path).await?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    /// Save a stored key.
+    pub async fn save(
+        &self,
+        id: &str,
+        key: &StoredKey,
+    ) -> Result<()> {
+        let path = self.path(id)?;
+        fs::write(&path, serde_json::to_vec_pretty(key)?).await?;
+        Ok(())
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf> {
+        if !is_valid_id(id) {
+            return Err(Error::InvalidKeyId(id.to_owned()));
+        }
+        Ok(self.dir.join(format!("{}.{}", id, EXT)))
+    }
+}
+
+/// Key identifiers are used as file names so
+/// must not contain path separators.
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.chars().all(|c| {
+            c.is_ascii_alphanumeric() || c == '-' || c == '_'
+        })
+}
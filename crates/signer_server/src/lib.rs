@@ -0,0 +1,37 @@
+//! Remote co-signer service.
+//!
+//! Loads key shares from a keystore directory and participates
+//! in relay signing sessions for requests that are accepted by
+//! the policy, see [ServerConfig] for configuration details.
+//!
+//! Requests are sent as JSON to the `/sign` endpoint and every
+//! signer in the session must receive an identical request,
+//! see [SignRequest]. Requests must send the token configured
+//! with `auth_token_file` as a bearer token.
+
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+mod config;
+mod error;
+mod keystore;
+mod policy;
+mod server;
+mod signer;
+mod transaction;
+
+pub use config::{
+    CorsConfig, PolicyConfig, RateLimitConfig, RelayConfig,
+    ServerConfig, TlsConfig,
+};
+pub use error::{Error, PolicyViolation};
+pub use keystore::{KeyParty, KeyProtocol, KeyStore, StoredKey};
+pub use policy::Policy;
+pub use server::SignerServer;
+pub use signer::{SignRequest, SignResponse, Signer};
+pub use transaction::{Chain, Payload, Transaction, Transfer};
+
+pub use axum;
+
+/// Result type for the signer service.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,90 @@
+//! Policy engine for signing requests.
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::PolicyConfig, error::PolicyViolation,
+    transaction::Transaction,
+};
+
+/// Applies the configured policy to signing requests.
+pub struct Policy {
+    config: PolicyConfig,
+    accepted: VecDeque<Instant>,
+}
+
+impl Policy {
+    /// Create a policy engine.
+    pub fn new(config: PolicyConfig) -> Self {
+        Self {
+            config,
+            accepted: VecDeque::new(),
+        }
+    }
+
+    /// Check the transaction decoded from a request against
+    /// the policy.
+    ///
+    /// Accepted requests count towards the rate limit.
+    pub fn check(
+        &mut self,
+        transaction: Option<&Transaction>,
+    ) -> std::result::Result<(), PolicyViolation> {
+        if let Some(transaction) = transaction {
+            if !self.config.allowed_addresses.is_empty() {
+                for transfer in &transaction.transfers {
+                    let to = transfer
+                        .to
+                        .as_ref()
+                        .ok_or(PolicyViolation::MissingAddress)?;
+                    if !self.config.allowed_addresses.iter().any(
+                        |a| transaction.chain.is_same_address(a, to),
+                    ) {
+                        return Err(
+                            PolicyViolation::AddressNotAllowed(
+                                to.to_owned(),
+                            ),
+                        );
+                    }
+                }
+            }
+
+            if let Some(limit) = self.config.max_amount {
+                let amount = transaction.amount();
+                if amount > limit {
+                    return Err(PolicyViolation::AmountLimit(
+                        amount, limit,
+                    ));
+                }
+            }
+        } else if self.config.require_transaction
+            || !self.config.allowed_addresses.is_empty()
+            || self.config.max_amount.is_some()
+        {
+            return Err(PolicyViolation::MissingTransaction);
+        }
+
+        if let Some(rate_limit) = &self.config.rate_limit {
+            let now = Instant::now();
+            let window = Duration::from_secs(rate_limit.interval);
+            while let Some(accepted) = self.accepted.front() {
+                if now.duration_since(*accepted) >= window {
+                    self.accepted.pop_front();
+                } else {
+                    break;
+                }
+            }
+            if self.accepted.len() >= rate_limit.requests as usize {
+                return Err(PolicyViolation::RateLimit(
+                    rate_limit.requests,
+                    rate_limit.interval,
+                ));
+            }
+            self.accepted.push_back(now);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use axum::{
+    extract::{Extension, Request},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use axum_server::{tls_rustls::RustlsConfig, Handle};
+use polysig_client::{Error as ClientError, SigningPolicy};
+use polysig_driver::PartyKeys;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+
+use crate::{
+    config::{ServerConfig, TlsConfig},
+    keystore::KeyStore,
+    policy::Policy,
+    signer::{SignRequest, SignResponse, Signer},
+    Error, Result,
+};
+
+type State = Arc<Signer>;
+
+/// Digest of the auth token.
+#[derive(Clone)]
+struct AuthToken([u8; 32]);
+
+impl AuthToken {
+    fn new(token: &str) -> Self {
+        Self(Sha256::digest(token.as_bytes()).into())
+    }
+
+    /// Verify the bearer token for a request.
+    fn verify(&self, headers: &HeaderMap) -> Result<()> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(Error::Unauthorized)?;
+        // Compare digests so the comparison does not
+        // reveal the length of the token
+        if Self::new(token.trim()).0 != self.0 {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+/// Co-signer web server.
+pub struct SignerServer {
+    signer: State,
+    auth_token_file: PathBuf,
+    tls: Option<TlsConfig>,
+    origins: Vec<String>,
+}
+
+impl SignerServer {
+    /// Create a new signer server.
+    pub fn new(config: ServerConfig, keys: PartyKeys) -> Self {
+        Self::new_with_policy(config, keys, None)
+    }
+
+    /// Create a new signer server that consults a signing
+    /// policy before participating in a signature.
+    pub fn new_with_policy(
+        config: ServerConfig,
+        keys: PartyKeys,
+        signing_policy: Option<Arc<dyn SigningPolicy>>,
+    ) -> Self {
+        let mut signer = Signer::new(
+            keys,
+            config.relay,
+            KeyStore::new(&config.keystore),
+            Policy::new(config.policy),
+        );
+        if let Some(signing_policy) = signing_policy {
+            signer = signer.with_signing_policy(signing_policy);
+        }
+        Self {
+            signer: Arc::new(signer),
+            auth_token_file: config.auth_token_file,
+            tls: config.tls,
+            origins: config
+                .cors
+                .origins
+                .iter()
+                .map(|u| u.as_str().trim_end_matches('/').to_owned())
+                .collect(),
+        }
+    }
+
+    /// Start the server.
+    pub async fn start(
+        &self,
+        addr: SocketAddr,
+        handle: Handle,
+    ) -> Result<()> {
+        let auth_token = self.auth_token().await?;
+        if let Some(tls) = &self.tls {
+            self.run_tls(addr, handle, tls, auth_token).await
+        } else {
+            self.run(addr, handle, auth_token).await
+        }
+    }
+
+    /// Read the auth token.
+    async fn auth_token(&self) -> Result<AuthToken> {
+        if self.auth_token_file == PathBuf::default() {
+            return Err(Error::AuthToken);
+        }
+        let token = fs::read_to_string(&self.auth_token_file).await?;
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(Error::AuthToken);
+        }
+        Ok(AuthToken::new(token))
+    }
+
+    /// Start the server running on HTTPS.
+    async fn run_tls(
+        &self,
+        addr: SocketAddr,
+        handle: Handle,
+        tls: &TlsConfig,
+        auth_token: AuthToken,
+    ) -> Result<()> {
+        let tls =
+            RustlsConfig::from_pem_file(&tls.cert, &tls.key).await?;
+        let app = self.router(auth_token)?;
+        tracing::info!("listening on {}", addr);
+        axum_server::bind_rustls(addr, tls)
+            .handle(handle)
+            .serve(app.into_make_service())
+            .await?;
+        Ok(())
+    }
+
+    /// Start the server running on HTTP.
+    async fn run(
+        &self,
+        addr: SocketAddr,
+        handle: Handle,
+        auth_token: AuthToken,
+    ) -> Result<()> {
+        let app = self.router(auth_token)?;
+        tracing::info!("listening on {}", addr);
+        axum_server::bind(addr)
+            .handle(handle)
+            .serve(app.into_make_service())
+            .await?;
+        Ok(())
+    }
+
+    fn router(&self, auth_token: AuthToken) -> Result<Router> {
+        let mut origins = Vec::new();
+        for url in self.origins.iter() {
+            tracing::info!(url = %url, "cors");
+            origins.push(HeaderValue::from_str(url)?);
+        }
+
+        let cors = CorsLayer::new()
+            .allow_methods(vec![Method::GET, Method::POST])
+            .allow_headers(vec![AUTHORIZATION, CONTENT_TYPE])
+            .allow_origin(origins);
+
+        let app = Router::new()
+            .route("/keys", get(list_keys))
+            .route("/sign", post(sign))
+            .route_layer(middleware::from_fn(authorize))
+            .layer(cors)
+            .layer(TraceLayer::new_for_http())
+            .layer(Extension(Arc::clone(&self.signer)))
+            .layer(Extension(auth_token));
+        Ok(app)
+    }
+}
+
+/// Reject requests that do not send the auth token.
+async fn authorize(
+    Extension(auth_token): Extension<AuthToken>,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> std::result::Result<Response, Error> {
+    auth_token.verify(&headers)?;
+    Ok(next.run(request).await)
+}
+
+/// List the identifiers of the stored keys.
+async fn list_keys(
+    Extension(signer): Extension<State>,
+) -> std::result::Result<Json<Vec<String>>, Error> {
+    Ok(Json(signer.keystore().list().await?))
+}
+
+/// Participate in a signing session.
+async fn sign(
+    Extension(signer): Extension<State>,
+    Json(request): Json<SignRequest>,
+) -> std::result::Result<Json<SignResponse>, Error> {
+    Ok(Json(signer.sign(request).await?))
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Error::Unauthorized => StatusCode::UNAUTHORIZED,
+            Error::KeyNotFound(_) => StatusCode::NOT_FOUND,
+            Error::PolicyDenied(_)
+            | Error::Client(ClientError::PolicyRejected(_)) => {
+                StatusCode::FORBIDDEN
+            }
+            // Retry once approval is granted out of band
+            Error::Client(ClientError::PolicyDeferred(_)) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            Error::InvalidKeyId(_)
+            | Error::NotKeyParty(_)
+            | Error::PartyNumber(_)
+            | Error::SignerOrder
+            | Error::SignerThreshold(_, _)
+            | Error::NotSigner
+            | Error::SessionSeed
+            | Error::Prehash
+            | Error::PayloadProtocol(_)
+            | Error::InvalidPayload(_)
+            | Error::Hex(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        if status == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::error!(error = %self, "sign");
+        } else {
+            tracing::warn!(error = %self, "sign");
+        }
+        (status, self.to_string()).into_response()
+    }
+}
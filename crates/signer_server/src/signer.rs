@@ -0,0 +1,354 @@
+//! Participate in signing sessions for stored key shares.
+use polysig_client::{ServerOptions, SessionOptions, SigningPolicy};
+use polysig_driver::PartyKeys;
+use polysig_protocol::hex;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::RelayConfig,
+    keystore::{KeyParty, KeyProtocol, KeyStore, StoredKey},
+    policy::Policy,
+    transaction::Payload,
+    Error, Result,
+};
+
+/// Request to participate in a signing session.
+///
+/// Every signer in the session must receive an identical
+/// request; the signer with the lowest party number
+/// initiates the session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignRequest {
+    /// Identifier of the key in the keystore.
+    pub key_id: String,
+    /// Party numbers of the signers ordered by party number.
+    pub signers: Vec<u16>,
+    /// Payload to sign.
+    pub payload: Payload,
+    /// Hex encoded seed for the session identifier.
+    ///
+    /// Required for the CGGMP protocol.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_seed: Option<String>,
+}
+
+/// Signature created by a signing session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignResponse {
+    /// Protocol used to create the signature.
+    pub protocol: KeyProtocol,
+    /// Signature bytes.
+    #[serde(with = "hex::serde")]
+    pub signature: Vec<u8>,
+    /// Recovery identifier for ECDSA signatures.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_id: Option<u8>,
+}
+
+/// Co-signer that participates in signing sessions
+/// for the keys in a keystore.
+pub struct Signer {
+    keys: PartyKeys,
+    server: ServerOptions,
+    keystore: KeyStore,
+    policy: Mutex<Policy>,
+    signing_policy: Option<Arc<dyn SigningPolicy>>,
+}
+
+impl Signer {
+    /// Create a co-signer.
+    pub fn new(
+        keys: PartyKeys,
+        relay: RelayConfig,
+        keystore: KeyStore,
+        policy: Policy,
+    ) -> Self {
+        Self {
+            keys,
+            server: ServerOptions {
+                server_url: relay.url,
+                server_public_key: relay.public_key,
+                pattern: relay.pattern,
+            },
+            keystore,
+            policy: Mutex::new(policy),
+            signing_policy: None,
+        }
+    }
+
+    /// Assign a signing policy.
+    ///
+    /// The signing policy is consulted with the message for
+    /// every request that is accepted by the configured policy.
+    pub fn with_signing_policy(
+        mut self,
+        policy: Arc<dyn SigningPolicy>,
+    ) -> Self {
+        self.signing_policy = Some(policy);
+        self
+    }
+
+    /// Keystore for this signer.
+    pub fn keystore(&self) -> &KeyStore {
+        &self.keystore
+    }
+
+    /// Noise transport public key for this signer.
+    pub fn public_key(&self) -> &[u8] {
+        self.keys.encrypt.public_key()
+    }
+
+    /// Decode the payload of a request, apply the policy
+    /// and participate in the signing session.
+    pub async fn sign(
+        &self,
+        request: SignRequest,
+    ) -> Result<SignResponse> {
+        let key = self.keystore.load(&request.key_id).await?;
+        let party_number =
+            key.party_number(self.public_key()).ok_or_else(|| {
+                Error::NotKeyParty(request.key_id.clone())
+            })?;
+
+        if !request.signers.windows(2).all(|w| w[0] < w[1]) {
+            return Err(Error::SignerOrder);
+        }
+        if request.signers.len() < key.parameters.threshold as usize {
+            return Err(Error::SignerThreshold(
+                key.parameters.threshold,
+                request.signers.len(),
+            ));
+        }
+        if !request.signers.contains(&party_number) {
+            return Err(Error::NotSigner);
+        }
+        let parties = request
+            .signers
+            .iter()
+            .map(|n| key.party(*n).cloned())
+            .collect::<Result<Vec<_>>>()?;
+
+        let (message, transaction) =
+            request.payload.decode(key.protocol)?;
+        {
+            let mut policy = self.policy.lock().await;
+            policy.check(transaction.as_ref())?;
+        }
+
+        tracing::info!(
+            key_id = %request.key_id,
+            protocol = %key.protocol,
+            party_number = %party_number,
+            "sign",
+        );
+
+        let session = SigningSession {
+            options: SessionOptions {
+                keypair: self.keys.encrypt.clone(),
+                server: self.server.clone(),
+                parameters: key.parameters,
+                policy: self.signing_policy.clone(),
+                progress: None,
+                cancel: None,
+                key_share_policy: key.key_share.policy.clone(),
+                signing_context: None,
+                attestation: None,
+                round_timeout: None,
+                keepalive: None,
+            },
+            is_initiator: request.signers.first()
+                == Some(&party_number),
+            parties,
+        };
+
+        match key.protocol {
+            KeyProtocol::Cggmp => {
+                self.sign_cggmp(session, &key, request, message).await
+            }
+            KeyProtocol::FrostEd25519 => {
+                self.sign_frost_ed25519(
+                    session, &key, request, message,
+                )
+                .await
+            }
+            KeyProtocol::FrostSecp256k1 => {
+                self.sign_frost_secp256k1(
+                    session, &key, request, message,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn sign_cggmp(
+        &self,
+        session: SigningSession,
+        key: &StoredKey,
+        request: SignRequest,
+        message: Vec<u8>,
+    ) -> Result<SignResponse> {
+        use polysig_client::cggmp::sign_key_share;
+        use polysig_driver::{
+            cggmp::Participant,
+            k256::ecdsa::{SigningKey, VerifyingKey},
+            synedrion::{
+                PrehashedMessage, ProductionParams, SessionId,
+            },
+        };
+
+        let seed = request.session_seed.ok_or(Error::SessionSeed)?;
+        let session_id = SessionId::from_seed(&hex::decode(seed)?);
+        let message: PrehashedMessage = message
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::Prehash)?;
+
+        let signing_key =
+            SigningKey::from_slice(self.keys.sign.private_key())?;
+        let verifiers = session
+            .parties
+            .iter()
+            .map(|p| VerifyingKey::from_sec1_bytes(&p.verifier))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let verifier = *signing_key.verifying_key();
+        let party = session.party(self.public_key(), verifiers)?;
+        let participant =
+            Participant::new(signing_key, verifier, party)?;
+
+        let signature = sign_key_share::<ProductionParams>(
+            session.options,
+            participant,
+            session_id,
+            &key.key_share,
+            &message,
+        )
+        .await?;
+
+        Ok(SignResponse {
+            protocol: KeyProtocol::Cggmp,
+            signature: signature.bytes,
+            recovery_id: Some(signature.recovery_id),
+        })
+    }
+
+    async fn sign_frost_ed25519(
+        &self,
+        session: SigningSession,
+        key: &StoredKey,
+        request: SignRequest,
+        message: Vec<u8>,
+    ) -> Result<SignResponse> {
+        use polysig_driver::{
+            ed25519_dalek::{SigningKey, VerifyingKey},
+            frost::ed25519::{Identifier, Participant},
+        };
+
+        let signing_key =
+            SigningKey::try_from(self.keys.sign.private_key())?;
+        let verifiers = session
+            .parties
+            .iter()
+            .map(|p| VerifyingKey::try_from(p.verifier.as_slice()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let verifier = signing_key.verifying_key();
+        let party = session.party(self.public_key(), verifiers)?;
+        let participant =
+            Participant::new(signing_key, verifier, party)?;
+
+        let identifiers = request
+            .signers
+            .iter()
+            .map(|n| Identifier::try_from(*n))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let signature =
+            polysig_client::frost::ed25519::sign_key_share(
+                session.options,
+                participant,
+                identifiers,
+                &key.key_share,
+                message,
+            )
+            .await?;
+
+        Ok(SignResponse {
+            protocol: KeyProtocol::FrostEd25519,
+            signature: signature.serialize()?,
+            recovery_id: None,
+        })
+    }
+
+    async fn sign_frost_secp256k1(
+        &self,
+        session: SigningSession,
+        key: &StoredKey,
+        request: SignRequest,
+        message: Vec<u8>,
+    ) -> Result<SignResponse> {
+        use polysig_driver::{
+            frost::secp256k1::{Identifier, Participant},
+            k256::schnorr::{SigningKey, VerifyingKey},
+        };
+
+        let signing_key =
+            SigningKey::from_bytes(self.keys.sign.private_key())?;
+        let verifiers = session
+            .parties
+            .iter()
+            .map(|p| VerifyingKey::from_bytes(&p.verifier))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let verifier = signing_key.verifying_key().clone();
+        let party = session.party(self.public_key(), verifiers)?;
+        let participant =
+            Participant::new(signing_key, verifier, party)?;
+
+        let identifiers = request
+            .signers
+            .iter()
+            .map(|n| Identifier::try_from(*n))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let signature =
+            polysig_client::frost::secp256k1::sign_key_share(
+                session.options,
+                participant,
+                identifiers,
+                &key.key_share,
+                message,
+            )
+            .await?;
+
+        Ok(SignResponse {
+            protocol: KeyProtocol::FrostSecp256k1,
+            signature: signature.serialize()?,
+            recovery_id: None,
+        })
+    }
+}
+
+/// Session options and parties for a signing request.
+struct SigningSession {
+    options: SessionOptions,
+    is_initiator: bool,
+    parties: Vec<KeyParty>,
+}
+
+impl SigningSession {
+    fn party<V>(
+        &self,
+        public_key: &[u8],
+        verifiers: Vec<V>,
+    ) -> Result<polysig_driver::PartyOptions<V>> {
+        Ok(polysig_driver::PartyOptions::new(
+            public_key.to_vec(),
+            self.parties
+                .iter()
+                .map(|p| p.public_key.clone())
+                .collect(),
+            self.is_initiator,
+            verifiers,
+        )?)
+    }
+}
@@ -0,0 +1,156 @@
+//! Payloads for signing requests.
+//!
+//! Transactions are decoded by the signer so the policy is
+//! checked against the transaction that is signed and the
+//! message to sign is computed from the transaction rather
+//! than supplied by the caller.
+use polysig_driver::{
+    frost::ed25519::solana,
+    signers::ecdsa::eth_tx::UnsignedTransaction,
+};
+use polysig_protocol::hex;
+use serde::{Deserialize, Serialize};
+
+use crate::{keystore::KeyProtocol, Error, Result};
+
+/// Payload to sign.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Payload {
+    /// Message that is signed as is.
+    ///
+    /// For the CGGMP protocol this must be a 32 byte digest.
+    ///
+    /// Messages cannot be checked against the address and
+    /// amount limits so they are denied when the policy
+    /// restricts transactions.
+    Message {
+        /// Message bytes.
+        #[serde(with = "hex::serde")]
+        message: Vec<u8>,
+    },
+    /// Unsigned Ethereum transaction for the CGGMP protocol.
+    ///
+    /// The signing hash of the transaction is signed.
+    Ethereum {
+        /// Unsigned transaction payload.
+        #[serde(with = "hex::serde")]
+        transaction: Vec<u8>,
+    },
+    /// Serialized legacy Solana transaction message for
+    /// the FROST Ed25519 protocol.
+    ///
+    /// Every instruction must be a system program transfer.
+    Solana {
+        /// Transaction message bytes.
+        #[serde(with = "hex::serde")]
+        message: Vec<u8>,
+    },
+}
+
+impl Payload {
+    /// Decode the payload for a key protocol.
+    ///
+    /// Returns the message to sign and the decoded
+    /// transaction for transaction payloads.
+    pub fn decode(
+        &self,
+        protocol: KeyProtocol,
+    ) -> Result<(Vec<u8>, Option<Transaction>)> {
+        match (self, protocol) {
+            (Self::Message { message }, _) => {
+                Ok((message.clone(), None))
+            }
+            (Self::Ethereum { transaction }, KeyProtocol::Cggmp) => {
+                let transaction =
+                    UnsignedTransaction::decode(transaction)
+                        .map_err(|e| {
+                            Error::InvalidPayload(e.into())
+                        })?;
+                let transfer = Transfer {
+                    to: transaction
+                        .to()
+                        .map(|to| format!("0x{}", hex::encode(to))),
+                    amount: transaction.value(),
+                };
+                Ok((
+                    transaction.signing_hash().to_vec(),
+                    Some(Transaction {
+                        chain: Chain::Ethereum,
+                        transfers: vec![transfer],
+                    }),
+                ))
+            }
+            (Self::Solana { message }, KeyProtocol::FrostEd25519) => {
+                let transfers = solana::transfers(message)
+                    .map_err(|e| Error::InvalidPayload(e.into()))?
+                    .into_iter()
+                    .map(|transfer| Transfer {
+                        to: Some(
+                            bs58::encode(transfer.to).into_string(),
+                        ),
+                        amount: transfer.lamports as u128,
+                    })
+                    .collect();
+                Ok((
+                    message.clone(),
+                    Some(Transaction {
+                        chain: Chain::Solana,
+                        transfers,
+                    }),
+                ))
+            }
+            _ => Err(Error::PayloadProtocol(protocol)),
+        }
+    }
+}
+
+/// Chain for a decoded transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    /// Ethereum and compatible chains.
+    Ethereum,
+    /// Solana.
+    Solana,
+}
+
+impl Chain {
+    /// Determine if two addresses are the same account.
+    ///
+    /// Ethereum addresses are hex encoded and may use mixed
+    /// case checksums; Solana addresses are case sensitive.
+    pub fn is_same_address(&self, a: &str, b: &str) -> bool {
+        match self {
+            Self::Ethereum => a.eq_ignore_ascii_case(b),
+            Self::Solana => a == b,
+        }
+    }
+}
+
+/// Transaction decoded from a payload.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    /// Chain for the transaction.
+    pub chain: Chain,
+    /// Transfers made by the transaction.
+    pub transfers: Vec<Transfer>,
+}
+
+impl Transaction {
+    /// Total amount of the transfers.
+    pub fn amount(&self) -> u128 {
+        self.transfers
+            .iter()
+            .fold(0u128, |total, t| total.saturating_add(t.amount))
+    }
+}
+
+/// Transfer in a decoded transaction.
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    /// Recipient address; `None` for Ethereum contract
+    /// creation.
+    pub to: Option<String>,
+    /// Amount in the smallest unit of the asset.
+    pub amount: u128,
+}